@@ -2,20 +2,29 @@
 //!
 //! Contains the main `collect_schema` workflow, data-sampling helpers,
 //! quality-threshold parsing, and the `list` subcommand implementation.
+//! Server-wide and `--attach` collection live in `server`.
 
-use crate::Cli;
+mod server;
+
+use crate::estimate::{MetadataTimer, estimate_run, report_estimate};
+use crate::metrics::RunMetrics;
 use crate::outcome::CollectionOutcome;
 use crate::sampling::SamplingOrchestrator;
-#[cfg(feature = "postgresql")]
-use dbsurveyor_core::adapters::postgres::PostgresAdapter;
+use crate::{Cli, ObjectType};
 use dbsurveyor_core::{
-    CollectionMode, CollectionStatus, DatabaseAdapter, DatabaseInfo, DatabaseSchema,
-    DatabaseServerSchema, DatabaseType, Result, SamplingConfig, ServerInfo,
-    adapters::create_adapter,
+    AuxiliaryFile, AuxiliaryFileKind, CollectionWarning, DatabaseAdapter, DatabaseSchema,
+    DatabaseServerSchema, DatabaseType, IntegrityCheck, ReadOnlyVerification, Result,
+    SamplingConfig, WarningCategory,
+    adapters::{
+        CollectionConfig, ConnectionConfig, ReadPreferenceConfig, SourcePolicy,
+        create_adapter_with_config,
+    },
     error::redact_database_url,
+    lint::{LintRule, lint_schema},
     quality::{AnomalyConfig, QualityAnalyzer, QualityConfig},
 };
 use std::path::Path;
+use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
 /// Parsed quality threshold values from CLI arguments.
@@ -73,9 +82,63 @@ pub(crate) fn build_sampling_config(cli: &Cli) -> SamplingConfig {
         config = config.with_throttle_ms(throttle_ms);
     }
 
+    if let Some(rules) = &cli.sensitive_patterns {
+        config = config.with_sensitive_pattern_rules(rules.clone());
+    }
+
+    if let Some(rows) = cli.sample_max_table_rows {
+        config = config.with_max_table_rows(rows);
+    }
+
+    if let Some(bytes) = cli.sample_max_table_bytes {
+        config = config.with_max_table_bytes(bytes);
+    }
+
+    config
+}
+
+/// Builds the `CollectionConfig` applied to adapters from CLI arguments.
+pub(crate) fn build_collection_config(cli: &Cli) -> CollectionConfig {
+    let mut connection = ConnectionConfig::default();
+
+    if let Some(secs) = cli.table_timeout {
+        connection = connection.with_table_timeout(Duration::from_secs(secs));
+    }
+    connection = connection
+        .with_immutable(cli.immutable)
+        .with_pooler_mode(cli.pooler_mode)
+        .with_catalog_mode(cli.catalog_mode.into());
+    if let Some(read_preference) = read_preference(cli) {
+        connection = connection.with_read_preference(read_preference);
+    }
+    if let Some(config) = &cli.config {
+        connection = connection.with_session_limits(config.session_limits.clone());
+    }
+
+    let objects = &cli.objects;
+    let mut config = CollectionConfig::new()
+        .with_connection(connection)
+        .with_system_databases(cli.include_system_databases)
+        .with_views(objects.includes(ObjectType::Views))
+        .with_procedures(objects.includes(ObjectType::Procedures))
+        .with_functions(objects.includes(ObjectType::Functions))
+        .with_triggers(objects.includes(ObjectType::Triggers))
+        .with_indexes(objects.includes(ObjectType::Indexes))
+        .with_constraints(objects.includes(ObjectType::Constraints))
+        .with_custom_types(objects.includes(ObjectType::CustomTypes));
+    config.exclude_databases = cli.exclude_databases.clone();
     config
 }
 
+/// Builds the MongoDB read preference from `--read-preference` and its options.
+fn read_preference(cli: &Cli) -> Option<ReadPreferenceConfig> {
+    cli.read_preference.map(|mode| ReadPreferenceConfig {
+        mode: mode.into(),
+        tag_sets: cli.read_preference_tags.clone(),
+        max_staleness: cli.max_staleness.map(Duration::from_secs),
+    })
+}
+
 /// Returns whether sampling is enabled for this CLI invocation.
 pub(crate) fn sampling_enabled(cli: &Cli) -> bool {
     cli.sample > 0
@@ -86,6 +149,7 @@ pub(crate) async fn collect_schema(
     database_url: &str,
     output_path: &Path,
     cli: &Cli,
+    metrics: &mut RunMetrics,
 ) -> Result<CollectionOutcome> {
     // CWE-22: warn if output path contains parent-directory traversal
     if output_path
@@ -103,30 +167,79 @@ pub(crate) async fn collect_schema(
     info!("Output: {}", output_path.display());
 
     if cli.all_databases {
-        return collect_all_databases(database_url, output_path, cli).await;
+        return server::collect_all_databases(database_url, output_path, cli, metrics).await;
     }
 
-    let adapter = create_adapter(database_url).await.map_err(|e| {
-        error!("Failed to create database adapter: {}", e);
-        e
-    })?;
+    if !cli.attach.is_empty() {
+        return server::collect_attached_databases(database_url, output_path, cli, metrics).await;
+    }
+
+    let phase_start = Instant::now();
+    let auxiliary_files = inspect_auxiliary_files(database_url, cli)?;
+    let collection_config = build_collection_config(cli);
+    let adapter = create_adapter_with_config(database_url, &collection_config)
+        .await
+        .map_err(|e| {
+            error!("Failed to create database adapter: {}", e);
+            e
+        })?;
 
     info!("Created {} adapter", adapter.database_type());
+    if cli.immutable && adapter.database_type() != DatabaseType::SQLite {
+        warn!(
+            "--immutable applies to SQLite files only; the {} connection is read-only but not immutable",
+            adapter.database_type()
+        );
+    }
+    if cli.pooler_mode
+        && !matches!(
+            adapter.database_type(),
+            DatabaseType::PostgreSQL | DatabaseType::MySQL
+        )
+    {
+        warn!(
+            "--pooler-mode applies to PostgreSQL and MySQL only; ignored for {}",
+            adapter.database_type()
+        );
+    }
+    if cli.read_preference.is_some() && adapter.database_type() != DatabaseType::MongoDB {
+        warn!(
+            "--read-preference applies to MongoDB only; ignored for {}",
+            adapter.database_type()
+        );
+    }
+    let read_only = verify_read_only(&*adapter, cli).await?;
+    let integrity = check_integrity(&*adapter, cli).await;
+    metrics.record_phase("connect", phase_start);
 
     // Collect schema
+    let phase_start = Instant::now();
+    let timer = MetadataTimer::start();
     let mut schema = adapter.collect_schema().await.map_err(|e| {
         error!("Schema collection failed: {}", e);
         e
     })?;
+    let metadata_cost = timer.finish();
+    schema.collection_metadata.read_only = Some(read_only);
+    record_integrity(&mut schema, integrity);
+    record_auxiliary_files(&mut schema, auxiliary_files, cli);
+    metrics.record_phase("schema", phase_start);
 
     info!("[OK]Schema collection completed");
     info!("Found {} tables", schema.tables.len());
     info!("Found {} views", schema.views.len());
     info!("Found {} indexes", schema.indexes.len());
 
+    let estimate = estimate_run(&schema.tables, &*adapter, metadata_cost, cli, metrics);
+    if cli.estimate {
+        return Ok(report_estimate(metrics));
+    }
+
     // Run sampling only when explicitly enabled.
     if sampling_enabled(cli) && !schema.tables.is_empty() {
+        let phase_start = Instant::now();
         let sampling_config = build_sampling_config(cli);
+        info!("{}", estimate.summary());
         info!(
             "Sampling {} tables (limit {} rows each)...",
             schema.tables.len(),
@@ -134,8 +247,11 @@ pub(crate) async fn collect_schema(
         );
 
         let sampling_run = SamplingOrchestrator::new(&*adapter, &sampling_config)
+            .with_shuffled_order(cli.shuffle_order)
             .run(&schema.tables)
             .await;
+        metrics.record_sampling(&sampling_run);
+        metrics.record_phase("sampling", phase_start);
 
         if sampling_run.samples.is_empty() {
             info!("No samples collected (all tables may have been empty or inaccessible)");
@@ -151,8 +267,11 @@ pub(crate) async fn collect_schema(
         }
     }
 
+    metrics.pool = adapter.connection_pool_stats();
+
     // Run quality analysis if enabled and samples exist
     if cli.enable_quality {
+        let phase_start = Instant::now();
         if let Some(ref samples) = schema.samples {
             info!(
                 "Running data quality analysis on {} samples...",
@@ -211,212 +330,261 @@ pub(crate) async fn collect_schema(
         } else {
             info!("Quality analysis skipped - no samples available");
         }
+        metrics.record_phase("quality", phase_start);
     }
 
+    check_server_version(&mut schema);
+    record_settings(&mut schema, cli);
+    apply_source_policy(&mut schema, cli);
+    redact_metadata_secrets(&mut schema);
+    apply_lint_rules(&mut schema, cli);
+
     // Save to file
+    let phase_start = Instant::now();
     let saved_path = crate::output::save_schema(&schema, output_path, cli).await?;
+    metrics.record_phase("output", phase_start);
+    metrics.record_output(&saved_path).await;
 
     info!("[OK]Schema saved to {}", saved_path.display());
-    println!("Schema collection completed successfully");
-    println!("Output: {}", saved_path.display());
-    println!("Tables: {}", schema.tables.len());
-    println!("Views: {}", schema.views.len());
-    println!("Indexes: {}", schema.indexes.len());
+    status!("Schema collection completed successfully");
+    status!("Output: {}", saved_path.display());
+    status!("Tables: {}", schema.tables.len());
+    status!("Views: {}", schema.views.len());
+    status!("Indexes: {}", schema.indexes.len());
+    if let Some(probe) = &schema.database_info.access_probe {
+        status!("Access: {}", probe);
+    }
 
     if cli.enable_quality
         && let Some(ref metrics) = schema.quality_metrics
     {
-        println!("Quality metrics: {} tables analyzed", metrics.len());
+        status!("Quality metrics: {} tables analyzed", metrics.len());
     }
+    report_privilege_skips(adapter.database_type(), std::slice::from_ref(&schema));
 
     Ok(CollectionOutcome::from_results(&[schema]))
 }
 
-#[cfg(feature = "postgresql")]
-async fn collect_all_databases(
-    database_url: &str,
-    output_path: &Path,
-    cli: &Cli,
-) -> Result<CollectionOutcome> {
-    let adapter = PostgresAdapter::new(database_url).await.map_err(|e| {
-        error!(
-            "Failed to create PostgreSQL adapter for multi-database collection: {}",
-            e
-        );
-        e
-    })?;
-
-    if adapter.database_type() != DatabaseType::PostgreSQL {
-        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
-            "--all-databases is currently supported only for PostgreSQL",
-        ));
+/// Lists the `-wal`, `-shm`, and `-journal` files beside a SQLite database
+/// before it is opened, refusing a hot journal unless `--force`.
+#[cfg(feature = "sqlite")]
+fn inspect_auxiliary_files(database_url: &str, cli: &Cli) -> Result<Vec<AuxiliaryFile>> {
+    match dbsurveyor_core::adapters::sqlite::database_file_path(database_url) {
+        Some(path) => inspect_database_files(&path, cli),
+        None => Ok(Vec::new()),
     }
+}
 
-    let enumerated = adapter
-        .list_databases_with_options(cli.include_system_databases)
-        .await?;
-    let system_databases_excluded = if cli.include_system_databases {
-        0
-    } else {
-        enumerated.iter().filter(|db| db.is_system_database).count()
-    };
-
-    let mut databases = Vec::new();
+#[cfg(not(feature = "sqlite"))]
+fn inspect_auxiliary_files(_database_url: &str, _cli: &Cli) -> Result<Vec<AuxiliaryFile>> {
+    Ok(Vec::new())
+}
 
-    for database in &enumerated {
-        if cli
-            .exclude_databases
-            .iter()
-            .any(|excluded| excluded == &database.name)
-        {
-            continue;
-        }
+#[cfg(feature = "sqlite")]
+fn inspect_database_files(path: &Path, cli: &Cli) -> Result<Vec<AuxiliaryFile>> {
+    let files = dbsurveyor_core::adapters::sqlite::find_auxiliary_files(path);
+    for file in &files {
+        info!(
+            "Found {} ({} bytes){}",
+            file.file_name,
+            file.size_bytes,
+            if file.hot { ", a hot journal" } else { "" }
+        );
+    }
+    // Immutable opens never recover a journal, so a hot one is safe to leave
+    if !cli.force && !cli.immutable {
+        dbsurveyor_core::adapters::sqlite::ensure_no_hot_journal(&files)?;
+    }
+    Ok(files)
+}
 
-        if !database.is_accessible {
-            databases.push(skipped_database_schema(
-                &database.name,
-                Some(database.owner.clone()),
-                database.is_system_database,
-                "Database is not accessible with current privileges".to_string(),
-            ));
-            continue;
+/// Records the auxiliary files found before opening, with a warning for
+/// each hot journal opened under `--force` and each write-ahead log skipped
+/// under `--immutable`.
+fn record_auxiliary_files(schema: &mut DatabaseSchema, files: Vec<AuxiliaryFile>, cli: &Cli) {
+    for file in &files {
+        let message = if cli.immutable {
+            (file.kind == AuxiliaryFileKind::Wal && file.size_bytes > 0).then(|| {
+                format!(
+                    "{} ({} bytes) was not read: --immutable ignores the write-ahead log, so changes not yet checkpointed are missing",
+                    file.file_name, file.size_bytes
+                )
+            })
+        } else {
+            file.hot.then(|| {
+                format!(
+                    "Hot journal {} was present; the database was opened with --force",
+                    file.file_name
+                )
+            })
+        };
+        if let Some(message) = message {
+            schema
+                .collection_metadata
+                .warnings
+                .push(CollectionWarning::new(WarningCategory::Integrity, message));
         }
+    }
+    schema.collection_metadata.auxiliary_files = files;
+}
 
-        match adapter.connect_to_database(&database.name).await {
-            Ok(database_adapter) => match database_adapter.collect_schema().await {
-                Ok(mut schema) => {
-                    if sampling_enabled(cli) && !schema.tables.is_empty() {
-                        let sampling_config = build_sampling_config(cli);
-                        let sampling_run =
-                            SamplingOrchestrator::new(&database_adapter, &sampling_config)
-                                .run(&schema.tables)
-                                .await;
-                        for warning in sampling_run.warnings {
-                            schema = schema.with_warning(warning);
-                        }
-                        schema = schema.with_samples(sampling_run.samples);
-                    }
-                    databases.push(schema);
-                }
-                Err(err) => {
-                    databases.push(failed_database_schema(
-                        &database.name,
-                        Some(database.owner.clone()),
-                        database.is_system_database,
-                        err.to_string(),
-                    ));
-                }
-            },
-            Err(err) => {
-                databases.push(failed_database_schema(
-                    &database.name,
-                    Some(database.owner.clone()),
-                    database.is_system_database,
-                    err.to_string(),
-                ));
-            }
-        }
+/// Runs the `--integrity-check`, if requested, on the adapter's database
+/// file before anything is collected.
+async fn check_integrity(adapter: &dyn DatabaseAdapter, cli: &Cli) -> Option<IntegrityCheck> {
+    let mode = cli.integrity_check?;
+    let check = adapter
+        .check_integrity(
+            mode.into(),
+            Some(Duration::from_secs(cli.integrity_timeout)),
+        )
+        .await;
+    match &check {
+        Some(check) => info!(
+            "Integrity check: {} ({} ms)",
+            check.status, check.duration_ms
+        ),
+        None => warn!(
+            "--integrity-check applies to SQLite files only; the {} database was not checked",
+            adapter.database_type()
+        ),
     }
+    check
+}
 
-    let collected = databases
-        .iter()
-        .filter(|schema| {
-            matches!(
-                schema.database_info.collection_status,
-                CollectionStatus::Success
-            )
-        })
-        .count();
-    let failed = databases
-        .iter()
-        .filter(|schema| {
-            matches!(
-                schema.database_info.collection_status,
-                CollectionStatus::Failed { .. }
-            )
-        })
-        .count();
-
-    let server_schema = DatabaseServerSchema {
-        format_version: dbsurveyor_core::FORMAT_VERSION.to_string(),
-        server_info: ServerInfo {
-            server_type: DatabaseType::PostgreSQL,
-            version: "unknown".to_string(),
-            host: adapter.config.host.clone(),
-            port: adapter.config.port,
-            total_databases: databases.len(),
-            collected_databases: collected,
-            system_databases_excluded,
-            connection_user: adapter
-                .config
-                .username
-                .clone()
-                .unwrap_or_else(|| "unknown".to_string()),
-            has_superuser_privileges: false,
-            collection_mode: CollectionMode::MultiDatabase {
-                discovered: databases.len(),
-                collected,
-                failed,
-            },
-        },
-        databases: databases.clone(),
-        collection_metadata: dbsurveyor_core::models::CollectionMetadata {
-            collected_at: chrono::Utc::now(),
-            collection_duration_ms: 0,
-            collector_version: env!("CARGO_PKG_VERSION").to_string(),
-            warnings: Vec::new(),
-        },
+/// Records an integrity check on the schema, with a warning unless the
+/// database was intact.
+fn record_integrity(schema: &mut DatabaseSchema, check: Option<IntegrityCheck>) {
+    let Some(check) = check else {
+        return;
     };
+    if let Some(warning) = check.to_warning() {
+        schema.collection_metadata.warnings.push(warning);
+    }
+    schema.collection_metadata.integrity = Some(check);
+}
 
-    let saved_path = crate::output::save_server_schema(&server_schema, output_path, cli).await?;
-    info!("[OK]Server schema saved to {}", saved_path.display());
+/// Checks that the adapter's sessions cannot write, before anything is
+/// collected.
+///
+/// # Errors
+/// Under `--require-read-only`, fails unless writes are known to be
+/// rejected at the session or server level.
+async fn verify_read_only(
+    adapter: &dyn DatabaseAdapter,
+    cli: &Cli,
+) -> Result<ReadOnlyVerification> {
+    let verification = adapter.verify_read_only().await.unwrap_or_else(|e| {
+        ReadOnlyVerification::unverified(format!("read-only check failed: {}", e))
+    });
+    let summary = format!(
+        "{} ({})",
+        verification.level,
+        verification.evidence.join(", ")
+    );
+    if verification.level.is_guaranteed() {
+        info!("[OK]Read-only enforcement: {}", summary);
+    } else if cli.require_read_only {
+        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+            format!("--require-read-only: read-only enforcement is {}", summary),
+        ));
+    } else {
+        warn!("Read-only enforcement: {}", summary);
+    }
+    Ok(verification)
+}
 
-    Ok(CollectionOutcome::from_results(&databases))
+/// Records the pre-collection read-only check on every database.
+#[cfg(any(
+    feature = "postgresql",
+    feature = "mysql",
+    feature = "mongodb",
+    feature = "sqlite"
+))]
+fn record_read_only(server_schema: &mut DatabaseServerSchema, verification: &ReadOnlyVerification) {
+    for schema in &mut server_schema.databases {
+        schema.collection_metadata.read_only = Some(verification.clone());
+    }
 }
 
-#[cfg(not(feature = "postgresql"))]
-async fn collect_all_databases(
-    _database_url: &str,
-    _output_path: &Path,
-    _cli: &Cli,
-) -> Result<CollectionOutcome> {
-    Err(dbsurveyor_core::error::DbSurveyorError::configuration(
-        "--all-databases requires the postgresql feature",
-    ))
+/// Warns when the server version is outside the adapter's tested range.
+fn check_server_version(schema: &mut DatabaseSchema) {
+    if let Some(warning) = dbsurveyor_core::versions::check_server_version(&schema.database_info) {
+        warn!("{}", warning.message);
+        schema.collection_metadata.warnings.push(warning);
+    }
+}
+
+/// Records the collection and sampling settings the run used, so the survey
+/// shows what was included, sampled, and throttled.
+fn record_settings(schema: &mut DatabaseSchema, cli: &Cli) {
+    let sampling = sampling_enabled(cli).then(|| build_sampling_config(cli));
+    schema.collection_metadata.settings =
+        Some(build_collection_config(cli).settings(sampling.as_ref()));
 }
 
-fn failed_database_schema(
-    name: &str,
-    owner: Option<String>,
-    is_system_database: bool,
-    error_message: String,
-) -> DatabaseSchema {
-    let mut info = DatabaseInfo::new(name.to_string());
-    info.owner = owner;
-    info.is_system_database = is_system_database;
-    info.collection_status = CollectionStatus::Failed {
-        error: error_message.clone(),
+/// Drops routine bodies unless `--include-source` is set, and oversized ones
+/// even then.
+fn apply_source_policy(schema: &mut DatabaseSchema, cli: &Cli) {
+    let policy = SourcePolicy {
+        include: cli.include_source,
+        max_bytes: cli.max_source_bytes,
     };
-    info.access_level = dbsurveyor_core::AccessLevel::None;
+    let warnings = policy.apply(schema);
+    if !warnings.is_empty() {
+        warn!(
+            "{} routine bodies in {} exceed {} bytes and were not included",
+            warnings.len(),
+            schema.database_info.name,
+            policy.max_bytes
+        );
+    }
+    schema.collection_metadata.warnings.extend(warnings);
+}
 
-    DatabaseSchema::new(info).with_warning(error_message)
+/// Redacts credentials embedded in metadata (view definitions, routine
+/// bodies, defaults, comments), recording each location as a warning.
+fn redact_metadata_secrets(schema: &mut DatabaseSchema) {
+    let findings = dbsurveyor_core::secrets::redact_metadata_secrets(schema);
+    for finding in &findings {
+        warn!(
+            "Redacted {} in {}",
+            finding.kind.label(),
+            finding.location()
+        );
+    }
+    schema
+        .collection_metadata
+        .warnings
+        .extend(findings.iter().map(|finding| finding.to_warning()));
 }
 
-fn skipped_database_schema(
-    name: &str,
-    owner: Option<String>,
-    is_system_database: bool,
-    reason: String,
-) -> DatabaseSchema {
-    let mut info = DatabaseInfo::new(name.to_string());
-    info.owner = owner;
-    info.is_system_database = is_system_database;
-    info.collection_status = CollectionStatus::Skipped {
-        reason: reason.clone(),
+/// Records strict-mode lint findings as warnings on the schema.
+fn apply_lint_rules(schema: &mut DatabaseSchema, cli: &Cli) {
+    if !cli.strict {
+        return;
+    }
+    let rules = if cli.lint_rules.is_empty() {
+        &LintRule::ALL[..]
+    } else {
+        &cli.lint_rules[..]
     };
-    info.access_level = dbsurveyor_core::AccessLevel::Limited;
 
-    DatabaseSchema::new(info).with_warning(reason)
+    let findings = lint_schema(schema, rules);
+    if !findings.is_empty() {
+        warn!(
+            "Strict mode: {} lint findings in {}",
+            findings.len(),
+            schema.database_info.name
+        );
+    }
+    schema.collection_metadata.warnings.extend(findings);
+}
+
+/// Prints the features skipped because the collecting role lacked privileges.
+fn report_privilege_skips(database_type: DatabaseType, schemas: &[DatabaseSchema]) {
+    if let Some(report) = crate::permissions::skip_report(database_type, schemas) {
+        status!("{}", report.trim_end());
+    }
 }
 
 /// Lists supported database types and their connection string formats.
@@ -483,121 +651,4 @@ pub(crate) fn list_supported_databases() {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_quality_thresholds_valid() {
-        let input = vec![
-            "completeness:0.9".to_string(),
-            "uniqueness:0.95".to_string(),
-            "consistency:0.85".to_string(),
-        ];
-        let result = parse_quality_thresholds(&input);
-        assert!((result.completeness.unwrap_or(0.0) - 0.9).abs() < f64::EPSILON);
-        assert!((result.uniqueness.unwrap_or(0.0) - 0.95).abs() < f64::EPSILON);
-        assert!((result.consistency.unwrap_or(0.0) - 0.85).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_empty() {
-        let result = parse_quality_thresholds(&[]);
-        assert!(result.completeness.is_none());
-        assert!(result.uniqueness.is_none());
-        assert!(result.consistency.is_none());
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_invalid_format() {
-        let input = vec!["not-a-threshold".to_string()];
-        let result = parse_quality_thresholds(&input);
-        assert!(result.completeness.is_none());
-        assert!(result.uniqueness.is_none());
-        assert!(result.consistency.is_none());
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_invalid_value() {
-        let input = vec!["completeness:abc".to_string()];
-        let result = parse_quality_thresholds(&input);
-        assert!(result.completeness.is_none());
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_clamps_out_of_range() {
-        let input = vec![
-            "completeness:1.5".to_string(),
-            "uniqueness:-0.5".to_string(),
-        ];
-        let result = parse_quality_thresholds(&input);
-        assert!((result.completeness.unwrap_or(0.0) - 1.0).abs() < f64::EPSILON);
-        assert!(result.uniqueness.unwrap_or(1.0).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_unknown_metric() {
-        let input = vec!["unknown_metric:0.5".to_string()];
-        let result = parse_quality_thresholds(&input);
-        assert!(result.completeness.is_none());
-        assert!(result.uniqueness.is_none());
-        assert!(result.consistency.is_none());
-    }
-
-    #[test]
-    fn test_parse_quality_thresholds_case_insensitive() {
-        let input = vec!["Completeness:0.8".to_string()];
-        let result = parse_quality_thresholds(&input);
-        assert!((result.completeness.unwrap_or(0.0) - 0.8).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn test_sampling_disabled_for_zero_sample() {
-        let cli = Cli {
-            global: crate::GlobalArgs {
-                verbose: 0,
-                quiet: false,
-            },
-            command: None,
-            database_url: None,
-            output: "schema.dbsurveyor.json".into(),
-            sample: 0,
-            throttle: None,
-            compress: false,
-            encrypt: false,
-            all_databases: false,
-            include_system_databases: false,
-            exclude_databases: Vec::new(),
-            enable_quality: false,
-            quality_threshold: Vec::new(),
-            disable_anomaly_detection: false,
-        };
-
-        assert!(!sampling_enabled(&cli));
-    }
-
-    #[test]
-    fn test_build_sampling_config_preserves_explicit_nonzero_sample() {
-        let cli = Cli {
-            global: crate::GlobalArgs {
-                verbose: 0,
-                quiet: false,
-            },
-            command: None,
-            database_url: None,
-            output: "schema.dbsurveyor.json".into(),
-            sample: 25,
-            throttle: None,
-            compress: false,
-            encrypt: false,
-            all_databases: false,
-            include_system_databases: false,
-            exclude_databases: Vec::new(),
-            enable_quality: false,
-            quality_threshold: Vec::new(),
-            disable_anomaly_detection: false,
-        };
-
-        let config = build_sampling_config(&cli);
-        assert_eq!(config.sample_size, 25);
-    }
-}
+mod tests;
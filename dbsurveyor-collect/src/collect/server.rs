@@ -0,0 +1,672 @@
+//! Server-wide collection: every database on a PostgreSQL, MySQL, or
+//! MongoDB server, or a SQLite database with its `--attach`ed files,
+//! gathered into one server schema.
+
+#[cfg(feature = "mongodb")]
+use super::read_preference;
+#[cfg(any(
+    feature = "postgresql",
+    feature = "mysql",
+    feature = "mongodb",
+    feature = "sqlite"
+))]
+use super::record_read_only;
+use super::{
+    apply_lint_rules, apply_source_policy, build_collection_config, build_sampling_config,
+    check_server_version, record_settings, redact_metadata_secrets, report_privilege_skips,
+    sampling_enabled, verify_read_only,
+};
+#[cfg(feature = "sqlite")]
+use super::{
+    check_integrity, inspect_auxiliary_files, inspect_database_files, record_auxiliary_files,
+    record_integrity,
+};
+use crate::Cli;
+use crate::estimate::{MetadataTimer, estimate_run, report_estimate};
+use crate::metrics::RunMetrics;
+use crate::outcome::CollectionOutcome;
+use crate::sampling::SamplingOrchestrator;
+#[cfg(feature = "mongodb")]
+use dbsurveyor_core::adapters::mongodb::MongoAdapter;
+#[cfg(feature = "mysql")]
+use dbsurveyor_core::adapters::mysql::MySqlAdapter;
+#[cfg(feature = "postgresql")]
+use dbsurveyor_core::adapters::postgres::PostgresAdapter;
+#[cfg(feature = "sqlite")]
+use dbsurveyor_core::adapters::sqlite::{AttachedDatabase, SqliteAdapter};
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+use dbsurveyor_core::{
+    CollectionMode, CollectionStatus, DatabaseAdapter, DatabaseInfo, DatabaseSchema,
+    DatabaseServerSchema, ServerInfo,
+    adapters::{CollectionConfig, ConnectionConfig},
+};
+use dbsurveyor_core::{DatabaseType, Result};
+use rand::seq::SliceRandom;
+use std::path::Path;
+#[cfg(feature = "sqlite")]
+use std::time::Duration;
+use std::time::Instant;
+use tracing::{error, info};
+
+/// Collects every database on the server into one server schema.
+///
+/// Supported for PostgreSQL, MySQL, and MongoDB connection URLs.
+pub(super) async fn collect_all_databases(
+    database_url: &str,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    #[cfg(feature = "postgresql")]
+    if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+        return collect_all_postgres_databases(database_url, output_path, cli, metrics).await;
+    }
+    #[cfg(feature = "mysql")]
+    if database_url.starts_with("mysql://") {
+        return collect_all_mysql_databases(database_url, output_path, cli, metrics).await;
+    }
+    #[cfg(feature = "mongodb")]
+    if database_url.starts_with("mongodb://") || database_url.starts_with("mongodb+srv://") {
+        return collect_all_mongodb_databases(database_url, output_path, cli, metrics).await;
+    }
+    let _ = (database_url, output_path, cli, metrics);
+    Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+        "--all-databases is supported only for PostgreSQL, MySQL, and MongoDB, and requires the matching feature",
+    ))
+}
+
+#[cfg(feature = "postgresql")]
+async fn collect_all_postgres_databases(
+    database_url: &str,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    let phase_start = Instant::now();
+    let opened = if cli.pooler_mode {
+        PostgresAdapter::behind_pooler(database_url).await
+    } else {
+        PostgresAdapter::new(database_url).await
+    };
+    let mut adapter = opened.map_err(|e| {
+        error!(
+            "Failed to create PostgreSQL adapter for multi-database collection: {}",
+            e
+        );
+        e
+    })?;
+    // Propagated to every per-database adapter by `connect_to_database`
+    let collection_config = build_collection_config(cli);
+    adapter.config.table_timeout = collection_config.connection.table_timeout;
+    adapter.objects = collection_config.object_selection();
+
+    let targets = adapter
+        .list_databases_with_options(collection_config.include_system_databases)
+        .await?
+        .into_iter()
+        .map(|database| DatabaseTarget {
+            name: database.name,
+            owner: Some(database.owner),
+            is_system_database: database.is_system_database,
+            is_accessible: database.is_accessible,
+        })
+        .collect();
+    let read_only = verify_read_only(&adapter, cli).await?;
+    metrics.record_phase("connect", phase_start);
+
+    let adapter = &adapter;
+    let (databases, system_databases_excluded) = collect_database_targets(
+        targets,
+        &collection_config,
+        cli,
+        metrics,
+        |name| async move { adapter.connect_to_database(&name).await },
+    )
+    .await;
+
+    let mut server_schema = build_server_schema(
+        DatabaseType::PostgreSQL,
+        &adapter.config,
+        system_databases_excluded,
+        databases,
+    );
+    record_read_only(&mut server_schema, &read_only);
+    server_schema.server_info.timezone = adapter.server_timezone().await;
+    server_schema.server_info.role = adapter.server_role().await;
+    metrics.pool = adapter.connection_pool_stats();
+    save_collected_server_schema(server_schema, output_path, cli, metrics).await
+}
+
+#[cfg(feature = "mysql")]
+async fn collect_all_mysql_databases(
+    database_url: &str,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    let phase_start = Instant::now();
+    let opened = if cli.pooler_mode {
+        MySqlAdapter::behind_pooler(database_url).await
+    } else {
+        MySqlAdapter::new(database_url).await
+    };
+    let mut adapter = opened.map_err(|e| {
+        error!(
+            "Failed to create MySQL adapter for multi-database collection: {}",
+            e
+        );
+        e
+    })?;
+    // Propagated to every per-database adapter by `connect_to_database`
+    let collection_config = build_collection_config(cli);
+    adapter.config.table_timeout = collection_config.connection.table_timeout;
+    adapter.objects = collection_config.object_selection();
+
+    // MySQL only lists databases the current user holds privileges on
+    let targets = adapter
+        .list_databases_with_options(collection_config.include_system_databases)
+        .await?
+        .into_iter()
+        .map(|database| DatabaseTarget {
+            name: database.name,
+            owner: None,
+            is_system_database: database.is_system_database,
+            is_accessible: true,
+        })
+        .collect();
+    let read_only = verify_read_only(&adapter, cli).await?;
+    metrics.record_phase("connect", phase_start);
+
+    let adapter = &adapter;
+    let (databases, system_databases_excluded) = collect_database_targets(
+        targets,
+        &collection_config,
+        cli,
+        metrics,
+        |name| async move { adapter.connect_to_database(&name).await },
+    )
+    .await;
+
+    let mut server_schema = build_server_schema(
+        DatabaseType::MySQL,
+        &adapter.config,
+        system_databases_excluded,
+        databases,
+    );
+    record_read_only(&mut server_schema, &read_only);
+    server_schema.server_info.timezone = adapter.server_timezone().await;
+    metrics.pool = adapter.connection_pool_stats();
+    save_collected_server_schema(server_schema, output_path, cli, metrics).await
+}
+
+#[cfg(feature = "mongodb")]
+async fn collect_all_mongodb_databases(
+    database_url: &str,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    let phase_start = Instant::now();
+    let opened = match read_preference(cli) {
+        Some(read_preference) => {
+            MongoAdapter::with_read_preference(database_url, read_preference).await
+        }
+        None => MongoAdapter::new(database_url).await,
+    };
+    let mut adapter = opened.map_err(|e| {
+        error!(
+            "Failed to create MongoDB adapter for multi-database collection: {}",
+            e
+        );
+        e
+    })?;
+    // Propagated to every per-database adapter by `for_database`
+    let collection_config = build_collection_config(cli);
+    adapter.config.table_timeout = collection_config.connection.table_timeout;
+    adapter.objects = collection_config.object_selection();
+
+    let targets = adapter
+        .list_databases(collection_config.include_system_databases)
+        .await?
+        .into_iter()
+        .map(|database| DatabaseTarget {
+            name: database.name,
+            owner: None,
+            is_system_database: database.is_system_database,
+            is_accessible: database.is_accessible,
+        })
+        .collect();
+    let read_only = verify_read_only(&adapter, cli).await?;
+    metrics.record_phase("connect", phase_start);
+
+    let adapter = &adapter;
+    let (databases, system_databases_excluded) = collect_database_targets(
+        targets,
+        &collection_config,
+        cli,
+        metrics,
+        |name| async move { adapter.for_database(&name) },
+    )
+    .await;
+
+    let mut server_schema = build_server_schema(
+        DatabaseType::MongoDB,
+        &adapter.config,
+        system_databases_excluded,
+        databases,
+    );
+    record_read_only(&mut server_schema, &read_only);
+    server_schema.server_info.timezone = Some("UTC".to_string());
+    save_collected_server_schema(server_schema, output_path, cli, metrics).await
+}
+
+/// A database enumerated for server-wide collection.
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+struct DatabaseTarget {
+    name: String,
+    owner: Option<String>,
+    is_system_database: bool,
+    is_accessible: bool,
+}
+
+/// Collects (and optionally samples) each target not excluded by the
+/// configuration, recording per-database failures instead of aborting.
+///
+/// Returns the per-database schemas and the number of system databases
+/// that were excluded.
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+async fn collect_database_targets<A, F, Fut>(
+    targets: Vec<DatabaseTarget>,
+    collection_config: &CollectionConfig,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+    connect: F,
+) -> (Vec<DatabaseSchema>, usize)
+where
+    A: DatabaseAdapter,
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<A>>,
+{
+    let system_databases_excluded = if collection_config.include_system_databases {
+        0
+    } else {
+        targets.iter().filter(|db| db.is_system_database).count()
+    };
+
+    // Positions restore the listing order after a shuffled crawl
+    let mut targets: Vec<_> = targets.into_iter().enumerate().collect();
+    if cli.shuffle_order {
+        targets.shuffle(&mut rand::rng());
+    }
+
+    let mut databases = Vec::new();
+
+    for (position, database) in targets {
+        if collection_config.excludes_database(&database.name, database.is_system_database) {
+            info!("Excluding database '{}'", database.name);
+            continue;
+        }
+
+        if !database.is_accessible {
+            databases.push((
+                position,
+                skipped_database_schema(
+                    &database.name,
+                    database.owner,
+                    database.is_system_database,
+                    "Database is not accessible with current privileges".to_string(),
+                ),
+            ));
+            continue;
+        }
+
+        let phase_start = Instant::now();
+        let timer = MetadataTimer::start();
+        let collected = match connect(database.name.clone()).await {
+            Ok(database_adapter) => database_adapter
+                .collect_schema()
+                .await
+                .map(|schema| (database_adapter, schema)),
+            Err(err) => Err(err),
+        };
+        match collected {
+            Ok((database_adapter, mut schema)) => {
+                metrics.record_phase("schema", phase_start);
+                let estimate = estimate_run(
+                    &schema.tables,
+                    &database_adapter,
+                    timer.finish(),
+                    cli,
+                    metrics,
+                );
+                if sampling_enabled(cli) && !cli.estimate && !schema.tables.is_empty() {
+                    let phase_start = Instant::now();
+                    let sampling_config = build_sampling_config(cli);
+                    info!("Database '{}': {}", database.name, estimate.summary());
+                    let sampling_run =
+                        SamplingOrchestrator::new(&database_adapter, &sampling_config)
+                            .with_shuffled_order(cli.shuffle_order)
+                            .run(&schema.tables)
+                            .await;
+                    metrics.record_sampling(&sampling_run);
+                    metrics.record_phase("sampling", phase_start);
+                    for warning in sampling_run.warnings {
+                        schema = schema.with_warning(warning);
+                    }
+                    schema = schema.with_samples(sampling_run.samples);
+                }
+                databases.push((position, schema));
+            }
+            Err(err) => {
+                databases.push((
+                    position,
+                    failed_database_schema(
+                        &database.name,
+                        database.owner,
+                        database.is_system_database,
+                        err.to_string(),
+                    ),
+                ));
+            }
+        }
+    }
+
+    databases.sort_by_key(|(position, _)| *position);
+    let databases = databases.into_iter().map(|(_, schema)| schema).collect();
+    (databases, system_databases_excluded)
+}
+
+/// Wraps per-database schemas in a server schema with aggregate counts.
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+fn build_server_schema(
+    server_type: DatabaseType,
+    connection: &ConnectionConfig,
+    system_databases_excluded: usize,
+    databases: Vec<DatabaseSchema>,
+) -> DatabaseServerSchema {
+    let collected = databases
+        .iter()
+        .filter(|schema| {
+            matches!(
+                schema.database_info.collection_status,
+                CollectionStatus::Success | CollectionStatus::Partial { .. }
+            )
+        })
+        .count();
+    let failed = databases
+        .iter()
+        .filter(|schema| {
+            matches!(
+                schema.database_info.collection_status,
+                CollectionStatus::Failed { .. }
+            )
+        })
+        .count();
+
+    DatabaseServerSchema::new(
+        ServerInfo {
+            server_type,
+            version: "unknown".to_string(),
+            host: connection.host.clone(),
+            port: connection.port,
+            total_databases: databases.len(),
+            collected_databases: collected,
+            system_databases_excluded,
+            connection_user: connection
+                .username
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            has_superuser_privileges: false,
+            collection_mode: CollectionMode::MultiDatabase {
+                discovered: databases.len(),
+                collected,
+                failed,
+            },
+            timezone: None,
+            role: None,
+        },
+        databases,
+        dbsurveyor_core::models::CollectionMetadata {
+            collected_at: chrono::Utc::now(),
+            collection_duration_ms: 0,
+            collector_version: env!("CARGO_PKG_VERSION").to_string(),
+            warnings: Vec::new(),
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
+        },
+    )
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+async fn save_collected_server_schema(
+    mut server_schema: DatabaseServerSchema,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    if cli.estimate {
+        return Ok(report_estimate(metrics));
+    }
+    for schema in &mut server_schema.databases {
+        check_server_version(schema);
+        record_settings(schema, cli);
+        apply_source_policy(schema, cli);
+        redact_metadata_secrets(schema);
+        apply_lint_rules(schema, cli);
+    }
+    let outcome = CollectionOutcome::from_results(&server_schema.databases);
+
+    if cli.dedup_tenants {
+        let folded = dbsurveyor_core::dedup::deduplicate_tenants(&mut server_schema);
+        info!(
+            "Deduplicated {} databases into {} tenant groups",
+            folded,
+            server_schema.tenant_groups.len()
+        );
+    }
+
+    let phase_start = Instant::now();
+    let saved_path = crate::output::save_server_schema(&server_schema, output_path, cli).await?;
+    metrics.record_phase("output", phase_start);
+    metrics.record_output(&saved_path).await;
+    info!("[OK]Server schema saved to {}", saved_path.display());
+    report_privilege_skips(
+        server_schema.server_info.server_type,
+        &server_schema.databases,
+    );
+
+    Ok(outcome)
+}
+
+/// Collects a SQLite database together with its `--attach`ed files as a
+/// server schema, one logical database per file.
+#[cfg(feature = "sqlite")]
+pub(super) async fn collect_attached_databases(
+    database_url: &str,
+    output_path: &Path,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    let phase_start = Instant::now();
+    let attachments = cli
+        .attach
+        .iter()
+        .map(|spec| spec.parse::<AttachedDatabase>())
+        .collect::<Result<Vec<_>>>()?;
+    let mut auxiliary_files = vec![inspect_auxiliary_files(database_url, cli)?];
+    for attachment in &attachments {
+        auxiliary_files.push(inspect_database_files(&attachment.path, cli)?);
+    }
+
+    let opened = if cli.immutable {
+        SqliteAdapter::immutable(database_url, attachments).await
+    } else {
+        SqliteAdapter::with_attachments(database_url, attachments).await
+    };
+    let mut adapter = opened.map_err(|e| {
+        error!(
+            "Failed to create SQLite adapter with attached databases: {}",
+            e
+        );
+        e
+    })?;
+    let collection_config = build_collection_config(cli);
+    adapter.config.table_timeout = collection_config.connection.table_timeout;
+    adapter.objects = collection_config.object_selection();
+
+    info!(
+        "Created SQLite adapter with {} attached databases",
+        adapter.attached_databases().len()
+    );
+    let read_only = verify_read_only(&adapter, cli).await?;
+    let mut integrity = vec![check_integrity(&adapter, cli).await];
+    if let Some(mode) = cli.integrity_check {
+        for attachment in adapter.attached_databases() {
+            integrity.push(Some(
+                adapter
+                    .check_database_integrity(
+                        Some(&attachment.name),
+                        mode.into(),
+                        Some(Duration::from_secs(cli.integrity_timeout)),
+                    )
+                    .await,
+            ));
+        }
+    }
+    metrics.record_phase("connect", phase_start);
+
+    let phase_start = Instant::now();
+    let timer = MetadataTimer::start();
+    let mut server_schema = adapter.collect_server_schema().await.map_err(|e| {
+        error!("Schema collection failed: {}", e);
+        e
+    })?;
+    let metadata_cost = timer.finish();
+    record_read_only(&mut server_schema, &read_only);
+    // Databases are the main file followed by each attachment, in order
+    for ((schema, check), files) in server_schema
+        .databases
+        .iter_mut()
+        .zip(integrity)
+        .zip(auxiliary_files)
+    {
+        record_integrity(schema, check);
+        record_auxiliary_files(schema, files, cli);
+    }
+    metrics.record_phase("schema", phase_start);
+
+    let estimate = estimate_run(
+        server_schema.databases.iter().flat_map(|db| &db.tables),
+        &adapter,
+        metadata_cost,
+        cli,
+        metrics,
+    );
+    if cli.estimate {
+        return Ok(report_estimate(metrics));
+    }
+
+    if sampling_enabled(cli) {
+        let phase_start = Instant::now();
+        let sampling_config = build_sampling_config(cli);
+        info!("{}", estimate.summary());
+        let mut order: Vec<usize> = (0..server_schema.databases.len()).collect();
+        if cli.shuffle_order {
+            order.shuffle(&mut rand::rng());
+        }
+        for index in order {
+            let schema = &mut server_schema.databases[index];
+            if schema.tables.is_empty() {
+                continue;
+            }
+            let sampling_run = SamplingOrchestrator::new(&adapter, &sampling_config)
+                .with_shuffled_order(cli.shuffle_order)
+                .run(&schema.tables)
+                .await;
+            metrics.record_sampling(&sampling_run);
+            schema
+                .collection_metadata
+                .warnings
+                .extend(sampling_run.warnings);
+            schema.samples = Some(sampling_run.samples);
+        }
+        server_schema.refresh_manifest();
+        metrics.record_phase("sampling", phase_start);
+    }
+
+    for schema in &mut server_schema.databases {
+        check_server_version(schema);
+        record_settings(schema, cli);
+        apply_source_policy(schema, cli);
+        redact_metadata_secrets(schema);
+        apply_lint_rules(schema, cli);
+    }
+
+    let phase_start = Instant::now();
+    let saved_path = crate::output::save_server_schema(&server_schema, output_path, cli).await?;
+    metrics.record_phase("output", phase_start);
+    metrics.record_output(&saved_path).await;
+    info!("[OK]Server schema saved to {}", saved_path.display());
+    status!("Schema collection completed successfully");
+    status!("Output: {}", saved_path.display());
+    status!(
+        "Databases: {} ({} collected)",
+        server_schema.server_info.total_databases,
+        server_schema.server_info.collected_databases
+    );
+    report_privilege_skips(DatabaseType::SQLite, &server_schema.databases);
+
+    Ok(CollectionOutcome::from_results(&server_schema.databases))
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub(super) async fn collect_attached_databases(
+    _database_url: &str,
+    _output_path: &Path,
+    _cli: &Cli,
+    _metrics: &mut RunMetrics,
+) -> Result<CollectionOutcome> {
+    Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+        "--attach requires the sqlite feature",
+    ))
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+fn failed_database_schema(
+    name: &str,
+    owner: Option<String>,
+    is_system_database: bool,
+    error_message: String,
+) -> DatabaseSchema {
+    let mut info = DatabaseInfo::new(name.to_string());
+    info.owner = owner;
+    info.is_system_database = is_system_database;
+    info.collection_status = CollectionStatus::Failed {
+        error: error_message.clone(),
+    };
+    info.access_level = dbsurveyor_core::AccessLevel::None;
+
+    DatabaseSchema::new(info).with_warning(error_message)
+}
+
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+fn skipped_database_schema(
+    name: &str,
+    owner: Option<String>,
+    is_system_database: bool,
+    reason: String,
+) -> DatabaseSchema {
+    let mut info = DatabaseInfo::new(name.to_string());
+    info.owner = owner;
+    info.is_system_database = is_system_database;
+    info.collection_status = CollectionStatus::Skipped {
+        reason: reason.clone(),
+    };
+    info.access_level = dbsurveyor_core::AccessLevel::Limited;
+
+    DatabaseSchema::new(info).with_warning(reason)
+}
@@ -0,0 +1,78 @@
+//! Collector configuration file (`--config`).
+//!
+//! Settings too detailed for command-line flags, such as per-engine session
+//! limits, are read from a TOML file:
+//!
+//! ```toml
+//! [session_limits.postgres]
+//! work_mem = "4MB"
+//! statement_timeout_ms = 15000
+//!
+//! [session_limits.mysql]
+//! max_execution_time_ms = 10000
+//! ```
+
+use dbsurveyor_core::adapters::SessionLimits;
+use serde::Deserialize;
+
+/// Contents of a collector configuration file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CollectorConfig {
+    /// Per-engine session resource limits
+    #[serde(default)]
+    pub session_limits: SessionLimits,
+}
+
+impl CollectorConfig {
+    /// Parses and validates a configuration file's contents.
+    pub(crate) fn from_toml(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        config
+            .session_limits
+            .validate()
+            .map_err(|e| e.to_string())?;
+        Ok(config)
+    }
+}
+
+/// Reads and validates a configuration file while arguments are parsed, so
+/// mistakes are reported before connecting to the database.
+pub(crate) fn parse_config_file(path: &str) -> Result<CollectorConfig, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read config file {path}: {e}"))?;
+    CollectorConfig::from_toml(&content).map_err(|e| format!("invalid config file {path}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_limits_parsed() {
+        let config = CollectorConfig::from_toml(
+            "[session_limits.postgres]\nwork_mem = \"4MB\"\nstatement_timeout_ms = 15000\n\n\
+             [session_limits.mssql]\nquery_governor_cost_limit = 300\n",
+        )
+        .expect("valid config");
+
+        let limits = &config.session_limits;
+        assert_eq!(limits.postgres.work_mem.as_deref(), Some("4MB"));
+        assert_eq!(limits.postgres.statement_timeout_ms, Some(15_000));
+        assert_eq!(limits.mysql.max_execution_time_ms, None);
+        assert_eq!(limits.mssql.query_governor_cost_limit, Some(300));
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        // Unknown keys are typos, not settings to ignore
+        assert!(
+            CollectorConfig::from_toml("[session_limits.postgres]\nwork_memory = \"4MB\"\n")
+                .is_err()
+        );
+        assert!(
+            CollectorConfig::from_toml("[session_limits.postgres]\nwork_mem = \"lots\"\n").is_err()
+        );
+        assert!(CollectorConfig::from_toml("").is_ok());
+    }
+}
@@ -0,0 +1,313 @@
+//! Runtime and query-count estimates for a planned collection.
+//!
+//! After metadata collection the collector knows how many tables there are,
+//! their estimated row counts, and how long the metadata queries took. That
+//! is enough to predict the sampling phase under the configured sample size,
+//! size limits, throttle, and per-table timeout, so operators can check the
+//! plan fits the engagement window (`--estimate`) before any rows are read.
+
+use crate::Cli;
+use crate::collect::{build_sampling_config, sampling_enabled};
+use crate::metrics::RunMetrics;
+use crate::outcome::CollectionOutcome;
+use dbsurveyor_core::{DatabaseAdapter, SamplingConfig, Table, statements_executed};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// Queries a sampled table costs: ordering detection, the row-count
+/// estimate, column projection, and the sample itself.
+const QUERIES_PER_SAMPLED_TABLE: u64 = 4;
+
+/// Measures the time and statements spent collecting metadata.
+pub(crate) struct MetadataTimer {
+    started: Instant,
+    statements_at_start: u64,
+}
+
+impl MetadataTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            statements_at_start: statements_executed(),
+        }
+    }
+
+    pub(crate) fn finish(self) -> MetadataCost {
+        MetadataCost {
+            duration: self.started.elapsed(),
+            statements: statements_executed().saturating_sub(self.statements_at_start),
+        }
+    }
+}
+
+/// Time and statements spent collecting one database's metadata.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MetadataCost {
+    pub(crate) duration: Duration,
+    /// SQL statements executed; zero for MongoDB, whose commands are not counted
+    pub(crate) statements: u64,
+}
+
+impl MetadataCost {
+    /// Average round trip of the metadata queries, used as the cost of each
+    /// sampling query. Without a statement count the whole phase counts as
+    /// one round trip.
+    fn query_latency(&self) -> Duration {
+        let statements = u32::try_from(self.statements.max(1)).unwrap_or(u32::MAX);
+        self.duration / statements
+    }
+}
+
+/// Predicted cost of a collection run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct RunEstimate {
+    pub(crate) tables: usize,
+    pub(crate) tables_to_sample: usize,
+    /// Tables over `--sample-max-table-rows` or `--sample-max-table-bytes`
+    pub(crate) tables_over_limit: usize,
+    pub(crate) rows_to_sample: u64,
+    pub(crate) queries: u64,
+    pub(crate) metadata_ms: u64,
+    pub(crate) throttle_ms: u64,
+    pub(crate) sampling_ms: u64,
+    /// Upper bound when every sampled table runs to `--table-timeout`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) worst_case_ms: Option<u64>,
+}
+
+impl RunEstimate {
+    /// Estimates a run over `tables`, sampling with `sampling` if enabled.
+    pub(crate) fn new<'a>(
+        tables: impl IntoIterator<Item = &'a Table>,
+        sampling: Option<&SamplingConfig>,
+        metadata: MetadataCost,
+        table_timeout: Option<Duration>,
+    ) -> Self {
+        let metadata_ms = millis(metadata.duration);
+        let mut estimate = Self {
+            queries: metadata.statements,
+            metadata_ms,
+            worst_case_ms: table_timeout.map(|_| metadata_ms),
+            ..Self::default()
+        };
+        let Some(config) = sampling else {
+            estimate.tables = tables.into_iter().count();
+            return estimate;
+        };
+
+        for table in tables {
+            estimate.tables += 1;
+            if config
+                .oversize_reason(table.row_count, table.size_bytes)
+                .is_some()
+            {
+                estimate.tables_over_limit += 1;
+                continue;
+            }
+            estimate.tables_to_sample += 1;
+            let limit = u64::from(config.sample_size);
+            estimate.rows_to_sample += table.row_count.map_or(limit, |rows| rows.min(limit));
+        }
+
+        let sampled = estimate.tables_to_sample as u64;
+        let sample_queries = sampled * QUERIES_PER_SAMPLED_TABLE;
+        estimate.queries += sample_queries;
+        estimate.throttle_ms = sampled.saturating_mul(config.throttle_ms.unwrap_or(0));
+        estimate.sampling_ms = millis(metadata.query_latency()).saturating_mul(sample_queries);
+        estimate.worst_case_ms = table_timeout.map(|timeout| {
+            metadata_ms
+                .saturating_add(estimate.throttle_ms)
+                .saturating_add(millis(timeout).saturating_mul(sampled))
+        });
+        estimate
+    }
+
+    /// Adds another database's estimate to this one.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.tables += other.tables;
+        self.tables_to_sample += other.tables_to_sample;
+        self.tables_over_limit += other.tables_over_limit;
+        self.rows_to_sample += other.rows_to_sample;
+        self.queries += other.queries;
+        self.metadata_ms += other.metadata_ms;
+        self.throttle_ms += other.throttle_ms;
+        self.sampling_ms += other.sampling_ms;
+        self.worst_case_ms = self
+            .worst_case_ms
+            .zip(other.worst_case_ms)
+            .map(|(a, b)| a + b);
+    }
+
+    /// Expected wall-clock time of the whole run.
+    pub(crate) fn total_ms(&self) -> u64 {
+        self.metadata_ms + self.throttle_ms + self.sampling_ms
+    }
+
+    /// Renders a human-readable summary.
+    pub(crate) fn summary(&self) -> String {
+        let mut out = String::from("Estimated run:\n");
+        let _ = writeln!(
+            out,
+            "  Tables:           {} ({} to sample, {} over size limits)",
+            self.tables, self.tables_to_sample, self.tables_over_limit
+        );
+        let _ = writeln!(out, "  Rows to sample:   {}", self.rows_to_sample);
+        let _ = writeln!(out, "  Queries:          ~{}", self.queries);
+        let _ = writeln!(
+            out,
+            "  Runtime:          ~{} (metadata {}, throttle {}, sampling {})",
+            format_ms(self.total_ms()),
+            format_ms(self.metadata_ms),
+            format_ms(self.throttle_ms),
+            format_ms(self.sampling_ms)
+        );
+        if let Some(worst_case_ms) = self.worst_case_ms {
+            let _ = writeln!(
+                out,
+                "  Worst case:       {} (every sampled table hits --table-timeout)",
+                format_ms(worst_case_ms)
+            );
+        }
+        out.truncate(out.trim_end().len());
+        out
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Formats milliseconds as `1h 2m 3s`, `2m 3s`, `3.4s`, or `120 ms`.
+fn format_ms(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        0 => format!("{ms} ms"),
+        1..60 => format!("{:.1}s", ms as f64 / 1000.0),
+        60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m {}s", secs / 3600, secs % 3600 / 60, secs % 60),
+    }
+}
+
+/// Estimates the rest of the run from collected metadata and adds it to the
+/// run metrics.
+pub(crate) fn estimate_run<'a>(
+    tables: impl IntoIterator<Item = &'a Table>,
+    adapter: &dyn DatabaseAdapter,
+    metadata: MetadataCost,
+    cli: &Cli,
+    metrics: &mut RunMetrics,
+) -> RunEstimate {
+    let sampling_config = sampling_enabled(cli).then(|| build_sampling_config(cli));
+    let estimate = RunEstimate::new(
+        tables,
+        sampling_config.as_ref(),
+        metadata,
+        adapter.connection_config().table_timeout,
+    );
+    metrics.record_estimate(&estimate);
+    estimate
+}
+
+/// Prints the run estimate for `--estimate`, which stops before sampling.
+pub(crate) fn report_estimate(metrics: &RunMetrics) -> CollectionOutcome {
+    if let Some(estimate) = &metrics.estimate {
+        status!("{}", estimate.summary());
+    }
+    status!("Estimate only; nothing was sampled or written");
+    CollectionOutcome::Success
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(name: &str, row_count: Option<u64>) -> Table {
+        Table {
+            row_count,
+            ..Table::new(name.to_string(), None, Vec::new())
+        }
+    }
+
+    fn metadata(ms: u64, statements: u64) -> MetadataCost {
+        MetadataCost {
+            duration: Duration::from_millis(ms),
+            statements,
+        }
+    }
+
+    #[test]
+    fn test_estimate_applies_sample_limit_throttle_and_size_limits() {
+        let tables = [
+            table("small", Some(10)),
+            table("medium", Some(1_000)),
+            table("unknown", None),
+            table("huge", Some(50_000_000)),
+        ];
+        let config = SamplingConfig::new()
+            .with_sample_size(100)
+            .with_throttle_ms(500)
+            .with_max_table_rows(1_000_000);
+
+        let estimate = RunEstimate::new(&tables, Some(&config), metadata(200, 20), None);
+
+        assert_eq!(estimate.tables, 4);
+        assert_eq!(estimate.tables_to_sample, 3);
+        assert_eq!(estimate.tables_over_limit, 1);
+        assert_eq!(estimate.rows_to_sample, 10 + 100 + 100);
+        assert_eq!(estimate.queries, 20 + 3 * QUERIES_PER_SAMPLED_TABLE);
+        assert_eq!(estimate.throttle_ms, 1_500);
+        // 10 ms per metadata query, 12 sampling queries
+        assert_eq!(estimate.sampling_ms, 120);
+        assert_eq!(estimate.total_ms(), 200 + 1_500 + 120);
+        assert_eq!(estimate.worst_case_ms, None);
+    }
+
+    #[test]
+    fn test_estimate_without_sampling_is_metadata_only() {
+        let tables = [table("users", Some(10))];
+        let estimate = RunEstimate::new(
+            &tables,
+            None,
+            metadata(300, 0),
+            Some(Duration::from_secs(30)),
+        );
+
+        assert_eq!(estimate.tables_to_sample, 0);
+        assert_eq!(estimate.queries, 0);
+        assert_eq!(estimate.total_ms(), 300);
+        assert_eq!(estimate.worst_case_ms, Some(300));
+    }
+
+    #[test]
+    fn test_merged_estimate_bounds_worst_case_by_table_timeout() {
+        let config = SamplingConfig::new();
+        let timeout = Some(Duration::from_secs(30));
+        let mut estimate = RunEstimate::new(
+            &[table("a", Some(5))],
+            Some(&config),
+            metadata(100, 10),
+            timeout,
+        );
+        estimate.merge(&RunEstimate::new(
+            &[table("b", Some(5)), table("c", Some(5))],
+            Some(&config),
+            metadata(100, 10),
+            timeout,
+        ));
+
+        assert_eq!(estimate.tables_to_sample, 3);
+        assert_eq!(estimate.worst_case_ms, Some(200 + 3 * 30_000));
+        let summary = estimate.summary();
+        assert!(summary.contains("3 to sample"), "{summary}");
+        assert!(summary.contains("Worst case:       1m 30s"), "{summary}");
+    }
+
+    #[test]
+    fn test_format_ms() {
+        assert_eq!(format_ms(120), "120 ms");
+        assert_eq!(format_ms(3_400), "3.4s");
+        assert_eq!(format_ms(123_000), "2m 3s");
+        assert_eq!(format_ms(3_723_000), "1h 2m 3s");
+    }
+}
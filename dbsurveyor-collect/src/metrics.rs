@@ -0,0 +1,235 @@
+//! Local run metrics for tuning collection.
+//!
+//! At the end of a run the collector summarizes what it did (statements
+//! executed, time per phase, sampling retries, bytes written, and connection
+//! pool usage) so operators can tune concurrency and throttling. Metrics are
+//! printed to stderr and optionally written to a JSON file; they are never
+//! sent anywhere.
+
+use crate::estimate::RunEstimate;
+use crate::sampling::SamplingRun;
+use dbsurveyor_core::{
+    Result, adapters::PoolStats, error::DbSurveyorError, models::SampleStatus, statements_executed,
+};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Instant;
+
+/// Wall-clock time spent in one phase of the run.
+#[derive(Debug, Serialize)]
+pub(crate) struct PhaseTiming {
+    pub(crate) phase: &'static str,
+    pub(crate) duration_ms: u64,
+}
+
+/// Metrics gathered over a single collector run.
+#[derive(Debug, Serialize)]
+pub(crate) struct RunMetrics {
+    #[serde(skip)]
+    started: Instant,
+    #[serde(skip)]
+    statements_at_start: u64,
+    pub(crate) total_duration_ms: u64,
+    /// Phases in the order they first ran; repeated phases are accumulated
+    pub(crate) phases: Vec<PhaseTiming>,
+    /// SQL statements executed through sqlx (MongoDB commands are not counted)
+    pub(crate) queries_executed: u64,
+    pub(crate) tables_sampled: usize,
+    pub(crate) tables_skipped: usize,
+    pub(crate) sample_retries: usize,
+    pub(crate) bytes_written: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) pool: Option<PoolStats>,
+    /// Predicted cost, for comparison with the measured values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) estimate: Option<RunEstimate>,
+}
+
+impl RunMetrics {
+    /// Starts measuring a run.
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            statements_at_start: statements_executed(),
+            total_duration_ms: 0,
+            phases: Vec::new(),
+            queries_executed: 0,
+            tables_sampled: 0,
+            tables_skipped: 0,
+            sample_retries: 0,
+            bytes_written: 0,
+            pool: None,
+            estimate: None,
+        }
+    }
+
+    /// Adds the time elapsed since `started` to `phase`.
+    pub(crate) fn record_phase(&mut self, phase: &'static str, started: Instant) {
+        let elapsed = duration_ms(started);
+        match self.phases.iter_mut().find(|p| p.phase == phase) {
+            Some(timing) => timing.duration_ms = timing.duration_ms.saturating_add(elapsed),
+            None => self.phases.push(PhaseTiming {
+                phase,
+                duration_ms: elapsed,
+            }),
+        }
+    }
+
+    /// Adds the outcome of a sampling run.
+    pub(crate) fn record_sampling(&mut self, run: &SamplingRun) {
+        let skipped = run
+            .samples
+            .iter()
+            .filter(|s| matches!(s.sample_status, Some(SampleStatus::Skipped { .. })))
+            .count();
+        self.tables_skipped += skipped;
+        self.tables_sampled += run.samples.len() - skipped;
+        self.sample_retries += run.retries;
+    }
+
+    /// Adds one database's estimate to the run estimate.
+    pub(crate) fn record_estimate(&mut self, estimate: &RunEstimate) {
+        match &mut self.estimate {
+            Some(total) => total.merge(estimate),
+            None => self.estimate = Some(estimate.clone()),
+        }
+    }
+
+    /// Records the size of the written output file.
+    pub(crate) async fn record_output(&mut self, path: &Path) {
+        if let Ok(metadata) = tokio::fs::metadata(path).await {
+            self.bytes_written += metadata.len();
+        }
+    }
+
+    /// Stops the clock and captures the statement count.
+    pub(crate) fn finish(&mut self) {
+        self.total_duration_ms = duration_ms(self.started);
+        self.queries_executed = statements_executed().saturating_sub(self.statements_at_start);
+    }
+
+    /// Renders a human-readable summary.
+    pub(crate) fn summary(&self) -> String {
+        let mut out = String::from("Run metrics:\n");
+        let _ = writeln!(out, "  Duration:         {} ms", self.total_duration_ms);
+        for timing in &self.phases {
+            let _ = writeln!(
+                out,
+                "    {:<16}{} ms",
+                format!("{}:", timing.phase),
+                timing.duration_ms
+            );
+        }
+        let _ = writeln!(out, "  Queries executed: {}", self.queries_executed);
+        let _ = writeln!(
+            out,
+            "  Tables sampled:   {} ({} skipped, {} retried)",
+            self.tables_sampled, self.tables_skipped, self.sample_retries
+        );
+        let _ = writeln!(out, "  Bytes written:    {}", self.bytes_written);
+        if let Some(pool) = &self.pool {
+            let _ = writeln!(
+                out,
+                "  Connection pool:  {} active, {} idle, {}/{} connections",
+                pool.active_connections,
+                pool.idle_connections,
+                pool.total_connections,
+                pool.max_connections
+            );
+        }
+        out.truncate(out.trim_end().len());
+        out
+    }
+
+    /// Writes the metrics as JSON to `path`.
+    pub(crate) fn write_to_file(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            DbSurveyorError::configuration(format!("Failed to serialize run metrics: {}", e))
+        })?;
+        std::fs::write(path, json).map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to write metrics file {}", path.display()),
+            source: e,
+        })
+    }
+}
+
+fn duration_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::{SamplingStrategy, TableSample};
+
+    fn sample(status: SampleStatus) -> TableSample {
+        TableSample {
+            table_name: "users".to_string(),
+            schema_name: None,
+            rows: Vec::new(),
+            sample_size: 0,
+            total_rows: None,
+            sampling_strategy: SamplingStrategy::None,
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: Some(status),
+            column_types: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_phase_accumulates_repeated_phases() {
+        let mut metrics = RunMetrics::start();
+        let started = Instant::now();
+        metrics.record_phase("schema", started);
+        metrics.record_phase("sampling", started);
+        metrics.record_phase("schema", started);
+
+        let phases: Vec<_> = metrics.phases.iter().map(|p| p.phase).collect();
+        assert_eq!(phases, vec!["schema", "sampling"]);
+    }
+
+    #[test]
+    fn test_record_sampling_counts_skips_and_retries() {
+        let mut metrics = RunMetrics::start();
+        metrics.record_sampling(&SamplingRun {
+            samples: vec![
+                sample(SampleStatus::Complete),
+                sample(SampleStatus::Skipped {
+                    reason: "timed out".to_string(),
+                }),
+            ],
+            warnings: Vec::new(),
+            retries: 1,
+        });
+
+        assert_eq!(metrics.tables_sampled, 1);
+        assert_eq!(metrics.tables_skipped, 1);
+        assert_eq!(metrics.sample_retries, 1);
+    }
+
+    #[test]
+    fn test_metrics_file_round_trip() {
+        let mut metrics = RunMetrics::start();
+        metrics.bytes_written = 2048;
+        metrics.pool = Some(PoolStats {
+            idle_connections: 1,
+            active_connections: 2,
+            total_connections: 3,
+            max_connections: 10,
+        });
+        metrics.finish();
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("metrics.json");
+        metrics.write_to_file(&path).expect("write metrics");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read metrics"))
+                .expect("valid JSON");
+        assert_eq!(json["bytes_written"], 2048);
+        assert_eq!(json["pool"]["max_connections"], 10);
+        assert!(metrics.summary().contains("Connection pool:  2 active"));
+    }
+}
@@ -60,12 +60,14 @@ pub(crate) fn from_results(databases: &[DatabaseSchema]) -> Self {
 
 fn database_has_no_samples(database: &DatabaseSchema) -> bool {
     match database.database_info.collection_status {
-        CollectionStatus::Success => database.samples.as_ref().is_some_and(|samples| {
-            !samples.is_empty()
-                && samples.iter().all(|sample| {
-                    matches!(sample.sample_status, Some(SampleStatus::Skipped { .. }))
-                })
-        }),
+        CollectionStatus::Success | CollectionStatus::Partial { .. } => {
+            database.samples.as_ref().is_some_and(|samples| {
+                !samples.is_empty()
+                    && samples.iter().all(|sample| {
+                        matches!(sample.sample_status, Some(SampleStatus::Skipped { .. }))
+                    })
+            })
+        }
         CollectionStatus::Failed { .. } | CollectionStatus::Skipped { .. } => false,
     }
 }
@@ -73,7 +75,9 @@ fn database_has_no_samples(database: &DatabaseSchema) -> bool {
 fn database_has_partial_data(database: &DatabaseSchema) -> bool {
     if matches!(
         database.database_info.collection_status,
-        CollectionStatus::Failed { .. } | CollectionStatus::Skipped { .. }
+        CollectionStatus::Failed { .. }
+            | CollectionStatus::Skipped { .. }
+            | CollectionStatus::Partial { .. }
     ) {
         return true;
     }
@@ -111,6 +115,7 @@ fn sample(status: SampleStatus) -> TableSample {
             collected_at: chrono::Utc::now(),
             warnings: Vec::new(),
             sample_status: Some(status),
+            column_types: Vec::new(),
         }
     }
 
@@ -143,7 +148,13 @@ fn test_outcome_partial_without_samples_takes_precedence() {
             collected_at: chrono::Utc::now(),
             collection_duration_ms: 0,
             collector_version: env!("CARGO_PKG_VERSION").to_string(),
-            warnings: vec!["warning".to_string()],
+            warnings: vec!["warning".into()],
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
         };
 
         assert_eq!(
@@ -182,13 +193,30 @@ fn test_outcome_partial_with_data_for_skipped_database() {
         );
     }
 
+    #[test]
+    fn test_outcome_partial_with_data_for_partial_database() {
+        let partial = database_schema("db1").with_object_failures(vec![
+            dbsurveyor_core::models::ObjectFailure::new(
+                Some("public"),
+                "orders",
+                "columns",
+                "boom",
+            ),
+        ]);
+
+        assert_eq!(
+            CollectionOutcome::from_results(&[partial]),
+            CollectionOutcome::PartialWithData
+        );
+    }
+
     #[test]
     fn test_outcome_partial_with_validation_warnings() {
         let mut warning_schema = database_schema("db1");
         warning_schema
             .collection_metadata
             .warnings
-            .push("warning".to_string());
+            .push("warning".into());
 
         assert_eq!(
             CollectionOutcome::from_results(&[warning_schema]),
@@ -216,7 +244,7 @@ fn test_outcome_partial_without_samples_beats_validation_warnings() {
         warning_schema
             .collection_metadata
             .warnings
-            .push("validation warning".to_string());
+            .push("validation warning".into());
 
         assert_eq!(
             CollectionOutcome::from_results(&[warning_schema, no_samples]),
@@ -6,16 +6,46 @@
 //! given, the output path is normalized to carry the matching extension
 //! (`.zst` or `.enc`) so the postprocessor's extension-based format
 //! detection can load the file.
+//!
+//! An output path of `-` writes the survey to stdout instead, for pipelines
+//! such as `dbsurveyor-collect -o - ... | dbsurveyor generate -`. Status
+//! messages and logs then go to stderr (see [`reserve_stdout`]).
 
 use crate::Cli;
 use dbsurveyor_core::Result;
 use dbsurveyor_core::error::DbSurveyorError;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::info;
+use zeroize::Zeroizing;
+
+/// Output path that writes the survey to stdout.
+pub(crate) const STDOUT_PATH: &str = "-";
+
+/// Set once stdout carries the survey.
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Whether `path` means stdout.
+pub(crate) fn is_stdout(path: &Path) -> bool {
+    path == Path::new(STDOUT_PATH)
+}
+
+/// Marks stdout as carrying the survey, so status messages move to stderr.
+pub(crate) fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+/// Whether stdout carries the survey.
+pub(crate) fn stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Environment variable holding the key for `--sign`.
+const SIGNING_KEY_ENV_VAR: &str = "DBSURVEYOR_SIGNING_KEY";
 
 /// Environment variable consulted for a non-interactive encryption password.
 #[cfg(feature = "encryption")]
-const PASSWORD_ENV_VAR: &str = "DBSURVEYOR_ENCRYPTION_PASSWORD";
+pub(crate) const PASSWORD_ENV_VAR: &str = "DBSURVEYOR_ENCRYPTION_PASSWORD";
 
 /// Saves schema to file with optional compression and encryption.
 ///
@@ -26,8 +56,14 @@ pub(crate) async fn save_schema(
     output_path: &Path,
     cli: &Cli,
 ) -> Result<PathBuf> {
-    let json_value = serde_json::to_value(schema)
-        .map_err(|e| DbSurveyorError::collection_failed("JSON serialization", e))?;
+    let json_value = if cli.no_metadata {
+        let mut schema = schema.clone();
+        dbsurveyor_core::anonymize::strip_database_environment(&mut schema);
+        serde_json::to_value(&schema)
+    } else {
+        serde_json::to_value(schema)
+    }
+    .map_err(|e| DbSurveyorError::collection_failed("JSON serialization", e))?;
     save_json_value(&json_value, output_path, cli, true).await
 }
 
@@ -40,8 +76,16 @@ pub(crate) async fn save_server_schema(
     output_path: &Path,
     cli: &Cli,
 ) -> Result<PathBuf> {
-    let json_value = serde_json::to_value(schema)
-        .map_err(|e| DbSurveyorError::collection_failed("JSON serialization", e))?;
+    let json_value = if cli.no_metadata {
+        let mut schema = schema.clone();
+        dbsurveyor_core::anonymize::strip_server_environment(&mut schema);
+        serde_json::to_value(&schema)
+    } else {
+        serde_json::to_value(schema)
+    }
+    .map_err(|e| DbSurveyorError::collection_failed("JSON serialization", e))?;
+    dbsurveyor_core::validate_server_schema_output(&json_value)
+        .map_err(|e| DbSurveyorError::collection_failed("Server schema validation failed", e))?;
     save_json_value(&json_value, output_path, cli, false).await
 }
 
@@ -61,14 +105,55 @@ async fn save_json_value(
         info!("[OK]Output serialization prepared for multi-database schema");
     }
 
+    if let Some(journal) = &cli.append_to {
+        let key = if cli.sign {
+            Some(obtain_signing_key(cli.signing_key_file.as_deref())?)
+        } else {
+            None
+        };
+        let (path, survey) = (journal.clone(), json_value.clone());
+        let header = tokio::task::spawn_blocking(move || {
+            dbsurveyor_core::journal::append(
+                &path,
+                &survey,
+                key.as_ref().map(|key| key.as_bytes()),
+                &dbsurveyor_core::InputLimits::default(),
+            )
+        })
+        .await
+        .map_err(|e| DbSurveyorError::collection_failed("Journal append task failed", e))??;
+        info!(
+            "[OK]Appended record {} ({:?}) to {}",
+            header.seq,
+            header.kind,
+            journal.display()
+        );
+        return Ok(journal.clone());
+    }
+
+    if cli.sign && is_stdout(output_path) {
+        return Err(DbSurveyorError::configuration(
+            "--sign writes a detached .sig file next to the survey and cannot be used with --output -",
+        ));
+    }
+
+    // Fail before writing anything if a signature was requested without a key
+    let signing_key = if cli.sign {
+        Some(obtain_signing_key(cli.signing_key_file.as_deref())?)
+    } else {
+        None
+    };
+    let canonical_json = (cli.canonical || cli.sign)
+        .then(|| dbsurveyor_core::canonical::to_canonical_json(json_value));
+
     let output_path = effective_output_path(output_path, cli.compress, cli.encrypt);
 
     if cli.encrypt && cli.compress {
         #[cfg(all(feature = "encryption", feature = "compression"))]
         {
-            let json_data = to_pretty_json(json_value)?;
+            let json_data = serialize_json(json_value, canonical_json.as_deref())?;
             let compressed = compress_bytes(json_data.into_bytes()).await?;
-            save_encrypted(compressed, &output_path).await?;
+            save_encrypted(compressed, &output_path, cli.password_file.as_deref()).await?;
         }
         #[cfg(not(all(feature = "encryption", feature = "compression")))]
         {
@@ -79,8 +164,13 @@ async fn save_json_value(
     } else if cli.encrypt {
         #[cfg(feature = "encryption")]
         {
-            let json_data = to_pretty_json(json_value)?;
-            save_encrypted(json_data.into_bytes(), &output_path).await?;
+            let json_data = serialize_json(json_value, canonical_json.as_deref())?;
+            save_encrypted(
+                json_data.into_bytes(),
+                &output_path,
+                cli.password_file.as_deref(),
+            )
+            .await?;
         }
         #[cfg(not(feature = "encryption"))]
         {
@@ -91,7 +181,7 @@ async fn save_json_value(
     } else if cli.compress {
         #[cfg(feature = "compression")]
         {
-            let json_data = to_pretty_json(json_value)?;
+            let json_data = serialize_json(json_value, canonical_json.as_deref())?;
             let compressed = compress_bytes(json_data.into_bytes()).await?;
             write_atomic(&output_path, compressed).await?;
         }
@@ -101,10 +191,20 @@ async fn save_json_value(
                 "Compression not available. Compile with --features compression",
             ));
         }
+    } else if let Some(canonical) = &canonical_json {
+        write_atomic(&output_path, canonical.clone().into_bytes()).await?;
     } else {
         save_json_streaming(json_value, &output_path)?;
     }
 
+    if let (Some(key), Some(canonical)) = (signing_key, &canonical_json) {
+        let signature =
+            dbsurveyor_core::security::signing::sign(canonical.as_bytes(), key.as_bytes())?;
+        let signature_path = signature_path(&output_path);
+        write_atomic(&signature_path, format!("{signature}\n").into_bytes()).await?;
+        info!("[OK]Signature written to {}", signature_path.display());
+    }
+
     Ok(output_path)
 }
 
@@ -116,7 +216,9 @@ async fn save_json_value(
 /// the final extension, so writing compressed or encrypted bytes to a
 /// `.json`-named file would produce an unloadable output.
 fn effective_output_path(output_path: &Path, compress: bool, encrypt: bool) -> PathBuf {
-    let target_ext = if encrypt {
+    let target_ext = if is_stdout(output_path) {
+        return output_path.to_path_buf();
+    } else if encrypt {
         "enc"
     } else if compress {
         "zst"
@@ -143,11 +245,36 @@ fn effective_output_path(output_path: &Path, compress: bool, encrypt: bool) -> P
     adjusted
 }
 
-/// Serializes a JSON value to a pretty-printed string.
+/// Serializes a JSON value to a pretty-printed string, or returns the
+/// canonical form when one was produced.
 #[cfg(any(feature = "encryption", feature = "compression"))]
-fn to_pretty_json(json_value: &serde_json::Value) -> Result<String> {
-    serde_json::to_string_pretty(json_value)
-        .map_err(|e| DbSurveyorError::collection_failed("JSON formatting", e))
+fn serialize_json(json_value: &serde_json::Value, canonical: Option<&str>) -> Result<String> {
+    match canonical {
+        Some(canonical) => Ok(canonical.to_string()),
+        None => serde_json::to_string_pretty(json_value)
+            .map_err(|e| DbSurveyorError::collection_failed("JSON formatting", e)),
+    }
+}
+
+/// Detached signature path: the output path with `.sig` appended.
+fn signature_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.as_os_str().to_os_string();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Reads the HMAC signing key from `--signing-key-file` or
+/// `DBSURVEYOR_SIGNING_KEY`.
+fn obtain_signing_key(key_file: Option<&Path>) -> Result<Zeroizing<String>> {
+    if let Some(path) = key_file {
+        return dbsurveyor_core::security::read_secret_file(path);
+    }
+    match std::env::var(SIGNING_KEY_ENV_VAR) {
+        Ok(key) if !key.is_empty() => Ok(Zeroizing::new(key)),
+        _ => Err(DbSurveyorError::configuration(format!(
+            "--sign requires a signing key in {SIGNING_KEY_ENV_VAR}"
+        ))),
+    }
 }
 
 /// Returns the directory a file will be created in, defaulting to the
@@ -165,6 +292,18 @@ fn parent_dir(path: &Path) -> &Path {
 fn save_json_streaming(json_value: &serde_json::Value, output_path: &Path) -> Result<()> {
     use std::io::Write;
 
+    if is_stdout(output_path) {
+        let mut writer = std::io::BufWriter::new(std::io::stdout().lock());
+        serde_json::to_writer_pretty(&mut writer, json_value)
+            .map_err(|e| DbSurveyorError::collection_failed("JSON streaming write", e))?;
+        return writeln!(writer)
+            .and_then(|()| writer.flush())
+            .map_err(|e| DbSurveyorError::Io {
+                context: "Failed to write survey to stdout".to_string(),
+                source: e,
+            });
+    }
+
     let tmp = tempfile::NamedTempFile::new_in(parent_dir(output_path)).map_err(|e| {
         DbSurveyorError::Io {
             context: format!(
@@ -188,9 +327,21 @@ fn save_json_streaming(json_value: &serde_json::Value, output_path: &Path) -> Re
 }
 
 /// Writes bytes to `output_path` atomically via a temporary file in the
-/// same directory. Runs on the blocking thread pool.
-#[cfg(any(feature = "encryption", feature = "compression"))]
+/// same directory, or to stdout for `-`. Runs on the blocking thread pool.
 async fn write_atomic(output_path: &Path, data: Vec<u8>) -> Result<()> {
+    if is_stdout(output_path) {
+        use std::io::Write;
+
+        let mut stdout = std::io::stdout().lock();
+        return stdout
+            .write_all(&data)
+            .and_then(|()| stdout.flush())
+            .map_err(|e| DbSurveyorError::Io {
+                context: "Failed to write survey to stdout".to_string(),
+                source: e,
+            });
+    }
+
     let path = output_path.to_path_buf();
     tokio::task::spawn_blocking(move || -> Result<()> {
         use std::io::Write;
@@ -247,10 +398,14 @@ async fn compress_bytes(data: Vec<u8>) -> Result<Vec<u8>> {
 /// zstd-compressed JSON; the postprocessor detects compression inside the
 /// decrypted payload via the zstd frame magic.
 #[cfg(feature = "encryption")]
-async fn save_encrypted(payload: Vec<u8>, output_path: &Path) -> Result<()> {
+async fn save_encrypted(
+    payload: Vec<u8>,
+    output_path: &Path,
+    password_file: Option<&Path>,
+) -> Result<()> {
     use dbsurveyor_core::security::encryption::encrypt_data_async;
 
-    let password = obtain_encryption_password()?;
+    let password = obtain_encryption_password(password_file)?;
     let encrypted = encrypt_data_async(&payload, &password).await?;
     let encrypted_json = serde_json::to_string_pretty(&encrypted)
         .map_err(|e| DbSurveyorError::collection_failed("Encryption serialization", e))?;
@@ -258,20 +413,27 @@ async fn save_encrypted(payload: Vec<u8>, output_path: &Path) -> Result<()> {
     write_atomic(output_path, encrypted_json.into_bytes()).await
 }
 
-/// Obtains the encryption password from `DBSURVEYOR_ENCRYPTION_PASSWORD`
-/// or interactively (with confirmation) when the variable is not set.
+/// Obtains the encryption password from `--password-file`,
+/// `DBSURVEYOR_ENCRYPTION_PASSWORD`, or interactively (with confirmation)
+/// when neither is set.
 #[cfg(feature = "encryption")]
-fn obtain_encryption_password() -> Result<String> {
+fn obtain_encryption_password(password_file: Option<&Path>) -> Result<Zeroizing<String>> {
+    if let Some(path) = password_file {
+        let password = dbsurveyor_core::security::read_secret_file(path)?;
+        validate_password(&password)?;
+        return Ok(password);
+    }
     if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        let password = Zeroizing::new(password);
         validate_password(&password)?;
         return Ok(password);
     }
 
-    let password = prompt_password("Enter encryption password: ")?;
+    let password = Zeroizing::new(prompt_password("Enter encryption password: ")?);
     validate_password(&password)?;
 
     // Confirm password to prevent typos
-    let password_confirm = prompt_password("Confirm encryption password: ")?;
+    let password_confirm = Zeroizing::new(prompt_password("Confirm encryption password: ")?);
     if password != password_confirm {
         return Err(DbSurveyorError::configuration("Passwords do not match"));
     }
@@ -284,10 +446,11 @@ fn obtain_encryption_password() -> Result<String> {
 fn prompt_password(prompt: &str) -> Result<String> {
     use std::io::{self, Write};
 
-    print!("{prompt}");
-    io::stdout().flush().map_err(|e| {
+    // stderr, so the prompt does not end up in a survey written to stdout
+    eprint!("{prompt}");
+    io::stderr().flush().map_err(|e| {
         DbSurveyorError::configuration(format!(
-            "Failed to flush stdout before reading password: {}",
+            "Failed to flush stderr before reading password: {}",
             e
         ))
     })?;
@@ -369,6 +532,41 @@ fn effective_path_keeps_existing_enc_extension() {
         );
     }
 
+    #[test]
+    fn canonical_output_is_signed_with_detached_signature() {
+        use clap::Parser;
+
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("schema.json");
+        let cli = Cli::try_parse_from(["dbsurveyor-collect", "--sign"]).expect("valid arguments");
+        let value = serde_json::json!({"b": 1.0, "collected_at": "2024-01-02T05:04:05+02:00"});
+
+        let saved = temp_env::with_var(SIGNING_KEY_ENV_VAR, Some("secret"), || {
+            tokio::runtime::Runtime::new()
+                .expect("failed to build runtime")
+                .block_on(save_json_value(&value, &path, &cli, false))
+        })
+        .expect("signed write failed");
+
+        let written = std::fs::read(&saved).expect("failed to read output");
+        assert_eq!(
+            written,
+            br#"{"b":1,"collected_at":"2024-01-02T03:04:05.000000Z"}"#
+        );
+        let signature =
+            std::fs::read_to_string(signature_path(&saved)).expect("failed to read signature");
+        assert!(dbsurveyor_core::security::signing::verify(
+            &written, b"secret", &signature
+        ));
+
+        let missing_key = temp_env::with_var_unset(SIGNING_KEY_ENV_VAR, || {
+            tokio::runtime::Runtime::new()
+                .expect("failed to build runtime")
+                .block_on(save_json_value(&value, &path, &cli, false))
+        });
+        assert!(missing_key.is_err());
+    }
+
     #[test]
     fn save_json_streaming_writes_atomically_and_overwrites() {
         let dir = tempfile::tempdir().expect("failed to create temp dir");
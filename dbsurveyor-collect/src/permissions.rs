@@ -0,0 +1,179 @@
+//! The `permissions` subcommand and the post-run report of features skipped
+//! for lack of privileges.
+
+use crate::Engine;
+use clap::ValueEnum;
+use dbsurveyor_core::privileges::{
+    grant_statements, privilege_requirements, privilege_skips, requirement_for,
+};
+use dbsurveyor_core::{DatabaseSchema, DatabaseType};
+use std::fmt::Write as _;
+
+/// The privilege each collection feature needs on `database_type`.
+pub(crate) fn requirements_table(database_type: DatabaseType) -> String {
+    let requirements = privilege_requirements(database_type);
+    let width = requirements
+        .iter()
+        .map(|requirement| requirement.feature.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{} collection privileges:\n\n", database_type);
+    for requirement in requirements {
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {}",
+            requirement.feature,
+            requirement.privilege,
+            width = width
+        );
+    }
+    out.push_str("\nRun with --sql for grant statement templates.\n");
+    out
+}
+
+/// Grant statements provisioning a collecting role on `database_type`.
+pub(crate) fn grant_script(
+    database_type: DatabaseType,
+    user: &str,
+    database: &str,
+    schema: &str,
+) -> String {
+    let comment = match database_type {
+        DatabaseType::MongoDB => "//",
+        _ => "--",
+    };
+    let statements = grant_statements(database_type, user, database, schema);
+
+    let mut out = format!(
+        "{} Minimum privileges for dbsurveyor-collect on {}\n",
+        comment, database_type
+    );
+    if statements.is_empty() {
+        let _ = writeln!(
+            out,
+            "{} Nothing to grant; see `dbsurveyor-collect permissions` for what collection needs",
+            comment
+        );
+    }
+    for statement in statements {
+        out.push_str(&statement);
+        out.push('\n');
+    }
+    out
+}
+
+/// Lists the features skipped because access was denied, with the privilege
+/// each needs; `None` when nothing was skipped.
+pub(crate) fn skip_report(
+    database_type: DatabaseType,
+    schemas: &[DatabaseSchema],
+) -> Option<String> {
+    let mut out = String::new();
+    for schema in schemas {
+        for skip in privilege_skips(schema) {
+            let feature = if schemas.len() > 1 {
+                format!("{}: {}", schema.database_info.name, skip.feature)
+            } else {
+                skip.feature.clone()
+            };
+            match skip.objects.len() {
+                0 => {
+                    let _ = writeln!(out, "  {}: {}", feature, skip.error);
+                }
+                count => {
+                    let _ = writeln!(
+                        out,
+                        "  {} ({} object{}): {}",
+                        feature,
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        skip.error
+                    );
+                }
+            }
+            if let Some(requirement) = requirement_for(database_type, &skip.feature) {
+                let _ = writeln!(out, "    needs: {}", requirement.privilege);
+            }
+        }
+    }
+    if out.is_empty() {
+        return None;
+    }
+
+    let mut report = format!("Skipped due to insufficient privileges:\n{}", out);
+    if let Some(engine) = Engine::value_variants()
+        .iter()
+        .find(|engine| DatabaseType::from(**engine) == database_type)
+        .and_then(|engine| engine.to_possible_value())
+    {
+        let _ = writeln!(
+            report,
+            "Run `dbsurveyor-collect permissions {} --sql` for grant statements.",
+            engine.get_name()
+        );
+    }
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::WarningCategory;
+    use dbsurveyor_core::models::{CollectionWarning, DatabaseInfo, ObjectFailure};
+
+    #[test]
+    fn test_requirements_table_lists_features() {
+        let table = requirements_table(DatabaseType::MySQL);
+        assert!(table.starts_with("MySQL collection privileges:"));
+        assert!(table.contains(&format!("  {:<21}  SHOW VIEW on the database", "views")));
+        assert!(table.contains("  read-only enforcement  super_read_only"));
+    }
+
+    #[test]
+    fn test_grant_script_comments_per_engine() {
+        let sql = grant_script(DatabaseType::MySQL, "ro", "shop", "public");
+        assert!(sql.starts_with("-- Minimum privileges for dbsurveyor-collect on MySQL\n"));
+        assert!(sql.contains("GRANT SHOW VIEW ON `shop`.* TO 'ro'@'%';\n"));
+
+        let mongo = grant_script(DatabaseType::MongoDB, "ro", "shop", "public");
+        assert!(mongo.starts_with("// "));
+        assert!(mongo.contains("grantRolesToUser(\"ro\""));
+
+        let sqlite = grant_script(DatabaseType::SQLite, "ro", "shop", "public");
+        assert!(sqlite.contains("-- Nothing to grant"));
+    }
+
+    #[test]
+    fn test_skip_report_names_features_and_requirements() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        assert!(skip_report(DatabaseType::MySQL, std::slice::from_ref(&schema)).is_none());
+
+        schema
+            .collection_metadata
+            .warnings
+            .push(CollectionWarning::new(
+                WarningCategory::Collection,
+                "Failed to collect views: Insufficient privileges: SHOW VIEW",
+            ));
+        schema
+            .collection_metadata
+            .object_failures
+            .push(ObjectFailure::new(
+                None,
+                "orders",
+                "indexes",
+                "SELECT command denied to user 'ro'@'%'",
+            ));
+
+        let report = skip_report(DatabaseType::MySQL, &[schema]).expect("skips reported");
+        assert_eq!(
+            report,
+            "Skipped due to insufficient privileges:\n\
+             \x20 views: Insufficient privileges: SHOW VIEW\n\
+             \x20   needs: SHOW VIEW on the database to read view definitions\n\
+             \x20 indexes (1 object): SELECT command denied to user 'ro'@'%'\n\
+             Run `dbsurveyor-collect permissions mysql --sql` for grant statements.\n"
+        );
+    }
+}
@@ -1,48 +1,146 @@
 //! Sampling orchestration for collector-driven retry and warning policy.
 
 use dbsurveyor_core::{
-    DatabaseAdapter, SamplingConfig, SamplingStrategy, Table, TableSample, adapters::TableRef,
-    models::SampleStatus,
+    CollectionWarning, DatabaseAdapter, SamplingConfig, SamplingStrategy, Table, TableSample,
+    WarningCategory, adapters::TableRef, models::SampleStatus,
 };
+use rand::seq::SliceRandom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 /// Aggregated result of a sampling run.
 pub(crate) struct SamplingRun {
     pub(crate) samples: Vec<TableSample>,
-    pub(crate) warnings: Vec<String>,
+    /// Sample warnings, attributed to the table they came from
+    pub(crate) warnings: Vec<CollectionWarning>,
+    /// Number of tables whose first sampling attempt failed and was retried
+    pub(crate) retries: usize,
 }
 
 /// Coordinates per-table sampling policy above database adapters.
 pub(crate) struct SamplingOrchestrator<'a> {
     adapter: &'a dyn DatabaseAdapter,
     config: &'a SamplingConfig,
+    table_timeout: Option<Duration>,
+    shuffle_order: bool,
+    retries: AtomicUsize,
 }
 
 impl<'a> SamplingOrchestrator<'a> {
     /// Creates a new sampling orchestrator.
+    ///
+    /// Each table is bounded by the adapter's per-table timeout, if configured.
     pub(crate) fn new(adapter: &'a dyn DatabaseAdapter, config: &'a SamplingConfig) -> Self {
-        Self { adapter, config }
+        Self {
+            adapter,
+            config,
+            table_timeout: adapter.connection_config().table_timeout,
+            shuffle_order: false,
+            retries: AtomicUsize::new(0),
+        }
+    }
+
+    /// Samples tables in random order instead of catalog order. Results are
+    /// still returned in catalog order.
+    #[must_use]
+    pub(crate) fn with_shuffled_order(mut self, shuffle: bool) -> Self {
+        self.shuffle_order = shuffle;
+        self
     }
 
     /// Samples all provided tables and applies collector retry policy.
     pub(crate) async fn run(&self, tables: &[Table]) -> SamplingRun {
-        let mut samples = Vec::with_capacity(tables.len());
-        let mut warnings = Vec::new();
+        let mut order: Vec<usize> = (0..tables.len()).collect();
+        if self.shuffle_order {
+            order.shuffle(&mut rand::rng());
+        }
 
-        for table in tables {
+        let mut results = Vec::with_capacity(tables.len());
+        for index in order {
+            let table = &tables[index];
             let table_ref = TableRef {
                 schema_name: table.schema.as_deref(),
                 table_name: &table.name,
             };
 
-            let sample = self.sample_single_table(table_ref).await;
-            warnings.extend(sample.warnings.iter().cloned());
+            // Adapters leave skip-column columns out of the sampling query
+            let skipped = self
+                .config
+                .skipped_column_warnings(table.columns.iter().map(|c| c.name.as_str()));
+            let oversize = self
+                .config
+                .oversize_reason(table.row_count, table.size_bytes);
+            let mut sample = match oversize {
+                Some(reason) => {
+                    let reason = format!("Sampling table '{}' skipped: {}", table_ref, reason);
+                    TableSample {
+                        total_rows: table.row_count,
+                        ..skipped_sample(table_ref.clone(), reason)
+                    }
+                }
+                None if !table.columns.is_empty() && skipped.len() == table.columns.len() => {
+                    let reason = format!(
+                        "Sampling table '{}' skipped: every column matches a skip-column pattern",
+                        table_ref
+                    );
+                    skipped_sample(table_ref.clone(), reason)
+                }
+                None => self.sample_single_table(table_ref.clone()).await,
+            };
+            let mut redactions = self.config.apply_sensitive_actions(&mut sample.rows);
+            for warning in skipped {
+                if !redactions.contains(&warning) {
+                    redactions.push(warning);
+                }
+            }
+            sample.warnings.extend(redactions);
+            let warnings: Vec<_> = sample
+                .warnings
+                .iter()
+                .map(|warning| {
+                    CollectionWarning::new(WarningCategory::Sampling, warning.clone())
+                        .with_object(table_ref.to_string())
+                })
+                .collect();
+            results.push((index, sample, warnings));
+        }
+
+        // Restore catalog order so shuffled runs produce identical output
+        results.sort_by_key(|(index, _, _)| *index);
+        let mut samples = Vec::with_capacity(results.len());
+        let mut warnings = Vec::new();
+        for (_, sample, sample_warnings) in results {
             samples.push(sample);
+            warnings.extend(sample_warnings);
         }
 
-        SamplingRun { samples, warnings }
+        SamplingRun {
+            samples,
+            warnings,
+            retries: self.retries.load(Ordering::Relaxed),
+        }
     }
 
+    /// Samples one table; a table exceeding the per-table timeout is skipped
+    /// without a retry, since a smaller limit rarely helps a slow scan.
     async fn sample_single_table(&self, table_ref: TableRef<'_>) -> TableSample {
+        let Some(limit) = self.table_timeout else {
+            return self.sample_with_retry(table_ref).await;
+        };
+
+        match tokio::time::timeout(limit, self.sample_with_retry(table_ref.clone())).await {
+            Ok(sample) => sample,
+            Err(_) => {
+                let reason = format!(
+                    "Sampling table '{}' timed out after {:?}; table skipped",
+                    table_ref, limit
+                );
+                skipped_sample(table_ref, reason)
+            }
+        }
+    }
+
+    async fn sample_with_retry(&self, table_ref: TableRef<'_>) -> TableSample {
         match self
             .adapter
             .sample_table(table_ref.clone(), self.config)
@@ -50,6 +148,7 @@ async fn sample_single_table(&self, table_ref: TableRef<'_>) -> TableSample {
         {
             Ok(sample) => self.mark_complete_if_unset(table_ref, sample),
             Err(first_error) => {
+                self.retries.fetch_add(1, Ordering::Relaxed);
                 let retry_size = (self.config.sample_size / 2).max(1);
                 let retry_config = self.config.clone().with_sample_size(retry_size);
 
@@ -148,6 +247,7 @@ fn skipped_sample(table_ref: TableRef<'_>, reason: String) -> TableSample {
         collected_at: chrono::Utc::now(),
         warnings: vec![reason.clone()],
         sample_status: Some(SampleStatus::Skipped { reason }),
+        column_types: Vec::new(),
     }
 }
 
@@ -159,7 +259,7 @@ mod tests {
         DatabaseSchema, DatabaseType, Result,
         adapters::{AdapterFeature, ConnectionConfig},
         error::DbSurveyorError,
-        models::{DatabaseInfo, SamplingStrategy},
+        models::{Column, DatabaseInfo, SamplingStrategy, UnifiedDataType},
     };
     use serde_json::json;
     use std::{collections::VecDeque, sync::Mutex};
@@ -167,6 +267,9 @@ mod tests {
     struct MockAdapter {
         responses: Mutex<VecDeque<Result<TableSample>>>,
         sample_sizes: Mutex<Vec<u32>>,
+        sampled_tables: Mutex<Vec<String>>,
+        delay: Option<Duration>,
+        config: ConnectionConfig,
     }
 
     impl MockAdapter {
@@ -174,6 +277,17 @@ fn new(responses: Vec<Result<TableSample>>) -> Self {
             Self {
                 responses: Mutex::new(VecDeque::from(responses)),
                 sample_sizes: Mutex::new(Vec::new()),
+                sampled_tables: Mutex::new(Vec::new()),
+                delay: None,
+                config: ConnectionConfig::default(),
+            }
+        }
+
+        fn slow(delay: Duration, table_timeout: Duration) -> Self {
+            Self {
+                delay: Some(delay),
+                config: ConnectionConfig::default().with_table_timeout(table_timeout),
+                ..Self::new(Vec::new())
             }
         }
 
@@ -194,18 +308,29 @@ async fn collect_schema(&self) -> Result<DatabaseSchema> {
 
         async fn sample_table(
             &self,
-            _table_ref: TableRef<'_>,
+            table_ref: TableRef<'_>,
             config: &SamplingConfig,
         ) -> Result<TableSample> {
             self.sample_sizes
                 .lock()
                 .expect("sample sizes lock")
                 .push(config.sample_size);
+            self.sampled_tables
+                .lock()
+                .expect("sampled tables lock")
+                .push(table_ref.table_name.to_string());
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
             self.responses
                 .lock()
                 .expect("responses lock")
                 .pop_front()
                 .expect("mock response missing")
+                .map(|sample| TableSample {
+                    table_name: table_ref.table_name.to_string(),
+                    ..sample
+                })
         }
 
         fn database_type(&self) -> DatabaseType {
@@ -217,22 +342,16 @@ fn supports_feature(&self, _feature: AdapterFeature) -> bool {
         }
 
         fn connection_config(&self) -> ConnectionConfig {
-            ConnectionConfig::default()
+            self.config.clone()
         }
     }
 
     fn test_table() -> Table {
-        Table {
-            name: "users".to_string(),
-            schema: Some("public".to_string()),
-            columns: Vec::new(),
-            primary_key: None,
-            foreign_keys: Vec::new(),
-            indexes: Vec::new(),
-            constraints: Vec::new(),
-            comment: None,
-            row_count: None,
-        }
+        Table::new("users".to_string(), Some("public".to_string()), Vec::new())
+    }
+
+    fn warning_messages(run: &SamplingRun) -> Vec<String> {
+        run.warnings.iter().map(|w| w.message.clone()).collect()
     }
 
     fn successful_sample(strategy: SamplingStrategy) -> TableSample {
@@ -246,6 +365,7 @@ fn successful_sample(strategy: SamplingStrategy) -> TableSample {
             collected_at: chrono::Utc::now(),
             warnings: Vec::new(),
             sample_status: None,
+            column_types: Vec::new(),
         }
     }
 
@@ -265,6 +385,7 @@ async fn test_orchestrator_success_path_marks_complete() {
             Some(SampleStatus::Complete)
         ));
         assert!(run.warnings.is_empty());
+        assert_eq!(run.retries, 0);
         assert_eq!(adapter.seen_sample_sizes(), vec![100]);
     }
 
@@ -282,6 +403,7 @@ async fn test_orchestrator_retry_success_marks_partial_retry() {
             .await;
 
         assert_eq!(adapter.seen_sample_sizes(), vec![100, 50]);
+        assert_eq!(run.retries, 1);
         assert_eq!(run.samples.len(), 1);
         assert!(matches!(
             run.samples[0].sample_status,
@@ -295,7 +417,7 @@ async fn test_orchestrator_retry_success_marks_partial_retry() {
                 .iter()
                 .any(|warning| warning.contains("retried with reduced limit 50"))
         );
-        assert_eq!(run.warnings, run.samples[0].warnings);
+        assert_eq!(warning_messages(&run), run.samples[0].warnings);
     }
 
     #[tokio::test]
@@ -319,7 +441,7 @@ async fn test_orchestrator_retry_failure_marks_skipped() {
             !run.samples[0].warnings.is_empty(),
             "skipped sample should include a reason warning"
         );
-        assert_eq!(run.warnings, run.samples[0].warnings);
+        assert_eq!(warning_messages(&run), run.samples[0].warnings);
     }
 
     #[tokio::test]
@@ -343,7 +465,7 @@ async fn test_orchestrator_unordered_fallback_adds_warning() {
                 .iter()
                 .any(|warning| warning.contains("random sampling fallback"))
         );
-        assert_eq!(run.warnings, run.samples[0].warnings);
+        assert_eq!(warning_messages(&run), run.samples[0].warnings);
     }
 
     #[tokio::test]
@@ -364,4 +486,166 @@ async fn test_orchestrator_preserves_adapter_supplied_status_on_first_success()
             Some(SampleStatus::Skipped { .. })
         ));
     }
+
+    #[tokio::test]
+    async fn test_orchestrator_table_timeout_skips_without_retry() {
+        let adapter = MockAdapter::slow(Duration::from_secs(5), Duration::from_millis(20));
+        let config = SamplingConfig::default().with_sample_size(100);
+        let run = SamplingOrchestrator::new(&adapter, &config)
+            .run(&[test_table()])
+            .await;
+
+        assert_eq!(adapter.seen_sample_sizes(), vec![100]);
+        assert_eq!(run.samples.len(), 1);
+        assert!(matches!(
+            run.samples[0].sample_status,
+            Some(SampleStatus::Skipped { .. })
+        ));
+        assert!(
+            run.warnings
+                .iter()
+                .any(|warning| warning.message.contains("timed out"))
+        );
+        assert_eq!(run.warnings[0].category, WarningCategory::Sampling);
+        assert_eq!(run.warnings[0].objects, vec!["public.users"]);
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_skips_oversized_tables_without_querying() {
+        let adapter = MockAdapter::new(Vec::new());
+        let config = SamplingConfig::default()
+            .with_max_table_rows(1_000_000)
+            .with_max_table_bytes(1 << 30);
+        let large = Table {
+            row_count: Some(5_000_000),
+            ..test_table()
+        };
+        let run = SamplingOrchestrator::new(&adapter, &config)
+            .run(&[large])
+            .await;
+
+        assert!(adapter.seen_sample_sizes().is_empty());
+        assert_eq!(run.samples[0].total_rows, Some(5_000_000));
+        match &run.samples[0].sample_status {
+            Some(SampleStatus::Skipped { reason }) => assert_eq!(
+                reason,
+                "Sampling table 'public.users' skipped: estimated 5000000 rows exceeds the sampling limit of 1000000 rows"
+            ),
+            other => panic!("expected Skipped, got {other:?}"),
+        }
+        assert_eq!(run.warnings[0].objects, vec!["public.users"]);
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_applies_sensitive_actions() {
+        let adapter = MockAdapter::new(vec![Ok(TableSample {
+            rows: vec![json!({"id": 1, "email": "a@example.com", "api_key": "secret"})],
+            ..successful_sample(SamplingStrategy::MostRecent { limit: 100 })
+        })]);
+        let config = SamplingConfig::default().with_sensitive_pattern_rules(
+            dbsurveyor_core::SensitivePatternRules::from_yaml(
+                "patterns:\n  - pattern: '(?i)(email|mail)'\n    description: Email\n    action: mask\n  - pattern: '(?i)api_key'\n    description: API key\n    action: skip-column\n",
+            )
+            .expect("valid rules"),
+        );
+        let run = SamplingOrchestrator::new(&adapter, &config)
+            .run(&[test_table()])
+            .await;
+
+        assert_eq!(
+            run.samples[0].rows,
+            vec![json!({"id": 1, "email": "[REDACTED]"})]
+        );
+        assert_eq!(run.samples[0].warnings.len(), 2);
+        assert_eq!(warning_messages(&run), run.samples[0].warnings);
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_reports_columns_left_out_of_the_query() {
+        // The adapter never returns api_key: it is left out of the query
+        let adapter = MockAdapter::new(vec![Ok(successful_sample(SamplingStrategy::MostRecent {
+            limit: 100,
+        }))]);
+        let config = SamplingConfig::default().with_sensitive_pattern_rules(
+            dbsurveyor_core::SensitivePatternRules::from_yaml(
+                "patterns:\n  - pattern: '(?i)api_key'\n    description: API key\n    action: skip-column\n",
+            )
+            .expect("valid rules"),
+        );
+        let column = |name: &str| {
+            Column::new(
+                name.to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        };
+        let users = Table {
+            columns: vec![column("id"), column("api_key")],
+            ..test_table()
+        };
+        let keys = Table {
+            name: "keys".to_string(),
+            columns: vec![column("api_key")],
+            ..test_table()
+        };
+        let run = SamplingOrchestrator::new(&adapter, &config)
+            .run(&[users, keys])
+            .await;
+
+        assert_eq!(adapter.seen_sample_sizes().len(), 1);
+        assert_eq!(run.samples[0].rows, vec![json!({"id": 1})]);
+        assert_eq!(
+            run.samples[0].warnings,
+            ["Column 'api_key' removed from samples (API key)"]
+        );
+        match &run.samples[1].sample_status {
+            Some(SampleStatus::Skipped { reason }) => assert_eq!(
+                reason,
+                "Sampling table 'public.keys' skipped: every column matches a skip-column pattern"
+            ),
+            other => panic!("expected Skipped, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shuffled_order_samples_randomly_but_returns_catalog_order() {
+        let names: Vec<String> = (0..30).map(|i| format!("t{i:02}")).collect();
+        let tables: Vec<Table> = names
+            .iter()
+            .map(|name| Table {
+                name: name.clone(),
+                ..test_table()
+            })
+            .collect();
+        let adapter = MockAdapter::new(
+            names
+                .iter()
+                .map(|_| {
+                    Ok(successful_sample(SamplingStrategy::MostRecent {
+                        limit: 100,
+                    }))
+                })
+                .collect(),
+        );
+        let config = SamplingConfig::default();
+        let run = SamplingOrchestrator::new(&adapter, &config)
+            .with_shuffled_order(true)
+            .run(&tables)
+            .await;
+
+        let sampled = adapter
+            .sampled_tables
+            .lock()
+            .expect("sampled tables lock")
+            .clone();
+        assert_ne!(
+            sampled, names,
+            "tables should not be sampled in catalog order"
+        );
+        let mut sorted = sampled;
+        sorted.sort();
+        assert_eq!(sorted, names);
+
+        let returned: Vec<_> = run.samples.iter().map(|s| s.table_name.clone()).collect();
+        assert_eq!(returned, names);
+    }
 }
@@ -0,0 +1,263 @@
+//! `self-test` subcommand: verifies this binary before a real collection.
+//!
+//! Transferring a binary to an air-gapped host can go wrong in ways that
+//! only show up mid-engagement (a truncated copy, a build without the
+//! expected features). The self-test seeds an in-memory SQLite database,
+//! runs the same collection pipeline as a real survey against it, then
+//! writes the survey again compressed and encrypted and reads every output
+//! back. Nothing leaves the process except files in a temporary directory,
+//! which is removed afterwards.
+
+use crate::Cli;
+use crate::metrics::RunMetrics;
+use crate::outcome::CollectionOutcome;
+use clap::Parser;
+use dbsurveyor_core::Result;
+use dbsurveyor_core::error::DbSurveyorError;
+use dbsurveyor_core::models::DatabaseSchema;
+use std::path::Path;
+
+/// Statements creating the fixture database.
+#[cfg(feature = "sqlite")]
+const FIXTURE_SQL: &[&str] = &[
+    "CREATE TABLE customers (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        region TEXT
+    )",
+    "CREATE TABLE orders (
+        id INTEGER PRIMARY KEY,
+        customer_id INTEGER NOT NULL REFERENCES customers(id),
+        total NUMERIC(10, 2),
+        placed_at TEXT DEFAULT CURRENT_TIMESTAMP
+    )",
+    "CREATE INDEX idx_orders_customer ON orders(customer_id)",
+    "CREATE VIEW order_totals AS
+        SELECT customer_id, SUM(total) AS total FROM orders GROUP BY customer_id",
+    "INSERT INTO customers (id, name, region) VALUES (1, 'Ada', 'north'), (2, 'Grace', 'south')",
+    "INSERT INTO orders (customer_id, total) VALUES (1, 12.50), (1, 3.00), (2, 40.25)",
+];
+
+/// Outcome of one check.
+enum Check {
+    Passed,
+    Skipped(&'static str),
+    Failed(DbSurveyorError),
+}
+
+/// Runs every check, printing one line each, and fails if any check failed.
+pub(crate) async fn run_self_test() -> Result<CollectionOutcome> {
+    let dir = tempfile::tempdir().map_err(|e| DbSurveyorError::Io {
+        context: "Failed to create self-test directory".to_string(),
+        source: e,
+    })?;
+    let plain = dir.path().join("self-test.dbsurveyor.json");
+
+    let mut failed = 0;
+    let mut report = |name: &str, check: Check| match check {
+        Check::Passed => status!("[OK] {}", name),
+        Check::Skipped(reason) => status!("[SKIP] {}: {}", name, reason),
+        Check::Failed(error) => {
+            failed += 1;
+            status!("[FAIL] {}: {}", name, error);
+        }
+    };
+
+    let collected = collect_fixture(&plain).await;
+    let schema = match collected.and_then(|()| read_survey(&plain)) {
+        Ok(schema) => {
+            report("collect, serialize, and validate", Check::Passed);
+            Some(schema)
+        }
+        Err(error) => {
+            report("collect, serialize, and validate", Check::Failed(error));
+            None
+        }
+    };
+
+    for (name, compress, encrypt) in [
+        ("compression round-trip", true, false),
+        ("encryption round-trip", false, true),
+        ("compressed encryption round-trip", true, true),
+    ] {
+        let check = match &schema {
+            None => Check::Skipped("no survey collected"),
+            Some(schema) => round_trip(schema, &plain, dir.path(), compress, encrypt).await,
+        };
+        report(name, check);
+    }
+
+    if failed > 0 {
+        return Err(DbSurveyorError::configuration(format!(
+            "Self-test failed: {} check(s) did not pass",
+            failed
+        )));
+    }
+    status!("Self-test passed");
+    Ok(CollectionOutcome::Success)
+}
+
+/// Options for self-test runs: the defaults, independent of the command
+/// line the self-test was started with.
+fn self_test_cli() -> Result<Cli> {
+    Cli::try_parse_from(["dbsurveyor-collect"])
+        .map_err(|e| DbSurveyorError::configuration(format!("Self-test options: {}", e)))
+}
+
+/// Seeds the fixture database and collects it to `output`.
+#[cfg(feature = "sqlite")]
+async fn collect_fixture(output: &Path) -> Result<()> {
+    // A shared-cache in-memory database lives as long as one connection to
+    // it is open, so the seeding pool keeps it alive during collection
+    let url = format!(
+        "sqlite:file:self-test-{}?mode=memory&cache=shared",
+        std::process::id()
+    );
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await
+        .map_err(|e| DbSurveyorError::collection_failed("Failed to open fixture database", e))?;
+    for statement in FIXTURE_SQL {
+        sqlx::query(statement).execute(&pool).await.map_err(|e| {
+            DbSurveyorError::collection_failed("Failed to seed fixture database", e)
+        })?;
+    }
+
+    let cli = self_test_cli()?;
+    let mut metrics = RunMetrics::start();
+    let outcome = crate::collect::collect_schema(&url, output, &cli, &mut metrics).await;
+    pool.close().await;
+    match outcome? {
+        CollectionOutcome::Success => Ok(()),
+        other => Err(DbSurveyorError::configuration(format!(
+            "collection of the fixture ended with exit code {}",
+            other.exit_code()
+        ))),
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+async fn collect_fixture(_output: &Path) -> Result<()> {
+    Err(DbSurveyorError::configuration(
+        "the SQLite driver is not compiled into this build",
+    ))
+}
+
+/// Loads a plain survey and checks that it holds the fixture.
+fn read_survey(path: &Path) -> Result<DatabaseSchema> {
+    let json: serde_json::Value = serde_json::from_slice(&read(path)?)
+        .map_err(|e| DbSurveyorError::collection_failed("Survey is not valid JSON", e))?;
+    dbsurveyor_core::validate_schema_output(&json)
+        .map_err(|e| DbSurveyorError::collection_failed("Survey validation failed", e))?;
+    let schema: DatabaseSchema = serde_json::from_value(json)
+        .map_err(|e| DbSurveyorError::collection_failed("Survey could not be loaded", e))?;
+
+    let orders = schema.tables.iter().find(|t| t.name == "orders");
+    let complete = schema.tables.len() == 2
+        && schema.views.len() == 1
+        && orders.is_some_and(|orders| orders.foreign_keys.len() == 1)
+        && schema
+            .samples
+            .as_deref()
+            .is_some_and(|samples| samples.iter().any(|s| !s.rows.is_empty()));
+    if !complete {
+        return Err(DbSurveyorError::configuration(
+            "survey is missing fixture tables, views, foreign keys, or samples",
+        ));
+    }
+    Ok(schema)
+}
+
+/// Writes `schema` compressed and/or encrypted and checks that reading it
+/// back yields the same survey as the plain output.
+async fn round_trip(
+    schema: &DatabaseSchema,
+    plain: &Path,
+    dir: &Path,
+    compress: bool,
+    encrypt: bool,
+) -> Check {
+    if compress && !cfg!(feature = "compression") {
+        return Check::Skipped("compression is not compiled into this build");
+    }
+    if encrypt && !cfg!(feature = "encryption") {
+        return Check::Skipped("encryption is not compiled into this build");
+    }
+
+    let result = async {
+        let mut cli = self_test_cli()?;
+        cli.compress = compress;
+        cli.encrypt = encrypt;
+        let password = encrypt.then(|| {
+            format!(
+                "self-test-{:016x}{:016x}",
+                rand::random::<u64>(),
+                rand::random::<u64>()
+            )
+        });
+        if let Some(password) = &password {
+            let password_file = dir.join("password");
+            write(&password_file, password.as_bytes())?;
+            cli.password_file = Some(password_file);
+        }
+
+        let output = dir.join(format!("round-trip-{}-{}.json", compress, encrypt));
+        let saved = crate::output::save_schema(schema, &output, &cli).await?;
+        let restored = decode(read(&saved)?, password.as_deref())?;
+        let expected: serde_json::Value = serde_json::from_slice(&read(plain)?)
+            .map_err(|e| DbSurveyorError::collection_failed("Survey is not valid JSON", e))?;
+        let restored: serde_json::Value = serde_json::from_slice(&restored).map_err(|e| {
+            DbSurveyorError::collection_failed("Restored survey is not valid JSON", e)
+        })?;
+        if restored != expected {
+            return Err(DbSurveyorError::configuration(format!(
+                "{} does not match the plain survey",
+                saved.display()
+            )));
+        }
+        Ok(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => Check::Passed,
+        Err(error) => Check::Failed(error),
+    }
+}
+
+/// Reverses encryption and compression of an output file.
+#[cfg_attr(
+    not(all(feature = "compression", feature = "encryption")),
+    allow(unused_mut, unused_variables)
+)]
+fn decode(mut data: Vec<u8>, password: Option<&str>) -> Result<Vec<u8>> {
+    #[cfg(feature = "encryption")]
+    if let Some(password) = password {
+        let encrypted = serde_json::from_slice(&data)
+            .map_err(|e| DbSurveyorError::collection_failed("Encrypted envelope", e))?;
+        data = dbsurveyor_core::security::encryption::decrypt_data(&encrypted, password)?;
+    }
+    #[cfg(feature = "compression")]
+    if data.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        data = zstd::decode_all(data.as_slice()).map_err(|e| DbSurveyorError::Io {
+            context: "Decompression failed".to_string(),
+            source: e,
+        })?;
+    }
+    Ok(data)
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| DbSurveyorError::Io {
+        context: format!("Failed to read {}", path.display()),
+        source: e,
+    })
+}
+
+fn write(path: &Path, data: &[u8]) -> Result<()> {
+    std::fs::write(path, data).map_err(|e| DbSurveyorError::Io {
+        context: format!("Failed to write {}", path.display()),
+        source: e,
+    })
+}
@@ -77,3 +77,151 @@ fn test_collect_list_subcommand() {
         "list output should mention supported databases"
     );
 }
+
+#[test]
+fn test_generate_fixture_subcommand() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let output_path = dir.path().join("synthetic.json");
+    let output = Command::new(bin_path())
+        .args(["--quiet", "generate-fixture", "--tables", "40"])
+        .args(["--columns-avg", "8", "--schemas", "2", "--output"])
+        .arg(&output_path)
+        .output()
+        .expect("failed to execute dbsurveyor-collect generate-fixture");
+
+    assert!(
+        output.status.success(),
+        "expected exit 0 for generate-fixture: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let survey: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&output_path).expect("failed to read generated survey"),
+    )
+    .expect("generated survey should be JSON");
+    let tables = survey["tables"].as_array().expect("tables array");
+    assert_eq!(tables.len(), 40);
+    assert!(tables.iter().any(|t| t["schema"] == "sales"));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_self_test_subcommand() {
+    let output = Command::new(bin_path())
+        .args(["--quiet", "self-test"])
+        .output()
+        .expect("failed to execute dbsurveyor-collect self-test");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "expected exit 0 for self-test: {}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("[OK] collect, serialize, and validate"));
+    assert!(!stdout.contains("[FAIL]"));
+    assert!(stdout.contains("Self-test passed"));
+}
+
+#[test]
+fn test_generate_fixture_to_stdout() {
+    let output = Command::new(bin_path())
+        .args(["generate-fixture", "--tables", "3", "--output", "-"])
+        .output()
+        .expect("failed to execute dbsurveyor-collect generate-fixture");
+
+    assert!(
+        output.status.success(),
+        "expected exit 0 for generate-fixture: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Logs and status lines stay off stdout so it can be piped
+    let survey: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be the survey JSON");
+    assert_eq!(survey["tables"].as_array().map(Vec::len), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Synthetic survey generated"));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_append_to_journal() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let journal_path = dir.path().join("engagement.journal.jsonl");
+    for _ in 0..2 {
+        // --sign keys the journal's hash chain
+        let output = Command::new(bin_path())
+            .env("DBSURVEYOR_SIGNING_KEY", "engagement-key")
+            .args(["--quiet", "--sign", "--append-to"])
+            .arg(&journal_path)
+            .args(["--database-url", "sqlite::memory:"])
+            .output()
+            .expect("failed to execute dbsurveyor-collect --append-to");
+        assert!(
+            output.status.success(),
+            "expected exit 0 for --append-to: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let limits = dbsurveyor_core::InputLimits::default();
+    assert!(dbsurveyor_core::journal::Journal::read(&journal_path, None, &limits).is_err());
+    let journal = dbsurveyor_core::journal::Journal::read(
+        &journal_path,
+        Some(b"engagement-key".as_slice()),
+        &limits,
+    )
+    .expect("valid journal");
+    let kinds: Vec<_> = journal.index().map(|header| header.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            dbsurveyor_core::journal::RecordKind::Full,
+            dbsurveyor_core::journal::RecordKind::Diff
+        ]
+    );
+    let latest = journal.latest().expect("records apply").expect("a survey");
+    dbsurveyor_core::initialize_schema_validator().expect("schema validator");
+    dbsurveyor_core::validate_schema_output(&latest).expect("reconstructed survey validates");
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_profile_flag_conflicting_with_explicit_option() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let config_path = dir.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "[profiles.ops.collect]\ncompress = true\nsample = 5\n\n\
+         [profiles.secure.collect]\nencrypt = true\n",
+    )
+    .expect("failed to write config");
+    let journal_path = dir.path().join("engagement.journal.jsonl");
+    let collect = |profile: &str| {
+        Command::new(bin_path())
+            .env(dbsurveyor_core::profile::CONFIG_ENV_VAR, &config_path)
+            .args(["--quiet", "--profile", profile, "--append-to"])
+            .arg(&journal_path)
+            .args(["--database-url", "sqlite::memory:"])
+            .output()
+            .expect("failed to execute dbsurveyor-collect --profile")
+    };
+
+    // --append-to conflicts with --compress, which the profile turns on
+    let output = collect("ops");
+    assert!(
+        output.status.success(),
+        "expected exit 0 for a profile with compress = true and --append-to: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(journal_path.exists());
+
+    // Dropping --encrypt would write a plaintext journal
+    let output = collect("secure");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("--encrypt, which conflicts with --append-to")
+    );
+}
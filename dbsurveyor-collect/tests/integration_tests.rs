@@ -99,6 +99,55 @@ async fn plain_json_output_round_trips() {
     assert_schema_json(&json);
 }
 
+#[tokio::test]
+async fn attached_databases_produce_server_schema() {
+    use sqlx::sqlite::SqliteConnectOptions;
+
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let url = create_test_database(dir.path()).await;
+
+    let archive_path = dir.path().join("archive.db");
+    let options = SqliteConnectOptions::new()
+        .filename(&archive_path)
+        .create_if_missing(true);
+    let pool = sqlx::SqlitePool::connect_with(options)
+        .await
+        .expect("failed to create attached sqlite database");
+    sqlx::query("CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT)")
+        .execute(&pool)
+        .await
+        .expect("failed to create events table");
+    pool.close().await;
+
+    let out = dir.path().join("schema.dbsurveyor.json");
+    let attach = format!("archive={}", archive_path.display());
+
+    let output = run_collector(
+        &[
+            "--database-url",
+            &url,
+            "--attach",
+            &attach,
+            "--output",
+            out.to_str().expect("non-UTF8 path"),
+        ],
+        &[],
+    );
+    assert_success(&output);
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&out).expect("output file missing"))
+            .expect("output is not valid JSON");
+    let databases = json["databases"]
+        .as_array()
+        .expect("server schema must list databases");
+    assert_eq!(databases.len(), 2);
+    assert_schema_json(&databases[0]);
+    assert_eq!(databases[1]["database_info"]["name"], "archive");
+    assert_eq!(databases[1]["tables"][0]["name"], "events");
+    assert_eq!(databases[1]["tables"][0]["schema"], "archive");
+}
+
 #[cfg(feature = "compression")]
 #[tokio::test]
 async fn compressed_output_appends_extension_and_round_trips() {
@@ -241,3 +290,33 @@ async fn combined_compressed_encrypted_output_round_trips() {
         serde_json::from_slice(&decompressed).expect("decompressed output is not valid JSON");
     assert_schema_json(&json);
 }
+
+#[tokio::test]
+async fn estimate_prints_plan_without_writing_output() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let url = create_test_database(dir.path()).await;
+    let out = dir.path().join("schema.dbsurveyor.json");
+
+    let output = run_collector(
+        &[
+            "--database-url",
+            &url,
+            "--output",
+            out.to_str().expect("non-UTF8 path"),
+            "--throttle",
+            "250",
+            "--estimate",
+        ],
+        &[],
+    );
+    assert_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Estimated run:"), "{stdout}");
+    assert!(
+        stdout.contains("Tables:           1 (1 to sample"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("throttle 250 ms"), "{stdout}");
+    assert!(!out.exists(), "--estimate must not write a survey");
+}
@@ -84,6 +84,7 @@ fn build_table_sample(rows: usize, cols: usize) -> TableSample {
         collected_at: chrono::Utc::now(),
         warnings: Vec::new(),
         sample_status: None,
+        column_types: Vec::new(),
     }
 }
 
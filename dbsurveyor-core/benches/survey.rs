@@ -0,0 +1,225 @@
+//! Criterion benchmarks for survey-level hot paths.
+//!
+//! Runs on synthetic schemas of increasing size and covers serialization,
+//! deserialization, canonical output, structural comparison of schemas and
+//! tenant databases, and classification (sensitive columns, inferred
+//! relationships, lint rules).
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use dbsurveyor_core::canonical::to_canonical_json;
+use dbsurveyor_core::dedup::deduplicate_tenants;
+use dbsurveyor_core::lint::{LintRule, lint_schema};
+use dbsurveyor_core::models::{CollectionMetadata, CollectionMode, DatabaseServerSchema};
+use dbsurveyor_core::validation::validate_and_parse_schema;
+use dbsurveyor_core::{
+    DatabaseSchema, DatabaseType, SamplingConfig, ServerInfo, SyntheticSchemaConfig,
+    generate_synthetic_schema, infer_relationships, initialize_schema_validator,
+};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Fixture sizes as (tables, average columns per table).
+const SIZES: [(usize, usize); 3] = [(50, 12), (500, 20), (2000, 30)];
+
+fn fixture(tables: usize, columns_avg: usize) -> DatabaseSchema {
+    generate_synthetic_schema(
+        &SyntheticSchemaConfig::default()
+            .with_tables(tables)
+            .with_columns_avg(columns_avg)
+            .with_schemas(3),
+    )
+}
+
+fn size_id((tables, columns_avg): (usize, usize)) -> String {
+    format!("{tables}t_{columns_avg}c")
+}
+
+/// Builds a multi-tenant server where every database has the same structure.
+fn tenant_server(tenants: usize, schema: &DatabaseSchema) -> DatabaseServerSchema {
+    let databases: Vec<DatabaseSchema> = (0..tenants)
+        .map(|i| {
+            let mut tenant = schema.clone();
+            tenant.database_info.name = format!("tenant_{i}");
+            for table in &mut tenant.tables {
+                table.row_count = Some(i as u64);
+            }
+            tenant
+        })
+        .collect();
+
+    DatabaseServerSchema::new(
+        ServerInfo {
+            server_type: DatabaseType::PostgreSQL,
+            version: "16.2".to_string(),
+            host: "localhost".to_string(),
+            port: Some(5432),
+            total_databases: tenants,
+            collected_databases: tenants,
+            system_databases_excluded: 0,
+            connection_user: "surveyor".to_string(),
+            has_superuser_privileges: false,
+            collection_mode: CollectionMode::MultiDatabase {
+                discovered: tenants,
+                collected: tenants,
+                failed: 0,
+            },
+            timezone: None,
+            role: None,
+        },
+        databases,
+        CollectionMetadata {
+            collected_at: schema.collection_metadata.collected_at,
+            collection_duration_ms: 0,
+            collector_version: "bench".to_string(),
+            warnings: Vec::new(),
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
+        },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Benchmark groups
+// ---------------------------------------------------------------------------
+
+/// Schema to JSON, plain and canonical.
+fn bench_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialization");
+
+    for size in SIZES {
+        let schema = fixture(size.0, size.1);
+        let value = serde_json::to_value(&schema).expect("serialize schema");
+        group.throughput(Throughput::Elements(size.0 as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("to_string", size_id(size)),
+            &schema,
+            |b, s| {
+                b.iter(|| serde_json::to_string(black_box(s)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("canonical", size_id(size)),
+            &value,
+            |b, v| {
+                b.iter(|| to_canonical_json(black_box(v)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// JSON to schema, with and without JSON Schema validation.
+fn bench_deserialization(c: &mut Criterion) {
+    initialize_schema_validator().expect("initialize schema validator");
+    let mut group = c.benchmark_group("deserialization");
+
+    for size in SIZES {
+        let json = serde_json::to_string(&fixture(size.0, size.1)).expect("serialize schema");
+        group.throughput(Throughput::Bytes(json.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("from_str", size_id(size)),
+            &json,
+            |b, j| {
+                b.iter(|| serde_json::from_str::<DatabaseSchema>(black_box(j)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("validated", size_id(size)),
+            &json,
+            |b, j| {
+                b.iter(|| validate_and_parse_schema(black_box(j)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Structural comparison: schema equality and tenant deduplication.
+fn bench_diffing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("diffing");
+
+    for size in SIZES {
+        let schema = fixture(size.0, size.1);
+        let other = schema.clone();
+        group.throughput(Throughput::Elements(size.0 as u64));
+
+        group.bench_function(BenchmarkId::new("schema_eq", size_id(size)), |b| {
+            b.iter(|| black_box(&schema) == black_box(&other));
+        });
+    }
+
+    let schema = fixture(200, 20);
+    for tenants in [10, 50] {
+        let server = tenant_server(tenants, &schema);
+        group.throughput(Throughput::Elements(tenants as u64));
+        group.bench_function(BenchmarkId::new("dedup_tenants", tenants), |b| {
+            b.iter_batched(
+                || server.clone(),
+                |mut server| deduplicate_tenants(&mut server),
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+/// Sensitive column classification, relationship inference, and linting.
+fn bench_classification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("classification");
+    let config = SamplingConfig::default();
+
+    for size in SIZES {
+        let schema = fixture(size.0, size.1);
+        let columns: Vec<&str> = schema
+            .tables
+            .iter()
+            .flat_map(|t| t.columns.iter().map(|c| c.name.as_str()))
+            .collect();
+
+        group.throughput(Throughput::Elements(columns.len() as u64));
+        group.bench_function(BenchmarkId::new("sensitive_columns", size_id(size)), |b| {
+            b.iter(|| {
+                columns
+                    .iter()
+                    .filter(|name| config.sensitive_action(black_box(name)).is_some())
+                    .count()
+            });
+        });
+
+        group.throughput(Throughput::Elements(size.0 as u64));
+        group.bench_function(
+            BenchmarkId::new("infer_relationships", size_id(size)),
+            |b| {
+                b.iter(|| infer_relationships(black_box(&schema)));
+            },
+        );
+        group.bench_function(BenchmarkId::new("lint", size_id(size)), |b| {
+            b.iter(|| lint_schema(black_box(&schema), &LintRule::ALL));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_serialization,
+    bench_deserialization,
+    bench_diffing,
+    bench_classification,
+);
+criterion_main!(benches);
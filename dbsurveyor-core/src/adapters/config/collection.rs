@@ -4,6 +4,9 @@
 //! database schema collection operations.
 
 use super::{ConnectionConfig, SamplingConfig};
+use crate::models::{
+    CollectionSettings, CollectionWarning, DatabaseSchema, SamplingSettings, WarningCategory,
+};
 use serde::{Deserialize, Serialize};
 
 /// Output format options for collected schema data.
@@ -18,6 +21,142 @@ pub enum OutputFormat {
     Encrypted,
 }
 
+/// Optional object types an adapter collects.
+///
+/// Tables, with their columns, primary keys, and foreign keys, are always
+/// collected. Adapters skip the catalog queries for unselected types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectSelection {
+    pub views: bool,
+    pub procedures: bool,
+    pub functions: bool,
+    pub triggers: bool,
+    pub indexes: bool,
+    pub constraints: bool,
+    pub custom_types: bool,
+}
+
+impl Default for ObjectSelection {
+    fn default() -> Self {
+        Self {
+            views: true,
+            procedures: true,
+            functions: true,
+            triggers: true,
+            indexes: true,
+            constraints: true,
+            custom_types: true,
+        }
+    }
+}
+
+impl ObjectSelection {
+    /// Removes objects of unselected types from a collected schema.
+    ///
+    /// Needed where one catalog query returns several object types at once,
+    /// such as batch collection of indexes and constraints.
+    pub fn apply(&self, schema: &mut DatabaseSchema) {
+        if !self.views {
+            schema.views.clear();
+        }
+        if !self.procedures {
+            schema.procedures.clear();
+        }
+        if !self.functions {
+            schema.functions.clear();
+        }
+        if !self.triggers {
+            schema.triggers.clear();
+        }
+        if !self.indexes {
+            schema.indexes.clear();
+            schema.tables.iter_mut().for_each(|t| t.indexes.clear());
+        }
+        if !self.constraints {
+            schema.constraints.clear();
+            schema.tables.iter_mut().for_each(|t| t.constraints.clear());
+        }
+        if !self.custom_types {
+            schema.custom_types.clear();
+        }
+    }
+}
+
+/// Default [`SourcePolicy::max_bytes`]: 64 KiB.
+pub const DEFAULT_MAX_SOURCE_BYTES: usize = 64 * 1024;
+
+/// Whether procedure, function, and trigger bodies are kept in the output.
+///
+/// Adapters always read routine definitions with the rest of the routine
+/// metadata; the policy decides what is persisted. By default output is
+/// metadata-only: names, parameters, return types, and languages are kept
+/// and bodies are dropped. View definitions are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePolicy {
+    /// Keep routine and trigger bodies
+    pub include: bool,
+    /// Bodies larger than this many bytes are dropped even when included
+    pub max_bytes: usize,
+}
+
+impl Default for SourcePolicy {
+    fn default() -> Self {
+        Self {
+            include: false,
+            max_bytes: DEFAULT_MAX_SOURCE_BYTES,
+        }
+    }
+}
+
+impl SourcePolicy {
+    /// Removes bodies the policy excludes from a collected schema, returning
+    /// one warning per body dropped for exceeding the size limit.
+    pub fn apply(&self, schema: &mut DatabaseSchema) -> Vec<CollectionWarning> {
+        let procedures = schema.procedures.iter_mut().map(|routine| {
+            let name = qualified(routine.schema.as_deref(), &routine.name);
+            (format!("procedure {}", name), &mut routine.definition)
+        });
+        let functions = schema.functions.iter_mut().map(|routine| {
+            let name = qualified(routine.schema.as_deref(), &routine.name);
+            (format!("function {}", name), &mut routine.definition)
+        });
+        let triggers = schema.triggers.iter_mut().map(|trigger| {
+            let name = qualified(trigger.schema.as_deref(), &trigger.name);
+            (format!("trigger {}", name), &mut trigger.definition)
+        });
+
+        let mut warnings = Vec::new();
+        for (object, definition) in procedures.chain(functions).chain(triggers) {
+            let Some(body) = definition else {
+                continue;
+            };
+            if self.include && body.len() > self.max_bytes {
+                warnings.push(
+                    CollectionWarning::new(
+                        WarningCategory::ObjectFailure,
+                        format!(
+                            "Source larger than {} bytes was not included",
+                            self.max_bytes
+                        ),
+                    )
+                    .with_object(object),
+                );
+            }
+            if !self.include || body.len() > self.max_bytes {
+                *definition = None;
+            }
+        }
+        warnings
+    }
+}
+
+fn qualified(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
 /// Configuration for database schema collection.
 ///
 /// This struct controls all aspects of database schema collection including
@@ -45,7 +184,8 @@ pub struct CollectionConfig {
     pub sampling: SamplingConfig,
     /// Whether to include system/internal databases
     pub include_system_databases: bool,
-    /// List of database names to exclude from collection
+    /// Database names or glob patterns (`test_*`) to exclude from
+    /// multi-database collection
     pub exclude_databases: Vec<String>,
     /// Whether to collect database views
     pub include_views: bool,
@@ -119,6 +259,59 @@ pub fn validate(&self) -> crate::Result<()> {
         Ok(())
     }
 
+    /// Object types selected by the `include_*` fields.
+    pub fn object_selection(&self) -> ObjectSelection {
+        ObjectSelection {
+            views: self.include_views,
+            procedures: self.include_procedures,
+            functions: self.include_functions,
+            triggers: self.include_triggers,
+            indexes: self.include_indexes,
+            constraints: self.include_constraints,
+            custom_types: self.include_custom_types,
+        }
+    }
+
+    /// Settings to record in the survey, with `sampling` the sampling
+    /// configuration when rows were sampled. Connection details are left out.
+    pub fn settings(&self, sampling: Option<&SamplingConfig>) -> CollectionSettings {
+        let selection = self.object_selection();
+        let excluded_objects = [
+            ("views", selection.views),
+            ("procedures", selection.procedures),
+            ("functions", selection.functions),
+            ("triggers", selection.triggers),
+            ("indexes", selection.indexes),
+            ("constraints", selection.constraints),
+            ("custom_types", selection.custom_types),
+        ]
+        .into_iter()
+        .filter(|(_, included)| !included)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+        CollectionSettings {
+            include_system_databases: self.include_system_databases,
+            exclude_databases: self.exclude_databases.clone(),
+            excluded_objects,
+            read_only: self.connection.read_only,
+            immutable: self.connection.immutable,
+            pooler_mode: self.connection.pooler_mode,
+            max_concurrent_queries: self.max_concurrent_queries,
+            query_timeout_secs: self.connection.query_timeout.as_secs(),
+            table_timeout_secs: self.connection.table_timeout.map(|t| t.as_secs()),
+            session_limits: self.connection.session_limits.clone(),
+            sampling: sampling.map(|sampling| SamplingSettings {
+                sample_size: sampling.sample_size,
+                throttle_ms: sampling.throttle_ms,
+                query_timeout_secs: sampling.query_timeout_secs,
+                max_table_rows: sampling.max_table_rows,
+                max_table_bytes: sampling.max_table_bytes,
+                sensitive_patterns: sampling.sensitive_detection_patterns.clone(),
+            }),
+        }
+    }
+
     /// Creates a new collection config with safe defaults.
     pub fn new() -> Self {
         Self::default()
@@ -227,12 +420,31 @@ pub fn with_encryption(mut self, enabled: bool) -> Self {
         self
     }
 
-    /// Builder method to exclude specific databases.
+    /// Builder method to exclude a database name or glob pattern.
     #[must_use]
     pub fn exclude_database(mut self, database: impl Into<String>) -> Self {
         self.exclude_databases.push(database.into());
         self
     }
+
+    /// Builder method to include/exclude system databases.
+    #[must_use]
+    pub fn with_system_databases(mut self, include: bool) -> Self {
+        self.include_system_databases = include;
+        self
+    }
+
+    /// Whether multi-database collection should skip `database`.
+    ///
+    /// System databases are skipped unless `include_system_databases` is set;
+    /// any database matching an `exclude_databases` pattern is skipped.
+    pub fn excludes_database(&self, database: &str, is_system_database: bool) -> bool {
+        (is_system_database && !self.include_system_databases)
+            || self
+                .exclude_databases
+                .iter()
+                .any(|pattern| glob_match::glob_match(pattern, database))
+    }
 }
 
 #[cfg(test)]
@@ -292,6 +504,87 @@ fn test_collection_config_builder() {
         assert!(config.exclude_databases.contains(&"system_db".to_string()));
     }
 
+    #[test]
+    fn test_object_selection_clears_unselected_types() {
+        use crate::models::{DatabaseInfo, Index};
+
+        let selection = CollectionConfig::new()
+            .with_indexes(false)
+            .with_triggers(false)
+            .object_selection();
+        assert!(selection.views);
+        assert!(!selection.indexes);
+        assert_eq!(
+            CollectionConfig::new().object_selection(),
+            ObjectSelection::default()
+        );
+
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("db".to_string()));
+        schema.indexes.push(Index {
+            name: "idx".to_string(),
+            table_name: "t".to_string(),
+            schema: None,
+            columns: Vec::new(),
+            is_unique: false,
+            is_primary: false,
+            index_type: None,
+            options: None,
+        });
+        selection.apply(&mut schema);
+        assert!(schema.indexes.is_empty());
+    }
+
+    #[test]
+    fn test_settings_omit_connection_details() {
+        let config = CollectionConfig::new()
+            .with_connection(
+                ConnectionConfig::new("db.internal".to_string())
+                    .with_username("admin".to_string())
+                    .with_table_timeout(std::time::Duration::from_secs(90)),
+            )
+            .with_triggers(false)
+            .exclude_database("test_*");
+        let sampling = SamplingConfig::default()
+            .with_sample_size(25)
+            .with_throttle_ms(50);
+
+        let settings = config.settings(Some(&sampling));
+        assert_eq!(settings.excluded_objects, ["triggers"]);
+        assert_eq!(settings.exclude_databases, ["test_*"]);
+        assert!(settings.read_only);
+        assert_eq!(settings.table_timeout_secs, Some(90));
+        let recorded = settings.sampling.as_ref().expect("sampling recorded");
+        assert_eq!(recorded.sample_size, 25);
+        assert_eq!(recorded.throttle_ms, Some(50));
+        assert_eq!(
+            recorded.sensitive_patterns,
+            sampling.sensitive_detection_patterns
+        );
+
+        let json = serde_json::to_string(&settings).expect("serializes");
+        assert!(!json.contains("db.internal"), "{json}");
+        assert!(!json.contains("admin"), "{json}");
+        assert!(config.settings(None).sampling.is_none());
+    }
+
+    #[test]
+    fn test_excludes_database() {
+        let config = CollectionConfig::new()
+            .exclude_database("test_*")
+            .exclude_database("legacy");
+
+        assert!(config.excludes_database("test_orders", false));
+        assert!(config.excludes_database("legacy", false));
+        assert!(!config.excludes_database("legacy_archive", false));
+        assert!(!config.excludes_database("orders", false));
+        assert!(config.excludes_database("mysql", true));
+        assert!(
+            !config
+                .with_system_databases(true)
+                .excludes_database("mysql", true)
+        );
+    }
+
     #[test]
     fn test_with_max_concurrent_queries_validation() {
         // Valid value should succeed
@@ -308,6 +601,46 @@ fn test_with_max_concurrent_queries_validation() {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_source_policy_keeps_bodies_within_limit() {
+        use crate::models::{DatabaseInfo, Procedure};
+
+        let routine = |name: &str, body: &str| Procedure {
+            name: name.to_string(),
+            schema: Some("public".to_string()),
+            definition: Some(body.to_string()),
+            parameters: Vec::new(),
+            return_type: None,
+            language: Some("sql".to_string()),
+            comment: None,
+        };
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("app".to_string()));
+        schema.functions = vec![
+            routine("small", "SELECT 1"),
+            routine("large", &"x".repeat(20)),
+        ];
+
+        let mut metadata_only = schema.clone();
+        assert!(SourcePolicy::default().apply(&mut metadata_only).is_empty());
+        assert!(
+            metadata_only
+                .functions
+                .iter()
+                .all(|f| f.definition.is_none())
+        );
+        assert_eq!(metadata_only.functions[0].language.as_deref(), Some("sql"));
+
+        let policy = SourcePolicy {
+            include: true,
+            max_bytes: 16,
+        };
+        let warnings = policy.apply(&mut schema);
+        assert_eq!(schema.functions[0].definition.as_deref(), Some("SELECT 1"));
+        assert_eq!(schema.functions[1].definition, None);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].objects, ["function public.large"]);
+    }
+
     #[test]
     fn test_output_format_default() {
         assert_eq!(OutputFormat::default(), OutputFormat::Json);
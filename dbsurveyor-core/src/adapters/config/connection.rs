@@ -3,9 +3,92 @@
 //! This module provides the `ConnectionConfig` struct for configuring
 //! database connections with security-focused defaults.
 
+use super::SessionLimits;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+/// Smallest `max_staleness` MongoDB accepts.
+pub const MIN_MAX_STALENESS: Duration = Duration::from_secs(90);
+
+/// Replica set members a MongoDB read may be routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReadPreferenceMode {
+    /// Only the primary
+    Primary,
+    /// The primary, or a secondary when it is unavailable
+    PrimaryPreferred,
+    /// Only secondaries
+    Secondary,
+    /// A secondary, or the primary when none is available
+    SecondaryPreferred,
+    /// The member with the lowest latency
+    Nearest,
+}
+
+/// Read preference for MongoDB collection, so the survey can be directed at
+/// secondaries instead of a production primary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadPreferenceConfig {
+    pub mode: ReadPreferenceMode,
+    /// Tag sets tried in order until one matches a member; an empty set
+    /// matches any member
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_sets: Vec<BTreeMap<String, String>>,
+    /// Skip secondaries lagging the primary by more than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_staleness: Option<Duration>,
+}
+
+impl ReadPreferenceConfig {
+    /// A read preference with no tag sets or staleness limit.
+    pub fn new(mode: ReadPreferenceMode) -> Self {
+        Self {
+            mode,
+            tag_sets: Vec::new(),
+            max_staleness: None,
+        }
+    }
+
+    /// Checks the combination is one MongoDB accepts.
+    ///
+    /// # Errors
+    /// Returns a configuration error if tag sets or a staleness limit are
+    /// given with `primary`, or the staleness limit is under 90 seconds.
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.mode == ReadPreferenceMode::Primary
+            && (!self.tag_sets.is_empty() || self.max_staleness.is_some())
+        {
+            return Err(crate::error::DbSurveyorError::configuration(
+                "read preference tags and max staleness cannot be used with primary",
+            ));
+        }
+        if self.max_staleness.is_some_and(|s| s < MIN_MAX_STALENESS) {
+            return Err(crate::error::DbSurveyorError::configuration(format!(
+                "max staleness must be at least {} seconds",
+                MIN_MAX_STALENESS.as_secs()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Which system catalogs PostgreSQL collection reads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogMode {
+    /// `pg_catalog`, falling back to `information_schema` alone when the
+    /// role is denied access to it
+    #[default]
+    Auto,
+    /// `pg_catalog` only; a denied catalog fails collection
+    Native,
+    /// The ANSI `information_schema` views only, for services that block
+    /// `pg_catalog`. Indexes, comments, sizes, and storage are not collected.
+    Ansi,
+}
+
 /// Configuration for database connections.
 ///
 /// # Security
@@ -37,6 +120,10 @@ pub struct ConnectionConfig {
     pub connect_timeout: Duration,
     /// Query timeout duration
     pub query_timeout: Duration,
+    /// Time limit for collecting (or sampling) a single table. A table that
+    /// exceeds it is skipped and recorded as a failure; `None` disables the limit.
+    #[serde(default)]
+    pub table_timeout: Option<Duration>,
     /// Maximum number of connections in pool
     pub max_connections: u32,
     /// Minimum number of idle connections to maintain
@@ -47,6 +134,24 @@ pub struct ConnectionConfig {
     pub max_lifetime: Option<Duration>,
     /// Whether to enforce read-only mode
     pub read_only: bool,
+    /// Open file databases as immutable (SQLite `immutable=1`): no locking,
+    /// journal recovery, or WAL checkpointing. Ignored by server engines.
+    #[serde(default)]
+    pub immutable: bool,
+    /// Connect through a transaction-pooling proxy (PgBouncer, ProxySQL):
+    /// apply settings per transaction instead of per session.
+    #[serde(default)]
+    pub pooler_mode: bool,
+    /// MongoDB read preference, overriding any in the connection string.
+    /// Ignored by other engines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_preference: Option<ReadPreferenceConfig>,
+    /// Engine-specific session limits applied to every connection
+    #[serde(default, skip_serializing_if = "SessionLimits::is_empty")]
+    pub session_limits: SessionLimits,
+    /// PostgreSQL system catalogs to collect from. Ignored by other engines.
+    #[serde(default)]
+    pub catalog_mode: CatalogMode,
 }
 
 impl Default for ConnectionConfig {
@@ -58,11 +163,17 @@ fn default() -> Self {
             username: None,
             connect_timeout: Duration::from_secs(30),
             query_timeout: Duration::from_secs(30),
+            table_timeout: None,
             max_connections: 10,
             min_idle_connections: 2,
             idle_timeout: Some(Duration::from_secs(600)), // 10 minutes
             max_lifetime: Some(Duration::from_secs(3600)), // 1 hour
             read_only: true,
+            immutable: false,
+            pooler_mode: false,
+            read_preference: None,
+            session_limits: SessionLimits::default(),
+            catalog_mode: CatalogMode::default(),
         }
     }
 }
@@ -126,6 +237,18 @@ pub fn validate(&self) -> crate::Result<()> {
             ));
         }
 
+        if self.table_timeout.is_some_and(|t| t.is_zero()) {
+            return Err(crate::error::DbSurveyorError::configuration(
+                "table_timeout must be greater than 0",
+            ));
+        }
+
+        if let Some(read_preference) = &self.read_preference {
+            read_preference.validate()?;
+        }
+
+        self.session_limits.validate()?;
+
         Ok(())
     }
 
@@ -232,6 +355,48 @@ pub fn with_min_idle_connections(mut self, min_idle: u32) -> Self {
         self.min_idle_connections = min_idle;
         self
     }
+
+    /// Builder method to set the per-table collection timeout.
+    #[must_use]
+    pub fn with_table_timeout(mut self, timeout: Duration) -> Self {
+        self.table_timeout = Some(timeout);
+        self
+    }
+
+    /// Builder method to open file databases as immutable.
+    #[must_use]
+    pub fn with_immutable(mut self, immutable: bool) -> Self {
+        self.immutable = immutable;
+        self
+    }
+
+    /// Builder method to connect through a transaction-pooling proxy.
+    #[must_use]
+    pub fn with_pooler_mode(mut self, pooler_mode: bool) -> Self {
+        self.pooler_mode = pooler_mode;
+        self
+    }
+
+    /// Builder method to choose the PostgreSQL system catalogs to read.
+    #[must_use]
+    pub fn with_catalog_mode(mut self, catalog_mode: CatalogMode) -> Self {
+        self.catalog_mode = catalog_mode;
+        self
+    }
+
+    /// Builder method to set the MongoDB read preference.
+    #[must_use]
+    pub fn with_read_preference(mut self, read_preference: ReadPreferenceConfig) -> Self {
+        self.read_preference = Some(read_preference);
+        self
+    }
+
+    /// Builder method to set engine-specific session limits.
+    #[must_use]
+    pub fn with_session_limits(mut self, session_limits: SessionLimits) -> Self {
+        self.session_limits = session_limits;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +442,31 @@ fn test_connection_config_validation() {
             ..Default::default()
         };
         assert!(config.validate().is_err());
+
+        // Zero table timeout should fail
+        let config = ConnectionConfig::default().with_table_timeout(Duration::ZERO);
+        assert!(config.validate().is_err());
+        let config = ConnectionConfig::default().with_table_timeout(Duration::from_secs(5));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_read_preference_validation() {
+        let mut read_preference = ReadPreferenceConfig::new(ReadPreferenceMode::SecondaryPreferred);
+        read_preference
+            .tag_sets
+            .push(BTreeMap::from([("dc".to_string(), "east".to_string())]));
+        read_preference.max_staleness = Some(Duration::from_secs(120));
+        let config = ConnectionConfig::default().with_read_preference(read_preference.clone());
+        assert!(config.validate().is_ok());
+
+        read_preference.max_staleness = Some(Duration::from_secs(30));
+        assert!(read_preference.validate().is_err());
+
+        read_preference.max_staleness = None;
+        read_preference.mode = ReadPreferenceMode::Primary;
+        let config = ConnectionConfig::default().with_read_preference(read_preference);
+        assert!(config.validate().is_err());
     }
 
     #[test]
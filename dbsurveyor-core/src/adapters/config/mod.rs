@@ -2,8 +2,11 @@
 //!
 //! This module contains all configuration structures used by database adapters:
 //! - `ConnectionConfig`: Database connection settings
+//! - `SessionLimits`: Per-engine session resource limits
 //! - `SamplingConfig`: Data sampling configuration
 //! - `CollectionConfig`: Schema collection settings
+//! - `ObjectSelection`: Optional object types to collect
+//! - `SourcePolicy`: Whether routine bodies are persisted
 //! - `OutputFormat`: Output format options
 //!
 //! # Security
@@ -13,7 +16,16 @@
 mod collection;
 mod connection;
 mod sampling;
+mod session_limits;
 
-pub use collection::{CollectionConfig, OutputFormat};
-pub use connection::ConnectionConfig;
-pub use sampling::{MAX_SAMPLE_SIZE, SamplingConfig, SensitivePattern};
+pub use collection::{
+    CollectionConfig, DEFAULT_MAX_SOURCE_BYTES, ObjectSelection, OutputFormat, SourcePolicy,
+};
+pub use connection::{
+    CatalogMode, ConnectionConfig, MIN_MAX_STALENESS, ReadPreferenceConfig, ReadPreferenceMode,
+};
+pub use sampling::{
+    MASKED_VALUE, MAX_SAMPLE_SIZE, SamplingConfig, SensitiveAction, SensitivePattern,
+    SensitivePatternRules,
+};
+pub use session_limits::{MySqlLimits, PostgresLimits, SessionLimits, SqlServerLimits};
@@ -5,30 +5,121 @@
 
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
 
 /// Maximum allowed sample size to prevent OOM from unbounded LIMIT clauses.
 pub const MAX_SAMPLE_SIZE: u32 = 10_000;
 
+/// Replacement for sampled values of columns with the
+/// [`SensitiveAction::Mask`] action.
+pub const MASKED_VALUE: &str = "[REDACTED]";
+
+/// What happens to sampled values of a column matching a sensitive pattern.
+///
+/// Ordered from least to most restrictive; when several patterns match a
+/// column, the most restrictive action applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SensitiveAction {
+    /// Keep the values and only warn about the column
+    #[default]
+    Warn,
+    /// Replace non-null values with [`MASKED_VALUE`]
+    Mask,
+    /// Remove the column from sampled rows
+    SkipColumn,
+}
+
 /// Pattern for detecting sensitive data fields.
 ///
 /// Used to identify columns that may contain sensitive information
 /// such as passwords, emails, or social security numbers.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SensitivePattern {
     /// Regex pattern to match column names
     pub pattern: String,
     /// Human-readable description of what was detected
     pub description: String,
+    /// Action applied to sampled values of matching columns
+    #[serde(default)]
+    pub action: SensitiveAction,
 }
 
 impl SensitivePattern {
-    /// Creates a new sensitive pattern.
+    /// Creates a new sensitive pattern that only warns.
     pub fn new(pattern: impl Into<String>, description: impl Into<String>) -> Self {
         Self {
             pattern: pattern.into(),
             description: description.into(),
+            action: SensitiveAction::Warn,
         }
     }
+
+    /// Builder method to set the action for matching columns.
+    #[must_use]
+    pub fn with_action(mut self, action: SensitiveAction) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+/// Sensitive pattern rules loaded from a YAML file.
+///
+/// ```yaml
+/// # Drop the built-in patterns instead of extending them (default: false)
+/// replace_defaults: false
+/// patterns:
+///   - pattern: '(?i)api_?key'
+///     description: API key detected
+///     action: skip-column   # warn (default), mask, or skip-column
+/// ```
+///
+/// A pattern whose regex equals a built-in pattern overrides it, which is
+/// how a built-in pattern's action is changed.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SensitivePatternRules {
+    /// Whether the built-in patterns are dropped
+    #[serde(default)]
+    pub replace_defaults: bool,
+    /// Additional or overriding patterns
+    #[serde(default)]
+    pub patterns: Vec<SensitivePattern>,
+}
+
+impl SensitivePatternRules {
+    /// Parses and validates a rules file.
+    ///
+    /// # Errors
+    /// Returns a configuration error if the YAML is malformed or any
+    /// pattern is not a valid regex.
+    pub fn from_yaml(content: &str) -> crate::Result<Self> {
+        let rules: Self = serde_norway::from_str(content).map_err(|e| {
+            crate::error::DbSurveyorError::configuration(format!(
+                "invalid sensitive patterns file: {e}"
+            ))
+        })?;
+
+        for pattern in &rules.patterns {
+            Regex::new(&pattern.pattern).map_err(|e| {
+                crate::error::DbSurveyorError::configuration(format!(
+                    "invalid sensitive pattern '{}': {e}",
+                    pattern.pattern
+                ))
+            })?;
+        }
+
+        Ok(rules)
+    }
+}
+
+/// A sensitive pattern with its regex compiled.
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledPattern {
+    pub(crate) regex: Regex,
+    pub(crate) description: String,
+    pub(crate) action: SensitiveAction,
 }
 
 /// Configuration for data sampling.
@@ -49,12 +140,16 @@ pub struct SamplingConfig {
     pub timestamp_columns: Vec<String>,
     /// Patterns for detecting sensitive data fields
     pub sensitive_detection_patterns: Vec<SensitivePattern>,
-    /// Pre-compiled regex patterns paired with their description.
+    /// Tables whose estimated row count exceeds this are not sampled
+    pub max_table_rows: Option<u64>,
+    /// Tables whose estimated size in bytes exceeds this are not sampled
+    pub max_table_bytes: Option<u64>,
+    /// Pre-compiled regex patterns.
     ///
-    /// Each entry is `(compiled_regex, description)`. Built from
-    /// `sensitive_detection_patterns` to avoid recompiling on every row.
+    /// Built from `sensitive_detection_patterns` to avoid recompiling on
+    /// every row.
     #[serde(skip)]
-    pub(crate) compiled_patterns: Vec<(Regex, String)>,
+    pub(crate) compiled_patterns: Vec<CompiledPattern>,
 }
 
 impl<'de> Deserialize<'de> for SamplingConfig {
@@ -71,6 +166,10 @@ struct Raw {
             warn_sensitive: bool,
             timestamp_columns: Vec<String>,
             sensitive_detection_patterns: Vec<SensitivePattern>,
+            #[serde(default)]
+            max_table_rows: Option<u64>,
+            #[serde(default)]
+            max_table_bytes: Option<u64>,
         }
 
         let raw = Raw::deserialize(deserializer)?;
@@ -82,21 +181,27 @@ struct Raw {
             warn_sensitive: raw.warn_sensitive,
             timestamp_columns: raw.timestamp_columns,
             sensitive_detection_patterns: raw.sensitive_detection_patterns,
+            max_table_rows: raw.max_table_rows,
+            max_table_bytes: raw.max_table_bytes,
             compiled_patterns,
         })
     }
 }
 
-/// Compiles a list of [`SensitivePattern`]s into `(Regex, description)` pairs.
+/// Compiles a list of [`SensitivePattern`]s.
 ///
 /// Invalid patterns are logged as warnings and skipped rather than
 /// causing a hard failure, which also eliminates any ReDoS risk from
 /// malformed user-supplied patterns.
-fn compile_sensitive_patterns(patterns: &[SensitivePattern]) -> Vec<(Regex, String)> {
+fn compile_sensitive_patterns(patterns: &[SensitivePattern]) -> Vec<CompiledPattern> {
     patterns
         .iter()
         .filter_map(|p| match Regex::new(&p.pattern) {
-            Ok(regex) => Some((regex, p.description.clone())),
+            Ok(regex) => Some(CompiledPattern {
+                regex,
+                description: p.description.clone(),
+                action: p.action,
+            }),
             Err(e) => {
                 tracing::warn!("Skipping invalid sensitive pattern '{}': {e}", p.pattern);
                 None
@@ -108,18 +213,12 @@ fn compile_sensitive_patterns(patterns: &[SensitivePattern]) -> Vec<(Regex, Stri
 impl Default for SamplingConfig {
     fn default() -> Self {
         let sensitive_detection_patterns = vec![
-            SensitivePattern {
-                pattern: r"(?i)(password|passwd|pwd)".to_string(),
-                description: "Password field detected".to_string(),
-            },
-            SensitivePattern {
-                pattern: r"(?i)(email|mail)".to_string(),
-                description: "Email field detected".to_string(),
-            },
-            SensitivePattern {
-                pattern: r"(?i)(ssn|social_security)".to_string(),
-                description: "Social Security Number field detected".to_string(),
-            },
+            SensitivePattern::new(r"(?i)(password|passwd|pwd)", "Password field detected"),
+            SensitivePattern::new(r"(?i)(email|mail)", "Email field detected"),
+            SensitivePattern::new(
+                r"(?i)(ssn|social_security)",
+                "Social Security Number field detected",
+            ),
         ];
         let compiled_patterns = compile_sensitive_patterns(&sensitive_detection_patterns);
         Self {
@@ -134,6 +233,8 @@ fn default() -> Self {
                 "timestamp".to_string(),
             ],
             sensitive_detection_patterns,
+            max_table_rows: None,
+            max_table_bytes: None,
             compiled_patterns,
         }
     }
@@ -215,6 +316,43 @@ pub fn with_sensitive_warnings(mut self, enabled: bool) -> Self {
         self
     }
 
+    /// Builder method to skip sampling tables with more estimated rows.
+    #[must_use]
+    pub fn with_max_table_rows(mut self, rows: u64) -> Self {
+        self.max_table_rows = Some(rows);
+        self
+    }
+
+    /// Builder method to skip sampling tables with a larger estimated size.
+    #[must_use]
+    pub fn with_max_table_bytes(mut self, bytes: u64) -> Self {
+        self.max_table_bytes = Some(bytes);
+        self
+    }
+
+    /// Returns why a table with the given size estimates must not be
+    /// sampled, or `None` when it is within the configured limits.
+    ///
+    /// A missing estimate never exceeds a limit, since engines without
+    /// statistics would otherwise never be sampled.
+    pub fn oversize_reason(&self, rows: Option<u64>, bytes: Option<u64>) -> Option<String> {
+        if let (Some(limit), Some(rows)) = (self.max_table_rows, rows)
+            && rows > limit
+        {
+            return Some(format!(
+                "estimated {rows} rows exceeds the sampling limit of {limit} rows"
+            ));
+        }
+        if let (Some(limit), Some(bytes)) = (self.max_table_bytes, bytes)
+            && bytes > limit
+        {
+            return Some(format!(
+                "estimated {bytes} bytes exceeds the sampling limit of {limit} bytes"
+            ));
+        }
+        None
+    }
+
     /// Adds a custom sensitive pattern.
     ///
     /// The pattern is compiled immediately and added to `compiled_patterns`.
@@ -225,8 +363,11 @@ pub fn with_sensitive_warnings(mut self, enabled: bool) -> Self {
     pub fn add_sensitive_pattern(mut self, pattern: SensitivePattern) -> Self {
         match Regex::new(&pattern.pattern) {
             Ok(regex) => {
-                self.compiled_patterns
-                    .push((regex, pattern.description.clone()));
+                self.compiled_patterns.push(CompiledPattern {
+                    regex,
+                    description: pattern.description.clone(),
+                    action: pattern.action,
+                });
                 self.sensitive_detection_patterns.push(pattern);
             }
             Err(e) => {
@@ -246,6 +387,127 @@ pub fn add_sensitive_pattern(mut self, pattern: SensitivePattern) -> Self {
     pub fn recompile_patterns(&mut self) {
         self.compiled_patterns = compile_sensitive_patterns(&self.sensitive_detection_patterns);
     }
+
+    /// Applies sensitive pattern rules loaded from a file.
+    ///
+    /// Rule patterns replace built-in patterns with the same regex and are
+    /// otherwise appended; with `replace_defaults`, only rule patterns remain.
+    #[must_use]
+    pub fn with_sensitive_pattern_rules(mut self, rules: SensitivePatternRules) -> Self {
+        if rules.replace_defaults {
+            self.sensitive_detection_patterns.clear();
+        }
+        for pattern in rules.patterns {
+            match self
+                .sensitive_detection_patterns
+                .iter_mut()
+                .find(|existing| existing.pattern == pattern.pattern)
+            {
+                Some(existing) => *existing = pattern,
+                None => self.sensitive_detection_patterns.push(pattern),
+            }
+        }
+        self.recompile_patterns();
+        self
+    }
+
+    /// Returns the most restrictive action of the patterns matching a
+    /// column name, with the matching pattern's description.
+    pub fn sensitive_action(&self, column_name: &str) -> Option<(SensitiveAction, &str)> {
+        let name_lower = column_name.to_lowercase();
+        self.compiled_patterns
+            .iter()
+            .filter(|pattern| pattern.regex.is_match(&name_lower))
+            .max_by_key(|pattern| pattern.action)
+            .map(|pattern| (pattern.action, pattern.description.as_str()))
+    }
+
+    /// Whether a `skip-column` pattern removes the column from samples.
+    ///
+    /// SQL adapters leave such columns out of the sampling query, so their
+    /// values are never read from the database.
+    pub fn skips_column(&self, column_name: &str) -> bool {
+        matches!(
+            self.sensitive_action(column_name),
+            Some((SensitiveAction::SkipColumn, _))
+        )
+    }
+
+    /// Warnings for the columns a `skip-column` pattern removes, in the
+    /// wording of [`Self::apply_sensitive_actions`].
+    pub fn skipped_column_warnings<'a>(
+        &self,
+        column_names: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<String> {
+        column_names
+            .into_iter()
+            .filter_map(|name| match self.sensitive_action(name)? {
+                (SensitiveAction::SkipColumn, description) => Some(format!(
+                    "Column '{name}' removed from samples ({description})"
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Masks or removes sensitive columns in sampled rows according to the
+    /// pattern actions.
+    ///
+    /// Removal here covers rows fetched without an explicit column list
+    /// (MongoDB documents, or a `SELECT *` fallback when column metadata is
+    /// unreadable); other columns are already left out by [`Self::skips_column`].
+    ///
+    /// Returns one warning per masked or removed column.
+    pub fn apply_sensitive_actions(&self, rows: &mut [JsonValue]) -> Vec<String> {
+        let mut columns: Vec<(String, SensitiveAction, String)> = Vec::new();
+        for row in rows.iter() {
+            let Some(object) = row.as_object() else {
+                continue;
+            };
+            for name in object.keys() {
+                if columns.iter().any(|(known, _, _)| known == name) {
+                    continue;
+                }
+                if let Some((action, description)) = self.sensitive_action(name)
+                    && action != SensitiveAction::Warn
+                {
+                    columns.push((name.clone(), action, description.to_string()));
+                }
+            }
+        }
+
+        for row in rows.iter_mut() {
+            let Some(object) = row.as_object_mut() else {
+                continue;
+            };
+            for (name, action, _) in &columns {
+                match action {
+                    SensitiveAction::SkipColumn => {
+                        object.remove(name);
+                    }
+                    SensitiveAction::Mask => {
+                        if let Some(value) = object.get_mut(name)
+                            && !value.is_null()
+                        {
+                            *value = JsonValue::String(MASKED_VALUE.to_string());
+                        }
+                    }
+                    SensitiveAction::Warn => {}
+                }
+            }
+        }
+
+        columns
+            .into_iter()
+            .map(|(name, action, description)| {
+                let verb = match action {
+                    SensitiveAction::SkipColumn => "removed from",
+                    _ => "masked in",
+                };
+                format!("Column '{name}' {verb} samples ({description})")
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -277,6 +539,28 @@ fn test_sampling_config_builder() {
         assert!(!config.warn_sensitive);
     }
 
+    #[test]
+    fn test_oversize_reason() {
+        let config = SamplingConfig::new()
+            .with_max_table_rows(1_000)
+            .with_max_table_bytes(4_096);
+
+        assert_eq!(config.oversize_reason(Some(1_000), Some(4_096)), None);
+        assert_eq!(config.oversize_reason(None, None), None);
+        assert_eq!(
+            config.oversize_reason(Some(1_001), None).as_deref(),
+            Some("estimated 1001 rows exceeds the sampling limit of 1000 rows")
+        );
+        assert_eq!(
+            config.oversize_reason(Some(10), Some(8_192)).as_deref(),
+            Some("estimated 8192 bytes exceeds the sampling limit of 4096 bytes")
+        );
+        assert_eq!(
+            SamplingConfig::new().oversize_reason(Some(u64::MAX), None),
+            None
+        );
+    }
+
     #[test]
     fn test_sensitive_pattern_new() {
         let pattern = SensitivePattern::new(r"(?i)api_key", "API key detected");
@@ -420,4 +704,145 @@ fn test_validate_rejects_invalid_regex_pattern() {
             "Error should mention the bad pattern, got: {msg}"
         );
     }
+
+    const RULES_YAML: &str = r#"
+patterns:
+  - pattern: '(?i)api_?key'
+    description: API key detected
+    action: skip-column
+  - pattern: '(?i)(email|mail)'
+    description: Email address
+    action: mask
+  - pattern: '(?i)token'
+    description: Token detected
+"#;
+
+    #[test]
+    fn test_sensitive_pattern_rules_from_yaml() {
+        let rules = SensitivePatternRules::from_yaml(RULES_YAML).expect("valid rules");
+        assert!(!rules.replace_defaults);
+        assert_eq!(rules.patterns.len(), 3);
+        assert_eq!(rules.patterns[0].action, SensitiveAction::SkipColumn);
+        assert_eq!(rules.patterns[1].action, SensitiveAction::Mask);
+        assert_eq!(rules.patterns[2].action, SensitiveAction::Warn);
+    }
+
+    #[test]
+    fn test_sensitive_pattern_rules_reject_invalid_regex() {
+        let err = SensitivePatternRules::from_yaml(
+            "patterns:\n  - pattern: '[invalid'\n    description: Bad regex\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("[invalid"), "got: {err}");
+    }
+
+    #[test]
+    fn test_sensitive_pattern_rules_reject_unknown_fields_and_actions() {
+        assert!(SensitivePatternRules::from_yaml("pattern: x").is_err());
+        assert!(
+            SensitivePatternRules::from_yaml(
+                "patterns:\n  - pattern: x\n    description: y\n    action: delete\n"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_with_sensitive_pattern_rules_overrides_and_extends() {
+        let rules = SensitivePatternRules::from_yaml(RULES_YAML).expect("valid rules");
+        let default_count = SamplingConfig::default().sensitive_detection_patterns.len();
+        let config = SamplingConfig::new().with_sensitive_pattern_rules(rules.clone());
+
+        // The email rule overrides the built-in pattern with the same regex
+        assert_eq!(config.sensitive_detection_patterns.len(), default_count + 2);
+        assert_eq!(config.compiled_patterns.len(), default_count + 2);
+        assert_eq!(
+            config.sensitive_action("user_email"),
+            Some((SensitiveAction::Mask, "Email address"))
+        );
+        assert_eq!(
+            config
+                .sensitive_action("password")
+                .map(|(action, _)| action),
+            Some(SensitiveAction::Warn)
+        );
+        assert_eq!(config.sensitive_action("created_at"), None);
+
+        let replaced = SamplingConfig::new().with_sensitive_pattern_rules(SensitivePatternRules {
+            replace_defaults: true,
+            ..rules
+        });
+        assert_eq!(replaced.sensitive_detection_patterns.len(), 3);
+        assert_eq!(replaced.sensitive_action("password"), None);
+    }
+
+    #[test]
+    fn test_most_restrictive_action_wins() {
+        let config = SamplingConfig::new()
+            .add_sensitive_pattern(
+                SensitivePattern::new(r"(?i)customer_email", "Customer email")
+                    .with_action(SensitiveAction::SkipColumn),
+            )
+            .add_sensitive_pattern(
+                SensitivePattern::new(r"(?i)customer", "Customer data")
+                    .with_action(SensitiveAction::Mask),
+            );
+        assert_eq!(
+            config.sensitive_action("CUSTOMER_EMAIL"),
+            Some((SensitiveAction::SkipColumn, "Customer email"))
+        );
+    }
+
+    #[test]
+    fn test_apply_sensitive_actions() {
+        let rules = SensitivePatternRules::from_yaml(RULES_YAML).expect("valid rules");
+        let config = SamplingConfig::new().with_sensitive_pattern_rules(rules);
+        let mut rows = vec![
+            serde_json::json!({"id": 1, "email": "a@example.com", "api_key": "k1", "password": "p"}),
+            serde_json::json!({"id": 2, "email": null, "api_key": "k2", "password": "q"}),
+        ];
+
+        let warnings = config.apply_sensitive_actions(&mut rows);
+
+        assert_eq!(
+            rows[0],
+            serde_json::json!({"id": 1, "email": MASKED_VALUE, "password": "p"})
+        );
+        assert_eq!(
+            rows[1],
+            serde_json::json!({"id": 2, "email": null, "password": "q"})
+        );
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w == "Column 'api_key' removed from samples (API key detected)")
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w == "Column 'email' masked in samples (Email address)")
+        );
+    }
+
+    #[test]
+    fn test_skipped_columns() {
+        let rules = SensitivePatternRules::from_yaml(RULES_YAML).expect("valid rules");
+        let config = SamplingConfig::new().with_sensitive_pattern_rules(rules);
+
+        assert!(config.skips_column("API_KEY"));
+        assert!(!config.skips_column("email"));
+        assert!(!config.skips_column("id"));
+        assert_eq!(
+            config.skipped_column_warnings(["id", "email", "api_key"]),
+            ["Column 'api_key' removed from samples (API key detected)"]
+        );
+    }
+
+    #[test]
+    fn test_sensitive_pattern_action_defaults_to_warn_when_deserialized() {
+        let pattern: SensitivePattern =
+            serde_json::from_str(r#"{"pattern": "x", "description": "y"}"#).expect("deserialize");
+        assert_eq!(pattern.action, SensitiveAction::Warn);
+    }
 }
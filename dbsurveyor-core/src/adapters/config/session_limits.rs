@@ -0,0 +1,160 @@
+//! Per-engine session resource limits.
+//!
+//! Beyond the query timeout every engine gets, operators can cap what a
+//! collection session may consume on the server: PostgreSQL `work_mem` and
+//! `statement_timeout`, MySQL `max_execution_time`, and the SQL Server query
+//! governor. Limits for other engines are ignored.
+
+use serde::{Deserialize, Serialize};
+
+/// Session limits applied to every collection connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SessionLimits {
+    #[serde(default, skip_serializing_if = "PostgresLimits::is_empty")]
+    pub postgres: PostgresLimits,
+    #[serde(default, skip_serializing_if = "MySqlLimits::is_empty")]
+    pub mysql: MySqlLimits,
+    #[serde(default, skip_serializing_if = "SqlServerLimits::is_empty")]
+    pub mssql: SqlServerLimits,
+}
+
+/// PostgreSQL session settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresLimits {
+    /// `work_mem` for sorts and hashes, e.g. `"4MB"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work_mem: Option<String>,
+    /// `statement_timeout` in milliseconds, overriding the query timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_timeout_ms: Option<u64>,
+}
+
+/// MySQL session settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MySqlLimits {
+    /// `max_execution_time` for `SELECT`s in milliseconds, overriding the
+    /// query timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_execution_time_ms: Option<u64>,
+}
+
+/// SQL Server session settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqlServerLimits {
+    /// `QUERY_GOVERNOR_COST_LIMIT`: queries the optimizer estimates will cost
+    /// more are refused before they run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_governor_cost_limit: Option<u64>,
+}
+
+impl SessionLimits {
+    /// Returns true when no limit is configured for any engine.
+    pub fn is_empty(&self) -> bool {
+        self.postgres.is_empty() && self.mysql.is_empty() && self.mssql.is_empty()
+    }
+
+    /// Validates the configured limits.
+    ///
+    /// Settings are interpolated into `SET` statements, which take no bind
+    /// parameters, so `work_mem` is restricted to a number and unit.
+    ///
+    /// # Errors
+    /// Returns a configuration error for a malformed `work_mem` or a zero
+    /// limit, which the engines treat as "no limit".
+    pub fn validate(&self) -> crate::Result<()> {
+        if let Some(work_mem) = &self.postgres.work_mem
+            && !is_memory_size(work_mem)
+        {
+            return Err(crate::error::DbSurveyorError::configuration(format!(
+                "postgres work_mem must be a positive size such as '4MB', got '{work_mem}'"
+            )));
+        }
+        for (name, value) in [
+            (
+                "postgres statement_timeout_ms",
+                self.postgres.statement_timeout_ms,
+            ),
+            (
+                "mysql max_execution_time_ms",
+                self.mysql.max_execution_time_ms,
+            ),
+            (
+                "mssql query_governor_cost_limit",
+                self.mssql.query_governor_cost_limit,
+            ),
+        ] {
+            if value == Some(0) {
+                return Err(crate::error::DbSurveyorError::configuration(format!(
+                    "{name} must be greater than 0; 0 disables the limit"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PostgresLimits {
+    pub fn is_empty(&self) -> bool {
+        self.work_mem.is_none() && self.statement_timeout_ms.is_none()
+    }
+}
+
+impl MySqlLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_execution_time_ms.is_none()
+    }
+}
+
+impl SqlServerLimits {
+    pub fn is_empty(&self) -> bool {
+        self.query_governor_cost_limit.is_none()
+    }
+}
+
+/// Matches PostgreSQL memory sizes: digits with an optional `kB`, `MB`,
+/// `GB`, or `TB` unit, not zero.
+fn is_memory_size(value: &str) -> bool {
+    let digits = value.trim_end_matches(char::is_alphabetic);
+    let unit = &value[digits.len()..];
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && digits.bytes().any(|b| b != b'0')
+        && matches!(unit, "" | "kB" | "MB" | "GB" | "TB")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_work_mem_must_be_a_size() {
+        for valid in ["4MB", "64kB", "1GB", "4096"] {
+            let mut limits = SessionLimits::default();
+            limits.postgres.work_mem = Some(valid.to_string());
+            assert!(limits.validate().is_ok(), "{valid}");
+        }
+        for invalid in ["", "0MB", "4 MB", "4mb", "4MB'; DROP TABLE x; --", "-1"] {
+            let mut limits = SessionLimits::default();
+            limits.postgres.work_mem = Some(invalid.to_string());
+            assert!(limits.validate().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_zero_limits_are_rejected() {
+        let mut limits = SessionLimits::default();
+        limits.mysql.max_execution_time_ms = Some(0);
+        let err = limits.validate().expect_err("zero disables the limit");
+        assert!(err.to_string().contains("mysql max_execution_time_ms"));
+
+        limits.mysql.max_execution_time_ms = Some(5_000);
+        limits.mssql.query_governor_cost_limit = Some(300);
+        assert!(limits.validate().is_ok());
+        assert!(!limits.is_empty());
+        assert!(SessionLimits::default().is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides common functionality shared across different database adapters
 //! to reduce code duplication and ensure consistent error handling.
 
+use crate::models::{CollectionWarning, DegradedCollection, WarningCategory};
 use std::sync::OnceLock;
 
 /// Pre-compiled regex patterns for validation to avoid repeated compilation.
@@ -104,7 +105,7 @@ pub fn is_sensitive_field(&self, field_name: &str) -> bool {
 pub(crate) fn resolve_optional_collection<T>(
     description: &str,
     result: crate::Result<Vec<T>>,
-    warnings: &mut Vec<String>,
+    warnings: &mut Vec<CollectionWarning>,
 ) -> Vec<T> {
     match result {
         Ok(items) => {
@@ -114,12 +115,168 @@ pub(crate) fn resolve_optional_collection<T>(
         Err(e) => {
             let warning = format!("Failed to collect {}: {}", description, e);
             tracing::warn!("{}", warning);
-            warnings.push(warning);
+            warnings.push(CollectionWarning::new(WarningCategory::Collection, warning));
             Vec::new()
         }
     }
 }
 
+/// Retries an object class through a less-privileged query when its usual
+/// query was denied access.
+///
+/// The fallback only runs for privilege errors. If it succeeds, `record` is
+/// completed with the denial and the names of the objects it returned and
+/// pushed to `degraded`; if it fails too, the original error is returned so
+/// the class is reported as skipped.
+pub(crate) async fn retry_with_fallback<T>(
+    result: crate::Result<Vec<T>>,
+    fallback: impl std::future::Future<Output = crate::Result<Vec<T>>>,
+    mut record: DegradedCollection,
+    name_of: impl Fn(&T) -> String,
+    degraded: &mut Vec<DegradedCollection>,
+) -> crate::Result<Vec<T>> {
+    let error = match result {
+        Err(e) if e.is_insufficient_privileges() => e,
+        other => return other,
+    };
+
+    tracing::info!(
+        "Access denied collecting {}; retrying from {}",
+        record.object_class,
+        record.source
+    );
+    match fallback.await {
+        Ok(items) => {
+            // The top-level message only names the query; the driver's
+            // message says what was denied
+            record.reason =
+                std::iter::successors(Some(&error as &(dyn std::error::Error + 'static)), |e| {
+                    e.source()
+                })
+                .last()
+                .map_or_else(|| error.to_string(), ToString::to_string);
+            record.objects = items.iter().map(name_of).collect();
+            degraded.push(record);
+            Ok(items)
+        }
+        Err(fallback_error) => {
+            tracing::warn!(
+                "Fallback collection of {} from {} failed: {}",
+                record.object_class,
+                record.source,
+                fallback_error
+            );
+            Err(error)
+        }
+    }
+}
+
+/// Returns `name` qualified with `schema`, if any.
+pub(crate) fn qualified_name(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+/// Resolves one part of an object's metadata, recording a failure instead of
+/// aborting collection.
+///
+/// On error the failure is attributed to the object in `failures` and
+/// `T::default()` (an empty collection or `None`) is returned, so the rest of
+/// the object and the remaining objects can still be collected.
+pub(crate) fn resolve_object_collection<T: Default>(
+    result: crate::Result<T>,
+    schema: Option<&str>,
+    name: &str,
+    stage: &str,
+    failures: &mut Vec<crate::models::ObjectFailure>,
+) -> T {
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Failed to collect {} for '{}': {}", stage, name, e);
+        failures.push(crate::models::ObjectFailure::new(schema, name, stage, e));
+        T::default()
+    })
+}
+
+/// Runs one table's collection under the per-table timeout, if configured.
+///
+/// When the timeout elapses the table is attributed a failure in `failures`
+/// and `None` is returned so the caller can skip it and move on.
+pub(crate) async fn collect_within_table_timeout<T>(
+    collection: impl std::future::Future<Output = T>,
+    timeout: Option<std::time::Duration>,
+    schema: Option<&str>,
+    name: &str,
+    failures: &mut Vec<crate::models::ObjectFailure>,
+) -> Option<T> {
+    let Some(limit) = timeout else {
+        return Some(collection.await);
+    };
+
+    match tokio::time::timeout(limit, collection).await {
+        Ok(value) => Some(value),
+        Err(_) => {
+            tracing::warn!("Skipping table '{}': timed out after {:?}", name, limit);
+            failures.push(crate::models::ObjectFailure::new(
+                schema,
+                name,
+                "table",
+                format!("timed out after {:?}", limit),
+            ));
+            None
+        }
+    }
+}
+
+/// Runs a collection query only when its object type is selected.
+///
+/// Unselected types resolve to an empty collection without querying the
+/// database.
+pub(crate) async fn collect_if_selected<T: Default>(
+    selected: bool,
+    collection: impl std::future::Future<Output = crate::Result<T>>,
+) -> crate::Result<T> {
+    if selected {
+        collection.await
+    } else {
+        Ok(T::default())
+    }
+}
+
+/// Formats a timestamp as RFC 3339 in UTC with a `Z` suffix.
+///
+/// Fractional seconds are written only when present, in groups of three
+/// digits (`2024-01-02T03:04:05.120Z`).
+pub fn format_utc_timestamp(timestamp: &chrono::DateTime<chrono::Utc>) -> String {
+    timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+}
+
+/// Encodes binary data for a sampled row as
+/// `{"base64": "<standard base64>", "length": <bytes>}`.
+pub fn binary_sample_value(bytes: &[u8]) -> serde_json::Value {
+    use base64::Engine;
+
+    serde_json::json!({
+        "base64": base64::engine::general_purpose::STANDARD.encode(bytes),
+        "length": bytes.len(),
+    })
+}
+
+/// Encodes a float for a sampled row.
+///
+/// JSON numbers cannot represent NaN or infinities, so those are written as
+/// the strings `"NaN"`, `"Infinity"`, and `"-Infinity"` (the spelling
+/// PostgreSQL uses in its own JSON output).
+pub fn float_sample_value(value: f64) -> serde_json::Value {
+    match serde_json::Number::from_f64(value) {
+        Some(number) => serde_json::Value::Number(number),
+        None if value.is_nan() => serde_json::Value::String("NaN".to_string()),
+        None if value > 0.0 => serde_json::Value::String("Infinity".to_string()),
+        None => serde_json::Value::String("-Infinity".to_string()),
+    }
+}
+
 /// Macro for reducing boilerplate error handling when querying database metadata.
 ///
 /// # Example
@@ -174,6 +331,32 @@ fn test_contains_credentials() {
         assert!(!patterns.contains_credentials("just a normal string"));
     }
 
+    #[test]
+    fn test_binary_sample_value() {
+        assert_eq!(
+            binary_sample_value(&[0xde, 0xad, 0xbe, 0xef]),
+            serde_json::json!({"base64": "3q2+7w==", "length": 4})
+        );
+        assert_eq!(
+            binary_sample_value(&[]),
+            serde_json::json!({"base64": "", "length": 0})
+        );
+    }
+
+    #[test]
+    fn test_float_sample_value() {
+        assert_eq!(float_sample_value(1.5), serde_json::json!(1.5));
+        assert_eq!(float_sample_value(f64::NAN), serde_json::json!("NaN"));
+        assert_eq!(
+            float_sample_value(f64::INFINITY),
+            serde_json::json!("Infinity")
+        );
+        assert_eq!(
+            float_sample_value(f64::NEG_INFINITY),
+            serde_json::json!("-Infinity")
+        );
+    }
+
     #[test]
     fn test_resolve_optional_collection_ok() {
         let mut warnings = Vec::new();
@@ -194,7 +377,8 @@ fn test_resolve_optional_collection_err() {
         let items = resolve_optional_collection("widgets", result, &mut warnings);
         assert!(items.is_empty());
         assert_eq!(warnings.len(), 1);
-        assert!(warnings[0].contains("Failed to collect widgets"));
+        assert!(warnings[0].message.contains("Failed to collect widgets"));
+        assert_eq!(warnings[0].category, WarningCategory::Collection);
     }
 
     #[test]
@@ -206,6 +390,112 @@ fn test_resolve_optional_collection_empty_ok() {
         assert!(warnings.is_empty());
     }
 
+    #[test]
+    fn test_resolve_object_collection_records_failure() {
+        let mut failures = Vec::new();
+        let ok: crate::Result<Option<u32>> = Ok(Some(7));
+        assert_eq!(
+            resolve_object_collection(ok, Some("public"), "orders", "primary key", &mut failures),
+            Some(7)
+        );
+        assert!(failures.is_empty());
+
+        let err: crate::Result<Vec<String>> =
+            Err(crate::error::DbSurveyorError::collection_failed(
+                "index query",
+                std::io::Error::other("boom"),
+            ));
+        let items =
+            resolve_object_collection(err, Some("public"), "orders", "indexes", &mut failures);
+        assert!(items.is_empty());
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].qualified_name(), "public.orders");
+        assert_eq!(failures[0].stage, "indexes");
+    }
+
+    #[tokio::test]
+    async fn test_collect_within_table_timeout_skips_slow_table() {
+        let mut failures = Vec::new();
+        let limit = Some(std::time::Duration::from_millis(10));
+
+        let fast = collect_within_table_timeout(async { 1 }, limit, None, "fast", &mut failures);
+        assert_eq!(fast.await, Some(1));
+        assert!(failures.is_empty());
+
+        let slow = async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            2
+        };
+        let result = collect_within_table_timeout(slow, limit, None, "huge", &mut failures).await;
+        assert_eq!(result, None);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "huge");
+        assert!(failures[0].error.contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_fallback_only_after_denial() {
+        let denied = || {
+            Err(crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate database views",
+                std::io::Error::other("permission denied for view pg_views"),
+            ))
+        };
+        let record = || DegradedCollection::new("views", "information_schema");
+        let mut degraded = Vec::new();
+
+        let views = retry_with_fallback(
+            denied(),
+            async { Ok(vec!["public.v1".to_string()]) },
+            record().with_missing(&["comments"]),
+            String::clone,
+            &mut degraded,
+        )
+        .await
+        .expect("fallback succeeds");
+        assert_eq!(views, vec!["public.v1"]);
+        assert_eq!(degraded.len(), 1);
+        assert_eq!(degraded[0].objects, vec!["public.v1"]);
+        assert_eq!(degraded[0].reason, "permission denied for view pg_views");
+
+        // A failed fallback reports the original denial
+        let error = retry_with_fallback::<String>(
+            denied(),
+            async { Err(crate::error::DbSurveyorError::query_failed("also denied")) },
+            record(),
+            String::clone,
+            &mut degraded,
+        )
+        .await
+        .expect_err("both queries failed");
+        assert!(
+            error
+                .to_string()
+                .contains("Failed to enumerate database views")
+        );
+
+        // Other errors are not retried
+        let error = retry_with_fallback::<String>(
+            Err(crate::error::DbSurveyorError::query_failed("syntax error")),
+            async { panic!("fallback must not run") },
+            record(),
+            String::clone,
+            &mut degraded,
+        )
+        .await
+        .expect_err("not a privilege error");
+        assert!(error.to_string().contains("syntax error"));
+        assert_eq!(degraded.len(), 1);
+    }
+
+    #[test]
+    fn test_format_utc_timestamp() {
+        let timestamp = chrono::DateTime::parse_from_rfc3339("2024-01-02T05:04:05.12+02:00")
+            .expect("valid timestamp")
+            .with_timezone(&chrono::Utc);
+        assert_eq!(format_utc_timestamp(&timestamp), "2024-01-02T03:04:05.120Z");
+    }
+
     #[test]
     fn test_is_sensitive_field() {
         let patterns = ValidationPatterns::instance();
@@ -7,25 +7,31 @@
 //! # Module Structure
 //! - `config`: Configuration types (ConnectionConfig, SamplingConfig, CollectionConfig)
 //! - `helpers`: Shared helper utilities
+//! - `multi_database`: Multi-database collection shared by server-level adapters
 //! - `placeholder`: Placeholder adapter macro for unimplemented databases
 //! - Database-specific modules (postgres, mysql, sqlite, mongodb, mssql)
 
 use crate::{
     Result,
-    models::{DatabaseSchema, TableSample},
+    models::{
+        DatabaseSchema, IntegrityCheck, IntegrityCheckMode, ReadOnlyVerification, TableSample,
+    },
 };
 use async_trait::async_trait;
+use serde::Serialize;
 
 // Configuration module
 pub mod config;
 
 // Re-export configuration types for convenience
 pub use config::{
-    CollectionConfig, ConnectionConfig, OutputFormat, SamplingConfig, SensitivePattern,
+    CatalogMode, CollectionConfig, ConnectionConfig, ObjectSelection, OutputFormat,
+    ReadPreferenceConfig, ReadPreferenceMode, SamplingConfig, SensitiveAction, SensitivePattern,
+    SensitivePatternRules, SessionLimits, SourcePolicy,
 };
 
 /// Features that database adapters may support.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AdapterFeature {
     /// Schema introspection and metadata collection
     SchemaCollection,
@@ -41,6 +47,19 @@ pub enum AdapterFeature {
     ReadOnlyMode,
 }
 
+/// Pool statistics for monitoring connection pool health and usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PoolStats {
+    /// Number of idle connections ready to be used
+    pub idle_connections: u32,
+    /// Number of connections currently in use
+    pub active_connections: u32,
+    /// Total number of connections in the pool
+    pub total_connections: u32,
+    /// Maximum allowed connections (from configuration)
+    pub max_connections: u32,
+}
+
 /// Identifies a table by optional schema and name, used as input to
 /// [`DatabaseAdapter::sample_table`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -129,6 +148,41 @@ async fn sample_table(
 
     /// Gets the connection configuration (credentials sanitized).
     fn connection_config(&self) -> ConnectionConfig;
+
+    /// Returns a snapshot of connection pool usage, for adapters that pool
+    /// connections.
+    fn connection_pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
+
+    /// Checks whether collection sessions are prevented from writing.
+    ///
+    /// Runs before collection and only reads settings; it never attempts a
+    /// write. Adapters that cannot inspect read-only state report
+    /// [`ReadOnlyLevel::Unverified`](crate::models::ReadOnlyLevel::Unverified).
+    ///
+    /// # Errors
+    /// Returns error if the settings cannot be queried.
+    async fn verify_read_only(&self) -> Result<ReadOnlyVerification> {
+        Ok(ReadOnlyVerification::unverified(format!(
+            "{} adapter cannot verify read-only enforcement",
+            self.database_type()
+        )))
+    }
+
+    /// Checks the source database file for corruption before collection.
+    ///
+    /// Only file-based engines can run this; the default returns `None`. A
+    /// check that reports problems, times out, or fails to run is returned
+    /// with that status rather than as an error, so collection can proceed
+    /// and record it.
+    async fn check_integrity(
+        &self,
+        _mode: IntegrityCheckMode,
+        _timeout: Option<std::time::Duration>,
+    ) -> Option<IntegrityCheck> {
+        None
+    }
 }
 
 /// Factory function to create database adapters based on connection string.
@@ -150,12 +204,48 @@ async fn sample_table(
 /// - Database type is not supported
 /// - Required features are not compiled in
 pub async fn create_adapter(connection_string: &str) -> Result<Box<dyn DatabaseAdapter>> {
+    create_adapter_with_config(connection_string, &CollectionConfig::default()).await
+}
+
+/// Creates a database adapter and applies collection settings to it.
+///
+/// Connection settings are parsed from the connection string exactly as in
+/// [`create_adapter`]; settings that cannot be expressed in a URL are then
+/// taken from `config`: the per-table timeout (`config.connection.table_timeout`),
+/// immutable opening of SQLite files (`config.connection.immutable`),
+/// per-transaction settings behind a connection pooler
+/// (`config.connection.pooler_mode`), the PostgreSQL catalog mode
+/// (`config.connection.catalog_mode`), the MongoDB read preference
+/// (`config.connection.read_preference`), and the object types to collect
+/// (`config.include_*`).
+///
+/// # Errors
+/// Returns the same errors as [`create_adapter`].
+pub async fn create_adapter_with_config(
+    connection_string: &str,
+    config: &CollectionConfig,
+) -> Result<Box<dyn DatabaseAdapter>> {
     let database_type = detect_database_type(connection_string)?;
+    let table_timeout = config.connection.table_timeout;
+    let objects = config.object_selection();
+    tracing::debug!(
+        "Creating {:?} adapter (per-table timeout: {:?}, {:?})",
+        database_type,
+        table_timeout,
+        objects
+    );
 
     match database_type {
         #[cfg(feature = "postgresql")]
         crate::models::DatabaseType::PostgreSQL => {
-            let adapter = postgres::PostgresAdapter::new(connection_string).await?;
+            let mut adapter = if config.connection.pooler_mode {
+                postgres::PostgresAdapter::behind_pooler(connection_string).await?
+            } else {
+                postgres::PostgresAdapter::new(connection_string).await?
+            };
+            adapter.config.table_timeout = table_timeout;
+            adapter.config.catalog_mode = config.connection.catalog_mode;
+            adapter.objects = objects;
             Ok(Box::new(adapter))
         }
         #[cfg(not(feature = "postgresql"))]
@@ -167,7 +257,13 @@ pub async fn create_adapter(connection_string: &str) -> Result<Box<dyn DatabaseA
         }
         #[cfg(feature = "mysql")]
         crate::models::DatabaseType::MySQL => {
-            let adapter = mysql::MySqlAdapter::new(connection_string).await?;
+            let mut adapter = if config.connection.pooler_mode {
+                mysql::MySqlAdapter::behind_pooler(connection_string).await?
+            } else {
+                mysql::MySqlAdapter::new(connection_string).await?
+            };
+            adapter.config.table_timeout = table_timeout;
+            adapter.objects = objects;
             Ok(Box::new(adapter))
         }
         #[cfg(not(feature = "mysql"))]
@@ -179,7 +275,13 @@ pub async fn create_adapter(connection_string: &str) -> Result<Box<dyn DatabaseA
         }
         #[cfg(feature = "sqlite")]
         crate::models::DatabaseType::SQLite => {
-            let adapter = sqlite::SqliteAdapter::new(connection_string).await?;
+            let mut adapter = if config.connection.immutable {
+                sqlite::SqliteAdapter::immutable(connection_string, Vec::new()).await?
+            } else {
+                sqlite::SqliteAdapter::new(connection_string).await?
+            };
+            adapter.config.table_timeout = table_timeout;
+            adapter.objects = objects;
             Ok(Box::new(adapter))
         }
         #[cfg(not(feature = "sqlite"))]
@@ -191,7 +293,18 @@ pub async fn create_adapter(connection_string: &str) -> Result<Box<dyn DatabaseA
         }
         #[cfg(feature = "mongodb")]
         crate::models::DatabaseType::MongoDB => {
-            let adapter = mongodb::MongoAdapter::new(connection_string).await?;
+            let mut adapter = match &config.connection.read_preference {
+                Some(read_preference) => {
+                    mongodb::MongoAdapter::with_read_preference(
+                        connection_string,
+                        read_preference.clone(),
+                    )
+                    .await?
+                }
+                None => mongodb::MongoAdapter::new(connection_string).await?,
+            };
+            adapter.config.table_timeout = table_timeout;
+            adapter.objects = objects;
             Ok(Box::new(adapter))
         }
         #[cfg(not(feature = "mongodb"))]
@@ -266,6 +379,9 @@ fn detect_database_type(connection_string: &str) -> Result<crate::models::Databa
 // Shared helper utilities
 pub mod helpers;
 
+// Multi-database collection shared by server-level adapters
+pub mod multi_database;
+
 // Placeholder adapter macro
 pub mod placeholder;
 
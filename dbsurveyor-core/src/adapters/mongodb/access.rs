@@ -0,0 +1,142 @@
+//! Probing what the authenticated user can read in one database.
+
+use crate::Result;
+use crate::models::AccessProbe;
+use mongodb::Client;
+use mongodb::bson::{Document, doc};
+
+/// Built-in roles that grant every action on every resource.
+const SUPERUSER_ROLES: &[&str] = &["root", "__system"];
+
+/// Probes the user's roles and privileges through `connectionStatus`.
+pub(crate) async fn probe_access(
+    client: &Client,
+    database_name: &str,
+    collections: &[String],
+) -> Result<AccessProbe> {
+    let status = client
+        .database("admin")
+        .run_command(doc! { "connectionStatus": 1, "showPrivileges": true })
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to probe user access", e)
+        })?;
+
+    let auth_info = status.get_document("authInfo").cloned().unwrap_or_default();
+    Ok(classify_access(&auth_info, database_name, collections))
+}
+
+/// Builds the probe result from `connectionStatus.authInfo`.
+///
+/// With no authenticated user the server is not enforcing access control,
+/// which grants the connection everything a superuser has.
+pub(crate) fn classify_access(
+    auth_info: &Document,
+    database_name: &str,
+    collections: &[String],
+) -> AccessProbe {
+    let documents = |key: &str| -> Vec<Document> {
+        auth_info
+            .get_array(key)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_document().cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let users = documents("authenticatedUsers");
+    let roles = documents("authenticatedUserRoles");
+    let privileges = documents("authenticatedUserPrivileges");
+
+    let mut evidence = Vec::new();
+    let superuser = if users.is_empty() {
+        evidence.push("no authenticated user; access control is not enforced".to_string());
+        true
+    } else {
+        let role_names: Vec<String> = roles
+            .iter()
+            .filter_map(|role| {
+                Some(format!(
+                    "{}@{}",
+                    role.get_str("role").ok()?,
+                    role.get_str("db").ok()?
+                ))
+            })
+            .collect();
+        evidence.push(format!("roles: {}", role_names.join(", ")));
+        roles.iter().any(|role| {
+            role.get_str("db") == Ok("admin")
+                && role
+                    .get_str("role")
+                    .is_ok_and(|name| SUPERUSER_ROLES.contains(&name))
+        })
+    };
+
+    let allows = |action: &str, collection: Option<&str>| {
+        superuser
+            || privileges
+                .iter()
+                .any(|privilege| grants(privilege, action, database_name, collection))
+    };
+    let catalog_access = allows("listCollections", None);
+    let readable_tables = collections
+        .iter()
+        .filter(|name| allows("find", Some(name)))
+        .count();
+    if !catalog_access {
+        evidence.push(format!("listCollections not granted on {}", database_name));
+    }
+    evidence.push(format!(
+        "find allowed on {} of {} collections",
+        readable_tables,
+        collections.len()
+    ));
+
+    let identity = users.first().and_then(|user| {
+        Some(format!(
+            "{}@{}",
+            user.get_str("user").ok()?,
+            user.get_str("db").ok()?
+        ))
+    });
+
+    AccessProbe {
+        superuser,
+        catalog_access,
+        readable_tables,
+        total_tables: collections.len(),
+        identity,
+        evidence,
+    }
+}
+
+/// Whether `privilege` grants `action` on the database, or on one of its
+/// collections when `collection` is given.
+///
+/// An empty `db` or `collection` in the resource matches any name; a
+/// database-level action needs a resource that is not tied to a collection.
+fn grants(privilege: &Document, action: &str, database: &str, collection: Option<&str>) -> bool {
+    let has_action = privilege
+        .get_array("actions")
+        .is_ok_and(|actions| actions.iter().any(|a| a.as_str() == Some(action)));
+    let Ok(resource) = privilege.get_document("resource") else {
+        return false;
+    };
+    if !has_action {
+        return false;
+    }
+    if resource.get_bool("anyResource") == Ok(true) {
+        return true;
+    }
+    let db_matches = resource
+        .get_str("db")
+        .is_ok_and(|db| db.is_empty() || db == database);
+    let collection_matches = match (resource.get_str("collection"), collection) {
+        (Ok(granted), Some(name)) => granted.is_empty() || granted == name,
+        (Ok(granted), None) => granted.is_empty(),
+        (Err(_), _) => false,
+    };
+    db_matches && collection_matches
+}
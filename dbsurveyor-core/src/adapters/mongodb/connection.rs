@@ -10,9 +10,11 @@
 
 use super::MongoAdapter;
 use crate::Result;
-use crate::adapters::ConnectionConfig;
+use crate::adapters::{
+    ConnectionConfig, ObjectSelection, ReadPreferenceConfig, ReadPreferenceMode,
+};
 use mongodb::Client;
-use mongodb::options::ClientOptions;
+use mongodb::options::{ClientOptions, ReadPreference, ReadPreferenceOptions, SelectionCriteria};
 use std::time::Duration;
 use url::Url;
 use zeroize::Zeroizing;
@@ -54,6 +56,7 @@ pub async fn new(connection_string: &str) -> Result<Self> {
         Ok(Self {
             client,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
         })
     }
@@ -90,10 +93,26 @@ pub async fn with_config(connection_string: &str, config: ConnectionConfig) -> R
         Ok(Self {
             client,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
         })
     }
 
+    /// Creates a MongoDB adapter whose reads follow `read_preference`,
+    /// overriding any `readPreference` options in the connection string.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`MongoAdapter::new`], or a configuration
+    /// error if the read preference is invalid.
+    pub async fn with_read_preference(
+        connection_string: &str,
+        read_preference: ReadPreferenceConfig,
+    ) -> Result<Self> {
+        let config =
+            Self::parse_connection_config(connection_string)?.with_read_preference(read_preference);
+        Self::with_config(connection_string, config).await
+    }
+
     /// Parses a MongoDB connection string to extract configuration.
     ///
     /// # Arguments
@@ -250,6 +269,10 @@ async fn create_client_options(
             options.max_idle_time = Some(idle_timeout);
         }
 
+        if let Some(read_preference) = &config.read_preference {
+            options.selection_criteria = Some(selection_criteria(read_preference));
+        }
+
         // Set application name for connection tracking
         options.app_name = Some(format!("dbsurveyor-collect-{}", env!("CARGO_PKG_VERSION")));
 
@@ -295,11 +318,37 @@ pub fn for_database(&self, database: &str) -> Result<Self> {
         Ok(Self {
             client: self.client.clone(),
             config,
+            objects: self.objects,
             connection_url: Zeroizing::new((*self.connection_url).clone()),
         })
     }
 }
 
+/// Converts a read preference to the driver's selection criteria.
+pub(super) fn selection_criteria(read_preference: &ReadPreferenceConfig) -> SelectionCriteria {
+    let options = (!read_preference.tag_sets.is_empty() || read_preference.max_staleness.is_some())
+        .then(|| {
+            ReadPreferenceOptions::builder()
+                .tag_sets((!read_preference.tag_sets.is_empty()).then(|| {
+                    read_preference
+                        .tag_sets
+                        .iter()
+                        .map(|tags| tags.clone().into_iter().collect())
+                        .collect::<Vec<_>>()
+                }))
+                .max_staleness(read_preference.max_staleness)
+                .build()
+        });
+    let read_preference = match read_preference.mode {
+        ReadPreferenceMode::Primary => ReadPreference::Primary,
+        ReadPreferenceMode::PrimaryPreferred => ReadPreference::PrimaryPreferred { options },
+        ReadPreferenceMode::Secondary => ReadPreference::Secondary { options },
+        ReadPreferenceMode::SecondaryPreferred => ReadPreference::SecondaryPreferred { options },
+        ReadPreferenceMode::Nearest => ReadPreference::Nearest { options },
+    };
+    SelectionCriteria::ReadPreference(read_preference)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,4 +426,32 @@ fn test_parse_connection_config_invalid_port() {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("port"));
     }
+
+    #[test]
+    fn test_selection_criteria_from_read_preference() {
+        let mut read_preference = ReadPreferenceConfig::new(ReadPreferenceMode::SecondaryPreferred);
+        read_preference.tag_sets = vec![
+            std::collections::BTreeMap::from([("dc".to_string(), "east".to_string())]),
+            std::collections::BTreeMap::new(),
+        ];
+        read_preference.max_staleness = Some(Duration::from_secs(120));
+
+        let SelectionCriteria::ReadPreference(ReadPreference::SecondaryPreferred {
+            options: Some(options),
+        }) = selection_criteria(&read_preference)
+        else {
+            panic!("expected secondaryPreferred with options");
+        };
+        let tag_sets = options.tag_sets.expect("tag sets");
+        assert_eq!(tag_sets.len(), 2);
+        assert_eq!(tag_sets[0].get("dc").map(String::as_str), Some("east"));
+        assert!(tag_sets[1].is_empty());
+        assert_eq!(options.max_staleness, Some(Duration::from_secs(120)));
+
+        let criteria = selection_criteria(&ReadPreferenceConfig::new(ReadPreferenceMode::Nearest));
+        assert!(matches!(
+            criteria,
+            SelectionCriteria::ReadPreference(ReadPreference::Nearest { options: None })
+        ));
+    }
 }
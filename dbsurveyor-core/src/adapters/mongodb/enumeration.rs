@@ -203,16 +203,9 @@ pub async fn list_collections(
 
         let collection = EnumeratedCollection {
             name: collection_name.clone(),
-            collection_type: stats.as_ref().map_or(
-                CollectionType::Collection,
-                |(_, _, _, _, _, _, is_view)| {
-                    if *is_view {
-                        CollectionType::View
-                    } else {
-                        CollectionType::Collection
-                    }
-                },
-            ),
+            collection_type: stats
+                .as_ref()
+                .map_or(CollectionType::Collection, |(_, _, _, _, _, _, kind)| *kind),
             document_count: stats
                 .as_ref()
                 .ok()
@@ -255,7 +248,7 @@ pub async fn list_collections(
 /// * `collection_name` - Collection name
 ///
 /// # Returns
-/// Tuple of (document_count, size_bytes, avg_doc_size, index_count, total_index_size, is_capped, is_view)
+/// Tuple of (document_count, size_bytes, avg_doc_size, index_count, total_index_size, is_capped, collection_type)
 async fn get_collection_stats(
     client: &Client,
     database_name: &str,
@@ -267,7 +260,7 @@ async fn get_collection_stats(
     Option<u32>,
     Option<u64>,
     bool,
-    bool,
+    CollectionType,
 )> {
     let db = client.database(database_name);
 
@@ -325,7 +318,14 @@ async fn get_collection_stats(
     let is_capped = result.get_bool("capped").unwrap_or(false);
 
     // Check if it's a view (views don't have 'count' in collStats, they have 'ns' matching)
-    let is_view = result.get_str("ns").is_err() && count.is_none();
+    // or a time-series collection (collStats reports its bucketing)
+    let collection_type = if result.get_document("timeseries").is_ok() {
+        CollectionType::TimeSeries
+    } else if result.get_str("ns").is_err() && count.is_none() {
+        CollectionType::View
+    } else {
+        CollectionType::Collection
+    };
 
     Ok((
         count,
@@ -334,7 +334,7 @@ async fn get_collection_stats(
         num_indexes,
         total_index_size,
         is_capped,
-        is_view,
+        collection_type,
     ))
 }
 
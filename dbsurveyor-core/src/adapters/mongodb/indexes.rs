@@ -0,0 +1,183 @@
+//! MongoDB index definitions.
+//!
+//! `listIndexes` reports each index's key document and the options it was
+//! created with. The key values give the index type (`1`/`-1` for ordered
+//! B-tree keys, a string such as `"text"` or `"2dsphere"` for special
+//! indexes), and the options carry semantics that change which documents
+//! are indexed or how long they live: TTL expiry, partial filters, sparse
+//! and hidden indexes, and collation.
+
+use crate::models::{Index, IndexColumn, IndexOptions, SortDirection};
+use mongodb::IndexModel;
+use mongodb::bson::{Bson, Document};
+
+/// Name of the index MongoDB creates on `_id` for every collection.
+const ID_INDEX_NAME: &str = "_id_";
+
+/// Converts an index reported by `listIndexes` into the unified model.
+pub(super) fn index_from_model(model: IndexModel, database: &str, collection: &str) -> Index {
+    let options = model.options;
+    let name = options
+        .as_ref()
+        .and_then(|o| o.name.clone())
+        .unwrap_or_else(|| "unnamed".to_string());
+    let is_unique = options.as_ref().and_then(|o| o.unique).unwrap_or(false);
+
+    let columns = model
+        .keys
+        .iter()
+        .map(|(key, value)| IndexColumn {
+            name: key.clone(),
+            sort_order: match key_direction(value) {
+                Some(direction) if direction > 0 => Some(SortDirection::Ascending),
+                Some(direction) if direction < 0 => Some(SortDirection::Descending),
+                _ => None,
+            },
+        })
+        .collect();
+
+    Index {
+        is_primary: name == ID_INDEX_NAME,
+        name,
+        table_name: collection.to_string(),
+        schema: Some(database.to_string()),
+        columns,
+        is_unique,
+        index_type: Some(index_type(&model.keys).to_string()),
+        options: options.as_ref().and_then(declared_options),
+    }
+}
+
+/// Index type from the key document: the special index kind named by a
+/// string key value, `wildcard` for `$**` keys, otherwise `btree`.
+fn index_type(keys: &Document) -> &str {
+    keys.iter()
+        .find_map(|(key, value)| match value {
+            Bson::String(kind) => Some(kind.as_str()),
+            _ if key.contains("$**") => Some("wildcard"),
+            _ => None,
+        })
+        .unwrap_or("btree")
+}
+
+/// Direction of an ordered key; drivers and shells store it as any numeric type.
+fn key_direction(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(direction) => Some(i64::from(*direction)),
+        Bson::Int64(direction) => Some(*direction),
+        #[allow(clippy::cast_possible_truncation)]
+        Bson::Double(direction) => Some(direction.signum() as i64),
+        _ => None,
+    }
+}
+
+/// Options worth recording, or `None` for an index declared with none.
+fn declared_options(options: &mongodb::options::IndexOptions) -> Option<IndexOptions> {
+    let declared = IndexOptions {
+        expire_after_seconds: options.expire_after.map(|expiry| expiry.as_secs()),
+        partial_filter: options.partial_filter_expression.as_ref().map(|filter| {
+            Bson::Document(filter.clone())
+                .into_relaxed_extjson()
+                .to_string()
+        }),
+        sparse: options.sparse.unwrap_or(false),
+        hidden: options.hidden.unwrap_or(false),
+        collation: options
+            .collation
+            .as_ref()
+            .and_then(|collation| serde_json::to_string(collation).ok()),
+    };
+    (declared != IndexOptions::default()).then_some(declared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+    use mongodb::options::{Collation, CollationStrength};
+    use std::time::Duration;
+
+    fn model(keys: Document, options: mongodb::options::IndexOptions) -> IndexModel {
+        IndexModel::builder().keys(keys).options(options).build()
+    }
+
+    #[test]
+    fn test_ttl_partial_sparse_hidden_and_collation_are_recorded() {
+        let options = mongodb::options::IndexOptions::builder()
+            .name("session_expiry".to_string())
+            .expire_after(Duration::from_secs(3600))
+            .partial_filter_expression(doc! { "active": true })
+            .sparse(true)
+            .hidden(true)
+            .collation(
+                Collation::builder()
+                    .locale("en")
+                    .strength(CollationStrength::Secondary)
+                    .build(),
+            )
+            .build();
+        let index = index_from_model(model(doc! { "created_at": 1 }, options), "app", "sessions");
+
+        assert_eq!(index.name, "session_expiry");
+        assert_eq!(index.index_type.as_deref(), Some("btree"));
+        assert!(!index.is_primary);
+        let options = index.options.expect("options are recorded");
+        assert_eq!(options.expire_after_seconds, Some(3600));
+        assert_eq!(
+            options.partial_filter.as_deref(),
+            Some(r#"{"active":true}"#)
+        );
+        assert!(options.sparse);
+        assert!(options.hidden);
+        let collation = options.collation.expect("collation is recorded");
+        assert!(collation.contains(r#""locale":"en""#), "{collation}");
+        assert!(collation.contains(r#""strength":2"#), "{collation}");
+    }
+
+    #[test]
+    fn test_index_type_follows_key_values() {
+        let plain = mongodb::options::IndexOptions::builder()
+            .name(ID_INDEX_NAME.to_string())
+            .build();
+        let index = index_from_model(model(doc! { "_id": 1 }, plain), "app", "users");
+        assert!(index.is_primary);
+        assert_eq!(index.index_type.as_deref(), Some("btree"));
+        assert_eq!(index.options, None);
+
+        for (keys, expected) in [
+            (doc! { "body": "text" }, "text"),
+            (doc! { "location": "2dsphere" }, "2dsphere"),
+            (doc! { "user_id": "hashed" }, "hashed"),
+            (doc! { "attributes.$**": 1 }, "wildcard"),
+            (doc! { "tenant": 1, "name": "text" }, "text"),
+        ] {
+            let index = index_from_model(
+                model(keys, mongodb::options::IndexOptions::default()),
+                "app",
+                "docs",
+            );
+            assert_eq!(index.index_type.as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_key_direction_accepts_any_numeric_type() {
+        let index = index_from_model(
+            model(
+                doc! { "a": 1_i64, "b": -1.0, "c": "text" },
+                mongodb::options::IndexOptions::default(),
+            ),
+            "app",
+            "docs",
+        );
+        let orders: Vec<_> = index.columns.iter().map(|c| c.sort_order.clone()).collect();
+        assert_eq!(
+            orders,
+            [
+                Some(SortDirection::Ascending),
+                Some(SortDirection::Descending),
+                None
+            ]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 //! MongoDB database adapter with schema inference and document sampling.
 //!
 //! # Module Structure
+//! - `access`: Probing the authenticated user's roles and privileges
 //! - `connection`: MongoDB client management and connection validation
 //! - `type_mapping`: BSON to `UnifiedDataType` conversion
 //! - `schema_inference`: Schema inference from document samples
 //! - `sampling`: Document sampling utilities and ordering strategies
 //! - `enumeration`: Database and collection enumeration
+//! - `multi_database`: Multi-database collection orchestration
+//! - `validators`: View definitions and `$jsonSchema` validator comparison
 //!
 //! # Schema Inference
 //! MongoDB is a schemaless database, so this adapter infers schema by:
@@ -19,17 +22,24 @@
 //! - Connection strings are sanitized in error messages
 //! - Query timeouts prevent resource exhaustion
 
+mod access;
 mod connection;
 mod enumeration;
+mod indexes;
+mod multi_database;
 mod sampling;
 mod schema_inference;
 mod type_mapping;
+mod validators;
 
 #[cfg(test)]
 mod tests;
 
-use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, SamplingConfig, TableRef};
+use super::{
+    AdapterFeature, ConnectionConfig, DatabaseAdapter, ObjectSelection, SamplingConfig, TableRef,
+};
 use crate::Result;
+use crate::adapters::helpers::{collect_if_selected, collect_within_table_timeout};
 use crate::models::*;
 use async_trait::async_trait;
 use mongodb::Client;
@@ -38,13 +48,19 @@
 use zeroize::Zeroizing;
 
 // Re-export public items from submodules
+pub use super::multi_database::{
+    DatabaseCollectionResult, DatabaseFailure, MultiDatabaseConfig, MultiDatabaseMetadata,
+    MultiDatabaseResult,
+};
 pub use enumeration::{
     CollectionType, EnumeratedCollection, EnumeratedDatabase, SYSTEM_DATABASES,
     list_accessible_databases, list_collections, list_databases, list_indexes,
 };
+pub use multi_database::collect_all_databases;
 pub use sampling::{detect_ordering_strategy, generate_sort_document, sample_collection};
 pub use schema_inference::{InferredField, InferredSchema};
 pub use type_mapping::{bson_type_name, map_bson_to_unified};
+pub use validators::{CollectionDefinition, find_discrepancies, list_collection_definitions};
 
 /// MongoDB database adapter with schema inference and document sampling.
 ///
@@ -60,6 +76,8 @@ pub struct MongoAdapter {
     pub client: Client,
     /// Connection configuration
     pub config: ConnectionConfig,
+    /// Optional object types to collect
+    pub objects: ObjectSelection,
     /// Original connection URL (kept private to prevent credential exposure).
     /// Wrapped in `Zeroizing` so the URL (which may contain credentials) is
     /// scrubbed from memory when the adapter is dropped (CWE-316).
@@ -70,6 +88,7 @@ impl std::fmt::Debug for MongoAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MongoAdapter")
             .field("config", &self.config)
+            .field("objects", &self.objects)
             // Note: connection_url is intentionally omitted to prevent credential exposure
             .finish_non_exhaustive()
     }
@@ -107,6 +126,7 @@ fn supports_feature(&self, feature: AdapterFeature) -> bool {
             feature,
             AdapterFeature::SchemaCollection
                 | AdapterFeature::DataSampling
+                | AdapterFeature::MultiDatabase
                 | AdapterFeature::QueryTimeout
         )
     }
@@ -118,6 +138,9 @@ fn connection_config(&self) -> ConnectionConfig {
 
 impl MongoAdapter {
     /// Collects the database schema by inferring it from document samples.
+    ///
+    /// A collection whose inference exceeds the per-table timeout is skipped
+    /// and recorded as an object failure.
     async fn collect_schema_internal(&self) -> Result<DatabaseSchema> {
         let start_time = std::time::Instant::now();
         let mut warnings = Vec::new();
@@ -150,23 +173,80 @@ async fn collect_schema_internal(&self) -> Result<DatabaseSchema> {
             database_name
         );
 
+        let collection_names: Vec<String> = collections.iter().map(|c| c.name.clone()).collect();
+        let database_info = database_info.with_access_probe(
+            access::probe_access(&self.client, &database_name, &collection_names).await,
+        );
+
+        // View pipelines and validators come from the collection creation options
+        let definitions = list_collection_definitions(&self.client, &database_name)
+            .await
+            .unwrap_or_else(|e| {
+                let warning = format!(
+                    "Failed to collect view definitions and validators for '{}': {}",
+                    database_name, e
+                );
+                tracing::warn!("{}", warning);
+                warnings.push(CollectionWarning::new(WarningCategory::Collection, warning));
+                std::collections::HashMap::new()
+            });
+
         // Collect schema for each collection
         let mut tables = Vec::new();
+        let mut views = Vec::new();
         let mut all_indexes = Vec::new();
+        let mut object_failures = Vec::new();
         let sampling_config = SamplingConfig::default();
 
         for collection_info in &collections {
-            // Skip views - they don't have their own schema
-            if collection_info.collection_type == CollectionType::View {
-                tracing::debug!("Skipping view: {}", collection_info.name);
+            let definition = definitions.get(&collection_info.name);
+
+            // Views don't have their own schema; record their pipeline instead
+            if collection_info.collection_type == CollectionType::View
+                || definition.is_some_and(|d| d.is_view)
+            {
+                if !self.objects.views {
+                    continue;
+                }
+                tracing::debug!("Collecting view definition: {}", collection_info.name);
+                if let Some(definition) = definition {
+                    views.push(definition.to_view(&database_name));
+                }
                 continue;
             }
 
-            match self
-                .collect_collection_schema(&database_name, &collection_info.name, &sampling_config)
-                .await
-            {
+            let collected = collect_within_table_timeout(
+                self.collect_collection_schema(
+                    &database_name,
+                    &collection_info.name,
+                    &sampling_config,
+                    definition.and_then(|d| d.validator.clone()),
+                    definition.and_then(|d| d.options.clone()),
+                ),
+                self.config.table_timeout,
+                Some(&database_name),
+                &collection_info.name,
+                &mut object_failures,
+            )
+            .await;
+            // A collection that timed out is already recorded as a failure
+            let Some(collected) = collected else {
+                continue;
+            };
+            match collected {
                 Ok((table, indexes)) => {
+                    if let Some(validator) = &table.validator {
+                        for discrepancy in &validator.discrepancies {
+                            warnings.push(
+                                CollectionWarning::new(
+                                    WarningCategory::General,
+                                    format!("Validator discrepancy: {}", discrepancy),
+                                )
+                                .with_severity(WarningSeverity::Info)
+                                .with_object(collection_info.name.clone()),
+                            );
+                        }
+                    }
                     tracing::debug!(
                         "Collected schema for collection '{}' with {} fields",
                         collection_info.name,
@@ -176,12 +256,18 @@ async fn collect_schema_internal(&self) -> Result<DatabaseSchema> {
                     all_indexes.extend(indexes);
                 }
                 Err(e) => {
-                    let warning = format!(
+                    tracing::warn!(
                         "Failed to collect schema for collection '{}': {}",
-                        collection_info.name, e
+                        collection_info.name,
+                        e
+                    );
+                    warnings.push(
+                        CollectionWarning::new(
+                            WarningCategory::ObjectFailure,
+                            format!("Failed to collect schema: {}", e),
+                        )
+                        .with_object(collection_info.name.clone()),
                     );
-                    tracing::warn!("{}", warning);
-                    warnings.push(warning);
                 }
             }
         }
@@ -195,11 +281,11 @@ async fn collect_schema_internal(&self) -> Result<DatabaseSchema> {
             tables.iter().map(|t| t.columns.len()).sum::<usize>()
         );
 
-        Ok(DatabaseSchema {
+        let schema = DatabaseSchema {
             format_version: FORMAT_VERSION.to_string(),
             database_info,
             tables,
-            views: Vec::new(),
+            views,
             indexes: all_indexes,
             constraints: Vec::new(),
             procedures: Vec::new(),
@@ -214,8 +300,16 @@ async fn collect_schema_internal(&self) -> Result<DatabaseSchema> {
                     .unwrap_or(u64::MAX),
                 collector_version: env!("CARGO_PKG_VERSION").to_string(),
                 warnings,
+                object_failures: Vec::new(),
+                degraded: Vec::new(),
+                read_only: None,
+                integrity: None,
+                auxiliary_files: Vec::new(),
+                settings: None,
             },
-        })
+        };
+
+        Ok(schema.with_object_failures(object_failures))
     }
 
     /// Collects database information for a MongoDB database.
@@ -246,23 +340,33 @@ async fn collect_database_info(&self, database_name: &str) -> Result<DatabaseInf
 
         Ok(DatabaseInfo {
             name: database_name.to_string(),
+            engine: Some(DatabaseType::MongoDB),
             version,
             size_bytes,
             encoding: Some("UTF-8".to_string()), // MongoDB uses UTF-8
             collation: None,
+            timezone: Some("UTC".to_string()),
             owner: None,
             is_system_database,
             access_level: AccessLevel::Full,
+            access_probe: None,
+            schemas: Vec::new(),
             collection_status: CollectionStatus::Success,
         })
     }
 
     /// Collects schema for a single collection by inferring from document samples.
+    ///
+    /// When the collection declares a validator, it is attached to the table
+    /// together with any discrepancies against the inferred structure.
+    /// Capped and time-series collections carry their creation options.
     async fn collect_collection_schema(
         &self,
         database_name: &str,
         collection_name: &str,
         sampling_config: &SamplingConfig,
+        validator: Option<SchemaValidator>,
+        table_options: Option<TableOptions>,
     ) -> Result<(Table, Vec<Index>)> {
         let db = self.client.database(database_name);
         let collection = db.collection::<mongodb::bson::Document>(collection_name);
@@ -276,6 +380,16 @@ async fn collect_collection_schema(
         let row_count = stats
             .as_ref()
             .and_then(|s| s.get_i64("count").ok().map(|c| c.max(0) as u64));
+        // Storage plus index size; MongoDB returns either as i32 or i64
+        let stat = |name: &str| {
+            stats.as_ref().and_then(|s| {
+                s.get_i64(name)
+                    .ok()
+                    .or_else(|| s.get_i32(name).ok().map(i64::from))
+            })
+        };
+        let size_bytes = stat("storageSize")
+            .map(|storage| (storage + stat("totalIndexSize").unwrap_or(0)).max(0) as u64);
 
         // Sample documents to infer schema
         let mut inferrer = SchemaInferrer::new();
@@ -322,6 +436,11 @@ async fn collect_collection_schema(
         // Finalize schema inference
         let inferred_schema = inferrer.finalize(collection_name.to_string());
         let columns = inferred_schema.to_columns();
+        let validator = validator.map(|mut validator| {
+            validator.discrepancies =
+                validators::find_discrepancies(&validator.definition, &inferred_schema);
+            validator
+        });
 
         // Determine primary key (always _id in MongoDB)
         let primary_key = if columns.iter().any(|c| c.name == "_id") {
@@ -334,24 +453,31 @@ async fn collect_collection_schema(
         };
 
         // Collect indexes
-        let indexes = self
-            .collect_collection_indexes(database_name, collection_name)
-            .await
-            .unwrap_or_default();
+        let indexes = collect_if_selected(
+            self.objects.indexes,
+            self.collect_collection_indexes(database_name, collection_name),
+        )
+        .await
+        .unwrap_or_default();
 
         let table = Table {
-            name: collection_name.to_string(),
-            schema: Some(database_name.to_string()),
-            columns,
             primary_key,
-            foreign_keys: Vec::new(), // MongoDB doesn't have foreign keys
             indexes: indexes.clone(),
-            constraints: Vec::new(),
             comment: Some(format!(
-                "MongoDB collection (sampled {} documents)",
+                "MongoDB {} (sampled {} documents)",
+                collection_kind(table_options.as_ref()),
                 inferred_schema.documents_sampled
             )),
             row_count,
+            size_bytes,
+            validator,
+            options: table_options,
+            // MongoDB has no foreign keys or constraints
+            ..Table::new(
+                collection_name.to_string(),
+                Some(database_name.to_string()),
+                columns,
+            )
         };
 
         Ok((table, indexes))
@@ -397,41 +523,11 @@ async fn collect_collection_indexes(
                 )
             })?;
 
-            let options = index_model.options;
-            let name = options
-                .as_ref()
-                .and_then(|o| o.name.clone())
-                .unwrap_or_else(|| "unnamed".to_string());
-            let is_unique = options.as_ref().and_then(|o| o.unique).unwrap_or(false);
-
-            // Parse index keys
-            let columns: Vec<IndexColumn> = index_model
-                .keys
-                .iter()
-                .map(|(key, value)| {
-                    let sort_order = match value.as_i32() {
-                        Some(1) => Some(SortDirection::Ascending),
-                        Some(-1) => Some(SortDirection::Descending),
-                        _ => None,
-                    };
-                    IndexColumn {
-                        name: key.clone(),
-                        sort_order,
-                    }
-                })
-                .collect();
-
-            let is_primary = name == "_id_";
-
-            indexes.push(Index {
-                name,
-                table_name: collection_name.to_string(),
-                schema: Some(database_name.to_string()),
-                columns,
-                is_unique,
-                is_primary,
-                index_type: Some("btree".to_string()), // MongoDB primarily uses B-tree indexes
-            });
+            indexes.push(indexes::index_from_model(
+                index_model,
+                database_name,
+                collection_name,
+            ));
         }
 
         Ok(indexes)
@@ -492,4 +588,25 @@ pub async fn list_databases(&self, include_system: bool) -> Result<Vec<Enumerate
     pub async fn list_collections(&self, database: &str) -> Result<Vec<EnumeratedCollection>> {
         enumeration::list_collections(&self.client, database).await
     }
+
+    /// Collects schemas from all databases on the server.
+    ///
+    /// # Errors
+    /// Returns an error if database enumeration fails. Per-database failures
+    /// are reported in the result.
+    pub async fn collect_all_databases(
+        &self,
+        config: &MultiDatabaseConfig,
+    ) -> Result<MultiDatabaseResult> {
+        multi_database::collect_all_databases(self, config).await
+    }
+}
+
+/// Kind of collection named in table comments.
+fn collection_kind(options: Option<&TableOptions>) -> &'static str {
+    match options {
+        Some(options) if options.time_series.is_some() => "time-series collection",
+        Some(options) if options.capped.is_some() => "capped collection",
+        _ => "collection",
+    }
 }
@@ -0,0 +1,125 @@
+//! Multi-database collection orchestration for MongoDB.
+//!
+//! Databases come from `listDatabases` and are collected through
+//! [`crate::adapters::multi_database`], producing the same
+//! [`MultiDatabaseResult`] as the SQL adapters. Per-database adapters share
+//! the server's client, so no additional connections are opened.
+//!
+//! # Security
+//! - All operations are read-only
+//! - Credentials are never logged or included in results
+
+use super::MongoAdapter;
+use crate::Result;
+use crate::adapters::DatabaseAdapter;
+use crate::adapters::multi_database::{
+    DatabaseCandidate, MultiDatabaseConfig, MultiDatabaseResult, collect_databases,
+};
+use crate::models::{CollectionMode, DatabaseType, ServerInfo};
+use mongodb::bson::doc;
+
+/// Collects schema information from all databases on the server.
+///
+/// # Errors
+///
+/// Returns an error if database enumeration fails. Per-database failures
+/// are reported in the result.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let adapter = MongoAdapter::new("mongodb://localhost:27017").await?;
+/// let result = collect_all_databases(&adapter, &MultiDatabaseConfig::new()).await?;
+/// println!("Collected {} databases", result.databases.len());
+/// ```
+pub async fn collect_all_databases(
+    adapter: &MongoAdapter,
+    config: &MultiDatabaseConfig,
+) -> Result<MultiDatabaseResult> {
+    let candidates = adapter
+        .list_databases(true)
+        .await?
+        .into_iter()
+        .map(|db| DatabaseCandidate {
+            name: db.name,
+            is_system_database: db.is_system_database,
+            is_accessible: db.is_accessible,
+        })
+        .collect();
+
+    let server_info = get_server_info(adapter).await;
+
+    tracing::info!(
+        "Connected to {} {} at {}:{}",
+        server_info.server_type,
+        server_info.version,
+        server_info.host,
+        server_info.port.unwrap_or(27017)
+    );
+
+    Ok(
+        collect_databases(server_info, candidates, config, |name| async move {
+            adapter.for_database(&name)?.collect_schema().await
+        })
+        .await,
+    )
+}
+
+/// Gets server-level information from the MongoDB server.
+///
+/// `buildInfo` and `connectionStatus` are best-effort: restricted users
+/// may not be allowed to run them. Database counts are filled in by
+/// [`collect_databases`].
+async fn get_server_info(adapter: &MongoAdapter) -> ServerInfo {
+    let admin_db = adapter.client.database("admin");
+
+    let version = admin_db
+        .run_command(doc! { "buildInfo": 1 })
+        .await
+        .ok()
+        .and_then(|info| info.get_str("version").ok().map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let status = admin_db
+        .run_command(doc! { "connectionStatus": 1 })
+        .await
+        .ok();
+    let auth_info = status
+        .as_ref()
+        .and_then(|status| status.get_document("authInfo").ok());
+    let connection_user = auth_info
+        .and_then(|info| info.get_array("authenticatedUsers").ok())
+        .and_then(|users| users.first())
+        .and_then(|user| user.as_document())
+        .and_then(|user| user.get_str("user").ok().map(str::to_string))
+        .or_else(|| adapter.config.username.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+    let has_superuser = auth_info
+        .and_then(|info| info.get_array("authenticatedUserRoles").ok())
+        .is_some_and(|roles| {
+            roles
+                .iter()
+                .filter_map(|role| role.as_document())
+                .any(|role| role.get_str("role") == Ok("root") && role.get_str("db") == Ok("admin"))
+        });
+
+    ServerInfo {
+        server_type: DatabaseType::MongoDB,
+        version,
+        host: adapter.config.host.clone(),
+        port: adapter.config.port,
+        total_databases: 0,
+        collected_databases: 0,
+        system_databases_excluded: 0,
+        connection_user,
+        has_superuser_privileges: has_superuser,
+        // BSON dates are always stored in UTC
+        timezone: Some("UTC".to_string()),
+        role: None,
+        collection_mode: CollectionMode::MultiDatabase {
+            discovered: 0,
+            collected: 0,
+            failed: 0,
+        },
+    }
+}
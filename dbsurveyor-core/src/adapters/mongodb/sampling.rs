@@ -4,17 +4,24 @@
 //! collections for schema inference and data analysis.
 //!
 //! # Sampling Strategies
-//! - Most recent: Order by `_id` descending (ObjectId includes timestamp)
+//! - Most recent: Order by `_id` descending (ObjectId includes timestamp),
+//!   or by the time field of a time-series collection
 //! - Random: Use `$sample` aggregation stage
 //! - Natural order: Use natural document order
 
+use super::type_mapping::bson_type_name;
 use crate::Result;
 use crate::adapters::config::SamplingConfig;
-use crate::models::{OrderingStrategy, SampleStatus, SamplingStrategy, SortDirection, TableSample};
+use crate::adapters::helpers::{binary_sample_value, float_sample_value, format_utc_timestamp};
+use crate::models::{
+    OrderingStrategy, SampleColumnType, SampleStatus, SampleValueEncoding, SamplingStrategy,
+    SortDirection, TableSample,
+};
 use mongodb::Client;
-use mongodb::bson::{Document, doc};
+use mongodb::bson::{Bson, Document, doc};
 use mongodb::options::FindOptions;
 use serde_json::Value as JsonValue;
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Common timestamp field names used for ordering by "most recent".
@@ -34,9 +41,11 @@
 /// Detects the best ordering strategy for sampling a collection.
 ///
 /// MongoDB has some natural ordering options:
-/// 1. `_id` field (ObjectId contains timestamp, so descending = most recent)
-/// 2. Timestamp fields (createdAt, updatedAt, etc.)
-/// 3. Natural order (order documents are stored on disk)
+/// 1. The declared time field of a time-series collection, whose `_id`
+///    values are not indexed
+/// 2. `_id` field (ObjectId contains timestamp, so descending = most recent)
+/// 3. Timestamp fields (createdAt, updatedAt, etc.)
+/// 4. Natural order (order documents are stored on disk)
 ///
 /// # Arguments
 /// * `client` - MongoDB client
@@ -53,6 +62,19 @@ pub async fn detect_ordering_strategy(
     let db = client.database(database);
     let coll = db.collection::<Document>(collection);
 
+    if let Some(time_field) = time_series_field(client, database, collection).await {
+        tracing::debug!(
+            "Using time-series time field ordering for {}.{}: {}",
+            database,
+            collection,
+            time_field
+        );
+        return Ok(OrderingStrategy::Timestamp {
+            column: time_field,
+            direction: SortDirection::Descending,
+        });
+    }
+
     // Get a sample document to analyze its structure
     let sample_doc = coll.find_one(doc! {}).await.map_err(|e| {
         crate::error::DbSurveyorError::collection_failed(
@@ -98,6 +120,22 @@ pub async fn detect_ordering_strategy(
     })
 }
 
+/// Time field of `collection` if it is a time-series collection. Lookup
+/// failures are treated as an ordinary collection.
+async fn time_series_field(client: &Client, database: &str, collection: &str) -> Option<String> {
+    let mut cursor = client
+        .database(database)
+        .list_collections()
+        .filter(doc! { "name": collection })
+        .await
+        .ok()?;
+    if !cursor.advance().await.ok()? {
+        return None;
+    }
+    let spec = cursor.deserialize_current().ok()?;
+    spec.options.timeseries.map(|options| options.time_field)
+}
+
 /// Generates a MongoDB sort document for the given ordering strategy.
 ///
 /// # Arguments
@@ -197,9 +235,10 @@ pub async fn sample_collection(
         }
     };
 
+    let mut field_types = FieldTypes::default();
     let rows: Vec<JsonValue> = if use_random {
         // Use $sample aggregation for random sampling
-        sample_random_as_json(client, database, collection, config.sample_size).await?
+        sample_random_as_json(client, database, collection, config, &mut field_types).await?
     } else {
         // Use find with sort for ordered sampling
         let sort_doc = generate_sort_document(&strategy, true);
@@ -229,7 +268,7 @@ pub async fn sample_collection(
                 e,
             )
         })? {
-            let doc = cursor.deserialize_current().map_err(|e| {
+            let mut doc = cursor.deserialize_current().map_err(|e| {
                 crate::error::DbSurveyorError::collection_failed(
                     format!(
                         "Failed to deserialize document from '{}.{}'",
@@ -238,6 +277,8 @@ pub async fn sample_collection(
                     e,
                 )
             })?;
+            drop_skipped_fields(&mut doc, config);
+            field_types.observe(&doc);
             json_rows.push(bson_doc_to_json(&doc));
         }
         json_rows
@@ -265,6 +306,7 @@ pub async fn sample_collection(
         collected_at: chrono::Utc::now(),
         warnings,
         sample_status: Some(SampleStatus::Complete),
+        column_types: field_types.into_legend(),
     })
 }
 
@@ -274,13 +316,14 @@ async fn sample_random_as_json(
     client: &Client,
     database: &str,
     collection: &str,
-    sample_size: u32,
+    config: &SamplingConfig,
+    field_types: &mut FieldTypes,
 ) -> Result<Vec<JsonValue>> {
     let db = client.database(database);
     let coll = db.collection::<Document>(collection);
 
     // Use $sample aggregation stage
-    let pipeline = vec![doc! { "$sample": { "size": i64::from(sample_size) } }];
+    let pipeline = vec![doc! { "$sample": { "size": i64::from(config.sample_size) } }];
 
     let mut cursor = coll.aggregate(pipeline).await.map_err(|e| {
         crate::error::DbSurveyorError::collection_failed(
@@ -302,7 +345,7 @@ async fn sample_random_as_json(
             e,
         )
     })? {
-        let doc = cursor.deserialize_current().map_err(|e| {
+        let mut doc = cursor.deserialize_current().map_err(|e| {
             crate::error::DbSurveyorError::collection_failed(
                 format!(
                     "Failed to deserialize sampled document from '{}.{}'",
@@ -311,21 +354,115 @@ async fn sample_random_as_json(
                 e,
             )
         })?;
+        drop_skipped_fields(&mut doc, config);
+        field_types.observe(&doc);
         rows.push(bson_doc_to_json(&doc));
     }
 
     Ok(rows)
 }
 
+/// Removes top-level fields matched by a `skip-column` pattern as each
+/// document arrives. Documents have no fixed field list to leave out of the
+/// query, so unlike the SQL adapters the values are read but never kept.
+fn drop_skipped_fields(doc: &mut Document, config: &SamplingConfig) {
+    let skipped: Vec<String> = doc
+        .keys()
+        .filter(|key| config.skips_column(key))
+        .cloned()
+        .collect();
+    for key in skipped {
+        doc.remove(&key);
+    }
+}
+
+/// BSON types of the top-level fields seen across sampled documents.
+///
+/// Fields keep the order they were first seen in. A field holding more than
+/// one non-null type is reported as `mixed`.
+#[derive(Debug, Default)]
+struct FieldTypes {
+    fields: Vec<(String, Option<&'static str>)>,
+    positions: HashMap<String, usize>,
+}
+
+impl FieldTypes {
+    fn observe(&mut self, doc: &Document) {
+        for (key, value) in doc {
+            if matches!(value, Bson::Null) {
+                continue;
+            }
+            let type_name = bson_type_name(value);
+            match self.positions.get(key) {
+                Some(&index) => {
+                    let seen = &mut self.fields[index].1;
+                    if *seen != Some(type_name) {
+                        *seen = None;
+                    }
+                }
+                None => {
+                    self.positions.insert(key.clone(), self.fields.len());
+                    self.fields.push((key.clone(), Some(type_name)));
+                }
+            }
+        }
+    }
+
+    fn into_legend(self) -> Vec<SampleColumnType> {
+        self.fields
+            .into_iter()
+            .map(|(name, type_name)| {
+                let encoding = match type_name {
+                    Some("decimal") => SampleValueEncoding::DecimalString,
+                    Some("binData") => SampleValueEncoding::Base64,
+                    Some("double") => SampleValueEncoding::Float,
+                    Some("date") => SampleValueEncoding::TimestampWithOffset,
+                    _ => SampleValueEncoding::Json,
+                };
+                SampleColumnType::new(name, type_name.unwrap_or("mixed"), encoding)
+            })
+            .collect()
+    }
+}
+
 /// Converts a BSON document to a JSON value.
 ///
-/// This handles special BSON types like ObjectId, DateTime, etc.
-/// Logs a warning and returns Null if serialization fails.
+/// Dates become RFC 3339 strings in UTC, Decimal128 values become decimal
+/// strings, and binary data becomes `{"base64", "length"}`. Other special
+/// BSON types like ObjectId use their extended JSON form.
 fn bson_doc_to_json(doc: &Document) -> JsonValue {
-    match serde_json::to_value(doc) {
+    JsonValue::Object(
+        doc.iter()
+            .map(|(key, value)| (key.clone(), bson_to_json(value)))
+            .collect(),
+    )
+}
+
+/// Converts a BSON value to JSON.
+fn bson_to_json(value: &Bson) -> JsonValue {
+    match value {
+        Bson::Document(doc) => bson_doc_to_json(doc),
+        Bson::Array(items) => JsonValue::Array(items.iter().map(bson_to_json).collect()),
+        Bson::DateTime(dt) => chrono::DateTime::from_timestamp_millis(dt.timestamp_millis())
+            .map_or_else(
+                || extended_json(value),
+                |date| JsonValue::String(format_utc_timestamp(&date)),
+            ),
+        Bson::Double(number) => float_sample_value(*number),
+        Bson::Decimal128(decimal) => JsonValue::String(decimal.to_string()),
+        Bson::Binary(binary) => binary_sample_value(&binary.bytes),
+        _ => extended_json(value),
+    }
+}
+
+/// Serializes a BSON value as extended JSON.
+///
+/// Logs a warning and returns Null if serialization fails.
+fn extended_json(value: &Bson) -> JsonValue {
+    match serde_json::to_value(value) {
         Ok(value) => value,
         Err(e) => {
-            tracing::warn!("Failed to convert BSON document to JSON: {}", e);
+            tracing::warn!("Failed to convert BSON value to JSON: {}", e);
             JsonValue::Null
         }
     }
@@ -396,4 +533,58 @@ fn test_bson_doc_to_json_with_nested() {
         assert_eq!(json["profile"]["firstName"], "John");
         assert!(json["tags"].is_array());
     }
+
+    #[test]
+    fn test_bson_doc_to_json_dates_are_utc_rfc3339() {
+        let created = mongodb::bson::DateTime::from_millis(1_704_164_645_120);
+        let doc = doc! {
+            "createdAt": created,
+            "history": [{ "at": created }],
+            "_id": mongodb::bson::oid::ObjectId::parse_str("65940f25a1b2c3d4e5f60718")
+                .expect("valid ObjectId")
+        };
+
+        let json = bson_doc_to_json(&doc);
+        assert_eq!(json["createdAt"], "2024-01-02T03:04:05.120Z");
+        assert_eq!(json["history"][0]["at"], "2024-01-02T03:04:05.120Z");
+        assert_eq!(json["_id"]["$oid"], "65940f25a1b2c3d4e5f60718");
+    }
+
+    #[test]
+    fn test_bson_doc_to_json_preserves_decimal_binary_and_floats() {
+        let doc = doc! {
+            "price": "12.50".parse::<mongodb::bson::Decimal128>().expect("valid decimal"),
+            "payload": mongodb::bson::Binary {
+                subtype: mongodb::bson::spec::BinarySubtype::Generic,
+                bytes: vec![0xde, 0xad, 0xbe, 0xef],
+            },
+            "ratio": f64::NAN,
+            "limit": f64::NEG_INFINITY,
+            "score": 1.5
+        };
+
+        let json = bson_doc_to_json(&doc);
+        assert_eq!(json["price"], "12.50");
+        assert_eq!(json["payload"]["base64"], "3q2+7w==");
+        assert_eq!(json["payload"]["length"], 4);
+        assert_eq!(json["ratio"], "NaN");
+        assert_eq!(json["limit"], "-Infinity");
+        assert_eq!(json["score"], 1.5);
+    }
+
+    #[test]
+    fn test_field_types_legend() {
+        let mut field_types = FieldTypes::default();
+        field_types.observe(&doc! { "price": 1.5, "note": "a", "tag": mongodb::bson::Bson::Null });
+        field_types.observe(&doc! { "price": 2.5, "note": 3, "tag": "x" });
+
+        let legend = field_types.into_legend();
+        let names: Vec<&str> = legend.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["price", "note", "tag"]);
+        assert_eq!(legend[0].data_type, "double");
+        assert_eq!(legend[0].encoding, SampleValueEncoding::Float);
+        assert_eq!(legend[1].data_type, "mixed");
+        assert_eq!(legend[1].encoding, SampleValueEncoding::Json);
+        assert_eq!(legend[2].data_type, "string");
+    }
 }
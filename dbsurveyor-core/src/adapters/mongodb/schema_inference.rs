@@ -67,12 +67,20 @@ pub fn to_columns(&self) -> Vec<Column> {
                 is_primary_key: field.name == "_id",
                 is_auto_increment: field.name == "_id", // ObjectId is auto-generated
                 default_value: None,
+                normalized_default: None,
                 comment: if field.observed_types.len() > 1 {
                     Some(format!("Mixed types: {}", field.observed_types.join(", ")))
                 } else {
                     None
                 },
                 ordinal_position: field.ordinal_position,
+                generated: None,
+                declared_type: None,
+                collation: None,
+                character_set: None,
+                storage: None,
+                identity: None,
+                masking: None,
             })
             .collect()
     }
@@ -264,8 +272,9 @@ fn type_name_to_unified(type_name: &str) -> UnifiedDataType {
                 type_name: "unknown".to_string(),
             }),
         },
-        "decimal" => UnifiedDataType::Float {
-            precision: Some(128),
+        "decimal" => UnifiedDataType::Decimal {
+            precision: None,
+            scale: None,
         },
         _ => UnifiedDataType::Custom {
             type_name: type_name.to_string(),
@@ -449,5 +458,9 @@ fn test_type_name_to_unified() {
                 max_length: Some(24)
             }
         ));
+        assert!(matches!(
+            type_name_to_unified("decimal"),
+            UnifiedDataType::Decimal { .. }
+        ));
     }
 }
@@ -4,6 +4,56 @@
 use crate::adapters::ConnectionConfig;
 use std::time::Duration;
 
+#[test]
+fn test_classify_access() {
+    use super::access::classify_access;
+    use mongodb::bson::doc;
+
+    let collections = vec!["orders".to_string(), "users".to_string()];
+
+    let open = classify_access(&doc! {}, "shop", &collections);
+    assert!(open.superuser);
+    assert_eq!(open.access_level(), AccessLevel::Full);
+
+    let root = doc! {
+        "authenticatedUsers": [{ "user": "admin", "db": "admin" }],
+        "authenticatedUserRoles": [{ "role": "root", "db": "admin" }],
+    };
+    assert!(classify_access(&root, "shop", &collections).superuser);
+
+    let reader = doc! {
+        "authenticatedUsers": [{ "user": "app", "db": "shop" }],
+        "authenticatedUserRoles": [{ "role": "ordersReader", "db": "shop" }],
+        "authenticatedUserPrivileges": [
+            { "resource": { "db": "shop", "collection": "orders" }, "actions": ["find"] },
+            { "resource": { "db": "shop", "collection": "" }, "actions": ["listIndexes"] },
+        ],
+    };
+    let probe = classify_access(&reader, "shop", &collections);
+    assert!(!probe.superuser);
+    assert!(!probe.catalog_access);
+    assert_eq!(probe.readable_tables, 1);
+    assert_eq!(probe.access_level(), AccessLevel::Limited);
+    assert_eq!(probe.evidence[0], "roles: ordersReader@shop");
+    assert_eq!(probe.identity.as_deref(), Some("app@shop"));
+    assert_eq!(open.identity, None);
+
+    let read_role = doc! {
+        "authenticatedUsers": [{ "user": "app", "db": "shop" }],
+        "authenticatedUserRoles": [{ "role": "read", "db": "shop" }],
+        "authenticatedUserPrivileges": [
+            { "resource": { "db": "shop", "collection": "" }, "actions": ["find", "listCollections"] },
+        ],
+    };
+    let probe = classify_access(&read_role, "shop", &collections);
+    assert_eq!(probe.readable_tables, 2);
+    assert_eq!(probe.access_level(), AccessLevel::Full);
+    assert_eq!(
+        classify_access(&read_role, "billing", &collections).readable_tables,
+        0
+    );
+}
+
 #[test]
 fn test_parse_connection_config() {
     let connection_string = "mongodb://testuser@localhost:27017/testdb";
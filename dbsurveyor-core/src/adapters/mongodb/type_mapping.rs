@@ -107,9 +107,11 @@ pub fn map_bson_to_unified(value: &Bson) -> UnifiedDataType {
         // Symbols (deprecated in MongoDB)
         Bson::Symbol(_) => UnifiedDataType::String { max_length: None },
 
-        // Decimal128 for precise decimal arithmetic
-        Bson::Decimal128(_) => UnifiedDataType::Float {
-            precision: Some(128),
+        // Decimal128 for precise decimal arithmetic; 34 significant digits
+        // with a per-value exponent, so no fixed precision or scale applies
+        Bson::Decimal128(_) => UnifiedDataType::Decimal {
+            precision: None,
+            scale: None,
         },
 
         // Min/Max keys (internal MongoDB types)
@@ -303,12 +305,13 @@ fn test_map_null() {
     fn test_map_decimal128() {
         let bson = Bson::Decimal128(Decimal128::from_bytes([0; 16]));
         let unified = map_bson_to_unified(&bson);
-        assert!(matches!(
+        assert_eq!(
             unified,
-            UnifiedDataType::Float {
-                precision: Some(128)
+            UnifiedDataType::Decimal {
+                precision: None,
+                scale: None
             }
-        ));
+        );
     }
 
     #[test]
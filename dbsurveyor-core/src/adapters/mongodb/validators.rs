@@ -0,0 +1,528 @@
+//! MongoDB view definitions, `$jsonSchema` validators, and collection kinds.
+//!
+//! `listCollections` reports the options each collection was created with,
+//! including view pipelines, declared validators, and the size limits of
+//! capped collections or the bucketing of time-series collections. This
+//! module turns those options into unified models and compares declared
+//! validators against the structure inferred from sampled documents.
+//!
+//! # Discrepancy Detection
+//! A validator is treated as the authoritative declared schema. The
+//! inferred schema is checked against it for:
+//! - Declared properties never observed in sampled documents
+//! - Observed fields that are not declared
+//! - Required properties missing from some sampled documents
+//! - Observed BSON types that the declared `bsonType`/`type` does not allow
+
+use super::schema_inference::InferredSchema;
+use crate::Result;
+use crate::models::{CappedCollection, SchemaValidator, TableOptions, TimeSeriesCollection, View};
+use mongodb::Client;
+use mongodb::bson::{Bson, Document};
+use mongodb::options::{CreateCollectionOptions, ValidationAction, ValidationLevel};
+use mongodb::results::{CollectionSpecification, CollectionType as SpecCollectionType};
+use std::collections::{HashMap, HashSet};
+
+/// Creation options of a collection or view as reported by `listCollections`.
+#[derive(Debug, Clone)]
+pub struct CollectionDefinition {
+    /// Collection or view name
+    pub name: String,
+    /// Whether this entry is a view
+    pub is_view: bool,
+    /// Source collection of a view
+    pub view_on: Option<String>,
+    /// Aggregation pipeline of a view
+    pub pipeline: Option<Vec<Document>>,
+    /// Declared validator, if any
+    pub validator: Option<SchemaValidator>,
+    /// Capped or time-series options, for those collection kinds
+    pub options: Option<TableOptions>,
+}
+
+impl CollectionDefinition {
+    /// Builds a definition from a driver collection specification.
+    pub fn from_specification(spec: &CollectionSpecification) -> Self {
+        let options = &spec.options;
+        let validator = options
+            .validator
+            .as_ref()
+            .map(|definition| SchemaValidator {
+                definition: Bson::Document(definition.clone()).into_relaxed_extjson(),
+                validation_level: options.validation_level.as_ref().map(validation_level_name),
+                validation_action: options
+                    .validation_action
+                    .as_ref()
+                    .map(validation_action_name),
+                discrepancies: Vec::new(),
+            });
+
+        Self {
+            name: spec.name.clone(),
+            is_view: spec.collection_type == SpecCollectionType::View,
+            view_on: options.view_on.clone(),
+            pipeline: options.pipeline.clone(),
+            validator,
+            options: collection_options(options),
+        }
+    }
+
+    /// Converts a view definition into a unified `View`.
+    ///
+    /// The definition is stored as JSON with the source collection and the
+    /// pipeline, mirroring the arguments of `db.createView()`.
+    pub fn to_view(&self, database_name: &str) -> View {
+        let pipeline: Vec<serde_json::Value> = self
+            .pipeline
+            .iter()
+            .flatten()
+            .map(|stage| Bson::Document(stage.clone()).into_relaxed_extjson())
+            .collect();
+        let definition = serde_json::json!({
+            "viewOn": self.view_on,
+            "pipeline": pipeline,
+        });
+
+        View {
+            name: self.name.clone(),
+            schema: Some(database_name.to_string()),
+            definition: Some(definition.to_string()),
+            columns: Vec::new(),
+            comment: self
+                .view_on
+                .as_ref()
+                .map(|source| format!("MongoDB view on '{}'", source)),
+        }
+    }
+}
+
+/// Lists creation options (view pipelines, validators, capped and
+/// time-series options) for all collections in a database, keyed by
+/// collection name.
+///
+/// # Errors
+/// Returns an error if `listCollections` fails or a specification cannot be
+/// deserialized.
+pub async fn list_collection_definitions(
+    client: &Client,
+    database_name: &str,
+) -> Result<HashMap<String, CollectionDefinition>> {
+    let db = client.database(database_name);
+
+    let mut cursor = db.list_collections().await.map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed(
+            format!(
+                "Failed to list collection definitions in database '{}'",
+                database_name
+            ),
+            e,
+        )
+    })?;
+
+    let mut definitions = HashMap::new();
+    while cursor.advance().await.map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed(
+            format!(
+                "Failed to iterate collection definitions in database '{}'",
+                database_name
+            ),
+            e,
+        )
+    })? {
+        let spec = cursor.deserialize_current().map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                format!(
+                    "Failed to deserialize collection definition in database '{}'",
+                    database_name
+                ),
+                e,
+            )
+        })?;
+        if spec.name.starts_with("system.") {
+            continue;
+        }
+        let definition = CollectionDefinition::from_specification(&spec);
+        definitions.insert(definition.name.clone(), definition);
+    }
+
+    Ok(definitions)
+}
+
+/// Capped and time-series options, or `None` for an ordinary collection.
+fn collection_options(options: &CreateCollectionOptions) -> Option<TableOptions> {
+    let capped = options.capped.unwrap_or(false).then(|| CappedCollection {
+        max_bytes: options.size,
+        max_documents: options.max.filter(|max| *max > 0),
+    });
+    let time_series = options
+        .timeseries
+        .as_ref()
+        .map(|time_series| TimeSeriesCollection {
+            time_field: time_series.time_field.clone(),
+            meta_field: time_series.meta_field.clone(),
+            granularity: time_series
+                .granularity
+                .as_ref()
+                .map(|granularity| format!("{:?}", granularity).to_lowercase()),
+            expire_after_seconds: options.expire_after_seconds.map(|expiry| expiry.as_secs()),
+        });
+    (capped.is_some() || time_series.is_some()).then(|| TableOptions {
+        capped,
+        time_series,
+        ..TableOptions::default()
+    })
+}
+
+/// Compares a declared validator with the inferred schema of a collection.
+///
+/// Only the `$jsonSchema` portion of the validator is analyzed; query-operator
+/// validators are stored but not compared. Returns human-readable discrepancy
+/// descriptions, or an empty vector when the structures agree or no documents
+/// were sampled.
+pub fn find_discrepancies(validator: &serde_json::Value, inferred: &InferredSchema) -> Vec<String> {
+    let Some(json_schema) = validator.get("$jsonSchema") else {
+        return Vec::new();
+    };
+    if inferred.documents_sampled == 0 {
+        return Vec::new();
+    }
+
+    let mut declared = DeclaredSchema::default();
+    declared.collect(json_schema, "");
+
+    let mut discrepancies = Vec::new();
+
+    for (path, property) in &declared.properties {
+        match inferred.fields.iter().find(|f| &f.name == path) {
+            None => discrepancies.push(format!(
+                "Declared field '{}' was not observed in {} sampled documents",
+                path, inferred.documents_sampled
+            )),
+            Some(field) => {
+                if property.required && field.occurrence_count < inferred.documents_sampled {
+                    discrepancies.push(format!(
+                        "Required field '{}' is missing from {} of {} sampled documents",
+                        path,
+                        inferred
+                            .documents_sampled
+                            .saturating_sub(field.occurrence_count),
+                        inferred.documents_sampled
+                    ));
+                }
+                if !property.allowed_types.is_empty() {
+                    let mut unexpected: Vec<&str> = field
+                        .observed_types
+                        .iter()
+                        .map(String::as_str)
+                        .filter(|observed| {
+                            !property
+                                .allowed_types
+                                .iter()
+                                .any(|allowed| declared_type_allows(allowed, observed))
+                        })
+                        .collect();
+                    if !unexpected.is_empty() {
+                        unexpected.sort_unstable();
+                        discrepancies.push(format!(
+                            "Field '{}' declared as [{}] but observed as [{}]",
+                            path,
+                            property.allowed_types.join(", "),
+                            unexpected.join(", ")
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    for field in &inferred.fields {
+        if field.name == "_id" || declared.properties.contains_key(&field.name) {
+            continue;
+        }
+        let parent = field.name.rsplit_once('.').map_or("", |(parent, _)| parent);
+        // Objects that do not declare `properties` are opaque to the validator
+        if !declared.described_objects.contains(parent) {
+            continue;
+        }
+        if declared.strict_objects.contains(parent) {
+            discrepancies.push(format!(
+                "Observed field '{}' is not declared and additionalProperties is false",
+                field.name
+            ));
+        } else {
+            discrepancies.push(format!(
+                "Observed field '{}' is not declared in the validator",
+                field.name
+            ));
+        }
+    }
+
+    discrepancies.sort();
+    discrepancies
+}
+
+/// A property declared in a `$jsonSchema` validator.
+#[derive(Debug, Default)]
+struct DeclaredProperty {
+    /// Allowed `bsonType`/`type` values (empty means unconstrained)
+    allowed_types: Vec<String>,
+    /// Whether the parent object lists this property as required
+    required: bool,
+}
+
+/// Declared structure of a `$jsonSchema`, flattened to dot-notation paths
+/// matching the inferred field names.
+#[derive(Debug, Default)]
+struct DeclaredSchema {
+    /// Declared properties keyed by path
+    properties: HashMap<String, DeclaredProperty>,
+    /// Object paths that declare `properties` ("" is the document root)
+    described_objects: HashSet<String>,
+    /// Object paths with `additionalProperties: false`
+    strict_objects: HashSet<String>,
+}
+
+impl DeclaredSchema {
+    /// Recursively records the properties declared under `prefix`.
+    fn collect(&mut self, schema: &serde_json::Value, prefix: &str) {
+        let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+            return;
+        };
+        self.described_objects.insert(prefix.to_string());
+
+        if schema.get("additionalProperties") == Some(&serde_json::Value::Bool(false)) {
+            self.strict_objects.insert(prefix.to_string());
+        }
+
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+
+        for (name, property) in properties {
+            let path = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+
+            self.properties.insert(
+                path.clone(),
+                DeclaredProperty {
+                    allowed_types: declared_types(property),
+                    required: required.contains(&name.as_str()),
+                },
+            );
+
+            self.collect(property, &path);
+        }
+    }
+}
+
+/// Reads `bsonType` (preferred) or `type` from a property schema.
+fn declared_types(property: &serde_json::Value) -> Vec<String> {
+    let value = property.get("bsonType").or_else(|| property.get("type"));
+    match value {
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(ToString::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Checks whether a declared type name allows an observed BSON type name.
+///
+/// Observed names come from [`super::type_mapping::bson_type_name`], which
+/// uses `int32`/`int64` where `$jsonSchema` uses `int`/`long`.
+fn declared_type_allows(declared: &str, observed: &str) -> bool {
+    match declared {
+        "int" => observed == "int32",
+        "long" => observed == "int64",
+        "number" => matches!(observed, "int32" | "int64" | "double" | "decimal"),
+        "boolean" => observed == "bool",
+        "date" => matches!(observed, "date" | "timestamp"),
+        other => other == observed,
+    }
+}
+
+/// Returns the lowercase name used by MongoDB for a validation level.
+fn validation_level_name(level: &ValidationLevel) -> String {
+    match level {
+        ValidationLevel::Off => "off".to_string(),
+        ValidationLevel::Strict => "strict".to_string(),
+        ValidationLevel::Moderate => "moderate".to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// Returns the lowercase name used by MongoDB for a validation action.
+fn validation_action_name(action: &ValidationAction) -> String {
+    match action {
+        ValidationAction::Error => "error".to_string(),
+        ValidationAction::Warn => "warn".to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::mongodb::schema_inference::SchemaInferrer;
+    use mongodb::bson::doc;
+
+    fn inferred(docs: &[Document]) -> InferredSchema {
+        let mut inferrer = SchemaInferrer::new();
+        for d in docs {
+            inferrer.analyze_document(d);
+        }
+        inferrer.finalize("users".to_string())
+    }
+
+    fn validator() -> serde_json::Value {
+        serde_json::json!({
+            "$jsonSchema": {
+                "bsonType": "object",
+                "required": ["name", "age"],
+                "properties": {
+                    "name": { "bsonType": "string" },
+                    "age": { "bsonType": ["int", "long"] },
+                    "address": {
+                        "bsonType": "object",
+                        "additionalProperties": false,
+                        "properties": { "city": { "bsonType": "string" } }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_no_discrepancies_when_structure_matches() {
+        let schema = inferred(&[
+            doc! { "_id": 1, "name": "a", "age": 30, "address": { "city": "x" } },
+            doc! { "_id": 2, "name": "b", "age": 40_i64, "address": { "city": "y" } },
+        ]);
+        assert!(find_discrepancies(&validator(), &schema).is_empty());
+    }
+
+    #[test]
+    fn test_detects_type_mismatch_and_missing_required() {
+        let schema = inferred(&[
+            doc! { "_id": 1, "name": "a", "age": "thirty", "address": { "city": "x" } },
+            doc! { "_id": 2, "name": "b", "address": { "city": "y" } },
+        ]);
+        let found = find_discrepancies(&validator(), &schema);
+        assert!(
+            found
+                .iter()
+                .any(|d| d.contains("'age'") && d.contains("[string]"))
+        );
+        assert!(
+            found
+                .iter()
+                .any(|d| d.contains("Required field 'age'") && d.contains("1 of 2"))
+        );
+    }
+
+    #[test]
+    fn test_detects_undeclared_and_unobserved_fields() {
+        let schema = inferred(&[doc! {
+            "_id": 1,
+            "name": "a",
+            "age": 1,
+            "nickname": "z",
+            "address": { "city": "x", "zip": "123" }
+        }]);
+        let found = find_discrepancies(&validator(), &schema);
+        assert!(
+            found
+                .iter()
+                .any(|d| d.contains("'nickname' is not declared in the validator"))
+        );
+        assert!(
+            found
+                .iter()
+                .any(|d| d.contains("'address.zip'") && d.contains("additionalProperties"))
+        );
+
+        let empty_address = inferred(&[doc! { "_id": 1, "name": "a", "age": 1 }]);
+        let found = find_discrepancies(&validator(), &empty_address);
+        assert!(
+            found
+                .iter()
+                .any(|d| d.contains("Declared field 'address.city' was not observed"))
+        );
+    }
+
+    #[test]
+    fn test_query_operator_validator_is_not_compared() {
+        let schema = inferred(&[doc! { "_id": 1, "anything": true }]);
+        let validator = serde_json::json!({ "age": { "$gte": 0 } });
+        assert!(find_discrepancies(&validator, &schema).is_empty());
+    }
+
+    #[test]
+    fn test_view_definition_includes_source_and_pipeline() {
+        let definition = CollectionDefinition {
+            name: "active_users".to_string(),
+            is_view: true,
+            view_on: Some("users".to_string()),
+            pipeline: Some(vec![doc! { "$match": { "active": true } }]),
+            validator: None,
+            options: None,
+        };
+        let view = definition.to_view("app");
+        assert_eq!(view.schema.as_deref(), Some("app"));
+        let parsed: serde_json::Value =
+            serde_json::from_str(view.definition.as_deref().unwrap_or_default()).unwrap();
+        assert_eq!(parsed["viewOn"], "users");
+        assert_eq!(parsed["pipeline"][0]["$match"]["active"], true);
+    }
+
+    #[test]
+    fn test_capped_and_time_series_options() {
+        let capped = CreateCollectionOptions::builder()
+            .capped(true)
+            .size(1_048_576)
+            .max(1000)
+            .build();
+        let options = collection_options(&capped).expect("capped options");
+        assert_eq!(
+            options.capped,
+            Some(CappedCollection {
+                max_bytes: Some(1_048_576),
+                max_documents: Some(1000),
+            })
+        );
+        assert_eq!(options.time_series, None);
+
+        let time_series = CreateCollectionOptions::builder()
+            .timeseries(
+                mongodb::options::TimeseriesOptions::builder()
+                    .time_field("ts".to_string())
+                    .meta_field(Some("sensor".to_string()))
+                    .granularity(Some(mongodb::options::TimeseriesGranularity::Minutes))
+                    .build(),
+            )
+            .expire_after_seconds(std::time::Duration::from_secs(86_400))
+            .build();
+        let options = collection_options(&time_series).expect("time-series options");
+        assert_eq!(
+            options.time_series,
+            Some(TimeSeriesCollection {
+                time_field: "ts".to_string(),
+                meta_field: Some("sensor".to_string()),
+                granularity: Some("minutes".to_string()),
+                expire_after_seconds: Some(86_400),
+            })
+        );
+
+        assert_eq!(
+            collection_options(&CreateCollectionOptions::default()),
+            None
+        );
+    }
+}
@@ -2,10 +2,59 @@
 //!
 //! This module will be fully implemented in subsequent tasks.
 //! Currently provides a placeholder that returns appropriate errors.
+//!
+//! # Planned Collection
+//! - Table and column descriptions from `sys.extended_properties`
+//!   (`name = 'MS_Description'`, `class = 1`), mapped into the unified
+//!   `comment` fields: `minor_id = 0` describes the table, otherwise the
+//!   column with that `column_id`
+//! - Synonyms from `sys.synonyms` with their `base_object_name`; base objects
+//!   with three- or four-part names are flagged as cross-database or
+//!   cross-server references
+//! - Schema ownership from `sys.schemas` joined to `sys.database_principals`
+//! - Server-wide collection over [`DATABASE_ENUMERATION_QUERY`], reporting
+//!   through [`crate::adapters::multi_database`] like the other engines
+//! - Session limits from [`session_setup_statements`], run on each new
+//!   connection
 
+use crate::adapters::config::SqlServerLimits;
+use crate::adapters::multi_database::{MultiDatabaseConfig, MultiDatabaseResult};
 use crate::define_placeholder_adapter;
 use crate::models::DatabaseType;
 
+/// System databases that are excluded by default when listing databases.
+pub const SYSTEM_DATABASES: &[&str] = &["master", "model", "msdb", "tempdb"];
+
+/// Lists databases with the current login's access to each.
+///
+/// `HAS_DBACCESS` is 1 when the login can open the database, 0 when it
+/// cannot, and `NULL` for databases that are offline or being restored.
+/// System databases are the four with `database_id <= 4`.
+pub const DATABASE_ENUMERATION_QUERY: &str = r#"
+    SELECT
+        name,
+        database_id,
+        CAST(CASE WHEN database_id <= 4 THEN 1 ELSE 0 END AS bit) AS is_system_database,
+        CAST(COALESCE(HAS_DBACCESS(name), 0) AS bit) AS is_accessible,
+        state_desc,
+        collation_name
+    FROM sys.databases
+    ORDER BY name
+"#;
+
+/// Statements applying the configured session limits.
+///
+/// `SET QUERY_GOVERNOR_COST_LIMIT` needs no special permission and affects
+/// only the current session; queries whose estimated cost exceeds the limit
+/// fail before running instead of loading the server.
+pub fn session_setup_statements(limits: &SqlServerLimits) -> Vec<String> {
+    limits
+        .query_governor_cost_limit
+        .map(|limit| format!("SET QUERY_GOVERNOR_COST_LIMIT {limit}"))
+        .into_iter()
+        .collect()
+}
+
 define_placeholder_adapter!(
     SqlServerAdapter,
     "SQL Server",
@@ -19,3 +68,19 @@
         ReadOnlyMode
     ]
 );
+
+impl SqlServerAdapter {
+    /// Collects schemas from all accessible databases on the server.
+    ///
+    /// # Errors
+    /// Always returns a configuration error until the SQL Server driver is
+    /// implemented.
+    pub async fn collect_all_databases(
+        &self,
+        _config: &MultiDatabaseConfig,
+    ) -> crate::Result<MultiDatabaseResult> {
+        Err(crate::error::DbSurveyorError::configuration(
+            "SQL Server adapter not yet implemented",
+        ))
+    }
+}
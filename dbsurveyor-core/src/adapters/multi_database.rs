@@ -0,0 +1,630 @@
+//! Multi-database collection shared by server-level adapters.
+//!
+//! Adapters that can enumerate the databases on a server supply the
+//! enumeration and a per-database collection function; this module filters
+//! the databases, collects them with bounded concurrency, and aggregates the
+//! results so every engine produces the same [`MultiDatabaseResult`].
+//!
+//! # Features
+//! - Concurrent database collection with configurable parallelism
+//! - Glob pattern-based database filtering
+//! - Graceful error handling with continue-on-error support
+//! - Comprehensive collection metadata and statistics
+
+use crate::Result;
+use crate::models::{CollectionMode, DatabaseSchema, ServerInfo};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Instant;
+
+/// Configuration for multi-database collection operations.
+///
+/// Controls which databases are collected and how the collection
+/// process handles errors and concurrency.
+#[derive(Debug, Clone)]
+pub struct MultiDatabaseConfig {
+    /// Maximum number of concurrent database collections.
+    ///
+    /// Higher values speed up collection but increase resource usage.
+    /// Default: 4
+    pub max_concurrency: usize,
+
+    /// Include system databases (such as `template0` or `mysql`) in collection.
+    ///
+    /// System databases are typically not useful for schema analysis
+    /// and are excluded by default.
+    /// Default: false
+    pub include_system: bool,
+
+    /// Database name patterns to exclude (glob patterns).
+    ///
+    /// Patterns use glob syntax:
+    /// - `*` matches any sequence of characters
+    /// - `?` matches any single character
+    /// - `[abc]` matches any character in the set
+    ///
+    /// Example: `["test_*", "*_backup"]` excludes all test and backup databases.
+    pub exclude_patterns: Vec<String>,
+
+    /// Continue collection if a database fails.
+    ///
+    /// When true, failures are recorded but collection continues with
+    /// remaining databases. When false, the first error stops collection.
+    /// Default: true
+    pub continue_on_error: bool,
+}
+
+impl Default for MultiDatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 4,
+            include_system: false,
+            exclude_patterns: Vec::new(),
+            continue_on_error: true,
+        }
+    }
+}
+
+impl MultiDatabaseConfig {
+    /// Creates a new configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum concurrency for database collection.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1); // Ensure at least 1
+        self
+    }
+
+    /// Sets whether to include system databases.
+    pub fn with_include_system(mut self, include_system: bool) -> Self {
+        self.include_system = include_system;
+        self
+    }
+
+    /// Adds patterns to exclude from collection.
+    pub fn with_exclude_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.exclude_patterns = patterns;
+        self
+    }
+
+    /// Sets whether to continue on error.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+}
+
+/// Result of collecting from a single database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseCollectionResult {
+    /// Name of the database that was collected
+    pub database_name: String,
+
+    /// The collected schema (if successful)
+    pub schema: DatabaseSchema,
+
+    /// Time taken to collect this database (in milliseconds)
+    pub collection_duration_ms: u64,
+}
+
+/// Information about a failed database collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseFailure {
+    /// Name of the database that failed
+    pub database_name: String,
+
+    /// Error message describing the failure
+    pub error_message: String,
+
+    /// Whether this was a connection failure vs collection failure
+    pub is_connection_error: bool,
+}
+
+/// Metadata about the multi-database collection operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiDatabaseMetadata {
+    /// When the collection started
+    pub started_at: chrono::DateTime<chrono::Utc>,
+
+    /// Total collection duration (in milliseconds)
+    pub total_duration_ms: u64,
+
+    /// Number of databases discovered on the server
+    pub databases_discovered: usize,
+
+    /// Number of databases filtered out by patterns
+    pub databases_filtered: usize,
+
+    /// Number of databases successfully collected
+    pub databases_collected: usize,
+
+    /// Number of databases that failed collection
+    pub databases_failed: usize,
+
+    /// Number of databases skipped (inaccessible)
+    pub databases_skipped: usize,
+
+    /// Maximum concurrency used
+    pub max_concurrency: usize,
+
+    /// Collector version
+    pub collector_version: String,
+
+    /// Any warnings generated during collection
+    pub warnings: Vec<String>,
+}
+
+/// Result of collecting schemas from all databases on a server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiDatabaseResult {
+    /// Server-level information
+    pub server_info: ServerInfo,
+
+    /// Successfully collected database schemas
+    pub databases: Vec<DatabaseCollectionResult>,
+
+    /// Failed database collections
+    pub failures: Vec<DatabaseFailure>,
+
+    /// Collection metadata and statistics
+    pub collection_metadata: MultiDatabaseMetadata,
+}
+
+/// A database found by enumerating a server.
+#[derive(Debug, Clone)]
+pub(crate) struct DatabaseCandidate {
+    pub(crate) name: String,
+    pub(crate) is_system_database: bool,
+    pub(crate) is_accessible: bool,
+}
+
+/// Collects every enumerated database that passes the configured filters.
+///
+/// System databases are dropped unless `config.include_system`; inaccessible
+/// databases are skipped and excluded ones filtered. `collect` is called for
+/// each remaining database, at most `config.max_concurrency` at a time.
+/// `server_info` is completed with the database counts.
+pub(crate) async fn collect_databases<F, Fut>(
+    server_info: ServerInfo,
+    candidates: Vec<DatabaseCandidate>,
+    config: &MultiDatabaseConfig,
+    collect: F,
+) -> MultiDatabaseResult
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<DatabaseSchema>>,
+{
+    let start_time = Instant::now();
+    let started_at = chrono::Utc::now();
+    let mut warnings = Vec::new();
+
+    tracing::info!(
+        "Starting multi-database collection (max_concurrency: {}, include_system: {}, continue_on_error: {})",
+        config.max_concurrency,
+        config.include_system,
+        config.continue_on_error
+    );
+
+    let system_databases_excluded = if config.include_system {
+        0
+    } else {
+        candidates.iter().filter(|db| db.is_system_database).count()
+    };
+    let candidates: Vec<DatabaseCandidate> = candidates
+        .into_iter()
+        .filter(|db| config.include_system || !db.is_system_database)
+        .collect();
+    let databases_discovered = candidates.len();
+    tracing::info!("Discovered {} databases on server", databases_discovered);
+
+    let (databases_to_collect, databases_filtered, databases_skipped) =
+        filter_databases(&candidates, config, &mut warnings);
+
+    tracing::info!(
+        "After filtering: {} to collect, {} filtered by patterns, {} inaccessible",
+        databases_to_collect.len(),
+        databases_filtered,
+        databases_skipped
+    );
+
+    let (collected_results, failures) =
+        collect_databases_concurrent(&databases_to_collect, config, collect).await;
+
+    let total_duration = start_time.elapsed();
+    let databases_collected = collected_results.len();
+    let databases_failed = failures.len();
+
+    let server_info = ServerInfo {
+        total_databases: databases_discovered,
+        collected_databases: databases_collected,
+        system_databases_excluded,
+        collection_mode: CollectionMode::MultiDatabase {
+            discovered: databases_discovered,
+            collected: databases_collected,
+            failed: databases_failed,
+        },
+        ..server_info
+    };
+
+    let collection_metadata = MultiDatabaseMetadata {
+        started_at,
+        total_duration_ms: u64::try_from(total_duration.as_millis()).unwrap_or(u64::MAX),
+        databases_discovered,
+        databases_filtered,
+        databases_collected,
+        databases_failed,
+        databases_skipped,
+        max_concurrency: config.max_concurrency,
+        collector_version: env!("CARGO_PKG_VERSION").to_string(),
+        warnings,
+    };
+
+    tracing::info!(
+        "Multi-database collection completed in {:.2}s: {} collected, {} failed, {} filtered",
+        total_duration.as_secs_f64(),
+        databases_collected,
+        databases_failed,
+        databases_filtered
+    );
+
+    MultiDatabaseResult {
+        server_info,
+        databases: collected_results,
+        failures,
+        collection_metadata,
+    }
+}
+
+/// Filters databases based on configuration.
+///
+/// Returns (databases_to_collect, count_filtered, count_skipped)
+fn filter_databases(
+    all_databases: &[DatabaseCandidate],
+    config: &MultiDatabaseConfig,
+    warnings: &mut Vec<String>,
+) -> (Vec<String>, usize, usize) {
+    let mut databases_to_collect = Vec::new();
+    let mut filtered_count = 0;
+    let mut skipped_count = 0;
+
+    for db in all_databases {
+        // Check if database is accessible
+        if !db.is_accessible {
+            tracing::debug!("Skipping inaccessible database: {}", db.name);
+            skipped_count += 1;
+            continue;
+        }
+
+        // Check against exclude patterns
+        if matches_any_pattern(&db.name, &config.exclude_patterns) {
+            tracing::debug!("Filtering database '{}' - matches exclude pattern", db.name);
+            filtered_count += 1;
+            continue;
+        }
+
+        databases_to_collect.push(db.name.clone());
+    }
+
+    if skipped_count > 0 {
+        warnings.push(format!(
+            "{} database(s) were inaccessible and skipped",
+            skipped_count
+        ));
+    }
+
+    (databases_to_collect, filtered_count, skipped_count)
+}
+
+/// Checks if a database name matches any of the exclude patterns.
+fn matches_any_pattern(name: &str, patterns: &[String]) -> bool {
+    for pattern in patterns {
+        if glob_match(pattern, name) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Glob pattern matching for database name filtering.
+///
+/// Supports:
+/// - `*` matches any sequence of characters
+/// - `?` matches any single character
+/// - `[abc]` matches any character in the set
+fn glob_match(pattern: &str, text: &str) -> bool {
+    glob_match::glob_match(pattern, text)
+}
+
+/// Collects schemas from multiple databases concurrently.
+///
+/// Uses `futures::stream::buffer_unordered` for controlled parallelism.
+async fn collect_databases_concurrent<F, Fut>(
+    databases: &[String],
+    config: &MultiDatabaseConfig,
+    collect: F,
+) -> (Vec<DatabaseCollectionResult>, Vec<DatabaseFailure>)
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<DatabaseSchema>>,
+{
+    let mut collected_results = Vec::new();
+    let mut failures = Vec::new();
+
+    // Create async tasks for each database
+    let collection_futures = databases.iter().map(|db_name| {
+        let collection = collect(db_name.clone());
+        async move {
+            let start = Instant::now();
+            let result = collection.await;
+            (db_name, result, start.elapsed())
+        }
+    });
+
+    // Process with controlled concurrency
+    let mut stream = stream::iter(collection_futures).buffer_unordered(config.max_concurrency);
+
+    while let Some((db_name, result, duration)) = stream.next().await {
+        match result {
+            Ok(schema) => {
+                let collection_duration_ms =
+                    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                tracing::info!(
+                    "Collected schema from '{}' in {}ms",
+                    db_name,
+                    collection_duration_ms
+                );
+                collected_results.push(DatabaseCollectionResult {
+                    database_name: db_name.clone(),
+                    schema,
+                    collection_duration_ms,
+                });
+            }
+            Err(e) => {
+                let error_str = e.to_string();
+                let is_connection_error = matches!(
+                    e,
+                    crate::error::DbSurveyorError::Connection { .. }
+                        | crate::error::DbSurveyorError::ConnectionTimeout { .. }
+                );
+
+                tracing::warn!("Failed to collect schema from '{}': {}", db_name, error_str);
+
+                failures.push(DatabaseFailure {
+                    database_name: db_name.clone(),
+                    error_message: error_str,
+                    is_connection_error,
+                });
+
+                if !config.continue_on_error {
+                    tracing::error!("Stopping collection due to continue_on_error=false");
+                    break;
+                }
+            }
+        }
+    }
+
+    (collected_results, failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(name: &str, is_system_database: bool, is_accessible: bool) -> DatabaseCandidate {
+        DatabaseCandidate {
+            name: name.to_string(),
+            is_system_database,
+            is_accessible,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_databases_filters_and_aggregates() {
+        let server_info = ServerInfo {
+            server_type: crate::models::DatabaseType::MySQL,
+            version: "8.0.36".to_string(),
+            host: "localhost".to_string(),
+            port: Some(3306),
+            total_databases: 0,
+            collected_databases: 0,
+            system_databases_excluded: 0,
+            connection_user: "surveyor".to_string(),
+            has_superuser_privileges: false,
+            timezone: None,
+            role: None,
+            collection_mode: CollectionMode::SingleDatabase,
+        };
+        let candidates = vec![
+            candidate("orders", false, true),
+            candidate("broken", false, true),
+            candidate("test_orders", false, true),
+            candidate("locked", false, false),
+            candidate("mysql", true, true),
+        ];
+        let config = MultiDatabaseConfig::new().with_exclude_patterns(vec!["test_*".to_string()]);
+
+        let result = collect_databases(server_info, candidates, &config, |name| async move {
+            if name == "broken" {
+                Err(crate::error::DbSurveyorError::configuration("boom"))
+            } else {
+                Ok(DatabaseSchema::new(crate::models::DatabaseInfo::new(name)))
+            }
+        })
+        .await;
+
+        let names: Vec<&str> = result
+            .databases
+            .iter()
+            .map(|db| db.database_name.as_str())
+            .collect();
+        assert_eq!(names, ["orders"]);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].database_name, "broken");
+        assert!(!result.failures[0].is_connection_error);
+
+        let metadata = &result.collection_metadata;
+        assert_eq!(metadata.databases_discovered, 4);
+        assert_eq!(metadata.databases_filtered, 1);
+        assert_eq!(metadata.databases_skipped, 1);
+        assert_eq!(result.server_info.total_databases, 4);
+        assert_eq!(result.server_info.system_databases_excluded, 1);
+        assert_eq!(result.server_info.collected_databases, 1);
+    }
+
+    #[test]
+    fn test_multi_database_config_default() {
+        let config = MultiDatabaseConfig::default();
+        assert_eq!(config.max_concurrency, 4);
+        assert!(!config.include_system);
+        assert!(config.exclude_patterns.is_empty());
+        assert!(config.continue_on_error);
+    }
+
+    #[test]
+    fn test_multi_database_config_builder() {
+        let config = MultiDatabaseConfig::new()
+            .with_max_concurrency(8)
+            .with_include_system(true)
+            .with_exclude_patterns(vec!["test_*".to_string(), "*_backup".to_string()])
+            .with_continue_on_error(false);
+
+        assert_eq!(config.max_concurrency, 8);
+        assert!(config.include_system);
+        assert_eq!(config.exclude_patterns.len(), 2);
+        assert!(!config.continue_on_error);
+    }
+
+    #[test]
+    fn test_multi_database_config_min_concurrency() {
+        // Should enforce minimum concurrency of 1
+        let config = MultiDatabaseConfig::new().with_max_concurrency(0);
+        assert_eq!(config.max_concurrency, 1);
+    }
+
+    #[test]
+    fn test_glob_match_exact() {
+        assert!(glob_match("test", "test"));
+        assert!(!glob_match("test", "testing"));
+        assert!(!glob_match("testing", "test"));
+    }
+
+    #[test]
+    fn test_glob_match_star() {
+        assert!(glob_match("test_*", "test_db"));
+        assert!(glob_match("test_*", "test_"));
+        assert!(glob_match("test_*", "test_database_backup"));
+        assert!(!glob_match("test_*", "testdb"));
+        assert!(!glob_match("test_*", "mytest_db"));
+    }
+
+    #[test]
+    fn test_glob_match_star_prefix() {
+        assert!(glob_match("*_backup", "db_backup"));
+        assert!(glob_match("*_backup", "test_backup"));
+        assert!(glob_match("*_backup", "_backup"));
+        assert!(!glob_match("*_backup", "backup"));
+        assert!(!glob_match("*_backup", "db_backup_old"));
+    }
+
+    #[test]
+    fn test_glob_match_star_middle() {
+        assert!(glob_match("test_*_db", "test_123_db"));
+        assert!(glob_match("test_*_db", "test__db"));
+        assert!(!glob_match("test_*_db", "test_db"));
+        assert!(!glob_match("test_*_db", "testing_123_db"));
+    }
+
+    #[test]
+    fn test_glob_match_multiple_stars() {
+        assert!(glob_match("*test*", "test"));
+        assert!(glob_match("*test*", "mytest"));
+        assert!(glob_match("*test*", "testdb"));
+        assert!(glob_match("*test*", "mytestdb"));
+        assert!(!glob_match("*test*", "tst"));
+    }
+
+    #[test]
+    fn test_glob_match_question_mark() {
+        assert!(glob_match("test?", "test1"));
+        assert!(glob_match("test?", "testa"));
+        assert!(!glob_match("test?", "test"));
+        assert!(!glob_match("test?", "test12"));
+    }
+
+    #[test]
+    fn test_glob_match_combined() {
+        assert!(glob_match("test_?_*", "test_1_db"));
+        assert!(glob_match("test_?_*", "test_a_"));
+        assert!(!glob_match("test_?_*", "test__db"));
+        assert!(!glob_match("test_?_*", "test_12_db"));
+    }
+
+    #[test]
+    fn test_matches_any_pattern() {
+        let patterns = vec!["test_*".to_string(), "*_backup".to_string()];
+
+        assert!(matches_any_pattern("test_db", &patterns));
+        assert!(matches_any_pattern("test_", &patterns));
+        assert!(matches_any_pattern("my_backup", &patterns));
+        assert!(matches_any_pattern("test_backup", &patterns)); // matches both
+        assert!(!matches_any_pattern("production", &patterns));
+        assert!(!matches_any_pattern("testdb", &patterns));
+    }
+
+    #[test]
+    fn test_matches_any_pattern_empty() {
+        let patterns: Vec<String> = vec![];
+        assert!(!matches_any_pattern("anything", &patterns));
+    }
+
+    #[test]
+    fn test_database_failure_serialization() {
+        let failure = DatabaseFailure {
+            database_name: "test_db".to_string(),
+            error_message: "Connection refused".to_string(),
+            is_connection_error: true,
+        };
+
+        let json = serde_json::to_string(&failure).unwrap();
+        assert!(json.contains("\"database_name\":\"test_db\""));
+        assert!(json.contains("\"error_message\":\"Connection refused\""));
+        assert!(json.contains("\"is_connection_error\":true"));
+
+        let deserialized: DatabaseFailure = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.database_name, failure.database_name);
+        assert_eq!(deserialized.error_message, failure.error_message);
+        assert_eq!(
+            deserialized.is_connection_error,
+            failure.is_connection_error
+        );
+    }
+
+    #[test]
+    fn test_multi_database_metadata_serialization() {
+        let metadata = MultiDatabaseMetadata {
+            started_at: chrono::Utc::now(),
+            total_duration_ms: 1234,
+            databases_discovered: 10,
+            databases_filtered: 2,
+            databases_collected: 7,
+            databases_failed: 1,
+            databases_skipped: 0,
+            max_concurrency: 4,
+            collector_version: "1.0.0".to_string(),
+            warnings: vec!["Test warning".to_string()],
+        };
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(json.contains("\"databases_discovered\":10"));
+        assert!(json.contains("\"databases_collected\":7"));
+
+        let deserialized: MultiDatabaseMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.databases_discovered,
+            metadata.databases_discovered
+        );
+    }
+}
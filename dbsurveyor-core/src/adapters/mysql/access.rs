@@ -0,0 +1,109 @@
+//! Probing what the connected account can read in one database.
+
+use super::MySqlAdapter;
+use crate::Result;
+use crate::models::AccessProbe;
+use sqlx::Row;
+
+/// Probes the account's `SUPER` grant and the tables and views it may
+/// select from in `db_name`.
+///
+/// `INFORMATION_SCHEMA` only lists objects the account holds some privilege
+/// on, so the table total is what the account can see; only a global
+/// `SELECT` (or `SUPER`) makes every object visible. Privileges held through
+/// roles are not listed in the privilege tables and do not count.
+pub(crate) async fn probe_access(adapter: &MySqlAdapter, db_name: &str) -> Result<AccessProbe> {
+    let probe_query = r#"
+        SELECT
+            CAST(CURRENT_USER() AS CHAR) AS account,
+            (SELECT COUNT(*) FROM INFORMATION_SCHEMA.USER_PRIVILEGES
+             WHERE GRANTEE = me.grantee AND PRIVILEGE_TYPE = 'SUPER') AS super_grants,
+            (SELECT COUNT(*) FROM INFORMATION_SCHEMA.USER_PRIVILEGES
+             WHERE GRANTEE = me.grantee AND PRIVILEGE_TYPE = 'SELECT') AS global_select,
+            (SELECT COUNT(*) FROM INFORMATION_SCHEMA.SCHEMA_PRIVILEGES
+             WHERE GRANTEE = me.grantee AND PRIVILEGE_TYPE = 'SELECT'
+             AND TABLE_SCHEMA = ?) AS database_select,
+            (SELECT COUNT(DISTINCT TABLE_NAME) FROM INFORMATION_SCHEMA.TABLE_PRIVILEGES
+             WHERE GRANTEE = me.grantee AND PRIVILEGE_TYPE = 'SELECT'
+             AND TABLE_SCHEMA = ?) AS table_select,
+            (SELECT COUNT(*) FROM INFORMATION_SCHEMA.TABLES
+             WHERE TABLE_SCHEMA = ?) AS total_tables
+        FROM (
+            SELECT CONCAT('''', SUBSTRING_INDEX(CURRENT_USER(), '@', 1), '''@''',
+                          SUBSTRING_INDEX(CURRENT_USER(), '@', -1), '''') AS grantee
+        ) AS me
+    "#;
+
+    let row = sqlx::query(probe_query)
+        .bind(db_name)
+        .bind(db_name)
+        .bind(db_name)
+        .fetch_one(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to probe account access", e)
+        })?;
+
+    let field = |name: &str| -> Result<i64> {
+        row.try_get(name)
+            .map_err(|e| crate::error::DbSurveyorError::parse_field(name, None, e))
+    };
+    let account: String = row
+        .try_get("account")
+        .map_err(|e| crate::error::DbSurveyorError::parse_field("account", None, e))?;
+
+    Ok(classify_access(
+        &account,
+        field("super_grants")? > 0,
+        field("global_select")? > 0,
+        field("database_select")? > 0,
+        field("table_select")?,
+        field("total_tables")?,
+    ))
+}
+
+/// Builds the probe result from the account's grants.
+///
+/// A global or database-wide `SELECT` covers every table; otherwise only the
+/// tables granted individually are readable.
+pub(crate) fn classify_access(
+    account: &str,
+    superuser: bool,
+    global_select: bool,
+    database_select: bool,
+    table_select: i64,
+    total_tables: i64,
+) -> AccessProbe {
+    let mut evidence = vec![format!(
+        "account {} SUPER",
+        if superuser { "holds" } else { "lacks" }
+    )];
+    let readable_tables = if superuser || global_select || database_select {
+        evidence.push(
+            if global_select {
+                "SELECT granted on *.*"
+            } else if database_select {
+                "SELECT granted on the database"
+            } else {
+                "SUPER bypasses object privileges"
+            }
+            .to_string(),
+        );
+        total_tables
+    } else {
+        table_select.min(total_tables)
+    };
+    evidence.push(format!(
+        "SELECT allowed on {} of {} visible tables and views",
+        readable_tables, total_tables
+    ));
+
+    AccessProbe {
+        superuser,
+        catalog_access: superuser || global_select,
+        readable_tables: usize::try_from(readable_tables).unwrap_or_default(),
+        total_tables: usize::try_from(total_tables).unwrap_or_default(),
+        identity: Some(account.to_string()),
+        evidence,
+    }
+}
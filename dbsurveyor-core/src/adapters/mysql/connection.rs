@@ -5,26 +5,15 @@
 //! - Enforces connection limits to prevent resource exhaustion
 //! - Sets appropriate timeouts for all operations
 
-use super::{ConnectionConfig, MySqlAdapter};
+use super::{ConnectionConfig, MySqlAdapter, ObjectSelection, PoolStats};
 use crate::Result;
+use crate::adapters::config::MySqlLimits;
+use crate::models::{ReadOnlyLevel, ReadOnlyVerification};
 use sqlx::MySqlPool;
 use std::time::Duration;
 use url::Url;
 use zeroize::Zeroizing;
 
-/// Pool statistics for monitoring connection pool health and usage
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PoolStats {
-    /// Number of idle connections ready to be used
-    pub idle_connections: u32,
-    /// Number of connections currently in use
-    pub active_connections: u32,
-    /// Total number of connections in the pool
-    pub total_connections: u32,
-    /// Maximum allowed connections (from configuration)
-    pub max_connections: u32,
-}
-
 impl MySqlAdapter {
     /// Creates a new MySQL adapter with connection pooling
     ///
@@ -52,11 +41,23 @@ pub async fn new(connection_string: &str) -> Result<Self> {
         let adapter = Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
         };
         Ok(adapter)
     }
 
+    /// Creates an adapter for a server reached through a multiplexing proxy
+    /// such as ProxySQL, applying settings per transaction rather than per
+    /// session.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`MySqlAdapter::new`].
+    pub async fn behind_pooler(connection_string: &str) -> Result<Self> {
+        let config = parse_mysql_connection_config(connection_string)?.with_pooler_mode(true);
+        Self::with_config(connection_string, config).await
+    }
+
     /// Creates a new MySQL adapter with custom configuration
     ///
     /// # Arguments
@@ -78,6 +79,7 @@ pub async fn with_config(connection_string: &str, config: ConnectionConfig) -> R
         let adapter = Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
         };
         Ok(adapter)
@@ -132,6 +134,49 @@ pub async fn is_pool_healthy(&self) -> bool {
         }
     }
 
+    /// The server's configured time zone, before the session override to UTC.
+    ///
+    /// A global zone of `SYSTEM` is resolved to the host's zone. Returns
+    /// `None` if the setting cannot be read.
+    pub async fn server_timezone(&self) -> Option<String> {
+        sqlx::query_scalar(
+            "SELECT CAST(IF(@@global.time_zone = 'SYSTEM', @@system_time_zone, @@global.time_zone) AS CHAR)",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .ok()
+    }
+
+    /// Reads the session and server read-only variables.
+    ///
+    /// `transaction_read_only` replaced `tx_read_only` in MySQL 5.7.20, and
+    /// MariaDB has no `super_read_only`; missing variables are skipped.
+    ///
+    /// # Errors
+    /// Returns error if neither session variable can be read.
+    pub async fn read_only_verification(&self) -> Result<ReadOnlyVerification> {
+        let mut session = self.system_variable("session.transaction_read_only").await;
+        if session.is_none() {
+            session = self.system_variable("session.tx_read_only").await;
+        }
+        let session = session.ok_or_else(|| {
+            crate::error::DbSurveyorError::configuration(
+                "Cannot read the session transaction_read_only variable",
+            )
+        })?;
+        let global = self.system_variable("global.read_only").await;
+        let super_read_only = self.system_variable("global.super_read_only").await;
+        Ok(classify_read_only(session, global, super_read_only))
+    }
+
+    /// Reads a numeric system variable such as `global.read_only`.
+    async fn system_variable(&self, name: &str) -> Option<i64> {
+        sqlx::query_scalar(&format!("SELECT CAST(@@{} AS SIGNED)", name))
+            .fetch_one(&self.pool)
+            .await
+            .ok()
+    }
+
     /// Generate connection URL for a different database on the same server.
     ///
     /// # Arguments
@@ -303,8 +348,13 @@ async fn create_mysql_connection_pool(
     // Validate connection string format
     validate_mysql_connection_string(connection_string)?;
 
+    if config.pooler_mode {
+        return create_pooler_connection_pool(connection_string, config);
+    }
+
     // Clone config values needed for the after_connect closure
-    let query_timeout_secs = config.query_timeout.as_secs();
+    let max_execution_time =
+        max_execution_time_ms(&config.session_limits.mysql, config.query_timeout.as_secs());
     let read_only = config.read_only;
 
     let pool = sqlx::mysql::MySqlPoolOptions::new()
@@ -317,10 +367,8 @@ async fn create_mysql_connection_pool(
         .after_connect(move |conn, _meta| {
             Box::pin(async move {
                 // Set query timeout
-                conn.execute(
-                    format!("SET max_execution_time = {}", query_timeout_secs * 1000).as_str(),
-                )
-                .await?;
+                conn.execute(format!("SET max_execution_time = {}", max_execution_time).as_str())
+                    .await?;
 
                 // Set session to read-only if configured
                 if read_only {
@@ -346,3 +394,127 @@ async fn create_mysql_connection_pool(
 
     Ok(pool)
 }
+
+/// Creates a connection pool for use behind a multiplexing proxy such as
+/// ProxySQL.
+///
+/// Session settings would follow whichever backend connection the proxy
+/// picks, so every checkout instead sets the timeout and UTC time zone and
+/// opens a transaction, which pins one backend until release rolls it back.
+fn create_pooler_connection_pool(
+    connection_string: &str,
+    config: &ConnectionConfig,
+) -> Result<MySqlPool> {
+    use sqlx::Executor;
+
+    let options = connection_string
+        .parse::<sqlx::mysql::MySqlConnectOptions>()
+        .map_err(|e| {
+            crate::error::DbSurveyorError::configuration(format!(
+                "Invalid MySQL connection string {}: {}",
+                crate::error::redact_database_url(connection_string),
+                e
+            ))
+        })?;
+    let statements = pooler_transaction_sql(
+        config.query_timeout.as_secs(),
+        config.read_only,
+        &config.session_limits.mysql,
+    );
+    let statements_on_connect = statements.clone();
+
+    let pool = sqlx::mysql::MySqlPoolOptions::new()
+        .max_connections(config.max_connections.min(100))
+        // Idle connections must not hold a backend connection open
+        .min_connections(0)
+        .acquire_timeout(config.connect_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+        .test_before_acquire(true)
+        // New connections go straight to the caller; reused ones pass
+        // through `before_acquire`
+        .after_connect(move |conn, _meta| {
+            let statements = statements_on_connect.clone();
+            Box::pin(async move {
+                for statement in &statements {
+                    conn.execute(statement.as_str()).await?;
+                }
+                Ok(())
+            })
+        })
+        .before_acquire(move |conn, _meta| {
+            let statements = statements.clone();
+            Box::pin(async move {
+                for statement in &statements {
+                    conn.execute(statement.as_str()).await?;
+                }
+                Ok(true)
+            })
+        })
+        .after_release(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("ROLLBACK").await?;
+                Ok(true)
+            })
+        })
+        .connect_lazy_with(options);
+
+    Ok(pool)
+}
+
+/// Statements that open a checkout's transaction in pooler mode.
+pub(crate) fn pooler_transaction_sql(
+    query_timeout_secs: u64,
+    read_only: bool,
+    limits: &MySqlLimits,
+) -> [String; 3] {
+    [
+        format!(
+            "SET max_execution_time = {}",
+            max_execution_time_ms(limits, query_timeout_secs)
+        ),
+        // TIMESTAMP values are decoded as UTC
+        "SET time_zone = '+00:00'".to_string(),
+        format!(
+            "START TRANSACTION{}",
+            if read_only { " READ ONLY" } else { "" }
+        ),
+    ]
+}
+
+/// Value for `max_execution_time`: the configured limit, or else the query
+/// timeout.
+fn max_execution_time_ms(limits: &MySqlLimits, query_timeout_secs: u64) -> u64 {
+    limits
+        .max_execution_time_ms
+        .unwrap_or_else(|| query_timeout_secs.saturating_mul(1000))
+}
+
+/// Classifies MySQL read-only variables.
+///
+/// `super_read_only` rejects writes from every account. `read_only` alone
+/// still admits accounts with `SUPER` or `CONNECTION_ADMIN`, so it only
+/// counts through the session setting.
+pub(crate) fn classify_read_only(
+    transaction_read_only: i64,
+    read_only: Option<i64>,
+    super_read_only: Option<i64>,
+) -> ReadOnlyVerification {
+    let mut evidence = vec![format!("transaction_read_only = {}", transaction_read_only)];
+    if let Some(read_only) = read_only {
+        evidence.push(format!("read_only = {}", read_only));
+    }
+    if let Some(super_read_only) = super_read_only {
+        evidence.push(format!("super_read_only = {}", super_read_only));
+    }
+    let level = if super_read_only == Some(1) {
+        ReadOnlyLevel::Server
+    } else if transaction_read_only == 1 {
+        ReadOnlyLevel::Session
+    } else if read_only == Some(1) {
+        ReadOnlyLevel::Unverified
+    } else {
+        ReadOnlyLevel::NotEnforced
+    };
+    ReadOnlyVerification::new(level, evidence)
+}
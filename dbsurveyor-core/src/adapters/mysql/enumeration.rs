@@ -0,0 +1,120 @@
+//! MySQL database enumeration implementation.
+//!
+//! Databases are listed from `INFORMATION_SCHEMA.SCHEMATA`, which applies the
+//! same visibility rules as `SHOW DATABASES`: an account without the global
+//! `SHOW DATABASES` privilege only sees databases it holds some privilege on.
+//! Every listed database is therefore accessible to the current user.
+//!
+//! # Security
+//! - All operations are read-only
+//! - Databases hidden by the server's privilege checks are never reported
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{MySqlPool, Row};
+
+/// System databases that are excluded by default when listing databases.
+///
+/// These hold server metadata, grants, and instrumentation rather than
+/// application schemas.
+pub const SYSTEM_DATABASES: &[&str] = &["information_schema", "mysql", "performance_schema", "sys"];
+
+/// Information about a database on the MySQL server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumeratedDatabase {
+    /// Database name
+    pub name: String,
+    /// Default character set (e.g., "utf8mb4")
+    pub character_set: String,
+    /// Default collation
+    pub collation: String,
+    /// Whether this is a system database
+    pub is_system_database: bool,
+}
+
+impl EnumeratedDatabase {
+    /// Checks if this database is a known system database.
+    ///
+    /// MySQL database names are case-insensitive on some platforms, so the
+    /// comparison ignores case.
+    pub fn check_is_system_database(name: &str) -> bool {
+        SYSTEM_DATABASES
+            .iter()
+            .any(|system| system.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Lists the databases visible to the current user.
+///
+/// # Arguments
+///
+/// * `pool` - Connection pool to use for queries
+/// * `include_system` - If true, includes `mysql`, `sys`, and the schema
+///   metadata databases
+///
+/// # Returns
+///
+/// A vector of `EnumeratedDatabase` structs, sorted by database name.
+///
+/// # Errors
+///
+/// Returns an error if the enumeration query fails.
+pub async fn list_databases(
+    pool: &MySqlPool,
+    include_system: bool,
+) -> Result<Vec<EnumeratedDatabase>> {
+    tracing::debug!("Listing databases (include_system: {})", include_system);
+
+    // Cast to CHAR to avoid VARBINARY type issues in MySQL 8.0+
+    let query = r#"
+        SELECT
+            CAST(SCHEMA_NAME AS CHAR) as name,
+            CAST(DEFAULT_CHARACTER_SET_NAME AS CHAR) as character_set,
+            CAST(DEFAULT_COLLATION_NAME AS CHAR) as collation
+        FROM INFORMATION_SCHEMA.SCHEMATA
+        ORDER BY SCHEMA_NAME
+    "#;
+
+    let rows = sqlx::query(query).fetch_all(pool).await.map_err(|e| {
+        tracing::error!("Failed to enumerate databases: {}", e);
+        crate::error::DbSurveyorError::collection_failed(
+            "Failed to enumerate databases from INFORMATION_SCHEMA.SCHEMATA",
+            e,
+        )
+    })?;
+
+    let mut databases = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let name: String = row.try_get("name").map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to parse database name", e)
+        })?;
+        let is_system_database = EnumeratedDatabase::check_is_system_database(&name);
+
+        // Skip system databases if not requested
+        if !include_system && is_system_database {
+            tracing::trace!("Skipping system database: {}", name);
+            continue;
+        }
+
+        let db = EnumeratedDatabase {
+            name,
+            character_set: row.try_get("character_set").unwrap_or_default(),
+            collation: row.try_get("collation").unwrap_or_default(),
+            is_system_database,
+        };
+
+        tracing::trace!(
+            "Found database: {} (charset: {}, system: {})",
+            db.name,
+            db.character_set,
+            db.is_system_database
+        );
+
+        databases.push(db);
+    }
+
+    tracing::info!("Found {} databases", databases.len());
+
+    Ok(databases)
+}
@@ -1,10 +1,13 @@
 //! MySQL database adapter with connection pooling and schema collection.
 //!
 //! # Module Structure
+//! - `access`: Probing the connected account's privileges
 //! - `connection`: Connection pool management and validation
 //! - `type_mapping`: MySQL to unified data type conversion
-//! - `schema_collection`: Table, column, constraint, and index collection
+//! - `schema_collection`: Table, column, constraint, index, and partition collection
 //! - `sampling`: Data sampling utilities and ordering strategy detection
+//! - `enumeration`: Database enumeration for multi-database collection
+//! - `multi_database`: Multi-database collection orchestration
 //!
 //! # Security Guarantees
 //! - All operations are read-only (SELECT/SHOW only)
@@ -12,7 +15,10 @@
 //! - Query timeouts prevent resource exhaustion
 //! - Connection pooling with configurable limits
 
+mod access;
 mod connection;
+mod enumeration;
+mod multi_database;
 mod sampling;
 mod schema_collection;
 mod type_mapping;
@@ -20,7 +26,9 @@
 #[cfg(test)]
 mod tests;
 
-use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, TableRef};
+use super::{
+    AdapterFeature, ConnectionConfig, DatabaseAdapter, ObjectSelection, PoolStats, TableRef,
+};
 use crate::Result;
 use crate::models::*;
 use async_trait::async_trait;
@@ -28,6 +36,12 @@
 use zeroize::Zeroizing;
 
 // Re-export public items from submodules
+pub use super::multi_database::{
+    DatabaseCollectionResult, DatabaseFailure, MultiDatabaseConfig, MultiDatabaseMetadata,
+    MultiDatabaseResult,
+};
+pub use enumeration::{EnumeratedDatabase, SYSTEM_DATABASES};
+pub use multi_database::collect_all_databases;
 pub use sampling::{detect_ordering_strategy, generate_order_by_clause, sample_table};
 pub use type_mapping::{map_mysql_type, map_referential_action};
 
@@ -37,6 +51,8 @@ pub struct MySqlAdapter {
     pub pool: MySqlPool,
     /// Connection configuration (pool settings, timeouts, etc.)
     pub config: ConnectionConfig,
+    /// Optional object types to collect
+    pub objects: ObjectSelection,
     /// Original connection URL (stored for creating connections to other databases)
     /// This is kept private to prevent credential exposure.
     /// Wrapped in `Zeroizing` so the URL (which may contain credentials) is
@@ -48,6 +64,7 @@ impl std::fmt::Debug for MySqlAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MySqlAdapter")
             .field("config", &self.config)
+            .field("objects", &self.objects)
             .field("pool_size", &self.pool.size())
             .field("pool_idle", &self.pool.num_idle())
             // Note: connection_url is intentionally omitted to prevent credential exposure
@@ -130,6 +147,76 @@ fn supports_feature(&self, feature: AdapterFeature) -> bool {
     fn connection_config(&self) -> ConnectionConfig {
         self.config.clone()
     }
+
+    fn connection_pool_stats(&self) -> Option<PoolStats> {
+        Some(self.pool_statistics())
+    }
+
+    async fn verify_read_only(&self) -> Result<ReadOnlyVerification> {
+        self.read_only_verification().await
+    }
+}
+
+// MySqlAdapter methods for multi-database collection
+impl MySqlAdapter {
+    /// List the non-system databases visible to the current user.
+    ///
+    /// Visibility follows `SHOW DATABASES`: without the global
+    /// `SHOW DATABASES` privilege, only databases the user holds some
+    /// privilege on are listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the enumeration query fails.
+    pub async fn list_databases(&self) -> Result<Vec<EnumeratedDatabase>> {
+        enumeration::list_databases(&self.pool, false).await
+    }
+
+    /// List the databases visible to the current user.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_system` - If true, includes `mysql`, `sys`,
+    ///   `information_schema`, and `performance_schema`
+    pub async fn list_databases_with_options(
+        &self,
+        include_system: bool,
+    ) -> Result<Vec<EnumeratedDatabase>> {
+        enumeration::list_databases(&self.pool, include_system).await
+    }
+
+    /// Create a new adapter connected to a different database on the same server.
+    ///
+    /// The new adapter shares this adapter's configuration and object
+    /// selection, with `database` set so schema collection targets it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database name is invalid or the connection fails.
+    pub async fn connect_to_database(&self, database: &str) -> Result<MySqlAdapter> {
+        let new_url = self.connection_url_for_database(database)?;
+
+        tracing::debug!("Connecting to database: {}", database);
+
+        let mut config = self.config.clone();
+        config.database = Some(database.to_string());
+        let mut adapter = Self::with_config(&new_url, config).await?;
+        adapter.objects = self.objects;
+        Ok(adapter)
+    }
+
+    /// Collect schemas from all accessible databases on the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if database enumeration fails. Per-database failures
+    /// are reported in the result.
+    pub async fn collect_all_databases(
+        &self,
+        config: &MultiDatabaseConfig,
+    ) -> Result<MultiDatabaseResult> {
+        multi_database::collect_all_databases(self, config).await
+    }
 }
 
 // Additional MySqlAdapter methods for data sampling
@@ -0,0 +1,159 @@
+//! Multi-database collection orchestration for MySQL.
+//!
+//! This module enumerates the databases visible to the current user and
+//! collects each one through [`crate::adapters::multi_database`], producing
+//! the same [`MultiDatabaseResult`] as the PostgreSQL adapter.
+//!
+//! # Security
+//! - All operations are read-only
+//! - Database names are validated before connection
+//! - Credentials are never logged or included in results
+
+use super::MySqlAdapter;
+use crate::Result;
+use crate::adapters::DatabaseAdapter;
+use crate::adapters::multi_database::{
+    DatabaseCandidate, MultiDatabaseConfig, MultiDatabaseResult, collect_databases,
+};
+use crate::models::{CollectionMode, DatabaseSchema, DatabaseType, ServerInfo};
+
+/// Collects schema information from all accessible databases on the server.
+///
+/// Databases are enumerated once (honoring `SHOW DATABASES` privileges),
+/// filtered by `config`, and collected concurrently over per-database
+/// connections.
+///
+/// # Errors
+///
+/// Returns an error if database enumeration fails or server information
+/// cannot be retrieved. Per-database failures are reported in the result.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let adapter = MySqlAdapter::new(&database_url).await?;
+/// let config = MultiDatabaseConfig::new().with_max_concurrency(4);
+///
+/// let result = collect_all_databases(&adapter, &config).await?;
+/// println!("Collected {} databases", result.databases.len());
+/// ```
+pub async fn collect_all_databases(
+    adapter: &MySqlAdapter,
+    config: &MultiDatabaseConfig,
+) -> Result<MultiDatabaseResult> {
+    // Every database listed by the server is visible to, and therefore
+    // usable by, the current user
+    let candidates = adapter
+        .list_databases_with_options(true)
+        .await?
+        .into_iter()
+        .map(|db| DatabaseCandidate {
+            name: db.name,
+            is_system_database: db.is_system_database,
+            is_accessible: true,
+        })
+        .collect();
+
+    let server_info = get_server_info(adapter).await?;
+
+    tracing::info!(
+        "Connected to {} {} at {}:{}",
+        server_info.server_type,
+        server_info.version,
+        server_info.host,
+        server_info.port.unwrap_or(3306)
+    );
+
+    Ok(
+        collect_databases(server_info, candidates, config, |name| async move {
+            collect_single_database(adapter, &name).await
+        })
+        .await,
+    )
+}
+
+/// Gets server-level information from the MySQL server.
+///
+/// Database counts are filled in by [`collect_databases`].
+async fn get_server_info(adapter: &MySqlAdapter) -> Result<ServerInfo> {
+    let version: String = sqlx::query_scalar("SELECT VERSION()")
+        .fetch_one(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to get server version", e)
+        })?;
+
+    let connection_user: String = sqlx::query_scalar("SELECT CAST(CURRENT_USER() AS CHAR)")
+        .fetch_one(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to get current user", e)
+        })?;
+
+    // SUPER is reported per grantee; accounts that cannot read
+    // USER_PRIVILEGES are treated as unprivileged
+    let super_grants: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM INFORMATION_SCHEMA.USER_PRIVILEGES
+        WHERE PRIVILEGE_TYPE = 'SUPER'
+        AND GRANTEE = CONCAT('''', SUBSTRING_INDEX(CURRENT_USER(), '@', 1), '''@''',
+                             SUBSTRING_INDEX(CURRENT_USER(), '@', -1), '''')
+        "#,
+    )
+    .fetch_one(&adapter.pool)
+    .await
+    .unwrap_or(0);
+
+    Ok(ServerInfo {
+        server_type: DatabaseType::MySQL,
+        version,
+        host: adapter.config.host.clone(),
+        port: adapter.config.port,
+        total_databases: 0,
+        collected_databases: 0,
+        system_databases_excluded: 0,
+        connection_user,
+        has_superuser_privileges: super_grants > 0,
+        timezone: adapter.server_timezone().await,
+        role: None,
+        collection_mode: CollectionMode::MultiDatabase {
+            discovered: 0,
+            collected: 0,
+            failed: 0,
+        },
+    })
+}
+
+/// Collects schema from a single database.
+///
+/// Uses a reduced connection pool (max 2 connections, 0 min idle) to prevent
+/// connection exhaustion when scanning many databases concurrently. The pool
+/// is explicitly closed after collection to release connections promptly.
+async fn collect_single_database(
+    adapter: &MySqlAdapter,
+    database_name: &str,
+) -> Result<DatabaseSchema> {
+    tracing::debug!("Connecting to database: {}", database_name);
+
+    let db_url = adapter.connection_url_for_database(database_name)?;
+
+    // Only 1-2 queries run at a time per database, so 2 max connections suffice
+    let mut reduced_config = adapter
+        .config
+        .clone()
+        .with_max_connections(2)
+        .with_min_idle_connections(0);
+    reduced_config.database = Some(database_name.to_string());
+
+    let mut db_adapter = MySqlAdapter::with_config(&db_url, reduced_config).await?;
+    db_adapter.objects = adapter.objects;
+
+    tracing::debug!("Collecting schema from database: {}", database_name);
+    let result = db_adapter.collect_schema().await;
+
+    // Close regardless of success/failure to prevent connection exhaustion
+    db_adapter.close().await;
+
+    result
+}
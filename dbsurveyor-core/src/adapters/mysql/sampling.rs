@@ -12,9 +12,14 @@
 //! 4. Fallback to unordered (will use RAND() for sampling)
 
 use crate::adapters::config::SamplingConfig;
-use crate::adapters::helpers::TIMESTAMP_COLUMN_NAMES;
+use crate::adapters::helpers::{
+    TIMESTAMP_COLUMN_NAMES, binary_sample_value, float_sample_value, format_utc_timestamp,
+};
 use crate::error::DbSurveyorError;
-use crate::models::{OrderingStrategy, SampleStatus, SamplingStrategy, SortDirection, TableSample};
+use crate::models::{
+    OrderingStrategy, SampleColumnType, SampleStatus, SampleValueEncoding, SamplingStrategy,
+    SortDirection, TableSample,
+};
 use serde_json::Value as JsonValue;
 use sqlx::{MySqlPool, Row};
 use std::time::Duration;
@@ -226,15 +231,41 @@ fn escape_identifier(ident: &str) -> String {
     ident.replace('`', "``")
 }
 
+/// Chooses how sampled values of a column with the given `DATA_TYPE` are written.
+fn sample_encoding(data_type: &str) -> SampleValueEncoding {
+    match data_type.to_ascii_lowercase().as_str() {
+        "decimal" => SampleValueEncoding::DecimalString,
+        "float" | "double" => SampleValueEncoding::Float,
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+            SampleValueEncoding::Base64
+        }
+        "timestamp" => SampleValueEncoding::TimestampWithOffset,
+        "datetime" | "date" | "time" => SampleValueEncoding::LocalDateTime,
+        _ => SampleValueEncoding::Json,
+    }
+}
+
 /// Build an explicit column projection for a MySQL table.
 ///
-/// Queries `INFORMATION_SCHEMA.COLUMNS` to get every column name and returns a
+/// Queries `INFORMATION_SCHEMA.COLUMNS` to get every column and returns a
 /// comma-separated, backtick-quoted projection string (e.g.,
-/// `` `col1`, `col2`, `col3` ``). Falls back to `*` if the metadata query fails
+/// `` `col1`, `col2`, `col3` ``) with the sample type legend. `DECIMAL`
+/// columns are cast to text so their scale survives. Columns removed by a
+/// `skip-column` pattern are left out, leaving an empty projection if none
+/// remain. Falls back to `*` and an empty legend if the metadata query fails
 /// so that sampling still works even when `INFORMATION_SCHEMA` is unavailable.
-async fn build_column_projection(pool: &MySqlPool, db_name: &str, table: &str) -> String {
+async fn build_column_projection(
+    pool: &MySqlPool,
+    db_name: &str,
+    table: &str,
+    config: &SamplingConfig,
+) -> (String, Vec<SampleColumnType>) {
     let col_query = r#"
-        SELECT CAST(COLUMN_NAME AS CHAR) AS COLUMN_NAME
+        SELECT
+            CAST(COLUMN_NAME AS CHAR) AS COLUMN_NAME,
+            CAST(DATA_TYPE AS CHAR) AS DATA_TYPE,
+            CAST(COLUMN_TYPE AS CHAR) AS COLUMN_TYPE,
+            CAST(NUMERIC_SCALE AS SIGNED) AS NUMERIC_SCALE
         FROM INFORMATION_SCHEMA.COLUMNS
         WHERE TABLE_SCHEMA = ?
         AND TABLE_NAME = ?
@@ -248,14 +279,33 @@ async fn build_column_projection(pool: &MySqlPool, db_name: &str, table: &str) -
         .await
     {
         Ok(rows) if !rows.is_empty() => {
-            let cols: Vec<String> = rows
-                .iter()
-                .map(|r| {
-                    let name: String = r.get("COLUMN_NAME");
-                    format!("`{}`", escape_identifier(&name))
-                })
-                .collect();
-            cols.join(", ")
+            let mut cols = Vec::with_capacity(rows.len());
+            let mut legend = Vec::with_capacity(rows.len());
+            for r in &rows {
+                let name: String = r.get("COLUMN_NAME");
+                if config.skips_column(&name) {
+                    continue;
+                }
+                let data_type: String = r.try_get("DATA_TYPE").unwrap_or_default();
+                let column_type: String = r.try_get("COLUMN_TYPE").unwrap_or_default();
+                let encoding = sample_encoding(&data_type);
+                let quoted = format!("`{}`", escape_identifier(&name));
+                cols.push(if encoding == SampleValueEncoding::DecimalString {
+                    format!("CAST({quoted} AS CHAR) AS {quoted}")
+                } else {
+                    quoted
+                });
+                let scale = if encoding == SampleValueEncoding::DecimalString {
+                    r.try_get::<Option<i64>, _>("NUMERIC_SCALE")
+                        .ok()
+                        .flatten()
+                        .and_then(|scale| u32::try_from(scale).ok())
+                } else {
+                    None
+                };
+                legend.push(SampleColumnType::new(name, column_type, encoding).with_scale(scale));
+            }
+            (cols.join(", "), legend)
         }
         _ => {
             tracing::debug!(
@@ -263,7 +313,7 @@ async fn build_column_projection(pool: &MySqlPool, db_name: &str, table: &str) -
                 db_name,
                 table
             );
-            "*".to_string()
+            ("*".to_string(), Vec::new())
         }
     }
 }
@@ -321,7 +371,13 @@ pub async fn sample_table(
     // Fetch column names so we can project explicitly instead of SELECT *.
     // This avoids fetching unnecessary BLOB/TEXT columns and gives the caller
     // control over which columns are transferred.
-    let projection = build_column_projection(pool, db_name, table).await;
+    let (projection, column_types) = build_column_projection(pool, db_name, table, config).await;
+    if projection.is_empty() {
+        return Err(DbSurveyorError::configuration(format!(
+            "Every column of table '{}' matches a skip-column pattern",
+            table
+        )));
+    }
 
     // Build and execute the sample query.
     // Identifiers are escaped to prevent SQL injection from embedded backticks.
@@ -347,7 +403,7 @@ pub async fn sample_table(
     // Convert rows to JSON
     let mut json_rows = Vec::with_capacity(rows.len());
     for row in &rows {
-        let json_row = row_to_json(row, config, &mut warnings)?;
+        let json_row = row_to_json(row, &column_types, config, &mut warnings)?;
         json_rows.push(json_row);
     }
 
@@ -407,12 +463,14 @@ pub async fn sample_table(
         collected_at: chrono::Utc::now(),
         warnings,
         sample_status: Some(SampleStatus::Complete),
+        column_types,
     })
 }
 
 /// Convert a database row to JSON, checking for sensitive data patterns.
 fn row_to_json(
     row: &sqlx::mysql::MySqlRow,
+    column_types: &[SampleColumnType],
     config: &SamplingConfig,
     warnings: &mut Vec<String>,
 ) -> Result<JsonValue, DbSurveyorError> {
@@ -426,11 +484,11 @@ fn row_to_json(
         // Check for sensitive column names if warnings are enabled
         if config.warn_sensitive {
             let name_lower = column_name.to_lowercase();
-            for (regex, description) in &config.compiled_patterns {
-                if regex.is_match(&name_lower) {
+            for pattern in &config.compiled_patterns {
+                if pattern.regex.is_match(&name_lower) {
                     warnings.push(format!(
                         "Column '{}' may contain sensitive data ({})",
-                        column_name, description
+                        column_name, pattern.description
                     ));
                     break;
                 }
@@ -438,7 +496,11 @@ fn row_to_json(
         }
 
         // Try to extract value as JSON-compatible type
-        let value = extract_column_value(row, column_name);
+        let encoding = column_types
+            .iter()
+            .find(|c| c.name == column_name)
+            .map(|c| c.encoding);
+        let value = extract_column_value(row, column_name, encoding);
         map.insert(column_name.to_string(), value);
     }
 
@@ -446,7 +508,22 @@ fn row_to_json(
 }
 
 /// Extract a column value as a JSON value.
-fn extract_column_value(row: &sqlx::mysql::MySqlRow, column_name: &str) -> JsonValue {
+///
+/// Binary columns are read as bytes before text is tried, since a binary
+/// value that happens to be valid UTF-8 would otherwise become a string.
+fn extract_column_value(
+    row: &sqlx::mysql::MySqlRow,
+    column_name: &str,
+    encoding: Option<SampleValueEncoding>,
+) -> JsonValue {
+    if encoding == Some(SampleValueEncoding::Base64)
+        && let Ok(v) = row.try_get::<Option<Vec<u8>>, _>(column_name)
+    {
+        return v
+            .map(|bytes| binary_sample_value(&bytes))
+            .unwrap_or(JsonValue::Null);
+    }
+
     // Try different types in order of likelihood
     if let Ok(v) = row.try_get::<Option<String>, _>(column_name) {
         return v.map(JsonValue::String).unwrap_or(JsonValue::Null);
@@ -457,23 +534,82 @@ fn extract_column_value(row: &sqlx::mysql::MySqlRow, column_name: &str) -> JsonV
             .unwrap_or(JsonValue::Null);
     }
     if let Ok(v) = row.try_get::<Option<f64>, _>(column_name) {
+        return v.map(float_sample_value).unwrap_or(JsonValue::Null);
+    }
+    if let Ok(v) = row.try_get::<Option<f32>, _>(column_name) {
         return v
-            .and_then(serde_json::Number::from_f64)
-            .map(JsonValue::Number)
+            .map(|n| float_sample_value(f64::from(n)))
             .unwrap_or(JsonValue::Null);
     }
     if let Ok(v) = row.try_get::<Option<bool>, _>(column_name) {
         return v.map(JsonValue::Bool).unwrap_or(JsonValue::Null);
     }
+    if let Some(v) = extract_temporal_value(row, column_name) {
+        return v;
+    }
+    if let Ok(v) = row.try_get::<Option<Vec<u8>>, _>(column_name) {
+        return v
+            .map(|bytes| binary_sample_value(&bytes))
+            .unwrap_or(JsonValue::Null);
+    }
 
     // Default to null for unsupported types
     JsonValue::Null
 }
 
+/// Extract a date or time column as an ISO 8601 string.
+///
+/// `TIMESTAMP` values are converted by the server to the session time zone,
+/// which is always UTC, and are written with a `Z` suffix. `DATETIME`,
+/// `DATE`, and `TIME` values carry no zone and are written as stored; the
+/// server's zone is recorded in `DatabaseInfo::timezone` to interpret them.
+fn extract_temporal_value(row: &sqlx::mysql::MySqlRow, column_name: &str) -> Option<JsonValue> {
+    use sqlx::{Column, TypeInfo};
+
+    let column = row.columns().iter().find(|c| c.name() == column_name)?;
+    let value = match column.type_info().name() {
+        "TIMESTAMP" => row
+            .try_get::<Option<chrono::DateTime<chrono::Utc>>, _>(column_name)
+            .ok()?
+            .map(|ts| format_utc_timestamp(&ts)),
+        "DATETIME" => row
+            .try_get::<Option<chrono::NaiveDateTime>, _>(column_name)
+            .ok()?
+            .map(|dt| format_local_datetime(&dt)),
+        "DATE" => row
+            .try_get::<Option<chrono::NaiveDate>, _>(column_name)
+            .ok()?
+            .map(|d| d.to_string()),
+        "TIME" => row
+            .try_get::<Option<chrono::NaiveTime>, _>(column_name)
+            .ok()?
+            .map(|t| t.to_string()),
+        _ => return None,
+    };
+    Some(value.map_or(JsonValue::Null, JsonValue::String))
+}
+
+/// Formats a zone-less `DATETIME` as an ISO 8601 local date-time, without
+/// the `Z` suffix that would mislabel it as UTC.
+fn format_local_datetime(datetime: &chrono::NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_local_datetime() {
+        let datetime = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .and_then(|date| date.and_hms_milli_opt(3, 4, 5, 120))
+            .expect("valid date-time");
+        assert_eq!(format_local_datetime(&datetime), "2024-01-02T03:04:05.120");
+
+        let whole_seconds = chrono::Timelike::with_nanosecond(&datetime, 0).expect("valid time");
+        assert_eq!(format_local_datetime(&whole_seconds), "2024-01-02T03:04:05");
+    }
+
     #[test]
     fn test_generate_order_by_primary_key() {
         let strategy = OrderingStrategy::PrimaryKey {
@@ -518,6 +654,25 @@ fn test_generate_order_by_unordered() {
         assert_eq!(clause, "ORDER BY RAND()");
     }
 
+    #[test]
+    fn test_sample_encoding() {
+        assert_eq!(
+            sample_encoding("DECIMAL"),
+            SampleValueEncoding::DecimalString
+        );
+        assert_eq!(sample_encoding("double"), SampleValueEncoding::Float);
+        assert_eq!(sample_encoding("varbinary"), SampleValueEncoding::Base64);
+        assert_eq!(
+            sample_encoding("timestamp"),
+            SampleValueEncoding::TimestampWithOffset
+        );
+        assert_eq!(
+            sample_encoding("datetime"),
+            SampleValueEncoding::LocalDateTime
+        );
+        assert_eq!(sample_encoding("varchar"), SampleValueEncoding::Json);
+    }
+
     #[test]
     fn test_escape_identifier() {
         assert_eq!(escape_identifier("normal"), "normal");
@@ -4,9 +4,13 @@
 //! tables, columns, constraints, indexes, and foreign keys from MySQL.
 
 use super::MySqlAdapter;
+use super::enumeration::EnumeratedDatabase;
 use super::type_mapping::map_mysql_type;
 use crate::Result;
-use crate::adapters::helpers::resolve_optional_collection;
+use crate::adapters::helpers::{
+    collect_if_selected, collect_within_table_timeout, resolve_optional_collection,
+};
+use crate::defaults::classify_default;
 use crate::models::*;
 use sqlx::Row;
 use std::collections::HashMap;
@@ -23,10 +27,115 @@ fn parse_referential_action(action: Option<String>) -> Option<ReferentialAction>
     })
 }
 
+/// Builds generated column metadata from the `EXTRA` and `GENERATION_EXPRESSION` columns.
+///
+/// MySQL 8.0 also reports `DEFAULT_GENERATED` in `EXTRA` for columns with an
+/// expression default; those are ordinary columns and are not treated as generated.
+pub(super) fn parse_generated_column(
+    extra: &str,
+    expression: Option<String>,
+) -> Option<GeneratedColumn> {
+    let extra = extra.to_uppercase();
+    let kind = if extra.contains("VIRTUAL GENERATED") {
+        GenerationKind::Virtual
+    } else if extra.contains("STORED GENERATED") {
+        GenerationKind::Stored
+    } else {
+        return None;
+    };
+
+    expression
+        .filter(|e| !e.is_empty())
+        .map(|expression| GeneratedColumn { expression, kind })
+}
+
+/// Classifies a `COLUMN_DEFAULT` value.
+///
+/// MySQL reports literal defaults unquoted (`abc` for `DEFAULT 'abc'`), so
+/// they are re-quoted unless the column is numeric. Expression defaults are
+/// flagged with `DEFAULT_GENERATED` in `EXTRA` and classified as written.
+pub(super) fn parse_column_default(
+    default: Option<&str>,
+    extra: &str,
+    data_type: &UnifiedDataType,
+) -> Option<DefaultValue> {
+    let default = default?;
+    let upper = default.trim().to_ascii_uppercase();
+    if extra.to_uppercase().contains("DEFAULT_GENERATED")
+        || upper.starts_with("CURRENT_TIMESTAMP")
+        || upper == "NULL"
+    {
+        return Some(classify_default(default));
+    }
+
+    let numeric = matches!(
+        data_type,
+        UnifiedDataType::Integer { .. }
+            | UnifiedDataType::Float { .. }
+            | UnifiedDataType::Decimal { .. }
+            | UnifiedDataType::Boolean
+    );
+    let value = if numeric {
+        default.to_string()
+    } else {
+        format!("'{}'", default.replace('\'', "''"))
+    };
+    Some(DefaultValue::Literal { value })
+}
+
+/// One row of `INFORMATION_SCHEMA.PARTITIONS`
+#[derive(Debug, Clone)]
+pub(super) struct PartitionRow {
+    pub table_name: String,
+    pub partition_name: String,
+    pub subpartition_name: Option<String>,
+    pub ordinal_position: u32,
+    pub method: String,
+    pub expression: Option<String>,
+    pub subpartition_method: Option<String>,
+    pub subpartition_expression: Option<String>,
+    pub description: Option<String>,
+    pub row_count: Option<u64>,
+}
+
+/// Groups partition rows by table, preserving row order within each table
+pub(super) fn group_partition_rows(rows: Vec<PartitionRow>) -> Vec<(String, PartitionInfo)> {
+    let mut grouped: Vec<(String, PartitionInfo)> = Vec::new();
+
+    for row in rows {
+        let partition = Partition {
+            name: row.partition_name,
+            subpartition_name: row.subpartition_name,
+            ordinal_position: row.ordinal_position,
+            description: row.description,
+            row_count: row.row_count,
+        };
+
+        match grouped.last_mut() {
+            Some((table_name, info)) if *table_name == row.table_name => {
+                info.partitions.push(partition);
+            }
+            _ => grouped.push((
+                row.table_name,
+                PartitionInfo {
+                    method: row.method,
+                    expression: row.expression,
+                    subpartition_method: row.subpartition_method,
+                    subpartition_expression: row.subpartition_expression,
+                    partitions: vec![partition],
+                },
+            )),
+        }
+    }
+
+    grouped
+}
+
 /// Main entry point for schema collection
 pub(crate) async fn collect_schema(adapter: &MySqlAdapter) -> Result<DatabaseSchema> {
     let start_time = std::time::Instant::now();
     let mut warnings = Vec::new();
+    let mut object_failures = Vec::new();
 
     let db_name = adapter
         .config
@@ -48,7 +157,7 @@ pub(crate) async fn collect_schema(adapter: &MySqlAdapter) -> Result<DatabaseSch
     // Collect tables with comprehensive metadata
     tracing::debug!("Enumerating database tables");
     let table_collection_start = std::time::Instant::now();
-    let tables = match collect_tables(adapter, &db_name).await {
+    let tables = match collect_tables(adapter, &db_name, &mut object_failures).await {
         Ok(tables) => {
             let table_collection_duration = table_collection_start.elapsed();
             tracing::info!(
@@ -64,10 +173,55 @@ pub(crate) async fn collect_schema(adapter: &MySqlAdapter) -> Result<DatabaseSch
         }
     };
 
+    // Attach partitioning metadata to partitioned tables
+    let partitions: HashMap<String, PartitionInfo> = resolve_optional_collection(
+        "partition metadata",
+        collect_partitions(adapter, &db_name).await,
+        &mut warnings,
+    )
+    .into_iter()
+    .collect();
+    // Shared tablespaces are only reported by MySQL 8.0+; older servers and
+    // MariaDB leave every table without one
+    let tablespaces: HashMap<String, String> = collect_tablespaces(adapter, &db_name)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::debug!("Tablespaces not collected: {}", e);
+            Vec::new()
+        })
+        .into_iter()
+        .collect();
+    let versioned: HashMap<String, SystemVersioning> = resolve_optional_collection(
+        "system versioning",
+        collect_system_versioning(adapter, &db_name).await,
+        &mut warnings,
+    )
+    .into_iter()
+    .collect();
+    let tables: Vec<Table> = tables
+        .into_iter()
+        .map(|mut table| {
+            table.partitioning = partitions.get(&table.name).cloned();
+            if let Some(tablespace) = tablespaces.get(&table.name) {
+                table
+                    .options
+                    .get_or_insert_with(TableOptions::default)
+                    .tablespace = Some(tablespace.clone());
+            }
+            if let Some(versioning) = versioned.get(&table.name) {
+                table
+                    .options
+                    .get_or_insert_with(TableOptions::default)
+                    .system_versioning = Some(versioning.clone());
+            }
+            table
+        })
+        .collect();
+
     // Collect views
     let views = resolve_optional_collection(
         "views",
-        collect_views(adapter, &db_name).await,
+        collect_if_selected(adapter.objects.views, collect_views(adapter, &db_name)).await,
         &mut warnings,
     );
 
@@ -99,11 +253,19 @@ pub(crate) async fn collect_schema(adapter: &MySqlAdapter) -> Result<DatabaseSch
                 .unwrap_or(u64::MAX),
             collector_version: env!("CARGO_PKG_VERSION").to_string(),
             warnings,
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
         },
     };
 
     // Aggregate indexes and constraints from per-table data into schema-level vectors
-    let schema = schema.with_aggregated_indexes_and_constraints();
+    let schema = schema
+        .with_aggregated_indexes_and_constraints()
+        .with_object_failures(object_failures);
 
     Ok(schema)
 }
@@ -159,37 +321,49 @@ async fn collect_database_info(adapter: &MySqlAdapter, db_name: &str) -> Result<
         .flatten();
 
     // Check if this is a system database
-    let is_system_database = matches!(
-        db_name.to_lowercase().as_str(),
-        "mysql" | "information_schema" | "performance_schema" | "sys"
-    );
+    let is_system_database = EnumeratedDatabase::check_is_system_database(db_name);
 
     Ok(DatabaseInfo {
         name: db_name.to_string(),
+        engine: Some(DatabaseType::MySQL),
         version: Some(version),
         size_bytes: size_bytes.map(|s| s.max(0) as u64),
         encoding,
         collation,
+        timezone: adapter.server_timezone().await,
         owner: None, // MySQL doesn't have per-database owners like PostgreSQL
         is_system_database,
         access_level: AccessLevel::Full,
+        access_probe: None,
+        schemas: Vec::new(),
         collection_status: CollectionStatus::Success,
-    })
+    }
+    .with_access_probe(super::access::probe_access(adapter, db_name).await))
 }
 
 /// Collects all tables from the MySQL database
-async fn collect_tables(adapter: &MySqlAdapter, db_name: &str) -> Result<Vec<Table>> {
+/// Collects all base tables in the database.
+///
+/// A table that exceeds the per-table timeout is skipped and recorded in
+/// `failures`.
+async fn collect_tables(
+    adapter: &MySqlAdapter,
+    db_name: &str,
+    failures: &mut Vec<ObjectFailure>,
+) -> Result<Vec<Table>> {
     // Cast to CHAR to avoid VARBINARY type issues in MySQL 8.0+
     let tables_query = r#"
         SELECT
             CAST(TABLE_NAME AS CHAR) as TABLE_NAME,
             CAST(TABLE_COMMENT AS CHAR) as TABLE_COMMENT,
             TABLE_ROWS,
-            DATA_LENGTH,
-            INDEX_LENGTH
+            CAST(DATA_LENGTH + INDEX_LENGTH AS SIGNED) as TABLE_BYTES,
+            CAST(ENGINE AS CHAR) as ENGINE,
+            CAST(ROW_FORMAT AS CHAR) as ROW_FORMAT,
+            CAST(AUTO_INCREMENT AS UNSIGNED) as AUTO_INCREMENT
         FROM INFORMATION_SCHEMA.TABLES
         WHERE TABLE_SCHEMA = ?
-        AND TABLE_TYPE = 'BASE TABLE'
+        AND TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED')
         ORDER BY TABLE_NAME
     "#;
 
@@ -209,49 +383,117 @@ async fn collect_tables(adapter: &MySqlAdapter, db_name: &str) -> Result<Vec<Tab
         })?;
         let table_comment: Option<String> = row.try_get("TABLE_COMMENT").ok();
         let estimated_rows: Option<i64> = row.try_get("TABLE_ROWS").ok();
+        let size_bytes: Option<i64> = row.try_get("TABLE_BYTES").ok().flatten();
+        let options = table_options(
+            row.try_get("ENGINE").ok().flatten(),
+            row.try_get("ROW_FORMAT").ok().flatten(),
+            row.try_get("AUTO_INCREMENT").ok().flatten(),
+        );
 
-        // Collect columns for this table
-        let columns = collect_table_columns(adapter, db_name, &table_name).await?;
-
-        // Collect primary key
-        let primary_key = collect_table_primary_key(adapter, db_name, &table_name).await?;
-
-        // Collect foreign keys
-        let foreign_keys = collect_table_foreign_keys(adapter, db_name, &table_name).await?;
-
-        // Collect indexes
-        let indexes = collect_table_indexes(adapter, db_name, &table_name).await?;
-
-        // Collect constraints
-        let constraints = collect_table_constraints(adapter, db_name, &table_name).await?;
+        let collected = collect_within_table_timeout(
+            collect_table(
+                adapter,
+                db_name,
+                &table_name,
+                table_comment,
+                estimated_rows,
+                size_bytes,
+                options,
+            ),
+            adapter.config.table_timeout,
+            Some(db_name),
+            &table_name,
+            failures,
+        )
+        .await;
+
+        if let Some(table) = collected.transpose()? {
+            tables.push(table);
+        }
+    }
 
-        // Filter out empty comments (MySQL returns empty string for no comment)
-        let comment = table_comment.filter(|c| !c.is_empty());
+    Ok(tables)
+}
 
-        let table = Table {
-            name: table_name.clone(),
-            schema: Some(db_name.to_string()),
-            columns,
-            primary_key,
-            foreign_keys,
-            indexes,
-            constraints,
-            comment,
-            row_count: estimated_rows.map(|r| r.max(0) as u64),
-        };
+/// Storage engine, row format, and auto-increment counter of a table, or
+/// `None` when the catalog reports none of them.
+pub(super) fn table_options(
+    engine: Option<String>,
+    row_format: Option<String>,
+    auto_increment: Option<u64>,
+) -> Option<TableOptions> {
+    let options = TableOptions {
+        engine: engine.filter(|e| !e.is_empty()),
+        row_format: row_format.filter(|f| !f.is_empty()),
+        auto_increment,
+        ..TableOptions::default()
+    };
+    (options != TableOptions::default()).then_some(options)
+}
 
-        tracing::debug!(
-            "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
-            table.name,
-            table.columns.len(),
-            table.foreign_keys.len(),
-            table.indexes.len()
-        );
+/// Collects a single table's columns, keys, indexes, and constraints.
+async fn collect_table(
+    adapter: &MySqlAdapter,
+    db_name: &str,
+    table_name: &str,
+    table_comment: Option<String>,
+    estimated_rows: Option<i64>,
+    size_bytes: Option<i64>,
+    options: Option<TableOptions>,
+) -> Result<Table> {
+    // Collect columns for this table
+    let columns = collect_table_columns(adapter, db_name, table_name).await?;
+
+    // Collect primary key
+    let primary_key = collect_table_primary_key(adapter, db_name, table_name).await?;
+
+    // Collect foreign keys
+    let foreign_keys = collect_table_foreign_keys(adapter, db_name, table_name).await?;
+
+    // Collect indexes
+    let indexes = collect_if_selected(
+        adapter.objects.indexes,
+        collect_table_indexes(adapter, db_name, table_name),
+    )
+    .await?;
+
+    // Collect constraints
+    let constraints = collect_if_selected(
+        adapter.objects.constraints,
+        collect_table_constraints(adapter, db_name, table_name),
+    )
+    .await?;
+
+    // Filter out empty comments (MySQL returns empty string for no comment)
+    let comment = table_comment.filter(|c| !c.is_empty());
+
+    let table = Table {
+        name: table_name.to_string(),
+        schema: Some(db_name.to_string()),
+        columns,
+        primary_key,
+        foreign_keys,
+        indexes,
+        constraints,
+        comment,
+        row_count: estimated_rows.map(|r| r.max(0) as u64),
+        size_bytes: size_bytes.map(|b| b.max(0) as u64),
+        validator: None,
+        partitioning: None,
+        virtual_table: None,
+        options,
+        inheritance: None,
+    };
 
-        tables.push(table);
-    }
+    tracing::debug!(
+        "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
+        table.name,
+        table.columns.len(),
+        table.foreign_keys.len(),
+        table.indexes.len()
+    );
 
-    Ok(tables)
+    Ok(table)
 }
 
 /// Collects column metadata for a specific table
@@ -274,7 +516,10 @@ async fn collect_table_columns(
             c.ORDINAL_POSITION,
             CAST(c.COLUMN_COMMENT AS CHAR) as COLUMN_COMMENT,
             CAST(c.EXTRA AS CHAR) as EXTRA,
-            CAST(c.COLUMN_KEY AS CHAR) as COLUMN_KEY
+            CAST(c.COLUMN_KEY AS CHAR) as COLUMN_KEY,
+            CAST(c.GENERATION_EXPRESSION AS CHAR) as GENERATION_EXPRESSION,
+            CAST(c.CHARACTER_SET_NAME AS CHAR) as CHARACTER_SET_NAME,
+            CAST(c.COLLATION_NAME AS CHAR) as COLLATION_NAME
         FROM INFORMATION_SCHEMA.COLUMNS c
         WHERE c.TABLE_SCHEMA = ?
         AND c.TABLE_NAME = ?
@@ -322,6 +567,9 @@ async fn collect_table_columns(
         let column_key: String = row.try_get("COLUMN_KEY").map_err(|e| {
             crate::error::DbSurveyorError::collection_failed("Failed to parse COLUMN_KEY", e)
         })?;
+        let generation_expression: Option<String> = row.try_get("GENERATION_EXPRESSION").ok();
+        let character_set: Option<String> = row.try_get("CHARACTER_SET_NAME").ok().flatten();
+        let collation: Option<String> = row.try_get("COLLATION_NAME").ok().flatten();
 
         // Check for unsigned in COLUMN_TYPE
         let is_unsigned = column_type.to_lowercase().contains("unsigned");
@@ -339,6 +587,9 @@ async fn collect_table_columns(
             numeric_scale.map(|s| s as u8),
         );
 
+        let normalized_default =
+            parse_column_default(column_default.as_deref(), &extra, &unified_data_type);
+
         // Filter out empty comments
         let comment = column_comment.filter(|c| !c.is_empty());
 
@@ -348,9 +599,17 @@ async fn collect_table_columns(
             is_nullable: is_nullable.to_uppercase() == "YES",
             is_primary_key: column_key == "PRI",
             is_auto_increment: extra.to_lowercase().contains("auto_increment"),
+            normalized_default,
             default_value: column_default,
             comment,
             ordinal_position,
+            generated: parse_generated_column(&extra, generation_expression),
+            declared_type: None,
+            collation,
+            character_set,
+            storage: None,
+            identity: None,
+            masking: None,
         };
 
         columns.push(column);
@@ -563,6 +822,7 @@ async fn collect_table_indexes(
             is_unique,
             is_primary,
             index_type,
+            options: None,
         });
 
         index.columns.push(IndexColumn {
@@ -689,6 +949,165 @@ async fn get_constraint_columns(
     Ok(columns)
 }
 
+/// Collects partitioning details for all partitioned tables in the database
+async fn collect_partitions(
+    adapter: &MySqlAdapter,
+    db_name: &str,
+) -> Result<Vec<(String, PartitionInfo)>> {
+    // Cast to CHAR to avoid VARBINARY type issues in MySQL 8.0+
+    let partitions_query = r#"
+        SELECT
+            CAST(TABLE_NAME AS CHAR) as TABLE_NAME,
+            CAST(PARTITION_NAME AS CHAR) as PARTITION_NAME,
+            CAST(SUBPARTITION_NAME AS CHAR) as SUBPARTITION_NAME,
+            PARTITION_ORDINAL_POSITION,
+            CAST(PARTITION_METHOD AS CHAR) as PARTITION_METHOD,
+            CAST(SUBPARTITION_METHOD AS CHAR) as SUBPARTITION_METHOD,
+            CAST(PARTITION_EXPRESSION AS CHAR) as PARTITION_EXPRESSION,
+            CAST(SUBPARTITION_EXPRESSION AS CHAR) as SUBPARTITION_EXPRESSION,
+            CAST(PARTITION_DESCRIPTION AS CHAR) as PARTITION_DESCRIPTION,
+            CAST(TABLE_ROWS AS SIGNED) as TABLE_ROWS
+        FROM INFORMATION_SCHEMA.PARTITIONS
+        WHERE TABLE_SCHEMA = ?
+        AND PARTITION_NAME IS NOT NULL
+        ORDER BY TABLE_NAME, PARTITION_ORDINAL_POSITION, SUBPARTITION_ORDINAL_POSITION
+    "#;
+
+    let partition_rows = sqlx::query(partitions_query)
+        .bind(db_name)
+        .fetch_all(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to collect partitions", e)
+        })?;
+
+    let mut rows = Vec::with_capacity(partition_rows.len());
+
+    for row in partition_rows {
+        let table_name: String = row.try_get("TABLE_NAME").map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to parse TABLE_NAME", e)
+        })?;
+        let partition_name: String = row.try_get("PARTITION_NAME").map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to parse PARTITION_NAME", e)
+        })?;
+        let method: String = row.try_get("PARTITION_METHOD").map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to parse PARTITION_METHOD", e)
+        })?;
+        let ordinal_position: Option<u32> = row.try_get("PARTITION_ORDINAL_POSITION").ok();
+        let table_rows: Option<i64> = row.try_get("TABLE_ROWS").ok();
+
+        rows.push(PartitionRow {
+            table_name,
+            partition_name,
+            subpartition_name: row.try_get("SUBPARTITION_NAME").ok(),
+            ordinal_position: ordinal_position.unwrap_or(0),
+            method,
+            expression: row.try_get("PARTITION_EXPRESSION").ok(),
+            subpartition_method: row.try_get("SUBPARTITION_METHOD").ok(),
+            subpartition_expression: row.try_get("SUBPARTITION_EXPRESSION").ok(),
+            description: row.try_get("PARTITION_DESCRIPTION").ok(),
+            row_count: table_rows.map(|r| r.max(0) as u64),
+        });
+    }
+
+    Ok(group_partition_rows(rows))
+}
+
+/// Collects the shared (system or general) InnoDB tablespace of each table
+/// stored in one, keyed by table name. Tables in their own file-per-table
+/// tablespace and partitions are omitted.
+async fn collect_tablespaces(
+    adapter: &MySqlAdapter,
+    db_name: &str,
+) -> Result<Vec<(String, String)>> {
+    let tablespaces_query = r#"
+        SELECT
+            CAST(SUBSTRING_INDEX(t.NAME, '/', -1) AS CHAR) as TABLE_NAME,
+            CAST(s.NAME AS CHAR) as TABLESPACE_NAME
+        FROM INFORMATION_SCHEMA.INNODB_TABLES t
+        JOIN INFORMATION_SCHEMA.INNODB_TABLESPACES s ON s.SPACE = t.SPACE
+        WHERE SUBSTRING_INDEX(t.NAME, '/', 1) = ?
+        AND t.NAME NOT LIKE '%#%'
+        AND s.SPACE_TYPE <> 'Single'
+    "#;
+
+    let rows = sqlx::query(tablespaces_query)
+        .bind(db_name)
+        .fetch_all(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to collect tablespaces", e)
+        })?;
+
+    rows.iter()
+        .map(|row| {
+            let table: String = row.try_get("TABLE_NAME").map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed("Failed to parse TABLE_NAME", e)
+            })?;
+            let tablespace: String = row.try_get("TABLESPACE_NAME").map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed(
+                    "Failed to parse TABLESPACE_NAME",
+                    e,
+                )
+            })?;
+            Ok((table, tablespace))
+        })
+        .collect()
+}
+
+/// Collects the period columns of MariaDB system-versioned tables, keyed by
+/// table name. Columns declared implicitly are hidden from
+/// `INFORMATION_SCHEMA.COLUMNS` and left unnamed. MariaDB keeps history
+/// until it is deleted with `DELETE HISTORY` or by dropping history
+/// partitions, so no retention is recorded. MySQL has no system versioning
+/// and returns no rows.
+async fn collect_system_versioning(
+    adapter: &MySqlAdapter,
+    db_name: &str,
+) -> Result<Vec<(String, SystemVersioning)>> {
+    let versioning_query = r#"
+        SELECT
+            CAST(t.TABLE_NAME AS CHAR) as TABLE_NAME,
+            CAST(MAX(CASE WHEN c.EXTRA LIKE '%ROW START%' THEN c.COLUMN_NAME END) AS CHAR)
+                as ROW_START,
+            CAST(MAX(CASE WHEN c.EXTRA LIKE '%ROW END%' THEN c.COLUMN_NAME END) AS CHAR)
+                as ROW_END
+        FROM INFORMATION_SCHEMA.TABLES t
+        LEFT JOIN INFORMATION_SCHEMA.COLUMNS c
+            ON c.TABLE_SCHEMA = t.TABLE_SCHEMA AND c.TABLE_NAME = t.TABLE_NAME
+        WHERE t.TABLE_SCHEMA = ?
+        AND t.TABLE_TYPE = 'SYSTEM VERSIONED'
+        GROUP BY t.TABLE_NAME
+    "#;
+
+    let rows = sqlx::query(versioning_query)
+        .bind(db_name)
+        .fetch_all(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to collect system versioning",
+                e,
+            )
+        })?;
+
+    rows.iter()
+        .map(|row| {
+            let table: String = row.try_get("TABLE_NAME").map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed("Failed to parse TABLE_NAME", e)
+            })?;
+            Ok((
+                table,
+                SystemVersioning {
+                    row_start: row.try_get("ROW_START").ok().flatten(),
+                    row_end: row.try_get("ROW_END").ok().flatten(),
+                    ..SystemVersioning::default()
+                },
+            ))
+        })
+        .collect()
+}
+
 /// Collects views from the MySQL database
 async fn collect_views(adapter: &MySqlAdapter, db_name: &str) -> Result<Vec<View>> {
     // Cast to CHAR to avoid VARBINARY type issues in MySQL 8.0+
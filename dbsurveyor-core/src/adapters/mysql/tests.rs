@@ -167,34 +167,25 @@ fn test_map_mysql_integer_types() {
 
 #[test]
 fn test_map_mysql_decimal_types() {
-    // DECIMAL with precision and scale - maps to Float
+    // DECIMAL keeps precision and scale
     let result = map_mysql_type("decimal", None, Some(10), Some(2));
-    assert!(matches!(
-        result,
-        UnifiedDataType::Float {
-            precision: Some(10)
-        }
-    ));
-
-    // DECIMAL with scale 0 - maps to Integer
-    let result = map_mysql_type("decimal", None, Some(10), Some(0));
-    assert!(matches!(
+    assert_eq!(
         result,
-        UnifiedDataType::Integer {
-            bits: 64,
-            signed: true
+        UnifiedDataType::Decimal {
+            precision: Some(10),
+            scale: Some(2)
         }
-    ));
+    );
 
-    // Small DECIMAL with scale 0 - maps to smaller Integer
-    let result = map_mysql_type("decimal", None, Some(4), Some(0));
-    assert!(matches!(
+    // DECIMAL with scale 0 stays exact rather than narrowing to an integer
+    let result = map_mysql_type("numeric", None, Some(20), Some(0));
+    assert_eq!(
         result,
-        UnifiedDataType::Integer {
-            bits: 16,
-            signed: true
+        UnifiedDataType::Decimal {
+            precision: Some(20),
+            scale: Some(0)
         }
-    ));
+    );
 }
 
 #[test]
@@ -387,20 +378,13 @@ fn test_map_mysql_bit_type() {
     assert!(matches!(result, UnifiedDataType::Boolean));
 
     let result = map_mysql_type("bit", Some(8), None, None);
-    assert!(matches!(
-        result,
-        UnifiedDataType::Binary {
-            max_length: Some(1)
-        }
-    ));
+    assert_eq!(result, UnifiedDataType::Bit { length: Some(8) });
 
-    let result = map_mysql_type("bit", Some(64), None, None);
-    assert!(matches!(
-        result,
-        UnifiedDataType::Binary {
-            max_length: Some(8)
-        }
-    ));
+    // information_schema reports the BIT length as NUMERIC_PRECISION
+    let result = map_mysql_type("bit", None, Some(64), None);
+    assert_eq!(result, UnifiedDataType::Bit { length: Some(64) });
+    let result = map_mysql_type("bit", None, Some(1), None);
+    assert!(matches!(result, UnifiedDataType::Boolean));
 }
 
 #[test]
@@ -488,3 +472,237 @@ fn test_validate_mysql_connection_string_missing_host() {
     let result = validate_mysql_connection_string("mysql:///test");
     assert!(result.is_err());
 }
+
+// =============================================================================
+// Generated Column and Partition Tests
+// =============================================================================
+
+#[test]
+fn test_parse_generated_column_kinds() {
+    use super::schema_collection::parse_generated_column;
+    use crate::models::GenerationKind;
+
+    let virtual_col =
+        parse_generated_column("VIRTUAL GENERATED", Some("(`price` * `qty`)".to_string()))
+            .expect("virtual column should be detected");
+    assert_eq!(virtual_col.kind, GenerationKind::Virtual);
+    assert_eq!(virtual_col.expression, "(`price` * `qty`)");
+
+    let stored_col = parse_generated_column("STORED GENERATED", Some("lower(`email`)".to_string()))
+        .expect("stored column should be detected");
+    assert_eq!(stored_col.kind, GenerationKind::Stored);
+}
+
+#[test]
+fn test_parse_generated_column_ignores_expression_defaults() {
+    use super::schema_collection::parse_generated_column;
+
+    // MySQL 8.0 reports expression defaults as DEFAULT_GENERATED
+    assert!(parse_generated_column("DEFAULT_GENERATED", Some(String::new())).is_none());
+    assert!(parse_generated_column("auto_increment", None).is_none());
+    assert!(parse_generated_column("", Some(String::new())).is_none());
+}
+
+#[test]
+fn test_parse_column_default() {
+    use super::schema_collection::parse_column_default;
+    use crate::models::DefaultValue;
+
+    let text = UnifiedDataType::String { max_length: None };
+    let literal = |value: &str| {
+        Some(DefaultValue::Literal {
+            value: value.to_string(),
+        })
+    };
+
+    // Literal defaults are reported unquoted
+    assert_eq!(
+        parse_column_default(Some("it's"), "", &text),
+        literal("'it''s'")
+    );
+    assert_eq!(
+        parse_column_default(
+            Some("0"),
+            "",
+            &UnifiedDataType::Integer {
+                bits: 32,
+                signed: true
+            }
+        ),
+        literal("0")
+    );
+    assert_eq!(
+        parse_column_default(
+            Some("CURRENT_TIMESTAMP"),
+            "DEFAULT_GENERATED",
+            &UnifiedDataType::DateTime {
+                with_timezone: false
+            }
+        ),
+        Some(DefaultValue::CurrentTimestamp)
+    );
+    assert_eq!(
+        parse_column_default(Some("uuid()"), "DEFAULT_GENERATED", &text),
+        Some(DefaultValue::UuidGenerate)
+    );
+    assert_eq!(parse_column_default(None, "", &text), None);
+}
+
+#[test]
+fn test_group_partition_rows_by_table() {
+    use super::schema_collection::{PartitionRow, group_partition_rows};
+
+    let row = |table: &str, name: &str, position: u32, description: &str| PartitionRow {
+        table_name: table.to_string(),
+        partition_name: name.to_string(),
+        subpartition_name: None,
+        ordinal_position: position,
+        method: "RANGE".to_string(),
+        expression: Some("year(`created_at`)".to_string()),
+        subpartition_method: None,
+        subpartition_expression: None,
+        description: Some(description.to_string()),
+        row_count: Some(10),
+    };
+
+    let grouped = group_partition_rows(vec![
+        row("events", "p2023", 1, "2024"),
+        row("events", "p2024", 2, "2025"),
+        row("logs", "p0", 1, "MAXVALUE"),
+    ]);
+
+    assert_eq!(grouped.len(), 2);
+    let (table, events) = &grouped[0];
+    assert_eq!(table, "events");
+    assert_eq!(events.method, "RANGE");
+    assert_eq!(events.expression.as_deref(), Some("year(`created_at`)"));
+    let names: Vec<&str> = events.partitions.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["p2023", "p2024"]);
+    assert_eq!(events.partitions[1].description.as_deref(), Some("2025"));
+    assert_eq!(grouped[1].0, "logs");
+    assert_eq!(grouped[1].1.partitions.len(), 1);
+}
+
+#[test]
+fn test_table_options() {
+    use super::schema_collection::table_options;
+
+    let options = table_options(
+        Some("MyISAM".to_string()),
+        Some("Fixed".to_string()),
+        Some(42),
+    )
+    .expect("options are recorded");
+    assert_eq!(options.engine.as_deref(), Some("MyISAM"));
+    assert_eq!(options.row_format.as_deref(), Some("Fixed"));
+    assert_eq!(options.auto_increment, Some(42));
+    assert_eq!(options.tablespace, None);
+
+    assert_eq!(table_options(Some(String::new()), None, None), None);
+}
+
+// =============================================================================
+// Database Enumeration Tests
+// =============================================================================
+
+#[test]
+fn test_mysql_system_database_detection() {
+    use super::EnumeratedDatabase;
+
+    for name in ["mysql", "sys", "information_schema", "PERFORMANCE_SCHEMA"] {
+        assert!(EnumeratedDatabase::check_is_system_database(name), "{name}");
+    }
+    for name in ["app", "mysql_archive", "system"] {
+        assert!(
+            !EnumeratedDatabase::check_is_system_database(name),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_classify_read_only() {
+    use super::connection::classify_read_only;
+    use crate::models::ReadOnlyLevel;
+
+    let session = classify_read_only(1, Some(0), Some(0));
+    assert_eq!(session.level, ReadOnlyLevel::Session);
+    assert_eq!(
+        session.evidence,
+        [
+            "transaction_read_only = 1",
+            "read_only = 0",
+            "super_read_only = 0"
+        ]
+    );
+
+    assert_eq!(
+        classify_read_only(0, Some(1), Some(1)).level,
+        ReadOnlyLevel::Server
+    );
+    // MariaDB: read_only without super_read_only does not bind SUPER accounts
+    assert_eq!(
+        classify_read_only(0, Some(1), None).level,
+        ReadOnlyLevel::Unverified
+    );
+    assert_eq!(
+        classify_read_only(0, Some(0), None).level,
+        ReadOnlyLevel::NotEnforced
+    );
+}
+
+#[test]
+fn test_pooler_transaction_sql() {
+    use super::connection::pooler_transaction_sql;
+    use crate::adapters::config::MySqlLimits;
+
+    let defaults = MySqlLimits::default();
+    assert_eq!(
+        pooler_transaction_sql(30, true, &defaults),
+        [
+            "SET max_execution_time = 30000",
+            "SET time_zone = '+00:00'",
+            "START TRANSACTION READ ONLY"
+        ]
+    );
+    assert_eq!(
+        pooler_transaction_sql(30, false, &defaults)[2],
+        "START TRANSACTION"
+    );
+
+    let limits = MySqlLimits {
+        max_execution_time_ms: Some(5_000),
+    };
+    assert_eq!(
+        pooler_transaction_sql(30, true, &limits)[0],
+        "SET max_execution_time = 5000"
+    );
+}
+
+#[test]
+fn test_classify_access() {
+    use super::access::classify_access;
+    use crate::models::AccessLevel;
+
+    let table_grants = classify_access("app@%", false, false, false, 2, 5);
+    assert_eq!(table_grants.readable_tables, 2);
+    assert_eq!(table_grants.identity.as_deref(), Some("app@%"));
+    // The account is kept out of free text so it can be anonymized
+    assert_eq!(table_grants.evidence[0], "account lacks SUPER");
+    assert_eq!(table_grants.access_level(), AccessLevel::Limited);
+
+    // A database-wide grant covers every table, but only a global one
+    // reveals objects the account holds no privilege on
+    let database = classify_access("app@%", false, false, true, 0, 5);
+    assert_eq!(database.readable_tables, 5);
+    assert!(!database.catalog_access);
+    assert_eq!(database.access_level(), AccessLevel::Limited);
+
+    let global = classify_access("auditor@%", false, true, false, 0, 5);
+    assert_eq!(global.access_level(), AccessLevel::Full);
+    assert_eq!(global.evidence[1], "SELECT granted on *.*");
+
+    let root = classify_access("root@localhost", true, false, false, 0, 5);
+    assert!(root.superuser);
+    assert_eq!(root.access_level(), AccessLevel::Full);
+}
@@ -87,30 +87,10 @@ pub fn map_mysql_type(
         },
 
         // Decimal/Numeric types
-        // Map to Float with precision (scale information is lost but precision is preserved)
-        "decimal" | "numeric" | "dec" | "fixed" => {
-            if let Some(scale) = numeric_scale {
-                if scale == 0 {
-                    // No decimal places - treat as integer
-                    let bits = match numeric_precision {
-                        Some(p) if p <= 2 => 8,
-                        Some(p) if p <= 4 => 16,
-                        Some(p) if p <= 9 => 32,
-                        _ => 64,
-                    };
-                    UnifiedDataType::Integer { bits, signed: true }
-                } else {
-                    // Has decimal places - treat as float
-                    UnifiedDataType::Float {
-                        precision: numeric_precision,
-                    }
-                }
-            } else {
-                UnifiedDataType::Float {
-                    precision: numeric_precision,
-                }
-            }
-        }
+        "decimal" | "numeric" | "dec" | "fixed" => UnifiedDataType::Decimal {
+            precision: numeric_precision.map(u32::from),
+            scale: numeric_scale.map(u32::from),
+        },
 
         // Floating point types
         "float" => UnifiedDataType::Float {
@@ -157,17 +137,13 @@ pub fn map_mysql_type(
         },
         "longblob" => UnifiedDataType::Binary { max_length: None },
 
-        // BIT type
+        // BIT type; information_schema reports the bit count as NUMERIC_PRECISION
         "bit" => {
-            if char_max_length == Some(1) {
+            let length = char_max_length.or(numeric_precision.map(u32::from));
+            if length == Some(1) {
                 UnifiedDataType::Boolean
             } else {
-                // BIT(n) stores n bits, calculate byte length
-                let bits = char_max_length.unwrap_or(1);
-                let bytes = bits.div_ceil(8);
-                UnifiedDataType::Binary {
-                    max_length: Some(bytes),
-                }
+                UnifiedDataType::Bit { length }
             }
         }
 
@@ -116,6 +116,7 @@ async fn sample_table(
                     sample_status: Some($crate::models::SampleStatus::Skipped {
                         reason: concat!($display_name, " adapter not yet implemented").to_string(),
                     }),
+                    column_types: Vec::new(),
                 })
             }
 
@@ -0,0 +1,102 @@
+//! Probing what the connected role can read in the current database.
+
+use super::row_ext::RowExt;
+use crate::Result;
+use crate::models::AccessProbe;
+use sqlx::PgPool;
+
+/// Catalogs whose access separates a role that sees every object's
+/// definition from one limited to what `information_schema` shows it.
+const PROBED_CATALOGS: &[&str] = &[
+    "pg_attribute",
+    "pg_index",
+    "pg_proc",
+    "pg_trigger",
+    "pg_views",
+];
+
+/// Probes the role's superuser status, catalog access, and the tables and
+/// views it may select from.
+///
+/// Relations in schemas the role lacks `USAGE` on count as unreadable.
+pub(crate) async fn probe_access(pool: &PgPool) -> Result<AccessProbe> {
+    let probe_query = r#"
+        SELECT
+            current_user::text AS role_name,
+            current_setting('is_superuser') = 'on' AS superuser,
+            ARRAY(
+                SELECT catalog
+                FROM unnest($1::text[]) AS catalog
+                WHERE NOT has_table_privilege('pg_catalog.' || catalog, 'SELECT')
+            ) AS unreadable_catalogs,
+            count(c.oid) AS total_tables,
+            count(c.oid) FILTER (
+                WHERE has_schema_privilege(n.oid, 'USAGE')
+                AND has_table_privilege(c.oid, 'SELECT')
+            ) AS readable_tables
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f')
+        AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+        AND n.nspname NOT LIKE 'pg_toast%'
+    "#;
+
+    let row = sqlx::query(probe_query)
+        .bind(PROBED_CATALOGS)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to probe role access", e)
+        })?;
+
+    let role_name: String = row.get_field("role_name", None)?;
+    let superuser: bool = row.get_field("superuser", None)?;
+    let unreadable_catalogs: Vec<String> = row.get_field("unreadable_catalogs", None)?;
+    let total_tables: i64 = row.get_field("total_tables", None)?;
+    let readable_tables: i64 = row.get_field("readable_tables", None)?;
+
+    Ok(classify_access(
+        &role_name,
+        superuser,
+        &unreadable_catalogs,
+        readable_tables,
+        total_tables,
+    ))
+}
+
+/// Builds the probe result from the role's observed privileges.
+pub(crate) fn classify_access(
+    role_name: &str,
+    superuser: bool,
+    unreadable_catalogs: &[String],
+    readable_tables: i64,
+    total_tables: i64,
+) -> AccessProbe {
+    let mut evidence = vec![format!(
+        "role {} a superuser",
+        if superuser { "is" } else { "is not" }
+    )];
+    if !unreadable_catalogs.is_empty() {
+        evidence.push(format!(
+            "SELECT denied on {}",
+            unreadable_catalogs
+                .iter()
+                .map(|c| format!("pg_catalog.{}", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    evidence.push(format!(
+        "SELECT allowed on {} of {} tables and views",
+        readable_tables, total_tables
+    ));
+
+    AccessProbe {
+        superuser,
+        catalog_access: unreadable_catalogs.is_empty(),
+        readable_tables: usize::try_from(readable_tables).unwrap_or_default(),
+        total_tables: usize::try_from(total_tables).unwrap_or_default(),
+        identity: Some(role_name.to_string()),
+        evidence,
+    }
+}
@@ -0,0 +1,335 @@
+//! Collection through `information_schema` alone (ANSI catalog mode).
+//!
+//! Some hosted PostgreSQL services deny access to `pg_catalog`. In ANSI
+//! catalog mode, requested explicitly or chosen when the catalog probe is
+//! denied, tables, columns, keys, and constraints are read from the standard
+//! `information_schema` views, and views, routines, and triggers from the
+//! fallback queries also used when only those catalogs are denied.
+//!
+//! What `information_schema` does not describe (indexes, comments, sizes and
+//! row estimates, column storage, owned sequences, and table inheritance) is
+//! left out, and every object class is recorded as degraded collection.
+//! PostgreSQL also hides keys and constraints in `information_schema` from
+//! roles holding only `SELECT` on a table, so a read-only role may see none.
+
+use super::PostgresAdapter;
+use super::batch_collection::{self, BatchCollectionResult};
+use super::row_ext::RowExt;
+use super::schema_collection::fallback_record;
+use super::{routines, triggers, views};
+use crate::Result;
+use crate::adapters::helpers::{collect_if_selected, qualified_name, resolve_optional_collection};
+use crate::models::*;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Table attributes `information_schema` does not provide.
+const MISSING_TABLE_ATTRIBUTES: &[&str] = &[
+    "indexes",
+    "comments",
+    "row estimates",
+    "sizes",
+    "column storage",
+    "owned sequences",
+    "inheritance",
+];
+
+/// Collects the schema through `information_schema` only.
+///
+/// `denial` is the error that ruled out `pg_catalog` when the mode was
+/// chosen automatically; `None` when ANSI mode was requested.
+pub(crate) async fn collect_schema(
+    adapter: &PostgresAdapter,
+    denial: Option<String>,
+) -> Result<DatabaseSchema> {
+    let start_time = std::time::Instant::now();
+    let mut warnings = Vec::new();
+    tracing::info!("Collecting PostgreSQL schema through information_schema only");
+
+    let mut database_info = collect_database_info(&adapter.pool, denial.is_some()).await?;
+    database_info.schemas =
+        resolve_optional_collection("schemas", adapter.collect_schemas().await, &mut warnings);
+    let tables = collect_tables(&adapter.pool).await?;
+
+    let objects = adapter.objects;
+    let (views_result, functions_result, procedures_result, triggers_result) = tokio::join!(
+        collect_if_selected(
+            objects.views,
+            views::collect_views_from_information_schema(&adapter.pool)
+        ),
+        collect_if_selected(
+            objects.functions,
+            routines::collect_routines_from_information_schema(&adapter.pool, "FUNCTION")
+        ),
+        collect_if_selected(
+            objects.procedures,
+            routines::collect_routines_from_information_schema(&adapter.pool, "PROCEDURE")
+        ),
+        collect_if_selected(
+            objects.triggers,
+            triggers::collect_triggers_from_information_schema(&adapter.pool)
+        ),
+    );
+    let collected_views = resolve_optional_collection("views", views_result, &mut warnings);
+    let functions = resolve_optional_collection("functions", functions_result, &mut warnings);
+    let procedures = resolve_optional_collection("procedures", procedures_result, &mut warnings);
+    let collected_triggers =
+        resolve_optional_collection("triggers", triggers_result, &mut warnings);
+
+    let reason = denial
+        .clone()
+        .unwrap_or_else(|| "ANSI catalog mode selected".to_string());
+    let routine_names = |routines: &[Procedure]| {
+        routines
+            .iter()
+            .map(|routine| qualified_name(routine.schema.as_deref(), &routine.name))
+            .collect::<Vec<_>>()
+    };
+    let degraded: Vec<DegradedCollection> = [
+        (
+            DegradedCollection::new("tables", "information_schema")
+                .with_missing(MISSING_TABLE_ATTRIBUTES),
+            tables
+                .iter()
+                .map(|table| qualified_name(table.schema.as_deref(), &table.name))
+                .collect(),
+        ),
+        (
+            fallback_record("views"),
+            collected_views
+                .iter()
+                .map(|view| qualified_name(view.schema.as_deref(), &view.name))
+                .collect(),
+        ),
+        (fallback_record("functions"), routine_names(&functions)),
+        (fallback_record("procedures"), routine_names(&procedures)),
+        (
+            fallback_record("triggers"),
+            collected_triggers
+                .iter()
+                .map(|trigger| {
+                    qualified_name(
+                        trigger.schema.as_deref(),
+                        &format!("{}.{}", trigger.table_name, trigger.name),
+                    )
+                })
+                .collect(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, objects): &(DegradedCollection, Vec<String>)| !objects.is_empty())
+    .map(|(record, objects)| DegradedCollection {
+        reason: reason.clone(),
+        objects,
+        ..record
+    })
+    .collect();
+
+    let mut schema = DatabaseSchema::new(database_info);
+    schema.tables = tables;
+    schema.views = collected_views;
+    schema.functions = functions;
+    schema.procedures = procedures;
+    schema.triggers = collected_triggers;
+    schema.collection_metadata.warnings = warnings;
+    schema.collection_metadata.collection_duration_ms =
+        u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let mut schema = schema.with_aggregated_indexes_and_constraints();
+    if denial.is_some() {
+        schema = schema.with_degraded_collections(degraded);
+    } else {
+        schema.collection_metadata.degraded.extend(degraded);
+        schema = schema.with_warning(CollectionWarning::new(
+            WarningCategory::Collection,
+            format!(
+                "Collected from information_schema only (ANSI catalog mode); missing {}",
+                MISSING_TABLE_ATTRIBUTES.join(", ")
+            ),
+        ));
+    }
+    objects.apply(&mut schema);
+
+    tracing::info!(
+        "Collected {} tables, {} views, {} functions, {} procedures, {} triggers from information_schema",
+        schema.tables.len(),
+        schema.views.len(),
+        schema.functions.len(),
+        schema.procedures.len(),
+        schema.triggers.len()
+    );
+    Ok(schema)
+}
+
+/// Reads the database name, version, and character set. Size and owner
+/// are only in `pg_database`.
+async fn collect_database_info(pool: &PgPool, catalog_denied: bool) -> Result<DatabaseInfo> {
+    let query = r#"
+        SELECT
+            current_database()::text AS name,
+            version() AS version,
+            (SELECT character_set_name::text FROM information_schema.character_sets LIMIT 1)
+                AS encoding,
+            (SELECT default_collate_name::text FROM information_schema.character_sets LIMIT 1)
+                AS collation
+    "#;
+    let row = sqlx::query(query).fetch_one(pool).await.map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed(
+            "Failed to query database metadata from information_schema",
+            e,
+        )
+    })?;
+
+    let name: String = row.get_field("name", Some("information_schema"))?;
+    let mut info = DatabaseInfo::new(name.clone());
+    info.engine = Some(DatabaseType::PostgreSQL);
+    info.version = row.get_field("version", Some("information_schema"))?;
+    info.encoding = row.get_field("encoding", Some("information_schema"))?;
+    info.collation = row.get_field("collation", Some("information_schema"))?;
+    info.is_system_database = matches!(name.as_str(), "template0" | "template1" | "postgres");
+    if catalog_denied {
+        info.access_level = AccessLevel::Limited;
+    }
+    Ok(info)
+}
+
+/// Collects tables and views with their columns, keys, and constraints.
+async fn collect_tables(pool: &PgPool) -> Result<Vec<Table>> {
+    let tables_query = r#"
+        SELECT table_schema::text, table_name::text
+        FROM information_schema.tables
+        WHERE table_type IN ('BASE TABLE', 'VIEW')
+        AND table_schema NOT IN ('information_schema', 'pg_catalog', 'pg_toast')
+        ORDER BY table_schema, table_name
+    "#;
+    let rows = sqlx::query(tables_query)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("42501") => {
+                crate::error::DbSurveyorError::insufficient_privileges(
+                    "Cannot access information_schema.tables - insufficient privileges",
+                )
+            }
+            _ => crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate database tables",
+                e,
+            ),
+        })?;
+
+    let (columns, primary_keys, foreign_keys, constraints) = tokio::try_join!(
+        collect_columns(pool),
+        batch_collection::batch_collect_primary_keys(pool),
+        collect_foreign_keys(pool),
+        batch_collection::batch_collect_constraints(pool),
+    )?;
+    let mut batch = BatchCollectionResult {
+        columns,
+        primary_keys,
+        foreign_keys,
+        indexes: HashMap::new(),
+        constraints,
+    };
+
+    let mut tables = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let schema: Option<String> = row.get_field("table_schema", Some("tables"))?;
+        let name: String = row.get_field("table_name", Some("tables"))?;
+        tables.push(batch_collection::assemble_table_from_batch(
+            &mut batch, &name, &schema, None, None, None,
+        ));
+    }
+    Ok(tables)
+}
+
+/// Fetches columns from `information_schema.columns`, without the comments,
+/// owned sequences, and storage that the native query joins from
+/// `pg_catalog`.
+async fn collect_columns(pool: &PgPool) -> Result<HashMap<(String, String), Vec<Column>>> {
+    let query = r#"
+        SELECT
+            c.table_schema,
+            c.table_name,
+            c.column_name,
+            c.data_type,
+            c.udt_name,
+            c.character_maximum_length,
+            c.numeric_precision,
+            c.numeric_scale,
+            c.is_nullable,
+            c.column_default,
+            c.ordinal_position,
+            NULL::text as column_comment,
+            c.is_identity,
+            c.identity_generation,
+            NULL::text as owned_sequence,
+            c.collation_name::text as collation_name,
+            NULL::text as storage_strategy,
+            NULL::text as compression_method,
+            CASE
+                WHEN c.data_type = 'ARRAY' THEN
+                    CASE
+                        WHEN c.udt_name LIKE '_%' THEN substring(c.udt_name from 2)
+                        ELSE c.udt_name
+                    END
+                ELSE NULL
+            END as array_element_type,
+            EXISTS (
+                SELECT 1
+                FROM information_schema.table_constraints tc
+                JOIN information_schema.key_column_usage kcu
+                    ON tc.constraint_name = kcu.constraint_name
+                    AND tc.table_schema = kcu.table_schema
+                WHERE tc.constraint_type = 'PRIMARY KEY'
+                AND kcu.table_schema = c.table_schema
+                AND kcu.table_name = c.table_name
+                AND kcu.column_name = c.column_name
+            ) as is_primary_key
+        FROM information_schema.columns c
+        WHERE c.table_schema NOT IN ('information_schema', 'pg_catalog', 'pg_toast')
+        ORDER BY c.table_schema, c.table_name, c.ordinal_position
+    "#;
+
+    let rows = sqlx::query(query).fetch_all(pool).await.map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed(
+            "Column collection from information_schema failed",
+            e,
+        )
+    })?;
+    batch_collection::columns_from_rows(&rows)
+}
+
+/// Fetches foreign keys by pairing each referencing column with the
+/// referenced key column at the same position.
+async fn collect_foreign_keys(pool: &PgPool) -> Result<HashMap<(String, String), Vec<ForeignKey>>> {
+    let query = r#"
+        SELECT
+            kcu.table_schema::text as table_schema,
+            kcu.table_name::text as table_name,
+            rc.constraint_name::text as constraint_name,
+            rc.update_rule::text,
+            rc.delete_rule::text,
+            kcu.column_name::text as column_name,
+            ref.table_schema::text as referenced_table_schema,
+            ref.table_name::text as referenced_table_name,
+            ref.column_name::text as referenced_column_name
+        FROM information_schema.referential_constraints rc
+        JOIN information_schema.key_column_usage kcu
+            ON kcu.constraint_schema = rc.constraint_schema
+            AND kcu.constraint_name = rc.constraint_name
+        JOIN information_schema.key_column_usage ref
+            ON ref.constraint_schema = rc.unique_constraint_schema
+            AND ref.constraint_name = rc.unique_constraint_name
+            AND ref.ordinal_position = kcu.position_in_unique_constraint
+        WHERE kcu.table_schema NOT IN ('information_schema', 'pg_catalog', 'pg_toast')
+        ORDER BY kcu.table_schema, kcu.table_name, rc.constraint_name, kcu.ordinal_position
+    "#;
+
+    let rows = sqlx::query(query).fetch_all(pool).await.map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed(
+            "Foreign key collection from information_schema failed",
+            e,
+        )
+    })?;
+    batch_collection::foreign_keys_from_rows(&rows)
+}
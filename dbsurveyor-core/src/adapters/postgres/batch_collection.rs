@@ -11,6 +11,7 @@
 use super::row_ext::RowExt;
 use super::type_mapping;
 use crate::Result;
+use crate::defaults::classify_default;
 use crate::models::*;
 use sqlx::PgPool;
 use std::collections::HashMap;
@@ -44,6 +45,11 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
             col_description(pgc.oid, c.ordinal_position) as column_comment,
             c.is_identity,
             c.identity_generation,
+            pg_get_serial_sequence(quote_ident(c.table_schema) || '.' || quote_ident(c.table_name), c.column_name) as owned_sequence,
+            c.collation_name::text as collation_name,
+            -- Only variable-length columns can be TOASTed; attcompression is PostgreSQL 14+
+            CASE WHEN pa.attlen = -1 THEN pa.attstorage::text END as storage_strategy,
+            NULLIF(to_jsonb(pa) ->> 'attcompression', '') as compression_method,
             CASE
                 WHEN c.data_type = 'ARRAY' THEN
                     CASE
@@ -57,6 +63,9 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
                 ELSE false
             END as is_primary_key
         FROM information_schema.columns c
+        LEFT JOIN pg_attribute pa
+            ON pa.attrelid = (quote_ident(c.table_schema) || '.' || quote_ident(c.table_name))::regclass
+            AND pa.attname = c.column_name
         LEFT JOIN pg_class pgc ON pgc.relname = c.table_name
         LEFT JOIN pg_namespace pgn
             ON pgn.nspname = c.table_schema AND pgc.relnamespace = pgn.oid
@@ -80,10 +89,16 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
     let rows = sqlx::query(query).fetch_all(pool).await.map_err(|e| {
         crate::error::DbSurveyorError::collection_failed("Batch column collection failed", e)
     })?;
+    columns_from_rows(&rows)
+}
 
+/// Parses rows shaped like the batch column query and groups them by table.
+pub(crate) fn columns_from_rows(
+    rows: &[sqlx::postgres::PgRow],
+) -> Result<HashMap<TableKey, Vec<Column>>> {
     let mut map: HashMap<TableKey, Vec<Column>> = HashMap::new();
 
-    for row in &rows {
+    for row in rows {
         let schema: String = row.get_field("table_schema", Some("batch_columns"))?;
         let table: String = row.get_field("table_name", Some("batch_columns"))?;
         let column_name: String = row.get_field("column_name", Some("batch_columns"))?;
@@ -101,9 +116,18 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
         let column_comment: Option<String> =
             row.get_field("column_comment", Some("batch_columns"))?;
         let is_identity: String = row.get_field("is_identity", Some("batch_columns"))?;
+        let identity_generation: Option<String> =
+            row.get_field("identity_generation", Some("batch_columns"))?;
+        let owned_sequence: Option<String> =
+            row.get_field("owned_sequence", Some("batch_columns"))?;
         let array_element_type: Option<String> =
             row.get_field("array_element_type", Some("batch_columns"))?;
         let is_primary_key: bool = row.get_field("is_primary_key", Some("batch_columns"))?;
+        let collation: Option<String> = row.get_field("collation_name", Some("batch_columns"))?;
+        let storage_strategy: Option<String> =
+            row.get_field("storage_strategy", Some("batch_columns"))?;
+        let compression_method: Option<String> =
+            row.get_field("compression_method", Some("batch_columns"))?;
 
         let unified_data_type = PostgresAdapter::map_postgres_type_to_unified(
             &data_type,
@@ -114,12 +138,18 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
             array_element_type.as_deref(),
         )?;
 
+        let normalized_default = column_default.as_deref().map(classify_default);
         let is_auto_increment = is_identity == "YES"
-            || column_default.as_ref().is_some_and(|default| {
-                default.starts_with("nextval(")
-                    || default.contains("_seq'::regclass)")
-                    || default.contains("::regclass")
-            });
+            || matches!(
+                normalized_default,
+                Some(DefaultValue::SequenceNextval { .. })
+            );
+        let identity = type_mapping::map_column_identity(
+            is_identity == "YES",
+            identity_generation.as_deref(),
+            normalized_default.as_ref(),
+            owned_sequence,
+        );
 
         let col = Column {
             name: column_name,
@@ -128,8 +158,19 @@ pub(crate) async fn batch_collect_columns(pool: &PgPool) -> Result<HashMap<Table
             is_primary_key,
             is_auto_increment,
             default_value: column_default,
+            normalized_default,
             comment: column_comment,
             ordinal_position: u32::try_from(ordinal_position).unwrap_or(0),
+            generated: None,
+            declared_type: None,
+            collation,
+            character_set: None,
+            storage: type_mapping::map_column_storage(
+                storage_strategy.as_deref(),
+                compression_method.as_deref(),
+            ),
+            identity,
+            masking: None,
         };
 
         map.entry((schema, table)).or_default().push(col);
@@ -151,7 +192,7 @@ pub(crate) async fn batch_collect_primary_keys(
             tc.table_schema,
             tc.table_name,
             tc.constraint_name,
-            string_agg(kcu.column_name, ',' ORDER BY kcu.ordinal_position) as columns
+            array_agg(kcu.column_name::text ORDER BY kcu.ordinal_position) as columns
         FROM information_schema.table_constraints tc
         JOIN information_schema.key_column_usage kcu
             ON tc.constraint_name = kcu.constraint_name
@@ -172,8 +213,7 @@ pub(crate) async fn batch_collect_primary_keys(
         let schema: String = row.get_field("table_schema", Some("batch_pks"))?;
         let table: String = row.get_field("table_name", Some("batch_pks"))?;
         let name: Option<String> = row.get_field("constraint_name", Some("batch_pks"))?;
-        let columns_str: String = row.get_field("columns", Some("batch_pks"))?;
-        let columns: Vec<String> = columns_str.split(',').map(|s| s.to_string()).collect();
+        let columns: Vec<String> = row.get_field("columns", Some("batch_pks"))?;
 
         map.insert((schema, table), PrimaryKey { name, columns });
     }
@@ -235,10 +275,17 @@ pub(crate) async fn batch_collect_foreign_keys(
     let rows = sqlx::query(query).fetch_all(pool).await.map_err(|e| {
         crate::error::DbSurveyorError::collection_failed("Batch foreign key collection failed", e)
     })?;
+    foreign_keys_from_rows(&rows)
+}
 
+/// Parses rows shaped like the batch foreign key query, one per key column
+/// in key order, and groups them by table and constraint.
+pub(crate) fn foreign_keys_from_rows(
+    rows: &[sqlx::postgres::PgRow],
+) -> Result<HashMap<TableKey, Vec<ForeignKey>>> {
     // Parse into intermediate structs
     let mut raw_rows = Vec::with_capacity(rows.len());
-    for row in &rows {
+    for row in rows {
         raw_rows.push(FkRawRow {
             schema: row.get_field("table_schema", Some("batch_fks"))?,
             table: row.get_field("table_name", Some("batch_fks"))?,
@@ -310,6 +357,26 @@ pub(crate) async fn batch_collect_foreign_keys(
 // Batch index collection
 // ---------------------------------------------------------------------------
 
+/// Pairs index column names with the per-column `DESC` flags from
+/// `pg_index.indoption`, in key order.
+///
+/// Column names arrive as a `text[]` rather than a delimited string so that
+/// quoted identifiers containing commas, spaces, or quotes survive intact.
+pub(super) fn index_columns(names: Vec<String>, descending: Vec<bool>) -> Vec<IndexColumn> {
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(position, name)| IndexColumn {
+            name,
+            sort_order: Some(if descending.get(position).copied().unwrap_or(false) {
+                SortDirection::Descending
+            } else {
+                SortDirection::Ascending
+            }),
+        })
+        .collect()
+}
+
 /// Fetches indexes for all user tables in one query.
 pub(crate) async fn batch_collect_indexes(pool: &PgPool) -> Result<HashMap<TableKey, Vec<Index>>> {
     let query = r#"
@@ -320,8 +387,9 @@ pub(crate) async fn batch_collect_indexes(pool: &PgPool) -> Result<HashMap<Table
             am.amname::text as index_type,
             ix.indisunique as is_unique,
             ix.indisprimary as is_primary,
-            string_agg(a.attname::text, ',' ORDER BY array_position(ix.indkey, a.attnum)) as columns,
-            pg_get_indexdef(i.oid) as index_definition
+            array_agg(a.attname::text ORDER BY array_position(ix.indkey, a.attnum)) as columns,
+            array_agg((ix.indoption[array_position(ix.indkey, a.attnum) - 1]::int & 1) = 1
+                ORDER BY array_position(ix.indkey, a.attnum)) as descending
         FROM pg_index ix
         JOIN pg_class t ON t.oid = ix.indrelid
         JOIN pg_class i ON i.oid = ix.indexrelid
@@ -346,26 +414,9 @@ pub(crate) async fn batch_collect_indexes(pool: &PgPool) -> Result<HashMap<Table
         let index_type: String = row.get_field("index_type", Some("batch_indexes"))?;
         let is_unique: bool = row.get_field("is_unique", Some("batch_indexes"))?;
         let is_primary: bool = row.get_field("is_primary", Some("batch_indexes"))?;
-        let columns_str: String = row.get_field("columns", Some("batch_indexes"))?;
-        let index_definition: String = row.get_field("index_definition", Some("batch_indexes"))?;
-
-        let columns: Vec<IndexColumn> = columns_str
-            .split(',')
-            .map(|col_name| {
-                let col_name = col_name.trim();
-                let sort_order = if index_definition.contains(&format!("{col_name} DESC"))
-                    || index_definition.contains(&format!("\"{col_name}\" DESC"))
-                {
-                    Some(SortDirection::Descending)
-                } else {
-                    Some(SortDirection::Ascending)
-                };
-                IndexColumn {
-                    name: col_name.to_string(),
-                    sort_order,
-                }
-            })
-            .collect();
+        let column_names: Vec<String> = row.get_field("columns", Some("batch_indexes"))?;
+        let descending: Vec<bool> = row.get_field("descending", Some("batch_indexes"))?;
+        let columns = index_columns(column_names, descending);
 
         let idx = Index {
             name,
@@ -375,6 +426,7 @@ pub(crate) async fn batch_collect_indexes(pool: &PgPool) -> Result<HashMap<Table
             is_unique,
             is_primary,
             index_type: Some(index_type),
+            options: None,
         };
 
         map.entry((schema, table)).or_default().push(idx);
@@ -399,9 +451,10 @@ pub(crate) async fn batch_collect_constraints(
             tc.constraint_type::text,
             cc.check_clause::text,
             COALESCE(
-                string_agg(kcu.column_name::text, ',' ORDER BY kcu.ordinal_position),
-                ''
-            )::text as column_names
+                array_agg(kcu.column_name::text ORDER BY kcu.ordinal_position)
+                    FILTER (WHERE kcu.column_name IS NOT NULL),
+                '{}'
+            )::text[] as column_names
         FROM information_schema.table_constraints tc
         LEFT JOIN information_schema.key_column_usage kcu
             ON tc.constraint_name = kcu.constraint_name
@@ -430,7 +483,7 @@ pub(crate) async fn batch_collect_constraints(
             row.get_field("constraint_type", Some("batch_constraints"))?;
         let check_clause: Option<String> =
             row.get_field("check_clause", Some("batch_constraints"))?;
-        let columns_str: String = row.get_field("column_names", Some("batch_constraints"))?;
+        let columns: Vec<String> = row.get_field("column_names", Some("batch_constraints"))?;
 
         let constraint_type = match constraint_type_str.as_str() {
             "CHECK" => ConstraintType::Check,
@@ -440,12 +493,6 @@ pub(crate) async fn batch_collect_constraints(
             _ => ConstraintType::Check,
         };
 
-        let columns: Vec<String> = if columns_str.is_empty() {
-            Vec::new()
-        } else {
-            columns_str.split(',').map(|s| s.to_string()).collect()
-        };
-
         let constraint = Constraint {
             name,
             table_name: table.clone(),
@@ -522,6 +569,7 @@ pub(crate) fn assemble_table_from_batch(
     schema_name: &Option<String>,
     comment: Option<String>,
     estimated_rows: Option<i64>,
+    size_bytes: Option<i64>,
 ) -> Table {
     let schema = schema_name.as_deref().unwrap_or("public").to_string();
     let key = (schema, table_name.to_string());
@@ -542,6 +590,12 @@ pub(crate) fn assemble_table_from_batch(
         constraints,
         comment,
         row_count: estimated_rows.map(|r| r.max(0) as u64),
+        size_bytes: size_bytes.map(|b| b.max(0) as u64),
+        validator: None,
+        partitioning: None,
+        virtual_table: None,
+        options: None,
+        inheritance: None,
     }
 }
 
@@ -570,12 +624,14 @@ fn assemble_table_defaults_on_empty_batch() {
             &Some("public".to_string()),
             Some("test comment".to_string()),
             Some(42),
+            Some(8192),
         );
 
         assert_eq!(table.name, "nonexistent");
         assert_eq!(table.schema, Some("public".to_string()));
         assert_eq!(table.comment, Some("test comment".to_string()));
         assert_eq!(table.row_count, Some(42));
+        assert_eq!(table.size_bytes, Some(8192));
         assert!(table.columns.is_empty());
         assert!(table.primary_key.is_none());
         assert!(table.foreign_keys.is_empty());
@@ -603,7 +659,7 @@ fn assemble_table_uses_public_schema_default() {
         );
 
         // schema_name is None -> should default to "public" for the lookup key
-        let table = assemble_table_from_batch(&mut batch, "users", &None, None, None);
+        let table = assemble_table_from_batch(&mut batch, "users", &None, None, None, None);
 
         assert!(table.primary_key.is_some());
         assert_eq!(
@@ -622,8 +678,14 @@ fn assemble_table_negative_rows_clamped_to_zero() {
             constraints: HashMap::new(),
         };
 
-        let table =
-            assemble_table_from_batch(&mut batch, "t", &Some("public".to_string()), None, Some(-5));
+        let table = assemble_table_from_batch(
+            &mut batch,
+            "t",
+            &Some("public".to_string()),
+            None,
+            Some(-5),
+            None,
+        );
 
         assert_eq!(table.row_count, Some(0));
     }
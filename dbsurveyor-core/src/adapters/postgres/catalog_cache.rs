@@ -0,0 +1,340 @@
+//! Per-run cache of the catalog lookups made while sampling.
+//!
+//! Sampling a table needs its row estimate, the type of every column, and
+//! enough key information to pick an ordering. Looked up table by table that
+//! is up to five catalog queries per table; the cache instead reads a whole
+//! schema's worth with one query the first time any of its tables is
+//! sampled, and answers the rest from memory.
+//!
+//! The cache lives on the adapter, so it spans one run against one database.
+//! A schema whose catalog cannot be read is remembered as unavailable and its
+//! tables fall back to the per-table queries.
+
+use crate::adapters::config::SamplingConfig;
+use crate::adapters::helpers::TIMESTAMP_COLUMN_NAMES;
+use crate::models::{OrderingStrategy, SampleColumnType, SortDirection};
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Catalog details of every relation in a schema, by relation name.
+#[derive(Debug, Default)]
+pub(crate) struct SchemaCatalog {
+    tables: HashMap<String, CatalogTable>,
+}
+
+/// Catalog details of one table or view.
+#[derive(Debug, Default)]
+pub(crate) struct CatalogTable {
+    /// `pg_class.reltuples`, negative when the table was never analyzed
+    pub(crate) estimated_rows: Option<i64>,
+    /// Columns in `attnum` order
+    columns: Vec<CatalogColumn>,
+}
+
+/// Catalog details of one column.
+#[derive(Debug, Clone, Default)]
+struct CatalogColumn {
+    name: String,
+    /// `format_type()` rendering, e.g. `numeric(10,2)`
+    data_type: String,
+    /// `pg_type.typname`, e.g. `numeric`
+    type_name: String,
+    scale: Option<i32>,
+    /// Position within the primary key (`pg_index.indkey` is zero-based)
+    primary_key_position: Option<i32>,
+    /// Serial (`nextval` default) or identity column
+    is_auto_increment: bool,
+}
+
+/// Lazily loaded [`SchemaCatalog`]s, keyed by schema name.
+#[derive(Debug, Default)]
+pub(crate) struct CatalogCache {
+    /// `None` marks a schema whose catalog could not be read
+    schemas: Mutex<HashMap<String, Option<Arc<SchemaCatalog>>>>,
+}
+
+impl CatalogCache {
+    /// Returns the catalog of `schema`, reading it on first use.
+    ///
+    /// Returns `None` when the catalog query failed, now or earlier in the
+    /// run; callers then query per table.
+    pub(crate) async fn schema(&self, pool: &PgPool, schema: &str) -> Option<Arc<SchemaCatalog>> {
+        if let Some(cached) = self.lock().get(schema) {
+            return cached.clone();
+        }
+
+        // Sampling runs one table at a time, so the lock is not held across
+        // the query; a concurrent caller would at worst read the schema twice
+        let loaded = match load_schema(pool, schema).await {
+            Ok(catalog) => {
+                tracing::debug!(
+                    "Cached catalog for schema '{}' ({} relations)",
+                    schema,
+                    catalog.tables.len()
+                );
+                Some(Arc::new(catalog))
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "Could not read catalog for schema '{}'; sampling will query per table: {}",
+                    schema,
+                    e
+                );
+                None
+            }
+        };
+        self.lock().insert(schema.to_string(), loaded.clone());
+        loaded
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Option<Arc<SchemaCatalog>>>> {
+        // The map is only ever inserted into, so a poisoned lock still holds
+        // consistent data
+        self.schemas
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl SchemaCatalog {
+    pub(crate) fn table(&self, name: &str) -> Option<&CatalogTable> {
+        self.tables.get(name)
+    }
+}
+
+impl CatalogTable {
+    /// Picks the sampling order with the same priority as the per-table
+    /// detection: primary key, a timestamp column by name, then an
+    /// auto-increment column.
+    pub(crate) fn ordering_strategy(&self) -> OrderingStrategy {
+        let mut primary_key: Vec<&CatalogColumn> = self
+            .columns
+            .iter()
+            .filter(|c| c.primary_key_position.is_some())
+            .collect();
+        if !primary_key.is_empty() {
+            primary_key.sort_by_key(|c| c.primary_key_position);
+            return OrderingStrategy::PrimaryKey {
+                columns: primary_key.iter().map(|c| c.name.clone()).collect(),
+            };
+        }
+
+        let timestamps: Vec<&CatalogColumn> = self
+            .columns
+            .iter()
+            .filter(|c| matches!(c.type_name.as_str(), "timestamp" | "timestamptz" | "date"))
+            .collect();
+        let exact = timestamps.iter().find(|c| {
+            let lower = c.name.to_lowercase();
+            TIMESTAMP_COLUMN_NAMES.iter().any(|&name| lower == name)
+        });
+        let partial = || {
+            timestamps.iter().find(|c| {
+                let lower = c.name.to_lowercase();
+                lower.contains("created")
+                    || lower.contains("inserted")
+                    || lower.contains("timestamp")
+            })
+        };
+        if let Some(column) = exact.or_else(partial) {
+            return OrderingStrategy::Timestamp {
+                column: column.name.clone(),
+                direction: SortDirection::Descending,
+            };
+        }
+
+        if let Some(column) = self.columns.iter().find(|c| c.is_auto_increment) {
+            return OrderingStrategy::AutoIncrement {
+                column: column.name.clone(),
+            };
+        }
+
+        OrderingStrategy::Unordered
+    }
+
+    /// Builds the select list and type legend for sampling the table, as
+    /// the per-table projection query would.
+    pub(crate) fn projection(
+        &self,
+        config: &SamplingConfig,
+    ) -> Option<(String, Vec<SampleColumnType>)> {
+        if self.columns.is_empty() {
+            return None;
+        }
+        Some(super::sampling::build_projection(
+            self.columns
+                .iter()
+                .map(|c| super::sampling::ProjectedColumn {
+                    name: c.name.clone(),
+                    data_type: c.data_type.clone(),
+                    type_name: c.type_name.clone(),
+                    scale: c.scale,
+                }),
+            config,
+        ))
+    }
+}
+
+/// Reads the sampling-relevant catalog of every relation in `schema`.
+async fn load_schema(pool: &PgPool, schema: &str) -> Result<SchemaCatalog, sqlx::Error> {
+    let catalog_query = r#"
+        SELECT
+            c.relname::text AS table_name,
+            c.reltuples::bigint AS estimated_rows,
+            a.attname::text AS column_name,
+            format_type(a.atttypid, a.atttypmod) AS data_type,
+            ty.typname::text AS type_name,
+            CASE WHEN ty.typname = 'numeric' AND a.atttypmod >= 4
+                THEN (a.atttypmod - 4) & 65535
+            END AS scale,
+            array_position(pk.indkey::int2[], a.attnum) AS primary_key_position,
+            -- attidentity is PostgreSQL 10+
+            COALESCE(to_jsonb(a) ->> 'attidentity', '') <> ''
+                OR COALESCE(pg_get_expr(d.adbin, d.adrelid) LIKE 'nextval%', false)
+                AS is_auto_increment
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped
+        JOIN pg_type ty ON ty.oid = a.atttypid
+        LEFT JOIN pg_index pk ON pk.indrelid = c.oid AND pk.indisprimary
+        LEFT JOIN pg_attrdef d ON d.adrelid = c.oid AND d.adnum = a.attnum
+        WHERE n.nspname = $1
+        AND c.relkind IN ('r', 'p', 'v', 'm', 'f')
+        ORDER BY c.relname, a.attnum
+    "#;
+
+    let rows = sqlx::query(catalog_query)
+        .bind(schema)
+        .fetch_all(pool)
+        .await?;
+
+    let mut catalog = SchemaCatalog::default();
+    for row in &rows {
+        let table_name: String = row.try_get("table_name")?;
+        let table = catalog.tables.entry(table_name).or_default();
+        table.estimated_rows = row.try_get("estimated_rows")?;
+        table.columns.push(CatalogColumn {
+            name: row.try_get("column_name")?,
+            data_type: row.try_get("data_type").unwrap_or_default(),
+            type_name: row.try_get("type_name").unwrap_or_default(),
+            scale: row.try_get("scale").ok().flatten(),
+            primary_key_position: row.try_get("primary_key_position").ok().flatten(),
+            is_auto_increment: row.try_get("is_auto_increment")?,
+        });
+    }
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::config::{SensitiveAction, SensitivePattern, SensitivePatternRules};
+
+    fn column(name: &str, type_name: &str) -> CatalogColumn {
+        CatalogColumn {
+            name: name.to_string(),
+            data_type: type_name.to_string(),
+            type_name: type_name.to_string(),
+            ..CatalogColumn::default()
+        }
+    }
+
+    fn table(columns: Vec<CatalogColumn>) -> CatalogTable {
+        CatalogTable {
+            estimated_rows: Some(10),
+            columns,
+        }
+    }
+
+    #[test]
+    fn test_ordering_prefers_primary_key_in_key_order() {
+        let mut tenant = column("tenant_id", "int4");
+        tenant.primary_key_position = Some(0);
+        let mut id = column("id", "int8");
+        id.primary_key_position = Some(1);
+        id.is_auto_increment = true;
+
+        let ordering =
+            table(vec![id, column("created_at", "timestamptz"), tenant]).ordering_strategy();
+        assert_eq!(
+            ordering,
+            OrderingStrategy::PrimaryKey {
+                columns: vec!["tenant_id".to_string(), "id".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_ordering_falls_back_through_timestamp_and_auto_increment() {
+        // An exact timestamp name wins over an earlier partial match
+        let ordering = table(vec![
+            column("first_inserted", "timestamp"),
+            column("created_at", "timestamptz"),
+        ])
+        .ordering_strategy();
+        assert_eq!(
+            ordering,
+            OrderingStrategy::Timestamp {
+                column: "created_at".to_string(),
+                direction: SortDirection::Descending
+            }
+        );
+
+        // Timestamp names only count on timestamp types
+        let mut seq = column("seq", "int8");
+        seq.is_auto_increment = true;
+        let ordering = table(vec![column("created_at", "text"), seq]).ordering_strategy();
+        assert_eq!(
+            ordering,
+            OrderingStrategy::AutoIncrement {
+                column: "seq".to_string()
+            }
+        );
+
+        assert_eq!(
+            table(vec![column("name", "text")]).ordering_strategy(),
+            OrderingStrategy::Unordered
+        );
+    }
+
+    #[test]
+    fn test_projection_matches_column_types() {
+        let mut price = column("price", "numeric");
+        price.data_type = "numeric(10,2)".to_string();
+        price.scale = Some(2);
+
+        let config = SamplingConfig::new();
+        let (select_list, legend) = table(vec![column("id", "int4"), price])
+            .projection(&config)
+            .expect("table has columns");
+        assert_eq!(select_list, "t.\"id\", t.\"price\"::text AS \"price\"");
+        assert_eq!(legend.len(), 2);
+        assert_eq!(legend[1].data_type, "numeric(10,2)");
+
+        assert!(table(Vec::new()).projection(&config).is_none());
+    }
+
+    #[test]
+    fn test_projection_leaves_out_skipped_columns() {
+        let config = SamplingConfig::new().with_sensitive_pattern_rules(SensitivePatternRules {
+            replace_defaults: false,
+            patterns: vec![
+                SensitivePattern::new("(?i)api_key", "API key")
+                    .with_action(SensitiveAction::SkipColumn),
+            ],
+        });
+
+        let (select_list, legend) = table(vec![column("id", "int4"), column("api_key", "text")])
+            .projection(&config)
+            .expect("table has columns");
+        assert_eq!(select_list, "t.\"id\"");
+        assert_eq!(legend.len(), 1);
+
+        let (select_list, legend) = table(vec![column("api_key", "text")])
+            .projection(&config)
+            .expect("table has columns");
+        assert!(select_list.is_empty());
+        assert!(legend.is_empty());
+    }
+}
@@ -6,8 +6,10 @@
 //! - Sets appropriate timeouts for all operations
 //! - Configures SSL/TLS settings for secure connections
 
-use super::{ConnectionConfig, PostgresAdapter};
+use super::{ConnectionConfig, ObjectSelection, PoolStats, PostgresAdapter};
 use crate::Result;
+use crate::adapters::config::PostgresLimits;
+use crate::models::{ReadOnlyLevel, ReadOnlyVerification};
 use sqlx::PgPool;
 use sqlx::pool::PoolConnection;
 use sqlx::postgres::Postgres;
@@ -15,24 +17,13 @@
 use url::Url;
 use zeroize::Zeroizing;
 
-/// Pool statistics for monitoring connection pool health and usage
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PoolStats {
-    /// Number of idle connections ready to be used
-    pub idle_connections: u32,
-    /// Number of connections currently in use
-    pub active_connections: u32,
-    /// Total number of connections in the pool
-    pub total_connections: u32,
-    /// Maximum allowed connections (from configuration)
-    pub max_connections: u32,
-}
-
 impl PostgresAdapter {
     /// Creates a new PostgreSQL adapter with connection pooling
     ///
     /// # Arguments
-    /// * `connection_string` - PostgreSQL connection URL (credentials sanitized in errors)
+    /// * `connection_string` - PostgreSQL connection URL (credentials sanitized in errors).
+    ///   A URL listing several hosts connects to the first reachable standby,
+    ///   or the first reachable primary if no standby answers.
     ///
     /// # Security
     /// - Enforces read-only mode by default
@@ -47,6 +38,11 @@ impl PostgresAdapter {
     /// - Pool configuration is invalid
     /// - Security validation fails
     pub async fn new(connection_string: &str) -> Result<Self> {
+        let hosts = super::replica::split_hosts(connection_string);
+        if hosts.len() > 1 {
+            return Self::connect_preferring_replica(&hosts, None).await;
+        }
+
         // Parse and validate connection configuration
         let config = Self::parse_connection_config(connection_string)?;
 
@@ -56,7 +52,9 @@ pub async fn new(connection_string: &str) -> Result<Self> {
         let adapter = Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
+            catalog: Default::default(),
         };
         Ok(adapter)
     }
@@ -73,6 +71,11 @@ pub async fn with_config(connection_string: &str, config: ConnectionConfig) -> R
         // Validate the provided configuration
         config.validate()?;
 
+        let hosts = super::replica::split_hosts(connection_string);
+        if hosts.len() > 1 {
+            return Self::connect_preferring_replica(&hosts, Some(&config)).await;
+        }
+
         // Validate connection string
         Self::validate_connection_string(connection_string)?;
 
@@ -82,11 +85,26 @@ pub async fn with_config(connection_string: &str, config: ConnectionConfig) -> R
         let adapter = Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_url: Zeroizing::new(connection_string.to_string()),
+            catalog: Default::default(),
         };
         Ok(adapter)
     }
 
+    /// Creates an adapter for a server reached through a transaction-pooling
+    /// proxy such as PgBouncer, applying settings per transaction rather than
+    /// per session.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`PostgresAdapter::new`].
+    pub async fn behind_pooler(connection_string: &str) -> Result<Self> {
+        let hosts = super::replica::split_hosts(connection_string);
+        let first_host = hosts.first().map_or(connection_string, String::as_str);
+        let config = Self::parse_connection_config(first_host)?.with_pooler_mode(true);
+        Self::with_config(connection_string, config).await
+    }
+
     /// Gets connection pool statistics for monitoring
     ///
     /// # Returns
@@ -141,6 +159,35 @@ pub async fn is_pool_healthy(&self) -> bool {
         }
     }
 
+    /// The server's configured time zone, before the session override to UTC.
+    ///
+    /// Returns `None` if the setting cannot be read.
+    pub async fn server_timezone(&self) -> Option<String> {
+        // Sessions run with `timezone = 'UTC'`; `reset_val` is the value the
+        // session would have had from server, database, or role configuration
+        sqlx::query_scalar("SELECT reset_val FROM pg_settings WHERE name = 'TimeZone'")
+            .fetch_optional(&self.pool)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// Reads whether pooled sessions run read-only and whether the server is
+    /// a standby.
+    ///
+    /// # Errors
+    /// Returns error if the settings cannot be queried.
+    pub async fn read_only_verification(&self) -> Result<ReadOnlyVerification> {
+        let (transaction, default, in_recovery): (String, String, bool) = sqlx::query_as(
+            "SELECT current_setting('transaction_read_only'), \
+             current_setting('default_transaction_read_only'), pg_is_in_recovery()",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbSurveyorError::connection_failed)?;
+        Ok(classify_read_only(&transaction, &default, in_recovery))
+    }
+
     /// Acquire a connection from the pool
     ///
     /// Returns a pooled connection that will be returned to the pool on drop.
@@ -337,8 +384,16 @@ pub(crate) async fn create_connection_pool(
         // Validate connection string format before creating pool
         Self::validate_connection_string(connection_string)?;
 
+        if config.pooler_mode {
+            return Self::create_pooler_connection_pool(connection_string, config);
+        }
+
         // Clone config values needed for the after_connect closure
-        let query_timeout_secs = config.query_timeout.as_secs();
+        let statement_timeout = statement_timeout(
+            &config.session_limits.postgres,
+            config.query_timeout.as_secs(),
+        );
+        let work_mem = config.session_limits.postgres.work_mem.clone();
         let read_only = config.read_only;
 
         let pool = sqlx::postgres::PgPoolOptions::new()
@@ -353,12 +408,18 @@ pub(crate) async fn create_connection_pool(
             .test_before_acquire(true) // Validate connections before use
             // Apply session security settings to EVERY new connection
             .after_connect(move |conn, _meta| {
+                let statement_timeout = statement_timeout.clone();
+                let work_mem = work_mem.clone();
                 Box::pin(async move {
                     // Set query timeout to prevent resource exhaustion
-                    conn.execute(
-                        format!("SET statement_timeout = '{}s'", query_timeout_secs).as_str(),
-                    )
-                    .await?;
+                    conn.execute(format!("SET statement_timeout = {}", statement_timeout).as_str())
+                        .await?;
+
+                    // Cap memory per sort or hash when configured
+                    if let Some(work_mem) = &work_mem {
+                        conn.execute(format!("SET work_mem = '{}'", work_mem).as_str())
+                            .await?;
+                    }
 
                     // Set lock timeout to prevent blocking operations
                     conn.execute("SET lock_timeout = '30s'").await?;
@@ -399,6 +460,78 @@ pub(crate) async fn create_connection_pool(
         Ok(pool)
     }
 
+    /// Creates a connection pool for use behind a transaction-pooling proxy
+    /// such as PgBouncer.
+    ///
+    /// A transaction pooler hands each transaction to whichever server
+    /// connection is free, so session `SET`s would be lost or leak to other
+    /// clients. Instead every checkout opens a transaction and applies the
+    /// settings with `SET LOCAL`; release deallocates prepared statements and
+    /// rolls back, leaving the server connection clean for the next client.
+    /// The `extra_float_digits` startup parameter, which PgBouncer rejects by
+    /// default, is not sent.
+    fn create_pooler_connection_pool(
+        connection_string: &str,
+        config: &ConnectionConfig,
+    ) -> Result<PgPool> {
+        use sqlx::Executor;
+
+        let app_name = format!("dbsurveyor-collect-{}", env!("CARGO_PKG_VERSION"));
+        let options = connection_string
+            .parse::<sqlx::postgres::PgConnectOptions>()
+            .map_err(|e| {
+                crate::error::DbSurveyorError::configuration(format!(
+                    "Invalid PostgreSQL connection string {}: {}",
+                    crate::error::redact_database_url(connection_string),
+                    e
+                ))
+            })?
+            .application_name(&app_name)
+            .extra_float_digits(None)
+            .statement_cache_capacity(0);
+
+        let begin = pooler_transaction_sql(
+            config.query_timeout.as_secs(),
+            config.read_only,
+            &config.session_limits.postgres,
+        );
+        let begin_on_connect = begin.clone();
+
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(config.max_connections.min(100))
+            // Idle connections must not hold a server connection open
+            .min_connections(0)
+            .acquire_timeout(config.connect_timeout)
+            .idle_timeout(config.idle_timeout)
+            .max_lifetime(config.max_lifetime)
+            .test_before_acquire(true)
+            // New connections go straight to the caller; reused ones pass
+            // through `before_acquire`
+            .after_connect(move |conn, _meta| {
+                let begin = begin_on_connect.clone();
+                Box::pin(async move {
+                    conn.execute(begin.as_str()).await?;
+                    Ok(())
+                })
+            })
+            .before_acquire(move |conn, _meta| {
+                let begin = begin.clone();
+                Box::pin(async move {
+                    conn.execute(begin.as_str()).await?;
+                    Ok(true)
+                })
+            })
+            .after_release(|conn, _meta| {
+                Box::pin(async move {
+                    conn.execute("DEALLOCATE ALL; ROLLBACK").await?;
+                    Ok(true)
+                })
+            })
+            .connect_lazy_with(options);
+
+        Ok(pool)
+    }
+
     /// Validates connection string format and security requirements
     ///
     /// # Security Checks
@@ -503,27 +636,84 @@ pub(crate) async fn validate_schema_privileges(&self) -> Result<()> {
             );
         }
 
-        // Check access to pg_catalog (required for comments and additional metadata)
-        let pg_catalog_access: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM pg_catalog.pg_class LIMIT 1")
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| {
-                    tracing::warn!("Limited pg_catalog access: {}", e);
-                    // Don't fail on pg_catalog access issues - it just limits metadata collection
-                    crate::error::DbSurveyorError::insufficient_privileges(format!(
-                        "Cannot access pg_catalog.pg_class: {}",
-                        e
-                    ))
-                })?;
-
-        if pg_catalog_access == 0 {
-            tracing::warn!(
-                "pg_catalog.pg_class returned 0 rows - metadata collection may be limited"
-            );
-        }
-
         tracing::info!("Schema collection privileges validated successfully");
         Ok(())
     }
+
+    /// Checks that the catalogs native collection reads can be queried.
+    ///
+    /// Privileges are checked when the statement starts, so the `LIMIT 0`
+    /// query fails on a denied catalog without reading any rows. A denial
+    /// is returned as `InsufficientPrivileges` carrying the server's message.
+    pub(crate) async fn probe_catalog_access(&self) -> Result<()> {
+        sqlx::query(
+            "SELECT NULL FROM pg_catalog.pg_class, pg_catalog.pg_namespace, \
+             pg_catalog.pg_attribute, pg_catalog.pg_constraint, pg_catalog.pg_index LIMIT 0",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("42501") => {
+                crate::error::DbSurveyorError::insufficient_privileges(db_err.message())
+            }
+            _ => crate::error::DbSurveyorError::collection_failed(
+                "Failed to check pg_catalog access",
+                e,
+            ),
+        })?;
+        Ok(())
+    }
+}
+
+/// Statements that open a checkout's transaction in pooler mode.
+pub(crate) fn pooler_transaction_sql(
+    query_timeout_secs: u64,
+    read_only: bool,
+    limits: &PostgresLimits,
+) -> String {
+    let mut sql = format!(
+        "BEGIN{}; SET LOCAL statement_timeout = {}; SET LOCAL lock_timeout = '30s'",
+        if read_only { " READ ONLY" } else { "" },
+        statement_timeout(limits, query_timeout_secs)
+    );
+    if let Some(work_mem) = &limits.work_mem {
+        sql.push_str(&format!("; SET LOCAL work_mem = '{}'", work_mem));
+    }
+    sql
+}
+
+/// Value for `statement_timeout`: the configured limit in milliseconds, or
+/// else the query timeout.
+fn statement_timeout(limits: &PostgresLimits, query_timeout_secs: u64) -> String {
+    limits
+        .statement_timeout_ms
+        .map_or_else(|| format!("'{}s'", query_timeout_secs), |ms| ms.to_string())
+}
+
+/// Classifies PostgreSQL read-only settings.
+///
+/// A standby rejects all writes. Otherwise the session is read-only when
+/// its transactions are, which the pool arranges through
+/// `default_transaction_read_only`.
+pub(crate) fn classify_read_only(
+    transaction_read_only: &str,
+    default_transaction_read_only: &str,
+    in_recovery: bool,
+) -> ReadOnlyVerification {
+    let mut evidence = vec![
+        format!("transaction_read_only = {}", transaction_read_only),
+        format!(
+            "default_transaction_read_only = {}",
+            default_transaction_read_only
+        ),
+    ];
+    let level = if in_recovery {
+        evidence.insert(0, "pg_is_in_recovery() = true".to_string());
+        ReadOnlyLevel::Server
+    } else if transaction_read_only == "on" {
+        ReadOnlyLevel::Session
+    } else {
+        ReadOnlyLevel::NotEnforced
+    };
+    ReadOnlyVerification::new(level, evidence)
 }
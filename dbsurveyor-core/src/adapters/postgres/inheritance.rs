@@ -0,0 +1,247 @@
+//! PostgreSQL table inheritance collection.
+//!
+//! `pg_inherits` links a child table to each of its parents, whether it
+//! was declared with `INHERITS (...)` or attached as a declarative
+//! partition. Without these links, children look like unrelated tables that
+//! happen to repeat their parents' columns, so the hierarchy is recorded on
+//! every participating table as a [`TableInheritance`].
+
+use super::RowExt;
+use crate::Result;
+use crate::models::{RelatedTable, Table, TableInheritance};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// One parent-child link from `pg_inherits`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InheritanceLink {
+    pub child: RelatedTable,
+    pub parent: RelatedTable,
+    /// True when the child is a declarative partition of the parent
+    pub is_partition: bool,
+}
+
+/// Inheritance links and inherited columns of every table in the database.
+#[derive(Debug, Default)]
+pub(crate) struct InheritanceCatalog {
+    /// Links ordered by child, then by position in the child's parent list
+    pub links: Vec<InheritanceLink>,
+    /// Inherited (non-local) columns per table, in column order
+    pub inherited_columns: HashMap<RelatedTable, Vec<String>>,
+}
+
+/// Collects table inheritance links and inherited columns.
+///
+/// Only relations that can hold rows (ordinary, partitioned, and foreign
+/// tables) are included; partitioned index inheritance is not.
+pub(crate) async fn collect_inheritance(pool: &PgPool) -> Result<InheritanceCatalog> {
+    tracing::debug!("Starting table inheritance collection for PostgreSQL database");
+
+    let links_query = r#"
+        SELECT
+            cn.nspname::text as child_schema,
+            c.relname::text as child_name,
+            pn.nspname::text as parent_schema,
+            p.relname::text as parent_name,
+            -- relispartition is PostgreSQL 10+
+            COALESCE((to_jsonb(c) ->> 'relispartition')::boolean, false) as is_partition
+        FROM pg_inherits i
+        JOIN pg_class c ON c.oid = i.inhrelid
+        JOIN pg_namespace cn ON cn.oid = c.relnamespace
+        JOIN pg_class p ON p.oid = i.inhparent
+        JOIN pg_namespace pn ON pn.oid = p.relnamespace
+        WHERE c.relkind IN ('r', 'p', 'f')
+        AND cn.nspname NOT IN ('pg_catalog', 'information_schema', 'pg_toast')
+        ORDER BY cn.nspname, c.relname, i.inhseqno
+    "#;
+
+    let link_rows = sqlx::query(links_query)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate table inheritance",
+                e,
+            )
+        })?;
+
+    let mut catalog = InheritanceCatalog::default();
+    for row in &link_rows {
+        catalog.links.push(InheritanceLink {
+            child: RelatedTable {
+                schema: row.get_field("child_schema", Some("pg_inherits"))?,
+                name: row.get_field("child_name", Some("pg_inherits"))?,
+            },
+            parent: RelatedTable {
+                schema: row.get_field("parent_schema", Some("pg_inherits"))?,
+                name: row.get_field("parent_name", Some("pg_inherits"))?,
+            },
+            is_partition: row.get_field("is_partition", Some("pg_inherits"))?,
+        });
+    }
+
+    if catalog.links.is_empty() {
+        return Ok(catalog);
+    }
+
+    let columns_query = r#"
+        SELECT
+            n.nspname::text as table_schema,
+            c.relname::text as table_name,
+            a.attname::text as column_name
+        FROM pg_attribute a
+        JOIN pg_class c ON c.oid = a.attrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE a.attnum > 0
+        AND NOT a.attisdropped
+        AND NOT a.attislocal
+        AND c.relkind IN ('r', 'p', 'f')
+        AND n.nspname NOT IN ('pg_catalog', 'information_schema', 'pg_toast')
+        ORDER BY n.nspname, c.relname, a.attnum
+    "#;
+
+    let column_rows = sqlx::query(columns_query)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate inherited columns",
+                e,
+            )
+        })?;
+
+    for row in &column_rows {
+        let table = RelatedTable {
+            schema: row.get_field("table_schema", Some("pg_attribute"))?,
+            name: row.get_field("table_name", Some("pg_attribute"))?,
+        };
+        let column: String = row.get_field("column_name", Some("pg_attribute"))?;
+        catalog
+            .inherited_columns
+            .entry(table)
+            .or_default()
+            .push(column);
+    }
+
+    tracing::info!("Collected {} table inheritance links", catalog.links.len());
+    Ok(catalog)
+}
+
+/// Records the inheritance hierarchy on each table that has parents or children.
+pub(crate) fn apply_inheritance(tables: &mut [Table], catalog: &InheritanceCatalog) {
+    let mut hierarchy: HashMap<RelatedTable, TableInheritance> = HashMap::new();
+    for link in &catalog.links {
+        let child = hierarchy.entry(link.child.clone()).or_default();
+        child.parents.push(link.parent.clone());
+        child.is_partition |= link.is_partition;
+        hierarchy
+            .entry(link.parent.clone())
+            .or_default()
+            .children
+            .push(link.child.clone());
+    }
+
+    for table in tables {
+        let key = RelatedTable {
+            schema: table.schema.clone(),
+            name: table.name.clone(),
+        };
+        if let Some(mut inheritance) = hierarchy.remove(&key) {
+            inheritance.children.sort();
+            inheritance.inherited_columns = catalog
+                .inherited_columns
+                .get(&key)
+                .cloned()
+                .unwrap_or_default();
+            table.inheritance = Some(inheritance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, UnifiedDataType};
+
+    fn related(name: &str) -> RelatedTable {
+        RelatedTable {
+            schema: Some("public".to_string()),
+            name: name.to_string(),
+        }
+    }
+
+    fn link(child: &str, parent: &str, is_partition: bool) -> InheritanceLink {
+        InheritanceLink {
+            child: related(child),
+            parent: related(parent),
+            is_partition,
+        }
+    }
+
+    fn table(name: &str) -> Table {
+        Table::new(
+            name.to_string(),
+            Some("public".to_string()),
+            vec![Column {
+                is_nullable: false,
+                ..Column::new(
+                    "id".to_string(),
+                    UnifiedDataType::Integer {
+                        bits: 32,
+                        signed: true,
+                    },
+                )
+            }],
+        )
+    }
+
+    #[test]
+    fn test_apply_inheritance_builds_hierarchy() {
+        let catalog = InheritanceCatalog {
+            links: vec![
+                link("capitals", "cities", false),
+                link("capitals", "landmarks", false),
+                link("measurements_2024", "measurements", true),
+                link("measurements_2023", "measurements", true),
+            ],
+            inherited_columns: HashMap::from([(
+                related("capitals"),
+                vec!["name".to_string(), "population".to_string()],
+            )]),
+        };
+        let mut tables = vec![
+            table("capitals"),
+            table("cities"),
+            table("measurements"),
+            table("measurements_2023"),
+            table("orders"),
+        ];
+
+        apply_inheritance(&mut tables, &catalog);
+
+        let capitals = tables[0].inheritance.as_ref().expect("capitals inherits");
+        assert_eq!(
+            capitals.parents,
+            vec![related("cities"), related("landmarks")]
+        );
+        assert!(capitals.children.is_empty());
+        assert!(!capitals.is_partition);
+        assert_eq!(capitals.inherited_columns, vec!["name", "population"]);
+
+        let cities = tables[1].inheritance.as_ref().expect("cities has children");
+        assert_eq!(cities.children, vec![related("capitals")]);
+        assert!(cities.parents.is_empty());
+
+        let measurements = tables[2].inheritance.as_ref().expect("partitioned");
+        assert_eq!(
+            measurements.children,
+            vec![related("measurements_2023"), related("measurements_2024")]
+        );
+
+        let partition = tables[3].inheritance.as_ref().expect("partition");
+        assert!(partition.is_partition);
+        assert_eq!(partition.parents, vec![related("measurements")]);
+
+        assert!(tables[4].inheritance.is_none());
+    }
+}
@@ -0,0 +1,231 @@
+//! PostgreSQL Anonymizer masking rule collection.
+//!
+//! The `anon` extension declares masking rules as security labels
+//! (`SECURITY LABEL FOR anon ON COLUMN ... IS 'MASKED WITH FUNCTION ...'`).
+//! Roles labelled `MASKED` read the rule's output instead of the stored
+//! value, so a sample taken by such a role shows masked data. Each masked
+//! column records its rule as a [`ColumnMasking`], and the run is flagged
+//! when the collecting role is itself masked.
+
+use super::RowExt;
+use crate::Result;
+use crate::adapters::helpers::qualified_name;
+use crate::models::{CollectionWarning, ColumnMasking, Table, WarningCategory, WarningSeverity};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Security label provider registered by PostgreSQL Anonymizer.
+const ANON_PROVIDER: &str = "anon";
+
+/// Masking rules known to the database.
+#[derive(Debug, Default)]
+pub(crate) struct MaskingCatalog {
+    /// Installed PostgreSQL Anonymizer version; `None` when not installed
+    pub extension_version: Option<String>,
+    /// Rule per (schema, table, column)
+    pub rules: HashMap<(String, String, String), String>,
+    /// True when the collecting role is labelled `MASKED`
+    pub role_masked: bool,
+}
+
+/// Collects PostgreSQL Anonymizer masking rules. Returns an empty catalog
+/// when the extension is not installed.
+pub(crate) async fn collect_masking(pool: &PgPool) -> Result<MaskingCatalog> {
+    let extension_version: Option<String> =
+        sqlx::query_scalar("SELECT extversion::text FROM pg_extension WHERE extname = 'anon'")
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed(
+                    "Failed to check for the anon extension",
+                    e,
+                )
+            })?;
+    if extension_version.is_none() {
+        return Ok(MaskingCatalog::default());
+    }
+
+    let rules_query = r#"
+        SELECT
+            n.nspname::text as table_schema,
+            c.relname::text as table_name,
+            a.attname::text as column_name,
+            s.label
+        FROM pg_seclabel s
+        JOIN pg_class c ON s.classoid = 'pg_class'::regclass AND c.oid = s.objoid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = s.objsubid
+        WHERE s.provider = $1
+        AND s.objsubid > 0
+        AND s.label ILIKE 'MASKED WITH %'
+        ORDER BY n.nspname, c.relname, a.attnum
+    "#;
+    let rows = sqlx::query(rules_query)
+        .bind(ANON_PROVIDER)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to collect masking rules", e)
+        })?;
+
+    let mut rules = HashMap::with_capacity(rows.len());
+    for row in &rows {
+        let key = (
+            row.get_field("table_schema", Some("pg_seclabel"))?,
+            row.get_field("table_name", Some("pg_seclabel"))?,
+            row.get_field("column_name", Some("pg_seclabel"))?,
+        );
+        rules.insert(key, row.get_field("label", Some("pg_seclabel"))?);
+    }
+
+    let role_masked: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM pg_shseclabel s
+            JOIN pg_roles r ON r.oid = s.objoid
+            WHERE s.classoid = 'pg_authid'::regclass
+            AND s.provider = $1
+            AND r.rolname = current_user
+            AND upper(s.label) = 'MASKED'
+        )
+        "#,
+    )
+    .bind(ANON_PROVIDER)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        crate::error::DbSurveyorError::collection_failed("Failed to check for a masked role", e)
+    })?;
+
+    tracing::info!("Collected {} masking rules", rules.len());
+    Ok(MaskingCatalog {
+        extension_version,
+        rules,
+        role_masked,
+    })
+}
+
+/// Records masking rules on their columns and returns warnings describing
+/// the extension and whether sampled values of those columns are masked.
+pub(crate) fn apply_masking(
+    tables: &mut [Table],
+    catalog: &MaskingCatalog,
+) -> Vec<CollectionWarning> {
+    let Some(version) = &catalog.extension_version else {
+        return Vec::new();
+    };
+
+    let mut masked = Vec::new();
+    for table in tables.iter_mut() {
+        let schema = table.schema.clone().unwrap_or_else(|| "public".to_string());
+        for column in &mut table.columns {
+            let key = (schema.clone(), table.name.clone(), column.name.clone());
+            if let Some(rule) = catalog.rules.get(&key) {
+                column.masking = Some(ColumnMasking {
+                    provider: ANON_PROVIDER.to_string(),
+                    rule: rule.clone(),
+                });
+                masked.push(format!(
+                    "{}.{}",
+                    qualified_name(table.schema.as_deref(), &table.name),
+                    column.name
+                ));
+            }
+        }
+    }
+
+    let mut summary = CollectionWarning::new(
+        WarningCategory::Sampling,
+        format!(
+            "PostgreSQL Anonymizer {} is installed; {} columns have masking rules",
+            version,
+            masked.len()
+        ),
+    )
+    .with_severity(WarningSeverity::Info);
+    summary.objects.clone_from(&masked);
+
+    let mut warnings = vec![summary];
+    if catalog.role_masked && !masked.is_empty() {
+        let mut warning = CollectionWarning::new(
+            WarningCategory::Sampling,
+            "The collecting role is masked by PostgreSQL Anonymizer; sampled values of \
+             masked columns are the masking rule's output, not the stored data",
+        );
+        warning.objects = masked;
+        warnings.push(warning);
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, UnifiedDataType};
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        let mut table = Table::new(name.to_string(), Some("public".to_string()), Vec::new());
+        for (position, column) in columns.iter().enumerate() {
+            table.columns.push(Column {
+                ordinal_position: position as u32 + 1,
+                ..Column::new(
+                    column.to_string(),
+                    UnifiedDataType::String { max_length: None },
+                )
+            });
+        }
+        table
+    }
+
+    fn catalog(role_masked: bool) -> MaskingCatalog {
+        MaskingCatalog {
+            extension_version: Some("1.3.2".to_string()),
+            rules: HashMap::from([(
+                (
+                    "public".to_string(),
+                    "customers".to_string(),
+                    "email".to_string(),
+                ),
+                "MASKED WITH FUNCTION anon.fake_email()".to_string(),
+            )]),
+            role_masked,
+        }
+    }
+
+    #[test]
+    fn test_apply_masking_records_rules() {
+        let mut tables = vec![
+            table("customers", &["id", "email"]),
+            table("orders", &["email"]),
+        ];
+
+        let warnings = apply_masking(&mut tables, &catalog(false));
+
+        let masking = tables[0].columns[1].masking.as_ref().expect("email masked");
+        assert_eq!(masking.provider, "anon");
+        assert_eq!(masking.rule, "MASKED WITH FUNCTION anon.fake_email()");
+        assert!(tables[0].columns[0].masking.is_none());
+        assert!(tables[1].columns[0].masking.is_none());
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, WarningSeverity::Info);
+        assert_eq!(
+            warnings[0].message,
+            "PostgreSQL Anonymizer 1.3.2 is installed; 1 columns have masking rules"
+        );
+        assert_eq!(warnings[0].objects, vec!["public.customers.email"]);
+    }
+
+    #[test]
+    fn test_masked_role_flags_samples() {
+        let mut tables = vec![table("customers", &["id", "email"])];
+        let warnings = apply_masking(&mut tables, &catalog(true));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1].severity, WarningSeverity::Warning);
+        assert_eq!(warnings[1].objects, vec!["public.customers.email"]);
+
+        let mut tables = vec![table("customers", &["id", "email"])];
+        assert!(apply_masking(&mut tables, &MaskingCatalog::default()).is_empty());
+    }
+}
@@ -1,15 +1,20 @@
 //! PostgreSQL database adapter with connection pooling and comprehensive schema collection.
 //!
 //! # Module Structure
+//! - `access`: Probing the connected role's privileges
 //! - `connection`: Connection pool management and validation
+//! - `catalog_cache`: Per-run cache of the catalog lookups made while sampling
 //! - `type_mapping`: PostgreSQL to unified data type conversion
 //! - `schema_collection`: Table, column, constraint, and index collection
+//! - `ansi`: Collection through `information_schema` alone (ANSI catalog mode)
 //! - `views`: Database view collection
 //! - `routines`: Stored procedures and functions collection
 //! - `triggers`: Database trigger collection
+//! - `masking`: PostgreSQL Anonymizer masking rule collection
 //! - `sampling`: Data sampling utilities and ordering strategy detection
 //! - `enumeration`: Database enumeration for multi-database collection
 //! - `multi_database`: Multi-database collection orchestration
+//! - `replica`: Server role detection and standby preference for multi-host URLs
 //!
 //! # Security Guarantees
 //! - All operations are read-only (SELECT/DESCRIBE only)
@@ -17,10 +22,16 @@
 //! - Query timeouts prevent resource exhaustion
 //! - Connection pooling with configurable limits
 
+mod access;
+mod ansi;
 mod batch_collection;
+mod catalog_cache;
 mod connection;
 mod enumeration;
+mod inheritance;
+mod masking;
 mod multi_database;
+mod replica;
 mod routines;
 mod row_ext;
 mod sampling;
@@ -32,22 +43,24 @@
 #[cfg(test)]
 mod tests;
 
-use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, TableRef};
+use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, ObjectSelection, TableRef};
 use crate::{Result, models::*};
 use async_trait::async_trait;
 use sqlx::PgPool;
 use zeroize::Zeroizing;
 
 // Re-export public items from submodules
-pub use connection::PoolStats;
+pub use super::PoolStats;
+pub use super::multi_database::{
+    DatabaseCollectionResult, DatabaseFailure, MultiDatabaseConfig, MultiDatabaseMetadata,
+    MultiDatabaseResult,
+};
 pub use enumeration::{
     EnumeratedDatabase, ListDatabasesOptions, SYSTEM_DATABASES, list_accessible_databases,
     list_databases,
 };
-pub use multi_database::{
-    DatabaseCollectionResult, DatabaseFailure, MultiDatabaseConfig, MultiDatabaseMetadata,
-    MultiDatabaseResult, collect_all_databases,
-};
+pub use multi_database::collect_all_databases;
+pub use replica::split_hosts;
 pub use row_ext::RowExt;
 pub use sampling::{
     detect_ordering_strategy, detect_ordering_strategy_with_columns, generate_order_by_clause,
@@ -61,17 +74,22 @@ pub struct PostgresAdapter {
     pub pool: PgPool,
     /// Connection configuration (pool settings, timeouts, etc.)
     pub config: ConnectionConfig,
+    /// Optional object types to collect
+    pub objects: ObjectSelection,
     /// Original connection URL (stored for creating connections to other databases)
     /// This is kept private to prevent credential exposure.
     /// Wrapped in `Zeroizing` so the URL (which may contain credentials) is
     /// scrubbed from memory when the adapter is dropped (CWE-316).
     connection_url: Zeroizing<String>,
+    /// Catalog lookups shared by every table sampled through this adapter
+    catalog: catalog_cache::CatalogCache,
 }
 
 impl std::fmt::Debug for PostgresAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PostgresAdapter")
             .field("config", &self.config)
+            .field("objects", &self.objects)
             .field("pool_size", &self.pool.size())
             .field("pool_idle", &self.pool.num_idle())
             // Note: connection_url is intentionally omitted to prevent credential exposure
@@ -124,8 +142,9 @@ async fn sample_table(
         table_ref: TableRef<'_>,
         config: &super::SamplingConfig,
     ) -> Result<TableSample> {
-        sampling::sample_table(
+        sampling::sample_table_cached(
             &self.pool,
+            &self.catalog,
             table_ref.schema_name,
             table_ref.table_name,
             config,
@@ -152,6 +171,14 @@ fn supports_feature(&self, feature: AdapterFeature) -> bool {
     fn connection_config(&self) -> ConnectionConfig {
         self.config.clone()
     }
+
+    fn connection_pool_stats(&self) -> Option<PoolStats> {
+        Some(self.pool_statistics())
+    }
+
+    async fn verify_read_only(&self) -> Result<ReadOnlyVerification> {
+        self.read_only_verification().await
+    }
 }
 
 // Additional PostgresAdapter methods for data sampling
@@ -251,7 +278,7 @@ pub async fn sample_table(
         table: &str,
         config: &super::SamplingConfig,
     ) -> Result<crate::models::TableSample> {
-        sampling::sample_table(&self.pool, Some(schema), table, config).await
+        sampling::sample_table_cached(&self.pool, &self.catalog, Some(schema), table, config).await
     }
 }
 
@@ -376,7 +403,9 @@ pub async fn connect_to_database(&self, database: &str) -> Result<PostgresAdapte
         tracing::debug!("Connecting to database: {}", database);
 
         // Create new adapter with the same config but different database
-        Self::with_config(&new_url, self.config.clone()).await
+        let mut adapter = Self::with_config(&new_url, self.config.clone()).await?;
+        adapter.objects = self.objects;
+        Ok(adapter)
     }
 
     /// Generate connection URL for a different database on the same server.
@@ -252,6 +252,56 @@ pub async fn collect_procedures(pool: &PgPool) -> Result<Vec<Procedure>> {
     Ok(procedures)
 }
 
+/// Collects functions (`routine_type` `FUNCTION`) or procedures (`PROCEDURE`)
+/// from `information_schema.routines`, for roles denied access to `pg_proc`.
+///
+/// Parameters and comments are not collected, and PostgreSQL only shows the
+/// definitions of routines the role owns.
+pub async fn collect_routines_from_information_schema(
+    pool: &PgPool,
+    routine_type: &str,
+) -> Result<Vec<Procedure>> {
+    let routines_query = r#"
+        SELECT
+            r.routine_name::text as routine_name,
+            r.routine_schema::text as schema_name,
+            r.routine_definition::text as routine_definition,
+            lower(r.external_language)::text as language,
+            r.type_udt_name::text as return_type
+        FROM information_schema.routines r
+        WHERE r.routine_schema NOT IN ('pg_catalog', 'information_schema')
+        AND r.routine_type = $1
+        ORDER BY r.routine_schema, r.routine_name
+    "#;
+
+    let rows = sqlx::query(routines_query)
+        .bind(routine_type)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate routines from information_schema",
+                e,
+            )
+        })?;
+
+    let mut routines = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let context = Some("information_schema.routines");
+        let return_type: Option<String> = row.get_field("return_type", context)?;
+        routines.push(Procedure {
+            name: row.get_field("routine_name", context)?,
+            schema: row.get_field("schema_name", context)?,
+            definition: row.get_field("routine_definition", context)?,
+            parameters: Vec::new(),
+            return_type: return_type.as_deref().map(map_pg_type_to_unified),
+            language: row.get_field("language", context)?,
+            comment: None,
+        });
+    }
+    Ok(routines)
+}
+
 /// Parses function/procedure parameters from PostgreSQL metadata.
 ///
 /// # Arguments
@@ -354,7 +404,11 @@ fn map_pg_type_to_unified(pg_type: &str) -> UnifiedDataType {
         "float8" | "double precision" => UnifiedDataType::Float {
             precision: Some(53),
         },
-        "numeric" => UnifiedDataType::Float { precision: None },
+        "numeric" => UnifiedDataType::Decimal {
+            precision: None,
+            scale: None,
+        },
+        "money" => super::type_mapping::MONEY,
 
         // Boolean
         "bool" | "boolean" => UnifiedDataType::Boolean,
@@ -367,6 +421,7 @@ fn map_pg_type_to_unified(pg_type: &str) -> UnifiedDataType {
         "time" | "timetz" => UnifiedDataType::Time {
             with_timezone: pg_type.contains("tz"),
         },
+        "interval" => UnifiedDataType::Interval,
 
         // JSON
         "json" | "jsonb" => UnifiedDataType::Json,
@@ -374,8 +429,12 @@ fn map_pg_type_to_unified(pg_type: &str) -> UnifiedDataType {
         // UUID
         "uuid" => UnifiedDataType::Uuid,
 
+        // Network addresses
+        "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::NetworkAddress,
+
         // Binary
         "bytea" => UnifiedDataType::Binary { max_length: None },
+        "bit" | "varbit" => UnifiedDataType::Bit { length: None },
 
         // Void (for procedures)
         "void" => UnifiedDataType::Custom {
@@ -485,6 +544,14 @@ fn test_map_pg_type_to_unified() {
             map_pg_type_to_unified("uuid"),
             UnifiedDataType::Uuid
         ));
+        assert!(matches!(
+            map_pg_type_to_unified("numeric"),
+            UnifiedDataType::Decimal { .. }
+        ));
+        assert!(matches!(
+            map_pg_type_to_unified("inet"),
+            UnifiedDataType::NetworkAddress
+        ));
         assert!(matches!(
             map_pg_type_to_unified("void"),
             UnifiedDataType::Custom { .. }
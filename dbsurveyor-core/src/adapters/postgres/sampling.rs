@@ -17,12 +17,13 @@
 //! - Uses parameterized queries to prevent SQL injection
 //! - Respects connection pool timeout settings
 
+use super::catalog_cache::{CatalogCache, CatalogTable};
 use crate::adapters::config::SamplingConfig;
 use crate::adapters::helpers::TIMESTAMP_COLUMN_NAMES;
 use crate::error::DbSurveyorError;
 use crate::models::{
-    Column, OrderingStrategy, SampleStatus, SamplingStrategy, SortDirection, TableSample,
-    UnifiedDataType,
+    Column, OrderingStrategy, SampleColumnType, SampleStatus, SampleValueEncoding,
+    SamplingStrategy, SortDirection, TableSample, UnifiedDataType,
 };
 use serde_json::Value as JsonValue;
 use sqlx::{PgPool, Row};
@@ -444,6 +445,128 @@ pub fn generate_order_by_clause(strategy: &OrderingStrategy, descending: bool) -
     }
 }
 
+/// Chooses how sampled values of a column with the given `pg_type.typname` are written.
+fn sample_encoding(type_name: &str) -> SampleValueEncoding {
+    match type_name {
+        "numeric" | "money" => SampleValueEncoding::DecimalString,
+        "bytea" => SampleValueEncoding::Base64,
+        "float4" | "float8" => SampleValueEncoding::Float,
+        "timestamptz" => SampleValueEncoding::TimestampWithOffset,
+        "timestamp" | "date" | "time" => SampleValueEncoding::LocalDateTime,
+        _ => SampleValueEncoding::Json,
+    }
+}
+
+/// Select-list expression producing a column's sampled value from `t`.
+///
+/// `row_to_json` writes numerics as JSON numbers, which lose their scale
+/// when parsed, and bytea as hex text, so those are converted here. Floats
+/// need nothing: PostgreSQL already writes NaN and infinities as strings.
+fn sample_expression(name: &str, type_name: &str) -> String {
+    let column = format!("t.\"{}\"", escape_identifier(name));
+    let alias = format!("\"{}\"", escape_identifier(name));
+    match type_name {
+        "numeric" => format!("{column}::text AS {alias}"),
+        // money text carries a locale-dependent currency format
+        "money" => format!("{column}::numeric::text AS {alias}"),
+        "bytea" => format!(
+            "CASE WHEN {column} IS NULL THEN NULL ELSE json_build_object(\
+             'base64', translate(encode({column}, 'base64'), E'\\n', ''), \
+             'length', octet_length({column})) END AS {alias}"
+        ),
+        _ => column,
+    }
+}
+
+/// Builds the select list and type legend for sampling a table.
+///
+/// Falls back to `t.*` and an empty legend when the column types cannot be
+/// read, so sampling still works with plain `row_to_json` output.
+async fn sample_projection(
+    pool: &PgPool,
+    schema: &str,
+    table: &str,
+    config: &SamplingConfig,
+) -> (String, Vec<SampleColumnType>) {
+    let columns_query = r#"
+        SELECT
+            a.attname::text AS name,
+            format_type(a.atttypid, a.atttypmod) AS data_type,
+            ty.typname::text AS type_name,
+            CASE WHEN ty.typname = 'numeric' AND a.atttypmod >= 4
+                THEN (a.atttypmod - 4) & 65535
+            END AS scale
+        FROM pg_attribute a
+        JOIN pg_class c ON c.oid = a.attrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_type ty ON ty.oid = a.atttypid
+        WHERE n.nspname = $1 AND c.relname = $2
+        AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum
+    "#;
+
+    let rows = match sqlx::query(columns_query)
+        .bind(schema)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) if !rows.is_empty() => rows,
+        _ => {
+            tracing::debug!(
+                "Could not fetch column types for {}.{}; sampling without a type legend",
+                schema,
+                table
+            );
+            return ("t.*".to_string(), Vec::new());
+        }
+    };
+
+    build_projection(
+        rows.iter().map(|row| ProjectedColumn {
+            name: row.get("name"),
+            data_type: row.try_get("data_type").unwrap_or_default(),
+            type_name: row.try_get("type_name").unwrap_or_default(),
+            scale: row.try_get("scale").ok().flatten(),
+        }),
+        config,
+    )
+}
+
+/// A column's type details as read from `pg_attribute` and `pg_type`.
+pub(super) struct ProjectedColumn {
+    pub(super) name: String,
+    /// `format_type()` rendering, e.g. `numeric(10,2)`
+    pub(super) data_type: String,
+    /// `pg_type.typname`
+    pub(super) type_name: String,
+    pub(super) scale: Option<i32>,
+}
+
+/// Builds the select list and type legend from column type details.
+///
+/// Columns removed by a `skip-column` pattern are left out, leaving an empty
+/// select list if none remain.
+pub(super) fn build_projection(
+    columns: impl Iterator<Item = ProjectedColumn>,
+    config: &SamplingConfig,
+) -> (String, Vec<SampleColumnType>) {
+    let mut expressions = Vec::new();
+    let mut legend = Vec::new();
+    for column in columns.filter(|column| !config.skips_column(&column.name)) {
+        expressions.push(sample_expression(&column.name, &column.type_name));
+        legend.push(
+            SampleColumnType::new(
+                column.name,
+                column.data_type,
+                sample_encoding(&column.type_name),
+            )
+            .with_scale(column.scale.and_then(|s| u32::try_from(s).ok())),
+        );
+    }
+    (expressions.join(", "), legend)
+}
+
 /// Sample data from a table with rate limiting and intelligent ordering.
 ///
 /// This function samples rows from a table using the detected ordering strategy
@@ -509,6 +632,34 @@ pub async fn sample_table_with_columns(
     table: &str,
     config: &SamplingConfig,
     columns: Option<&[Column]>,
+) -> Result<TableSample, DbSurveyorError> {
+    sample_table_from_catalog(pool, schema, table, config, columns, None).await
+}
+
+/// Sample data from a table, answering catalog lookups from `cache`.
+///
+/// The table's row estimate, column types, and ordering come from the
+/// cached schema catalog, leaving the sample itself as the only per-table
+/// query. Tables missing from the cache are looked up individually.
+pub(super) async fn sample_table_cached(
+    pool: &PgPool,
+    cache: &CatalogCache,
+    schema: Option<&str>,
+    table: &str,
+    config: &SamplingConfig,
+) -> Result<TableSample, DbSurveyorError> {
+    let catalog = cache.schema(pool, schema.unwrap_or("public")).await;
+    let cached = catalog.as_deref().and_then(|c| c.table(table));
+    sample_table_from_catalog(pool, schema, table, config, None, cached).await
+}
+
+async fn sample_table_from_catalog(
+    pool: &PgPool,
+    schema: Option<&str>,
+    table: &str,
+    config: &SamplingConfig,
+    columns: Option<&[Column]>,
+    cached: Option<&CatalogTable>,
 ) -> Result<TableSample, DbSurveyorError> {
     config.validate()?;
     let mut warnings = Vec::new();
@@ -539,8 +690,10 @@ pub async fn sample_table_with_columns(
             "public"
         }
     };
-    let strategy =
-        detect_ordering_strategy_with_columns(pool, detection_schema, table, columns).await?;
+    let strategy = match (columns, cached) {
+        (None, Some(cached)) => cached.ordering_strategy(),
+        _ => detect_ordering_strategy_with_columns(pool, detection_schema, table, columns).await?,
+    };
 
     // Determine sampling strategy and add warnings for unordered tables
     let (sampling_strategy, is_random) = match &strategy {
@@ -572,17 +725,31 @@ pub async fn sample_table_with_columns(
         WHERE n.nspname = $1 AND c.relname = $2
     "#;
 
-    let total_rows: Option<i64> = sqlx::query_scalar(count_query)
-        .bind(detection_schema)
-        .bind(table)
-        .fetch_optional(pool)
-        .await
-        .map_err(|e| {
-            DbSurveyorError::collection_failed(
-                format!("Failed to get row count for table '{}'", display_name),
-                e,
-            )
-        })?;
+    let total_rows: Option<i64> = match cached {
+        Some(cached) => cached.estimated_rows,
+        None => sqlx::query_scalar(count_query)
+            .bind(detection_schema)
+            .bind(table)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                DbSurveyorError::collection_failed(
+                    format!("Failed to get row count for table '{}'", display_name),
+                    e,
+                )
+            })?,
+    };
+
+    let (projection, column_types) = match cached.and_then(|cached| cached.projection(config)) {
+        Some(projection) => projection,
+        None => sample_projection(pool, detection_schema, table, config).await,
+    };
+    if projection.is_empty() {
+        return Err(DbSurveyorError::configuration(format!(
+            "Every column of table '{}' matches a skip-column pattern",
+            display_name
+        )));
+    }
 
     // Build FROM clause: schema-qualified when schema is present, table-only otherwise.
     // Identifiers are escaped to prevent SQL injection from embedded quotes.
@@ -602,7 +769,7 @@ pub async fn sample_table_with_columns(
             .and_then(|r| u64::try_from(r.max(0)).ok())
             .is_some_and(|r| r >= TABLESAMPLE_MIN_ROWS);
 
-    let sample_query = if use_tablesample {
+    let limited_rows = if use_tablesample {
         // Safety: we checked total_rows is Some and >= TABLESAMPLE_MIN_ROWS above
         #[allow(clippy::cast_precision_loss)]
         let estimated = total_rows.unwrap_or(0).max(1) as f64;
@@ -612,16 +779,19 @@ pub async fn sample_table_with_columns(
         let pct =
             ((desired * TABLESAMPLE_OVERSAMPLING_FACTOR) / estimated * 100.0).clamp(0.01, 100.0);
         format!(
-            "SELECT row_to_json(t.*) AS row_data FROM {} TABLESAMPLE BERNOULLI({:.4}) AS t LIMIT $1",
+            "SELECT * FROM {} TABLESAMPLE BERNOULLI({:.4}) AS t LIMIT $1",
             base_table, pct
         )
     } else {
         let order_clause = generate_order_by_clause(&strategy, true); // DESC for most recent
-        format!(
-            "SELECT row_to_json(t.*) AS row_data FROM {} t {} LIMIT $1",
-            base_table, order_clause
-        )
+        format!("SELECT * FROM {} t {} LIMIT $1", base_table, order_clause)
     };
+    // Values are encoded outside the ordered subquery so ORDER BY sees the
+    // original column types rather than the text conversions
+    let sample_query = format!(
+        "SELECT row_to_json(s.*) AS row_data FROM (SELECT {} FROM ({}) t) s",
+        projection, limited_rows
+    );
 
     tracing::debug!(
         "Sampling {} with query: {} (limit: {})",
@@ -630,17 +800,31 @@ pub async fn sample_table_with_columns(
         config.sample_size
     );
 
-    // Execute sample query
-    let rows: Vec<JsonValue> = sqlx::query_scalar(&sample_query)
-        .bind(i64::from(config.sample_size))
-        .fetch_all(pool)
-        .await
-        .map_err(|e| {
-            DbSurveyorError::collection_failed(
-                format!("Failed to sample data from table '{}'", display_name),
-                e,
-            )
-        })?;
+    // Execute sample query, retrying once if a standby cancels it for WAL replay
+    let fetch = || {
+        sqlx::query_scalar(&sample_query)
+            .bind(i64::from(config.sample_size))
+            .fetch_all(pool)
+    };
+    let rows: Vec<JsonValue> = match fetch().await {
+        Err(e) if super::replica::is_recovery_conflict(&e) => {
+            tracing::debug!(
+                "Retrying sample of {} after recovery conflict",
+                display_name
+            );
+            warnings.push(
+                "Sample query conflicted with recovery on the standby and was retried".to_string(),
+            );
+            fetch().await
+        }
+        result => result,
+    }
+    .map_err(|e| {
+        DbSurveyorError::collection_failed(
+            format!("Failed to sample data from table '{}'", display_name),
+            e,
+        )
+    })?;
 
     let actual_sample_size = u32::try_from(rows.len()).unwrap_or(u32::MAX);
 
@@ -664,6 +848,7 @@ pub async fn sample_table_with_columns(
         collected_at: chrono::Utc::now(),
         warnings,
         sample_status: Some(SampleStatus::Complete),
+        column_types,
     })
 }
 
@@ -738,6 +923,42 @@ fn test_generate_order_by_clause_special_characters() {
         assert_eq!(clause, "ORDER BY \"user-id\" DESC, \"table\" DESC");
     }
 
+    #[test]
+    fn test_sample_expression() {
+        assert_eq!(sample_expression("id", "int4"), "t.\"id\"");
+        assert_eq!(
+            sample_expression("Price", "numeric"),
+            "t.\"Price\"::text AS \"Price\""
+        );
+        assert_eq!(
+            sample_expression("fee", "money"),
+            "t.\"fee\"::numeric::text AS \"fee\""
+        );
+        let bytea = sample_expression("blob\"x", "bytea");
+        assert!(bytea.starts_with("CASE WHEN t.\"blob\"\"x\" IS NULL THEN NULL"));
+        assert!(bytea.contains("'length', octet_length(t.\"blob\"\"x\"))"));
+        assert!(bytea.ends_with("END AS \"blob\"\"x\""));
+    }
+
+    #[test]
+    fn test_sample_encoding() {
+        assert_eq!(
+            sample_encoding("numeric"),
+            SampleValueEncoding::DecimalString
+        );
+        assert_eq!(sample_encoding("bytea"), SampleValueEncoding::Base64);
+        assert_eq!(sample_encoding("float8"), SampleValueEncoding::Float);
+        assert_eq!(
+            sample_encoding("timestamptz"),
+            SampleValueEncoding::TimestampWithOffset
+        );
+        assert_eq!(
+            sample_encoding("timestamp"),
+            SampleValueEncoding::LocalDateTime
+        );
+        assert_eq!(sample_encoding("jsonb"), SampleValueEncoding::Json);
+    }
+
     #[test]
     fn test_escape_identifier() {
         assert_eq!(escape_identifier("normal"), "normal");
@@ -764,14 +985,11 @@ fn make_column(
         is_auto: bool,
     ) -> Column {
         Column {
-            name: name.to_string(),
-            data_type,
             is_nullable: false,
             is_primary_key: is_pk,
             is_auto_increment: is_auto,
-            default_value: None,
-            comment: None,
             ordinal_position: ordinal,
+            ..Column::new(name.to_string(), data_type)
         }
     }
 
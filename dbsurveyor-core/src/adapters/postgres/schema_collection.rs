@@ -7,9 +7,15 @@
 use super::PostgresAdapter;
 use super::RowExt;
 use super::batch_collection;
-use super::{routines, triggers, views};
+use super::{ansi, inheritance, masking, routines, triggers, views};
 use crate::Result;
-use crate::adapters::helpers::resolve_optional_collection;
+use crate::adapters::CatalogMode;
+use crate::adapters::helpers::{
+    collect_if_selected, collect_within_table_timeout, qualified_name, resolve_object_collection,
+    resolve_optional_collection, retry_with_fallback,
+};
+use crate::defaults::classify_default;
+use crate::error::DbSurveyorError;
 use crate::models::*;
 use sqlx::Row;
 use std::collections::HashMap;
@@ -21,6 +27,18 @@ struct TableMetadata {
     schema: Option<String>,
     comment: Option<String>,
     estimated_rows: Option<i64>,
+    size_bytes: Option<i64>,
+    toast_size_bytes: Option<i64>,
+}
+
+impl TableMetadata {
+    /// Table options known from enumeration: the size of the TOAST relation.
+    fn options(&self) -> Option<TableOptions> {
+        self.toast_size_bytes.map(|bytes| TableOptions {
+            toast_bytes: Some(bytes.max(0) as u64),
+            ..TableOptions::default()
+        })
+    }
 }
 
 /// Main entry point for schema collection
@@ -39,10 +57,25 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
         tracing::error!("Schema collection privilege validation failed: {}", e);
         return Err(e);
     }
+    match adapter.config.catalog_mode {
+        CatalogMode::Ansi => return ansi::collect_schema(adapter, None).await,
+        CatalogMode::Native => adapter.probe_catalog_access().await?,
+        CatalogMode::Auto => match adapter.probe_catalog_access().await {
+            Ok(()) => {}
+            Err(DbSurveyorError::InsufficientPrivileges { required }) => {
+                tracing::warn!(
+                    "pg_catalog is not readable ({}); collecting from information_schema only",
+                    required
+                );
+                return ansi::collect_schema(adapter, Some(required)).await;
+            }
+            Err(e) => return Err(e),
+        },
+    }
 
     // Collect database information
     tracing::debug!("Collecting database information");
-    let database_info = adapter.collect_database_info().await?;
+    let mut database_info = adapter.collect_database_info().await?;
 
     // Collect schemas first to understand database structure
     tracing::debug!("Enumerating database schemas");
@@ -52,7 +85,8 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
     // Collect tables with comprehensive metadata
     tracing::debug!("Enumerating database tables");
     let table_collection_start = std::time::Instant::now();
-    let tables = match adapter.collect_tables().await {
+    let mut object_failures = Vec::new();
+    let mut tables = match adapter.collect_tables(&mut object_failures).await {
         Ok(tables) => {
             let table_collection_duration = table_collection_start.elapsed();
             tracing::info!(
@@ -68,16 +102,87 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
         }
     };
 
+    // Link inherited and partitioned tables to their parents and children
+    match inheritance::collect_inheritance(&adapter.pool).await {
+        Ok(catalog) => inheritance::apply_inheritance(&mut tables, &catalog),
+        Err(e) => {
+            let warning = format!("Failed to collect table inheritance: {}", e);
+            tracing::warn!("{}", warning);
+            warnings.push(CollectionWarning::new(WarningCategory::Collection, warning));
+        }
+    }
+
+    // Record masking rules so sampled values of masked columns are not
+    // mistaken for stored data
+    match masking::collect_masking(&adapter.pool).await {
+        Ok(catalog) => warnings.extend(masking::apply_masking(&mut tables, &catalog)),
+        Err(e) => {
+            let warning = format!("Failed to collect masking rules: {}", e);
+            tracing::warn!("{}", warning);
+            warnings.push(CollectionWarning::new(WarningCategory::Collection, warning));
+        }
+    }
+
     // Collect views, functions, procedures, and triggers concurrently
     // These are independent queries that can safely run in parallel
     tracing::debug!("Enumerating views, functions, procedures, and triggers concurrently");
+    let objects = adapter.objects;
     let (views_result, functions_result, procedures_result, triggers_result) = tokio::join!(
-        views::collect_views(&adapter.pool),
-        routines::collect_functions(&adapter.pool),
-        routines::collect_procedures(&adapter.pool),
-        triggers::collect_triggers(&adapter.pool),
+        collect_if_selected(objects.views, views::collect_views(&adapter.pool)),
+        collect_if_selected(
+            objects.functions,
+            routines::collect_functions(&adapter.pool)
+        ),
+        collect_if_selected(
+            objects.procedures,
+            routines::collect_procedures(&adapter.pool)
+        ),
+        collect_if_selected(objects.triggers, triggers::collect_triggers(&adapter.pool)),
     );
 
+    // A role denied the catalog queries still gets what information_schema
+    // shows, recorded as degraded rather than skipped
+    let mut degraded = Vec::new();
+    let routine_name =
+        |routine: &Procedure| qualified_name(routine.schema.as_deref(), &routine.name);
+    let views_result = retry_with_fallback(
+        views_result,
+        views::collect_views_from_information_schema(&adapter.pool),
+        fallback_record("views"),
+        |view: &View| qualified_name(view.schema.as_deref(), &view.name),
+        &mut degraded,
+    )
+    .await;
+    let functions_result = retry_with_fallback(
+        functions_result,
+        routines::collect_routines_from_information_schema(&adapter.pool, "FUNCTION"),
+        fallback_record("functions"),
+        routine_name,
+        &mut degraded,
+    )
+    .await;
+    let procedures_result = retry_with_fallback(
+        procedures_result,
+        routines::collect_routines_from_information_schema(&adapter.pool, "PROCEDURE"),
+        fallback_record("procedures"),
+        routine_name,
+        &mut degraded,
+    )
+    .await;
+    let triggers_result = retry_with_fallback(
+        triggers_result,
+        triggers::collect_triggers_from_information_schema(&adapter.pool),
+        fallback_record("triggers"),
+        |trigger: &Trigger| {
+            qualified_name(
+                trigger.schema.as_deref(),
+                &format!("{}.{}", trigger.table_name, trigger.name),
+            )
+        },
+        &mut degraded,
+    )
+    .await;
+
     // Count actual errors before consuming results (empty results are valid)
     let metadata_error_count = views_result.is_err() as u8
         + functions_result.is_err() as u8
@@ -123,6 +228,7 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
         collected_triggers.len(),
         schemas.len()
     );
+    database_info.schemas = schemas;
 
     let schema = DatabaseSchema {
         format_version: FORMAT_VERSION.to_string(),
@@ -143,11 +249,22 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
                 .unwrap_or(u64::MAX),
             collector_version: env!("CARGO_PKG_VERSION").to_string(),
             warnings,
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
         },
     };
 
     // Aggregate indexes and constraints from per-table data into schema-level vectors
-    let schema = schema.with_aggregated_indexes_and_constraints();
+    let mut schema = schema
+        .with_aggregated_indexes_and_constraints()
+        .with_object_failures(object_failures)
+        .with_degraded_collections(degraded);
+    // Batch collection fetches indexes and constraints whether selected or not
+    objects.apply(&mut schema);
 
     tracing::info!(
         "Collected {} total indexes and {} total constraints across all tables",
@@ -158,6 +275,27 @@ pub(crate) async fn collect_schema(adapter: &PostgresAdapter) -> Result<Database
     Ok(schema)
 }
 
+/// The degraded record for views, functions, procedures, or triggers read
+/// through the `information_schema` fallback, naming what that view omits.
+pub(super) fn fallback_record(object_class: &str) -> DegradedCollection {
+    let missing: &[&str] = match object_class {
+        "views" => &["comments", "definitions of views owned by other roles"],
+        "functions" => &[
+            "parameters",
+            "comments",
+            "definitions of functions owned by other roles",
+        ],
+        "procedures" => &[
+            "parameters",
+            "comments",
+            "definitions of procedures owned by other roles",
+        ],
+        "triggers" => &["definitions", "triggers on tables the role can only read"],
+        _ => &[],
+    };
+    DegradedCollection::new(object_class, "information_schema").with_missing(missing)
+}
+
 impl PostgresAdapter {
     /// Collects comprehensive database information
     pub(crate) async fn collect_database_info(&self) -> Result<DatabaseInfo> {
@@ -207,15 +345,20 @@ pub(crate) async fn collect_database_info(&self) -> Result<DatabaseInfo> {
 
         Ok(DatabaseInfo {
             name,
+            engine: Some(DatabaseType::PostgreSQL),
             version: Some(version),
             size_bytes: size_bytes.map(|s| s.max(0) as u64),
             encoding,
             collation,
+            timezone: self.server_timezone().await,
             owner,
             is_system_database,
             access_level: AccessLevel::Full,
+            access_probe: None,
+            schemas: Vec::new(),
             collection_status: CollectionStatus::Success,
-        })
+        }
+        .with_access_probe(super::access::probe_access(&self.pool).await))
     }
 
     /// Collects all schemas from the database
@@ -267,23 +410,40 @@ pub(crate) async fn collect_schemas(&self) -> Result<Vec<String>> {
     ///
     /// Uses batch collection (5 queries total) as the default path. Falls back
     /// to per-table queries if batch collection fails.
-    pub(crate) async fn collect_tables(&self) -> Result<Vec<Table>> {
+    ///
+    /// Only a failure to enumerate tables is fatal. In the per-table fallback,
+    /// a failure for one table is recorded in `failures` and collection
+    /// continues with the remaining tables.
+    pub(crate) async fn collect_tables(
+        &self,
+        failures: &mut Vec<ObjectFailure>,
+    ) -> Result<Vec<Table>> {
         tracing::debug!("Starting table enumeration for PostgreSQL database");
 
         let table_metadata = self.enumerate_table_metadata().await?;
 
+        // Batch queries cover every table at once, so a per-table timeout can
+        // only be enforced by collecting each table separately
+        if self.config.table_timeout.is_some() {
+            return Ok(self
+                .collect_tables_per_table(&table_metadata, failures)
+                .await);
+        }
+
         // Try batch collection first (5 queries instead of 5*N)
         match batch_collection::collect_all_batch(&self.pool).await {
             Ok(mut batch) => {
                 let mut tables = Vec::with_capacity(table_metadata.len());
                 for meta in &table_metadata {
-                    let table = batch_collection::assemble_table_from_batch(
+                    let mut table = batch_collection::assemble_table_from_batch(
                         &mut batch,
                         &meta.name,
                         &meta.schema,
                         meta.comment.clone(),
                         meta.estimated_rows,
+                        meta.size_bytes,
                     );
+                    table.options = meta.options();
 
                     tracing::debug!(
                         "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
@@ -302,7 +462,9 @@ pub(crate) async fn collect_tables(&self) -> Result<Vec<Table>> {
                     "Batch collection failed, falling back to per-table queries: {}",
                     e
                 );
-                self.collect_tables_per_table(&table_metadata).await
+                Ok(self
+                    .collect_tables_per_table(&table_metadata, failures)
+                    .await)
             }
         }
     }
@@ -317,8 +479,10 @@ async fn enumerate_table_metadata(&self) -> Result<Vec<TableMetadata>> {
                 t.table_type,
                 obj_description(c.oid) as table_comment,
                 c.reltuples::bigint as estimated_rows,
-                pg_size_pretty(pg_total_relation_size(c.oid)) as table_size,
-                pg_total_relation_size(c.oid) as table_size_bytes
+                pg_total_relation_size(c.oid) as table_size_bytes,
+                CASE WHEN c.reltoastrelid <> 0
+                    THEN pg_total_relation_size(c.reltoastrelid)
+                END as toast_size_bytes
             FROM information_schema.tables t
             LEFT JOIN pg_class c ON c.relname = t.table_name
             LEFT JOIN pg_namespace n ON n.nspname = t.table_schema AND c.relnamespace = n.oid
@@ -372,11 +536,25 @@ async fn enumerate_table_metadata(&self) -> Result<Vec<TableMetadata>> {
                     e,
                 )
             })?;
+            let size_bytes: Option<i64> = row.try_get("table_size_bytes").map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed(
+                    "Failed to parse table size from database result",
+                    e,
+                )
+            })?;
+            let toast_size_bytes: Option<i64> = row.try_get("toast_size_bytes").map_err(|e| {
+                crate::error::DbSurveyorError::collection_failed(
+                    "Failed to parse TOAST size from database result",
+                    e,
+                )
+            })?;
             metadata.push(TableMetadata {
                 name,
                 schema,
                 comment,
                 estimated_rows,
+                size_bytes,
+                toast_size_bytes,
             });
         }
 
@@ -385,50 +563,120 @@ async fn enumerate_table_metadata(&self) -> Result<Vec<TableMetadata>> {
 
     /// Fallback: collects tables using individual per-table queries (N+1 pattern).
     ///
-    /// Used only when batch collection fails.
+    /// Used when batch collection fails or a per-table timeout is configured.
+    /// A table whose columns cannot be collected, or whose queries exceed the
+    /// timeout, is skipped; a failure in any other query leaves that part of
+    /// the table empty. Either way the failure is recorded in `failures`.
     async fn collect_tables_per_table(
         &self,
         table_metadata: &[TableMetadata],
-    ) -> Result<Vec<Table>> {
+        failures: &mut Vec<ObjectFailure>,
+    ) -> Vec<Table> {
         let mut tables = Vec::with_capacity(table_metadata.len());
 
         for meta in table_metadata {
-            let columns = self.collect_table_columns(&meta.name, &meta.schema).await?;
-            let primary_key = self
-                .collect_table_primary_key(&meta.name, &meta.schema)
-                .await?;
-            let foreign_keys = self
-                .collect_table_foreign_keys(&meta.name, &meta.schema)
-                .await?;
-            let indexes = self.collect_table_indexes(&meta.name, &meta.schema).await?;
-            let constraints = self
-                .collect_table_constraints(&meta.name, &meta.schema)
-                .await?;
-
-            let table = Table {
-                name: meta.name.clone(),
-                schema: meta.schema.clone(),
-                columns,
-                primary_key,
-                foreign_keys,
-                indexes,
-                constraints,
-                comment: meta.comment.clone(),
-                row_count: meta.estimated_rows.map(|r| r.max(0) as u64),
-            };
+            let mut table_failures = Vec::new();
+            let collected = collect_within_table_timeout(
+                self.collect_single_table(meta, &mut table_failures),
+                self.config.table_timeout,
+                meta.schema.as_deref(),
+                &meta.name,
+                failures,
+            )
+            .await;
+
+            if let Some(table) = collected {
+                failures.append(&mut table_failures);
+                tables.extend(table);
+            }
+        }
 
-            tracing::debug!(
-                "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
-                table.name,
-                table.columns.len(),
-                table.foreign_keys.len(),
-                table.indexes.len()
-            );
+        tables
+    }
 
-            tables.push(table);
-        }
+    /// Collects one table with per-table queries, recording failures.
+    ///
+    /// Returns `None` when the table's columns cannot be collected.
+    async fn collect_single_table(
+        &self,
+        meta: &TableMetadata,
+        failures: &mut Vec<ObjectFailure>,
+    ) -> Option<Table> {
+        let schema = meta.schema.as_deref();
+        let columns = match self.collect_table_columns(&meta.name, &meta.schema).await {
+            Ok(columns) => columns,
+            Err(e) => {
+                tracing::warn!("Skipping table '{}': {}", meta.name, e);
+                failures.push(ObjectFailure::new(schema, &meta.name, "columns", e));
+                return None;
+            }
+        };
+        let primary_key = resolve_object_collection(
+            self.collect_table_primary_key(&meta.name, &meta.schema)
+                .await,
+            schema,
+            &meta.name,
+            "primary key",
+            failures,
+        );
+        let foreign_keys = resolve_object_collection(
+            self.collect_table_foreign_keys(&meta.name, &meta.schema)
+                .await,
+            schema,
+            &meta.name,
+            "foreign keys",
+            failures,
+        );
+        let indexes = resolve_object_collection(
+            collect_if_selected(
+                self.objects.indexes,
+                self.collect_table_indexes(&meta.name, &meta.schema),
+            )
+            .await,
+            schema,
+            &meta.name,
+            "indexes",
+            failures,
+        );
+        let constraints = resolve_object_collection(
+            collect_if_selected(
+                self.objects.constraints,
+                self.collect_table_constraints(&meta.name, &meta.schema),
+            )
+            .await,
+            schema,
+            &meta.name,
+            "constraints",
+            failures,
+        );
 
-        Ok(tables)
+        let table = Table {
+            name: meta.name.clone(),
+            schema: meta.schema.clone(),
+            columns,
+            primary_key,
+            foreign_keys,
+            indexes,
+            constraints,
+            comment: meta.comment.clone(),
+            row_count: meta.estimated_rows.map(|r| r.max(0) as u64),
+            size_bytes: meta.size_bytes.map(|b| b.max(0) as u64),
+            validator: None,
+            partitioning: None,
+            virtual_table: None,
+            options: meta.options(),
+            inheritance: None,
+        };
+
+        tracing::debug!(
+            "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
+            table.name,
+            table.columns.len(),
+            table.foreign_keys.len(),
+            table.indexes.len()
+        );
+
+        Some(table)
     }
 
     /// Collects column metadata for a specific table
@@ -454,6 +702,11 @@ pub(crate) async fn collect_table_columns(
                 col_description(pgc.oid, c.ordinal_position) as column_comment,
                 c.is_identity,
                 c.identity_generation,
+                pg_get_serial_sequence(quote_ident(c.table_schema) || '.' || quote_ident(c.table_name), c.column_name) as owned_sequence,
+                c.collation_name::text as collation_name,
+                -- Only variable-length columns can be TOASTed; attcompression is PostgreSQL 14+
+                CASE WHEN pa.attlen = -1 THEN pa.attstorage::text END as storage_strategy,
+                NULLIF(to_jsonb(pa) ->> 'attcompression', '') as compression_method,
                 CASE
                     WHEN c.data_type = 'ARRAY' THEN
                         CASE
@@ -467,6 +720,9 @@ pub(crate) async fn collect_table_columns(
                     ELSE false
                 END as is_primary_key
             FROM information_schema.columns c
+            LEFT JOIN pg_attribute pa
+                ON pa.attrelid = (quote_ident(c.table_schema) || '.' || quote_ident(c.table_name))::regclass
+                AND pa.attname = c.column_name
             LEFT JOIN pg_class pgc ON pgc.relname = c.table_name
             LEFT JOIN pg_namespace pgn ON pgn.nspname = c.table_schema AND pgc.relnamespace = pgn.oid
             LEFT JOIN (
@@ -520,9 +776,18 @@ pub(crate) async fn collect_table_columns(
             let column_comment: Option<String> =
                 row.get_field("column_comment", Some(table_name))?;
             let is_identity: String = row.get_field("is_identity", Some(table_name))?;
+            let identity_generation: Option<String> =
+                row.get_field("identity_generation", Some(table_name))?;
+            let owned_sequence: Option<String> =
+                row.get_field("owned_sequence", Some(table_name))?;
             let array_element_type: Option<String> =
                 row.get_field("array_element_type", Some(table_name))?;
             let is_primary_key: bool = row.get_field("is_primary_key", Some(table_name))?;
+            let collation: Option<String> = row.get_field("collation_name", Some(table_name))?;
+            let storage_strategy: Option<String> =
+                row.get_field("storage_strategy", Some(table_name))?;
+            let compression_method: Option<String> =
+                row.get_field("compression_method", Some(table_name))?;
 
             // Map PostgreSQL data type to unified data type
             let unified_data_type = Self::map_postgres_type_to_unified(
@@ -535,12 +800,18 @@ pub(crate) async fn collect_table_columns(
             )?;
 
             // Determine if column is auto-increment
+            let normalized_default = column_default.as_deref().map(classify_default);
             let is_auto_increment = is_identity == "YES"
-                || column_default.as_ref().is_some_and(|default| {
-                    default.starts_with("nextval(")
-                        || default.contains("_seq'::regclass)")
-                        || default.contains("::regclass")
-                });
+                || matches!(
+                    normalized_default,
+                    Some(DefaultValue::SequenceNextval { .. })
+                );
+            let identity = super::type_mapping::map_column_identity(
+                is_identity == "YES",
+                identity_generation.as_deref(),
+                normalized_default.as_ref(),
+                owned_sequence,
+            );
 
             columns.push(Column {
                 name: column_name,
@@ -549,8 +820,19 @@ pub(crate) async fn collect_table_columns(
                 is_primary_key,
                 is_auto_increment,
                 default_value: column_default,
+                normalized_default,
                 comment: column_comment,
                 ordinal_position: u32::try_from(ordinal_position).unwrap_or(0),
+                generated: None,
+                declared_type: None,
+                collation,
+                character_set: None,
+                storage: super::type_mapping::map_column_storage(
+                    storage_strategy.as_deref(),
+                    compression_method.as_deref(),
+                ),
+                identity,
+                masking: None,
             });
         }
 
@@ -568,7 +850,7 @@ pub(crate) async fn collect_table_primary_key(
         let pk_query = r#"
             SELECT
                 tc.constraint_name,
-                string_agg(kcu.column_name, ',' ORDER BY kcu.ordinal_position) as columns
+                array_agg(kcu.column_name::text ORDER BY kcu.ordinal_position) as columns
             FROM information_schema.table_constraints tc
             JOIN information_schema.key_column_usage kcu
                 ON tc.constraint_name = kcu.constraint_name
@@ -596,8 +878,7 @@ pub(crate) async fn collect_table_primary_key(
 
         if let Some(row) = pk_row {
             let name: Option<String> = row.get_field("constraint_name", Some(table_name))?;
-            let columns_str: String = row.get_field("columns", Some(table_name))?;
-            let columns: Vec<String> = columns_str.split(',').map(|s| s.to_string()).collect();
+            let columns: Vec<String> = row.get_field("columns", Some(table_name))?;
 
             Ok(Some(PrimaryKey { name, columns }))
         } else {
@@ -720,8 +1001,9 @@ pub(crate) async fn collect_table_indexes(
                 am.amname::text as index_type,
                 ix.indisunique as is_unique,
                 ix.indisprimary as is_primary,
-                string_agg(a.attname::text, ',' ORDER BY array_position(ix.indkey, a.attnum)) as columns,
-                pg_get_indexdef(i.oid) as index_definition
+                array_agg(a.attname::text ORDER BY array_position(ix.indkey, a.attnum)) as columns,
+                array_agg((ix.indoption[array_position(ix.indkey, a.attnum) - 1]::int & 1) = 1
+                    ORDER BY array_position(ix.indkey, a.attnum)) as descending
             FROM pg_index ix
             JOIN pg_class t ON t.oid = ix.indrelid
             JOIN pg_class i ON i.oid = ix.indexrelid
@@ -756,29 +1038,9 @@ pub(crate) async fn collect_table_indexes(
             let index_type: String = row.get_field("index_type", Some(table_name))?;
             let is_unique: bool = row.get_field("is_unique", Some(table_name))?;
             let is_primary: bool = row.get_field("is_primary", Some(table_name))?;
-            let columns_str: String = row.get_field("columns", Some(table_name))?;
-            let index_definition: String = row.get_field("index_definition", Some(table_name))?;
-
-            // Parse columns with sort order from index definition
-            let columns: Vec<IndexColumn> = columns_str
-                .split(',')
-                .map(|col_name| {
-                    let col_name = col_name.trim();
-                    // Check if this column has DESC in the index definition
-                    // Pattern: column_name DESC or "column_name" DESC
-                    let sort_order = if index_definition.contains(&format!("{} DESC", col_name))
-                        || index_definition.contains(&format!("\"{}\" DESC", col_name))
-                    {
-                        Some(SortDirection::Descending)
-                    } else {
-                        Some(SortDirection::Ascending)
-                    };
-                    IndexColumn {
-                        name: col_name.to_string(),
-                        sort_order,
-                    }
-                })
-                .collect();
+            let column_names: Vec<String> = row.get_field("columns", Some(table_name))?;
+            let descending: Vec<bool> = row.get_field("descending", Some(table_name))?;
+            let columns = batch_collection::index_columns(column_names, descending);
 
             indexes.push(Index {
                 name,
@@ -788,6 +1050,7 @@ pub(crate) async fn collect_table_indexes(
                 is_unique,
                 is_primary,
                 index_type: Some(index_type),
+                options: None,
             });
         }
 
@@ -808,9 +1071,10 @@ pub(crate) async fn collect_table_constraints(
                 tc.constraint_type::text,
                 cc.check_clause::text,
                 COALESCE(
-                    string_agg(kcu.column_name::text, ',' ORDER BY kcu.ordinal_position),
-                    ''
-                )::text as column_names
+                    array_agg(kcu.column_name::text ORDER BY kcu.ordinal_position)
+                        FILTER (WHERE kcu.column_name IS NOT NULL),
+                    '{}'
+                )::text[] as column_names
             FROM information_schema.table_constraints tc
             LEFT JOIN information_schema.key_column_usage kcu
                 ON tc.constraint_name = kcu.constraint_name
@@ -846,7 +1110,7 @@ pub(crate) async fn collect_table_constraints(
             let name: String = row.get_field("constraint_name", Some(table_name))?;
             let constraint_type_str: String = row.get_field("constraint_type", Some(table_name))?;
             let check_clause: Option<String> = row.get_field("check_clause", Some(table_name))?;
-            let columns_str: String = row.get_field("column_names", Some(table_name))?;
+            let columns: Vec<String> = row.get_field("column_names", Some(table_name))?;
 
             let constraint_type = match constraint_type_str.as_str() {
                 "CHECK" => ConstraintType::Check,
@@ -856,12 +1120,6 @@ pub(crate) async fn collect_table_constraints(
                 _ => ConstraintType::Check,
             };
 
-            let columns: Vec<String> = if columns_str.is_empty() {
-                Vec::new()
-            } else {
-                columns_str.split(',').map(|s| s.to_string()).collect()
-            };
-
             constraints.push(Constraint {
                 name,
                 table_name: table_name.to_string(),
@@ -158,6 +158,34 @@ fn test_map_postgresql_type_basic_types() {
         panic!("Expected array type");
     }
 
+    // Test exact numeric, interval, bit, and network types
+    assert_eq!(
+        map_postgresql_type("numeric", None, Some(10), Some(2)).unwrap(),
+        UnifiedDataType::Decimal {
+            precision: Some(10),
+            scale: Some(2)
+        }
+    );
+    assert_eq!(
+        map_postgresql_type("numeric", None, None, None).unwrap(),
+        UnifiedDataType::Decimal {
+            precision: None,
+            scale: None
+        }
+    );
+    assert_eq!(
+        map_postgresql_type("interval", None, None, None).unwrap(),
+        UnifiedDataType::Interval
+    );
+    assert_eq!(
+        map_postgresql_type("bit varying", Some(16), None, None).unwrap(),
+        UnifiedDataType::Bit { length: Some(16) }
+    );
+    assert_eq!(
+        map_postgresql_type("macaddr8", None, None, None).unwrap(),
+        UnifiedDataType::NetworkAddress
+    );
+
     // Test custom type
     let custom_type = map_postgresql_type("custom_enum", None, None, None).unwrap();
     assert!(
@@ -419,3 +447,138 @@ fn test_username_validation() {
     assert!(PostgresAdapter::parse_connection_config("postgres://invalid-char@host/db").is_err()); // Contains dash
     assert!(PostgresAdapter::parse_connection_config("postgres://invalid@char@host/db").is_err()); // Contains @
 }
+
+#[test]
+fn test_classify_read_only() {
+    use super::connection::classify_read_only;
+
+    let session = classify_read_only("on", "on", false);
+    assert_eq!(session.level, ReadOnlyLevel::Session);
+    assert_eq!(session.evidence[0], "transaction_read_only = on");
+
+    assert_eq!(
+        classify_read_only("on", "off", true).level,
+        ReadOnlyLevel::Server
+    );
+    assert_eq!(
+        classify_read_only("off", "off", false).level,
+        ReadOnlyLevel::NotEnforced
+    );
+}
+
+#[test]
+fn test_classify_access() {
+    use super::access::classify_access;
+
+    let full = classify_access("admin", true, &[], 4, 4);
+    assert_eq!(full.access_level(), AccessLevel::Full);
+    assert_eq!(full.evidence[0], "role is a superuser");
+    assert_eq!(full.identity.as_deref(), Some("admin"));
+
+    let denied = vec!["pg_proc".to_string(), "pg_views".to_string()];
+    let limited = classify_access("app", false, &denied, 2, 3);
+    assert_eq!(limited.access_level(), AccessLevel::Limited);
+    assert!(!limited.catalog_access);
+    assert_eq!(
+        limited.evidence,
+        vec![
+            "role is not a superuser",
+            "SELECT denied on pg_catalog.pg_proc, pg_catalog.pg_views",
+            "SELECT allowed on 2 of 3 tables and views",
+        ]
+    );
+
+    // Reading every table and catalog is full access without superuser
+    assert_eq!(
+        classify_access("reader", false, &[], 3, 3).access_level(),
+        AccessLevel::Full
+    );
+}
+
+#[test]
+fn test_pooler_transaction_sql() {
+    use super::connection::pooler_transaction_sql;
+    use crate::adapters::config::PostgresLimits;
+
+    let defaults = PostgresLimits::default();
+    assert_eq!(
+        pooler_transaction_sql(30, true, &defaults),
+        "BEGIN READ ONLY; SET LOCAL statement_timeout = '30s'; SET LOCAL lock_timeout = '30s'"
+    );
+    assert!(pooler_transaction_sql(30, false, &defaults).starts_with("BEGIN; "));
+
+    let limits = PostgresLimits {
+        work_mem: Some("4MB".to_string()),
+        statement_timeout_ms: Some(15_000),
+    };
+    assert_eq!(
+        pooler_transaction_sql(30, true, &limits),
+        "BEGIN READ ONLY; SET LOCAL statement_timeout = 15000; SET LOCAL lock_timeout = '30s'; SET LOCAL work_mem = '4MB'"
+    );
+}
+
+#[test]
+fn test_index_columns_keep_names_intact() {
+    use crate::models::SortDirection;
+
+    let columns = batch_collection::index_columns(
+        vec!["a,b".to_string(), "Created At".to_string()],
+        vec![false, true],
+    );
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].name, "a,b");
+    assert_eq!(columns[0].sort_order, Some(SortDirection::Ascending));
+    assert_eq!(columns[1].name, "Created At");
+    assert_eq!(columns[1].sort_order, Some(SortDirection::Descending));
+}
+
+#[test]
+fn test_map_column_storage() {
+    use super::type_mapping::map_column_storage;
+
+    assert_eq!(
+        map_column_storage(Some("x"), Some("l")),
+        Some(ColumnStorage {
+            strategy: StorageStrategy::Extended,
+            compression: Some("lz4".to_string()),
+        })
+    );
+    assert_eq!(
+        map_column_storage(Some("e"), None),
+        Some(ColumnStorage {
+            strategy: StorageStrategy::External,
+            compression: None,
+        })
+    );
+    assert_eq!(
+        map_column_storage(Some("m"), Some("p")).map(|s| (s.strategy, s.compression)),
+        Some((StorageStrategy::Main, Some("pglz".to_string())))
+    );
+    assert_eq!(map_column_storage(None, None), None);
+}
+
+#[test]
+fn test_map_column_identity() {
+    use super::type_mapping::map_column_identity;
+
+    let sequence = || Some("public.orders_id_seq".to_string());
+    let always =
+        map_column_identity(true, Some("ALWAYS"), None, sequence()).expect("identity column");
+    assert_eq!(always.generation, IdentityGeneration::Always);
+    assert_eq!(always.sequence.as_deref(), Some("public.orders_id_seq"));
+    assert_eq!(
+        map_column_identity(true, Some("BY DEFAULT"), None, sequence()).map(|i| i.generation),
+        Some(IdentityGeneration::ByDefault)
+    );
+
+    let nextval = DefaultValue::SequenceNextval {
+        sequence: "orders_id_seq".to_string(),
+    };
+    assert_eq!(
+        map_column_identity(false, None, Some(&nextval), sequence()).map(|i| i.generation),
+        Some(IdentityGeneration::Serial)
+    );
+    // A default on a sequence the column does not own is not serial
+    assert_eq!(map_column_identity(false, None, Some(&nextval), None), None);
+    assert_eq!(map_column_identity(false, None, None, None), None);
+}
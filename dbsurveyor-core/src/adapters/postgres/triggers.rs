@@ -89,6 +89,71 @@ pub async fn collect_triggers(pool: &PgPool) -> Result<Vec<Trigger>> {
     Ok(triggers)
 }
 
+/// Collects triggers from `information_schema.triggers`, for roles denied
+/// access to `pg_trigger`.
+///
+/// PostgreSQL only lists triggers on tables the role owns or holds a
+/// privilege other than `SELECT` on, and the view has no full definition.
+/// A trigger appears once per event; the first of INSERT, UPDATE, and
+/// DELETE is kept, as for `pg_trigger`.
+pub async fn collect_triggers_from_information_schema(pool: &PgPool) -> Result<Vec<Trigger>> {
+    let triggers_query = r#"
+        SELECT
+            t.trigger_name::text as trigger_name,
+            t.event_object_table::text as table_name,
+            t.trigger_schema::text as schema_name,
+            t.event_manipulation::text as event,
+            t.action_timing::text as timing
+        FROM information_schema.triggers t
+        WHERE t.trigger_schema NOT IN ('pg_catalog', 'information_schema')
+        ORDER BY t.trigger_schema, t.event_object_table, t.trigger_name,
+            array_position(ARRAY['INSERT', 'UPDATE', 'DELETE'], t.event_manipulation::text)
+    "#;
+
+    let rows = sqlx::query(triggers_query)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate triggers from information_schema",
+                e,
+            )
+        })?;
+
+    let mut triggers: Vec<Trigger> = Vec::new();
+    for row in &rows {
+        let context = Some("information_schema.triggers");
+        let name: String = row.get_field("trigger_name", context)?;
+        let table_name: String = row.get_field("table_name", context)?;
+        let schema: Option<String> = row.get_field("schema_name", context)?;
+        if triggers.last().is_some_and(|last| {
+            last.name == name && last.table_name == table_name && last.schema == schema
+        }) {
+            continue;
+        }
+
+        let event: String = row.get_field("event", context)?;
+        let timing: String = row.get_field("timing", context)?;
+        triggers.push(Trigger {
+            name,
+            table_name,
+            schema,
+            event: match event.as_str() {
+                "UPDATE" => TriggerEvent::Update,
+                "DELETE" => TriggerEvent::Delete,
+                _ => TriggerEvent::Insert,
+            },
+            timing: match timing.as_str() {
+                "BEFORE" => TriggerTiming::Before,
+                "INSTEAD OF" => TriggerTiming::InsteadOf,
+                _ => TriggerTiming::After,
+            },
+            definition: None,
+        });
+    }
+    Ok(triggers)
+}
+
 /// Parses PostgreSQL trigger type bitmask to timing and event.
 ///
 /// PostgreSQL stores trigger configuration as a bitmask (tgtype) with the following bits:
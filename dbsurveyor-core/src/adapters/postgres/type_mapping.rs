@@ -4,10 +4,28 @@
 //! and the unified type system used by DBSurveyor.
 
 use crate::Result;
-use crate::models::{ReferentialAction, UnifiedDataType};
+use crate::models::{
+    ColumnIdentity, ColumnStorage, DefaultValue, IdentityGeneration, ReferentialAction,
+    StorageStrategy, UnifiedDataType,
+};
 
 use super::PostgresAdapter;
 
+/// `money` is a 64-bit integer count of the currency's minor unit; two
+/// fractional digits is the scale under nearly every `lc_monetary` setting.
+pub(super) const MONEY: UnifiedDataType = UnifiedDataType::Decimal {
+    precision: Some(19),
+    scale: Some(2),
+};
+
+/// Maps `numeric(p, s)`; unconstrained numerics have neither value.
+fn decimal_type(precision: Option<i32>, scale: Option<i32>) -> UnifiedDataType {
+    UnifiedDataType::Decimal {
+        precision: precision.and_then(|p| u32::try_from(p).ok()),
+        scale: scale.and_then(|s| u32::try_from(s).ok()),
+    }
+}
+
 impl PostgresAdapter {
     /// Maps PostgreSQL data types to unified data types (internal version).
     ///
@@ -60,28 +78,8 @@ pub(crate) fn map_postgres_type_to_unified(
             "double precision" | "float8" => UnifiedDataType::Float {
                 precision: Some(53),
             },
-            "numeric" | "decimal" => {
-                if let Some(scale) = numeric_scale {
-                    if scale == 0 {
-                        // No decimal places - treat as integer
-                        let bits = match numeric_precision {
-                            Some(p) if p <= 4 => 16,
-                            Some(p) if p <= 9 => 32,
-                            _ => 64,
-                        };
-                        UnifiedDataType::Integer { bits, signed: true }
-                    } else {
-                        // Has decimal places - treat as float
-                        UnifiedDataType::Float {
-                            precision: numeric_precision.map(|p| p as u8),
-                        }
-                    }
-                } else {
-                    UnifiedDataType::Float {
-                        precision: numeric_precision.map(|p| p as u8),
-                    }
-                }
-            }
+            "numeric" | "decimal" => decimal_type(numeric_precision, numeric_scale),
+            "money" => MONEY,
 
             // Boolean type
             "boolean" | "bool" => UnifiedDataType::Boolean,
@@ -100,9 +98,14 @@ pub(crate) fn map_postgres_type_to_unified(
             "time with time zone" | "timetz" => UnifiedDataType::Time {
                 with_timezone: true,
             },
+            "interval" => UnifiedDataType::Interval,
 
             // Binary types
             "bytea" => UnifiedDataType::Binary { max_length: None },
+            // character_maximum_length holds the length in bits for bit strings
+            "bit" | "bit varying" | "varbit" => UnifiedDataType::Bit {
+                length: character_maximum_length.and_then(|l| u32::try_from(l).ok()),
+            },
 
             // JSON types
             "json" => UnifiedDataType::Json,
@@ -134,10 +137,9 @@ pub(crate) fn map_postgres_type_to_unified(
                 }
             }
 
+            "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::NetworkAddress,
+
             // PostgreSQL-specific types that map to custom
-            "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::Custom {
-                type_name: udt_name.to_string(),
-            },
             "point" | "line" | "lseg" | "box" | "path" | "polygon" | "circle" => {
                 UnifiedDataType::Custom {
                     type_name: udt_name.to_string(),
@@ -157,9 +159,7 @@ pub(crate) fn map_postgres_type_to_unified(
                     "uuid" => UnifiedDataType::Uuid,
                     "json" => UnifiedDataType::Json,
                     "jsonb" => UnifiedDataType::Json,
-                    "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::Custom {
-                        type_name: udt_name.to_string(),
-                    },
+                    "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::NetworkAddress,
                     _ => {
                         // Assume it's an enum or custom type
                         UnifiedDataType::Custom {
@@ -203,8 +203,8 @@ pub(crate) fn map_postgres_type_to_unified(
 pub fn map_postgresql_type(
     pg_type: &str,
     char_max_length: Option<i32>,
-    _numeric_precision: Option<i32>,
-    _numeric_scale: Option<i32>,
+    numeric_precision: Option<i32>,
+    numeric_scale: Option<i32>,
 ) -> Result<UnifiedDataType> {
     let unified_type = match pg_type {
         // String types
@@ -227,6 +227,10 @@ pub fn map_postgresql_type(
             signed: true,
         },
 
+        // Exact numeric types
+        "numeric" | "decimal" => decimal_type(numeric_precision, numeric_scale),
+        "money" => MONEY,
+
         // Boolean type
         "boolean" | "bool" => UnifiedDataType::Boolean,
 
@@ -244,6 +248,7 @@ pub fn map_postgresql_type(
         "time with time zone" | "timetz" => UnifiedDataType::Time {
             with_timezone: true,
         },
+        "interval" => UnifiedDataType::Interval,
 
         // JSON types
         "json" | "jsonb" => UnifiedDataType::Json,
@@ -251,8 +256,14 @@ pub fn map_postgresql_type(
         // UUID type
         "uuid" => UnifiedDataType::Uuid,
 
-        // Binary type
+        // Network address types
+        "inet" | "cidr" | "macaddr" | "macaddr8" => UnifiedDataType::NetworkAddress,
+
+        // Binary types
         "bytea" => UnifiedDataType::Binary { max_length: None },
+        "bit" | "bit varying" | "varbit" => UnifiedDataType::Bit {
+            length: char_max_length.and_then(|l| u32::try_from(l).ok()),
+        },
 
         // Array types (simplified detection)
         t if t.ends_with("[]") => {
@@ -297,3 +308,61 @@ pub fn map_referential_action(action_rule: &str) -> Option<ReferentialAction> {
         }
     }
 }
+
+/// Maps `pg_attribute.attstorage` and `attcompression` codes to a column's
+/// storage. Returns `None` for fixed-length columns, which the catalog query
+/// reports without a strategy, and for unknown codes.
+pub(super) fn map_column_storage(
+    strategy: Option<&str>,
+    compression: Option<&str>,
+) -> Option<ColumnStorage> {
+    let strategy = match strategy? {
+        "p" => StorageStrategy::Plain,
+        "m" => StorageStrategy::Main,
+        "e" => StorageStrategy::External,
+        "x" => StorageStrategy::Extended,
+        other => {
+            tracing::warn!("Unknown column storage strategy: '{}'", other);
+            return None;
+        }
+    };
+    let compression = compression.map(|method| match method {
+        "p" => "pglz".to_string(),
+        "l" => "lz4".to_string(),
+        other => other.to_string(),
+    });
+    Some(ColumnStorage {
+        strategy,
+        compression,
+    })
+}
+
+/// Distinguishes identity columns from legacy `serial` columns. A `nextval()`
+/// default counts as serial only when the column owns the sequence, as
+/// `serial` arranges; a default on a shared sequence is left as a plain
+/// default.
+pub(super) fn map_column_identity(
+    is_identity: bool,
+    identity_generation: Option<&str>,
+    default: Option<&DefaultValue>,
+    owned_sequence: Option<String>,
+) -> Option<ColumnIdentity> {
+    let generation = if is_identity {
+        match identity_generation {
+            Some(generation) if generation.eq_ignore_ascii_case("ALWAYS") => {
+                IdentityGeneration::Always
+            }
+            _ => IdentityGeneration::ByDefault,
+        }
+    } else if matches!(default, Some(DefaultValue::SequenceNextval { .. }))
+        && owned_sequence.is_some()
+    {
+        IdentityGeneration::Serial
+    } else {
+        return None;
+    };
+    Some(ColumnIdentity {
+        generation,
+        sequence: owned_sequence,
+    })
+}
@@ -5,6 +5,7 @@
 
 use super::RowExt;
 use crate::Result;
+use crate::defaults::classify_default;
 use crate::models::{Column, View};
 use sqlx::PgPool;
 
@@ -54,7 +55,8 @@ pub async fn collect_views(pool: &PgPool) -> Result<Vec<View>> {
         let comment: Option<String> = row.get_field("view_comment", Some("pg_views"))?;
 
         // Collect view columns
-        let columns = collect_view_columns(pool, &view_name, &schema_name).await?;
+        let columns =
+            collect_view_columns(pool, VIEW_COLUMNS_QUERY, &view_name, &schema_name).await?;
 
         views.push(View {
             name: view_name.clone(),
@@ -75,10 +77,98 @@ pub async fn collect_views(pool: &PgPool) -> Result<Vec<View>> {
     Ok(views)
 }
 
+/// Collects views from `information_schema` alone, for roles denied access
+/// to `pg_views` or `pg_class`.
+///
+/// Comments are not available there, and PostgreSQL only shows the
+/// definitions of views the role owns.
+pub async fn collect_views_from_information_schema(pool: &PgPool) -> Result<Vec<View>> {
+    let views_query = r#"
+        SELECT
+            v.table_schema::text as schema_name,
+            v.table_name::text as view_name,
+            v.view_definition::text as view_definition
+        FROM information_schema.views v
+        WHERE v.table_schema NOT IN ('pg_catalog', 'information_schema')
+        ORDER BY v.table_schema, v.table_name
+    "#;
+
+    let view_rows = sqlx::query(views_query)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed(
+                "Failed to enumerate views from information_schema",
+                e,
+            )
+        })?;
+
+    let mut views = Vec::with_capacity(view_rows.len());
+    for row in &view_rows {
+        let schema_name: Option<String> =
+            row.get_field("schema_name", Some("information_schema.views"))?;
+        let view_name: String = row.get_field("view_name", Some("information_schema.views"))?;
+        let definition: Option<String> =
+            row.get_field("view_definition", Some("information_schema.views"))?;
+        let columns =
+            collect_view_columns(pool, VIEW_COLUMNS_FALLBACK_QUERY, &view_name, &schema_name)
+                .await?;
+
+        views.push(View {
+            name: view_name,
+            schema: schema_name,
+            definition,
+            columns,
+            comment: None,
+        });
+    }
+    Ok(views)
+}
+
+const VIEW_COLUMNS_QUERY: &str = r#"
+    SELECT
+        c.column_name::text,
+        c.data_type::text,
+        c.udt_name::text,
+        c.character_maximum_length,
+        c.numeric_precision,
+        c.numeric_scale,
+        c.is_nullable::text,
+        c.column_default::text,
+        c.ordinal_position::integer,
+        col_description(pgc.oid, c.ordinal_position)::text as column_comment
+    FROM information_schema.columns c
+    LEFT JOIN pg_class pgc ON pgc.relname = c.table_name
+    LEFT JOIN pg_namespace pgn ON pgn.nspname = c.table_schema AND pgc.relnamespace = pgn.oid
+    WHERE c.table_name = $1
+    AND c.table_schema = $2
+    ORDER BY c.ordinal_position
+"#;
+
+/// [`VIEW_COLUMNS_QUERY`] without the `pg_class` join for comments.
+const VIEW_COLUMNS_FALLBACK_QUERY: &str = r#"
+    SELECT
+        c.column_name::text,
+        c.data_type::text,
+        c.udt_name::text,
+        c.character_maximum_length,
+        c.numeric_precision,
+        c.numeric_scale,
+        c.is_nullable::text,
+        c.column_default::text,
+        c.ordinal_position::integer,
+        NULL::text as column_comment
+    FROM information_schema.columns c
+    WHERE c.table_name = $1
+    AND c.table_schema = $2
+    ORDER BY c.ordinal_position
+"#;
+
 /// Collects column metadata for a specific view.
 ///
 /// # Arguments
 /// * `pool` - PostgreSQL connection pool
+/// * `columns_query` - [`VIEW_COLUMNS_QUERY`] or its fallback
 /// * `view_name` - Name of the view
 /// * `schema_name` - Optional schema name
 ///
@@ -86,31 +176,12 @@ pub async fn collect_views(pool: &PgPool) -> Result<Vec<View>> {
 /// A vector of `Column` structs for the view.
 async fn collect_view_columns(
     pool: &PgPool,
+    columns_query: &str,
     view_name: &str,
     schema_name: &Option<String>,
 ) -> Result<Vec<Column>> {
     let schema = schema_name.as_deref().unwrap_or("public");
 
-    let columns_query = r#"
-        SELECT
-            c.column_name::text,
-            c.data_type::text,
-            c.udt_name::text,
-            c.character_maximum_length,
-            c.numeric_precision,
-            c.numeric_scale,
-            c.is_nullable::text,
-            c.column_default::text,
-            c.ordinal_position::integer,
-            col_description(pgc.oid, c.ordinal_position)::text as column_comment
-        FROM information_schema.columns c
-        LEFT JOIN pg_class pgc ON pgc.relname = c.table_name
-        LEFT JOIN pg_namespace pgn ON pgn.nspname = c.table_schema AND pgc.relnamespace = pgn.oid
-        WHERE c.table_name = $1
-        AND c.table_schema = $2
-        ORDER BY c.ordinal_position
-    "#;
-
     let column_rows = sqlx::query(columns_query)
         .bind(view_name)
         .bind(schema)
@@ -157,9 +228,17 @@ async fn collect_view_columns(
             is_nullable: is_nullable == "YES",
             is_primary_key: false, // Views don't have primary keys
             is_auto_increment: false,
+            normalized_default: column_default.as_deref().map(classify_default),
             default_value: column_default,
             comment: column_comment,
             ordinal_position: u32::try_from(ordinal_position).unwrap_or(0),
+            generated: None,
+            declared_type: None,
+            collation: None,
+            character_set: None,
+            storage: None,
+            identity: None,
+            masking: None,
         });
     }
 
@@ -0,0 +1,201 @@
+//! Attached database support for SQLite.
+//!
+//! Applications often split data across several SQLite files and combine them
+//! at runtime with `ATTACH DATABASE`. This module describes such additional
+//! files so the adapter can attach them read-only to every pooled connection
+//! and collect each one as a separate logical database.
+//!
+//! # Security Features
+//! - Attached files are opened with `mode=ro` via a SQLite URI filename
+//! - Schema names are restricted to plain identifiers
+//! - Files must already exist; nothing is ever created on disk
+
+use crate::Result;
+use crate::error::DbSurveyorError;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Schema names reserved by SQLite that cannot be used for attachments.
+const RESERVED_SCHEMA_NAMES: &[&str] = &["main", "temp"];
+
+/// An additional SQLite database file attached under its own schema name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachedDatabase {
+    /// Schema name used in `ATTACH DATABASE ... AS <name>`
+    pub name: String,
+    /// Path to the database file
+    pub path: PathBuf,
+}
+
+impl AttachedDatabase {
+    /// Creates a new attachment after validating the schema name.
+    ///
+    /// # Errors
+    /// Returns error if the name is empty, reserved (`main`, `temp`), or
+    /// contains characters other than ASCII letters, digits, and underscores.
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Result<Self> {
+        let name = name.into();
+        validate_schema_name(&name)?;
+
+        let path = path.into();
+        if path.as_os_str().is_empty() {
+            return Err(DbSurveyorError::configuration(format!(
+                "Attached database '{}' requires a file path",
+                name
+            )));
+        }
+
+        Ok(Self { name, path })
+    }
+
+    /// Returns the read-only SQLite URI filename used to attach this database.
+    pub(crate) fn uri(&self) -> String {
+        format!("file:{}?mode=ro", encode_uri_path(&self.path))
+    }
+}
+
+impl FromStr for AttachedDatabase {
+    type Err = DbSurveyorError;
+
+    /// Parses a `name=path` specification as accepted by `--attach`.
+    fn from_str(spec: &str) -> Result<Self> {
+        let (name, path) = spec.split_once('=').ok_or_else(|| {
+            DbSurveyorError::configuration(format!(
+                "Invalid attach specification '{}': expected NAME=PATH",
+                spec
+            ))
+        })?;
+
+        Self::new(name.trim(), path.trim())
+    }
+}
+
+/// Validates a list of attachments as a whole.
+///
+/// Checks that schema names are unique (SQLite compares them
+/// case-insensitively) and that every file exists.
+pub(crate) fn validate_attachments(attachments: &[AttachedDatabase]) -> Result<()> {
+    for (index, attachment) in attachments.iter().enumerate() {
+        if attachments[..index]
+            .iter()
+            .any(|other| other.name.eq_ignore_ascii_case(&attachment.name))
+        {
+            return Err(DbSurveyorError::configuration(format!(
+                "Attached database name '{}' is used more than once",
+                attachment.name
+            )));
+        }
+
+        if !attachment.path.is_file() {
+            return Err(DbSurveyorError::configuration(format!(
+                "Attached database '{}' not found: {}",
+                attachment.name,
+                attachment.path.display()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_schema_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if !valid {
+        return Err(DbSurveyorError::configuration(format!(
+            "Invalid attached database name '{}': use letters, digits, and underscores",
+            name
+        )));
+    }
+
+    if RESERVED_SCHEMA_NAMES
+        .iter()
+        .any(|reserved| name.eq_ignore_ascii_case(reserved))
+    {
+        return Err(DbSurveyorError::configuration(format!(
+            "Attached database name '{}' is reserved by SQLite",
+            name
+        )));
+    }
+
+    Ok(())
+}
+
+/// Percent-encodes the characters that have special meaning in SQLite URI filenames.
+fn encode_uri_path(path: &Path) -> String {
+    let raw = path.to_string_lossy();
+    let mut encoded = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '%' => encoded.push_str("%25"),
+            '?' => encoded.push_str("%3F"),
+            '#' => encoded.push_str("%23"),
+            _ => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_attach_spec() {
+        let attachment: AttachedDatabase = "archive=/data/archive.db".parse().unwrap();
+        assert_eq!(attachment.name, "archive");
+        assert_eq!(attachment.path, PathBuf::from("/data/archive.db"));
+    }
+
+    #[test]
+    fn test_parse_attach_spec_keeps_equals_in_path() {
+        let attachment: AttachedDatabase = "logs=./a=b.db".parse().unwrap();
+        assert_eq!(attachment.name, "logs");
+        assert_eq!(attachment.path, PathBuf::from("./a=b.db"));
+    }
+
+    #[test]
+    fn test_parse_attach_spec_invalid() {
+        assert!("archive".parse::<AttachedDatabase>().is_err());
+        assert!("=/data/archive.db".parse::<AttachedDatabase>().is_err());
+        assert!("archive=".parse::<AttachedDatabase>().is_err());
+        assert!("1st=/data/a.db".parse::<AttachedDatabase>().is_err());
+        assert!("my-db=/data/a.db".parse::<AttachedDatabase>().is_err());
+        assert!("\"x\"=/data/a.db".parse::<AttachedDatabase>().is_err());
+    }
+
+    #[test]
+    fn test_reserved_schema_names_rejected() {
+        assert!("main=/data/a.db".parse::<AttachedDatabase>().is_err());
+        assert!("TEMP=/data/a.db".parse::<AttachedDatabase>().is_err());
+    }
+
+    #[test]
+    fn test_uri_is_read_only_and_encoded() {
+        let attachment = AttachedDatabase::new("odd", "/data/what?#%.db").unwrap();
+        assert_eq!(attachment.uri(), "file:/data/what%3F%23%25.db?mode=ro");
+    }
+
+    #[test]
+    fn test_validate_attachments_rejects_duplicates_and_missing_files() {
+        let missing = AttachedDatabase::new("archive", "/nonexistent/archive.db").unwrap();
+        assert!(validate_attachments(std::slice::from_ref(&missing)).is_err());
+
+        let temp_dir =
+            std::env::temp_dir().join(format!("dbsurveyor_attach_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let db_path = temp_dir.join("archive.db");
+        std::fs::write(&db_path, b"").unwrap();
+
+        let first = AttachedDatabase::new("a", &db_path).unwrap();
+        let second = AttachedDatabase::new("A", &db_path).unwrap();
+        assert!(validate_attachments(std::slice::from_ref(&first)).is_ok());
+        assert!(validate_attachments(&[first, second]).is_err());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
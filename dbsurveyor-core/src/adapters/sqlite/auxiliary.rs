@@ -0,0 +1,160 @@
+//! Detection of the `-wal`, `-shm`, and `-journal` files SQLite keeps beside
+//! a database file.
+//!
+//! Acquired database files often arrive with these siblings, and they matter:
+//! a write-ahead log can hold committed changes that are not yet in the main
+//! file, and a hot rollback journal holds an interrupted transaction that
+//! SQLite rolls back into the main file when it opens it for writing. Both
+//! are inspected before the database is opened, without opening either.
+
+use crate::Result;
+use crate::models::{AuxiliaryFile, AuxiliaryFileKind};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Magic number at the start of a rollback journal whose transaction has
+/// not been committed or rolled back.
+const JOURNAL_MAGIC: [u8; 8] = [0xd9, 0xd5, 0x05, 0xf9, 0x20, 0xa1, 0x63, 0xd7];
+
+/// The database file a SQLite connection string refers to, or `None` for an
+/// in-memory database.
+pub fn database_file_path(connection_string: &str) -> Option<PathBuf> {
+    if connection_string.contains(":memory:") || connection_string.contains("mode=memory") {
+        return None;
+    }
+
+    if let Some(stripped) = connection_string
+        .strip_prefix("sqlite://")
+        .or_else(|| connection_string.strip_prefix("sqlite:"))
+    {
+        let path = stripped.split('?').next().unwrap_or(stripped);
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+
+    if connection_string.contains("://") {
+        return None;
+    }
+
+    if connection_string.ends_with(".db")
+        || connection_string.ends_with(".sqlite")
+        || connection_string.ends_with(".sqlite3")
+    {
+        return Some(PathBuf::from(connection_string));
+    }
+
+    None
+}
+
+/// Lists the auxiliary files present beside `database_path`.
+///
+/// A rollback journal is marked hot when it starts with the journal magic
+/// number; journals left behind by `PERSIST` or `TRUNCATE` journal modes
+/// have a zeroed header or no content and are not.
+pub fn find_auxiliary_files(database_path: &Path) -> Vec<AuxiliaryFile> {
+    let mut files = Vec::new();
+    for kind in [
+        AuxiliaryFileKind::Wal,
+        AuxiliaryFileKind::Shm,
+        AuxiliaryFileKind::Journal,
+    ] {
+        let mut path = database_path.as_os_str().to_owned();
+        path.push(kind.suffix());
+        let path = PathBuf::from(path);
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        files.push(AuxiliaryFile {
+            kind,
+            file_name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size_bytes: metadata.len(),
+            hot: kind == AuxiliaryFileKind::Journal && has_journal_header(&path),
+        });
+    }
+    files
+}
+
+fn has_journal_header(path: &Path) -> bool {
+    let mut header = [0u8; JOURNAL_MAGIC.len()];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| header == JOURNAL_MAGIC)
+}
+
+/// Refuses a database whose rollback journal is hot.
+///
+/// # Errors
+/// Returns a configuration error naming the hot journal.
+pub fn ensure_no_hot_journal(files: &[AuxiliaryFile]) -> Result<()> {
+    match files.iter().find(|file| file.hot) {
+        Some(journal) => Err(crate::error::DbSurveyorError::configuration(format!(
+            "{} is a hot journal: opening the database could roll back an interrupted transaction into the file. Survey a copy, or pass --force to open it anyway",
+            journal.file_name
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_file_path() {
+        assert_eq!(
+            database_file_path("sqlite:///data/app.db?mode=ro"),
+            Some(PathBuf::from("/data/app.db"))
+        );
+        assert_eq!(
+            database_file_path("sqlite:app.sqlite"),
+            Some(PathBuf::from("app.sqlite"))
+        );
+        assert_eq!(
+            database_file_path("/data/app.sqlite3"),
+            Some(PathBuf::from("/data/app.sqlite3"))
+        );
+        assert_eq!(database_file_path("sqlite::memory:"), None);
+        assert_eq!(database_file_path("postgres://localhost/db"), None);
+        assert_eq!(database_file_path("mysql://localhost/legacy.db"), None);
+    }
+
+    #[test]
+    fn test_find_auxiliary_files_marks_hot_journal() {
+        let dir = std::env::temp_dir().join(format!("dbsurveyor_aux_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let database = dir.join("evidence.db");
+        std::fs::write(&database, b"").unwrap();
+        assert!(find_auxiliary_files(&database).is_empty());
+
+        std::fs::write(dir.join("evidence.db-wal"), [0u8; 32]).unwrap();
+        let mut journal = JOURNAL_MAGIC.to_vec();
+        journal.extend([0u8; 504]);
+        std::fs::write(dir.join("evidence.db-journal"), &journal).unwrap();
+
+        let files = find_auxiliary_files(&database);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].kind, AuxiliaryFileKind::Wal);
+        assert_eq!(files[0].file_name, "evidence.db-wal");
+        assert_eq!(files[0].size_bytes, 32);
+        assert!(!files[0].hot);
+        assert_eq!(files[1].kind, AuxiliaryFileKind::Journal);
+        assert!(files[1].hot);
+
+        let error = ensure_no_hot_journal(&files).expect_err("hot journal is refused");
+        assert!(error.to_string().contains("evidence.db-journal"));
+
+        // A persisted journal with a zeroed header is not hot
+        std::fs::write(dir.join("evidence.db-journal"), [0u8; 512]).unwrap();
+        let files = find_auxiliary_files(&database);
+        assert!(!files[1].hot);
+        assert!(ensure_no_hot_journal(&files).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -6,13 +6,15 @@
 //! # Connection Modes
 //! - File-based: `sqlite:///path/to/database.db` or `sqlite://./relative.db`
 //! - In-memory: `sqlite::memory:` or `:memory:`
+//! - Attached: additional read-only files via [`SqliteAdapter::with_attachments`]
 //!
 //! # Security Features
 //! - Opens databases in read-only mode by default
 //! - No network access required
 //! - File path validation
 
-use super::{ConnectionConfig, SqliteAdapter};
+use super::attach::{AttachedDatabase, validate_attachments};
+use super::{ConnectionConfig, ObjectSelection, SqliteAdapter, escape_identifier};
 use crate::Result;
 use sqlx::SqlitePool;
 use url::Url;
@@ -40,12 +42,70 @@ impl SqliteAdapter {
     /// - Database cannot be opened
     pub async fn new(connection_string: &str) -> Result<Self> {
         let config = parse_sqlite_connection_config(connection_string)?;
-        let pool = create_sqlite_connection(connection_string, &config).await?;
+        let pool = create_sqlite_connection(connection_string, &config, &[]).await?;
 
         Ok(Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_string: Zeroizing::new(connection_string.to_string()),
+            attached: Vec::new(),
+        })
+    }
+
+    /// Creates a new SQLite adapter with additional database files attached.
+    ///
+    /// Each attachment is attached read-only to every pooled connection under
+    /// its own schema name, mirroring how applications combine multi-file
+    /// SQLite setups at runtime.
+    ///
+    /// # Arguments
+    /// * `connection_string` - SQLite connection URL or file path of the main database
+    /// * `attachments` - Additional database files to attach
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - Connection string format is invalid
+    /// - Attachment names are duplicated or an attached file does not exist
+    /// - Any database cannot be opened or attached
+    pub async fn with_attachments(
+        connection_string: &str,
+        attachments: Vec<AttachedDatabase>,
+    ) -> Result<Self> {
+        Self::connect(connection_string, attachments, false).await
+    }
+
+    /// Creates a new SQLite adapter that opens the main database and every
+    /// attachment as immutable (`immutable=1`).
+    ///
+    /// Immutable files are read without locks, hot journal recovery, or WAL
+    /// checkpointing, so surveying a seized or copied file cannot modify it.
+    /// Changes still in a write-ahead log are not read.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`SqliteAdapter::with_attachments`].
+    pub async fn immutable(
+        connection_string: &str,
+        attachments: Vec<AttachedDatabase>,
+    ) -> Result<Self> {
+        Self::connect(connection_string, attachments, true).await
+    }
+
+    async fn connect(
+        connection_string: &str,
+        attachments: Vec<AttachedDatabase>,
+        immutable: bool,
+    ) -> Result<Self> {
+        validate_attachments(&attachments)?;
+        let config = parse_sqlite_connection_config(connection_string)?.with_immutable(immutable);
+        let pool = create_sqlite_connection(connection_string, &config, &attachments).await?;
+
+        Ok(Self {
+            pool,
+            config,
+            objects: ObjectSelection::default(),
+            connection_string: Zeroizing::new(connection_string.to_string()),
+            attached: attachments,
         })
     }
 
@@ -57,12 +117,14 @@ pub async fn new(connection_string: &str) -> Result<Self> {
     pub async fn with_config(connection_string: &str, config: ConnectionConfig) -> Result<Self> {
         config.validate()?;
         validate_sqlite_connection_string(connection_string)?;
-        let pool = create_sqlite_connection(connection_string, &config).await?;
+        let pool = create_sqlite_connection(connection_string, &config, &[]).await?;
 
         Ok(Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_string: Zeroizing::new(connection_string.to_string()),
+            attached: Vec::new(),
         })
     }
 
@@ -73,7 +135,9 @@ pub fn from_pool(pool: SqlitePool, connection_string: &str, config: ConnectionCo
         Self {
             pool,
             config,
+            objects: ObjectSelection::default(),
             connection_string: Zeroizing::new(connection_string.to_string()),
+            attached: Vec::new(),
         }
     }
 
@@ -85,24 +149,8 @@ pub fn is_in_memory(&self) -> bool {
 
     /// Gets the database file path if using a file-based database.
     pub fn database_path(&self) -> Option<String> {
-        if self.is_in_memory() {
-            return None;
-        }
-
-        // Try to extract path from connection string
-        if let Some(stripped) = self.connection_string.strip_prefix("sqlite://") {
-            let path = stripped.split('?').next().unwrap_or(stripped);
-            if !path.is_empty() && path != ":memory:" {
-                return Some(path.to_string());
-            }
-        } else if self.connection_string.ends_with(".db")
-            || self.connection_string.ends_with(".sqlite")
-            || self.connection_string.ends_with(".sqlite3")
-        {
-            return Some((*self.connection_string).clone());
-        }
-
-        None
+        super::database_file_path(&self.connection_string)
+            .map(|path| path.to_string_lossy().into_owned())
     }
 
     /// Closes the connection gracefully.
@@ -226,9 +274,13 @@ fn extract_database_name(connection_string: &str) -> String {
 }
 
 /// Creates a SQLite connection with proper configuration.
-async fn create_sqlite_connection(
+///
+/// Attachments are applied in an `after_connect` hook so that every
+/// connection the pool opens sees the same set of attached databases.
+pub(super) async fn create_sqlite_connection(
     connection_string: &str,
     config: &ConnectionConfig,
+    attachments: &[AttachedDatabase],
 ) -> Result<SqlitePool> {
     use sqlx::sqlite::SqliteConnectOptions;
     use std::str::FromStr;
@@ -249,11 +301,41 @@ async fn create_sqlite_connection(
         options = options.read_only(true);
     }
 
+    // Immutable files are read without locks, journal recovery, or WAL
+    // checkpointing, so nothing can write to them
+    if config.immutable {
+        options = options.immutable(true);
+    }
+
+    // Attach additional databases read-only on every new connection
+    let attach_statements: Vec<(String, String)> = attachments
+        .iter()
+        .map(|a| {
+            (
+                format!("ATTACH DATABASE ? AS {}", escape_identifier(&a.name)),
+                if config.immutable {
+                    format!("{}&immutable=1", a.uri())
+                } else {
+                    a.uri()
+                },
+            )
+        })
+        .collect();
+
     // Create pool (SQLite pools are typically single-connection)
     let pool = sqlx::sqlite::SqlitePoolOptions::new()
         .max_connections(config.max_connections.max(1))
         .acquire_timeout(config.connect_timeout)
         .idle_timeout(config.idle_timeout)
+        .after_connect(move |conn, _meta| {
+            let attach_statements = attach_statements.clone();
+            Box::pin(async move {
+                for (statement, uri) in &attach_statements {
+                    sqlx::query(statement).bind(uri).execute(&mut *conn).await?;
+                }
+                Ok(())
+            })
+        })
         .connect_with(options)
         .await
         .map_err(|e| {
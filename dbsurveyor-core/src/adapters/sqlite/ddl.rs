@@ -0,0 +1,316 @@
+//! Lightweight parsing of the `CREATE` statements stored in `sqlite_master`.
+//!
+//! Some table properties (virtual table modules, `WITHOUT ROWID`, `STRICT`)
+//! are only recorded in the original DDL text. This module provides a small,
+//! quote- and comment-aware tokenizer sufficient to extract them; it is not a
+//! general SQL parser.
+
+use crate::models::TableOptions;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Token {
+    /// Bare keyword or identifier
+    Word(String),
+    /// Quoted identifier or string literal, with quotes removed
+    Quoted(String),
+    /// Raw text between a top-level `(` and its matching `)`
+    Group(String),
+    /// Any other single character
+    Symbol(char),
+}
+
+/// Splits a statement into coarse tokens; comments are skipped.
+pub(super) fn tokenize(sql: &str) -> Vec<Token> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if let Some(next) = skip_comment(&chars, i) {
+            i = next;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else if let Some(close) = closing_quote(c) {
+            let (text, next) = read_quoted(&chars, i + 1, close);
+            tokens.push(Token::Quoted(text));
+            i = next;
+        } else if c == '(' {
+            let start = i + 1;
+            let end = find_group_end(&chars, start);
+            tokens.push(Token::Group(chars[start..end].iter().collect()));
+            i = end + 1;
+        } else {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Splits a parenthesized list on commas that are not nested in parentheses,
+/// quotes, or comments. Items are trimmed and empty items dropped.
+pub(super) fn split_arguments(body: &str) -> Vec<String> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(close) = closing_quote(c) {
+            i = read_quoted(&chars, i + 1, close).1;
+            continue;
+        }
+        if let Some(next) = skip_comment(&chars, i) {
+            i = next;
+            continue;
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                arguments.push(chars[start..i].iter().collect::<String>());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    arguments.push(chars[start..].iter().collect::<String>());
+
+    arguments
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Extracts the `WITHOUT ROWID` and `STRICT` options that follow the column
+/// list of a `CREATE TABLE` statement.
+///
+/// Returns `None` when no option is declared (including `CREATE TABLE ... AS
+/// SELECT` and virtual tables), so ordinary tables carry no options.
+pub(crate) fn parse_table_options(sql: &str) -> Option<TableOptions> {
+    let tokens = tokenize(sql);
+    let column_list = tokens.iter().position(|t| matches!(t, Token::Group(_)))?;
+    let is_keyword =
+        |t: &Token, keyword: &str| matches!(t, Token::Word(w) if w.eq_ignore_ascii_case(keyword));
+    if tokens[..column_list]
+        .iter()
+        .any(|t| is_keyword(t, "AS") || is_keyword(t, "USING"))
+    {
+        return None;
+    }
+
+    let mut options = TableOptions::default();
+    let mut words = tokens[column_list + 1..].iter().filter_map(|t| match t {
+        Token::Word(w) => Some(w.to_uppercase()),
+        _ => None,
+    });
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "STRICT" => options.strict = true,
+            "WITHOUT" if words.next().as_deref() == Some("ROWID") => options.without_rowid = true,
+            _ => {}
+        }
+    }
+
+    (options != TableOptions::default()).then_some(options)
+}
+
+/// Extracts the `COLLATE` clause of each column definition in a `CREATE
+/// TABLE` statement, keyed by column name.
+///
+/// SQLite reports column collations only in the DDL. Columns without a
+/// clause use the `BINARY` default and are omitted.
+pub(crate) fn parse_column_collations(sql: &str) -> HashMap<String, String> {
+    let mut collations = HashMap::new();
+    let Some(Token::Group(body)) = tokenize(sql)
+        .into_iter()
+        .find(|t| matches!(t, Token::Group(_)))
+    else {
+        return collations;
+    };
+
+    for definition in split_arguments(&body) {
+        let tokens = tokenize(&definition);
+        let name = match tokens.first() {
+            Some(Token::Word(w))
+                if TABLE_CONSTRAINT_KEYWORDS
+                    .iter()
+                    .any(|k| w.eq_ignore_ascii_case(k)) =>
+            {
+                continue;
+            }
+            Some(Token::Word(w) | Token::Quoted(w)) => w.clone(),
+            _ => continue,
+        };
+        let collation = tokens.windows(2).find_map(|pair| match pair {
+            [Token::Word(keyword), Token::Word(c) | Token::Quoted(c)]
+                if keyword.eq_ignore_ascii_case("COLLATE") =>
+            {
+                Some(c.clone())
+            }
+            _ => None,
+        });
+        if let Some(collation) = collation {
+            collations.insert(name, collation);
+        }
+    }
+
+    collations
+}
+
+/// Keywords that start a table constraint rather than a column definition.
+const TABLE_CONSTRAINT_KEYWORDS: &[&str] = &["CONSTRAINT", "PRIMARY", "UNIQUE", "CHECK", "FOREIGN"];
+
+fn closing_quote(open: char) -> Option<char> {
+    match open {
+        '"' => Some('"'),
+        '\'' => Some('\''),
+        '`' => Some('`'),
+        '[' => Some(']'),
+        _ => None,
+    }
+}
+
+/// Returns the index after a `--` or `/* */` comment starting at `i`, if any.
+fn skip_comment(chars: &[char], i: usize) -> Option<usize> {
+    match (chars.get(i), chars.get(i + 1)) {
+        (Some('-'), Some('-')) => Some(
+            chars[i..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(chars.len(), |offset| i + offset + 1),
+        ),
+        (Some('/'), Some('*')) => Some(
+            chars[i + 2..]
+                .windows(2)
+                .position(|w| w == ['*', '/'])
+                .map_or(chars.len(), |offset| i + 2 + offset + 2),
+        ),
+        _ => None,
+    }
+}
+
+/// Reads a quoted token starting after the opening quote; doubled closing
+/// quotes are unescaped. Returns the text and the index after the closing quote.
+fn read_quoted(chars: &[char], mut i: usize, close: char) -> (String, usize) {
+    let mut text = String::new();
+    while i < chars.len() {
+        if chars[i] == close {
+            if close != ']' && chars.get(i + 1) == Some(&close) {
+                text.push(close);
+                i += 2;
+                continue;
+            }
+            return (text, i + 1);
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    (text, i)
+}
+
+/// Finds the index of the `)` matching a group that starts at `start`,
+/// ignoring parentheses inside quotes and comments. Returns `chars.len()` if
+/// unbalanced.
+fn find_group_end(chars: &[char], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(close) = closing_quote(c) {
+            i = read_quoted(chars, i + 1, close).1;
+            continue;
+        }
+        if let Some(next) = skip_comment(chars, i) {
+            i = next;
+            continue;
+        }
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return i,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_without_rowid_and_strict() {
+        let options = parse_table_options(
+            "CREATE TABLE kv (k TEXT PRIMARY KEY, v ANY) WITHOUT ROWID, STRICT",
+        )
+        .expect("options should be detected");
+        assert!(options.without_rowid);
+        assert!(options.strict);
+
+        let options = parse_table_options("create table t (id integer primary key) strict")
+            .expect("STRICT should be detected");
+        assert!(options.strict);
+        assert!(!options.without_rowid);
+    }
+
+    #[test]
+    fn test_plain_table_has_no_options() {
+        assert!(parse_table_options("CREATE TABLE t (id INTEGER, note TEXT)").is_none());
+        assert!(parse_table_options("CREATE TABLE t AS SELECT 1 AS strict").is_none());
+        assert!(
+            parse_table_options("CREATE VIRTUAL TABLE t USING fts5(a) ").is_none(),
+            "virtual tables have no storage options"
+        );
+    }
+
+    #[test]
+    fn test_options_in_columns_or_comments_ignored() {
+        assert!(
+            parse_table_options("CREATE TABLE t (\"strict\" TEXT, c TEXT DEFAULT 'WITHOUT ROWID')")
+                .is_none()
+        );
+        assert!(
+            parse_table_options("CREATE TABLE t (a TEXT -- odd ) comment\n) /* STRICT */")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_column_collations() {
+        let collations = parse_column_collations(
+            "CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                email TEXT COLLATE NOCASE NOT NULL,
+                \"display name\" TEXT COLLATE \"RTRIM\",
+                note TEXT DEFAULT 'COLLATE NOCASE', -- COLLATE BINARY
+                UNIQUE (email COLLATE NOCASE)
+            )",
+        );
+        assert_eq!(collations.len(), 2);
+        assert_eq!(collations["email"], "NOCASE");
+        assert_eq!(collations["display name"], "RTRIM");
+    }
+
+    #[test]
+    fn test_split_arguments_respects_nesting() {
+        assert_eq!(
+            split_arguments("a, b DECIMAL(10, 2), c TEXT DEFAULT ',' /* x, y */"),
+            vec!["a", "b DECIMAL(10, 2)", "c TEXT DEFAULT ',' /* x, y */"]
+        );
+    }
+}
@@ -2,14 +2,22 @@
 //!
 //! # Module Structure
 //! - `connection`: Connection handling (no pooling needed for SQLite)
+//! - `auxiliary`: Detection of `-wal`, `-shm`, and hot `-journal` files beside a database
+//! - `ddl`: Parsing of `CREATE` statements for options only recorded in DDL text
+//! - `integrity`: `PRAGMA quick_check`/`integrity_check` run before collection
+//! - `attach`: Additional database files attached read-only under their own schema names
 //! - `type_mapping`: SQLite to unified data type conversion
 //! - `schema_collection`: Table, column, constraint, and index collection via sqlite_master
 //! - `sampling`: Data sampling utilities and ordering strategy detection
+//! - `virtual_tables`: Virtual table module/argument parsing and shadow table detection
 //!
 //! # SQLite-Specific Features
 //! - Uses `sqlite_master` for schema introspection
 //! - Uses PRAGMA commands for detailed metadata
 //! - Supports both file-based and in-memory databases
+//! - Attached databases are collected as separate logical databases
+//! - Virtual tables (FTS, R*Tree, custom modules) are reported with their module
+//! - `WITHOUT ROWID` and `STRICT` tables and declared column types are preserved
 //! - No connection pooling (single connection is sufficient)
 //!
 //! # Security Guarantees
@@ -17,15 +25,20 @@
 //! - File paths are validated
 //! - No network access required
 
+mod attach;
+mod auxiliary;
 mod connection;
+mod ddl;
+mod integrity;
 mod sampling;
 mod schema_collection;
 mod type_mapping;
+mod virtual_tables;
 
 #[cfg(test)]
 mod tests;
 
-use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, TableRef};
+use super::{AdapterFeature, ConnectionConfig, DatabaseAdapter, ObjectSelection, TableRef};
 use crate::Result;
 use crate::models::*;
 use async_trait::async_trait;
@@ -33,6 +46,8 @@
 use zeroize::Zeroizing;
 
 // Re-export public items from submodules
+pub use attach::AttachedDatabase;
+pub use auxiliary::{database_file_path, ensure_no_hot_journal, find_auxiliary_files};
 pub use sampling::{detect_ordering_strategy, generate_order_by_clause, sample_table};
 pub use type_mapping::map_sqlite_type;
 
@@ -52,6 +67,32 @@ pub(crate) fn escape_pragma_arg(name: &str) -> String {
     format!("'{}'", name.replace('\'', "''"))
 }
 
+/// Qualifies a table name with an attached schema name (for use in DML statements).
+///
+/// `None` refers to the main database and leaves the name unqualified.
+pub(crate) fn qualified_table_name(schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", escape_identifier(schema), escape_identifier(table)),
+        None => escape_identifier(table),
+    }
+}
+
+/// Builds a single-argument PRAGMA statement, scoped to an attached schema when given.
+///
+/// For example `pragma_statement(Some("archive"), "table_info", "users")`
+/// yields `PRAGMA "archive".table_info('users')`.
+pub(crate) fn pragma_statement(schema: Option<&str>, pragma: &str, arg: &str) -> String {
+    match schema {
+        Some(schema) => format!(
+            "PRAGMA {}.{}({})",
+            escape_identifier(schema),
+            pragma,
+            escape_pragma_arg(arg)
+        ),
+        None => format!("PRAGMA {}({})", pragma, escape_pragma_arg(arg)),
+    }
+}
+
 /// SQLite database adapter with schema collection and data sampling.
 ///
 /// SQLite uses file-based databases, so this adapter works differently from
@@ -62,17 +103,26 @@ pub struct SqliteAdapter {
     pub pool: SqlitePool,
     /// Connection configuration
     pub config: ConnectionConfig,
+    /// Optional object types to collect
+    pub objects: ObjectSelection,
     /// Original connection string (kept for reference).
     /// Wrapped in `Zeroizing` so the connection string is scrubbed from
     /// memory when the adapter is dropped (CWE-316).
     pub(crate) connection_string: Zeroizing<String>,
+    /// Additional database files attached read-only to every connection
+    pub(crate) attached: Vec<AttachedDatabase>,
 }
 
 impl std::fmt::Debug for SqliteAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SqliteAdapter")
             .field("config", &self.config)
+            .field("objects", &self.objects)
             .field("is_in_memory", &self.is_in_memory())
+            .field(
+                "attached",
+                &self.attached.iter().map(|a| &a.name).collect::<Vec<_>>(),
+            )
             // Note: connection_string is intentionally omitted
             .finish_non_exhaustive()
     }
@@ -125,7 +175,13 @@ async fn sample_table(
         table_ref: TableRef<'_>,
         config: &super::SamplingConfig,
     ) -> Result<TableSample> {
-        sampling::sample_table(&self.pool, table_ref.table_name, config).await
+        sampling::sample_table_in_schema(
+            &self.pool,
+            table_ref.schema_name,
+            table_ref.table_name,
+            config,
+        )
+        .await
     }
 
     fn database_type(&self) -> DatabaseType {
@@ -142,16 +198,61 @@ fn supports_feature(&self, feature: AdapterFeature) -> bool {
         )
         // Note: SQLite does NOT support:
         // - ConnectionPooling (single connection is sufficient)
-        // - MultiDatabase (SQLite is single-database per file)
+        // - MultiDatabase (SQLite is single-database per file; attached
+        //   files are collected via `collect_server_schema` instead)
     }
 
     fn connection_config(&self) -> ConnectionConfig {
         self.config.clone()
     }
+
+    /// SQLite has no read-only setting to query; the open flags decide, and
+    /// a connection opened with `SQLITE_OPEN_READONLY` cannot write.
+    async fn verify_read_only(&self) -> Result<ReadOnlyVerification> {
+        Ok(if self.config.immutable {
+            ReadOnlyVerification::new(
+                ReadOnlyLevel::Session,
+                vec!["opened immutable (immutable=1)".to_string()],
+            )
+        } else if self.config.read_only {
+            ReadOnlyVerification::new(
+                ReadOnlyLevel::Session,
+                vec!["opened read-only (SQLITE_OPEN_READONLY)".to_string()],
+            )
+        } else {
+            ReadOnlyVerification::new(
+                ReadOnlyLevel::NotEnforced,
+                vec!["opened read-write".to_string()],
+            )
+        })
+    }
+
+    async fn check_integrity(
+        &self,
+        mode: IntegrityCheckMode,
+        timeout: Option<std::time::Duration>,
+    ) -> Option<IntegrityCheck> {
+        Some(self.check_database_integrity(None, mode, timeout).await)
+    }
 }
 
-// Additional SqliteAdapter methods for data sampling
+// Additional SqliteAdapter methods for attached databases and data sampling
 impl SqliteAdapter {
+    /// Returns the databases attached to this adapter's connections.
+    pub fn attached_databases(&self) -> &[AttachedDatabase] {
+        &self.attached
+    }
+
+    /// Collects the main database and every attached database as separate
+    /// logical databases of a single server schema.
+    ///
+    /// The main database must be collected successfully. An attached database
+    /// that fails is recorded with a failed collection status so that the
+    /// remaining databases are still reported.
+    pub async fn collect_server_schema(&self) -> Result<DatabaseServerSchema> {
+        schema_collection::collect_server_schema(self).await
+    }
+
     /// Detect the best ordering strategy for sampling a table.
     ///
     /// This method analyzes the table structure to determine the most reliable
@@ -12,11 +12,14 @@
 //! 4. ROWID (SQLite's built-in row identifier)
 //! 5. Fallback to unordered (uses RANDOM() for sampling)
 
-use super::{escape_identifier, escape_pragma_arg};
+use super::{escape_identifier, pragma_statement, qualified_table_name};
 use crate::adapters::config::SamplingConfig;
-use crate::adapters::helpers::TIMESTAMP_COLUMN_NAMES;
+use crate::adapters::helpers::{TIMESTAMP_COLUMN_NAMES, binary_sample_value, float_sample_value};
 use crate::error::DbSurveyorError;
-use crate::models::{OrderingStrategy, SampleStatus, SamplingStrategy, SortDirection, TableSample};
+use crate::models::{
+    OrderingStrategy, SampleColumnType, SampleStatus, SampleValueEncoding, SamplingStrategy,
+    SortDirection, TableSample,
+};
 use serde_json::Value as JsonValue;
 use sqlx::{Row, SqlitePool};
 use std::time::Duration;
@@ -25,9 +28,19 @@
 pub async fn detect_ordering_strategy(
     pool: &SqlitePool,
     table: &str,
+) -> Result<OrderingStrategy, DbSurveyorError> {
+    detect_ordering_strategy_in_schema(pool, None, table).await
+}
+
+/// Detect the best ordering strategy for a table in an attached schema
+/// (`None` for the main database).
+pub(crate) async fn detect_ordering_strategy_in_schema(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: &str,
 ) -> Result<OrderingStrategy, DbSurveyorError> {
     // 1. Check for primary key
-    if let Some(pk_strategy) = detect_primary_key(pool, table).await? {
+    if let Some(pk_strategy) = detect_primary_key(pool, schema, table).await? {
         tracing::debug!(
             "Detected primary key ordering for {}: {:?}",
             table,
@@ -37,7 +50,7 @@ pub async fn detect_ordering_strategy(
     }
 
     // 2. Check for timestamp columns
-    if let Some(ts_strategy) = detect_timestamp_column(pool, table).await? {
+    if let Some(ts_strategy) = detect_timestamp_column(pool, schema, table).await? {
         tracing::debug!(
             "Detected timestamp ordering for {}: {:?}",
             table,
@@ -47,7 +60,7 @@ pub async fn detect_ordering_strategy(
     }
 
     // 3. Check for auto-increment columns
-    if let Some(auto_strategy) = detect_auto_increment_column(pool, table).await? {
+    if let Some(auto_strategy) = detect_auto_increment_column(pool, schema, table).await? {
         tracing::debug!(
             "Detected auto-increment ordering for {}: {:?}",
             table,
@@ -57,7 +70,7 @@ pub async fn detect_ordering_strategy(
     }
 
     // 4. Check for ROWID (almost all SQLite tables have this)
-    if let Some(rowid_strategy) = detect_rowid(pool, table).await? {
+    if let Some(rowid_strategy) = detect_rowid(pool, schema, table).await? {
         tracing::debug!(
             "Detected ROWID ordering for {}: {:?}",
             table,
@@ -77,9 +90,10 @@ pub async fn detect_ordering_strategy(
 /// Detect primary key columns for ordering.
 async fn detect_primary_key(
     pool: &SqlitePool,
+    schema: Option<&str>,
     table: &str,
 ) -> Result<Option<OrderingStrategy>, DbSurveyorError> {
-    let pk_query = format!("PRAGMA table_info({})", escape_pragma_arg(table));
+    let pk_query = pragma_statement(schema, "table_info", table);
 
     let rows = sqlx::query(&pk_query).fetch_all(pool).await.map_err(|e| {
         DbSurveyorError::collection_failed(
@@ -116,9 +130,10 @@ async fn detect_primary_key(
 /// Detect timestamp columns suitable for ordering.
 async fn detect_timestamp_column(
     pool: &SqlitePool,
+    schema: Option<&str>,
     table: &str,
 ) -> Result<Option<OrderingStrategy>, DbSurveyorError> {
-    let columns_query = format!("PRAGMA table_info({})", escape_pragma_arg(table));
+    let columns_query = pragma_statement(schema, "table_info", table);
 
     let rows = sqlx::query(&columns_query)
         .fetch_all(pool)
@@ -186,9 +201,10 @@ async fn detect_timestamp_column(
 /// Detect auto-increment columns (INTEGER PRIMARY KEY in SQLite).
 async fn detect_auto_increment_column(
     pool: &SqlitePool,
+    schema: Option<&str>,
     table: &str,
 ) -> Result<Option<OrderingStrategy>, DbSurveyorError> {
-    let columns_query = format!("PRAGMA table_info({})", escape_pragma_arg(table));
+    let columns_query = pragma_statement(schema, "table_info", table);
 
     let rows = sqlx::query(&columns_query)
         .fetch_all(pool)
@@ -226,10 +242,14 @@ async fn detect_auto_increment_column(
 /// Detect if table has accessible ROWID.
 async fn detect_rowid(
     pool: &SqlitePool,
+    schema: Option<&str>,
     table: &str,
 ) -> Result<Option<OrderingStrategy>, DbSurveyorError> {
     // Try to query rowid to check if it exists
-    let test_query = format!("SELECT rowid FROM {} LIMIT 1", escape_identifier(table));
+    let test_query = format!(
+        "SELECT rowid FROM {} LIMIT 1",
+        qualified_table_name(schema, table)
+    );
 
     match sqlx::query(&test_query).fetch_optional(pool).await {
         Ok(_) => Ok(Some(OrderingStrategy::SystemRowId {
@@ -250,24 +270,41 @@ async fn detect_rowid(
 
 /// Build an explicit column projection for a SQLite table.
 ///
-/// Uses `PRAGMA table_info` to get every column name and returns a
+/// Uses `PRAGMA table_info` to get every column and returns a
 /// comma-separated, double-quote-escaped projection string (e.g.,
-/// `"col1", "col2", "col3"`). Falls back to `*` if the pragma fails
-/// so that sampling still works even for unusual table types.
-async fn build_column_projection(pool: &SqlitePool, table: &str) -> String {
-    let pragma = format!("PRAGMA table_info({})", escape_pragma_arg(table));
+/// `"col1", "col2", "col3"`) with the sample type legend derived from the
+/// declared types. Columns removed by a `skip-column` pattern are left out,
+/// leaving an empty projection if none remain. Falls back to `*` and an
+/// empty legend if the pragma fails so that sampling still works even for
+/// unusual table types.
+async fn build_column_projection(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: &str,
+    config: &SamplingConfig,
+) -> (String, Vec<SampleColumnType>) {
+    let pragma = pragma_statement(schema, "table_info", table);
 
     match sqlx::query(&pragma).fetch_all(pool).await {
         Ok(rows) if !rows.is_empty() => {
-            let cols: Vec<String> = rows
+            let mut legend: Vec<SampleColumnType> = rows
                 .iter()
-                .filter_map(|r| r.try_get::<String, _>("name").ok())
-                .map(|name| escape_identifier(&name))
+                .filter_map(|r| {
+                    let name: String = r.try_get("name").ok()?;
+                    let declared: String = r.try_get("type").unwrap_or_default();
+                    let (encoding, scale) = super::type_mapping::sample_encoding(&declared);
+                    Some(SampleColumnType::new(name, declared, encoding).with_scale(scale))
+                })
                 .collect();
-            if cols.is_empty() {
-                "*".to_string()
+            if legend.is_empty() {
+                ("*".to_string(), legend)
             } else {
-                cols.join(", ")
+                legend.retain(|column| !config.skips_column(&column.name));
+                let cols: Vec<String> = legend
+                    .iter()
+                    .map(|column| escape_identifier(&column.name))
+                    .collect();
+                (cols.join(", "), legend)
             }
         }
         _ => {
@@ -275,7 +312,7 @@ async fn build_column_projection(pool: &SqlitePool, table: &str) -> String {
                 "Could not fetch column names for '{}'; falling back to SELECT *",
                 table
             );
-            "*".to_string()
+            ("*".to_string(), Vec::new())
         }
     }
 }
@@ -313,6 +350,16 @@ pub async fn sample_table(
     pool: &SqlitePool,
     table: &str,
     config: &SamplingConfig,
+) -> Result<TableSample, DbSurveyorError> {
+    sample_table_in_schema(pool, None, table, config).await
+}
+
+/// Sample data from a table in an attached schema (`None` for the main database).
+pub(crate) async fn sample_table_in_schema(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: &str,
+    config: &SamplingConfig,
 ) -> Result<TableSample, DbSurveyorError> {
     config.validate()?;
     let mut warnings = Vec::new();
@@ -323,7 +370,7 @@ pub async fn sample_table(
     }
 
     // Detect ordering strategy
-    let strategy = detect_ordering_strategy(pool, table).await?;
+    let strategy = detect_ordering_strategy_in_schema(pool, schema, table).await?;
 
     // Generate ORDER BY clause
     let order_by = generate_order_by_clause(&strategy, true);
@@ -331,14 +378,20 @@ pub async fn sample_table(
     // Fetch column names so we can project explicitly instead of SELECT *.
     // This avoids fetching unnecessary BLOB/TEXT columns and gives the caller
     // control over which columns are transferred.
-    let projection = build_column_projection(pool, table).await;
+    let (projection, column_types) = build_column_projection(pool, schema, table, config).await;
+    if projection.is_empty() {
+        return Err(DbSurveyorError::configuration(format!(
+            "Every column of table '{}' matches a skip-column pattern",
+            table
+        )));
+    }
 
     // Build and execute the sample query.
     // Identifiers are escaped to prevent SQL injection from embedded quotes.
     let query = format!(
         "SELECT {} FROM {} {} LIMIT ?",
         projection,
-        escape_identifier(table),
+        qualified_table_name(schema, table),
         order_by
     );
 
@@ -356,14 +409,17 @@ pub async fn sample_table(
     // Convert rows to JSON
     let mut json_rows = Vec::with_capacity(rows.len());
     for row in &rows {
-        let json_row = row_to_json(row, config, &mut warnings)?;
+        let json_row = row_to_json(row, &column_types, config, &mut warnings)?;
         json_rows.push(json_row);
     }
 
     // Get estimated row count using MAX(rowid) which is O(1) for most SQLite tables.
     // This is an estimate (not exact if rows have been deleted), but avoids a full
     // table scan. Falls back to COUNT(*) only if the table has no rowid.
-    let estimate_query = format!("SELECT MAX(rowid) FROM {}", escape_identifier(table));
+    let estimate_query = format!(
+        "SELECT MAX(rowid) FROM {}",
+        qualified_table_name(schema, table)
+    );
     let total_rows: Option<i64> = match sqlx::query_scalar::<_, Option<i64>>(&estimate_query)
         .fetch_one(pool)
         .await
@@ -371,7 +427,10 @@ pub async fn sample_table(
         Ok(count) => Some(count.unwrap_or(0)),
         Err(_) => {
             // WITHOUT ROWID tables do not support MAX(rowid); fall back to COUNT(*).
-            let count_query = format!("SELECT COUNT(*) FROM {}", escape_identifier(table));
+            let count_query = format!(
+                "SELECT COUNT(*) FROM {}",
+                qualified_table_name(schema, table)
+            );
             match sqlx::query_scalar(&count_query).fetch_one(pool).await {
                 Ok(count) => Some(count),
                 Err(e) => {
@@ -409,7 +468,7 @@ pub async fn sample_table(
 
     Ok(TableSample {
         table_name: table.to_string(),
-        schema_name: None,
+        schema_name: schema.map(str::to_string),
         rows: json_rows,
         sample_size: u32::try_from(rows.len()).unwrap_or(u32::MAX),
         total_rows: total_rows.map(|t| t.max(0) as u64),
@@ -417,12 +476,14 @@ pub async fn sample_table(
         collected_at: chrono::Utc::now(),
         warnings,
         sample_status: Some(SampleStatus::Complete),
+        column_types,
     })
 }
 
 /// Convert a database row to JSON, checking for sensitive data patterns.
 fn row_to_json(
     row: &sqlx::sqlite::SqliteRow,
+    column_types: &[SampleColumnType],
     config: &SamplingConfig,
     warnings: &mut Vec<String>,
 ) -> Result<JsonValue, DbSurveyorError> {
@@ -436,11 +497,11 @@ fn row_to_json(
         // Check for sensitive column names if warnings are enabled
         if config.warn_sensitive {
             let name_lower = column_name.to_lowercase();
-            for (regex, description) in &config.compiled_patterns {
-                if regex.is_match(&name_lower) {
+            for pattern in &config.compiled_patterns {
+                if pattern.regex.is_match(&name_lower) {
                     warnings.push(format!(
                         "Column '{}' may contain sensitive data ({})",
-                        column_name, description
+                        column_name, pattern.description
                     ));
                     break;
                 }
@@ -448,7 +509,8 @@ fn row_to_json(
         }
 
         // Try to extract value as JSON-compatible type
-        let value = extract_column_value(row, column_name);
+        let column_type = column_types.iter().find(|c| c.name == column_name);
+        let value = extract_column_value(row, column_name, column_type);
         map.insert(column_name.to_string(), value);
     }
 
@@ -456,7 +518,18 @@ fn row_to_json(
 }
 
 /// Extract a column value as a JSON value.
-fn extract_column_value(row: &sqlx::sqlite::SqliteRow, column_name: &str) -> JsonValue {
+///
+/// Values of declared `DECIMAL(p,s)` columns are written as strings with
+/// the declared scale; see [`SampleValueEncoding`] for the other encodings.
+fn extract_column_value(
+    row: &sqlx::sqlite::SqliteRow,
+    column_name: &str,
+    column_type: Option<&SampleColumnType>,
+) -> JsonValue {
+    let decimal_scale = column_type
+        .filter(|c| c.encoding == SampleValueEncoding::DecimalString)
+        .and_then(|c| c.scale);
+
     // Try different types in order of likelihood
     // SQLite is dynamically typed, so we need to try multiple types
     if let Ok(v) = row.try_get::<Option<String>, _>(column_name) {
@@ -464,26 +537,27 @@ fn extract_column_value(row: &sqlx::sqlite::SqliteRow, column_name: &str) -> Jso
     }
     if let Ok(v) = row.try_get::<Option<i64>, _>(column_name) {
         return v
-            .map(|n| JsonValue::Number(n.into()))
+            .map(|n| match decimal_scale {
+                Some(0) => JsonValue::String(n.to_string()),
+                Some(scale) => JsonValue::String(format!("{n}.{:0>1$}", "", scale as usize)),
+                None => JsonValue::Number(n.into()),
+            })
             .unwrap_or(JsonValue::Null);
     }
     if let Ok(v) = row.try_get::<Option<f64>, _>(column_name) {
         return v
-            .and_then(serde_json::Number::from_f64)
-            .map(JsonValue::Number)
+            .map(|n| match decimal_scale {
+                Some(scale) if n.is_finite() => JsonValue::String(format_decimal(n, scale)),
+                _ => float_sample_value(n),
+            })
             .unwrap_or(JsonValue::Null);
     }
     if let Ok(v) = row.try_get::<Option<bool>, _>(column_name) {
         return v.map(JsonValue::Bool).unwrap_or(JsonValue::Null);
     }
     if let Ok(v) = row.try_get::<Option<Vec<u8>>, _>(column_name) {
-        // For BLOB data, convert to base64 string
         return v
-            .map(|bytes| {
-                use base64::Engine;
-                let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
-                JsonValue::String(format!("base64:{}", encoded))
-            })
+            .map(|bytes| binary_sample_value(&bytes))
             .unwrap_or(JsonValue::Null);
     }
 
@@ -491,6 +565,11 @@ fn extract_column_value(row: &sqlx::sqlite::SqliteRow, column_name: &str) -> Jso
     JsonValue::Null
 }
 
+/// Formats a number stored in a decimal column with the declared scale.
+fn format_decimal(value: f64, scale: u32) -> String {
+    format!("{:.*}", scale as usize, value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,21 +8,31 @@
 //! - `PRAGMA table_info()`: Returns column information for a table
 //! - `PRAGMA foreign_key_list()`: Returns foreign key information
 //! - `PRAGMA index_list()`: Returns index information
-//! - `PRAGMA index_info()`: Returns columns in an index
+//! - `PRAGMA index_xinfo()`: Returns columns in an index with their sort order
+//!
+//! Attached databases are introspected through the same catalog, qualified
+//! with their schema name (e.g. `"archive".sqlite_master`). Objects in the
+//! main database keep an unqualified (`None`) schema.
+//!
+//! Virtual tables are listed in `sqlite_master` like regular tables; they are
+//! distinguished by their `CREATE VIRTUAL TABLE` SQL (see `virtual_tables`).
+//! `WITHOUT ROWID` and `STRICT` options are likewise read from the table SQL.
 
+use super::ddl::{parse_column_collations, parse_table_options};
 use super::type_mapping::map_sqlite_type;
-use super::{SqliteAdapter, escape_identifier, escape_pragma_arg};
+use super::virtual_tables::{columns_from_arguments, find_shadow_tables, parse_virtual_table_sql};
+use super::{SqliteAdapter, escape_identifier, pragma_statement, qualified_table_name};
 use crate::Result;
-use crate::adapters::helpers::resolve_optional_collection;
+use crate::adapters::helpers::{
+    collect_if_selected, collect_within_table_timeout, resolve_optional_collection,
+};
+use crate::defaults::classify_default;
 use crate::models::*;
 use sqlx::Row;
 use std::collections::HashMap;
 
-/// Main entry point for schema collection.
+/// Main entry point for schema collection (main database only).
 pub(crate) async fn collect_schema(adapter: &SqliteAdapter) -> Result<DatabaseSchema> {
-    let start_time = std::time::Instant::now();
-    let mut warnings = Vec::new();
-
     let db_name = adapter
         .config
         .database
@@ -30,6 +40,105 @@ pub(crate) async fn collect_schema(adapter: &SqliteAdapter) -> Result<DatabaseSc
         .unwrap_or("main")
         .to_string();
 
+    collect_database_schema(adapter, None, &db_name).await
+}
+
+/// Collects the main database and all attached databases as a server schema.
+pub(crate) async fn collect_server_schema(adapter: &SqliteAdapter) -> Result<DatabaseServerSchema> {
+    let start_time = std::time::Instant::now();
+
+    let main_schema = collect_schema(adapter).await?;
+    let version = main_schema
+        .database_info
+        .version
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut databases = vec![main_schema];
+
+    for attachment in &adapter.attached {
+        match collect_database_schema(adapter, Some(&attachment.name), &attachment.name).await {
+            Ok(schema) => databases.push(schema),
+            Err(e) => {
+                let error = format!(
+                    "Failed to collect attached database '{}': {}",
+                    attachment.name, e
+                );
+                tracing::warn!("{}", error);
+
+                let mut info = DatabaseInfo::new(attachment.name.clone());
+                info.access_level = AccessLevel::None;
+                info.collection_status = CollectionStatus::Failed {
+                    error: error.clone(),
+                };
+                databases.push(DatabaseSchema::new(info).with_warning(error));
+            }
+        }
+    }
+
+    let collected = databases
+        .iter()
+        .filter(|db| {
+            matches!(
+                db.database_info.collection_status,
+                CollectionStatus::Success | CollectionStatus::Partial { .. }
+            )
+        })
+        .count();
+    let failed = databases.len() - collected;
+    let collection_duration = start_time.elapsed();
+
+    Ok(DatabaseServerSchema::new(
+        ServerInfo {
+            server_type: DatabaseType::SQLite,
+            version,
+            host: adapter.config.host.clone(),
+            port: None,
+            total_databases: databases.len(),
+            collected_databases: collected,
+            system_databases_excluded: 0,
+            connection_user: adapter
+                .config
+                .username
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            has_superuser_privileges: false,
+            timezone: None,
+            role: None,
+            collection_mode: CollectionMode::MultiDatabase {
+                discovered: databases.len(),
+                collected,
+                failed,
+            },
+        },
+        databases,
+        CollectionMetadata {
+            collected_at: chrono::Utc::now(),
+            collection_duration_ms: u64::try_from(collection_duration.as_millis())
+                .unwrap_or(u64::MAX),
+            collector_version: env!("CARGO_PKG_VERSION").to_string(),
+            warnings: Vec::new(),
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
+        },
+    ))
+}
+
+/// Collects one logical database: the main database (`schema` is `None`)
+/// or an attached database (`schema` is its schema name).
+async fn collect_database_schema(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    db_name: &str,
+) -> Result<DatabaseSchema> {
+    let start_time = std::time::Instant::now();
+    let mut warnings = Vec::new();
+    let mut object_failures = Vec::new();
+
     tracing::info!(
         "Starting SQLite schema collection for database: {}",
         db_name
@@ -37,12 +146,12 @@ pub(crate) async fn collect_schema(adapter: &SqliteAdapter) -> Result<DatabaseSc
 
     // Collect database information
     tracing::debug!("Collecting database information");
-    let database_info = collect_database_info(adapter, &db_name).await?;
+    let database_info = collect_database_info(adapter, schema, db_name).await?;
 
     // Collect tables with comprehensive metadata
     tracing::debug!("Enumerating database tables");
     let table_collection_start = std::time::Instant::now();
-    let tables = match collect_tables(adapter).await {
+    let tables = match collect_tables(adapter, schema, &mut warnings, &mut object_failures).await {
         Ok(tables) => {
             let table_collection_duration = table_collection_start.elapsed();
             tracing::info!(
@@ -59,11 +168,18 @@ pub(crate) async fn collect_schema(adapter: &SqliteAdapter) -> Result<DatabaseSc
     };
 
     // Collect views
-    let views = resolve_optional_collection("views", collect_views(adapter).await, &mut warnings);
+    let views = resolve_optional_collection(
+        "views",
+        collect_if_selected(adapter.objects.views, collect_views(adapter, schema)).await,
+        &mut warnings,
+    );
 
     // Collect triggers
-    let triggers =
-        resolve_optional_collection("triggers", collect_triggers(adapter).await, &mut warnings);
+    let triggers = resolve_optional_collection(
+        "triggers",
+        collect_if_selected(adapter.objects.triggers, collect_triggers(adapter, schema)).await,
+        &mut warnings,
+    );
 
     let collection_duration = start_time.elapsed();
 
@@ -94,17 +210,29 @@ pub(crate) async fn collect_schema(adapter: &SqliteAdapter) -> Result<DatabaseSc
                 .unwrap_or(u64::MAX),
             collector_version: env!("CARGO_PKG_VERSION").to_string(),
             warnings,
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
         },
     };
 
     // Aggregate indexes and constraints from per-table data into schema-level vectors
-    let schema = schema.with_aggregated_indexes_and_constraints();
+    let schema = schema
+        .with_aggregated_indexes_and_constraints()
+        .with_object_failures(object_failures);
 
     Ok(schema)
 }
 
 /// Collects database information from SQLite.
-async fn collect_database_info(adapter: &SqliteAdapter, db_name: &str) -> Result<DatabaseInfo> {
+async fn collect_database_info(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    db_name: &str,
+) -> Result<DatabaseInfo> {
     // Get SQLite version
     let version: String = sqlx::query_scalar("SELECT sqlite_version()")
         .fetch_one(&adapter.pool)
@@ -113,14 +241,18 @@ async fn collect_database_info(adapter: &SqliteAdapter, db_name: &str) -> Result
             crate::error::DbSurveyorError::collection_failed("Failed to get SQLite version", e)
         })?;
 
-    // Get database size if file-based
-    let size_bytes = if !adapter.is_in_memory() {
+    // Get database size if file-based (attached databases are always files)
+    let size_bytes = if schema.is_some() || !adapter.is_in_memory() {
+        let pragma_prefix = schema
+            .map(|s| format!("{}.", escape_identifier(s)))
+            .unwrap_or_default();
+
         // Query page_count and page_size to calculate size
-        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+        let page_count: i64 = sqlx::query_scalar(&format!("PRAGMA {}page_count", pragma_prefix))
             .fetch_one(&adapter.pool)
             .await
             .unwrap_or(0);
-        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+        let page_size: i64 = sqlx::query_scalar(&format!("PRAGMA {}page_size", pragma_prefix))
             .fetch_one(&adapter.pool)
             .await
             .unwrap_or(4096);
@@ -137,29 +269,73 @@ async fn collect_database_info(adapter: &SqliteAdapter, db_name: &str) -> Result
 
     Ok(DatabaseInfo {
         name: db_name.to_string(),
+        engine: Some(DatabaseType::SQLite),
         version: Some(format!("SQLite {}", version)),
         size_bytes,
         encoding: Some(encoding),
         collation: None, // SQLite uses per-column collation
-        owner: None,     // SQLite doesn't have database owners
+        timezone: None,
+        owner: None, // SQLite doesn't have database owners
         is_system_database: false,
         access_level: AccessLevel::Full,
+        access_probe: None,
+        schemas: Vec::new(),
         collection_status: CollectionStatus::Success,
+    }
+    .with_access_probe(probe_access(adapter, schema).await))
+}
+
+/// SQLite has no accounts or grants: opening the file gives read access to
+/// everything in it, so the probe only counts what there is to read.
+async fn probe_access(adapter: &SqliteAdapter, schema: Option<&str>) -> Result<AccessProbe> {
+    let count_query = format!(
+        "SELECT COUNT(*) FROM {} WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'",
+        qualified_table_name(schema, "sqlite_master")
+    );
+    let total_tables: i64 = sqlx::query_scalar(&count_query)
+        .fetch_one(&adapter.pool)
+        .await
+        .map_err(|e| {
+            crate::error::DbSurveyorError::collection_failed("Failed to count tables and views", e)
+        })?;
+    let total_tables = usize::try_from(total_tables).unwrap_or_default();
+
+    Ok(AccessProbe {
+        superuser: false,
+        catalog_access: true,
+        readable_tables: total_tables,
+        total_tables,
+        identity: None,
+        evidence: vec!["SQLite has no object privileges; file access grants all reads".to_string()],
     })
 }
 
 /// Collects all tables from the SQLite database.
-async fn collect_tables(adapter: &SqliteAdapter) -> Result<Vec<Table>> {
+///
+/// Virtual tables are recognised from their `CREATE VIRTUAL TABLE` statement.
+/// If a virtual table's module is not available in this SQLite build its
+/// columns are taken from the statement and a warning is added.
+/// A table that exceeds the per-table timeout is skipped and recorded in
+/// `failures`.
+async fn collect_tables(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    warnings: &mut Vec<CollectionWarning>,
+    failures: &mut Vec<ObjectFailure>,
+) -> Result<Vec<Table>> {
     // Query sqlite_master for table definitions
-    let tables_query = r#"
+    let tables_query = format!(
+        r#"
         SELECT name, sql
-        FROM sqlite_master
+        FROM {}
         WHERE type = 'table'
         AND name NOT LIKE 'sqlite_%'
         ORDER BY name
-    "#;
+    "#,
+        qualified_table_name(schema, "sqlite_master")
+    );
 
-    let table_rows = sqlx::query(tables_query)
+    let table_rows = sqlx::query(&tables_query)
         .fetch_all(&adapter.pool)
         .await
         .map_err(|e| {
@@ -172,55 +348,171 @@ async fn collect_tables(adapter: &SqliteAdapter) -> Result<Vec<Table>> {
         let table_name: String = row.try_get("name").map_err(|e| {
             crate::error::DbSurveyorError::collection_failed("Failed to parse table name", e)
         })?;
+        let sql: Option<String> = row.try_get("sql").ok();
+
+        let collected = collect_within_table_timeout(
+            collect_table(adapter, schema, &table_name, sql.as_deref(), warnings),
+            adapter.config.table_timeout,
+            schema,
+            &table_name,
+            failures,
+        )
+        .await;
+
+        if let Some(table) = collected.transpose()? {
+            tables.push(table);
+        }
+    }
 
-        // Collect columns for this table
-        let columns = collect_table_columns(adapter, &table_name).await?;
+    link_shadow_tables(&mut tables);
 
-        // Collect primary key
-        let primary_key = detect_primary_key(&columns);
+    Ok(tables)
+}
 
-        // Collect foreign keys
-        let foreign_keys = collect_table_foreign_keys(adapter, &table_name).await?;
+/// Collects a single table from its `sqlite_master` entry.
+///
+/// A virtual table whose module is not available is returned with only the
+/// columns named in its declaration, and a warning.
+async fn collect_table(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    table_name: &str,
+    sql: Option<&str>,
+    warnings: &mut Vec<CollectionWarning>,
+) -> Result<Table> {
+    let virtual_table = sql.and_then(parse_virtual_table_sql);
+    let options = sql.and_then(parse_table_options);
+
+    // Collect columns for this table
+    let mut columns = match (
+        collect_table_columns(adapter, schema, table_name).await,
+        &virtual_table,
+    ) {
+        (Ok(columns), _) => columns,
+        (Err(e), Some(vt)) => {
+            tracing::warn!(
+                "Failed to collect columns for virtual table '{}': {}",
+                table_name,
+                e
+            );
+            // The module's own tables cannot be queried either, so the
+            // table is reported with the columns its declaration names
+            let object = schema.map_or_else(
+                || table_name.to_string(),
+                |schema| format!("{}.{}", schema, table_name),
+            );
+            let columns = columns_from_arguments(vt);
+            let outcome = if columns.is_empty() {
+                "columns were not collected"
+            } else {
+                "columns were taken from its declaration"
+            };
+            warnings.push(
+                CollectionWarning::new(
+                    WarningCategory::Collection,
+                    format!(
+                        "Virtual table module '{}' is not available; {}",
+                        vt.module, outcome
+                    ),
+                )
+                .with_object(object),
+            );
+            return Ok(Table {
+                virtual_table,
+                ..Table::new(table_name.to_string(), schema.map(str::to_string), columns)
+            });
+        }
+        (Err(e), None) => return Err(e),
+    };
 
-        // Collect indexes
-        let indexes = collect_table_indexes(adapter, &table_name).await?;
+    if let Some(sql) = sql {
+        let mut collations = parse_column_collations(sql);
+        for column in &mut columns {
+            column.collation = collations.remove(&column.name);
+        }
+    }
 
-        // Collect constraints
-        let constraints = collect_table_constraints(&columns, &table_name);
+    // Without a rowid, INTEGER PRIMARY KEY is an ordinary column rather
+    // than an auto-assigned rowid alias
+    if options.as_ref().is_some_and(|o| o.without_rowid) {
+        for column in &mut columns {
+            column.is_auto_increment = false;
+        }
+    }
 
-        // Get row count estimate
-        let row_count = get_table_row_count(adapter, &table_name).await.ok();
+    // Collect primary key
+    let primary_key = detect_primary_key(&columns);
 
-        let table = Table {
-            name: table_name.clone(),
-            schema: None, // SQLite doesn't have schemas in the PostgreSQL sense
-            columns,
-            primary_key,
-            foreign_keys,
-            indexes,
-            constraints,
-            comment: None, // SQLite doesn't support table comments
-            row_count,
-        };
+    // Collect foreign keys
+    let foreign_keys = collect_table_foreign_keys(adapter, schema, table_name).await?;
 
-        tracing::debug!(
-            "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
-            table.name,
-            table.columns.len(),
-            table.foreign_keys.len(),
-            table.indexes.len()
-        );
+    // Collect indexes
+    let indexes = collect_if_selected(
+        adapter.objects.indexes,
+        collect_table_indexes(adapter, schema, table_name),
+    )
+    .await?;
 
-        tables.push(table);
-    }
+    // Collect constraints
+    let constraints = if adapter.objects.constraints {
+        collect_table_constraints(&columns, schema, table_name)
+    } else {
+        Vec::new()
+    };
 
-    Ok(tables)
+    // Get row count estimate
+    let row_count = get_table_row_count(adapter, schema, table_name).await.ok();
+
+    let table = Table {
+        name: table_name.to_string(),
+        // Only attached databases are qualified; the main database has no schema
+        schema: schema.map(str::to_string),
+        columns,
+        primary_key,
+        foreign_keys,
+        indexes,
+        constraints,
+        comment: None, // SQLite doesn't support table comments
+        row_count,
+        size_bytes: None,
+        validator: None,
+        partitioning: None,
+        virtual_table,
+        options,
+        inheritance: None,
+    };
+
+    tracing::debug!(
+        "Collected table '{}' with {} columns, {} foreign keys, {} indexes",
+        table.name,
+        table.columns.len(),
+        table.foreign_keys.len(),
+        table.indexes.len()
+    );
+
+    Ok(table)
+}
+
+/// Records, on each virtual table, the shadow tables its module created.
+fn link_shadow_tables(tables: &mut [Table]) {
+    let names: Vec<String> = tables.iter().map(|t| t.name.clone()).collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    for table in tables.iter_mut() {
+        if let Some(vt) = table.virtual_table.as_mut() {
+            vt.shadow_tables = find_shadow_tables(&table.name, vt, &names);
+        }
+    }
 }
 
 /// Collects column metadata for a specific table.
-async fn collect_table_columns(adapter: &SqliteAdapter, table_name: &str) -> Result<Vec<Column>> {
+async fn collect_table_columns(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    table_name: &str,
+) -> Result<Vec<Column>> {
     // Use PRAGMA table_info to get column details
-    let columns_query = format!("PRAGMA table_info({})", escape_pragma_arg(table_name));
+    let columns_query = pragma_statement(schema, "table_info", table_name);
 
     let column_rows = sqlx::query(&columns_query)
         .fetch_all(&adapter.pool)
@@ -270,9 +562,17 @@ async fn collect_table_columns(adapter: &SqliteAdapter, table_name: &str) -> Res
             is_nullable,
             is_primary_key: pk > 0,
             is_auto_increment,
+            normalized_default: default_value.as_deref().map(classify_default),
             default_value,
             comment: None, // SQLite doesn't support column comments
             ordinal_position: u32::try_from(cid + 1).unwrap_or(0),
+            generated: None,
+            declared_type: Some(data_type),
+            collation: None,
+            character_set: None,
+            storage: None,
+            identity: None,
+            masking: None,
         };
 
         columns.push(column);
@@ -302,9 +602,10 @@ fn detect_primary_key(columns: &[Column]) -> Option<PrimaryKey> {
 /// Collects foreign keys for a table.
 async fn collect_table_foreign_keys(
     adapter: &SqliteAdapter,
+    schema: Option<&str>,
     table_name: &str,
 ) -> Result<Vec<ForeignKey>> {
-    let fk_query = format!("PRAGMA foreign_key_list({})", escape_pragma_arg(table_name));
+    let fk_query = pragma_statement(schema, "foreign_key_list", table_name);
 
     let fk_rows = sqlx::query(&fk_query)
         .fetch_all(&adapter.pool)
@@ -342,7 +643,8 @@ async fn collect_table_foreign_keys(
             name: None, // SQLite doesn't name FK constraints
             columns: Vec::new(),
             referenced_table: ref_table,
-            referenced_schema: None,
+            // SQLite foreign keys always reference the same database
+            referenced_schema: schema.map(str::to_string),
             referenced_columns: Vec::new(),
             on_delete: parse_referential_action(&on_delete),
             on_update: parse_referential_action(&on_update),
@@ -374,8 +676,12 @@ fn parse_referential_action(action: &str) -> Option<ReferentialAction> {
 }
 
 /// Collects indexes for a table.
-async fn collect_table_indexes(adapter: &SqliteAdapter, table_name: &str) -> Result<Vec<Index>> {
-    let index_list_query = format!("PRAGMA index_list({})", escape_pragma_arg(table_name));
+async fn collect_table_indexes(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    table_name: &str,
+) -> Result<Vec<Index>> {
+    let index_list_query = pragma_statement(schema, "index_list", table_name);
 
     let index_rows = sqlx::query(&index_list_query)
         .fetch_all(&adapter.pool)
@@ -402,16 +708,17 @@ async fn collect_table_indexes(adapter: &SqliteAdapter, table_name: &str) -> Res
         let is_primary = origin == "pk";
 
         // Get index columns
-        let columns = collect_index_columns(adapter, &index_name).await?;
+        let columns = collect_index_columns(adapter, schema, &index_name).await?;
 
         let index = Index {
             name: index_name,
             table_name: table_name.to_string(),
-            schema: None,
+            schema: schema.map(str::to_string),
             columns,
             is_unique: is_unique != 0,
             is_primary,
             index_type: Some("btree".to_string()), // SQLite uses B-tree indexes
+            options: None,
         };
 
         indexes.push(index);
@@ -423,9 +730,11 @@ async fn collect_table_indexes(adapter: &SqliteAdapter, table_name: &str) -> Res
 /// Collects columns for a specific index.
 async fn collect_index_columns(
     adapter: &SqliteAdapter,
+    schema: Option<&str>,
     index_name: &str,
 ) -> Result<Vec<IndexColumn>> {
-    let index_info_query = format!("PRAGMA index_info({})", escape_pragma_arg(index_name));
+    // index_xinfo, unlike index_info, reports each column's sort direction
+    let index_info_query = pragma_statement(schema, "index_xinfo", index_name);
 
     let column_rows = sqlx::query(&index_info_query)
         .fetch_all(&adapter.pool)
@@ -440,14 +749,24 @@ async fn collect_index_columns(
     let mut columns = Vec::new();
 
     for row in column_rows {
+        // Auxiliary columns (the rowid or primary key appended to every
+        // index entry) are not part of the index key
+        let key: bool = row.try_get("key").unwrap_or(true);
+        if !key {
+            continue;
+        }
         let name: String = row.try_get("name").map_err(|e| {
             crate::error::DbSurveyorError::collection_failed("Failed to parse index column name", e)
         })?;
+        let descending: bool = row.try_get("desc").unwrap_or(false);
 
-        // SQLite doesn't store sort order in index_info, default to ascending
         columns.push(IndexColumn {
             name,
-            sort_order: Some(SortDirection::Ascending),
+            sort_order: Some(if descending {
+                SortDirection::Descending
+            } else {
+                SortDirection::Ascending
+            }),
         });
     }
 
@@ -455,7 +774,11 @@ async fn collect_index_columns(
 }
 
 /// Collects constraints from column information.
-fn collect_table_constraints(columns: &[Column], table_name: &str) -> Vec<Constraint> {
+fn collect_table_constraints(
+    columns: &[Column],
+    schema: Option<&str>,
+    table_name: &str,
+) -> Vec<Constraint> {
     let mut constraints = Vec::new();
 
     // Add NOT NULL constraints
@@ -464,7 +787,7 @@ fn collect_table_constraints(columns: &[Column], table_name: &str) -> Vec<Constr
             constraints.push(Constraint {
                 name: format!("{}_{}_{}", table_name, column.name, "notnull"),
                 table_name: table_name.to_string(),
-                schema: None,
+                schema: schema.map(str::to_string),
                 constraint_type: ConstraintType::NotNull,
                 columns: vec![column.name.clone()],
                 check_clause: None,
@@ -476,10 +799,17 @@ fn collect_table_constraints(columns: &[Column], table_name: &str) -> Vec<Constr
 }
 
 /// Gets estimated row count for a table.
-async fn get_table_row_count(adapter: &SqliteAdapter, table_name: &str) -> Result<u64> {
+async fn get_table_row_count(
+    adapter: &SqliteAdapter,
+    schema: Option<&str>,
+    table_name: &str,
+) -> Result<u64> {
     // Use a simple COUNT(*) for small tables
     // For larger tables, this could be slow, but SQLite doesn't have table statistics
-    let query = format!("SELECT COUNT(*) FROM {}", escape_identifier(table_name));
+    let query = format!(
+        "SELECT COUNT(*) FROM {}",
+        qualified_table_name(schema, table_name)
+    );
 
     let count: i64 = sqlx::query_scalar(&query)
         .fetch_one(&adapter.pool)
@@ -495,16 +825,19 @@ async fn get_table_row_count(adapter: &SqliteAdapter, table_name: &str) -> Resul
 }
 
 /// Collects views from the SQLite database.
-async fn collect_views(adapter: &SqliteAdapter) -> Result<Vec<View>> {
-    let views_query = r#"
+async fn collect_views(adapter: &SqliteAdapter, schema: Option<&str>) -> Result<Vec<View>> {
+    let views_query = format!(
+        r#"
         SELECT name, sql
-        FROM sqlite_master
+        FROM {}
         WHERE type = 'view'
         AND name NOT LIKE 'sqlite_%'
         ORDER BY name
-    "#;
+    "#,
+        qualified_table_name(schema, "sqlite_master")
+    );
 
-    let view_rows = sqlx::query(views_query)
+    let view_rows = sqlx::query(&views_query)
         .fetch_all(&adapter.pool)
         .await
         .map_err(|e| {
@@ -520,13 +853,13 @@ async fn collect_views(adapter: &SqliteAdapter) -> Result<Vec<View>> {
         let definition: Option<String> = row.try_get("sql").ok();
 
         // Collect view columns (same method as table columns)
-        let columns = collect_table_columns(adapter, &view_name)
+        let columns = collect_table_columns(adapter, schema, &view_name)
             .await
             .unwrap_or_default();
 
         views.push(View {
             name: view_name,
-            schema: None,
+            schema: schema.map(str::to_string),
             definition,
             columns,
             comment: None,
@@ -537,16 +870,19 @@ async fn collect_views(adapter: &SqliteAdapter) -> Result<Vec<View>> {
 }
 
 /// Collects triggers from the SQLite database.
-async fn collect_triggers(adapter: &SqliteAdapter) -> Result<Vec<Trigger>> {
-    let triggers_query = r#"
+async fn collect_triggers(adapter: &SqliteAdapter, schema: Option<&str>) -> Result<Vec<Trigger>> {
+    let triggers_query = format!(
+        r#"
         SELECT name, tbl_name, sql
-        FROM sqlite_master
+        FROM {}
         WHERE type = 'trigger'
         AND name NOT LIKE 'sqlite_%'
         ORDER BY name
-    "#;
+    "#,
+        qualified_table_name(schema, "sqlite_master")
+    );
 
-    let trigger_rows = sqlx::query(triggers_query)
+    let trigger_rows = sqlx::query(&triggers_query)
         .fetch_all(&adapter.pool)
         .await
         .map_err(|e| {
@@ -573,7 +909,7 @@ async fn collect_triggers(adapter: &SqliteAdapter) -> Result<Vec<Trigger>> {
         triggers.push(Trigger {
             name: trigger_name,
             table_name,
-            schema: None,
+            schema: schema.map(str::to_string),
             event,
             timing,
             definition,
@@ -5,7 +5,7 @@
 //! - Connection configuration parsing
 //! - Order by clause generation
 
-use crate::adapters::{AdapterFeature, DatabaseAdapter};
+use crate::adapters::{AdapterFeature, DatabaseAdapter, ObjectSelection};
 use crate::models::{DatabaseType, UnifiedDataType};
 
 use super::SqliteAdapter;
@@ -330,7 +330,9 @@ async fn test_sqlite_collect_schema_with_table() {
         pool,
         config: crate::adapters::ConnectionConfig::new("localhost".to_string())
             .with_database(":memory:".to_string()),
+        objects: ObjectSelection::default(),
         connection_string: zeroize::Zeroizing::new("sqlite::memory:".to_string()),
+        attached: Vec::new(),
     };
 
     let schema = adapter.collect_schema().await.unwrap();
@@ -393,7 +395,9 @@ async fn test_sqlite_collect_schema_with_foreign_key() {
         pool,
         config: crate::adapters::ConnectionConfig::new("localhost".to_string())
             .with_database(":memory:".to_string()),
+        objects: ObjectSelection::default(),
         connection_string: zeroize::Zeroizing::new("sqlite::memory:".to_string()),
+        attached: Vec::new(),
     };
 
     let schema = adapter.collect_schema().await.unwrap();
@@ -438,7 +442,9 @@ async fn test_sqlite_collect_schema_with_index() {
         pool,
         config: crate::adapters::ConnectionConfig::new("localhost".to_string())
             .with_database(":memory:".to_string()),
+        objects: ObjectSelection::default(),
         connection_string: zeroize::Zeroizing::new("sqlite::memory:".to_string()),
+        attached: Vec::new(),
     };
 
     let schema = adapter.collect_schema().await.unwrap();
@@ -487,7 +493,9 @@ async fn test_sqlite_collect_schema_with_view() {
         pool,
         config: crate::adapters::ConnectionConfig::new("localhost".to_string())
             .with_database(":memory:".to_string()),
+        objects: ObjectSelection::default(),
         connection_string: zeroize::Zeroizing::new("sqlite::memory:".to_string()),
+        attached: Vec::new(),
     };
 
     let schema = adapter.collect_schema().await.unwrap();
@@ -542,7 +550,9 @@ async fn test_sqlite_collect_schema_with_trigger() {
         pool,
         config: crate::adapters::ConnectionConfig::new("localhost".to_string())
             .with_database(":memory:".to_string()),
+        objects: ObjectSelection::default(),
         connection_string: zeroize::Zeroizing::new("sqlite::memory:".to_string()),
+        attached: Vec::new(),
     };
 
     let schema = adapter.collect_schema().await.unwrap();
@@ -552,3 +562,86 @@ async fn test_sqlite_collect_schema_with_trigger() {
     assert_eq!(trigger.name, "log_user_insert");
     assert_eq!(trigger.table_name, "users");
 }
+
+#[tokio::test]
+async fn test_sqlite_collect_schema_skips_unselected_objects() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    for statement in [
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+        "CREATE INDEX idx_items_name ON items(name)",
+        "CREATE VIEW named_items AS SELECT name FROM items",
+        "CREATE TRIGGER items_touch AFTER UPDATE ON items BEGIN SELECT 1; END",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let mut adapter = SqliteAdapter::from_pool(
+        pool,
+        "sqlite::memory:",
+        crate::adapters::ConnectionConfig::new("localhost".to_string())
+            .with_database(":memory:".to_string()),
+    );
+    adapter.objects = ObjectSelection {
+        views: false,
+        indexes: false,
+        constraints: false,
+        ..ObjectSelection::default()
+    };
+
+    let schema = adapter.collect_schema().await.unwrap();
+
+    assert_eq!(schema.tables.len(), 1);
+    assert_eq!(schema.tables[0].columns.len(), 2);
+    assert!(schema.views.is_empty());
+    assert!(schema.indexes.is_empty());
+    assert!(schema.constraints.is_empty());
+    assert!(schema.tables[0].indexes.is_empty());
+    assert_eq!(schema.triggers.len(), 1);
+}
+
+#[tokio::test]
+async fn test_verify_read_only_reports_open_mode() {
+    let adapter = SqliteAdapter::new(":memory:").await.unwrap();
+    let verification = adapter.verify_read_only().await.unwrap();
+    assert_eq!(verification.level, crate::models::ReadOnlyLevel::Session);
+}
+
+#[tokio::test]
+async fn test_integrity_check_of_intact_database() {
+    let adapter = SqliteAdapter::new(":memory:").await.unwrap();
+    for mode in [
+        crate::models::IntegrityCheckMode::Quick,
+        crate::models::IntegrityCheckMode::Full,
+    ] {
+        let check = adapter
+            .check_integrity(mode, Some(std::time::Duration::from_secs(30)))
+            .await
+            .expect("SQLite supports integrity checks");
+        assert_eq!(check.mode, mode);
+        assert_eq!(check.status, crate::models::IntegrityStatus::Intact);
+        assert!(check.problems.is_empty());
+        assert!(check.to_warning().is_none());
+    }
+}
+
+#[test]
+fn test_classify_integrity_rows() {
+    use super::integrity::classify_integrity_rows;
+    use crate::models::IntegrityStatus;
+
+    assert_eq!(
+        classify_integrity_rows(vec!["ok".to_string()]),
+        (IntegrityStatus::Intact, Vec::new())
+    );
+    let (status, problems) = classify_integrity_rows(vec![
+        "row 4 missing from index orders_customer_idx".to_string(),
+        "wrong # of entries in index orders_customer_idx".to_string(),
+    ]);
+    assert_eq!(status, IntegrityStatus::Corrupt);
+    assert_eq!(problems.len(), 2);
+}
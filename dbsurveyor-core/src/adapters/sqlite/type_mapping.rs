@@ -12,7 +12,7 @@
 //! 4. Contains "REAL", "FLOA", or "DOUB" -> REAL affinity
 //! 5. Otherwise -> NUMERIC affinity
 
-use crate::models::UnifiedDataType;
+use crate::models::{SampleValueEncoding, UnifiedDataType};
 
 /// Maps a SQLite data type to the unified data type system.
 ///
@@ -97,7 +97,7 @@ pub fn map_sqlite_type(sqlite_type: &str) -> UnifiedDataType {
         },
 
         // Numeric types
-        "NUMERIC" | "DECIMAL" | "NUMBER" => UnifiedDataType::Float { precision: None },
+        "NUMERIC" | "DECIMAL" | "NUMBER" => decimal_type(type_str, length),
 
         // JSON type (SQLite 3.9+ supports JSON functions)
         "JSON" | "JSONB" => UnifiedDataType::Json,
@@ -125,7 +125,7 @@ pub fn map_sqlite_type(sqlite_type: &str) -> UnifiedDataType {
         _ => {
             // If it looks like it might have numeric affinity
             if base_type.contains("NUM") || base_type.contains("DEC") {
-                UnifiedDataType::Float { precision: None }
+                decimal_type(type_str, length)
             } else {
                 UnifiedDataType::Custom {
                     type_name: sqlite_type.to_string(),
@@ -135,6 +135,56 @@ pub fn map_sqlite_type(sqlite_type: &str) -> UnifiedDataType {
     }
 }
 
+/// Chooses how sampled values of a column with the declared type are written.
+///
+/// Follows the affinity rules: REAL affinity columns are floats and
+/// declared BLOB columns binary. A `DECIMAL(p,s)` or `NUMERIC(p,s)` column
+/// has NUMERIC affinity, so its values are stored as integers or reals;
+/// they are written as strings padded to the declared scale `s`, which is
+/// returned alongside the encoding.
+pub(crate) fn sample_encoding(declared_type: &str) -> (SampleValueEncoding, Option<u32>) {
+    let type_upper = declared_type.to_uppercase();
+    let (base_type, _) = parse_type_with_length(type_upper.trim());
+
+    if base_type.contains("INT")
+        || base_type.contains("CHAR")
+        || base_type.contains("CLOB")
+        || base_type.contains("TEXT")
+    {
+        (SampleValueEncoding::Json, None)
+    } else if base_type.contains("BLOB") {
+        (SampleValueEncoding::Base64, None)
+    } else if base_type.contains("REAL") || base_type.contains("FLOA") || base_type.contains("DOUB")
+    {
+        (SampleValueEncoding::Float, None)
+    } else if base_type.contains("NUM") || base_type.contains("DEC") {
+        match parse_scale(&type_upper) {
+            Some(scale) => (SampleValueEncoding::DecimalString, Some(scale)),
+            None => (SampleValueEncoding::Json, None),
+        }
+    } else {
+        (SampleValueEncoding::Json, None)
+    }
+}
+
+/// Maps a NUMERIC affinity type, keeping any declared `(precision, scale)`.
+fn decimal_type(type_str: &str, precision: Option<i64>) -> UnifiedDataType {
+    let precision = precision.and_then(|p| u32::try_from(p).ok());
+    UnifiedDataType::Decimal {
+        precision,
+        // A bare precision means a scale of zero, as in standard SQL
+        scale: parse_scale(type_str).or(precision.map(|_| 0)),
+    }
+}
+
+/// Extracts the scale from a `(precision, scale)` type parameter list.
+fn parse_scale(type_str: &str) -> Option<u32> {
+    let params = &type_str[type_str.find('(')? + 1..];
+    let params = &params[..params.find(')')?];
+    let (_, scale) = params.split_once(',')?;
+    scale.trim().parse().ok()
+}
+
 /// Maps integer type variants to appropriate bit widths.
 fn map_integer_type(type_name: &str) -> UnifiedDataType {
     // SQLite INTEGER is 64-bit internally, but we try to map
@@ -432,10 +482,31 @@ fn test_map_uuid_type() {
     #[test]
     fn test_map_numeric_type() {
         let result = map_sqlite_type("NUMERIC");
-        assert!(matches!(result, UnifiedDataType::Float { precision: None }));
+        assert_eq!(
+            result,
+            UnifiedDataType::Decimal {
+                precision: None,
+                scale: None
+            }
+        );
 
         let result = map_sqlite_type("DECIMAL(10,2)");
-        assert!(matches!(result, UnifiedDataType::Float { precision: None }));
+        assert_eq!(
+            result,
+            UnifiedDataType::Decimal {
+                precision: Some(10),
+                scale: Some(2)
+            }
+        );
+
+        let result = map_sqlite_type("decimal(8)");
+        assert_eq!(
+            result,
+            UnifiedDataType::Decimal {
+                precision: Some(8),
+                scale: Some(0)
+            }
+        );
     }
 
     // =============================================================================
@@ -488,6 +559,29 @@ fn test_case_insensitivity() {
     // Length Parsing Tests
     // =============================================================================
 
+    #[test]
+    fn test_sample_encoding() {
+        assert_eq!(
+            sample_encoding("DECIMAL(10, 2)"),
+            (SampleValueEncoding::DecimalString, Some(2))
+        );
+        assert_eq!(
+            sample_encoding("NUMERIC"),
+            (SampleValueEncoding::Json, None)
+        );
+        assert_eq!(
+            sample_encoding("double precision"),
+            (SampleValueEncoding::Float, None)
+        );
+        assert_eq!(sample_encoding("BLOB"), (SampleValueEncoding::Base64, None));
+        assert_eq!(sample_encoding(""), (SampleValueEncoding::Json, None));
+        assert_eq!(sample_encoding("BIGINT"), (SampleValueEncoding::Json, None));
+        assert_eq!(
+            sample_encoding("VARCHAR(20)"),
+            (SampleValueEncoding::Json, None)
+        );
+    }
+
     #[test]
     fn test_parse_type_with_length() {
         let (base, len) = parse_type_with_length("VARCHAR(255)");
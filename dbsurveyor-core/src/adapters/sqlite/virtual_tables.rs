@@ -0,0 +1,222 @@
+//! SQLite virtual table introspection.
+//!
+//! Virtual tables (FTS3/4/5, R*Tree, and custom modules) appear in
+//! `sqlite_master` with `type = 'table'`, so they are recognised from their
+//! `CREATE VIRTUAL TABLE ... USING module(args)` statement. Built-in modules
+//! keep their data in ordinary "shadow" tables named after the virtual table,
+//! which are linked back to it here.
+
+use super::ddl::{Token, split_arguments, tokenize};
+use super::type_mapping::map_sqlite_type;
+use crate::models::{Column, VirtualTable};
+
+/// Shadow table suffixes created by SQLite's built-in modules.
+fn shadow_table_suffixes(module: &str) -> &'static [&'static str] {
+    match module.to_lowercase().as_str() {
+        "fts3" | "fts4" => &["content", "segments", "segdir", "docsize", "stat"],
+        "fts5" => &["data", "idx", "content", "docsize", "config"],
+        "rtree" | "rtree_i32" | "geopoly" => &["node", "parent", "rowid"],
+        _ => &[],
+    }
+}
+
+/// Parses a `CREATE VIRTUAL TABLE` statement into its module and arguments.
+///
+/// Returns `None` for ordinary `CREATE TABLE` statements. Arguments are
+/// split on top-level commas and kept verbatim (trimmed), since their meaning
+/// is defined by each module.
+pub(crate) fn parse_virtual_table_sql(sql: &str) -> Option<VirtualTable> {
+    let tokens = tokenize(sql);
+    let mut words = tokens.iter().filter_map(|t| match t {
+        Token::Word(w) => Some(w.as_str()),
+        _ => None,
+    });
+    if !(words.next()?.eq_ignore_ascii_case("CREATE")
+        && words.next()?.eq_ignore_ascii_case("VIRTUAL"))
+    {
+        return None;
+    }
+
+    let using = tokens
+        .iter()
+        .position(|t| matches!(t, Token::Word(w) if w.eq_ignore_ascii_case("USING")))?;
+    let module = match tokens.get(using + 1)? {
+        Token::Word(w) | Token::Quoted(w) => w.clone(),
+        _ => return None,
+    };
+
+    let arguments = match tokens.get(using + 2) {
+        Some(Token::Group(body)) => split_arguments(body),
+        _ => Vec::new(),
+    };
+
+    Some(VirtualTable {
+        module,
+        arguments,
+        shadow_tables: Vec::new(),
+    })
+}
+
+/// Returns the shadow tables a virtual table's module would create,
+/// restricted to the names present in `existing_tables`.
+pub(crate) fn find_shadow_tables(
+    table_name: &str,
+    virtual_table: &VirtualTable,
+    existing_tables: &[&str],
+) -> Vec<String> {
+    shadow_table_suffixes(&virtual_table.module)
+        .iter()
+        .map(|suffix| format!("{}_{}", table_name, suffix))
+        .filter(|candidate| existing_tables.contains(&candidate.as_str()))
+        .collect()
+}
+
+/// Columns declared in a virtual table's module arguments.
+///
+/// Used when the module is not compiled into this SQLite build, so
+/// `PRAGMA table_info` cannot read the table. FTS3/4/5 arguments are text
+/// column names (optionally followed by `UNINDEXED`) mixed with
+/// `key=value` options. R*Tree arguments are an integer id and coordinate
+/// columns, plus `+`-prefixed auxiliary columns with optional types. Other
+/// modules define their own argument syntax, so no columns are derived.
+pub(crate) fn columns_from_arguments(virtual_table: &VirtualTable) -> Vec<Column> {
+    let module = virtual_table.module.to_lowercase();
+    let mut columns = Vec::new();
+    for argument in &virtual_table.arguments {
+        let tokens = tokenize(argument);
+        if tokens.contains(&Token::Symbol('=')) {
+            continue;
+        }
+        let auxiliary = tokens.first() == Some(&Token::Symbol('+'));
+        let mut words = tokens.iter().filter_map(|token| match token {
+            Token::Word(word) | Token::Quoted(word) => Some(word.as_str()),
+            _ => None,
+        });
+        let Some(name) = words.next() else {
+            continue;
+        };
+        let data_type = match module.as_str() {
+            "fts3" | "fts4" | "fts5" if !name.eq_ignore_ascii_case("tokenize") => {
+                map_sqlite_type("TEXT")
+            }
+            "rtree" | "rtree_i32" if auxiliary => {
+                map_sqlite_type(&words.collect::<Vec<_>>().join(" "))
+            }
+            "rtree" | "rtree_i32" if columns.is_empty() => map_sqlite_type("INTEGER"),
+            "rtree" => map_sqlite_type("REAL"),
+            "rtree_i32" => map_sqlite_type("INTEGER"),
+            _ => continue,
+        };
+        columns.push(Column {
+            ordinal_position: columns.len() as u32 + 1,
+            ..Column::new(name.to_string(), data_type)
+        });
+    }
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fts5_table() {
+        let vt = parse_virtual_table_sql(
+            "CREATE VIRTUAL TABLE docs USING fts5(title, body, tokenize = 'porter unicode61')",
+        )
+        .expect("should parse FTS5 table");
+        assert_eq!(vt.module, "fts5");
+        assert_eq!(
+            vt.arguments,
+            vec!["title", "body", "tokenize = 'porter unicode61'"]
+        );
+    }
+
+    #[test]
+    fn test_parse_rtree_table() {
+        let vt = parse_virtual_table_sql(
+            "create virtual table if not exists \"geo index\" using rtree(id, minX, maxX)",
+        )
+        .expect("should parse R*Tree table");
+        assert_eq!(vt.module, "rtree");
+        assert_eq!(vt.arguments, vec!["id", "minX", "maxX"]);
+    }
+
+    #[test]
+    fn test_parse_module_without_arguments() {
+        let vt = parse_virtual_table_sql("CREATE VIRTUAL TABLE temp.t1 USING custom_mod")
+            .expect("should parse argument-less module");
+        assert_eq!(vt.module, "custom_mod");
+        assert!(vt.arguments.is_empty());
+    }
+
+    #[test]
+    fn test_parse_nested_and_quoted_arguments() {
+        let vt = parse_virtual_table_sql(
+            "CREATE VIRTUAL TABLE x USING csv(filename='a,b.csv', schema='CREATE TABLE y(a,b)')",
+        )
+        .expect("should parse quoted arguments");
+        assert_eq!(
+            vt.arguments,
+            vec!["filename='a,b.csv'", "schema='CREATE TABLE y(a,b)'"]
+        );
+    }
+
+    #[test]
+    fn test_regular_table_is_not_virtual() {
+        assert!(parse_virtual_table_sql("CREATE TABLE using_table (using TEXT)").is_none());
+        assert!(parse_virtual_table_sql("CREATE TABLE t (a TEXT) -- VIRTUAL USING fts5").is_none());
+    }
+
+    #[test]
+    fn test_find_shadow_tables() {
+        let vt = parse_virtual_table_sql("CREATE VIRTUAL TABLE docs USING fts5(body)").unwrap();
+        let existing = ["docs", "docs_data", "docs_idx", "docs_config", "documents"];
+        assert_eq!(
+            find_shadow_tables("docs", &vt, &existing),
+            vec!["docs_data", "docs_idx", "docs_config"]
+        );
+
+        let custom = parse_virtual_table_sql("CREATE VIRTUAL TABLE c USING custom").unwrap();
+        assert!(find_shadow_tables("c", &custom, &["c_data"]).is_empty());
+    }
+
+    #[test]
+    fn test_columns_from_arguments() {
+        let names = |sql: &str| -> Vec<String> {
+            columns_from_arguments(&parse_virtual_table_sql(sql).unwrap())
+                .into_iter()
+                .map(|c| c.name)
+                .collect()
+        };
+        assert_eq!(
+            names("CREATE VIRTUAL TABLE d USING fts5(title, body UNINDEXED, tokenize = 'porter')"),
+            ["title", "body"]
+        );
+        assert_eq!(
+            names("CREATE VIRTUAL TABLE d USING fts4(\"sub ject\", body, tokenize=porter)"),
+            ["sub ject", "body"]
+        );
+        assert!(names("CREATE VIRTUAL TABLE c USING csv(filename='a.csv')").is_empty());
+
+        let rtree = columns_from_arguments(
+            &parse_virtual_table_sql(
+                "CREATE VIRTUAL TABLE g USING rtree(id, minX, maxX, +label TEXT)",
+            )
+            .unwrap(),
+        );
+        let types: Vec<_> = rtree
+            .iter()
+            .map(|c| (c.name.as_str(), &c.data_type, c.ordinal_position))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ("id", &map_sqlite_type("INTEGER"), 1),
+                ("minX", &map_sqlite_type("REAL"), 2),
+                ("maxX", &map_sqlite_type("REAL"), 3),
+                ("label", &map_sqlite_type("TEXT"), 4),
+            ]
+        );
+    }
+}
@@ -0,0 +1,526 @@
+//! Pseudonymization of identifying survey metadata.
+//!
+//! Server host names, database names, and user names (database owners, the
+//! collecting user, and the identity the access probe ran as) identify the
+//! surveyed organization. Before a survey
+//! is shared with a vendor or quoted in a public write-up,
+//! [`anonymize_survey`] replaces each of them with a keyed HMAC-SHA256
+//! pseudonym such as `db_3f9a1c0b7e42`.
+//!
+//! Pseudonyms are deterministic for a given key: the same database name
+//! maps to the same pseudonym in every survey anonymized with that key, so
+//! artifacts from one engagement can still be correlated, while nobody
+//! without the key can recover or confirm the original names.
+//!
+//! Occurrences of the replaced names in free text (warnings, collection
+//! errors, read-only and access evidence, SQLite auxiliary file names) are rewritten
+//! too. Schema object names and sampled rows are left untouched.
+//!
+//! Separately, [`strip_database_environment`] and [`strip_server_environment`]
+//! remove what a survey records about how and from where it was collected
+//! (`dbsurveyor-collect --no-metadata`): the collector version is cut to
+//! major.minor, timestamps are rounded down to the day, durations are
+//! zeroed, and the server address, connecting user, and access probe
+//! identity are cleared.
+
+use crate::models::{
+    CollectionMetadata, CollectionStatus, DatabaseInfo, DatabaseSchema, DatabaseServerSchema,
+    SurveyDocument, TableSample,
+};
+use crate::{DbSurveyorError, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hex characters of the HMAC kept in a pseudonym (48 bits).
+const PSEUDONYM_HEX_LEN: usize = 12;
+
+/// Kind of identifier being replaced; each kind has its own prefix, and
+/// the kind is part of the HMAC input so a user and a database with the
+/// same name get unrelated pseudonyms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IdentifierKind {
+    Server,
+    Database,
+    User,
+}
+
+impl IdentifierKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Server => "server",
+            Self::Database => "db",
+            Self::User => "user",
+        }
+    }
+}
+
+/// Maps identifiers to keyed pseudonyms.
+#[derive(Clone)]
+pub struct Pseudonymizer {
+    mac: HmacSha256,
+}
+
+impl std::fmt::Debug for Pseudonymizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pseudonymizer").finish_non_exhaustive()
+    }
+}
+
+impl Pseudonymizer {
+    /// Creates a pseudonymizer keyed with `key`.
+    ///
+    /// # Errors
+    /// Returns a configuration error if the key is empty.
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.is_empty() {
+            return Err(DbSurveyorError::configuration(
+                "Anonymization key must not be empty",
+            ));
+        }
+        let mac = HmacSha256::new_from_slice(key)
+            .map_err(|_| DbSurveyorError::configuration("Invalid anonymization key"))?;
+        Ok(Self { mac })
+    }
+
+    /// Pseudonym for `value`, e.g. `db_3f9a1c0b7e42`.
+    pub fn pseudonym(&self, kind: IdentifierKind, value: &str) -> String {
+        let mut mac = self.mac.clone();
+        mac.update(kind.prefix().as_bytes());
+        mac.update(&[0]);
+        mac.update(value.as_bytes());
+        let digest: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        format!("{}_{}", kind.prefix(), &digest[..PSEUDONYM_HEX_LEN])
+    }
+}
+
+/// Identifiers replaced by [`anonymize_survey`], keyed by kind and
+/// original value.
+pub type Replacements = BTreeMap<(IdentifierKind, String), String>;
+
+/// Replaces identifying metadata in `survey` with pseudonyms, returning
+/// what was replaced.
+///
+/// The returned map holds the original names; it is for reporting counts
+/// and must not be shared alongside the anonymized survey.
+pub fn anonymize_survey(survey: &mut SurveyDocument, pseudonyms: &Pseudonymizer) -> Replacements {
+    let mut replacements = Replacements::new();
+    match survey {
+        SurveyDocument::Database(schema) => {
+            anonymize_database_info(&mut schema.database_info, pseudonyms, &mut replacements);
+        }
+        SurveyDocument::Server(server) => anonymize_server(server, pseudonyms, &mut replacements),
+    }
+
+    // Free text may mention any identifier, so it is rewritten once every
+    // name is known; longer names go first so `sales_eu` is not rewritten
+    // through `sales`
+    let mut rewrites: Vec<(&str, &str)> = replacements
+        .iter()
+        .map(|((_, original), pseudonym)| (original.as_str(), pseudonym.as_str()))
+        .collect();
+    rewrites.sort_by_key(|(original, _)| std::cmp::Reverse(original.len()));
+    let rewrite = |text: &mut String| {
+        for (original, pseudonym) in &rewrites {
+            *text = replace_identifier(text, original, pseudonym);
+        }
+    };
+    match survey {
+        SurveyDocument::Database(schema) => rewrite_schema_text(schema, &rewrite),
+        SurveyDocument::Server(server) => {
+            rewrite_metadata_text(&mut server.collection_metadata, &rewrite);
+            for schema in &mut server.databases {
+                rewrite_schema_text(schema, &rewrite);
+            }
+            for entry in &mut server.manifest {
+                rewrite_status_text(&mut entry.collection_status, &rewrite);
+            }
+            for tenant in server
+                .tenant_groups
+                .iter_mut()
+                .flat_map(|group| &mut group.tenants)
+            {
+                rewrite_status_text(&mut tenant.database_info.collection_status, &rewrite);
+                rewrite_metadata_text(&mut tenant.collection_metadata, &rewrite);
+            }
+        }
+    }
+    replacements
+}
+
+fn anonymize_server(
+    server: &mut DatabaseServerSchema,
+    pseudonyms: &Pseudonymizer,
+    replacements: &mut Replacements,
+) {
+    let info = &mut server.server_info;
+    info.host = replace(pseudonyms, replacements, IdentifierKind::Server, &info.host);
+    info.connection_user = replace(
+        pseudonyms,
+        replacements,
+        IdentifierKind::User,
+        &info.connection_user,
+    );
+    for schema in &mut server.databases {
+        anonymize_database_info(&mut schema.database_info, pseudonyms, replacements);
+    }
+    for entry in &mut server.manifest {
+        entry.name = replace(
+            pseudonyms,
+            replacements,
+            IdentifierKind::Database,
+            &entry.name,
+        );
+    }
+    for group in &mut server.tenant_groups {
+        group.canonical = replace(
+            pseudonyms,
+            replacements,
+            IdentifierKind::Database,
+            &group.canonical,
+        );
+        for tenant in &mut group.tenants {
+            anonymize_database_info(&mut tenant.database_info, pseudonyms, replacements);
+        }
+    }
+}
+
+fn anonymize_database_info(
+    info: &mut DatabaseInfo,
+    pseudonyms: &Pseudonymizer,
+    replacements: &mut Replacements,
+) {
+    info.name = replace(
+        pseudonyms,
+        replacements,
+        IdentifierKind::Database,
+        &info.name,
+    );
+    if let Some(owner) = &mut info.owner {
+        *owner = replace(pseudonyms, replacements, IdentifierKind::User, owner);
+    }
+    if let Some(identity) = info
+        .access_probe
+        .as_mut()
+        .and_then(|probe| probe.identity.as_mut())
+    {
+        *identity = replace(pseudonyms, replacements, IdentifierKind::User, identity);
+    }
+}
+
+fn replace(
+    pseudonyms: &Pseudonymizer,
+    replacements: &mut Replacements,
+    kind: IdentifierKind,
+    value: &str,
+) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+    replacements
+        .entry((kind, value.to_string()))
+        .or_insert_with(|| pseudonyms.pseudonym(kind, value))
+        .clone()
+}
+
+fn rewrite_schema_text(schema: &mut DatabaseSchema, rewrite: &impl Fn(&mut String)) {
+    rewrite_status_text(&mut schema.database_info.collection_status, rewrite);
+    if let Some(probe) = &mut schema.database_info.access_probe {
+        probe.evidence.iter_mut().for_each(rewrite);
+    }
+    rewrite_metadata_text(&mut schema.collection_metadata, rewrite);
+}
+
+fn rewrite_status_text(status: &mut CollectionStatus, rewrite: &impl Fn(&mut String)) {
+    match status {
+        CollectionStatus::Failed { error } => rewrite(error),
+        CollectionStatus::Skipped { reason } => rewrite(reason),
+        CollectionStatus::Success | CollectionStatus::Partial { .. } => {}
+    }
+}
+
+fn rewrite_metadata_text(metadata: &mut CollectionMetadata, rewrite: &impl Fn(&mut String)) {
+    for warning in &mut metadata.warnings {
+        rewrite(&mut warning.message);
+        warning.objects.iter_mut().for_each(rewrite);
+    }
+    for failure in &mut metadata.object_failures {
+        rewrite(&mut failure.error);
+    }
+    if let Some(read_only) = &mut metadata.read_only {
+        read_only.evidence.iter_mut().for_each(rewrite);
+    }
+    if let Some(integrity) = &mut metadata.integrity {
+        integrity.problems.iter_mut().for_each(rewrite);
+    }
+    for file in &mut metadata.auxiliary_files {
+        rewrite(&mut file.file_name);
+    }
+    if let Some(settings) = &mut metadata.settings {
+        settings.exclude_databases.iter_mut().for_each(rewrite);
+    }
+}
+
+/// Strips the collection environment from a database survey.
+pub fn strip_database_environment(schema: &mut DatabaseSchema) {
+    if let Some(probe) = &mut schema.database_info.access_probe {
+        probe.identity = None;
+    }
+    strip_metadata(&mut schema.collection_metadata);
+    strip_samples(schema.samples.as_deref_mut());
+}
+
+/// Strips the collection environment from a server survey, including its
+/// databases. Mentions of the server address or connecting user in
+/// warnings and errors become `[host]` and `[user]`.
+pub fn strip_server_environment(server: &mut DatabaseServerSchema) {
+    let info = &mut server.server_info;
+    let mut rewrites: Vec<(String, &str)> = [
+        (std::mem::take(&mut info.host), "[host]"),
+        (std::mem::take(&mut info.connection_user), "[user]"),
+    ]
+    .into_iter()
+    .filter(|(original, _)| !original.is_empty())
+    .collect();
+    info.port = None;
+    rewrites.sort_by_key(|(original, _)| std::cmp::Reverse(original.len()));
+    let rewrite = |text: &mut String| {
+        for (original, placeholder) in &rewrites {
+            *text = replace_identifier(text, original, placeholder);
+        }
+    };
+
+    strip_metadata(&mut server.collection_metadata);
+    rewrite_metadata_text(&mut server.collection_metadata, &rewrite);
+    for schema in &mut server.databases {
+        strip_database_environment(schema);
+        rewrite_schema_text(schema, &rewrite);
+    }
+    for entry in &mut server.manifest {
+        rewrite_status_text(&mut entry.collection_status, &rewrite);
+    }
+    for tenant in server
+        .tenant_groups
+        .iter_mut()
+        .flat_map(|group| &mut group.tenants)
+    {
+        strip_metadata(&mut tenant.collection_metadata);
+        strip_samples(tenant.samples.as_deref_mut());
+        rewrite_status_text(&mut tenant.database_info.collection_status, &rewrite);
+        rewrite_metadata_text(&mut tenant.collection_metadata, &rewrite);
+    }
+}
+
+fn strip_metadata(metadata: &mut CollectionMetadata) {
+    metadata.collected_at = start_of_day(metadata.collected_at);
+    metadata.collection_duration_ms = 0;
+    metadata.collector_version = major_minor(&metadata.collector_version);
+    if let Some(integrity) = &mut metadata.integrity {
+        integrity.duration_ms = 0;
+    }
+}
+
+fn strip_samples(samples: Option<&mut [TableSample]>) {
+    for sample in samples.unwrap_or_default() {
+        sample.collected_at = start_of_day(sample.collected_at);
+    }
+}
+
+/// Midnight UTC of the day of `timestamp`.
+fn start_of_day(timestamp: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+    timestamp
+        .date_naive()
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc()
+}
+
+/// The major and minor components of a version (`1.4.2` becomes `1.4`).
+fn major_minor(version: &str) -> String {
+    version.splitn(3, '.').take(2).collect::<Vec<_>>().join(".")
+}
+
+/// Replaces occurrences of `original` in `text` that are not part of a
+/// longer word, so the database `db` does not rewrite `dbsurveyor`.
+fn replace_identifier(text: &str, original: &str, pseudonym: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(original) {
+        let end = start + original.len();
+        let before = rest[..start].chars().next_back();
+        let after = rest[end..].chars().next();
+        result.push_str(&rest[..start]);
+        if before.is_some_and(is_word) || after.is_some_and(is_word) {
+            result.push_str(original);
+        } else {
+            result.push_str(pseudonym);
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AccessProbe, CollectionMode, DatabaseType, ServerInfo};
+
+    fn database(name: &str, owner: &str) -> DatabaseSchema {
+        let mut info = DatabaseInfo::new(name.to_string());
+        info.owner = Some(owner.to_string());
+        DatabaseSchema::new(info)
+    }
+
+    fn server_survey() -> SurveyDocument {
+        let mut sales = database("sales", "alice");
+        sales
+            .collection_metadata
+            .warnings
+            .push("Permission denied for alice on sales.audit (host db.acme.internal)".into());
+        sales.database_info.access_probe = Some(AccessProbe {
+            superuser: false,
+            catalog_access: true,
+            readable_tables: 1,
+            total_tables: 2,
+            identity: Some("alice".to_string()),
+            evidence: vec!["role alice is not a superuser".to_string()],
+        });
+        let mut hr = database("hr", "bob");
+        hr.database_info.collection_status = CollectionStatus::Failed {
+            error: "database \"hr\" is not accepting connections".to_string(),
+        };
+        let metadata = hr.collection_metadata.clone();
+        let info = ServerInfo {
+            server_type: DatabaseType::PostgreSQL,
+            version: "16.2".to_string(),
+            host: "db.acme.internal".to_string(),
+            port: Some(5432),
+            total_databases: 2,
+            collected_databases: 2,
+            system_databases_excluded: 0,
+            connection_user: "alice".to_string(),
+            has_superuser_privileges: false,
+            collection_mode: CollectionMode::SingleDatabase,
+            timezone: None,
+            role: None,
+        };
+        SurveyDocument::Server(Box::new(DatabaseServerSchema::new(
+            info,
+            vec![sales, hr],
+            metadata,
+        )))
+    }
+
+    #[test]
+    fn test_pseudonyms_are_keyed_and_deterministic() {
+        let first = Pseudonymizer::new(b"engagement-1").expect("valid key");
+        let second = Pseudonymizer::new(b"engagement-2").expect("valid key");
+
+        let sales = first.pseudonym(IdentifierKind::Database, "sales");
+        assert!(sales.starts_with("db_"));
+        assert_eq!(sales.len(), "db_".len() + PSEUDONYM_HEX_LEN);
+        assert_eq!(sales, first.pseudonym(IdentifierKind::Database, "sales"));
+        assert_ne!(sales, second.pseudonym(IdentifierKind::Database, "sales"));
+        assert_ne!(
+            first.pseudonym(IdentifierKind::User, "sales")[5..],
+            sales[3..]
+        );
+        assert!(Pseudonymizer::new(b"").is_err());
+    }
+
+    #[test]
+    fn test_anonymize_server_survey() {
+        let pseudonyms = Pseudonymizer::new(b"engagement-1").expect("valid key");
+        let mut survey = server_survey();
+        let replacements = anonymize_survey(&mut survey, &pseudonyms);
+        assert_eq!(replacements.len(), 5);
+
+        let SurveyDocument::Server(server) = &survey else {
+            panic!("expected a server survey");
+        };
+        let host = pseudonyms.pseudonym(IdentifierKind::Server, "db.acme.internal");
+        let alice = pseudonyms.pseudonym(IdentifierKind::User, "alice");
+        let sales = pseudonyms.pseudonym(IdentifierKind::Database, "sales");
+        assert_eq!(server.server_info.host, host);
+        assert_eq!(server.server_info.connection_user, alice);
+        assert_eq!(server.databases[0].database_info.name, sales);
+        assert_eq!(server.databases[0].database_info.owner, Some(alice.clone()));
+        let probe = server.databases[0]
+            .database_info
+            .access_probe
+            .as_ref()
+            .expect("probe kept");
+        assert_eq!(probe.identity.as_deref(), Some(alice.as_str()));
+        assert_eq!(
+            probe.evidence[0],
+            format!("role {} is not a superuser", alice)
+        );
+        assert_eq!(server.manifest[0].name, sales);
+
+        assert_eq!(
+            server.databases[0].collection_metadata.warnings[0].message,
+            format!(
+                "Permission denied for {} on {}.audit (host {})",
+                alice, sales, host
+            )
+        );
+        let serialized = serde_json::to_string(server.as_ref()).expect("serializes");
+        for original in ["db.acme.internal", "alice", "bob", "\\\"hr\\\""] {
+            assert!(!serialized.contains(original), "{original} leaked");
+        }
+    }
+
+    #[test]
+    fn test_strip_server_environment() {
+        let SurveyDocument::Server(mut server) = server_survey() else {
+            panic!("expected a server survey");
+        };
+        server.collection_metadata.collected_at =
+            "2026-03-14T15:09:26.535Z".parse().expect("valid timestamp");
+        server.collection_metadata.collection_duration_ms = 4_200;
+        server.collection_metadata.collector_version = "1.4.2".to_string();
+
+        strip_server_environment(&mut server);
+
+        let metadata = &server.collection_metadata;
+        assert_eq!(
+            metadata.collected_at.to_rfc3339(),
+            "2026-03-14T00:00:00+00:00"
+        );
+        assert_eq!(metadata.collection_duration_ms, 0);
+        assert_eq!(metadata.collector_version, "1.4");
+        assert_eq!(server.server_info.host, "");
+        assert_eq!(server.server_info.connection_user, "");
+        assert_eq!(server.server_info.port, None);
+        assert_eq!(
+            server.databases[0].collection_metadata.warnings[0].message,
+            "Permission denied for [user] on sales.audit (host [host])"
+        );
+        let probe = server.databases[0]
+            .database_info
+            .access_probe
+            .as_ref()
+            .expect("probe kept");
+        assert_eq!(probe.identity, None);
+        assert_eq!(probe.evidence[0], "role [user] is not a superuser");
+        // Database names are not environment; anonymize_survey covers them
+        assert_eq!(server.databases[0].database_info.name, "sales");
+    }
+
+    #[test]
+    fn test_replace_identifier_respects_word_boundaries() {
+        assert_eq!(
+            replace_identifier("db and dbsurveyor on db.", "db", "X"),
+            "X and dbsurveyor on X."
+        );
+        assert_eq!(replace_identifier("sales_eu", "sales", "X"), "sales_eu");
+    }
+}
@@ -0,0 +1,215 @@
+//! Canonical JSON serialization for signing and diffing.
+//!
+//! Canonical output is byte-for-byte reproducible for the same survey data,
+//! independent of `serde_json` version or map ordering, so signatures,
+//! checksums, and textual diffs stay stable. The rules follow RFC 8785
+//! (JSON Canonicalization Scheme) closely:
+//!
+//! - Object keys are sorted by their UTF-16 code units
+//! - No insignificant whitespace
+//! - Integers are written as-is; other numbers use the shortest decimal
+//!   form that round-trips, without an exponent, and integral floats drop
+//!   the fractional part (`1.0` becomes `1`)
+//! - Strings escape only `"`, `\`, and control characters
+//! - The survey's own timestamp fields (`collected_at`, `analyzed_at`,
+//!   `recorded_at`) are rewritten in UTC with microsecond precision
+//!   (`2024-01-02T03:04:05.000000Z`); every other string, including sampled
+//!   rows and validator documents, is kept byte-for-byte
+//!
+//! Canonicalizing canonical output is a no-op.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Keys holding timestamps written by the collector.
+const TIMESTAMP_FIELDS: &[&str] = &["collected_at", "analyzed_at", "recorded_at"];
+
+/// Keys holding data copied from the database, whose own keys may collide
+/// with [`TIMESTAMP_FIELDS`].
+const DATA_FIELDS: &[&str] = &["rows", "definition", "deviations"];
+
+/// Where a value sits in the document.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// Under a survey field other than a timestamp
+    Field,
+    /// Directly under one of [`TIMESTAMP_FIELDS`]
+    Timestamp,
+    /// Anywhere below one of [`DATA_FIELDS`]
+    Data,
+}
+
+/// Serializes a JSON value in canonical form.
+pub fn to_canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, Position::Field);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, position: Position) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(number) => write_number(out, number),
+        Value::String(s) => match normalize_timestamp(s) {
+            Some(timestamp) if position == Position::Timestamp => write_string(out, &timestamp),
+            _ => write_string(out, s),
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, item, position);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                let position = if position == Position::Data || DATA_FIELDS.contains(&key.as_str())
+                {
+                    Position::Data
+                } else if TIMESTAMP_FIELDS.contains(&key.as_str()) {
+                    Position::Timestamp
+                } else {
+                    Position::Field
+                };
+                write_value(out, item, position);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, number: &serde_json::Number) {
+    if let Some(n) = number.as_u64() {
+        let _ = write!(out, "{n}");
+    } else if let Some(n) = number.as_i64() {
+        let _ = write!(out, "{n}");
+    } else if let Some(f) = number.as_f64() {
+        // `Display` for f64 is the shortest round-tripping decimal and never
+        // uses exponent notation
+        if f == 0.0 {
+            out.push('0');
+        } else {
+            let _ = write!(out, "{f}");
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < '\u{20}' => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Rewrites an RFC 3339 timestamp in UTC with fixed precision.
+fn normalize_timestamp(s: &str) -> Option<String> {
+    // Cheap shape check before attempting a full parse
+    let bytes = s.as_bytes();
+    if bytes.len() < 20 || bytes.get(4) != Some(&b'-') || bytes.get(10) != Some(&b'T') {
+        return None;
+    }
+    let parsed = DateTime::parse_from_rfc3339(s).ok()?;
+    Some(
+        parsed
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Micros, true),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_keys_sorted_and_whitespace_removed() {
+        let value = json!({"b": [1, {"z": null, "a": true}], "a": "x", "B": 2});
+        assert_eq!(
+            to_canonical_json(&value),
+            r#"{"B":2,"a":"x","b":[1,{"a":true,"z":null}]}"#
+        );
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        let value = json!([1.0, -2.5, 0.1, 1e-7, -0.0, 12345678901234567890u64, -3]);
+        assert_eq!(
+            to_canonical_json(&value),
+            "[1,-2.5,0.1,0.0000001,0,12345678901234567890,-3]"
+        );
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let value = json!("quote\" slash\\ tab\t bell\u{07} é");
+        assert_eq!(
+            to_canonical_json(&value),
+            "\"quote\\\" slash\\\\ tab\\t bell\\u0007 é\""
+        );
+    }
+
+    #[test]
+    fn test_timestamps_normalized_to_utc() {
+        let value = json!({
+            "collected_at": "2024-01-02T05:04:05.5+02:00",
+            "analyzed_at": "2024-01-02T03:04:05Z",
+            "recorded_at": "2024-01-02"
+        });
+        assert_eq!(
+            to_canonical_json(&value),
+            r#"{"analyzed_at":"2024-01-02T03:04:05.000000Z","collected_at":"2024-01-02T03:04:05.500000Z","recorded_at":"2024-01-02"}"#
+        );
+    }
+
+    #[test]
+    fn test_data_timestamps_kept_verbatim() {
+        let value = json!({
+            "comment": "2024-01-02T05:04:05+02:00",
+            "samples": [{
+                "collected_at": "2024-01-02T05:04:05+02:00",
+                "rows": [{
+                    "created": "2024-01-02T05:04:05+02:00",
+                    "collected_at": "2024-01-02T05:04:05.5+02:00"
+                }]
+            }]
+        });
+        assert_eq!(
+            to_canonical_json(&value),
+            r#"{"comment":"2024-01-02T05:04:05+02:00","samples":[{"collected_at":"2024-01-02T03:04:05.000000Z","rows":[{"collected_at":"2024-01-02T05:04:05.5+02:00","created":"2024-01-02T05:04:05+02:00"}]}]}"#
+        );
+    }
+
+    #[test]
+    fn test_canonical_output_is_idempotent() {
+        let value = json!({"n": 1.50, "collected_at": "2024-06-01T00:00:00.123456789+00:00", "s": "\u{1F}"});
+        let once = to_canonical_json(&value);
+        let reparsed: Value = serde_json::from_str(&once).expect("canonical JSON parses");
+        assert_eq!(to_canonical_json(&reparsed), once);
+    }
+}
@@ -0,0 +1,213 @@
+//! Collation and character set analysis.
+//!
+//! Comparisons and joins between text columns with different collations or
+//! character sets either fail outright or silently sort, deduplicate, and
+//! match differently than their neighbours. [`summarize_collations`] reports
+//! the database default alongside every column that overrides it.
+//!
+//! A column without a collation of its own uses the database default, so
+//! only explicit collations and character sets can mismatch. Names are
+//! compared case-insensitively.
+
+use crate::models::{DatabaseSchema, Table};
+use std::collections::BTreeMap;
+
+/// Database-level collation and encoding with per-column overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollationSummary {
+    /// Default collation of the database
+    pub database_collation: Option<String>,
+    /// Default character set (encoding) of the database
+    pub database_encoding: Option<String>,
+    /// Number of columns per explicit collation
+    pub collations: BTreeMap<String, usize>,
+    /// Number of columns per explicit character set
+    pub character_sets: BTreeMap<String, usize>,
+    /// Columns whose collation or character set differs from the database default
+    pub mismatches: Vec<CollationMismatch>,
+}
+
+impl CollationSummary {
+    /// Returns true if columns use more than one collation or character set.
+    pub fn is_mixed(&self) -> bool {
+        !self.mismatches.is_empty()
+    }
+}
+
+/// A column that overrides the database collation or character set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollationMismatch {
+    /// Qualified table name
+    pub table: String,
+    pub column: String,
+    /// Column collation, when it differs from the database default
+    pub collation: Option<String>,
+    /// Column character set, when it differs from the database default
+    pub character_set: Option<String>,
+}
+
+impl CollationMismatch {
+    /// Qualified column name (`schema.table.column`).
+    pub fn object(&self) -> String {
+        format!("{}.{}", self.table, self.column)
+    }
+}
+
+/// Summarizes collations and character sets and finds columns that
+/// differ from the database default.
+///
+/// Without a database default (e.g. SQLite collation), every explicit value
+/// counts as an override.
+pub fn summarize_collations(schema: &DatabaseSchema) -> CollationSummary {
+    let database_collation = schema.database_info.collation.clone();
+    let database_encoding = schema.database_info.encoding.clone();
+    let mut summary = CollationSummary {
+        database_collation,
+        database_encoding,
+        ..CollationSummary::default()
+    };
+
+    for table in &schema.tables {
+        for column in &table.columns {
+            if let Some(collation) = &column.collation {
+                *summary.collations.entry(collation.clone()).or_default() += 1;
+            }
+            if let Some(character_set) = &column.character_set {
+                *summary
+                    .character_sets
+                    .entry(character_set.clone())
+                    .or_default() += 1;
+            }
+
+            let collation = differing(
+                column.collation.as_deref(),
+                summary.database_collation.as_deref(),
+            );
+            let character_set = differing(
+                column.character_set.as_deref(),
+                summary.database_encoding.as_deref(),
+            );
+            if collation.is_some() || character_set.is_some() {
+                summary.mismatches.push(CollationMismatch {
+                    table: table_name(table),
+                    column: column.name.clone(),
+                    collation,
+                    character_set,
+                });
+            }
+        }
+    }
+
+    summary
+}
+
+fn differing(column: Option<&str>, default: Option<&str>) -> Option<String> {
+    match (column, default) {
+        (Some(column), Some(default)) if column.eq_ignore_ascii_case(default) => None,
+        (Some(column), _) => Some(column.to_string()),
+        (None, _) => None,
+    }
+}
+
+fn table_name(table: &Table) -> String {
+    match &table.schema {
+        Some(schema) => format!("{}.{}", schema, table.name),
+        None => table.name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, DatabaseInfo, UnifiedDataType};
+
+    fn column(name: &str, collation: Option<&str>, character_set: Option<&str>) -> Column {
+        Column {
+            collation: collation.map(str::to_string),
+            character_set: character_set.map(str::to_string),
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        }
+    }
+
+    fn schema(
+        collation: Option<&str>,
+        encoding: Option<&str>,
+        columns: Vec<Column>,
+    ) -> DatabaseSchema {
+        let mut info = DatabaseInfo::new("shop".to_string());
+        info.collation = collation.map(str::to_string);
+        info.encoding = encoding.map(str::to_string);
+        let mut schema = DatabaseSchema::new(info);
+        schema.tables.push(Table::new(
+            "customers".to_string(),
+            Some("shop".to_string()),
+            columns,
+        ));
+        schema
+    }
+
+    #[test]
+    fn test_uniform_collation_is_not_mixed() {
+        let summary = summarize_collations(&schema(
+            Some("utf8mb4_0900_ai_ci"),
+            Some("utf8mb4"),
+            vec![
+                column("name", Some("utf8mb4_0900_ai_ci"), Some("utf8mb4")),
+                column("email", Some("UTF8MB4_0900_AI_CI"), Some("utf8mb4")),
+                column("id", None, None),
+            ],
+        ));
+        assert!(!summary.is_mixed());
+        assert_eq!(summary.collations.len(), 2);
+        assert_eq!(summary.character_sets["utf8mb4"], 2);
+    }
+
+    #[test]
+    fn test_mismatched_columns_reported() {
+        let summary = summarize_collations(&schema(
+            Some("utf8mb4_0900_ai_ci"),
+            Some("utf8mb4"),
+            vec![
+                column("name", Some("utf8mb4_0900_ai_ci"), Some("utf8mb4")),
+                column("code", Some("latin1_swedish_ci"), Some("latin1")),
+                column("slug", Some("utf8mb4_bin"), Some("utf8mb4")),
+            ],
+        ));
+        assert!(summary.is_mixed());
+        assert_eq!(
+            summary.mismatches,
+            vec![
+                CollationMismatch {
+                    table: "shop.customers".to_string(),
+                    column: "code".to_string(),
+                    collation: Some("latin1_swedish_ci".to_string()),
+                    character_set: Some("latin1".to_string()),
+                },
+                CollationMismatch {
+                    table: "shop.customers".to_string(),
+                    column: "slug".to_string(),
+                    collation: Some("utf8mb4_bin".to_string()),
+                    character_set: None,
+                },
+            ]
+        );
+        assert_eq!(summary.mismatches[0].object(), "shop.customers.code");
+    }
+
+    #[test]
+    fn test_explicit_collation_without_database_default() {
+        let summary = summarize_collations(&schema(
+            None,
+            Some("UTF-8"),
+            vec![
+                column("email", Some("NOCASE"), None),
+                column("name", None, None),
+            ],
+        ));
+        assert_eq!(summary.mismatches.len(), 1);
+        assert_eq!(summary.mismatches[0].column, "email");
+    }
+}
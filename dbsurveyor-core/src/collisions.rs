@@ -0,0 +1,378 @@
+//! Name collisions across the databases of a fleet.
+//!
+//! Sharded and per-environment deployments are expected to share one
+//! schema, so a table that has the same name everywhere but a different
+//! definition in some databases is easy to miss and a common source of
+//! failed migrations and confusing query results. [`CollisionFinder`]
+//! collects the tables and views of any number of databases and reports:
+//!
+//! - Collisions: a qualified name defined differently in two or more
+//!   databases, with each definition's differences from the most common one
+//! - Shadowing: one unqualified name used in several schemas of the same
+//!   database, where an unqualified reference resolves to whichever schema
+//!   comes first on the search path
+//!
+//! Tables are compared like `baseline check` compares them (see
+//! [`crate::drift`]): columns, primary keys, indexes, and foreign keys, but
+//! not row counts or comments. Views are compared by definition, ignoring
+//! whitespace.
+//!
+//! Only one copy of each distinct definition is kept, so databases can be
+//! added one at a time from a streamed bundle.
+
+use crate::drift::{DriftObject, SchemaChange, diff_tables};
+use crate::models::{DatabaseSchema, Table, View};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A name defined differently in two or more databases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NameCollision {
+    /// `table` or `view`
+    pub object_kind: DriftObject,
+    /// Qualified object name
+    pub name: String,
+    /// Distinct definitions, most widely used first
+    pub definitions: Vec<Definition>,
+}
+
+/// One definition of a colliding name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Definition {
+    /// Databases using this definition
+    pub databases: Vec<String>,
+    /// Differences from the first definition; empty for the first
+    pub differences: Vec<SchemaChange>,
+}
+
+/// An unqualified name used in several schemas of one database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShadowedName {
+    pub database: String,
+    pub name: String,
+    /// Qualified names of the tables and views sharing the name, sorted
+    pub objects: Vec<String>,
+}
+
+/// Findings over every database added to a [`CollisionFinder`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CollisionReport {
+    /// Databases compared
+    pub databases: usize,
+    pub collisions: Vec<NameCollision>,
+    pub shadowed: Vec<ShadowedName>,
+}
+
+/// A distinct definition seen so far and where it is used.
+struct Variant<T> {
+    representative: T,
+    databases: Vec<String>,
+}
+
+/// Accumulates databases and finds names defined differently between them.
+#[derive(Default)]
+pub struct CollisionFinder {
+    databases: usize,
+    tables: BTreeMap<String, Vec<Variant<Table>>>,
+    views: BTreeMap<String, Vec<Variant<String>>>,
+    shadowed: Vec<ShadowedName>,
+}
+
+impl CollisionFinder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the tables and views of one database, identified in findings
+    /// by `label`.
+    pub fn add(&mut self, label: &str, schema: &DatabaseSchema) {
+        self.databases += 1;
+        // A name listed twice in one survey counts once, as its first entry
+        let mut seen = BTreeSet::new();
+        for table in &schema.tables {
+            let name = qualified(table.schema.as_deref(), &table.name);
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let variants = self.tables.entry(name.clone()).or_default();
+            match variants
+                .iter_mut()
+                .find(|v| diff_tables(label, &name, &v.representative, table).is_empty())
+            {
+                Some(variant) => variant.databases.push(label.to_string()),
+                None => variants.push(Variant {
+                    representative: table.clone(),
+                    databases: vec![label.to_string()],
+                }),
+            }
+        }
+        seen.clear();
+        for view in &schema.views {
+            let name = qualified(view.schema.as_deref(), &view.name);
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let definition = normalized_definition(view);
+            let variants = self.views.entry(name).or_default();
+            match variants.iter_mut().find(|v| v.representative == definition) {
+                Some(variant) => variant.databases.push(label.to_string()),
+                None => variants.push(Variant {
+                    representative: definition,
+                    databases: vec![label.to_string()],
+                }),
+            }
+        }
+        self.shadowed.extend(shadowed_names(label, schema));
+    }
+
+    /// Collisions in name order, then shadowed names in database order.
+    pub fn finish(self) -> CollisionReport {
+        let mut collisions = Vec::new();
+        for (name, mut variants) in self.tables {
+            if variants.len() < 2 {
+                continue;
+            }
+            // Stable, so ties keep the definition seen first
+            variants.sort_by_key(|v| std::cmp::Reverse(v.databases.len()));
+            let reference = &variants[0].representative;
+            let definitions = variants
+                .iter()
+                .enumerate()
+                .map(|(position, variant)| Definition {
+                    databases: variant.databases.clone(),
+                    differences: if position == 0 {
+                        Vec::new()
+                    } else {
+                        diff_tables(
+                            &variant.databases[0],
+                            &name,
+                            reference,
+                            &variant.representative,
+                        )
+                    },
+                })
+                .collect();
+            collisions.push(NameCollision {
+                object_kind: DriftObject::Table,
+                name,
+                definitions,
+            });
+        }
+        for (name, mut variants) in self.views {
+            if variants.len() < 2 {
+                continue;
+            }
+            variants.sort_by_key(|v| std::cmp::Reverse(v.databases.len()));
+            let definitions = variants
+                .into_iter()
+                .enumerate()
+                .map(|(position, variant)| Definition {
+                    differences: if position == 0 {
+                        Vec::new()
+                    } else {
+                        vec![SchemaChange {
+                            database: variant.databases[0].clone(),
+                            kind: crate::drift::ChangeKind::Modified,
+                            object_kind: DriftObject::View,
+                            object: name.clone(),
+                            detail: Some("definition".to_string()),
+                        }]
+                    },
+                    databases: variant.databases,
+                })
+                .collect();
+            collisions.push(NameCollision {
+                object_kind: DriftObject::View,
+                name,
+                definitions,
+            });
+        }
+        collisions.sort_by(|a, b| a.name.cmp(&b.name).then(a.object_kind.cmp(&b.object_kind)));
+
+        CollisionReport {
+            databases: self.databases,
+            collisions,
+            shadowed: self.shadowed,
+        }
+    }
+}
+
+/// Unqualified table and view names defined in more than one schema.
+fn shadowed_names(label: &str, schema: &DatabaseSchema) -> Vec<ShadowedName> {
+    let mut by_name: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+    let tables = schema
+        .tables
+        .iter()
+        .map(|t| (t.schema.as_deref(), t.name.as_str()));
+    let views = schema
+        .views
+        .iter()
+        .map(|v| (v.schema.as_deref(), v.name.as_str()));
+    for (object_schema, name) in tables.chain(views) {
+        if object_schema.is_some() {
+            by_name
+                .entry(name)
+                .or_default()
+                .insert(qualified(object_schema, name));
+        }
+    }
+    by_name
+        .into_iter()
+        .filter(|(_, objects)| objects.len() > 1)
+        .map(|(name, objects)| ShadowedName {
+            database: label.to_string(),
+            name: name.to_string(),
+            objects: objects.into_iter().collect(),
+        })
+        .collect()
+}
+
+fn normalized_definition(view: &View) -> String {
+    view.definition
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn qualified(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drift::ChangeKind;
+    use crate::models::{Column, DatabaseInfo, UnifiedDataType};
+
+    fn column(name: &str, bits: u8) -> Column {
+        Column {
+            is_nullable: false,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::Integer { bits, signed: true },
+            )
+        }
+    }
+
+    fn table(schema: &str, name: &str, columns: Vec<Column>, row_count: u64) -> Table {
+        Table {
+            row_count: Some(row_count),
+            ..Table::new(name.to_string(), Some(schema.to_string()), columns)
+        }
+    }
+
+    fn database(name: &str, tables: Vec<Table>, view: &str) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new(name.to_string()));
+        schema.tables = tables;
+        schema.views = vec![View {
+            name: "active_orders".to_string(),
+            schema: Some("public".to_string()),
+            definition: Some(view.to_string()),
+            columns: Vec::new(),
+            comment: None,
+        }];
+        schema
+    }
+
+    #[test]
+    fn test_collisions_against_most_common_definition() {
+        let orders = || vec![column("id", 64), column("total", 32)];
+        let view = "SELECT * FROM orders WHERE active";
+        let mut finder = CollisionFinder::new();
+        finder.add(
+            "eu/shop",
+            &database("shop", vec![table("public", "orders", orders(), 10)], view),
+        );
+        // Row counts and view whitespace do not make a definition differ
+        finder.add(
+            "us/shop",
+            &database(
+                "shop",
+                vec![table("public", "orders", orders(), 99)],
+                "SELECT *\n  FROM orders WHERE active",
+            ),
+        );
+        finder.add(
+            "ap/shop",
+            &database(
+                "shop",
+                vec![table(
+                    "public",
+                    "orders",
+                    vec![column("id", 32), column("region", 16)],
+                    5,
+                )],
+                view,
+            ),
+        );
+
+        let report = finder.finish();
+        assert_eq!(report.databases, 3);
+        assert_eq!(report.collisions.len(), 1);
+        let collision = &report.collisions[0];
+        assert_eq!(collision.name, "public.orders");
+        assert_eq!(collision.definitions[0].databases, ["eu/shop", "us/shop"]);
+        assert!(collision.definitions[0].differences.is_empty());
+
+        let outlier = &collision.definitions[1];
+        assert_eq!(outlier.databases, ["ap/shop"]);
+        let changes: Vec<(ChangeKind, &str)> = outlier
+            .differences
+            .iter()
+            .map(|c| (c.kind, c.object.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                (ChangeKind::Modified, "public.orders.id"),
+                (ChangeKind::Removed, "public.orders.total"),
+                (ChangeKind::Added, "public.orders.region"),
+            ]
+        );
+        assert_eq!(
+            outlier.differences[0].detail.as_deref(),
+            Some("type: int64 -> int32")
+        );
+    }
+
+    #[test]
+    fn test_view_collisions_and_shadowing() {
+        let mut finder = CollisionFinder::new();
+        finder.add(
+            "prod",
+            &database(
+                "shop",
+                vec![
+                    table("public", "customers", vec![column("id", 64)], 1),
+                    table("archive", "customers", vec![column("id", 64)], 1),
+                    // Listed twice: only the first entry counts
+                    table("public", "customers", vec![column("id", 32)], 1),
+                ],
+                "SELECT 1",
+            ),
+        );
+        finder.add("staging", &database("shop", Vec::new(), "SELECT 2"));
+
+        let report = finder.finish();
+        assert_eq!(report.collisions.len(), 1);
+        assert_eq!(report.collisions[0].object_kind, DriftObject::View);
+        assert_eq!(report.collisions[0].definitions[1].databases, ["staging"]);
+
+        assert_eq!(
+            report.shadowed,
+            [ShadowedName {
+                database: "prod".to_string(),
+                name: "customers".to_string(),
+                objects: vec![
+                    "archive.customers".to_string(),
+                    "public.customers".to_string()
+                ],
+            }]
+        );
+    }
+}
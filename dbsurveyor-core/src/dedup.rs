@@ -0,0 +1,398 @@
+//! Deduplication of structurally identical multi-tenant databases.
+//!
+//! Servers that give every tenant its own database often hold hundreds of
+//! copies of one schema. [`deduplicate_tenants`] keeps a single canonical
+//! schema per distinct structure in `DatabaseServerSchema::databases` and
+//! folds the other databases into `tenant_groups`, storing only what is
+//! specific to each tenant: database info, collection metadata, samples,
+//! quality metrics, and deviating values such as row counts.
+//! [`expand_tenants`] restores the full list of schemas.
+//!
+//! Two schemas are structurally identical when they are equal after
+//! ignoring the per-tenant fields above, ignoring row counts, and treating
+//! object schemas named after the database itself (as MySQL reports them)
+//! as the same.
+
+use crate::canonical::to_canonical_json;
+use crate::models::{
+    CollectionStatus, DatabaseSchema, DatabaseServerSchema, TenantDatabase, TenantGroup,
+};
+use crate::{DbSurveyorError, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Top-level schema fields that describe the tenant rather than the structure
+const TENANT_FIELDS: &[&str] = &[
+    "database_info",
+    "samples",
+    "quality_metrics",
+    "collection_metadata",
+];
+
+/// Fields whose values may differ between structurally identical schemas
+const VOLATILE_FIELDS: &[&str] = &["row_count"];
+
+/// Fields naming an object's schema, which MySQL reports as the database
+const SCHEMA_FIELDS: &[&str] = &["schema", "referenced_schema"];
+
+/// Stands in for the database name in schema fields
+const DATABASE_PLACEHOLDER: &str = "${database}";
+
+/// Structure of a schema, with its volatile values set aside.
+struct Structure {
+    /// Canonical JSON of the normalized schema
+    canonical_json: String,
+    /// Volatile values keyed by JSON Pointer
+    volatile: BTreeMap<String, Value>,
+}
+
+/// Folds structurally identical databases into tenant groups.
+///
+/// Only successfully collected databases are grouped; the first database of
+/// each structure, in collection order, becomes the group's canonical schema.
+/// Returns the number of databases folded into groups.
+pub fn deduplicate_tenants(server: &mut DatabaseServerSchema) -> usize {
+    let databases = std::mem::take(&mut server.databases);
+    let mut kept = Vec::with_capacity(databases.len());
+    let mut groups: Vec<(TenantGroup, BTreeMap<String, Value>)> = Vec::new();
+    let mut by_structure: HashMap<String, usize> = HashMap::new();
+    let mut folded = 0;
+
+    for schema in databases {
+        let structure = match schema.database_info.collection_status {
+            CollectionStatus::Success => structure(&schema),
+            _ => None,
+        };
+        let Some(structure) = structure else {
+            kept.push(schema);
+            continue;
+        };
+
+        let existing = by_structure
+            .get(&structure.canonical_json)
+            .and_then(|&index| groups.get_mut(index));
+        match existing {
+            Some((group, canonical_volatile)) => {
+                let deviations = structure
+                    .volatile
+                    .into_iter()
+                    .filter(|(pointer, value)| canonical_volatile.get(pointer) != Some(value))
+                    .collect();
+                group.tenants.push(TenantDatabase {
+                    database_info: schema.database_info,
+                    deviations,
+                    samples: schema.samples,
+                    quality_metrics: schema.quality_metrics,
+                    collection_metadata: schema.collection_metadata,
+                });
+                folded += 1;
+            }
+            None => {
+                let fingerprint = Sha256::digest(structure.canonical_json.as_bytes())
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                by_structure.insert(structure.canonical_json, groups.len());
+                groups.push((
+                    TenantGroup {
+                        canonical: schema.database_info.name.clone(),
+                        fingerprint,
+                        tenants: Vec::new(),
+                    },
+                    structure.volatile,
+                ));
+                kept.push(schema);
+            }
+        }
+    }
+
+    server.databases = kept;
+    server.tenant_groups.extend(
+        groups
+            .into_iter()
+            .map(|(group, _)| group)
+            .filter(|group| !group.tenants.is_empty()),
+    );
+    folded
+}
+
+/// Restores every tenant database folded by [`deduplicate_tenants`].
+///
+/// Databases are returned to their collection order, as listed in the
+/// manifest.
+///
+/// # Errors
+/// Returns an error if a group's canonical database is missing or a
+/// deviation does not apply to the canonical schema.
+pub fn expand_tenants(server: &mut DatabaseServerSchema) -> Result<()> {
+    for group in std::mem::take(&mut server.tenant_groups) {
+        let canonical = server
+            .databases
+            .iter()
+            .find(|schema| schema.database_info.name == group.canonical)
+            .cloned()
+            .ok_or_else(|| {
+                DbSurveyorError::configuration(format!(
+                    "Tenant group refers to missing canonical database '{}'",
+                    group.canonical
+                ))
+            })?;
+        for tenant in group.tenants {
+            server.databases.push(restore(&canonical, tenant)?);
+        }
+    }
+
+    let positions: HashMap<&str, usize> = server
+        .manifest
+        .iter()
+        .enumerate()
+        .map(|(position, entry)| (entry.name.as_str(), position))
+        .collect();
+    server.databases.sort_by_key(|schema| {
+        positions
+            .get(schema.database_info.name.as_str())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+    Ok(())
+}
+
+fn structure(schema: &DatabaseSchema) -> Option<Structure> {
+    let mut value = serde_json::to_value(schema).ok()?;
+    let object = value.as_object_mut()?;
+    for field in TENANT_FIELDS {
+        object.remove(*field);
+    }
+
+    let mut volatile = BTreeMap::new();
+    normalize(&mut value, "", &schema.database_info.name, &mut volatile);
+    Some(Structure {
+        canonical_json: to_canonical_json(&value),
+        volatile,
+    })
+}
+
+fn normalize(
+    value: &mut Value,
+    pointer: &str,
+    database: &str,
+    volatile: &mut BTreeMap<String, Value>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let path = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                if VOLATILE_FIELDS.contains(&key.as_str()) {
+                    volatile.insert(path, item.take());
+                } else if SCHEMA_FIELDS.contains(&key.as_str()) && item.as_str() == Some(database) {
+                    *item = Value::String(DATABASE_PLACEHOLDER.to_string());
+                } else {
+                    normalize(item, &path, database, volatile);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                normalize(item, &format!("{}/{}", pointer, index), database, volatile);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rebuilds a tenant's schema from its group's canonical schema.
+fn restore(canonical: &DatabaseSchema, tenant: TenantDatabase) -> Result<DatabaseSchema> {
+    let mut value =
+        serde_json::to_value(canonical).map_err(|e| DbSurveyorError::Serialization {
+            context: format!(
+                "Failed to serialize database '{}'",
+                canonical.database_info.name
+            ),
+            source: e,
+        })?;
+    rename_schemas(
+        &mut value,
+        &canonical.database_info.name,
+        &tenant.database_info.name,
+    );
+    for (pointer, deviation) in tenant.deviations {
+        let slot = value.pointer_mut(&pointer).ok_or_else(|| {
+            DbSurveyorError::configuration(format!(
+                "Deviation '{}' of database '{}' does not match canonical database '{}'",
+                pointer, tenant.database_info.name, canonical.database_info.name
+            ))
+        })?;
+        *slot = deviation;
+    }
+
+    let mut schema: DatabaseSchema =
+        serde_json::from_value(value).map_err(|e| DbSurveyorError::Serialization {
+            context: format!("Failed to restore database '{}'", tenant.database_info.name),
+            source: e,
+        })?;
+    schema.database_info = tenant.database_info;
+    schema.samples = tenant.samples;
+    schema.quality_metrics = tenant.quality_metrics;
+    schema.collection_metadata = tenant.collection_metadata;
+    Ok(schema)
+}
+
+fn rename_schemas(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if SCHEMA_FIELDS.contains(&key.as_str()) && item.as_str() == Some(from) {
+                    *item = Value::String(to.to_string());
+                } else {
+                    rename_schemas(item, from, to);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rename_schemas(item, from, to);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        CollectionMetadata, CollectionMode, Column, DatabaseInfo, DatabaseType, ServerInfo, Table,
+        UnifiedDataType,
+    };
+
+    fn table(schema: &str, columns: &[&str], row_count: u64) -> Table {
+        Table {
+            row_count: Some(row_count),
+            ..Table::new(
+                "orders".to_string(),
+                Some(schema.to_string()),
+                columns
+                    .iter()
+                    .zip(1..)
+                    .map(|(name, ordinal_position)| Column {
+                        is_nullable: false,
+                        ordinal_position,
+                        ..Column::new(
+                            (*name).to_string(),
+                            UnifiedDataType::Integer {
+                                bits: 64,
+                                signed: true,
+                            },
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// A MySQL-style tenant database, whose tables report it as their schema.
+    fn tenant(name: &str, columns: &[&str], row_count: u64) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new(name.to_string()));
+        schema.tables.push(table(name, columns, row_count));
+        schema
+    }
+
+    fn server(databases: Vec<DatabaseSchema>) -> DatabaseServerSchema {
+        DatabaseServerSchema::new(
+            ServerInfo {
+                server_type: DatabaseType::MySQL,
+                version: "8.0.36".to_string(),
+                host: "localhost".to_string(),
+                port: Some(3306),
+                total_databases: databases.len(),
+                collected_databases: databases.len(),
+                system_databases_excluded: 0,
+                connection_user: "surveyor".to_string(),
+                has_superuser_privileges: false,
+                collection_mode: CollectionMode::MultiDatabase {
+                    discovered: databases.len(),
+                    collected: databases.len(),
+                    failed: 0,
+                },
+                timezone: None,
+                role: None,
+            },
+            databases,
+            CollectionMetadata {
+                collected_at: chrono::Utc::now(),
+                collection_duration_ms: 0,
+                collector_version: "1.0.0".to_string(),
+                warnings: Vec::new(),
+                object_failures: Vec::new(),
+                degraded: Vec::new(),
+                read_only: None,
+                integrity: None,
+                auxiliary_files: Vec::new(),
+                settings: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_identical_tenants_are_folded_with_deviations() {
+        let mut server = server(vec![
+            tenant("tenant_a", &["id", "total"], 10),
+            tenant("legacy", &["id"], 3),
+            tenant("tenant_b", &["id", "total"], 10),
+            tenant("tenant_c", &["id", "total"], 25),
+        ]);
+
+        assert_eq!(deduplicate_tenants(&mut server), 2);
+
+        let kept: Vec<&str> = server
+            .databases
+            .iter()
+            .map(|db| db.database_info.name.as_str())
+            .collect();
+        assert_eq!(kept, ["tenant_a", "legacy"]);
+        assert_eq!(server.manifest.len(), 4);
+
+        let [group] = server.tenant_groups.as_slice() else {
+            panic!("expected one tenant group");
+        };
+        assert_eq!(group.canonical, "tenant_a");
+        assert_eq!(group.fingerprint.len(), 64);
+        assert!(group.tenants[0].deviations.is_empty());
+        assert_eq!(
+            group.tenants[1].deviations,
+            BTreeMap::from([("/tables/0/row_count".to_string(), Value::from(25))])
+        );
+    }
+
+    #[test]
+    fn test_expand_restores_original_databases() {
+        let original = server(vec![
+            tenant("tenant_a", &["id", "total"], 10),
+            tenant("legacy", &["id"], 3),
+            tenant("tenant_b", &["id", "total"], 25),
+        ]);
+        let mut server = original.clone();
+        deduplicate_tenants(&mut server);
+
+        let json = serde_json::to_string(&server).expect("server schema serializes");
+        let mut parsed: DatabaseServerSchema =
+            serde_json::from_str(&json).expect("server schema parses");
+        expand_tenants(&mut parsed).expect("tenants expand");
+
+        assert_eq!(parsed, original);
+    }
+
+    #[test]
+    fn test_failed_databases_are_not_grouped() {
+        let mut failed = tenant("tenant_b", &["id"], 0);
+        failed.database_info.collection_status = CollectionStatus::Failed {
+            error: "access denied".to_string(),
+        };
+        let mut server = server(vec![tenant("tenant_a", &["id"], 0), failed]);
+
+        assert_eq!(deduplicate_tenants(&mut server), 0);
+        assert_eq!(server.databases.len(), 2);
+        assert!(server.tenant_groups.is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+//! Column default classification.
+//!
+//! Catalogs report defaults as SQL text in each engine's own dialect:
+//! PostgreSQL adds casts (`'new'::character varying`), SQLite keeps the
+//! declaration as written (`(datetime('now'))`), and SQL Server wraps
+//! everything in parentheses (`((0))`). [`classify_default`] reduces these
+//! to a [`DefaultValue`] so consumers can tell literals from sequences,
+//! timestamps, and generated UUIDs without sniffing strings themselves.
+
+use crate::models::DefaultValue;
+
+/// Functions returning the current date and time.
+const CURRENT_TIMESTAMP_FUNCTIONS: &[&str] = &[
+    "CURRENT_TIMESTAMP",
+    "NOW",
+    "LOCALTIMESTAMP",
+    "TRANSACTION_TIMESTAMP",
+    "STATEMENT_TIMESTAMP",
+    "CLOCK_TIMESTAMP",
+    "GETDATE",
+    "GETUTCDATE",
+    "SYSDATETIME",
+    "SYSUTCDATETIME",
+    "SYSDATETIMEOFFSET",
+    "UTC_TIMESTAMP",
+];
+
+/// Functions returning a random UUID.
+const UUID_FUNCTIONS: &[&str] = &[
+    "GEN_RANDOM_UUID",
+    "UUID_GENERATE_V4",
+    "UUID_GENERATE_V1",
+    "UUID_GENERATE_V1MC",
+    "UUID",
+    "NEWID",
+    "NEWSEQUENTIALID",
+];
+
+/// Classifies a column default as reported by the database catalog.
+///
+/// Anything that is not recognized as a literal, sequence, current
+/// timestamp, or UUID generator is returned unchanged as an expression.
+pub fn classify_default(default: &str) -> DefaultValue {
+    let expression = default.trim();
+    let value = strip_cast(strip_parentheses(expression));
+    let value = strip_parentheses(value);
+    let upper = value.to_ascii_uppercase();
+
+    if matches!(upper.as_str(), "NULL" | "TRUE" | "FALSE") {
+        return DefaultValue::Literal { value: upper };
+    }
+    if is_number(value) || is_string_literal(value) {
+        return DefaultValue::Literal {
+            value: value.to_string(),
+        };
+    }
+    // SQLite spells the current time as a call with a 'now' argument
+    if matches!(
+        upper.replace(' ', "").as_str(),
+        "DATETIME('NOW')" | "CURRENT_TIMESTAMP('NOW')"
+    ) {
+        return DefaultValue::CurrentTimestamp;
+    }
+    if let Some((function, arguments)) = function_call(value) {
+        let function = function.as_str();
+        if function == "NEXTVAL"
+            && let Some(sequence) = sequence_name(arguments)
+        {
+            return DefaultValue::SequenceNextval { sequence };
+        }
+        // MySQL allows fractional second precision, e.g. CURRENT_TIMESTAMP(6)
+        if CURRENT_TIMESTAMP_FUNCTIONS.contains(&function)
+            && arguments.chars().all(|c| c.is_ascii_digit())
+        {
+            return DefaultValue::CurrentTimestamp;
+        }
+        if UUID_FUNCTIONS.contains(&function) && arguments.is_empty() {
+            return DefaultValue::UuidGenerate;
+        }
+    } else if CURRENT_TIMESTAMP_FUNCTIONS.contains(&upper.as_str()) {
+        return DefaultValue::CurrentTimestamp;
+    }
+
+    DefaultValue::Expression {
+        expression: expression.to_string(),
+    }
+}
+
+/// Removes parentheses that enclose the whole value, e.g. `((0))`.
+fn strip_parentheses(value: &str) -> &str {
+    let mut value = value.trim();
+    while let Some(inner) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        if top_level(inner).any(|(_, c)| c == ')') || !balanced(inner) {
+            break;
+        }
+        value = inner.trim();
+    }
+    value
+}
+
+/// Removes a trailing PostgreSQL cast, e.g. `'new'::character varying`.
+fn strip_cast(value: &str) -> &str {
+    let bytes = value.as_bytes();
+    top_level(value)
+        .find(|&(i, c)| c == ':' && bytes.get(i + 1) == Some(&b':'))
+        .map_or(value, |(i, _)| value[..i].trim_end())
+}
+
+/// Characters outside quoted strings and parentheses, with their byte offsets.
+fn top_level(value: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut depth = 0usize;
+    let mut quoted = false;
+    value.char_indices().filter(move |&(_, c)| {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted && depth > 0 => {
+                depth -= 1;
+                return false;
+            }
+            _ => return !quoted && depth == 0,
+        }
+        false
+    })
+}
+
+fn balanced(value: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    depth == 0 && !quoted
+}
+
+fn is_number(value: &str) -> bool {
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+        && value.parse::<f64>().is_ok()
+}
+
+/// A single-quoted string; concatenations such as `'a' || 'b'` are rejected
+/// because their quotes are unbalanced once doubled quotes are removed.
+fn is_string_literal(value: &str) -> bool {
+    value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .is_some_and(|inner| !inner.replace("''", "").contains('\''))
+}
+
+/// Splits `schema.name(arguments)` into the unqualified, uppercased
+/// function name and its argument text.
+fn function_call(value: &str) -> Option<(String, &str)> {
+    let open = value.find('(')?;
+    let arguments = value[open + 1..].strip_suffix(')')?;
+    if !balanced(arguments) {
+        return None;
+    }
+    let name = value[..open].trim();
+    let name = name.rsplit('.').next().unwrap_or(name).trim_matches('"');
+    Some((name.to_ascii_uppercase(), arguments.trim()))
+}
+
+/// Extracts the sequence from `'orders_id_seq'::regclass`.
+fn sequence_name(argument: &str) -> Option<String> {
+    let name = strip_cast(argument)
+        .strip_prefix('\'')?
+        .strip_suffix('\'')?
+        .replace("''", "'");
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(value: &str) -> DefaultValue {
+        DefaultValue::Literal {
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(
+            classify_default("'new'::character varying"),
+            literal("'new'")
+        );
+        assert_eq!(classify_default("42"), literal("42"));
+        assert_eq!(classify_default("((0))"), literal("0"));
+        assert_eq!(classify_default("'-1.5'::numeric"), literal("'-1.5'"));
+        assert_eq!(classify_default("(-1)"), literal("-1"));
+        assert_eq!(classify_default("true"), literal("TRUE"));
+        assert_eq!(classify_default("NULL::text"), literal("NULL"));
+        assert_eq!(classify_default("'it''s'"), literal("'it''s'"));
+        assert_eq!(classify_default("('x')"), literal("'x'"));
+    }
+
+    #[test]
+    fn test_sequences() {
+        assert_eq!(
+            classify_default("nextval('orders_id_seq'::regclass)"),
+            DefaultValue::SequenceNextval {
+                sequence: "orders_id_seq".to_string()
+            }
+        );
+        assert_eq!(
+            classify_default("nextval('shop.\"Order_Id_seq\"'::regclass)"),
+            DefaultValue::SequenceNextval {
+                sequence: "shop.\"Order_Id_seq\"".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_current_timestamp() {
+        for default in [
+            "CURRENT_TIMESTAMP",
+            "now()",
+            "CURRENT_TIMESTAMP(6)",
+            "(getdate())",
+            "(datetime('now'))",
+            "pg_catalog.now()",
+        ] {
+            assert_eq!(
+                classify_default(default),
+                DefaultValue::CurrentTimestamp,
+                "{default}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_uuid_generation() {
+        for default in [
+            "gen_random_uuid()",
+            "public.uuid_generate_v4()",
+            "(newid())",
+            "uuid()",
+        ] {
+            assert_eq!(
+                classify_default(default),
+                DefaultValue::UuidGenerate,
+                "{default}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expressions() {
+        for default in [
+            "'a' || 'b'",
+            "(lower(hex(randomblob(16))))",
+            "CURRENT_DATE",
+            "(now() + '1 day'::interval)",
+            "nan",
+            "(a) + (b)",
+        ] {
+            assert_eq!(
+                classify_default(default),
+                DefaultValue::Expression {
+                    expression: default.to_string()
+                },
+                "{default}"
+            );
+        }
+    }
+}
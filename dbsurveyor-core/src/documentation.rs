@@ -0,0 +1,274 @@
+//! Comment coverage and documentation debt.
+//!
+//! [`comment_coverage`] measures how many tables and columns carry a
+//! comment, per schema. [`undocumented_tables`] lists the tables missing a
+//! table or column comment, most important first, so documentation effort
+//! goes where readers are most likely to look: large tables and tables
+//! many foreign keys point to or from.
+//!
+//! Blank comments count as missing.
+
+use crate::models::{DatabaseSchema, Table};
+use std::collections::BTreeMap;
+
+/// Comment coverage of the tables and columns in one schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommentCoverage {
+    /// Schema name, if the database has schemas
+    pub schema: Option<String>,
+    pub tables: usize,
+    pub documented_tables: usize,
+    pub columns: usize,
+    pub documented_columns: usize,
+}
+
+impl CommentCoverage {
+    /// Percentage of tables with a comment, or 100 when there are none.
+    pub fn table_percent(&self) -> f64 {
+        percent(self.documented_tables, self.tables)
+    }
+
+    /// Percentage of columns with a comment, or 100 when there are none.
+    pub fn column_percent(&self) -> f64 {
+        percent(self.documented_columns, self.columns)
+    }
+}
+
+/// A table missing its own comment or a comment on any column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndocumentedTable {
+    pub schema: Option<String>,
+    pub table: String,
+    pub row_count: Option<u64>,
+    /// Foreign keys declared on the table plus those referencing it
+    pub references: usize,
+    /// True when the table itself has no comment
+    pub missing_table_comment: bool,
+    /// Columns without a comment, in column order
+    pub undocumented_columns: Vec<String>,
+}
+
+/// Computes comment coverage per schema, ordered by schema name.
+pub fn comment_coverage<'a>(tables: impl IntoIterator<Item = &'a Table>) -> Vec<CommentCoverage> {
+    let mut by_schema: BTreeMap<Option<&str>, CommentCoverage> = BTreeMap::new();
+    for table in tables {
+        let coverage =
+            by_schema
+                .entry(table.schema.as_deref())
+                .or_insert_with(|| CommentCoverage {
+                    schema: table.schema.clone(),
+                    ..CommentCoverage::default()
+                });
+        coverage.tables += 1;
+        coverage.documented_tables += usize::from(is_documented(table.comment.as_deref()));
+        coverage.columns += table.columns.len();
+        coverage.documented_columns += table
+            .columns
+            .iter()
+            .filter(|column| is_documented(column.comment.as_deref()))
+            .count();
+    }
+    by_schema.into_values().collect()
+}
+
+/// Lists tables with missing comments, ordered by descending row count,
+/// then by descending foreign key references, then by name.
+///
+/// Tables without a row count sort after those with one.
+pub fn undocumented_tables(schema: &DatabaseSchema) -> Vec<UndocumentedTable> {
+    let mut undocumented: Vec<UndocumentedTable> = schema
+        .tables
+        .iter()
+        .filter_map(|table| {
+            let missing_table_comment = !is_documented(table.comment.as_deref());
+            let undocumented_columns: Vec<String> = table
+                .columns
+                .iter()
+                .filter(|column| !is_documented(column.comment.as_deref()))
+                .map(|column| column.name.clone())
+                .collect();
+            if !missing_table_comment && undocumented_columns.is_empty() {
+                return None;
+            }
+            Some(UndocumentedTable {
+                schema: table.schema.clone(),
+                table: table.name.clone(),
+                row_count: table.row_count,
+                references: references(schema, table),
+                missing_table_comment,
+                undocumented_columns,
+            })
+        })
+        .collect();
+
+    undocumented.sort_by(|a, b| {
+        b.row_count
+            .cmp(&a.row_count)
+            .then_with(|| b.references.cmp(&a.references))
+            .then_with(|| a.schema.cmp(&b.schema))
+            .then_with(|| a.table.cmp(&b.table))
+    });
+    undocumented
+}
+
+fn is_documented(comment: Option<&str>) -> bool {
+    comment.is_some_and(|c| !c.trim().is_empty())
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+/// Counts foreign keys declared on `table` and foreign keys referencing it.
+///
+/// A foreign key without a referenced schema refers to its own table's schema.
+fn references(schema: &DatabaseSchema, table: &Table) -> usize {
+    let inbound = schema
+        .tables
+        .iter()
+        .flat_map(|source| {
+            source.foreign_keys.iter().filter(move |fk| {
+                fk.referenced_table == table.name
+                    && fk.referenced_schema.as_ref().or(source.schema.as_ref())
+                        == table.schema.as_ref()
+            })
+        })
+        .count();
+    inbound + table.foreign_keys.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, DatabaseInfo, ForeignKey, UnifiedDataType};
+
+    fn column(name: &str, comment: Option<&str>) -> Column {
+        Column {
+            comment: comment.map(str::to_string),
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        }
+    }
+
+    fn table(
+        schema: &str,
+        name: &str,
+        comment: Option<&str>,
+        row_count: Option<u64>,
+        columns: Vec<Column>,
+    ) -> Table {
+        Table {
+            comment: comment.map(str::to_string),
+            row_count,
+            ..Table::new(name.to_string(), Some(schema.to_string()), columns)
+        }
+    }
+
+    fn foreign_key(referenced_table: &str) -> ForeignKey {
+        ForeignKey {
+            name: None,
+            columns: vec![format!("{}_id", referenced_table)],
+            referenced_table: referenced_table.to_string(),
+            referenced_schema: None,
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        let mut orders = table(
+            "public",
+            "orders",
+            None,
+            Some(5000),
+            vec![column("id", Some("Order id")), column("total", None)],
+        );
+        orders.foreign_keys.push(foreign_key("customers"));
+        let mut invoices = table(
+            "public",
+            "invoices",
+            Some("Issued invoices"),
+            None,
+            vec![column("id", None)],
+        );
+        invoices.foreign_keys.push(foreign_key("customers"));
+        schema.tables = vec![
+            orders,
+            invoices,
+            table(
+                "public",
+                "customers",
+                None,
+                Some(10),
+                vec![column("id", None)],
+            ),
+            table("public", "tags", None, Some(10), vec![column("id", None)]),
+            table(
+                "audit",
+                "events",
+                Some("Audit trail"),
+                Some(1_000_000),
+                vec![column("id", Some("  "))],
+            ),
+            table(
+                "audit",
+                "settings",
+                Some("Audit settings"),
+                Some(1),
+                vec![column("key", Some("Setting key"))],
+            ),
+        ];
+        schema
+    }
+
+    #[test]
+    fn test_comment_coverage_per_schema() {
+        let coverage = comment_coverage(&fixture().tables);
+        assert_eq!(coverage.len(), 2);
+
+        let audit = &coverage[0];
+        assert_eq!(audit.schema.as_deref(), Some("audit"));
+        assert_eq!((audit.documented_tables, audit.tables), (2, 2));
+        assert_eq!((audit.documented_columns, audit.columns), (1, 2));
+        assert!((audit.column_percent() - 50.0).abs() < f64::EPSILON);
+
+        let public = &coverage[1];
+        assert_eq!((public.documented_tables, public.tables), (1, 4));
+        assert!((public.table_percent() - 25.0).abs() < f64::EPSILON);
+        assert_eq!(public.documented_columns, 1);
+    }
+
+    #[test]
+    fn test_empty_coverage_is_complete() {
+        let coverage = CommentCoverage::default();
+        assert!((coverage.table_percent() - 100.0).abs() < f64::EPSILON);
+        assert!((coverage.column_percent() - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_undocumented_tables_ordered_by_importance() {
+        let undocumented = undocumented_tables(&fixture());
+        let order: Vec<&str> = undocumented.iter().map(|u| u.table.as_str()).collect();
+        assert_eq!(
+            order,
+            vec!["events", "orders", "customers", "tags", "invoices"]
+        );
+
+        let events = &undocumented[0];
+        assert!(!events.missing_table_comment);
+        assert_eq!(events.undocumented_columns, vec!["id"]);
+
+        let customers = &undocumented[2];
+        assert_eq!(customers.references, 2);
+        assert!(customers.missing_table_comment);
+        assert_eq!(undocumented[1].references, 1);
+    }
+}
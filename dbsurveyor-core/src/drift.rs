@@ -0,0 +1,652 @@
+//! Schema drift against an approved baseline.
+//!
+//! A [`Baseline`] stores the structure of approved database schemas with a
+//! SHA-256 checksum of their canonical JSON, so a baseline edited by hand or
+//! damaged in transit is rejected rather than silently approving drift.
+//! Samples and quality metrics are dropped before approval: a baseline
+//! records structure only and never holds sampled data.
+//!
+//! [`diff_surveys`] compares surveys structurally. Row counts, comments,
+//! and collection metadata are ignored; tables, columns, keys, indexes,
+//! views, routines, and triggers are compared.
+
+use crate::canonical::to_canonical_json;
+use crate::models::{
+    Column, DatabaseSchema, ForeignKey, Index, Procedure, Table, Trigger, UnifiedDataType, View,
+};
+use crate::{DbSurveyorError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+const BASELINE_FORMAT: &str = "dbsurveyor-baseline";
+const BASELINE_VERSION: u32 = 1;
+
+/// Approved database schemas with an integrity checksum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub format: String,
+    pub version: u32,
+    pub approved_at: chrono::DateTime<chrono::Utc>,
+    /// Survey file the baseline was approved from
+    pub source: String,
+    /// SHA-256 of the canonical JSON of `databases`, hex encoded
+    pub checksum: String,
+    pub databases: Vec<DatabaseSchema>,
+}
+
+impl Baseline {
+    /// Approves the structure of `databases` as the baseline.
+    pub fn approve(databases: &[DatabaseSchema], source: impl Into<String>) -> Result<Self> {
+        let databases: Vec<DatabaseSchema> = databases
+            .iter()
+            .cloned()
+            .map(|mut schema| {
+                schema.samples = None;
+                schema.quality_metrics = None;
+                schema
+            })
+            .collect();
+        Ok(Self {
+            format: BASELINE_FORMAT.to_string(),
+            version: BASELINE_VERSION,
+            approved_at: chrono::Utc::now(),
+            source: source.into(),
+            checksum: checksum(&databases)?,
+            databases,
+        })
+    }
+
+    /// Checks the format and that the schemas still match the checksum.
+    ///
+    /// # Errors
+    /// Returns a configuration error if the file is not a supported
+    /// baseline or its schemas were modified after approval.
+    pub fn verify(&self) -> Result<()> {
+        if self.format != BASELINE_FORMAT || self.version != BASELINE_VERSION {
+            return Err(DbSurveyorError::configuration(format!(
+                "Unsupported baseline format '{}' version {}",
+                self.format, self.version
+            )));
+        }
+        if checksum(&self.databases)? != self.checksum {
+            return Err(DbSurveyorError::configuration(
+                "Baseline checksum mismatch: the baseline was modified after approval",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn checksum(databases: &[DatabaseSchema]) -> Result<String> {
+    let value = serde_json::to_value(databases).map_err(|e| DbSurveyorError::Serialization {
+        context: "Failed to serialize baseline schemas".to_string(),
+        source: e,
+    })?;
+    Ok(Sha256::digest(to_canonical_json(&value).as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// How an object changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "modified",
+        })
+    }
+}
+
+/// Kind of object that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftObject {
+    Database,
+    Table,
+    Column,
+    Index,
+    ForeignKey,
+    View,
+    Procedure,
+    Function,
+    Trigger,
+}
+
+impl DriftObject {
+    /// All object kinds, for parsing and help text.
+    pub const ALL: [DriftObject; 9] = [
+        DriftObject::Database,
+        DriftObject::Table,
+        DriftObject::Column,
+        DriftObject::Index,
+        DriftObject::ForeignKey,
+        DriftObject::View,
+        DriftObject::Procedure,
+        DriftObject::Function,
+        DriftObject::Trigger,
+    ];
+
+    /// Stable kebab-case name (e.g. `foreign-key`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::Table => "table",
+            Self::Column => "column",
+            Self::Index => "index",
+            Self::ForeignKey => "foreign-key",
+            Self::View => "view",
+            Self::Procedure => "procedure",
+            Self::Function => "function",
+            Self::Trigger => "trigger",
+        }
+    }
+}
+
+impl fmt::Display for DriftObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A structural difference between a baseline and a newer survey.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaChange {
+    pub database: String,
+    pub kind: ChangeKind,
+    pub object_kind: DriftObject,
+    /// Qualified name within the database (`schema.table.column`)
+    pub object: String,
+    /// What changed, for modified objects (e.g. `type: int32 -> int64`)
+    pub detail: Option<String>,
+}
+
+/// Compares surveys database by database, matching databases by name.
+pub fn diff_surveys(baseline: &[DatabaseSchema], current: &[DatabaseSchema]) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for old in baseline {
+        let name = &old.database_info.name;
+        match current.iter().find(|new| &new.database_info.name == name) {
+            Some(new) => changes.extend(diff_schemas(old, new)),
+            None => changes.push(change(
+                name,
+                ChangeKind::Removed,
+                DriftObject::Database,
+                name,
+            )),
+        }
+    }
+    for new in current {
+        let name = &new.database_info.name;
+        if !baseline.iter().any(|old| &old.database_info.name == name) {
+            changes.push(change(name, ChangeKind::Added, DriftObject::Database, name));
+        }
+    }
+    changes
+}
+
+/// Compares two versions of one database schema.
+pub fn diff_schemas(baseline: &DatabaseSchema, current: &DatabaseSchema) -> Vec<SchemaChange> {
+    let mut diff = Diff {
+        database: &current.database_info.name,
+        changes: Vec::new(),
+    };
+
+    let old_tables = keyed(&baseline.tables, |t| {
+        qualified(t.schema.as_deref(), &t.name)
+    });
+    let new_tables = keyed(&current.tables, |t| qualified(t.schema.as_deref(), &t.name));
+    diff.compare(DriftObject::Table, &old_tables, &new_tables, |_, _| None);
+    for (name, old) in &old_tables {
+        if let Some(new) = new_tables.get(name) {
+            diff_table(&mut diff, name, old, new);
+        }
+    }
+
+    let view_key = |v: &View| qualified(v.schema.as_deref(), &v.name);
+    diff.compare(
+        DriftObject::View,
+        &keyed(&baseline.views, view_key),
+        &keyed(&current.views, view_key),
+        |old, new| (old.definition != new.definition).then(|| "definition".to_string()),
+    );
+    diff.compare(
+        DriftObject::Procedure,
+        &routines(&baseline.procedures),
+        &routines(&current.procedures),
+        routine_detail,
+    );
+    diff.compare(
+        DriftObject::Function,
+        &routines(&baseline.functions),
+        &routines(&current.functions),
+        routine_detail,
+    );
+    let trigger_key = |t: &Trigger| {
+        format!(
+            "{}.{}",
+            qualified(t.schema.as_deref(), &t.table_name),
+            t.name
+        )
+    };
+    diff.compare(
+        DriftObject::Trigger,
+        &keyed(&baseline.triggers, trigger_key),
+        &keyed(&current.triggers, trigger_key),
+        |old, new| {
+            let mut detail = Vec::new();
+            if old.event != new.event || old.timing != new.timing {
+                detail.push(format!(
+                    "{:?} {:?} -> {:?} {:?}",
+                    old.timing, old.event, new.timing, new.event
+                ));
+            }
+            if old.definition != new.definition {
+                detail.push("definition".to_string());
+            }
+            joined(detail)
+        },
+    );
+
+    diff.changes
+}
+
+/// Compares two definitions of one table: primary key, columns, indexes,
+/// and foreign keys.
+pub fn diff_tables(
+    database: &str,
+    table_name: &str,
+    baseline: &Table,
+    current: &Table,
+) -> Vec<SchemaChange> {
+    let mut diff = Diff {
+        database,
+        changes: Vec::new(),
+    };
+    diff_table(&mut diff, table_name, baseline, current);
+    diff.changes
+}
+
+fn diff_table(diff: &mut Diff<'_>, table_name: &str, old: &Table, new: &Table) {
+    let old_key = old.primary_key.as_ref().map(|pk| &pk.columns);
+    let new_key = new.primary_key.as_ref().map(|pk| &pk.columns);
+    if old_key != new_key {
+        diff.changes.push(SchemaChange {
+            detail: Some(format!(
+                "primary key: {} -> {}",
+                column_list(old_key),
+                column_list(new_key)
+            )),
+            ..change(
+                diff.database,
+                ChangeKind::Modified,
+                DriftObject::Table,
+                table_name,
+            )
+        });
+    }
+
+    let column_key = |c: &Column| format!("{}.{}", table_name, c.name);
+    diff.compare(
+        DriftObject::Column,
+        &keyed(&old.columns, column_key),
+        &keyed(&new.columns, column_key),
+        column_detail,
+    );
+
+    let index_key = |i: &Index| format!("{}.{}", table_name, i.name);
+    diff.compare(
+        DriftObject::Index,
+        &keyed(&old.indexes, index_key),
+        &keyed(&new.indexes, index_key),
+        index_detail,
+    );
+
+    // Unnamed foreign keys (SQLite) are identified by their columns
+    let foreign_key_key = |fk: &ForeignKey| {
+        let name = fk.name.clone().unwrap_or_else(|| fk.columns.join(","));
+        format!("{}.{}", table_name, name)
+    };
+    diff.compare(
+        DriftObject::ForeignKey,
+        &keyed(&old.foreign_keys, foreign_key_key),
+        &keyed(&new.foreign_keys, foreign_key_key),
+        foreign_key_detail,
+    );
+}
+
+struct Diff<'a> {
+    database: &'a str,
+    changes: Vec<SchemaChange>,
+}
+
+impl Diff<'_> {
+    /// Records added and removed objects, and modified objects for which
+    /// `detail` describes a difference.
+    fn compare<T>(
+        &mut self,
+        object_kind: DriftObject,
+        old: &BTreeMap<String, &T>,
+        new: &BTreeMap<String, &T>,
+        detail: impl Fn(&T, &T) -> Option<String>,
+    ) {
+        for (name, old_object) in old {
+            match new.get(name) {
+                None => self.changes.push(change(
+                    self.database,
+                    ChangeKind::Removed,
+                    object_kind,
+                    name,
+                )),
+                Some(new_object) => {
+                    if let Some(detail) = detail(old_object, new_object) {
+                        self.changes.push(SchemaChange {
+                            detail: Some(detail),
+                            ..change(self.database, ChangeKind::Modified, object_kind, name)
+                        });
+                    }
+                }
+            }
+        }
+        for name in new.keys().filter(|name| !old.contains_key(*name)) {
+            self.changes
+                .push(change(self.database, ChangeKind::Added, object_kind, name));
+        }
+    }
+}
+
+fn change(
+    database: &str,
+    kind: ChangeKind,
+    object_kind: DriftObject,
+    object: &str,
+) -> SchemaChange {
+    SchemaChange {
+        database: database.to_string(),
+        kind,
+        object_kind,
+        object: object.to_string(),
+        detail: None,
+    }
+}
+
+fn keyed<T>(items: &[T], key: impl Fn(&T) -> String) -> BTreeMap<String, &T> {
+    items.iter().map(|item| (key(item), item)).collect()
+}
+
+fn routines(routines: &[Procedure]) -> BTreeMap<String, &Procedure> {
+    keyed(routines, |r| qualified(r.schema.as_deref(), &r.name))
+}
+
+fn qualified(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+fn column_detail(old: &Column, new: &Column) -> Option<String> {
+    let mut detail = Vec::new();
+    let (old_type, new_type) = (column_type(old), column_type(new));
+    if old_type != new_type {
+        detail.push(format!("type: {} -> {}", old_type, new_type));
+    }
+    if old.is_nullable != new.is_nullable {
+        detail.push(format!(
+            "nullable: {} -> {}",
+            old.is_nullable, new.is_nullable
+        ));
+    }
+    if old.default_value != new.default_value {
+        detail.push(format!(
+            "default: {} -> {}",
+            old.default_value.as_deref().unwrap_or("none"),
+            new.default_value.as_deref().unwrap_or("none")
+        ));
+    }
+    if old.is_auto_increment != new.is_auto_increment {
+        detail.push(format!(
+            "auto-increment: {} -> {}",
+            old.is_auto_increment, new.is_auto_increment
+        ));
+    }
+    if old.generated != new.generated {
+        detail.push("generation expression".to_string());
+    }
+    joined(detail)
+}
+
+fn index_detail(old: &Index, new: &Index) -> Option<String> {
+    let columns = |index: &Index| {
+        index
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut detail = Vec::new();
+    if old.columns != new.columns {
+        detail.push(format!("columns: ({}) -> ({})", columns(old), columns(new)));
+    }
+    if old.is_unique != new.is_unique {
+        detail.push(format!("unique: {} -> {}", old.is_unique, new.is_unique));
+    }
+    joined(detail)
+}
+
+fn foreign_key_detail(old: &ForeignKey, new: &ForeignKey) -> Option<String> {
+    let target = |fk: &ForeignKey| {
+        format!(
+            "{}({})",
+            qualified(fk.referenced_schema.as_deref(), &fk.referenced_table),
+            fk.referenced_columns.join(", ")
+        )
+    };
+    let mut detail = Vec::new();
+    if old.columns != new.columns {
+        detail.push(format!(
+            "columns: ({}) -> ({})",
+            old.columns.join(", "),
+            new.columns.join(", ")
+        ));
+    }
+    if target(old) != target(new) {
+        detail.push(format!("references: {} -> {}", target(old), target(new)));
+    }
+    if old.on_delete != new.on_delete || old.on_update != new.on_update {
+        detail.push("referential actions".to_string());
+    }
+    joined(detail)
+}
+
+fn routine_detail(old: &Procedure, new: &Procedure) -> Option<String> {
+    let mut detail = Vec::new();
+    if old.parameters != new.parameters || old.return_type != new.return_type {
+        detail.push("signature".to_string());
+    }
+    if old.definition != new.definition {
+        detail.push("definition".to_string());
+    }
+    joined(detail)
+}
+
+fn joined(detail: Vec<String>) -> Option<String> {
+    (!detail.is_empty()).then(|| detail.join("; "))
+}
+
+fn column_list(columns: Option<&Vec<String>>) -> String {
+    match columns {
+        Some(columns) => format!("({})", columns.join(", ")),
+        None => "none".to_string(),
+    }
+}
+
+/// The declared type when the engine reported one, otherwise the unified type.
+fn column_type(column: &Column) -> String {
+    column
+        .declared_type
+        .clone()
+        .unwrap_or_else(|| type_label(&column.data_type))
+}
+
+fn type_label(data_type: &UnifiedDataType) -> String {
+    match data_type {
+        UnifiedDataType::String {
+            max_length: Some(length),
+        } => format!("string({})", length),
+        UnifiedDataType::String { max_length: None } => "string".to_string(),
+        UnifiedDataType::Integer { bits, signed } => {
+            format!("{}int{}", if *signed { "" } else { "u" }, bits)
+        }
+        UnifiedDataType::Float { .. } => "float".to_string(),
+        UnifiedDataType::Decimal { precision, scale } => match (precision, scale) {
+            (Some(precision), Some(scale)) => format!("decimal({},{})", precision, scale),
+            (Some(precision), None) => format!("decimal({})", precision),
+            _ => "decimal".to_string(),
+        },
+        UnifiedDataType::Boolean => "boolean".to_string(),
+        UnifiedDataType::DateTime { with_timezone } => if *with_timezone {
+            "timestamptz"
+        } else {
+            "timestamp"
+        }
+        .to_string(),
+        UnifiedDataType::Date => "date".to_string(),
+        UnifiedDataType::Time { .. } => "time".to_string(),
+        UnifiedDataType::Interval => "interval".to_string(),
+        UnifiedDataType::Binary { .. } => "binary".to_string(),
+        UnifiedDataType::Bit {
+            length: Some(length),
+        } => format!("bit({})", length),
+        UnifiedDataType::Bit { length: None } => "bit".to_string(),
+        UnifiedDataType::Json => "json".to_string(),
+        UnifiedDataType::Uuid => "uuid".to_string(),
+        UnifiedDataType::NetworkAddress => "network_address".to_string(),
+        UnifiedDataType::Array { element_type } => format!("{}[]", type_label(element_type)),
+        UnifiedDataType::Custom { type_name } => type_name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DatabaseInfo, PrimaryKey};
+
+    fn column(name: &str, bits: u8) -> Column {
+        Column {
+            is_nullable: false,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::Integer { bits, signed: true },
+            )
+        }
+    }
+
+    fn table(name: &str, columns: Vec<Column>) -> Table {
+        Table {
+            primary_key: Some(PrimaryKey {
+                name: None,
+                columns: vec!["id".to_string()],
+            }),
+            row_count: Some(10),
+            ..Table::new(name.to_string(), Some("public".to_string()), columns)
+        }
+    }
+
+    fn schema(tables: Vec<Table>) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = tables;
+        schema
+    }
+
+    fn summary(changes: &[SchemaChange]) -> Vec<String> {
+        changes
+            .iter()
+            .map(|c| {
+                let mut line = format!("{} {} {}", c.kind, c.object_kind, c.object);
+                if let Some(detail) = &c.detail {
+                    line.push_str(&format!(" ({})", detail));
+                }
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_drift() {
+        let old = schema(vec![table("orders", vec![column("id", 64)])]);
+        let mut new = old.clone();
+        new.tables[0].row_count = Some(99);
+        new.tables[0].comment = Some("Orders".to_string());
+        assert!(diff_schemas(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_table_column_and_view_changes() {
+        let old = schema(vec![
+            table("orders", vec![column("id", 32), column("legacy", 32)]),
+            table("archive", vec![column("id", 64)]),
+        ]);
+        let mut new = schema(vec![
+            table("orders", vec![column("id", 64), column("created_at", 64)]),
+            table("audit", vec![column("id", 64)]),
+        ]);
+        new.tables[0].columns[0].is_nullable = true;
+        new.views.push(View {
+            name: "open_orders".to_string(),
+            schema: Some("public".to_string()),
+            definition: None,
+            columns: Vec::new(),
+            comment: None,
+        });
+
+        assert_eq!(
+            summary(&diff_schemas(&old, &new)),
+            vec![
+                "removed table public.archive",
+                "added table public.audit",
+                "modified column public.orders.id (type: int32 -> int64; nullable: false -> true)",
+                "removed column public.orders.legacy",
+                "added column public.orders.created_at",
+                "added view public.open_orders",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_databases_matched_by_name() {
+        let old = schema(Vec::new());
+        let mut other = schema(Vec::new());
+        other.database_info.name = "billing".to_string();
+        let changes = diff_surveys(std::slice::from_ref(&old), &[old.clone(), other]);
+        assert_eq!(summary(&changes), vec!["added database billing"]);
+    }
+
+    #[test]
+    fn test_baseline_checksum_detects_tampering() {
+        let mut survey = schema(vec![table("orders", vec![column("id", 64)])]);
+        survey.samples = Some(Vec::new());
+        let mut baseline = Baseline::approve(&[survey], "shop.json").expect("approve");
+        assert!(baseline.databases[0].samples.is_none());
+        baseline.verify().expect("fresh baseline verifies");
+
+        let json = serde_json::to_string(&baseline).expect("serialize");
+        let reloaded: Baseline = serde_json::from_str(&json).expect("deserialize");
+        reloaded.verify().expect("reloaded baseline verifies");
+
+        baseline.databases[0].tables[0].columns[0].is_nullable = true;
+        assert!(baseline.verify().is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! Inference of de-facto enumerations from sampled values.
+//!
+//! Status, type, and category columns are often plain text or integers
+//! whose application only ever writes a handful of values. Such enums are
+//! part of the data model but appear nowhere in the catalog.
+//! [`infer_enums`] reports text and integer columns whose sampled values
+//! form a small closed set:
+//!
+//! - At least [`MIN_SAMPLED_VALUES`] non-NULL values were sampled
+//! - At most [`MAX_ENUM_VALUES`] distinct values, each seen
+//!   [`MIN_REPEATS`] times on average
+//! - Text values are short labels, not free text
+//!
+//! Keys, auto-increment, generated, and foreign key columns, and columns
+//! named like identifiers (`id`, `customer_id`), are skipped: a small
+//! sample of them repeats values without the column being an enum.
+//! Declared enum types are not numeric or text columns and never match.
+//!
+//! Unless the sample covered every row, the value set is a lower bound
+//! and rare values may be missing.
+//!
+//! # Security Guarantees
+//! - `Debug` output shows only the number of values, never the values
+//! - Values are copied from the samples as collected; callers showing them
+//!   must apply the same masking as for the samples themselves, as the
+//!   report does when the samples in scope redact the column
+
+use crate::models::{Column, DatabaseSchema, Table, TableSample, UnifiedDataType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Fewest non-NULL sampled values to infer an enum from.
+pub const MIN_SAMPLED_VALUES: usize = 20;
+
+/// Most distinct values an inferred enum may have.
+pub const MAX_ENUM_VALUES: usize = 12;
+
+/// Average number of times each distinct value must be sampled.
+pub const MIN_REPEATS: usize = 3;
+
+/// Longest text value treated as a label.
+const MAX_LABEL_LENGTH: usize = 64;
+
+/// A column whose sampled values form a small closed set.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferredEnum {
+    pub schema: Option<String>,
+    pub table: String,
+    pub column: String,
+    /// Distinct sampled values, sorted
+    pub values: Vec<Value>,
+    /// Non-NULL values sampled
+    pub sampled: usize,
+    /// True when the sample covered every row, so no value is missing
+    pub complete: bool,
+}
+
+/// Finds inferred enums in sampled tables, in table and column order.
+pub fn infer_enums(schema: &DatabaseSchema) -> Vec<InferredEnum> {
+    let samples = schema.samples.as_deref().unwrap_or_default();
+    let mut enums = Vec::new();
+    for table in &schema.tables {
+        let Some(sample) = samples
+            .iter()
+            .find(|s| s.table_name == table.name && s.schema_name == table.schema)
+        else {
+            continue;
+        };
+        let complete = sample
+            .total_rows
+            .is_some_and(|total| total <= sample.rows.len() as u64);
+        for column in table.columns.iter().filter(|c| is_candidate(table, c)) {
+            if let Some((values, sampled)) = closed_set(sample, &column.name) {
+                enums.push(InferredEnum {
+                    schema: table.schema.clone(),
+                    table: table.name.clone(),
+                    column: column.name.clone(),
+                    values,
+                    sampled,
+                    complete,
+                });
+            }
+        }
+    }
+    enums
+}
+
+fn is_candidate(table: &Table, column: &Column) -> bool {
+    matches!(
+        column.data_type,
+        UnifiedDataType::String { .. } | UnifiedDataType::Integer { .. }
+    ) && !column.is_primary_key
+        && !column.is_auto_increment
+        && column.generated.is_none()
+        && !is_identifier_name(&column.name)
+        && !table
+            .foreign_keys
+            .iter()
+            .any(|fk| fk.columns.contains(&column.name))
+}
+
+/// `id`, `customer_id`, `customerId`, `CustomerID`.
+fn is_identifier_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == "id"
+        || lower.ends_with("_id")
+        || name
+            .strip_suffix("Id")
+            .or_else(|| name.strip_suffix("ID"))
+            .is_some_and(|stem| stem.ends_with(char::is_lowercase))
+}
+
+/// Distinct values of `column` and the number of non-NULL values, when
+/// they form a closed set.
+fn closed_set(sample: &TableSample, column: &str) -> Option<(Vec<Value>, usize)> {
+    let mut values: Vec<&Value> = Vec::new();
+    let mut sampled = 0;
+    for value in sample.rows.iter().filter_map(|row| row.get(column)) {
+        match value {
+            Value::Null => continue,
+            Value::String(text) if text.len() <= MAX_LABEL_LENGTH && !text.trim().is_empty() => {}
+            Value::Number(number) if number.is_i64() || number.is_u64() => {}
+            // Free text, binary, or structured values
+            _ => return None,
+        }
+        sampled += 1;
+        if !values.contains(&value) {
+            if values.len() == MAX_ENUM_VALUES {
+                return None;
+            }
+            values.push(value);
+        }
+    }
+
+    if sampled < MIN_SAMPLED_VALUES || values.len() * MIN_REPEATS > sampled {
+        return None;
+    }
+    let mut values: Vec<Value> = values.into_iter().cloned().collect();
+    values.sort_by(compare_values);
+    Some((values, sampled))
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_i64()
+            .zip(b.as_i64())
+            .map_or_else(|| a.as_u64().cmp(&b.as_u64()), |(a, b)| a.cmp(&b)),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        // Numbers before text when a column mixes both
+        (Value::Number(_), _) => Ordering::Less,
+        (_, Value::Number(_)) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DatabaseInfo, ForeignKey, SamplingStrategy};
+    use serde_json::json;
+
+    fn column(name: &str, data_type: UnifiedDataType) -> Column {
+        Column::new(name.to_string(), data_type)
+    }
+
+    fn text() -> UnifiedDataType {
+        UnifiedDataType::String {
+            max_length: Some(50),
+        }
+    }
+
+    fn integer() -> UnifiedDataType {
+        UnifiedDataType::Integer {
+            bits: 32,
+            signed: true,
+        }
+    }
+
+    fn fixture(total_rows: u64) -> DatabaseSchema {
+        let mut id = column("id", integer());
+        id.is_primary_key = true;
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![Table {
+            foreign_keys: vec![ForeignKey {
+                name: None,
+                columns: vec!["warehouse".to_string()],
+                referenced_table: "warehouses".to_string(),
+                referenced_schema: None,
+                referenced_columns: vec!["id".to_string()],
+                on_delete: None,
+                on_update: None,
+            }],
+            row_count: Some(total_rows),
+            ..Table::new(
+                "orders".to_string(),
+                Some("public".to_string()),
+                vec![
+                    id,
+                    column("status", text()),
+                    column("priority", integer()),
+                    column("customer_id", integer()),
+                    column("warehouse", integer()),
+                    column("note", text()),
+                    column(
+                        "amount",
+                        UnifiedDataType::Decimal {
+                            precision: Some(10),
+                            scale: Some(2),
+                        },
+                    ),
+                ],
+            )
+        }];
+
+        let statuses = ["shipped", "pending", "cancelled"];
+        let rows = (0..30)
+            .map(|i| {
+                json!({
+                    "id": i,
+                    "status": (if i == 7 { Value::Null } else { json!(statuses[i % 3]) }),
+                    "priority": ([3, 1, 2][i % 3]),
+                    "customer_id": i % 4,
+                    "warehouse": i % 2,
+                    "note": format!("note {}", i % 5),
+                    "amount": "12.50",
+                })
+            })
+            .collect();
+        schema.samples = Some(vec![TableSample {
+            table_name: "orders".to_string(),
+            schema_name: Some("public".to_string()),
+            rows,
+            sample_size: 30,
+            total_rows: Some(total_rows),
+            sampling_strategy: SamplingStrategy::Random { limit: 30 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }]);
+        schema
+    }
+
+    #[test]
+    fn test_infer_enums() {
+        let enums = infer_enums(&fixture(5_000));
+        let columns: Vec<&str> = enums.iter().map(|e| e.column.as_str()).collect();
+        // Keys, identifier names, foreign keys, and decimals are skipped
+        assert_eq!(columns, ["status", "priority", "note"]);
+
+        assert_eq!(
+            enums[0].values,
+            [json!("cancelled"), json!("pending"), json!("shipped")]
+        );
+        assert_eq!(enums[0].sampled, 29);
+        assert!(!enums[0].complete);
+        assert_eq!(enums[1].values, [json!(1), json!(2), json!(3)]);
+
+        assert!(infer_enums(&fixture(30)).iter().all(|e| e.complete));
+    }
+
+    #[test]
+    fn test_open_sets_are_not_enums() {
+        let mut schema = fixture(5_000);
+        let sample = &mut schema.samples.as_mut().unwrap()[0];
+
+        // Too many distinct values for the sample size
+        for (i, row) in sample.rows.iter_mut().enumerate() {
+            row["note"] = json!(format!("note {}", i % 11));
+        }
+        // One value of free text disqualifies the column
+        sample.rows[0]["status"] = json!("x".repeat(MAX_LABEL_LENGTH + 1));
+        sample.rows.truncate(MIN_SAMPLED_VALUES);
+        sample.rows[0]["priority"] = Value::Null;
+
+        let enums = infer_enums(&schema);
+        assert!(enums.is_empty(), "{:?}", enums);
+    }
+
+    #[test]
+    fn test_identifier_names() {
+        assert!(is_identifier_name("id"));
+        assert!(is_identifier_name("customer_id"));
+        assert!(is_identifier_name("customerId"));
+        assert!(is_identifier_name("CustomerID"));
+        assert!(!is_identifier_name("valid"));
+        assert!(!is_identifier_name("paid"));
+        assert!(!is_identifier_name("PAID"));
+    }
+}
@@ -184,6 +184,18 @@ pub fn insufficient_privileges(required: impl Into<String>) -> Self {
         }
     }
 
+    /// Whether the error, or any error it wraps, reports denied access.
+    ///
+    /// Collection errors keep the driver error as their source, so a
+    /// `permission denied` from the database is found even though the
+    /// top-level message only names the query that failed.
+    pub fn is_insufficient_privileges(&self) -> bool {
+        std::iter::successors(Some(self as &(dyn std::error::Error + 'static)), |error| {
+            error.source()
+        })
+        .any(|error| crate::privileges::is_privilege_error(&error.to_string()))
+    }
+
     /// Creates a query execution error
     pub fn query_failed(context: impl Into<String>) -> Self {
         Self::QueryExecution {
@@ -275,4 +287,21 @@ fn test_error_creation() {
         let error = DbSurveyorError::insufficient_privileges("SELECT on schema");
         assert!(error.to_string().contains("SELECT on schema"));
     }
+
+    #[test]
+    fn test_is_insufficient_privileges_checks_sources() {
+        assert!(DbSurveyorError::insufficient_privileges("SHOW VIEW").is_insufficient_privileges());
+
+        let denied = std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "permission denied for view pg_views",
+        );
+        let error =
+            DbSurveyorError::collection_failed("Failed to enumerate database views", denied);
+        assert!(error.is_insufficient_privileges());
+
+        let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+        let error = DbSurveyorError::collection_failed("Failed to enumerate database views", reset);
+        assert!(!error.is_insufficient_privileges());
+    }
 }
@@ -0,0 +1,670 @@
+//! Append-only survey journal.
+//!
+//! A journal keeps every collection of an engagement in one file
+//! (`dbsurveyor-collect --append-to <journal>`). Each line is a JSON record:
+//! a header and a body that is either the full survey or a patch against
+//! the survey reconstructed from the records before it. The first record
+//! is always full; later ones are diffs unless the diff would be larger
+//! than the survey itself.
+//!
+//! The headers form the journal's index. Each header carries a hash over
+//! the previous record's hash, its own header fields, and its body, so
+//! editing, reordering, or removing a record breaks the chain from that
+//! point on and reading the journal fails. Truncating the newest records is
+//! only detectable by comparing against a recorded head hash.
+//!
+//! A journal written with the survey signing key (`--sign`) chains
+//! HMAC-SHA256 under that key, the same MAC as detached survey signatures,
+//! and is tamper-evident: rewriting a record needs the key to recompute the
+//! chain. Without a key the chain is plain SHA-256, which anyone can
+//! recompute, so it only detects accidental damage. A journal is keyed or
+//! not from its first record on, and reading a keyed journal requires the
+//! key.
+//!
+//! Patches use the `add`, `remove`, and `replace` operations of JSON Patch
+//! (RFC 6902). Arrays are compared by position, so a table inserted in the
+//! middle of the list patches every table after it.
+
+use crate::Result;
+use crate::canonical::to_canonical_json;
+use crate::error::DbSurveyorError;
+use crate::security::signing;
+use crate::validation::{InputLimits, ValidationError, check_input_limits};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// `prev_hash` of the first record.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Whether a record holds the whole survey or changes to the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    Full,
+    Diff,
+}
+
+/// Index entry of one journal record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalHeader {
+    /// 1-based position in the journal
+    pub seq: u64,
+    pub kind: RecordKind,
+    /// When the record was appended
+    pub recorded_at: DateTime<Utc>,
+    /// `collection_metadata.collected_at` of the survey, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collected_at: Option<DateTime<Utc>>,
+    /// Hash of the previous record
+    pub prev_hash: String,
+    /// Whether `hash` is an HMAC under the signing key
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keyed: bool,
+    /// SHA-256, or HMAC-SHA256 when keyed, over `prev_hash`, the other
+    /// header fields, and the body
+    pub hash: String,
+}
+
+impl JournalHeader {
+    /// Collection time, falling back to when the record was appended.
+    pub fn effective_time(&self) -> DateTime<Utc> {
+        self.collected_at.unwrap_or(self.recorded_at)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JournalRecord {
+    header: JournalHeader,
+    body: Value,
+}
+
+/// One JSON Patch operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// A journal read into memory, with its hash chain verified.
+#[derive(Debug)]
+pub struct Journal {
+    records: Vec<(JournalHeader, Value)>,
+}
+
+impl Journal {
+    /// Reads and verifies a journal file. A missing file is an empty journal.
+    ///
+    /// `key` is the signing key of a keyed journal; each record is checked
+    /// against `limits` before it is parsed.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, exceeds `limits`, a
+    /// record is malformed, the hash chain is broken, or the journal is
+    /// keyed and `key` is `None` (or the reverse).
+    pub fn read(path: &Path, key: Option<&[u8]>, limits: &InputLimits) -> Result<Self> {
+        let rejected = |e: ValidationError| {
+            DbSurveyorError::configuration(format!("Journal {} rejected: {}", path.display(), e))
+        };
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    records: Vec::new(),
+                });
+            }
+            Err(e) => {
+                return Err(DbSurveyorError::Io {
+                    context: format!("Failed to open journal {}", path.display()),
+                    source: e,
+                });
+            }
+        };
+
+        let size = file.metadata().map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to read journal {}", path.display()),
+            source: e,
+        })?;
+        limits.check_size(size.len()).map_err(rejected)?;
+
+        let mut records = Vec::new();
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| DbSurveyorError::Io {
+                context: format!("Failed to read journal {}", path.display()),
+                source: e,
+            })?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            check_input_limits(&line, limits).map_err(rejected)?;
+            let record: JournalRecord = serde_json::from_str(&line).map_err(|e| {
+                DbSurveyorError::configuration(format!(
+                    "Journal {} has a malformed record on line {}: {}",
+                    path.display(),
+                    number + 1,
+                    e
+                ))
+            })?;
+            let header = record.header;
+            let expected_seq = records.len() as u64 + 1;
+            match (header.keyed, key) {
+                (true, None) => {
+                    return Err(DbSurveyorError::configuration(format!(
+                        "Journal {} is keyed; reading it requires the signing key",
+                        path.display()
+                    )));
+                }
+                (false, Some(_)) => {
+                    return Err(DbSurveyorError::configuration(format!(
+                        "Journal {} record {} is not keyed and cannot be verified with the signing key",
+                        path.display(),
+                        expected_seq
+                    )));
+                }
+                _ => {}
+            }
+            if header.seq != expected_seq
+                || header.prev_hash != prev_hash
+                || !verify_record(&header, &record.body, key)
+            {
+                return Err(DbSurveyorError::configuration(format!(
+                    "Journal {} failed verification at record {} (line {}): the journal was modified",
+                    path.display(),
+                    expected_seq,
+                    number + 1
+                )));
+            }
+            prev_hash.clone_from(&header.hash);
+            records.push((header, record.body));
+        }
+        Ok(Self { records })
+    }
+
+    /// Headers of every record, oldest first.
+    pub fn index(&self) -> impl Iterator<Item = &JournalHeader> {
+        self.records.iter().map(|(header, _)| header)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Survey as of record `seq`.
+    ///
+    /// # Errors
+    /// Returns an error if there is no such record or a patch does not apply.
+    pub fn reconstruct(&self, seq: u64) -> Result<Value> {
+        if seq == 0 || seq > self.records.len() as u64 {
+            return Err(DbSurveyorError::configuration(format!(
+                "Journal has no record {} (records 1-{})",
+                seq,
+                self.records.len()
+            )));
+        }
+        let records = &self.records[..seq as usize];
+        let start = records
+            .iter()
+            .rposition(|(header, _)| header.kind == RecordKind::Full)
+            .ok_or_else(|| DbSurveyorError::configuration("Journal has no full record"))?;
+
+        let mut survey = records[start].1.clone();
+        for (header, body) in &records[start + 1..] {
+            let ops: Vec<PatchOp> = serde_json::from_value(body.clone()).map_err(|e| {
+                DbSurveyorError::configuration(format!(
+                    "Journal record {} has a malformed patch: {}",
+                    header.seq, e
+                ))
+            })?;
+            apply_patch(&mut survey, &ops).map_err(|e| {
+                DbSurveyorError::configuration(format!(
+                    "Journal record {} does not apply: {}",
+                    header.seq, e
+                ))
+            })?;
+        }
+        Ok(survey)
+    }
+
+    /// Survey as of `time`: the latest record collected at or before it.
+    ///
+    /// # Errors
+    /// Returns an error if every record was collected after `time`.
+    pub fn reconstruct_at(&self, time: DateTime<Utc>) -> Result<Value> {
+        let seq = self.seq_at(time).ok_or_else(|| {
+            DbSurveyorError::configuration(format!("Journal has no record before {}", time))
+        })?;
+        self.reconstruct(seq)
+    }
+
+    /// Number of the latest record collected at or before `time`.
+    pub fn seq_at(&self, time: DateTime<Utc>) -> Option<u64> {
+        self.index()
+            .filter(|header| header.effective_time() <= time)
+            .map(|header| header.seq)
+            .last()
+    }
+
+    /// Survey as of the newest record, if any.
+    ///
+    /// # Errors
+    /// Returns an error if a patch does not apply.
+    pub fn latest(&self) -> Result<Option<Value>> {
+        match self.records.len() {
+            0 => Ok(None),
+            len => self.reconstruct(len as u64).map(Some),
+        }
+    }
+}
+
+/// Appends `survey` to the journal at `path`, creating it if needed. With
+/// a `key` the record is chained with HMAC-SHA256 under it.
+///
+/// The existing journal is verified first, so nothing is appended to a
+/// journal that was tampered with, and a keyed journal only grows with the
+/// same key.
+///
+/// # Errors
+/// Returns an error if the journal fails verification or cannot be written.
+pub fn append(
+    path: &Path,
+    survey: &Value,
+    key: Option<&[u8]>,
+    limits: &InputLimits,
+) -> Result<JournalHeader> {
+    let journal = Journal::read(path, key, limits)?;
+    let (kind, body) = match journal.latest()? {
+        None => (RecordKind::Full, survey.clone()),
+        Some(previous) => {
+            let ops = serde_json::to_value(diff(&previous, survey))
+                .map_err(|e| DbSurveyorError::collection_failed("Journal patch", e))?;
+            if to_canonical_json(&ops).len() < to_canonical_json(survey).len() {
+                (RecordKind::Diff, ops)
+            } else {
+                (RecordKind::Full, survey.clone())
+            }
+        }
+    };
+
+    let mut header = JournalHeader {
+        seq: journal.len() as u64 + 1,
+        kind,
+        recorded_at: Utc::now(),
+        collected_at: survey
+            .pointer("/collection_metadata/collected_at")
+            .and_then(Value::as_str)
+            .and_then(|time| time.parse().ok()),
+        prev_hash: journal
+            .index()
+            .last()
+            .map_or_else(|| GENESIS_HASH.to_string(), |last| last.hash.clone()),
+        keyed: key.is_some(),
+        hash: String::new(),
+    };
+    let message = chain_message(&header, &body);
+    header.hash = match key {
+        Some(key) => signing::sign(&message, key)?,
+        None => sha256_hex(&message),
+    };
+
+    let mut line = serde_json::to_string(&JournalRecord {
+        header: header.clone(),
+        body,
+    })
+    .map_err(|e| DbSurveyorError::collection_failed("Journal record serialization", e))?;
+    line.push('\n');
+
+    let io_error = |e| DbSurveyorError::Io {
+        context: format!("Failed to append to journal {}", path.display()),
+        source: e,
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(io_error)?;
+    file.write_all(line.as_bytes()).map_err(io_error)?;
+    file.sync_all().map_err(io_error)?;
+    Ok(header)
+}
+
+/// Bytes hashed to chain a record to its predecessor.
+fn chain_message(header: &JournalHeader, body: &Value) -> Vec<u8> {
+    let fields = serde_json::json!({
+        "seq": header.seq,
+        "kind": header.kind,
+        "recorded_at": header.recorded_at,
+        "collected_at": header.collected_at,
+    });
+    [
+        header.prev_hash.as_str(),
+        &to_canonical_json(&fields),
+        &to_canonical_json(body),
+    ]
+    .concat()
+    .into_bytes()
+}
+
+/// Whether a record's hash matches its contents, under `key` when keyed.
+fn verify_record(header: &JournalHeader, body: &Value, key: Option<&[u8]>) -> bool {
+    let message = chain_message(header, body);
+    match key {
+        Some(key) => signing::verify(&message, key, &header.hash),
+        None => header.hash == sha256_hex(&message),
+    }
+}
+
+fn sha256_hex(message: &[u8]) -> String {
+    Sha256::digest(message)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Operations turning `old` into `new`.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOp> {
+    let mut ops = Vec::new();
+    diff_into(old, new, String::new(), &mut ops);
+    ops
+}
+
+fn diff_into(old: &Value, new: &Value, path: String, ops: &mut Vec<PatchOp>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = format!("{}/{}", path, escape_token(key));
+                match new.get(key) {
+                    Some(new_value) => diff_into(old_value, new_value, child, ops),
+                    None => ops.push(PatchOp::Remove { path: child }),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    ops.push(PatchOp::Add {
+                        path: format!("{}/{}", path, escape_token(key)),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let common = old.len().min(new.len());
+            for index in 0..common {
+                diff_into(&old[index], &new[index], format!("{}/{}", path, index), ops);
+            }
+            // Remove from the end so earlier indexes stay valid
+            for index in (common..old.len()).rev() {
+                ops.push(PatchOp::Remove {
+                    path: format!("{}/{}", path, index),
+                });
+            }
+            for value in &new[common..] {
+                ops.push(PatchOp::Add {
+                    path: format!("{}/-", path),
+                    value: value.clone(),
+                });
+            }
+        }
+        _ if old == new => {}
+        _ => ops.push(PatchOp::Replace {
+            path,
+            value: new.clone(),
+        }),
+    }
+}
+
+/// Applies patch operations in order.
+///
+/// # Errors
+/// Returns a description of the first operation whose path does not exist.
+pub fn apply_patch(target: &mut Value, ops: &[PatchOp]) -> std::result::Result<(), String> {
+    for op in ops {
+        match op {
+            PatchOp::Replace { path, value } => {
+                *target
+                    .pointer_mut(path)
+                    .ok_or_else(|| format!("no value at {}", path))? = value.clone();
+            }
+            PatchOp::Add { path, value } => {
+                let (parent, key) = split_path(path)?;
+                match target.pointer_mut(parent) {
+                    Some(Value::Object(map)) => {
+                        map.insert(key, value.clone());
+                    }
+                    Some(Value::Array(items)) if key == "-" => items.push(value.clone()),
+                    Some(Value::Array(items)) => {
+                        let index = array_index(&key, items.len() + 1, path)?;
+                        items.insert(index, value.clone());
+                    }
+                    _ => return Err(format!("no container at {}", path)),
+                }
+            }
+            PatchOp::Remove { path } => {
+                let (parent, key) = split_path(path)?;
+                let removed = match target.pointer_mut(parent) {
+                    Some(Value::Object(map)) => map.remove(&key).is_some(),
+                    Some(Value::Array(items)) => {
+                        let index = array_index(&key, items.len(), path)?;
+                        items.remove(index);
+                        true
+                    }
+                    _ => false,
+                };
+                if !removed {
+                    return Err(format!("no value at {}", path));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits a JSON Pointer into its parent pointer and unescaped last token.
+fn split_path(path: &str) -> std::result::Result<(&str, String), String> {
+    let (parent, token) = path
+        .rsplit_once('/')
+        .ok_or_else(|| format!("invalid path {}", path))?;
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn array_index(token: &str, len: usize, path: &str) -> std::result::Result<usize, String> {
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index < len)
+        .ok_or_else(|| format!("invalid array index at {}", path))
+}
+
+/// Escapes an object key for use in a JSON Pointer.
+fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn survey(collected_at: &str, tables: Value) -> Value {
+        let columns: Vec<Value> = (1..=20)
+            .map(|i| json!({"name": format!("column_{}", i), "data_type": "text"}))
+            .collect();
+        json!({
+            "format_version": "1.0",
+            "collection_metadata": {"collected_at": collected_at},
+            "views": [{"name": "order_summary", "columns": columns}],
+            "tables": tables,
+        })
+    }
+
+    #[test]
+    fn test_diff_round_trips() {
+        let old = json!({
+            "tables": [{"name": "a", "columns": [1, 2, 3]}, {"name": "b"}, {"name": "c"}],
+            "comment": "x",
+            "a/b": 1,
+        });
+        let new = json!({
+            "tables": [{"name": "a", "columns": [1, 4]}, {"name": "b", "rows": 7}],
+            "views": [],
+            "a/b": 2,
+        });
+        let ops = diff(&old, &new);
+        let mut patched = old.clone();
+        apply_patch(&mut patched, &ops).expect("patch applies");
+        assert_eq!(patched, new);
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    /// Fresh journal path unique to the test and process.
+    fn journal_path(test: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "dbsurveyor_journal_{}_{}.jsonl",
+            test,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_append_and_reconstruct() {
+        let path = journal_path("reconstruct");
+        let limits = InputLimits::default();
+        let first = survey("2026-01-05T10:00:00Z", json!([{"name": "orders"}]));
+        let second = survey(
+            "2026-02-05T10:00:00Z",
+            json!([{"name": "orders"}, {"name": "refunds"}]),
+        );
+
+        assert_eq!(
+            append(&path, &first, None, &limits).expect("append").kind,
+            RecordKind::Full
+        );
+        assert_eq!(
+            append(&path, &second, None, &limits).expect("append").kind,
+            RecordKind::Diff
+        );
+
+        let journal = Journal::read(&path, None, &limits).expect("journal verifies");
+        assert_eq!(journal.len(), 2);
+        assert_eq!(journal.reconstruct(1).expect("record 1"), first);
+        assert_eq!(journal.reconstruct(2).expect("record 2"), second);
+        let mid_january = "2026-01-20T00:00:00Z".parse().expect("timestamp");
+        assert_eq!(journal.reconstruct_at(mid_january).expect("view"), first);
+        assert!(journal.reconstruct(3).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_tampering_detected() {
+        let path = journal_path("tampering");
+        let limits = InputLimits::default();
+        append(
+            &path,
+            &survey("2026-01-05T10:00:00Z", json!([{"name": "orders"}])),
+            None,
+            &limits,
+        )
+        .expect("append");
+        append(
+            &path,
+            &survey("2026-02-05T10:00:00Z", json!([{"name": "refunds"}])),
+            None,
+            &limits,
+        )
+        .expect("append");
+
+        let content = std::fs::read_to_string(&path).expect("read journal");
+        std::fs::write(&path, content.replace("refunds", "returns")).expect("write journal");
+        let err = Journal::read(&path, None, &limits).expect_err("tampering detected");
+        assert!(err.to_string().contains("failed verification at record 2"));
+        assert!(
+            append(
+                &path,
+                &survey("2026-03-05T10:00:00Z", json!([])),
+                None,
+                &limits
+            )
+            .is_err()
+        );
+
+        // A truncated record is rejected as bad input, not a failed collection
+        std::fs::write(&path, &content[..content.len() / 2]).expect("write journal");
+        let err = Journal::read(&path, None, &limits).expect_err("truncation detected");
+        assert!(matches!(err, DbSurveyorError::Configuration { .. }));
+        assert!(err.to_string().contains("malformed record on line"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Rewrites record `seq` with `edit` and recomputes its unkeyed hash,
+    /// as someone without the key would.
+    fn rechain(path: &Path, seq: usize, edit: impl Fn(&mut Value)) {
+        let content = std::fs::read_to_string(path).expect("read journal");
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let mut record: JournalRecord = serde_json::from_str(&lines[seq - 1]).expect("record");
+        edit(&mut record.body);
+        record.header.hash = sha256_hex(&chain_message(&record.header, &record.body));
+        lines[seq - 1] = serde_json::to_string(&record).expect("serialize record");
+        std::fs::write(path, lines.join("\n") + "\n").expect("write journal");
+    }
+
+    #[test]
+    fn test_keyed_chain() {
+        let path = journal_path("keyed");
+        let limits = InputLimits::default();
+        let key: &[u8] = b"engagement-key";
+        let first = survey("2026-01-05T10:00:00Z", json!([{"name": "orders"}]));
+        let header = append(&path, &first, Some(key), &limits).expect("append");
+        assert!(header.keyed);
+
+        // A keyed journal is read and extended only with its key
+        assert!(Journal::read(&path, Some(key), &limits).is_ok());
+        let err = Journal::read(&path, None, &limits).expect_err("key required");
+        assert!(err.to_string().contains("requires the signing key"));
+        assert!(Journal::read(&path, Some(b"other".as_slice()), &limits).is_err());
+        assert!(append(&path, &first, None, &limits).is_err());
+
+        // Recomputing the hash without the key does not hide an edit
+        rechain(&path, 1, |body| {
+            body["tables"][0]["name"] = json!("returns")
+        });
+        let err = Journal::read(&path, Some(key), &limits).expect_err("edit detected");
+        assert!(err.to_string().contains("failed verification at record 1"));
+        let _ = std::fs::remove_file(&path);
+
+        // An unkeyed journal cannot be passed off as verified by the key
+        append(&path, &first, None, &limits).expect("append");
+        assert!(Journal::read(&path, Some(key), &limits).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_input_limits_applied() {
+        let path = journal_path("limits");
+        let limits = InputLimits::default();
+        append(
+            &path,
+            &survey("2026-01-05T10:00:00Z", json!([{"name": "orders"}])),
+            None,
+            &limits,
+        )
+        .expect("append");
+
+        let small = InputLimits::default().with_max_file_size_bytes(64);
+        let err = Journal::read(&path, None, &small).expect_err("file too large");
+        assert!(err.to_string().contains("rejected"));
+        let shallow = InputLimits::default().with_max_json_depth(2);
+        assert!(Journal::read(&path, None, &shallow).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -17,33 +17,79 @@
 //! - Comprehensive error handling with credential sanitization
 
 pub mod adapters;
+pub mod anonymize;
+pub mod canonical;
+pub mod collation;
+pub mod collisions;
+pub mod dedup;
+pub mod defaults;
+pub mod documentation;
+pub mod drift;
+pub mod enums;
 pub mod error;
+pub mod journal;
+pub mod lint;
+pub mod lob;
 pub mod logging;
 pub mod models;
+pub mod privileges;
+pub mod profile;
+pub mod protection;
 pub mod quality;
+pub mod redact;
+pub mod relationships;
+pub mod secrets;
 pub mod security;
+pub mod synthetic;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod validation;
+pub mod versions;
 
 // Re-export commonly used types
 pub use adapters::{
     AdapterFeature, CollectionConfig, ConnectionConfig, DatabaseAdapter, OutputFormat,
-    SamplingConfig, SensitivePattern,
+    SamplingConfig, SensitiveAction, SensitivePattern, SensitivePatternRules,
 };
+pub use collation::{CollationMismatch, CollationSummary, summarize_collations};
+pub use defaults::classify_default;
+pub use documentation::{
+    CommentCoverage, UndocumentedTable, comment_coverage, undocumented_tables,
+};
+pub use drift::{Baseline, ChangeKind, DriftObject, SchemaChange, diff_schemas, diff_surveys};
 pub use error::{DbSurveyorError, Result};
+pub use lob::{LobColumn, LobSignal, LobTable, VolumeEstimate, large_object_inventory};
 pub use models::{
-    AccessLevel, CollectionMode, CollectionStatus, Column, DatabaseInfo, DatabaseSchema,
-    DatabaseServerSchema, DatabaseType, FORMAT_VERSION, OrderingStrategy, SamplingStrategy,
-    ServerInfo, SortDirection, Table, TableSample, UnifiedDataType,
+    AccessLevel, AccessProbe, AuxiliaryFile, AuxiliaryFileKind, CollectionMode, CollectionStatus,
+    CollectionWarning, Column, DatabaseInfo, DatabaseManifestEntry, DatabaseSchema,
+    DatabaseServerSchema, DatabaseType, DefaultValue, FORMAT_VERSION, IntegrityCheck,
+    IntegrityCheckMode, IntegrityStatus, OrderingStrategy, ReadOnlyLevel, ReadOnlyVerification,
+    SampleColumnType, SampleValueEncoding, SamplingStrategy, ServerInfo, ServerRole, SortDirection,
+    SurveyDocument, Table, TableSample, UnifiedDataType, WarningCategory, WarningSeverity,
+};
+pub use privileges::{
+    PrivilegeRequirement, PrivilegeSkip, grant_statements, privilege_requirements, privilege_skips,
 };
+pub use protection::{ProtectedColumn, Protection, ProtectionEvidence, detect_protected_columns};
 pub use quality::{
     AnomalyConfig, AnomalySensitivity, QualityAnalyzer, QualityConfig, TableQualityMetrics,
 };
 
+pub use relationships::{
+    Cardinality, InferredRelationship, RelationshipEvidence, infer_relationships,
+};
+pub use secrets::{SecretFinding, SecretKind, redact_metadata_secrets};
+pub use synthetic::{SyntheticSchemaConfig, generate_synthetic_schema};
+
 #[cfg(feature = "encryption")]
 pub use security::encryption;
 
 pub use validation::{
-    ValidationError, initialize_schema_validator, validate_and_parse_schema, validate_schema_output,
+    InputLimits, ValidationError, initialize_schema_validator, validate_and_parse_schema,
+    validate_and_parse_schema_with_limits, validate_and_parse_survey_with_limits,
+    validate_schema_output, validate_server_schema_output,
 };
 
-pub use logging::{init_logging, should_disable_color};
+pub use logging::{
+    init_logging, init_logging_to_stderr, should_disable_color, statements_executed,
+};
@@ -0,0 +1,953 @@
+//! Schema lint rules for common performance and design problems, and for
+//! dangling or orphaned objects left behind by incomplete cleanups.
+//!
+//! Rules only inspect collected metadata, so they run at collection time
+//! (the collector's `--strict` mode) as cheaply as after it. Each finding is
+//! reported as a [`CollectionWarning`] in the [`WarningCategory::Lint`]
+//! category whose message starts with the rule id; findings of one rule
+//! share a message and are merged by warning deduplication, listing the
+//! affected objects.
+
+use crate::collation::summarize_collations;
+use crate::models::{
+    CollectionWarning, DatabaseSchema, Index, Table, WarningCategory, WarningSeverity,
+};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// A schema lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// Foreign key columns are not the leading columns of any index, so
+    /// joins and cascading deletes scan the referencing table
+    MissingForeignKeyIndex,
+    /// Table has no primary key
+    MissingPrimaryKey,
+    /// Non-unique index whose columns lead another index of the same type
+    RedundantIndex,
+    /// Column collation or character set differs from the database default
+    MixedCollation,
+    /// Table uses a storage engine without transactions or crash recovery
+    /// (e.g. MySQL MyISAM or MEMORY)
+    NonTransactionalEngine,
+    /// Foreign key references a table that is not in the survey: dropped,
+    /// or outside what a partial collection covered
+    DanglingForeignKey,
+    /// View definition selects from a table or view that is not in the
+    /// survey
+    DanglingViewReference,
+    /// Trigger executes a function that is not in the survey
+    OrphanedTrigger,
+    /// Schema enumerated during collection holds no collected objects
+    EmptySchema,
+}
+
+/// Storage engines that do not support transactions, lowercase.
+const NON_TRANSACTIONAL_ENGINES: &[&str] = &[
+    "myisam",
+    "mrg_myisam",
+    "memory",
+    "heap",
+    "archive",
+    "csv",
+    "aria",
+    "blackhole",
+    "federated",
+];
+
+impl LintRule {
+    /// Every rule, in reporting order.
+    pub const ALL: [Self; 9] = [
+        Self::MissingForeignKeyIndex,
+        Self::MissingPrimaryKey,
+        Self::RedundantIndex,
+        Self::MixedCollation,
+        Self::NonTransactionalEngine,
+        Self::DanglingForeignKey,
+        Self::DanglingViewReference,
+        Self::OrphanedTrigger,
+        Self::EmptySchema,
+    ];
+
+    /// Stable identifier used on the command line and in warning messages.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::MissingForeignKeyIndex => "missing-fk-index",
+            Self::MissingPrimaryKey => "missing-primary-key",
+            Self::RedundantIndex => "redundant-index",
+            Self::MixedCollation => "mixed-collation",
+            Self::NonTransactionalEngine => "non-transactional-engine",
+            Self::DanglingForeignKey => "dangling-foreign-key",
+            Self::DanglingViewReference => "dangling-view-reference",
+            Self::OrphanedTrigger => "orphaned-trigger",
+            Self::EmptySchema => "empty-schema",
+        }
+    }
+
+    fn severity(self) -> WarningSeverity {
+        match self {
+            Self::MissingForeignKeyIndex
+            | Self::MissingPrimaryKey
+            | Self::MixedCollation
+            | Self::NonTransactionalEngine
+            | Self::DanglingForeignKey
+            | Self::DanglingViewReference
+            | Self::OrphanedTrigger => WarningSeverity::Warning,
+            Self::RedundantIndex | Self::EmptySchema => WarningSeverity::Info,
+        }
+    }
+
+    fn message(self) -> String {
+        let description = match self {
+            Self::MissingForeignKeyIndex => "foreign key columns have no supporting index",
+            Self::MissingPrimaryKey => "table has no primary key",
+            Self::RedundantIndex => "index is covered by another index",
+            Self::MixedCollation => {
+                "column collation or character set differs from the database default"
+            }
+            Self::NonTransactionalEngine => "table uses a non-transactional storage engine",
+            Self::DanglingForeignKey => "foreign key references a table missing from the survey",
+            Self::DanglingViewReference => {
+                "view references a table or view missing from the survey"
+            }
+            Self::OrphanedTrigger => "trigger executes a function missing from the survey",
+            Self::EmptySchema => "schema contains no collected objects",
+        };
+        format!("{}: {}", self.id(), description)
+    }
+
+    fn warning(self, object: String) -> CollectionWarning {
+        CollectionWarning::new(WarningCategory::Lint, self.message())
+            .with_severity(self.severity())
+            .with_object(object)
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.id() == id)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|rule| rule.id()).collect();
+                format!(
+                    "unknown lint rule '{}' (expected one of: {})",
+                    s,
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Evaluates `rules` against a schema, returning one warning per finding.
+pub fn lint_schema(schema: &DatabaseSchema, rules: &[LintRule]) -> Vec<CollectionWarning> {
+    let mut warnings = Vec::new();
+    for rule in LintRule::ALL {
+        if !rules.contains(&rule) {
+            continue;
+        }
+        match rule {
+            LintRule::MixedCollation => {
+                for mismatch in summarize_collations(schema).mismatches {
+                    warnings.push(rule.warning(mismatch.object()));
+                }
+                continue;
+            }
+            LintRule::DanglingViewReference => {
+                warnings.extend(dangling_view_references(schema).map(|o| rule.warning(o)));
+                continue;
+            }
+            LintRule::OrphanedTrigger => {
+                warnings.extend(orphaned_triggers(schema).map(|o| rule.warning(o)));
+                continue;
+            }
+            LintRule::EmptySchema => {
+                warnings.extend(empty_schemas(schema).map(|o| rule.warning(o)));
+                continue;
+            }
+            _ => {}
+        }
+        for table in &schema.tables {
+            let indexes = table_indexes(schema, table);
+            match rule {
+                LintRule::MissingForeignKeyIndex => {
+                    for foreign_key in &table.foreign_keys {
+                        if !has_supporting_index(table, &indexes, &foreign_key.columns) {
+                            warnings.push(rule.warning(format!(
+                                "{} ({})",
+                                table_name(table),
+                                foreign_key.columns.join(", ")
+                            )));
+                        }
+                    }
+                }
+                LintRule::MissingPrimaryKey => {
+                    let has_primary_key =
+                        table.primary_key.is_some() || indexes.iter().any(|index| index.is_primary);
+                    if !has_primary_key && table.virtual_table.is_none() {
+                        warnings.push(rule.warning(table_name(table)));
+                    }
+                }
+                LintRule::DanglingForeignKey => {
+                    for foreign_key in &table.foreign_keys {
+                        let referenced_schema = foreign_key
+                            .referenced_schema
+                            .as_deref()
+                            .or(table.schema.as_deref());
+                        let exists = schema.tables.iter().any(|candidate| {
+                            same_name(&candidate.name, &foreign_key.referenced_table)
+                                && same_schema(candidate.schema.as_deref(), referenced_schema)
+                        });
+                        if !exists {
+                            let referenced = match referenced_schema {
+                                Some(s) => format!("{}.{}", s, foreign_key.referenced_table),
+                                None => foreign_key.referenced_table.clone(),
+                            };
+                            warnings.push(rule.warning(format!(
+                                "{} ({}) -> {}",
+                                table_name(table),
+                                foreign_key.columns.join(", "),
+                                referenced
+                            )));
+                        }
+                    }
+                }
+                LintRule::MixedCollation
+                | LintRule::DanglingViewReference
+                | LintRule::OrphanedTrigger
+                | LintRule::EmptySchema => {}
+                LintRule::NonTransactionalEngine => {
+                    let engine = table.options.as_ref().and_then(|o| o.engine.as_deref());
+                    if let Some(engine) = engine.filter(|engine| {
+                        NON_TRANSACTIONAL_ENGINES.contains(&engine.to_ascii_lowercase().as_str())
+                    }) {
+                        warnings.push(rule.warning(format!("{} ({})", table_name(table), engine)));
+                    }
+                }
+                LintRule::RedundantIndex => {
+                    for (position, index) in indexes.iter().enumerate() {
+                        if is_redundant(position, index, &indexes) {
+                            warnings.push(rule.warning(format!(
+                                "{}.{}",
+                                table_name(table),
+                                index.name
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    warnings
+}
+
+/// Schemas holding system catalogs, which surveys never collect.
+const SYSTEM_SCHEMAS: &[&str] = &[
+    "pg_catalog",
+    "information_schema",
+    "mysql",
+    "performance_schema",
+    "sys",
+];
+
+/// Functions whose arguments use `FROM` for something other than a
+/// relation, e.g. `EXTRACT(year FROM placed_at)`.
+const FROM_ARGUMENT_FUNCTIONS: &[&str] = &["extract", "substring", "trim", "overlay", "position"];
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Whether the run that produced the survey skipped an object type
+/// (`views`, `functions`, ...), so its absence proves nothing.
+fn excluded(schema: &DatabaseSchema, object_type: &str) -> bool {
+    schema
+        .collection_metadata
+        .settings
+        .as_ref()
+        .is_some_and(|settings| settings.excluded_objects.iter().any(|o| o == object_type))
+}
+
+/// Schemas match when equal, or when either side does not name one.
+fn same_schema(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same_name(a, b),
+        _ => true,
+    }
+}
+
+/// Views naming a relation in `FROM` or `JOIN` that the survey does not
+/// contain, as `view -> relation`.
+///
+/// References into system schemas, or into schemas the survey holds no
+/// objects from, are not reported: those relations were never collected.
+fn dangling_view_references(schema: &DatabaseSchema) -> impl Iterator<Item = String> + '_ {
+    let mut known_schemas: HashSet<String> = schema
+        .tables
+        .iter()
+        .filter_map(|t| t.schema.as_deref())
+        .chain(schema.views.iter().filter_map(|v| v.schema.as_deref()))
+        .chain(schema.database_info.schemas.iter().map(String::as_str))
+        .map(str::to_lowercase)
+        .collect();
+    known_schemas.insert(schema.database_info.name.to_lowercase());
+
+    schema.views.iter().flat_map(move |view| {
+        let definition = view.definition.as_deref().unwrap_or_default();
+        let mut missing: Vec<String> = Vec::new();
+        for (qualifier, name) in referenced_relations(definition) {
+            let collected = match &qualifier {
+                Some(q) => known_schemas.contains(&q.to_lowercase()),
+                None => {
+                    !name.to_lowercase().starts_with("pg_")
+                        && !name.to_lowercase().starts_with("sqlite_")
+                }
+            };
+            let exists = schema
+                .tables
+                .iter()
+                .map(|t| (t.schema.as_deref(), t.name.as_str()))
+                .chain(
+                    schema
+                        .views
+                        .iter()
+                        .map(|v| (v.schema.as_deref(), v.name.as_str())),
+                )
+                .any(|(s, n)| {
+                    same_name(n, &name)
+                        && (same_schema(s, qualifier.as_deref())
+                            || qualifier
+                                .as_deref()
+                                .is_some_and(|q| same_name(q, &schema.database_info.name)))
+                });
+            let reference = match &qualifier {
+                Some(q) => format!("{}.{}", q, name),
+                None => name,
+            };
+            if collected && !exists && !missing.contains(&reference) {
+                missing.push(reference);
+            }
+        }
+        let view_name = match &view.schema {
+            Some(s) => format!("{}.{}", s, view.name),
+            None => view.name.clone(),
+        };
+        missing
+            .into_iter()
+            .map(move |reference| format!("{} -> {}", view_name, reference))
+    })
+}
+
+/// Relations a SQL query selects from, as (schema, name) with quoting
+/// removed.
+///
+/// This is a lexical scan, not a parser: it reads the name after each
+/// `FROM` or `JOIN`, skipping table functions, subqueries, common table
+/// expressions, `IS DISTINCT FROM`, and `FROM` inside `EXTRACT`-like
+/// function arguments.
+fn referenced_relations(sql: &str) -> Vec<(Option<String>, String)> {
+    static PATTERNS: OnceLock<(Regex, Regex)> = OnceLock::new();
+    let (reference, cte) = PATTERNS.get_or_init(|| {
+        let identifier = r#"(?:"[^"]+"|`[^`]+`|\[[^\]]+\]|[A-Za-z_][\w$]*)"#;
+        (
+            Regex::new(&format!(
+                r"(?i)\b(?:from|join)\s+(?:only\s+|lateral\s+)?({id}(?:\s*\.\s*{id}){{0,2}})(\s*\()?",
+                id = identifier
+            ))
+            .expect("Invalid relation reference pattern"),
+            Regex::new(r"(?i)(?:\bwith\s+(?:recursive\s+)?|,\s*)([A-Za-z_]\w*)\s+as\s*\(")
+                .expect("Invalid CTE pattern"),
+        )
+    });
+
+    let ctes: HashSet<String> = cte
+        .captures_iter(sql)
+        .map(|c| c[1].to_lowercase())
+        .collect();
+
+    let mut relations = Vec::new();
+    for captures in reference.captures_iter(sql) {
+        let (Some(keyword), Some(path)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        let distinct_from = sql[..keyword.start()]
+            .trim_end()
+            .to_lowercase()
+            .ends_with("distinct");
+        if captures.get(2).is_some() || distinct_from || inside_from_argument(sql, path.start()) {
+            continue;
+        }
+        let mut parts: Vec<String> = split_qualified(path.as_str());
+        let Some(name) = parts.pop() else {
+            continue;
+        };
+        let qualifier = parts.pop();
+        if qualifier.is_none() && ctes.contains(&name.to_lowercase()) {
+            continue;
+        }
+        if qualifier
+            .as_deref()
+            .is_some_and(|q| SYSTEM_SCHEMAS.contains(&q.to_lowercase().as_str()))
+        {
+            continue;
+        }
+        relations.push((qualifier, name));
+    }
+    relations
+}
+
+/// Splits `a.b.c` into unquoted parts, keeping dots inside quotes.
+fn split_qualified(path: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut closing = None;
+    for c in path.chars() {
+        match (closing, c) {
+            (Some(end), c) if c == end => closing = None,
+            (Some(_), c) => current.push(c),
+            (None, '"') => closing = Some('"'),
+            (None, '`') => closing = Some('`'),
+            (None, '[') => closing = Some(']'),
+            (None, '.') => parts.push(std::mem::take(&mut current)),
+            (None, c) if c.is_whitespace() => {}
+            (None, c) => current.push(c),
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+/// Whether `offset` lies inside the parentheses of a function that takes
+/// `FROM` in its arguments.
+fn inside_from_argument(sql: &str, offset: usize) -> bool {
+    let mut open: Vec<usize> = Vec::new();
+    for (position, c) in sql[..offset].char_indices() {
+        match c {
+            '(' => open.push(position),
+            ')' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    open.last().is_some_and(|&paren| {
+        let function = sql[..paren]
+            .trim_end()
+            .rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or_default();
+        FROM_ARGUMENT_FUNCTIONS.contains(&function.to_lowercase().as_str())
+    })
+}
+
+/// Triggers whose `EXECUTE FUNCTION` (or `EXECUTE PROCEDURE`) target is
+/// not among the collected functions and procedures, as
+/// `table.trigger -> function`.
+///
+/// Nothing is reported when routines were skipped or none were collected,
+/// since their collection was then skipped or denied rather than the
+/// functions missing.
+fn orphaned_triggers(schema: &DatabaseSchema) -> impl Iterator<Item = String> + '_ {
+    static EXECUTE: OnceLock<Regex> = OnceLock::new();
+    let execute = EXECUTE.get_or_init(|| {
+        Regex::new(r#"(?i)\bexecute\s+(?:function|procedure)\s+((?:"[^"]+"|[\w$]+)(?:\s*\.\s*(?:"[^"]+"|[\w$]+))?)\s*\("#)
+            .expect("Invalid trigger function pattern")
+    });
+    let routines_skipped = excluded(schema, "functions") || excluded(schema, "procedures");
+    let routines_collected =
+        !routines_skipped && (!schema.functions.is_empty() || !schema.procedures.is_empty());
+
+    schema
+        .triggers
+        .iter()
+        .filter(move |_| routines_collected)
+        .filter_map(move |trigger| {
+            let definition = trigger.definition.as_deref()?;
+            let target = execute.captures(definition)?.get(1)?.as_str();
+            let mut parts = split_qualified(target);
+            let name = parts.pop()?;
+            let qualifier = parts.pop();
+            let exists = schema
+                .functions
+                .iter()
+                .chain(&schema.procedures)
+                .any(|routine| {
+                    same_name(&routine.name, &name)
+                        && same_schema(routine.schema.as_deref(), qualifier.as_deref())
+                });
+            if exists {
+                return None;
+            }
+            let table = match &trigger.schema {
+                Some(s) => format!("{}.{}", s, trigger.table_name),
+                None => trigger.table_name.clone(),
+            };
+            let function = match qualifier {
+                Some(q) => format!("{}.{}", q, name),
+                None => name,
+            };
+            Some(format!("{}.{} -> {}", table, trigger.name, function))
+        })
+}
+
+/// Enumerated schemas that no collected object belongs to.
+///
+/// Nothing is reported when an object type that lives in a schema was
+/// skipped, since the schema may hold only objects of that type.
+fn empty_schemas(schema: &DatabaseSchema) -> impl Iterator<Item = String> + '_ {
+    let complete = !["views", "procedures", "functions", "custom_types"]
+        .iter()
+        .any(|object_type| excluded(schema, object_type));
+    let occupied: HashSet<&str> = schema
+        .tables
+        .iter()
+        .filter_map(|t| t.schema.as_deref())
+        .chain(schema.views.iter().filter_map(|v| v.schema.as_deref()))
+        .chain(
+            schema
+                .functions
+                .iter()
+                .chain(&schema.procedures)
+                .filter_map(|r| r.schema.as_deref()),
+        )
+        .chain(schema.triggers.iter().filter_map(|t| t.schema.as_deref()))
+        .chain(
+            schema
+                .custom_types
+                .iter()
+                .filter_map(|t| t.schema.as_deref()),
+        )
+        .collect();
+    schema
+        .database_info
+        .schemas
+        .iter()
+        .filter(move |name| complete && !occupied.contains(name.as_str()))
+        .cloned()
+}
+
+fn table_name(table: &Table) -> String {
+    match &table.schema {
+        Some(schema) => format!("{}.{}", schema, table.name),
+        None => table.name.clone(),
+    }
+}
+
+/// Indexes of a table, whether reported on the table or at schema level.
+fn table_indexes<'a>(schema: &'a DatabaseSchema, table: &'a Table) -> Vec<&'a Index> {
+    let mut indexes: Vec<&Index> = table.indexes.iter().collect();
+    for index in &schema.indexes {
+        if index.table_name == table.name
+            && index.schema == table.schema
+            && !indexes.iter().any(|known| known.name == index.name)
+        {
+            indexes.push(index);
+        }
+    }
+    indexes
+}
+
+/// Whether some index (or the primary key) starts with all of `columns`,
+/// in any order.
+fn has_supporting_index(table: &Table, indexes: &[&Index], columns: &[String]) -> bool {
+    let leads_with = |key: &[&str]| {
+        key.get(..columns.len()).is_some_and(|leading| {
+            columns
+                .iter()
+                .all(|column| leading.contains(&column.as_str()))
+        })
+    };
+    let primary_key_leads = table.primary_key.as_ref().is_some_and(|primary_key| {
+        leads_with(
+            &primary_key
+                .columns
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        )
+    });
+    primary_key_leads
+        || indexes.iter().any(|index| {
+            leads_with(
+                &index
+                    .columns
+                    .iter()
+                    .map(|column| column.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+        })
+}
+
+/// Whether a non-unique index's columns lead another index of the same
+/// type. Of two identical indexes, only the later one is reported.
+fn is_redundant(position: usize, index: &Index, indexes: &[&Index]) -> bool {
+    if index.is_unique || index.is_primary || index.columns.is_empty() {
+        return false;
+    }
+    let columns: Vec<&str> = index.columns.iter().map(|c| c.name.as_str()).collect();
+    indexes.iter().enumerate().any(|(other_position, other)| {
+        if other_position == position || other.index_type != index.index_type {
+            return false;
+        }
+        let other_columns: Vec<&str> = other.columns.iter().map(|c| c.name.as_str()).collect();
+        other_columns.starts_with(&columns)
+            && (other_columns.len() > columns.len()
+                || other.is_unique
+                || other.is_primary
+                || other_position < position)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Column, DatabaseInfo, ForeignKey, IndexColumn, PrimaryKey, Procedure, TableOptions,
+        Trigger, TriggerEvent, TriggerTiming, UnifiedDataType, View,
+    };
+
+    fn index(name: &str, columns: &[&str], is_unique: bool) -> Index {
+        Index {
+            name: name.to_string(),
+            table_name: "orders".to_string(),
+            schema: Some("public".to_string()),
+            columns: columns
+                .iter()
+                .map(|column| IndexColumn {
+                    name: (*column).to_string(),
+                    sort_order: None,
+                })
+                .collect(),
+            is_unique,
+            is_primary: false,
+            index_type: Some("btree".to_string()),
+            options: None,
+        }
+    }
+
+    fn orders(primary_key: Option<&[&str]>, indexes: Vec<Index>) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables.push(Table {
+            primary_key: primary_key.map(|columns| PrimaryKey {
+                name: None,
+                columns: columns.iter().map(|c| (*c).to_string()).collect(),
+            }),
+            foreign_keys: vec![ForeignKey {
+                name: None,
+                columns: vec!["customer_id".to_string(), "region".to_string()],
+                referenced_table: "customers".to_string(),
+                referenced_schema: None,
+                referenced_columns: vec!["id".to_string(), "region".to_string()],
+                on_delete: None,
+                on_update: None,
+            }],
+            indexes,
+            ..Table::new("orders".to_string(), Some("public".to_string()), Vec::new())
+        });
+        // The table the foreign key references, so it does not dangle
+        let mut customers = schema.tables[0].clone();
+        customers.name = "customers".to_string();
+        customers.primary_key = Some(PrimaryKey {
+            name: None,
+            columns: vec!["id".to_string(), "region".to_string()],
+        });
+        customers.foreign_keys.clear();
+        customers.indexes.clear();
+        schema.tables.push(customers);
+        schema
+    }
+
+    fn objects(warnings: &[CollectionWarning], rule: LintRule) -> Vec<String> {
+        warnings
+            .iter()
+            .filter(|warning| warning.message.starts_with(rule.id()))
+            .flat_map(|warning| warning.objects.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_foreign_key_index() {
+        let unindexed = orders(
+            Some(&["id"]),
+            vec![index("by_customer", &["customer_id"], false)],
+        );
+        let warnings = lint_schema(&unindexed, &LintRule::ALL);
+        assert_eq!(
+            objects(&warnings, LintRule::MissingForeignKeyIndex),
+            ["public.orders (customer_id, region)"]
+        );
+        assert_eq!(warnings[0].category, WarningCategory::Lint);
+
+        // Column order within the leading columns does not matter
+        let indexed = orders(
+            Some(&["id"]),
+            vec![index(
+                "by_region",
+                &["region", "customer_id", "placed_at"],
+                false,
+            )],
+        );
+        assert!(
+            objects(
+                &lint_schema(&indexed, &LintRule::ALL),
+                LintRule::MissingForeignKeyIndex
+            )
+            .is_empty()
+        );
+
+        let by_primary_key = orders(Some(&["customer_id", "region", "id"]), Vec::new());
+        assert!(lint_schema(&by_primary_key, &LintRule::ALL).is_empty());
+    }
+
+    #[test]
+    fn test_missing_primary_key() {
+        let schema = orders(
+            None,
+            vec![index("by_customer", &["customer_id", "region"], false)],
+        );
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::MissingPrimaryKey
+            ),
+            ["public.orders"]
+        );
+    }
+
+    #[test]
+    fn test_redundant_index() {
+        let schema = orders(
+            Some(&["id"]),
+            vec![
+                index("by_customer", &["customer_id"], false),
+                index("by_customer_region", &["customer_id", "region"], false),
+                index("by_customer_region_copy", &["customer_id", "region"], false),
+                index("unique_customer", &["customer_id"], true),
+            ],
+        );
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::RedundantIndex
+            ),
+            [
+                "public.orders.by_customer",
+                "public.orders.by_customer_region_copy"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mixed_collation() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        schema.database_info.collation = Some("utf8mb4_0900_ai_ci".to_string());
+        for (name, collation) in [("status", "utf8mb4_0900_ai_ci"), ("code", "utf8mb4_bin")] {
+            schema.tables[0].columns.push(Column {
+                is_nullable: false,
+                collation: Some(collation.to_string()),
+                ..Column::new(
+                    name.to_string(),
+                    UnifiedDataType::String { max_length: None },
+                )
+            });
+        }
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::MixedCollation
+            ),
+            ["public.orders.code"]
+        );
+    }
+
+    #[test]
+    fn test_non_transactional_engine() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        schema.tables[0].options = Some(TableOptions {
+            engine: Some("MyISAM".to_string()),
+            ..TableOptions::default()
+        });
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::NonTransactionalEngine
+            ),
+            ["public.orders (MyISAM)"]
+        );
+
+        schema.tables[0].options = Some(TableOptions {
+            engine: Some("InnoDB".to_string()),
+            ..TableOptions::default()
+        });
+        assert!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::NonTransactionalEngine
+            )
+            .is_empty()
+        );
+    }
+
+    fn view(name: &str, definition: &str) -> View {
+        View {
+            name: name.to_string(),
+            schema: Some("public".to_string()),
+            definition: Some(definition.to_string()),
+            columns: Vec::new(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_dangling_foreign_key() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        assert!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::DanglingForeignKey
+            )
+            .is_empty()
+        );
+
+        schema.tables.retain(|table| table.name != "customers");
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::DanglingForeignKey
+            ),
+            ["public.orders (customer_id, region) -> public.customers"]
+        );
+    }
+
+    #[test]
+    fn test_dangling_view_reference() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        schema.views = vec![
+            view(
+                "recent_orders",
+                "WITH recent AS (SELECT * FROM public.orders) \
+                 SELECT r.id, EXTRACT(year FROM r.placed_at), s.n \
+                 FROM recent r JOIN archive.orders a ON a.id = r.id \
+                 LEFT JOIN generate_series(1, 3) s(n) ON true \
+                 JOIN legacy_customers lc ON lc.id IS DISTINCT FROM r.id \
+                 JOIN pg_catalog.pg_class c ON true",
+            ),
+            view(
+                "order_totals",
+                "SELECT * FROM `shop`.`orders` JOIN \"public\".\"refunds\" USING (id)",
+            ),
+        ];
+        schema.database_info.name = "shop".to_string();
+
+        // archive is not a collected schema, so archive.orders is not reported
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &[LintRule::DanglingViewReference]),
+                LintRule::DanglingViewReference
+            ),
+            [
+                "public.recent_orders -> legacy_customers",
+                "public.order_totals -> public.refunds"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_orphaned_trigger() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        schema.triggers = ["touch", "audit"]
+            .into_iter()
+            .map(|function| Trigger {
+                name: format!("orders_{}", function),
+                table_name: "orders".to_string(),
+                schema: Some("public".to_string()),
+                event: TriggerEvent::Update,
+                timing: TriggerTiming::Before,
+                definition: Some(format!(
+                    "CREATE TRIGGER orders_{0} BEFORE UPDATE ON public.orders \
+                     FOR EACH ROW EXECUTE FUNCTION public.{0}()",
+                    function
+                )),
+            })
+            .collect();
+
+        // Without collected routines the rule cannot tell what is missing
+        assert!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::OrphanedTrigger
+            )
+            .is_empty()
+        );
+
+        schema.functions.push(Procedure {
+            name: "touch".to_string(),
+            schema: Some("public".to_string()),
+            definition: None,
+            parameters: Vec::new(),
+            return_type: None,
+            language: Some("plpgsql".to_string()),
+            comment: None,
+        });
+        assert_eq!(
+            objects(
+                &lint_schema(&schema, &LintRule::ALL),
+                LintRule::OrphanedTrigger
+            ),
+            ["public.orders.orders_audit -> public.audit"]
+        );
+    }
+
+    #[test]
+    fn test_empty_schema() {
+        let mut schema = orders(Some(&["id"]), Vec::new());
+        schema.database_info.schemas = vec!["public".to_string(), "staging".to_string()];
+        let warnings = lint_schema(&schema, &LintRule::ALL);
+        assert_eq!(objects(&warnings, LintRule::EmptySchema), ["staging"]);
+        let warning = warnings
+            .iter()
+            .find(|w| w.message.starts_with("empty-schema"))
+            .expect("empty schema reported");
+        assert_eq!(warning.severity, WarningSeverity::Info);
+
+        // A schema holding only views looks empty when views were skipped
+        schema.collection_metadata.settings = Some(
+            crate::adapters::CollectionConfig::default()
+                .with_views(false)
+                .settings(None),
+        );
+        assert!(objects(&lint_schema(&schema, &LintRule::ALL), LintRule::EmptySchema).is_empty());
+    }
+
+    #[test]
+    fn test_only_selected_rules_run() {
+        let schema = orders(None, Vec::new());
+        let warnings = lint_schema(&schema, &[LintRule::MissingPrimaryKey]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("missing-primary-key: "));
+    }
+
+    #[test]
+    fn test_rule_ids_parse() {
+        for rule in LintRule::ALL {
+            assert_eq!(rule.id().parse::<LintRule>(), Ok(rule));
+        }
+        assert_eq!(
+            " Missing-FK-Index ".parse(),
+            Ok(LintRule::MissingForeignKeyIndex)
+        );
+        assert!("no-such-rule".parse::<LintRule>().is_err());
+    }
+}
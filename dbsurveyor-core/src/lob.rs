@@ -0,0 +1,401 @@
+//! Inventory of columns that store files and other large objects.
+//!
+//! Uploaded documents, images, and scans kept inside the database are the
+//! bulk of what an exfiltration would carry off and of what a storage audit
+//! needs to account for. [`large_object_inventory`] finds columns that
+//! appear to hold file content and estimates how much each table stores:
+//!
+//! - Unbounded or large binary columns (`bytea`, `BLOB`, `varbinary(max)`)
+//!   and engine large-object types (`lo`, `BFILE`)
+//! - Binary or unbounded text columns named like file content
+//!   (`attachment`, `invoice_pdf`, `profile_photo`); names that describe
+//!   a file instead (`file_name`, `image_url`) do not count
+//! - Content-type columns (`mime_type`, `content_type`) beside them, which
+//!   corroborate that the table stores files
+//!
+//! Volume is the average sampled value size times the table row count; only
+//! value sizes are read from samples. Without samples, the total table size
+//! is reported as an upper bound.
+
+use crate::models::{Column, DatabaseSchema, Table, TableSample, UnifiedDataType};
+use serde::{Deserialize, Serialize};
+
+/// Binary columns declared wider than this hold large objects (SQL Server
+/// allows `varbinary(8000)` before `varbinary(max)`).
+const MAX_INLINE_BINARY_LENGTH: u32 = 8000;
+
+/// Engine-specific large-object types.
+const LARGE_OBJECT_TYPES: &[&str] = &["lo", "bfile", "image"];
+
+/// Words naming file content.
+const FILE_WORDS: &[&str] = &[
+    "attachment",
+    "attachments",
+    "avatar",
+    "blob",
+    "document",
+    "file",
+    "image",
+    "img",
+    "pdf",
+    "photo",
+    "picture",
+    "scan",
+    "thumbnail",
+    "upload",
+];
+
+/// Words naming something about a file rather than its content.
+const FILE_DESCRIPTION_WORDS: &[&str] = &[
+    "at",
+    "count",
+    "date",
+    "ext",
+    "extension",
+    "hash",
+    "id",
+    "key",
+    "name",
+    "path",
+    "size",
+    "type",
+    "uri",
+    "url",
+];
+
+/// Names of columns recording the media type of stored files, compared
+/// without separators.
+const CONTENT_TYPE_NAMES: &[&str] = &["contenttype", "mimetype", "mediatype", "filetype"];
+
+/// Why a column is taken to hold file content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LobSignal {
+    /// Unbounded or large binary type
+    BinaryType,
+    /// Engine large-object type, e.g. PostgreSQL `lo`
+    LargeObjectType { type_name: String },
+    /// Column name suggests file content
+    FileName,
+}
+
+impl LobSignal {
+    /// Short human-readable description.
+    pub fn label(&self) -> String {
+        match self {
+            Self::BinaryType => "large binary type".to_string(),
+            Self::LargeObjectType { type_name } => format!("{} type", type_name),
+            Self::FileName => "file-like name".to_string(),
+        }
+    }
+}
+
+/// A column that appears to store file content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LobColumn {
+    pub column: String,
+    pub signals: Vec<LobSignal>,
+    /// Average sampled value size in bytes, counting NULLs as empty
+    pub average_bytes: Option<u64>,
+}
+
+/// Estimated volume of file content stored in a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VolumeEstimate {
+    /// Average sampled value sizes times the row count
+    Sampled { bytes: u64 },
+    /// Total table size, an upper bound when values were not sampled
+    TableSize { bytes: u64 },
+}
+
+impl VolumeEstimate {
+    pub fn bytes(&self) -> u64 {
+        match self {
+            Self::Sampled { bytes } | Self::TableSize { bytes } => *bytes,
+        }
+    }
+}
+
+/// A table with columns that appear to store file content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LobTable {
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<LobColumn>,
+    /// Columns recording the media type of the stored files
+    pub content_type_columns: Vec<String>,
+    pub row_count: Option<u64>,
+    pub volume: Option<VolumeEstimate>,
+}
+
+/// Finds tables storing file content, in table order.
+pub fn large_object_inventory(schema: &DatabaseSchema) -> Vec<LobTable> {
+    let samples = schema.samples.as_deref().unwrap_or_default();
+    let mut inventory = Vec::new();
+    for table in &schema.tables {
+        let sample = samples
+            .iter()
+            .find(|s| s.table_name == table.name && s.schema_name == table.schema);
+        let columns: Vec<LobColumn> = table
+            .columns
+            .iter()
+            .filter_map(|column| {
+                let signals = signals(column);
+                (!signals.is_empty()).then(|| LobColumn {
+                    column: column.name.clone(),
+                    signals,
+                    average_bytes: sample.and_then(|s| average_bytes(s, &column.name)),
+                })
+            })
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        let row_count = table
+            .row_count
+            .or_else(|| sample.and_then(|s| s.total_rows));
+        inventory.push(LobTable {
+            schema: table.schema.clone(),
+            table: table.name.clone(),
+            content_type_columns: table
+                .columns
+                .iter()
+                .filter(|column| is_content_type(&column.name))
+                .map(|column| column.name.clone())
+                .collect(),
+            volume: estimate_volume(table, &columns, row_count),
+            columns,
+            row_count,
+        });
+    }
+    inventory
+}
+
+fn signals(column: &Column) -> Vec<LobSignal> {
+    let mut signals = Vec::new();
+    let can_hold_file = match &column.data_type {
+        UnifiedDataType::Binary { max_length } => {
+            if max_length.is_none_or(|length| length > MAX_INLINE_BINARY_LENGTH) {
+                signals.push(LobSignal::BinaryType);
+            }
+            true
+        }
+        UnifiedDataType::Custom { type_name }
+            if LARGE_OBJECT_TYPES.contains(&type_name.to_lowercase().as_str()) =>
+        {
+            signals.push(LobSignal::LargeObjectType {
+                type_name: type_name.clone(),
+            });
+            true
+        }
+        // Base64-encoded files in unbounded text
+        UnifiedDataType::String { max_length: None } => true,
+        _ => false,
+    };
+    if can_hold_file && is_file_name(&column.name) {
+        signals.push(LobSignal::FileName);
+    }
+    signals
+}
+
+fn is_file_name(name: &str) -> bool {
+    let words = words(name);
+    words.iter().any(|word| FILE_WORDS.contains(&word.as_str()))
+        && !words
+            .iter()
+            .any(|word| FILE_DESCRIPTION_WORDS.contains(&word.as_str()))
+}
+
+fn is_content_type(name: &str) -> bool {
+    let compact: String = words(name).concat();
+    CONTENT_TYPE_NAMES
+        .iter()
+        .any(|suffix| compact.ends_with(suffix))
+}
+
+/// Lowercase words of an identifier, split at separators and camelCase.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            words.extend((!current.is_empty()).then(|| std::mem::take(&mut current)));
+            previous_lower = false;
+            continue;
+        }
+        if previous_lower && c.is_uppercase() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+        previous_lower = c.is_lowercase();
+    }
+    words.extend((!current.is_empty()).then_some(current));
+    words
+}
+
+/// Average size of a column's sampled values. Binary values are written
+/// as `{"base64": ..., "length": <bytes>}`.
+fn average_bytes(sample: &TableSample, column: &str) -> Option<u64> {
+    if sample.rows.is_empty() {
+        return None;
+    }
+    let total: u64 = sample
+        .rows
+        .iter()
+        .filter_map(|row| row.get(column))
+        .map(|value| match value {
+            serde_json::Value::String(text) => text.len() as u64,
+            serde_json::Value::Object(binary) => binary
+                .get("length")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or_default(),
+            _ => 0,
+        })
+        .sum();
+    Some(total / sample.rows.len() as u64)
+}
+
+fn estimate_volume(
+    table: &Table,
+    columns: &[LobColumn],
+    row_count: Option<u64>,
+) -> Option<VolumeEstimate> {
+    let sampled: Option<u64> = columns.iter().map(|column| column.average_bytes).sum();
+    match (sampled, row_count) {
+        (Some(average), Some(rows)) => Some(VolumeEstimate::Sampled {
+            bytes: average.saturating_mul(rows),
+        }),
+        _ => table
+            .size_bytes
+            .map(|bytes| VolumeEstimate::TableSize { bytes }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DatabaseInfo, SamplingStrategy};
+    use serde_json::json;
+
+    fn column(name: &str, data_type: UnifiedDataType) -> Column {
+        Column::new(name.to_string(), data_type)
+    }
+
+    fn table(name: &str, columns: Vec<Column>) -> Table {
+        Table {
+            row_count: Some(1_000),
+            size_bytes: Some(900_000_000),
+            ..Table::new(name.to_string(), Some("public".to_string()), columns)
+        }
+    }
+
+    fn text() -> UnifiedDataType {
+        UnifiedDataType::String { max_length: None }
+    }
+
+    fn binary(max_length: Option<u32>) -> UnifiedDataType {
+        UnifiedDataType::Binary { max_length }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("docs".to_string()));
+        schema.tables = vec![
+            table(
+                "invoices",
+                vec![
+                    column(
+                        "id",
+                        UnifiedDataType::Integer {
+                            bits: 64,
+                            signed: true,
+                        },
+                    ),
+                    column("invoice_pdf", binary(None)),
+                    column("pdf_file_name", text()),
+                    column(
+                        "mime_type",
+                        UnifiedDataType::String {
+                            max_length: Some(100),
+                        },
+                    ),
+                ],
+            ),
+            table(
+                "users",
+                vec![
+                    column("password_hash", binary(Some(32))),
+                    column("avatarImage", text()),
+                    column("avatar_url", text()),
+                    column("bio", text()),
+                ],
+            ),
+            table("events", vec![column("payload", binary(Some(16_777_215)))]),
+        ];
+        schema.samples = Some(vec![TableSample {
+            table_name: "invoices".to_string(),
+            schema_name: Some("public".to_string()),
+            rows: vec![
+                json!({"id": 1, "invoice_pdf": {"base64": "...", "length": 300_000}}),
+                json!({"id": 2, "invoice_pdf": {"base64": "...", "length": 100_000}}),
+                json!({"id": 3, "invoice_pdf": null}),
+                json!({"id": 4, "invoice_pdf": {"base64": "...", "length": 200_000}}),
+            ],
+            sample_size: 4,
+            total_rows: Some(1_000),
+            sampling_strategy: SamplingStrategy::Random { limit: 4 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }]);
+        schema
+    }
+
+    #[test]
+    fn test_large_object_inventory() {
+        let inventory = large_object_inventory(&fixture());
+        let tables: Vec<&str> = inventory.iter().map(|t| t.table.as_str()).collect();
+        assert_eq!(tables, ["invoices", "users", "events"]);
+
+        let invoices = &inventory[0];
+        assert_eq!(invoices.columns.len(), 1);
+        assert_eq!(
+            invoices.columns[0].signals,
+            [LobSignal::BinaryType, LobSignal::FileName]
+        );
+        assert_eq!(invoices.columns[0].average_bytes, Some(150_000));
+        assert_eq!(invoices.content_type_columns, ["mime_type"]);
+        assert_eq!(
+            invoices.volume,
+            Some(VolumeEstimate::Sampled { bytes: 150_000_000 })
+        );
+
+        // Short hashes and text without a file-like name are not files
+        let users = &inventory[1];
+        let columns: Vec<&str> = users.columns.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(columns, ["avatarImage"]);
+        assert_eq!(users.columns[0].signals, [LobSignal::FileName]);
+        assert_eq!(
+            users.volume,
+            Some(VolumeEstimate::TableSize { bytes: 900_000_000 })
+        );
+
+        assert_eq!(inventory[2].columns[0].signals, [LobSignal::BinaryType]);
+    }
+
+    #[test]
+    fn test_file_names() {
+        assert!(is_file_name("attachment"));
+        assert!(is_file_name("scanned_document"));
+        assert!(is_file_name("ProfilePhoto"));
+        assert!(!is_file_name("file_name"));
+        assert!(!is_file_name("imageUrl"));
+        assert!(!is_file_name("description"));
+        assert!(is_content_type("ContentType"));
+        assert!(is_content_type("attachment_mime_type"));
+        assert!(!is_content_type("type"));
+    }
+}
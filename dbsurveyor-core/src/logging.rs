@@ -1,8 +1,43 @@
 //! Shared logging utilities for DBSurveyor binaries.
 //!
 //! Provides consistent logging configuration across collector and postprocessor.
+//!
+//! The subscriber also counts the SQL statements executed through sqlx, which
+//! reports every statement as an event on the `sqlx::query` target. The count
+//! is kept in-process for local run metrics only.
 
 use crate::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing_subscriber::{
+    Layer, filter::LevelFilter, filter::Targets, fmt::writer::BoxMakeWriter, layer::SubscriberExt,
+    util::SubscriberInitExt,
+};
+
+/// Target on which sqlx reports each executed statement.
+const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+static STATEMENTS_EXECUTED: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of SQL statements executed since logging was initialized.
+///
+/// Only statements run through sqlx are counted (MongoDB commands are not),
+/// and the count is zero if [`init_logging`] was never called.
+pub fn statements_executed() -> u64 {
+    STATEMENTS_EXECUTED.load(Ordering::Relaxed)
+}
+
+/// Layer that counts sqlx statement events without printing them.
+struct StatementCounter;
+
+impl<S: tracing::Subscriber> Layer<S> for StatementCounter {
+    fn on_event(
+        &self,
+        _event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        STATEMENTS_EXECUTED.fetch_add(1, Ordering::Relaxed);
+    }
+}
 
 /// Returns true if ANSI color output should be disabled.
 ///
@@ -33,6 +68,21 @@ pub fn should_disable_color() -> bool {
 /// init_logging(1, false).expect("Failed to initialize logging");
 /// ```
 pub fn init_logging(verbose: u8, quiet: bool) -> Result<()> {
+    init(verbose, quiet, BoxMakeWriter::new(std::io::stdout))
+}
+
+/// Initializes logging like [`init_logging`], but writes to stderr.
+///
+/// Used when stdout carries data, e.g. a survey written with `--output -`
+/// for a shell pipeline.
+///
+/// # Errors
+/// Returns an error if a global subscriber is already installed.
+pub fn init_logging_to_stderr(verbose: u8, quiet: bool) -> Result<()> {
+    init(verbose, quiet, BoxMakeWriter::new(std::io::stderr))
+}
+
+fn init(verbose: u8, quiet: bool, writer: BoxMakeWriter) -> Result<()> {
     let level = match (quiet, verbose) {
         (true, _) => tracing::Level::ERROR,
         (false, 0) => tracing::Level::INFO,
@@ -42,13 +92,22 @@ pub fn init_logging(verbose: u8, quiet: bool) -> Result<()> {
 
     let use_ansi = !should_disable_color();
 
-    tracing_subscriber::fmt()
-        .with_max_level(level)
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_target(false)
         .with_thread_ids(false)
         .with_file(false)
         .with_line_number(false)
         .with_ansi(use_ansi)
+        .with_writer(writer)
+        .with_filter(LevelFilter::from_level(level));
+
+    // Filtered separately so statements are counted at every verbosity
+    let counter_layer = StatementCounter
+        .with_filter(Targets::new().with_target(SQLX_QUERY_TARGET, LevelFilter::TRACE));
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(counter_layer)
         .try_init()
         .map_err(|e| {
             crate::error::DbSurveyorError::configuration(format!(
@@ -5,6 +5,7 @@
 //! designed to be serializable and maintain security guarantees.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Current schema format version.
 pub const FORMAT_VERSION: &str = "1.0";
@@ -40,6 +41,12 @@ pub enum UnifiedDataType {
     Integer { bits: u8, signed: bool },
     /// Floating point types
     Float { precision: Option<u8> },
+    /// Exact numeric types (NUMERIC, DECIMAL, MONEY) with declared total
+    /// digits and digits after the decimal point, when known
+    Decimal {
+        precision: Option<u32>,
+        scale: Option<u32>,
+    },
     /// Boolean type
     Boolean,
     /// Date and time types
@@ -48,12 +55,18 @@ pub enum UnifiedDataType {
     Date,
     /// Time only
     Time { with_timezone: bool },
+    /// Duration between two points in time
+    Interval,
     /// Binary data
     Binary { max_length: Option<u32> },
+    /// Bit strings with a declared length in bits
+    Bit { length: Option<u32> },
     /// JSON/JSONB data
     Json,
     /// UUID type
     Uuid,
+    /// IP addresses, networks, and MAC addresses
+    NetworkAddress,
     /// Array types
     Array { element_type: Box<UnifiedDataType> },
     /// Custom/database-specific types
@@ -61,7 +74,7 @@ pub enum UnifiedDataType {
 }
 
 /// Database column information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Column {
     pub name: String,
     pub data_type: UnifiedDataType,
@@ -72,9 +85,146 @@ pub struct Column {
     pub is_auto_increment: bool,
     /// SQL expression for the column default, as reported by the database catalog
     pub default_value: Option<String>,
+    /// `default_value` classified by what it produces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized_default: Option<DefaultValue>,
     pub comment: Option<String>,
     /// 1-based position of the column within its table
     pub ordinal_position: u32,
+    /// Expression and storage kind for computed (generated) columns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<GeneratedColumn>,
+    /// Column type exactly as declared in the DDL, for databases where the
+    /// declaration carries meaning beyond `data_type` (e.g. SQLite type affinity)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_type: Option<String>,
+    /// Collation of a text column, when reported by the database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+    /// Character set of a text column, for databases that set it per column (e.g. MySQL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub character_set: Option<String>,
+    /// Storage strategy and compression of a variable-length column (PostgreSQL TOAST)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<ColumnStorage>,
+    /// Identity or serial generation and the backing sequence, for
+    /// auto-increment columns fed by a sequence (PostgreSQL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<ColumnIdentity>,
+    /// Server-side masking applied when masked roles read the column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masking: Option<ColumnMasking>,
+}
+
+impl Column {
+    /// Creates a nullable, non-key column at position 1 with no default,
+    /// comment, or engine-specific details
+    pub fn new(name: String, data_type: UnifiedDataType) -> Self {
+        Self {
+            name,
+            data_type,
+            is_nullable: true,
+            is_primary_key: false,
+            is_auto_increment: false,
+            default_value: None,
+            normalized_default: None,
+            comment: None,
+            ordinal_position: 1,
+            generated: None,
+            declared_type: None,
+            collation: None,
+            character_set: None,
+            storage: None,
+            identity: None,
+            masking: None,
+        }
+    }
+}
+
+/// A masking rule that replaces a column's values for some readers
+/// (PostgreSQL Anonymizer, SQL Server Dynamic Data Masking)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnMasking {
+    /// Masking feature: "anon" (PostgreSQL Anonymizer) or "ddm" (SQL Server)
+    pub provider: String,
+    /// Rule as declared, e.g. `MASKED WITH FUNCTION anon.fake_email()`
+    pub rule: String,
+}
+
+/// How an auto-increment column draws values from its sequence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnIdentity {
+    pub generation: IdentityGeneration,
+    /// Schema-qualified sequence owned by the column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<String>,
+}
+
+/// Kind of sequence-backed column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdentityGeneration {
+    /// `GENERATED ALWAYS AS IDENTITY`: explicit values are rejected unless overridden
+    Always,
+    /// `GENERATED BY DEFAULT AS IDENTITY`: explicit values are accepted
+    ByDefault,
+    /// Legacy `serial`: a `nextval()` default on a sequence owned by the column
+    Serial,
+}
+
+/// How the values of a variable-length column are stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnStorage {
+    pub strategy: StorageStrategy,
+    /// Compression method for compressed values ("pglz", "lz4"); `None` uses
+    /// the server default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+}
+
+/// PostgreSQL column storage strategy (`ALTER TABLE ... SET STORAGE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageStrategy {
+    /// Stored inline and uncompressed
+    Plain,
+    /// Compressed inline; moved out of line only as a last resort
+    Main,
+    /// Moved out of line when large, but never compressed
+    External,
+    /// Compressed, then moved out of line if still large
+    Extended,
+}
+
+/// Definition of a column whose value is computed from an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedColumn {
+    /// Generation expression as reported by the database catalog
+    pub expression: String,
+    pub kind: GenerationKind,
+}
+
+/// How a generated column's value is materialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GenerationKind {
+    /// Computed when read; not stored on disk
+    Virtual,
+    /// Computed on write and stored with the row
+    Stored,
+}
+
+/// Column default normalized across engines
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefaultValue {
+    /// Constant written as a standard SQL literal: a single-quoted string,
+    /// number, `TRUE`, `FALSE`, or `NULL`, with engine-specific casts removed
+    Literal { value: String },
+    /// Any other expression, as reported by the database catalog
+    Expression { expression: String },
+    /// Next value of a sequence, e.g. PostgreSQL `nextval('orders_id_seq')`
+    SequenceNextval { sequence: String },
+    /// Current date and time, e.g. `CURRENT_TIMESTAMP`, `now()`, `GETDATE()`
+    CurrentTimestamp,
+    /// Random UUID generated by the database, e.g. `gen_random_uuid()`
+    UuidGenerate,
 }
 
 /// Database table information
@@ -91,6 +241,211 @@ pub struct Table {
     pub comment: Option<String>,
     /// Estimated row count from database statistics; may be stale or unavailable
     pub row_count: Option<u64>,
+    /// Estimated on-disk size including indexes; not all engines provide this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Declared validation rules (e.g. a MongoDB `$jsonSchema` validator)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<SchemaValidator>,
+    /// Partitioning scheme, for tables split into partitions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partitioning: Option<PartitionInfo>,
+    /// Module details, for virtual tables (e.g. SQLite FTS5 or R*Tree tables)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virtual_table: Option<VirtualTable>,
+    /// Table-level storage options declared after the column list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<TableOptions>,
+    /// Position in a table inheritance hierarchy, for tables with parents or children
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inheritance: Option<TableInheritance>,
+}
+
+impl Table {
+    /// Creates a table with no keys, indexes, constraints, statistics, or
+    /// engine-specific details
+    pub fn new(name: String, schema: Option<String>, columns: Vec<Column>) -> Self {
+        Self {
+            name,
+            schema,
+            columns,
+            primary_key: None,
+            foreign_keys: Vec::new(),
+            indexes: Vec::new(),
+            constraints: Vec::new(),
+            comment: None,
+            row_count: None,
+            size_bytes: None,
+            validator: None,
+            partitioning: None,
+            virtual_table: None,
+            options: None,
+            inheritance: None,
+        }
+    }
+}
+
+/// Parent and child tables of a table in an inheritance hierarchy
+/// (PostgreSQL `INHERITS` or declarative partitioning)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableInheritance {
+    /// Tables this table inherits from, in declaration order; a partition has exactly one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<RelatedTable>,
+    /// Tables that inherit from this table, including its partitions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<RelatedTable>,
+    /// True when this table is a declarative partition of its parent
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_partition: bool,
+    /// Columns inherited from the parents rather than declared on this table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inherited_columns: Vec<String>,
+}
+
+/// Schema-qualified name of another table
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RelatedTable {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+/// Storage options declared on a table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableOptions {
+    /// SQLite `WITHOUT ROWID`: rows are stored clustered by the primary key
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub without_rowid: bool,
+    /// SQLite `STRICT`: column types are enforced rather than treated as affinities
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strict: bool,
+    /// MySQL storage engine (e.g. "InnoDB", "MyISAM")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    /// MySQL row format (e.g. "Dynamic", "Compressed")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_format: Option<String>,
+    /// Next value of the table's auto-increment counter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_increment: Option<u64>,
+    /// Shared tablespace holding the table; `None` for a file-per-table tablespace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tablespace: Option<String>,
+    /// Size of the table's TOAST relation and its index (PostgreSQL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toast_bytes: Option<u64>,
+    /// MongoDB capped collection: a fixed-size collection that discards the
+    /// oldest documents once full
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capped: Option<CappedCollection>,
+    /// MongoDB time-series collection: documents are bucketed by time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_series: Option<TimeSeriesCollection>,
+    /// System versioning: superseded row versions are kept and can be
+    /// queried as of a past time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_versioning: Option<SystemVersioning>,
+}
+
+/// History kept for a system-versioned table (MariaDB `WITH SYSTEM
+/// VERSIONING`, SQL Server temporal tables)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemVersioning {
+    /// Column recording when each row version became current; `None` when
+    /// the engine keeps it hidden
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_start: Option<String>,
+    /// Column recording when each row version was superseded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_end: Option<String>,
+    /// Separate table holding superseded versions (SQL Server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_table: Option<String>,
+    /// How long superseded versions are kept (e.g. "6 MONTHS"); `None`
+    /// when they are kept until deleted explicitly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<String>,
+}
+
+/// Size limits of a capped collection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CappedCollection {
+    /// Maximum size in bytes
+    pub max_bytes: Option<u64>,
+    /// Maximum number of documents, when limited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_documents: Option<u64>,
+}
+
+/// Bucketing options of a time-series collection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSeriesCollection {
+    /// Field holding each measurement's timestamp
+    pub time_field: String,
+    /// Field identifying the series a measurement belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_field: Option<String>,
+    /// Expected interval between measurements ("seconds", "minutes", "hours")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<String>,
+    /// Age after which measurements are removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expire_after_seconds: Option<u64>,
+}
+
+/// A table implemented by a module rather than stored as ordinary rows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualTable {
+    /// Module implementing the table (e.g. "fts5", "rtree")
+    pub module: String,
+    /// Module arguments as declared in `USING module(...)`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<String>,
+    /// Regular tables the module maintains to store the virtual table's content
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow_tables: Vec<String>,
+}
+
+/// Table partitioning scheme and its partitions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartitionInfo {
+    /// Partitioning method (e.g. "RANGE", "LIST", "HASH", "KEY")
+    pub method: String,
+    /// Partitioning expression or column list
+    pub expression: Option<String>,
+    pub subpartition_method: Option<String>,
+    pub subpartition_expression: Option<String>,
+    pub partitions: Vec<Partition>,
+}
+
+/// A single partition (or subpartition) of a partitioned table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Partition {
+    pub name: String,
+    pub subpartition_name: Option<String>,
+    /// 1-based position of the partition within the table
+    pub ordinal_position: u32,
+    /// Partition bound (e.g. the `VALUES LESS THAN` value for RANGE partitions)
+    pub description: Option<String>,
+    /// Estimated row count from database statistics; may be stale or unavailable
+    pub row_count: Option<u64>,
+}
+
+/// Declared schema validation rules attached to a table or collection.
+///
+/// For schemaless engines the validator is the authoritative declared schema;
+/// it is stored alongside the structure inferred from sampled documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaValidator {
+    /// Validator document as declared in the database, rendered as JSON
+    pub definition: serde_json::Value,
+    /// How strictly the rules are applied (e.g. "strict", "moderate", "off")
+    pub validation_level: Option<String>,
+    /// What happens to writes that fail validation (e.g. "error", "warn")
+    pub validation_action: Option<String>,
+    /// Differences between the declared rules and the structure observed in sampled data
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub discrepancies: Vec<String>,
 }
 
 /// Primary key constraint
@@ -142,6 +497,31 @@ pub struct Index {
     pub is_primary: bool,
     /// Engine-specific index type (e.g. "btree", "hash", "gin")
     pub index_type: Option<String>,
+    /// Options changing which rows are indexed or how the index behaves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<IndexOptions>,
+}
+
+/// Declared index options beyond the indexed key
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexOptions {
+    /// Entries expire this many seconds after the indexed date (MongoDB TTL index)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expire_after_seconds: Option<u64>,
+    /// Predicate limiting the indexed rows, as declared (a MongoDB
+    /// `partialFilterExpression` is rendered as JSON)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_filter: Option<String>,
+    /// Rows without the indexed field are left out of the index
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sparse: bool,
+    /// The index is maintained but not used by the query planner
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Collation used to compare indexed strings, as declared (a MongoDB
+    /// collation document is rendered as JSON)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
 }
 
 /// Index column with ordering
@@ -173,7 +553,7 @@ pub enum ConstraintType {
 }
 
 /// Database view information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct View {
     pub name: String,
     pub schema: Option<String>,
@@ -183,7 +563,7 @@ pub struct View {
 }
 
 /// Database procedure/function information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Procedure {
     pub name: String,
     pub schema: Option<String>,
@@ -195,7 +575,7 @@ pub struct Procedure {
 }
 
 /// Procedure parameter information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
     pub data_type: UnifiedDataType,
@@ -212,7 +592,7 @@ pub enum ParameterDirection {
 }
 
 /// Database trigger information
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trigger {
     pub name: String,
     pub table_name: String,
@@ -263,25 +643,561 @@ pub struct CollectionMetadata {
     /// Wall-clock duration of the collection in milliseconds
     pub collection_duration_ms: u64,
     pub collector_version: String,
-    /// Non-fatal issues encountered during collection (e.g. permission errors on specific tables)
-    pub warnings: Vec<String>,
+    /// Non-fatal issues encountered during collection (e.g. permission errors on specific tables).
+    /// Repeated warnings are merged when serialized; see [`deduplicate_warnings`].
+    #[serde(serialize_with = "serialize_deduplicated_warnings")]
+    pub warnings: Vec<CollectionWarning>,
+    /// Individual objects that could not be fully collected; collection continued without them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub object_failures: Vec<ObjectFailure>,
+    /// Object classes collected through a less-privileged fallback, with
+    /// the detail they lack
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<DegradedCollection>,
+    /// Read-only enforcement verified before collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<ReadOnlyVerification>,
+    /// Integrity check of the source database file, run before collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegrityCheck>,
+    /// SQLite `-wal`, `-shm`, and `-journal` files found beside the database
+    /// file before it was opened
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auxiliary_files: Vec<AuxiliaryFile>,
+    /// Settings the collector ran with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<CollectionSettings>,
+}
+
+/// How strongly writes were prevented while collecting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadOnlyLevel {
+    /// The server rejects writes from every client (a standby or replica,
+    /// or MySQL `super_read_only`)
+    Server,
+    /// The collector's session rejects writes
+    Session,
+    /// The adapter could not confirm that writes are rejected
+    Unverified,
+    /// The session can write
+    NotEnforced,
+}
+
+impl ReadOnlyLevel {
+    /// Whether writes are known to be rejected.
+    pub fn is_guaranteed(self) -> bool {
+        matches!(self, Self::Server | Self::Session)
+    }
+}
+
+impl std::fmt::Display for ReadOnlyLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Server => "server",
+            Self::Session => "session",
+            Self::Unverified => "unverified",
+            Self::NotEnforced => "not enforced",
+        })
+    }
+}
+
+/// Outcome of the read-only check run before collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadOnlyVerification {
+    pub level: ReadOnlyLevel,
+    /// Settings observed, e.g. `transaction_read_only = on`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<String>,
+}
+
+impl ReadOnlyVerification {
+    /// A verification at `level` supported by `evidence`.
+    pub fn new(level: ReadOnlyLevel, evidence: Vec<String>) -> Self {
+        Self { level, evidence }
+    }
+
+    /// An unverified result explaining why the check could not run.
+    pub fn unverified(reason: impl Into<String>) -> Self {
+        Self::new(ReadOnlyLevel::Unverified, vec![reason.into()])
+    }
+}
+
+/// Thoroughness of a database file integrity check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheckMode {
+    /// SQLite `PRAGMA quick_check`: page structure only, skipping index
+    /// content checks
+    Quick,
+    /// SQLite `PRAGMA integrity_check`: also verifies that indexes match
+    /// their tables
+    Full,
+}
+
+/// Result of a database file integrity check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    /// No problems found
+    Intact,
+    /// The check reported problems
+    Corrupt,
+    /// The check did not finish within its timeout
+    TimedOut,
+    /// The check could not run
+    Failed,
+}
+
+impl std::fmt::Display for IntegrityStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Intact => "intact",
+            Self::Corrupt => "corrupt",
+            Self::TimedOut => "timed out",
+            Self::Failed => "failed",
+        })
+    }
+}
+
+/// Outcome of the integrity check run before collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityCheck {
+    pub mode: IntegrityCheckMode,
+    pub status: IntegrityStatus,
+    /// Problems reported by the check, or why it did not complete
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+    /// Wall-clock duration of the check in milliseconds
+    pub duration_ms: u64,
+}
+
+impl IntegrityCheck {
+    /// The check as a collection warning, unless the database was intact.
+    pub fn to_warning(&self) -> Option<CollectionWarning> {
+        let message = match self.status {
+            IntegrityStatus::Intact => return None,
+            IntegrityStatus::Corrupt => format!(
+                "Integrity check found {} problem{}; collected metadata may be incomplete",
+                self.problems.len(),
+                if self.problems.len() == 1 { "" } else { "s" }
+            ),
+            IntegrityStatus::TimedOut | IntegrityStatus::Failed => format!(
+                "Integrity check {}; source integrity is unknown",
+                self.status
+            ),
+        };
+        Some(CollectionWarning::new(WarningCategory::Integrity, message))
+    }
+}
+
+/// Kind of file SQLite keeps beside a database file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuxiliaryFileKind {
+    /// Write-ahead log (`-wal`); may hold committed changes not yet
+    /// checkpointed into the database file
+    Wal,
+    /// WAL shared-memory index (`-shm`)
+    Shm,
+    /// Rollback journal (`-journal`)
+    Journal,
+}
+
+impl AuxiliaryFileKind {
+    /// The suffix SQLite appends to the database file name.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Wal => "-wal",
+            Self::Shm => "-shm",
+            Self::Journal => "-journal",
+        }
+    }
+}
+
+/// A SQLite auxiliary file found beside a database file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuxiliaryFile {
+    pub kind: AuxiliaryFileKind,
+    /// File name, without its directory
+    pub file_name: String,
+    pub size_bytes: u64,
+    /// A rollback journal holding an interrupted transaction, which SQLite
+    /// rolls back into the database file when it is opened for writing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hot: bool,
+}
+
+/// Effective settings of the run that produced a survey.
+///
+/// Built from the collector's `CollectionConfig` and `SamplingConfig`.
+/// Connection details (host, port, database name, and user) are left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectionSettings {
+    /// Whether system databases were included in multi-database collection
+    pub include_system_databases: bool,
+    /// Database names or glob patterns excluded from multi-database collection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_databases: Vec<String>,
+    /// Optional object types that were not collected (`views`, `triggers`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_objects: Vec<String>,
+    /// Whether read-only mode was enforced on the connection
+    pub read_only: bool,
+    /// Whether SQLite files were opened as immutable (`immutable=1`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub immutable: bool,
+    /// Whether timeouts and read-only mode were applied per transaction for a pooling proxy
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pooler_mode: bool,
+    /// Maximum number of concurrent database queries
+    pub max_concurrent_queries: u32,
+    /// Timeout for each metadata query, in seconds
+    pub query_timeout_secs: u64,
+    /// Time allowed per table before it was skipped, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_timeout_secs: Option<u64>,
+    /// Engine-specific session limits from the configuration file
+    #[serde(
+        default,
+        skip_serializing_if = "crate::adapters::SessionLimits::is_empty"
+    )]
+    pub session_limits: crate::adapters::SessionLimits,
+    /// Sampling settings; `None` when no rows were sampled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingSettings>,
+}
+
+/// Effective data sampling settings of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamplingSettings {
+    /// Number of rows sampled per table
+    pub sample_size: u32,
+    /// Delay between sampling queries, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttle_ms: Option<u64>,
+    /// Timeout for each sampling query, in seconds
+    pub query_timeout_secs: u64,
+    /// Tables estimated to have more rows than this were not sampled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_table_rows: Option<u64>,
+    /// Tables estimated to be larger than this many bytes were not sampled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_table_bytes: Option<u64>,
+    /// Column name patterns whose sampled values were flagged, masked, or dropped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_patterns: Vec<crate::adapters::SensitivePattern>,
+}
+
+/// A failure to collect part of a single database object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectFailure {
+    pub schema: Option<String>,
+    pub name: String,
+    /// The metadata being collected when the failure occurred (e.g. "columns", "indexes")
+    pub stage: String,
+    pub error: String,
+}
+
+impl ObjectFailure {
+    /// Creates a failure record for the named object
+    pub fn new(
+        schema: Option<&str>,
+        name: impl Into<String>,
+        stage: impl Into<String>,
+        error: impl std::fmt::Display,
+    ) -> Self {
+        Self {
+            schema: schema.map(str::to_string),
+            name: name.into(),
+            stage: stage.into(),
+            error: error.to_string(),
+        }
+    }
+
+    /// Returns the object name qualified with its schema, if any
+    pub fn qualified_name(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!("{}.{}", schema, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// An object class collected through a less-privileged fallback query after
+/// its usual query was denied access.
+///
+/// The listed objects are present in the survey but lack the `missing`
+/// attributes, so a consumer can tell an empty comment or parameter list
+/// from one the collector could not read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegradedCollection {
+    /// Object class, e.g. "views" or "triggers"
+    pub object_class: String,
+    /// Where the objects were read from instead, e.g. "information_schema"
+    pub source: String,
+    /// The error that denied the usual query
+    pub reason: String,
+    /// Attributes or objects the fallback cannot provide
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+    /// Qualified names of the objects collected with reduced detail
+    #[serde(default)]
+    pub objects: Vec<String>,
+}
+
+impl DegradedCollection {
+    /// Creates a record for `object_class` read from `source` instead.
+    pub fn new(object_class: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            object_class: object_class.into(),
+            source: source.into(),
+            reason: String::new(),
+            missing: Vec::new(),
+            objects: Vec::new(),
+        }
+    }
+
+    /// Sets the attributes the fallback cannot provide
+    #[must_use]
+    pub fn with_missing(mut self, missing: &[&str]) -> Self {
+        self.missing = missing.iter().map(|m| (*m).to_string()).collect();
+        self
+    }
+}
+
+/// Maximum number of example objects kept on a deduplicated warning
+pub const MAX_WARNING_OBJECTS: usize = 10;
+
+/// How serious a collection warning is
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+/// The part of collection that produced a warning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCategory {
+    /// Uncategorized; used for warnings read from older output files
+    #[default]
+    General,
+    /// A whole class of objects (views, functions, ...) could not be collected
+    Collection,
+    /// A single object could not be fully collected
+    ObjectFailure,
+    Sampling,
+    Quality,
+    /// A schema lint rule finding (see [`crate::lint`])
+    Lint,
+    /// A credential found in metadata and redacted (see [`crate::secrets`])
+    Secret,
+    /// The source database file failed or could not complete an integrity check
+    Integrity,
+    /// The server version is outside the adapter's tested range (see [`crate::versions`])
+    Compatibility,
+}
+
+/// A non-fatal issue encountered during collection.
+///
+/// Warnings with the same severity, category, and message are merged by
+/// [`deduplicate_warnings`], keeping a count and a capped list of the objects
+/// they applied to. Older output files stored warnings as plain strings; those
+/// deserialize as uncategorized warnings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "WarningRepr")]
+pub struct CollectionWarning {
+    pub severity: WarningSeverity,
+    pub category: WarningCategory,
+    pub message: String,
+    /// Objects the warning applies to (at most `MAX_WARNING_OBJECTS` after deduplication)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<String>,
+    /// Number of occurrences merged into this warning
+    #[serde(skip_serializing_if = "is_single_occurrence")]
+    pub count: u64,
+}
+
+impl CollectionWarning {
+    /// Creates a warning-level entry in the given category
+    pub fn new(category: WarningCategory, message: impl Into<String>) -> Self {
+        Self {
+            severity: WarningSeverity::Warning,
+            category,
+            message: message.into(),
+            objects: Vec::new(),
+            count: 1,
+        }
+    }
+
+    /// Sets the severity
+    #[must_use]
+    pub fn with_severity(mut self, severity: WarningSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Records the object (e.g. `schema.table`) the warning applies to
+    #[must_use]
+    pub fn with_object(mut self, object: impl Into<String>) -> Self {
+        self.objects.push(object.into());
+        self
+    }
+}
+
+impl std::fmt::Display for CollectionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.objects.is_empty() {
+            write!(f, " ({})", self.objects.join(", "))?;
+        }
+        if self.count > 1 {
+            write!(f, " [x{}]", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for CollectionWarning {
+    fn from(message: String) -> Self {
+        Self::new(WarningCategory::General, message)
+    }
+}
+
+impl From<&str> for CollectionWarning {
+    fn from(message: &str) -> Self {
+        Self::new(WarningCategory::General, message)
+    }
+}
+
+/// Accepts both the structured warning form and legacy plain strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WarningRepr {
+    Legacy(String),
+    Structured {
+        #[serde(default)]
+        severity: WarningSeverity,
+        #[serde(default)]
+        category: WarningCategory,
+        message: String,
+        #[serde(default)]
+        objects: Vec<String>,
+        #[serde(default = "single_occurrence")]
+        count: u64,
+    },
+}
+
+impl From<WarningRepr> for CollectionWarning {
+    fn from(repr: WarningRepr) -> Self {
+        match repr {
+            WarningRepr::Legacy(message) => message.into(),
+            WarningRepr::Structured {
+                severity,
+                category,
+                message,
+                objects,
+                count,
+            } => Self {
+                severity,
+                category,
+                message,
+                objects,
+                count,
+            },
+        }
+    }
+}
+
+const fn single_occurrence() -> u64 {
+    1
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+const fn is_single_occurrence(count: &u64) -> bool {
+    *count == 1
+}
+
+/// Merges warnings that share a severity, category, and message.
+///
+/// Counts are summed and the objects of merged warnings are combined, keeping
+/// the first `MAX_WARNING_OBJECTS` distinct names. Warnings keep the order in
+/// which they were first seen.
+pub fn deduplicate_warnings(warnings: &[CollectionWarning]) -> Vec<CollectionWarning> {
+    let mut merged: Vec<CollectionWarning> = Vec::new();
+    let mut positions = std::collections::HashMap::new();
+
+    for warning in warnings {
+        let key = (warning.severity, warning.category, warning.message.as_str());
+        match positions.get(&key) {
+            Some(&index) => {
+                let existing: &mut CollectionWarning = &mut merged[index];
+                existing.count = existing.count.saturating_add(warning.count);
+                for object in &warning.objects {
+                    if existing.objects.len() >= MAX_WARNING_OBJECTS {
+                        break;
+                    }
+                    if !existing.objects.contains(object) {
+                        existing.objects.push(object.clone());
+                    }
+                }
+            }
+            None => {
+                positions.insert(key, merged.len());
+                let mut warning = warning.clone();
+                warning.objects.truncate(MAX_WARNING_OBJECTS);
+                merged.push(warning);
+            }
+        }
+    }
+
+    merged
+}
+
+fn serialize_deduplicated_warnings<S: serde::Serializer>(
+    warnings: &[CollectionWarning],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    deduplicate_warnings(warnings).serialize(serializer)
 }
 
 /// Database information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub name: String,
+    /// Engine hosting the database; absent in surveys from older collectors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<DatabaseType>,
     /// Database engine version string (e.g. "16.2")
     pub version: Option<String>,
     /// Estimated on-disk size reported by the database engine; not all engines provide this
     pub size_bytes: Option<u64>,
     pub encoding: Option<String>,
     pub collation: Option<String>,
+    /// The server's configured time zone (e.g. `Europe/Berlin`). Sampled
+    /// date-times without a zone, such as MySQL `DATETIME`, are written as
+    /// stored and are read in this zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
     pub owner: Option<String>,
     /// True for built-in databases (e.g. postgres, template0, information_schema)
     #[serde(default)]
     pub is_system_database: bool,
     pub access_level: AccessLevel,
+    /// What the credential was found able to read; `access_level` is derived
+    /// from it when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_probe: Option<AccessProbe>,
+    /// Schemas enumerated during collection, including any that hold no
+    /// collected objects; empty for engines without schemas
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schemas: Vec<String>,
     pub collection_status: CollectionStatus,
 }
 
@@ -296,6 +1212,58 @@ pub enum AccessLevel {
     None,
 }
 
+/// Privileges of the collecting credential, probed in one database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessProbe {
+    /// Administrative rights that bypass object privileges (PostgreSQL
+    /// superuser, MySQL `SUPER`, MongoDB `root`)
+    pub superuser: bool,
+    /// Catalogs describe every object, not only those the credential holds
+    /// privileges on
+    pub catalog_access: bool,
+    /// Tables and views whose rows the credential may read
+    pub readable_tables: usize,
+    /// Tables and views the credential can see
+    pub total_tables: usize,
+    /// The role, account, or user probed (e.g. MySQL `app@%`), kept out of
+    /// `evidence` so anonymization and `--no-metadata` can replace it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+    /// Grants and settings observed, e.g. `role is not a superuser`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<String>,
+}
+
+impl AccessProbe {
+    /// `Full` when the credential is a superuser, or reads the catalogs and
+    /// every table; otherwise `Limited`.
+    pub fn access_level(&self) -> AccessLevel {
+        if self.superuser || (self.catalog_access && self.readable_tables >= self.total_tables) {
+            AccessLevel::Full
+        } else {
+            AccessLevel::Limited
+        }
+    }
+}
+
+impl std::fmt::Display for AccessProbe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} (rows readable in {} of {} tables and views",
+            self.access_level(),
+            self.readable_tables,
+            self.total_tables
+        )?;
+        if self.superuser {
+            f.write_str("; superuser")?;
+        } else if !self.catalog_access {
+            f.write_str("; catalogs restricted")?;
+        }
+        f.write_str(")")
+    }
+}
+
 /// Status of database collection
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CollectionStatus {
@@ -305,6 +1273,9 @@ pub enum CollectionStatus {
     Failed { error: String },
     /// Collection was skipped
     Skipped { reason: String },
+    /// Collection completed, but some objects could not be fully collected
+    /// (see `CollectionMetadata::object_failures`)
+    Partial { failed_objects: usize },
 }
 
 /// Server-level information for multi-database collection
@@ -320,6 +1291,33 @@ pub struct ServerInfo {
     pub connection_user: String,
     pub has_superuser_privileges: bool,
     pub collection_mode: CollectionMode,
+    /// The server's configured time zone (e.g. `Europe/Berlin`). Collected
+    /// timestamps are reported in UTC; this records the zone the server
+    /// itself uses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Whether the server was a primary or a replica when surveyed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<ServerRole>,
+}
+
+/// Replication role of a surveyed server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerRole {
+    /// Accepts writes
+    Primary,
+    /// A read-only standby replaying the primary's changes
+    Replica,
+}
+
+impl std::fmt::Display for ServerRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerRole::Primary => write!(f, "primary"),
+            ServerRole::Replica => write!(f, "replica"),
+        }
+    }
 }
 
 /// Collection mode for database operations
@@ -336,14 +1334,74 @@ pub enum CollectionMode {
 }
 
 /// Complete database server schema representation for multi-database collection
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseServerSchema {
     pub format_version: String,
     pub server_info: ServerInfo,
+    /// One summary entry per database collected from the server
+    #[serde(default)]
+    pub manifest: Vec<DatabaseManifestEntry>,
     pub databases: Vec<DatabaseSchema>,
+    /// Databases folded into a structurally identical schema in `databases`
+    /// (see [`crate::dedup`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tenant_groups: Vec<TenantGroup>,
     pub collection_metadata: CollectionMetadata,
 }
 
+/// Databases sharing the structure of one canonical schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantGroup {
+    /// Name of the database in `databases` holding the shared schema
+    pub canonical: String,
+    /// Hex SHA-256 of the canonical structure
+    pub fingerprint: String,
+    pub tenants: Vec<TenantDatabase>,
+}
+
+/// A database stored as its differences from a group's canonical schema
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantDatabase {
+    pub database_info: DatabaseInfo,
+    /// Values that differ from the canonical schema, such as row counts,
+    /// keyed by JSON Pointer into the schema
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub deviations: BTreeMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<TableSample>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_metrics: Option<Vec<crate::quality::TableQualityMetrics>>,
+    pub collection_metadata: CollectionMetadata,
+}
+
+/// Summary of one database in a server schema
+///
+/// The manifest lets tools list what a server document contains, and how
+/// each database fared, without walking every schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseManifestEntry {
+    pub name: String,
+    pub collection_status: CollectionStatus,
+    pub table_count: usize,
+    pub view_count: usize,
+    pub object_count: usize,
+    pub sampled_tables: usize,
+}
+
+impl DatabaseManifestEntry {
+    /// Summarizes a collected database schema
+    pub fn from_schema(schema: &DatabaseSchema) -> Self {
+        Self {
+            name: schema.database_info.name.clone(),
+            collection_status: schema.database_info.collection_status.clone(),
+            table_count: schema.tables.len(),
+            view_count: schema.views.len(),
+            object_count: schema.object_count(),
+            sampled_tables: schema.samples.as_ref().map_or(0, Vec::len),
+        }
+    }
+}
+
 /// Data sampling strategy used for table sampling
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SamplingStrategy {
@@ -394,8 +1452,62 @@ pub enum SampleStatus {
     Skipped { reason: String },
 }
 
+/// How the values of a sampled column are written in [`TableSample::rows`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleValueEncoding {
+    /// Plain JSON value (string, integer, boolean, array, or object)
+    Json,
+    /// Exact decimal as a string that keeps the stored scale (`"12.50"`)
+    DecimalString,
+    /// Binary as `{"base64": "...", "length": <bytes>}`
+    Base64,
+    /// Floating point number; NaN and infinities are written as the strings
+    /// `"NaN"`, `"Infinity"`, and `"-Infinity"`
+    Float,
+    /// Timestamp as RFC 3339 with a zone offset
+    TimestampWithOffset,
+    /// Date, time, or timestamp without a zone, written as stored
+    LocalDateTime,
+}
+
+/// Legend entry describing one sampled column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SampleColumnType {
+    pub name: String,
+    /// Type as reported by the engine (e.g. `numeric(10,2)`, `DATETIME`, `binData`)
+    pub data_type: String,
+    pub encoding: SampleValueEncoding,
+    /// Digits after the decimal point, for decimals with a declared scale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
+}
+
+impl SampleColumnType {
+    /// Creates a legend entry without a scale.
+    pub fn new(
+        name: impl Into<String>,
+        data_type: impl Into<String>,
+        encoding: SampleValueEncoding,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type: data_type.into(),
+            encoding,
+            scale: None,
+        }
+    }
+
+    /// Builder method to set the decimal scale.
+    #[must_use]
+    pub fn with_scale(mut self, scale: Option<u32>) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
 /// Sample data from a table
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct TableSample {
     pub table_name: String,
     pub schema_name: Option<String>,
@@ -411,6 +1523,9 @@ pub struct TableSample {
     /// Outcome of the sampling operation; None for legacy data without status tracking
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sample_status: Option<SampleStatus>,
+    /// Type and encoding of each sampled column, in column order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_types: Vec<SampleColumnType>,
 }
 
 impl TableSample {
@@ -427,7 +1542,7 @@ pub fn column_names(&self) -> Option<Vec<String>> {
 }
 
 /// Complete database schema representation
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseSchema {
     pub format_version: String,
     pub database_info: DatabaseInfo,
@@ -449,16 +1564,96 @@ impl DatabaseInfo {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            engine: None,
             version: None,
             size_bytes: None,
             encoding: None,
             collation: None,
+            timezone: None,
             owner: None,
             is_system_database: false,
             access_level: AccessLevel::Full,
+            access_probe: None,
+            schemas: Vec::new(),
             collection_status: CollectionStatus::Success,
         }
     }
+
+    /// Records an access probe and the access level it implies.
+    ///
+    /// A probe that could not run leaves the level `Limited`: nothing
+    /// confirmed full access.
+    #[must_use]
+    pub fn with_access_probe(mut self, probe: crate::Result<AccessProbe>) -> Self {
+        match probe {
+            Ok(probe) => {
+                self.access_level = probe.access_level();
+                self.access_probe = Some(probe);
+            }
+            Err(e) => {
+                tracing::debug!("Could not probe access to '{}': {}", self.name, e);
+                self.access_level = AccessLevel::Limited;
+            }
+        }
+        self
+    }
+}
+
+impl DatabaseServerSchema {
+    /// Creates a server schema, building the manifest from `databases`
+    pub fn new(
+        server_info: ServerInfo,
+        databases: Vec<DatabaseSchema>,
+        collection_metadata: CollectionMetadata,
+    ) -> Self {
+        let mut schema = Self {
+            format_version: FORMAT_VERSION.to_string(),
+            server_info,
+            manifest: Vec::new(),
+            databases,
+            tenant_groups: Vec::new(),
+            collection_metadata,
+        };
+        schema.refresh_manifest();
+        schema
+    }
+
+    /// Rebuilds the manifest after `databases` has been modified
+    ///
+    /// Databases folded into `tenant_groups` are not listed, so call this
+    /// before deduplicating.
+    pub fn refresh_manifest(&mut self) {
+        self.manifest = self
+            .databases
+            .iter()
+            .map(DatabaseManifestEntry::from_schema)
+            .collect();
+    }
+}
+
+/// A parsed survey file: one database, or a server with several
+#[derive(Debug, Clone, PartialEq)]
+pub enum SurveyDocument {
+    Database(Box<DatabaseSchema>),
+    Server(Box<DatabaseServerSchema>),
+}
+
+impl SurveyDocument {
+    /// Format version of the document
+    pub fn format_version(&self) -> &str {
+        match self {
+            Self::Database(schema) => &schema.format_version,
+            Self::Server(server) => &server.format_version,
+        }
+    }
+
+    /// The database schemas in the document, in collection order
+    pub fn databases(&self) -> &[DatabaseSchema] {
+        match self {
+            Self::Database(schema) => std::slice::from_ref(schema.as_ref()),
+            Self::Server(server) => &server.databases,
+        }
+    }
 }
 
 impl DatabaseSchema {
@@ -482,6 +1677,12 @@ pub fn new(database_info: DatabaseInfo) -> Self {
                 collection_duration_ms: 0,
                 collector_version: env!("CARGO_PKG_VERSION").to_string(),
                 warnings: Vec::new(),
+                object_failures: Vec::new(),
+                degraded: Vec::new(),
+                read_only: None,
+                integrity: None,
+                auxiliary_files: Vec::new(),
+                settings: None,
             },
         }
     }
@@ -506,8 +1707,62 @@ pub fn quality_metrics_count(&self) -> usize {
     }
 
     /// Adds a warning to the collection metadata
-    pub fn with_warning(mut self, warning: String) -> Self {
-        self.collection_metadata.warnings.push(warning);
+    pub fn with_warning(mut self, warning: impl Into<CollectionWarning>) -> Self {
+        self.collection_metadata.warnings.push(warning.into());
+        self
+    }
+
+    /// Records objects that could not be fully collected.
+    ///
+    /// Each failure is also added as a warning, and a successful collection
+    /// status is downgraded to `CollectionStatus::Partial`. Does nothing when
+    /// `failures` is empty.
+    pub fn with_object_failures(mut self, failures: Vec<ObjectFailure>) -> Self {
+        if failures.is_empty() {
+            return self;
+        }
+
+        for failure in &failures {
+            self.collection_metadata.warnings.push(
+                CollectionWarning::new(
+                    WarningCategory::ObjectFailure,
+                    format!("Failed to collect {}: {}", failure.stage, failure.error),
+                )
+                .with_object(failure.qualified_name()),
+            );
+        }
+        self.collection_metadata.object_failures.extend(failures);
+
+        if matches!(
+            self.database_info.collection_status,
+            CollectionStatus::Success | CollectionStatus::Partial { .. }
+        ) {
+            self.database_info.collection_status = CollectionStatus::Partial {
+                failed_objects: self.collection_metadata.object_failures.len(),
+            };
+        }
+        self
+    }
+
+    /// Records object classes collected with reduced detail.
+    ///
+    /// Each is also added as a warning naming what the fallback could not
+    /// read. The collection status is unchanged: the objects are present.
+    pub fn with_degraded_collections(mut self, degraded: Vec<DegradedCollection>) -> Self {
+        for collection in &degraded {
+            let mut warning = format!(
+                "Collected {} from {} after access was denied",
+                collection.object_class, collection.source
+            );
+            if !collection.missing.is_empty() {
+                warning.push_str(&format!("; missing {}", collection.missing.join(", ")));
+            }
+            warning.push_str(&format!(": {}", collection.reason));
+            self.collection_metadata
+                .warnings
+                .push(CollectionWarning::new(WarningCategory::Collection, warning));
+        }
+        self.collection_metadata.degraded.extend(degraded);
         self
     }
 
@@ -584,7 +1839,152 @@ fn test_with_warning() {
         let schema = schema.with_warning("Test warning".to_string());
 
         assert_eq!(schema.collection_metadata.warnings.len(), 1);
-        assert_eq!(schema.collection_metadata.warnings[0], "Test warning");
+        assert_eq!(
+            schema.collection_metadata.warnings[0].message,
+            "Test warning"
+        );
+        assert_eq!(
+            schema.collection_metadata.warnings[0].category,
+            WarningCategory::General
+        );
+    }
+
+    #[test]
+    fn test_with_object_failures_marks_partial() {
+        let schema = DatabaseSchema::new(DatabaseInfo::new("test_db".to_string()));
+        let schema = schema.with_object_failures(Vec::new());
+        assert_eq!(
+            schema.database_info.collection_status,
+            CollectionStatus::Success
+        );
+
+        let schema = schema.with_object_failures(vec![
+            ObjectFailure::new(Some("public"), "orders", "indexes", "permission denied"),
+            ObjectFailure::new(None, "events", "columns", "timeout"),
+        ]);
+        assert_eq!(
+            schema.database_info.collection_status,
+            CollectionStatus::Partial { failed_objects: 2 }
+        );
+        assert_eq!(schema.collection_metadata.object_failures.len(), 2);
+        let warning = &schema.collection_metadata.warnings[0];
+        assert_eq!(warning.category, WarningCategory::ObjectFailure);
+        assert_eq!(
+            warning.message,
+            "Failed to collect indexes: permission denied"
+        );
+        assert_eq!(warning.objects, vec!["public.orders"]);
+    }
+
+    #[test]
+    fn test_with_access_probe_sets_level() {
+        let probe = AccessProbe {
+            superuser: false,
+            catalog_access: true,
+            readable_tables: 3,
+            total_tables: 3,
+            identity: None,
+            evidence: Vec::new(),
+        };
+        let info = DatabaseInfo::new("test_db".to_string()).with_access_probe(Ok(probe.clone()));
+        assert_eq!(info.access_level, AccessLevel::Full);
+        assert_eq!(
+            info.access_probe
+                .as_ref()
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("Full (rows readable in 3 of 3 tables and views)")
+        );
+
+        let partial = AccessProbe {
+            readable_tables: 2,
+            ..probe
+        };
+        assert_eq!(partial.access_level(), AccessLevel::Limited);
+
+        let info = DatabaseInfo::new("test_db".to_string()).with_access_probe(Err(
+            crate::error::DbSurveyorError::insufficient_privileges("pg_class"),
+        ));
+        assert_eq!(info.access_level, AccessLevel::Limited);
+        assert!(info.access_probe.is_none());
+    }
+
+    #[test]
+    fn test_with_degraded_collections_keeps_status() {
+        let mut views =
+            DegradedCollection::new("views", "information_schema").with_missing(&["comments"]);
+        views.reason = "permission denied for view pg_views".to_string();
+        views.objects = vec!["public.big_orders".to_string()];
+
+        let schema = DatabaseSchema::new(DatabaseInfo::new("test_db".to_string()))
+            .with_degraded_collections(vec![views]);
+        assert_eq!(
+            schema.database_info.collection_status,
+            CollectionStatus::Success
+        );
+        assert_eq!(schema.collection_metadata.degraded.len(), 1);
+        let warning = &schema.collection_metadata.warnings[0];
+        assert_eq!(warning.category, WarningCategory::Collection);
+        assert_eq!(
+            warning.message,
+            "Collected views from information_schema after access was denied; \
+             missing comments: permission denied for view pg_views"
+        );
+    }
+
+    #[test]
+    fn test_deduplicate_warnings_merges_counts_and_caps_objects() {
+        let mut warnings: Vec<CollectionWarning> = (0..15)
+            .map(|i| {
+                CollectionWarning::new(WarningCategory::ObjectFailure, "permission denied")
+                    .with_object(format!("public.t{}", i))
+            })
+            .collect();
+        warnings.push(CollectionWarning::new(
+            WarningCategory::Sampling,
+            "permission denied",
+        ));
+        warnings.push(
+            CollectionWarning::new(WarningCategory::ObjectFailure, "permission denied")
+                .with_object("public.t0"),
+        );
+
+        let merged = deduplicate_warnings(&warnings);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].count, 16);
+        assert_eq!(merged[0].objects.len(), MAX_WARNING_OBJECTS);
+        assert_eq!(merged[0].objects[0], "public.t0");
+        assert_eq!(merged[1].category, WarningCategory::Sampling);
+        assert_eq!(merged[1].count, 1);
+    }
+
+    #[test]
+    fn test_warnings_deduplicated_on_serialization() {
+        let schema = DatabaseSchema::new(DatabaseInfo::new("test_db".to_string()))
+            .with_warning("slow query")
+            .with_warning("slow query");
+
+        let json = serde_json::to_value(&schema).expect("serialize");
+        assert_eq!(
+            json["collection_metadata"]["warnings"],
+            serde_json::json!([{
+                "severity": "warning",
+                "category": "general",
+                "message": "slow query",
+                "count": 2
+            }])
+        );
+
+        let parsed: DatabaseSchema = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(parsed.collection_metadata.warnings[0].count, 2);
+    }
+
+    #[test]
+    fn test_legacy_string_warnings_deserialize() {
+        let warning: CollectionWarning =
+            serde_json::from_str("\"Large table detected\"").expect("legacy warning");
+        assert_eq!(warning, CollectionWarning::from("Large table detected"));
+        assert_eq!(warning.severity, WarningSeverity::Warning);
     }
 
     #[test]
@@ -614,6 +2014,7 @@ fn test_with_samples() {
             collected_at: chrono::Utc::now(),
             warnings: Vec::new(),
             sample_status: None,
+            column_types: Vec::new(),
         };
 
         let schema = schema.with_samples(vec![sample]);
@@ -653,6 +2054,7 @@ fn test_table_sample_serialize_omits_none_sample_status() {
             collected_at: chrono::Utc::now(),
             warnings: Vec::new(),
             sample_status: None,
+            column_types: Vec::new(),
         };
 
         let json = serde_json::to_value(&sample).unwrap();
@@ -0,0 +1,396 @@
+//! Privileges each collection feature needs, per engine.
+//!
+//! Collection degrades rather than fails when the collecting role lacks a
+//! privilege: the feature is skipped and a warning or object failure is
+//! recorded. [`privilege_requirements`] lists the minimum privileges per
+//! feature so a role can be provisioned up front, and [`grant_statements`]
+//! renders them as statements to run. After a run, [`privilege_skips`]
+//! picks out the features that were skipped because access was denied.
+//!
+//! Feature names match the names collection uses in its warnings
+//! (`Failed to collect views: ...`) and object failure stages, so a skip can
+//! be traced back to its requirement with [`requirement_for`].
+
+use crate::models::{CollectionStatus, DatabaseSchema, DatabaseType, WarningCategory};
+
+/// Placeholder for the collecting role in grant templates.
+pub const USER_PLACEHOLDER: &str = "{user}";
+/// Placeholder for the database in grant templates.
+pub const DATABASE_PLACEHOLDER: &str = "{database}";
+/// Placeholder for the schema in grant templates.
+pub const SCHEMA_PLACEHOLDER: &str = "{schema}";
+
+/// The minimum privilege one collection feature needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivilegeRequirement {
+    /// Collection feature, e.g. `views` or `sampling`
+    pub feature: &'static str,
+    /// The privilege, in the engine's terms
+    pub privilege: &'static str,
+    /// Statement templates granting it; empty when nothing needs granting
+    pub grants: &'static [&'static str],
+}
+
+const fn requirement(
+    feature: &'static str,
+    privilege: &'static str,
+    grants: &'static [&'static str],
+) -> PrivilegeRequirement {
+    PrivilegeRequirement {
+        feature,
+        privilege,
+        grants,
+    }
+}
+
+const POSTGRES_SELECT: &[&str] = &[
+    "GRANT USAGE ON SCHEMA {schema} TO {user};",
+    "GRANT SELECT ON ALL TABLES IN SCHEMA {schema} TO {user};",
+];
+
+const POSTGRES: &[PrivilegeRequirement] = &[
+    requirement(
+        "connection",
+        "CONNECT on the database",
+        &["GRANT CONNECT ON DATABASE {database} TO {user};"],
+    ),
+    requirement(
+        "tables",
+        "USAGE on each schema and SELECT on its tables; information_schema omits tables the role holds no privilege on",
+        POSTGRES_SELECT,
+    ),
+    requirement(
+        "views",
+        "USAGE on each schema and SELECT on its views",
+        POSTGRES_SELECT,
+    ),
+    requirement(
+        "functions",
+        "None beyond CONNECT; pg_proc is readable by every role",
+        &[],
+    ),
+    requirement(
+        "procedures",
+        "None beyond CONNECT; pg_proc is readable by every role",
+        &[],
+    ),
+    requirement(
+        "triggers",
+        "None beyond CONNECT; pg_trigger is readable by every role",
+        &[],
+    ),
+    requirement("sampling", "SELECT on each sampled table", POSTGRES_SELECT),
+    requirement(
+        "database enumeration",
+        "CONNECT on every database to collect (--all-databases)",
+        &["GRANT CONNECT ON DATABASE {database} TO {user};"],
+    ),
+    requirement(
+        "read-only enforcement",
+        "A role whose transactions default to read-only (--require-read-only)",
+        &["ALTER ROLE {user} SET default_transaction_read_only = on;"],
+    ),
+];
+
+const MYSQL_SELECT: &[&str] = &["GRANT SELECT ON `{database}`.* TO '{user}'@'%';"];
+
+const MYSQL: &[PrivilegeRequirement] = &[
+    requirement(
+        "connection",
+        "Any privilege on the database; USAGE alone cannot select it",
+        MYSQL_SELECT,
+    ),
+    requirement(
+        "tables",
+        "SELECT on the database; INFORMATION_SCHEMA omits tables the role holds no privilege on",
+        MYSQL_SELECT,
+    ),
+    requirement("partition metadata", "SELECT on the database", MYSQL_SELECT),
+    requirement(
+        "views",
+        "SHOW VIEW on the database to read view definitions",
+        &["GRANT SHOW VIEW ON `{database}`.* TO '{user}'@'%';"],
+    ),
+    requirement("sampling", "SELECT on each sampled table", MYSQL_SELECT),
+    requirement(
+        "database enumeration",
+        "SHOW DATABASES to list databases the role holds no privilege on (--all-databases)",
+        &["GRANT SHOW DATABASES ON *.* TO '{user}'@'%';"],
+    ),
+    requirement(
+        "read-only enforcement",
+        "super_read_only on the server, typically a replica (--require-read-only)",
+        &["SET PERSIST super_read_only = ON;"],
+    ),
+];
+
+const SQLITE: &[PrivilegeRequirement] = &[
+    requirement(
+        "connection",
+        "Read permission on the database file and its directory",
+        &[],
+    ),
+    requirement(
+        "attached databases",
+        "Read permission on each --attach file",
+        &[],
+    ),
+    requirement(
+        "read-only enforcement",
+        "None; the collector opens files read-only unless configured otherwise",
+        &[],
+    ),
+];
+
+const MONGODB_READ: &[&str] = &[
+    "db.getSiblingDB(\"admin\").grantRolesToUser(\"{user}\", [{ role: \"read\", db: \"{database}\" }]);",
+];
+
+const MONGODB: &[PrivilegeRequirement] = &[
+    requirement(
+        "collections",
+        "listCollections, listIndexes, and collStats on the database (the read role)",
+        MONGODB_READ,
+    ),
+    requirement(
+        "sampling",
+        "find on each sampled collection (the read role)",
+        MONGODB_READ,
+    ),
+    requirement(
+        "database enumeration",
+        "listDatabases on the cluster (--all-databases)",
+        &[
+            "db.getSiblingDB(\"admin\").grantRolesToUser(\"{user}\", [{ role: \"readAnyDatabase\", db: \"admin\" }]);",
+        ],
+    ),
+];
+
+/// The minimum privileges for each collection feature on `database_type`.
+///
+/// Engines without an adapter have no requirements.
+pub fn privilege_requirements(database_type: DatabaseType) -> &'static [PrivilegeRequirement] {
+    match database_type {
+        DatabaseType::PostgreSQL => POSTGRES,
+        DatabaseType::MySQL => MYSQL,
+        DatabaseType::SQLite => SQLITE,
+        DatabaseType::MongoDB => MONGODB,
+        DatabaseType::SqlServer => &[],
+    }
+}
+
+/// The requirement for `feature` on `database_type`, if one is listed.
+pub fn requirement_for(
+    database_type: DatabaseType,
+    feature: &str,
+) -> Option<&'static PrivilegeRequirement> {
+    privilege_requirements(database_type)
+        .iter()
+        .find(|requirement| requirement.feature.eq_ignore_ascii_case(feature))
+}
+
+/// Renders the grant templates for `database_type`, each statement once, in
+/// feature order.
+pub fn grant_statements(
+    database_type: DatabaseType,
+    user: &str,
+    database: &str,
+    schema: &str,
+) -> Vec<String> {
+    let mut statements: Vec<String> = Vec::new();
+    for template in privilege_requirements(database_type)
+        .iter()
+        .flat_map(|requirement| requirement.grants)
+    {
+        let statement = template
+            .replace(USER_PLACEHOLDER, user)
+            .replace(DATABASE_PLACEHOLDER, database)
+            .replace(SCHEMA_PLACEHOLDER, schema);
+        if !statements.contains(&statement) {
+            statements.push(statement);
+        }
+    }
+    statements
+}
+
+/// Whether an error message reports denied access rather than another failure.
+pub fn is_privilege_error(message: &str) -> bool {
+    const DENIALS: &[&str] = &[
+        "insufficient privileges",
+        "permission denied",
+        "access denied",
+        "command denied",
+        "not authorized",
+    ];
+    let message = message.to_ascii_lowercase();
+    DENIALS.iter().any(|denial| message.contains(denial))
+}
+
+/// A feature skipped, wholly or for some objects, because access was denied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivilegeSkip {
+    /// Collection feature, e.g. `views` or `indexes`
+    pub feature: String,
+    /// Objects the feature was skipped for; empty when skipped entirely
+    pub objects: Vec<String>,
+    /// The first error reported for the feature
+    pub error: String,
+}
+
+/// Features of `schema` that were skipped because access was denied, in the
+/// order they were first reported.
+pub fn privilege_skips(schema: &DatabaseSchema) -> Vec<PrivilegeSkip> {
+    let mut skips: Vec<PrivilegeSkip> = Vec::new();
+    let mut record = |feature: &str, object: Option<String>, error: &str| {
+        let index = match skips.iter().position(|skip| skip.feature == feature) {
+            Some(index) => index,
+            None => {
+                skips.push(PrivilegeSkip {
+                    feature: feature.to_string(),
+                    objects: Vec::new(),
+                    error: error.to_string(),
+                });
+                skips.len() - 1
+            }
+        };
+        if let Some(object) = object
+            && !skips[index].objects.contains(&object)
+        {
+            skips[index].objects.push(object);
+        }
+    };
+
+    if let CollectionStatus::Failed { error } = &schema.database_info.collection_status
+        && is_privilege_error(error)
+    {
+        record("connection", Some(schema.database_info.name.clone()), error);
+    }
+
+    for warning in &schema.collection_metadata.warnings {
+        if !is_privilege_error(&warning.message) {
+            continue;
+        }
+        match warning.category {
+            WarningCategory::Collection => {
+                if let Some((feature, error)) = warning
+                    .message
+                    .strip_prefix("Failed to collect ")
+                    .and_then(|rest| rest.split_once(": "))
+                {
+                    record(feature, None, error);
+                }
+            }
+            WarningCategory::Sampling => {
+                if warning.objects.is_empty() {
+                    record("sampling", None, &warning.message);
+                }
+                for object in &warning.objects {
+                    record("sampling", Some(object.clone()), &warning.message);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for failure in &schema.collection_metadata.object_failures {
+        if is_privilege_error(&failure.error) {
+            record(
+                &failure.stage,
+                Some(failure.qualified_name()),
+                &failure.error,
+            );
+        }
+    }
+
+    skips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CollectionWarning, DatabaseInfo, ObjectFailure};
+
+    #[test]
+    fn test_every_engine_with_an_adapter_lists_requirements() {
+        for database_type in [
+            DatabaseType::PostgreSQL,
+            DatabaseType::MySQL,
+            DatabaseType::SQLite,
+            DatabaseType::MongoDB,
+        ] {
+            assert!(!privilege_requirements(database_type).is_empty());
+        }
+        assert_eq!(
+            requirement_for(DatabaseType::MySQL, "Views").map(|r| r.privilege),
+            Some("SHOW VIEW on the database to read view definitions")
+        );
+        assert!(requirement_for(DatabaseType::PostgreSQL, "widgets").is_none());
+    }
+
+    #[test]
+    fn test_grant_statements_are_rendered_once() {
+        let statements = grant_statements(DatabaseType::PostgreSQL, "surveyor", "shop", "sales");
+        assert_eq!(
+            statements,
+            vec![
+                "GRANT CONNECT ON DATABASE shop TO surveyor;",
+                "GRANT USAGE ON SCHEMA sales TO surveyor;",
+                "GRANT SELECT ON ALL TABLES IN SCHEMA sales TO surveyor;",
+                "ALTER ROLE surveyor SET default_transaction_read_only = on;",
+            ]
+        );
+        assert!(grant_statements(DatabaseType::SQLite, "u", "d", "s").is_empty());
+    }
+
+    #[test]
+    fn test_privilege_skips_from_warnings_and_failures() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.collection_metadata.warnings = vec![
+            CollectionWarning::new(
+                WarningCategory::Collection,
+                "Failed to collect views: Insufficient privileges: Cannot access pg_views",
+            ),
+            CollectionWarning::new(
+                WarningCategory::Collection,
+                "Failed to collect triggers: Query execution failed: timeout",
+            ),
+            CollectionWarning::new(
+                WarningCategory::Sampling,
+                "Sampling failed: permission denied for table payments",
+            )
+            .with_object("public.payments"),
+        ];
+        schema.collection_metadata.object_failures = vec![
+            ObjectFailure::new(
+                Some("public"),
+                "orders",
+                "indexes",
+                "permission denied for relation orders",
+            ),
+            ObjectFailure::new(Some("public"), "audit", "indexes", "Access denied"),
+        ];
+
+        let skips = privilege_skips(&schema);
+        let features: Vec<&str> = skips.iter().map(|s| s.feature.as_str()).collect();
+        assert_eq!(features, vec!["views", "sampling", "indexes"]);
+        assert!(skips[0].objects.is_empty());
+        assert_eq!(
+            skips[0].error,
+            "Insufficient privileges: Cannot access pg_views"
+        );
+        assert_eq!(skips[1].objects, vec!["public.payments"]);
+        assert_eq!(skips[2].objects, vec!["public.orders", "public.audit"]);
+    }
+
+    #[test]
+    fn test_is_privilege_error() {
+        assert!(is_privilege_error("ERROR: permission denied for schema hr"));
+        assert!(is_privilege_error(
+            "SELECT command denied to user 'ro'@'%' for table 'x'"
+        ));
+        assert!(is_privilege_error(
+            "not authorized on admin to execute command { listDatabases: 1 }"
+        ));
+        assert!(!is_privilege_error("connection reset by peer"));
+    }
+}
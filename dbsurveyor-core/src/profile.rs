@@ -0,0 +1,573 @@
+//! Named configuration profiles shared by the collector and postprocessor.
+//!
+//! Profiles live in `~/.config/dbsurveyor/config.toml` (or the file named
+//! by `DBSURVEYOR_CONFIG`) and are selected with `--profile <name>` on
+//! either binary:
+//!
+//! ```toml
+//! [profiles.prod.collect]
+//! sample = 20
+//! throttle = 250
+//! compress = true
+//! encrypt = true
+//!
+//! [profiles.prod.report]
+//! title = "Acme Data Catalog"
+//! footer = "Internal use only"
+//!
+//! [profiles.prod.redaction]
+//! mode = "strict"
+//! sensitive_patterns = "~/.config/dbsurveyor/patterns.yaml"
+//!
+//! [profiles.prod.keys]
+//! password_file = "~/.config/dbsurveyor/prod.pass"
+//! ```
+//!
+//! A profile only supplies defaults. Each setting stands for the
+//! command-line option of the same name, and options given on the command
+//! line (or through their environment variable) take precedence. A setting
+//! whose option conflicts with one given on the command line is dropped, so
+//! `compress = true` does not break a run with `--append-to`. The protective
+//! settings `encrypt`, `sign`, and `require_read_only` are never dropped
+//! that way: a conflict with them is an error rather than a survey written
+//! without the protection the profile asked for. Boolean settings can turn
+//! a flag on but not off.
+
+use crate::Result;
+use crate::error::DbSurveyorError;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the configuration file, overriding the
+/// default location.
+pub const CONFIG_ENV_VAR: &str = "DBSURVEYOR_CONFIG";
+
+/// Contents of the configuration file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// Profiles by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// One named profile.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Collector defaults
+    #[serde(default)]
+    pub collect: CollectDefaults,
+    /// Report defaults and branding
+    #[serde(default)]
+    pub report: ReportDefaults,
+    /// Redaction policy
+    #[serde(default)]
+    pub redaction: RedactionDefaults,
+    /// Key file locations
+    #[serde(default)]
+    pub keys: KeyLocations,
+}
+
+/// Collector defaults, named after `dbsurveyor-collect` options.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CollectDefaults {
+    /// `--sample`
+    pub sample: Option<u32>,
+    /// `--throttle` (milliseconds)
+    pub throttle: Option<u64>,
+    /// `--table-timeout` (seconds)
+    pub table_timeout: Option<u64>,
+    /// `--sample-max-table-rows`
+    pub sample_max_table_rows: Option<u64>,
+    /// `--config`, the session limits file
+    pub config: Option<PathBuf>,
+    /// `--compress`
+    pub compress: Option<bool>,
+    /// `--encrypt`
+    pub encrypt: Option<bool>,
+    /// `--sign`
+    pub sign: Option<bool>,
+    /// `--require-read-only`
+    pub require_read_only: Option<bool>,
+}
+
+/// Report defaults, named after `dbsurveyor` options.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportDefaults {
+    /// `--report-title`
+    pub title: Option<String>,
+    /// `--report-footer`
+    pub footer: Option<String>,
+    /// `--sections`
+    #[serde(default)]
+    pub sections: Vec<String>,
+    /// `--include-samples`
+    pub include_samples: Option<bool>,
+}
+
+/// Redaction policy for both binaries.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionDefaults {
+    /// `dbsurveyor --redact-mode`
+    pub mode: Option<String>,
+    /// `dbsurveyor-collect --sensitive-patterns`
+    pub sensitive_patterns: Option<PathBuf>,
+}
+
+/// Key file locations.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyLocations {
+    /// `--password-file`: encryption password for `--encrypt` and for
+    /// reading encrypted surveys
+    pub password_file: Option<PathBuf>,
+    /// `dbsurveyor-collect --signing-key-file`: key for `--sign`
+    pub signing_key_file: Option<PathBuf>,
+}
+
+/// A command-line option supplied by a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileArg {
+    /// Long option name without the leading dashes
+    pub long: &'static str,
+    /// Option value; `None` for a flag
+    pub value: Option<String>,
+}
+
+impl ProfileArg {
+    fn value(long: &'static str, value: impl ToString) -> Self {
+        Self {
+            long,
+            value: Some(value.to_string()),
+        }
+    }
+
+    fn flag(long: &'static str, enabled: Option<bool>) -> Option<Self> {
+        enabled
+            .unwrap_or(false)
+            .then_some(Self { long, value: None })
+    }
+}
+
+impl ProfileConfig {
+    /// Parses a configuration file's contents.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| DbSurveyorError::configuration(e.to_string()))
+    }
+
+    /// Reads the configuration file at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to read config file {}", path.display()),
+            source: e,
+        })?;
+        Self::from_toml(&content).map_err(|e| {
+            DbSurveyorError::configuration(format!("Invalid config file {}: {}", path.display(), e))
+        })
+    }
+
+    /// The profile named `name`.
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profiles.get(name).ok_or_else(|| {
+            let available: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            DbSurveyorError::configuration(if available.is_empty() {
+                format!("Unknown profile '{}': no profiles are defined", name)
+            } else {
+                format!(
+                    "Unknown profile '{}'; available profiles: {}",
+                    name,
+                    available.join(", ")
+                )
+            })
+        })
+    }
+}
+
+impl Profile {
+    /// Options this profile supplies to `dbsurveyor-collect`.
+    pub fn collector_args(&self) -> Vec<ProfileArg> {
+        let collect = &self.collect;
+        let mut args = Vec::new();
+        args.extend(collect.sample.map(|v| ProfileArg::value("sample", v)));
+        args.extend(collect.throttle.map(|v| ProfileArg::value("throttle", v)));
+        args.extend(
+            collect
+                .table_timeout
+                .map(|v| ProfileArg::value("table-timeout", v)),
+        );
+        args.extend(
+            collect
+                .sample_max_table_rows
+                .map(|v| ProfileArg::value("sample-max-table-rows", v)),
+        );
+        args.extend(
+            collect
+                .config
+                .as_deref()
+                .map(|path| ProfileArg::value("config", expand_home(path).display())),
+        );
+        args.extend(
+            self.redaction
+                .sensitive_patterns
+                .as_deref()
+                .map(|path| ProfileArg::value("sensitive-patterns", expand_home(path).display())),
+        );
+        args.extend(ProfileArg::flag("compress", collect.compress));
+        args.extend(ProfileArg::flag("encrypt", collect.encrypt));
+        args.extend(ProfileArg::flag("sign", collect.sign));
+        args.extend(ProfileArg::flag(
+            "require-read-only",
+            collect.require_read_only,
+        ));
+        args.extend(self.password_file_arg());
+        args.extend(
+            self.keys
+                .signing_key_file
+                .as_deref()
+                .map(|path| ProfileArg::value("signing-key-file", expand_home(path).display())),
+        );
+        args
+    }
+
+    /// Options this profile supplies to `dbsurveyor`.
+    pub fn postprocessor_args(&self) -> Vec<ProfileArg> {
+        let report = &self.report;
+        let mut args = Vec::new();
+        args.extend(
+            report
+                .title
+                .as_ref()
+                .map(|title| ProfileArg::value("report-title", title)),
+        );
+        args.extend(
+            report
+                .footer
+                .as_ref()
+                .map(|footer| ProfileArg::value("report-footer", footer)),
+        );
+        if !report.sections.is_empty() {
+            args.push(ProfileArg::value("sections", report.sections.join(",")));
+        }
+        args.extend(ProfileArg::flag("include-samples", report.include_samples));
+        args.extend(
+            self.redaction
+                .mode
+                .as_ref()
+                .map(|mode| ProfileArg::value("redact-mode", mode)),
+        );
+        args.extend(self.password_file_arg());
+        args
+    }
+
+    fn password_file_arg(&self) -> Option<ProfileArg> {
+        self.keys
+            .password_file
+            .as_deref()
+            .map(|path| ProfileArg::value("password-file", expand_home(path).display()))
+    }
+}
+
+/// Location of the configuration file: `DBSURVEYOR_CONFIG`, else
+/// `dbsurveyor/config.toml` under `XDG_CONFIG_HOME` or `~/.config`.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("dbsurveyor").join("config.toml"))
+}
+
+/// Loads the profile named `name` from the configuration file.
+pub fn load_profile(name: &str) -> Result<Profile> {
+    let path = config_path().ok_or_else(|| {
+        DbSurveyorError::configuration(format!(
+            "Cannot locate the config file for profile '{}'; set {} or HOME",
+            name, CONFIG_ENV_VAR
+        ))
+    })?;
+    let config = ProfileConfig::load(&path)?;
+    config.profile(name).cloned()
+}
+
+/// Profile options that may not be dropped for conflicting with the
+/// command line, since the survey would silently lose a protection.
+pub const PROTECTIVE_OPTIONS: &[&str] = &["encrypt", "sign", "require-read-only"];
+
+/// Why a profile option is left out of the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Override {
+    /// The option was given on the command line
+    Explicit,
+    /// The option conflicts with `--<long>`, which was given on the
+    /// command line
+    Conflict(String),
+}
+
+/// Adds profile options to a command line, after the program name so they
+/// read as top-level options. Options for which `overridden_by` returns an
+/// [`Override`], because the user gave them or an option they conflict
+/// with, are left out.
+///
+/// # Errors
+/// Returns an error if one of [`PROTECTIVE_OPTIONS`] conflicts with an
+/// option given on the command line.
+pub fn merge_args(
+    args: Vec<OsString>,
+    profile_args: &[ProfileArg],
+    overridden_by: impl Fn(&str) -> Option<Override>,
+) -> Result<Vec<OsString>> {
+    let mut args = args.into_iter();
+    let mut merged: Vec<OsString> = args.next().into_iter().collect();
+    for arg in profile_args {
+        match overridden_by(arg.long) {
+            None => {
+                merged.push(format!("--{}", arg.long).into());
+                merged.extend(arg.value.as_ref().map(OsString::from));
+            }
+            Some(Override::Conflict(other)) if PROTECTIVE_OPTIONS.contains(&arg.long) => {
+                return Err(DbSurveyorError::configuration(format!(
+                    "The profile sets --{}, which conflicts with --{}; remove the setting \
+                     from the profile or drop --{}",
+                    arg.long, other, other
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    merged.extend(args);
+    Ok(merged)
+}
+
+/// Whether a profile must leave out the option `--long` of `command`: it
+/// was given on the command line or through its environment variable, or
+/// it conflicts with an option that was. Available with the `cli` feature.
+#[cfg(feature = "cli")]
+pub fn overridden_by(
+    command: &clap::Command,
+    matches: &clap::ArgMatches,
+    long: &str,
+) -> Option<Override> {
+    let arg = command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(long))?;
+    if is_explicit(matches, arg) {
+        return Some(Override::Explicit);
+    }
+    let conflicts = |a: &clap::Arg, b: &clap::Arg| {
+        command
+            .get_arg_conflicts_with(a)
+            .iter()
+            .any(|other| other.get_id() == b.get_id())
+    };
+    command
+        .get_arguments()
+        .filter(|other| is_explicit(matches, other))
+        .find(|other| conflicts(arg, other) || conflicts(other, arg))
+        .map(|other| {
+            Override::Conflict(
+                other
+                    .get_long()
+                    .map_or_else(|| other.get_id().to_string(), str::to_string),
+            )
+        })
+}
+
+/// Whether `arg` was given on the command line or through its environment
+/// variable.
+#[cfg(feature = "cli")]
+fn is_explicit(matches: &clap::ArgMatches, arg: &clap::Arg) -> bool {
+    matches
+        .value_source(arg.get_id().as_str())
+        .is_some_and(|source| source != clap::parser::ValueSource::DefaultValue)
+}
+
+/// Expands a leading `~/` to the home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [profiles.prod.collect]
+        sample = 20
+        throttle = 250
+        encrypt = true
+        compress = false
+
+        [profiles.prod.report]
+        title = "Acme Data Catalog"
+
+        [profiles.prod.redaction]
+        mode = "strict"
+
+        [profiles.prod.keys]
+        password_file = "/etc/dbsurveyor/prod.pass"
+
+        [profiles.dev]
+    "#;
+
+    #[test]
+    fn test_profile_args() {
+        let config = ProfileConfig::from_toml(CONFIG).expect("valid config");
+        let prod = config.profile("prod").expect("prod profile");
+
+        let collector: Vec<String> = prod
+            .collector_args()
+            .iter()
+            .map(|arg| format!("--{} {}", arg.long, arg.value.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            collector,
+            vec![
+                "--sample 20",
+                "--throttle 250",
+                "--encrypt ",
+                "--password-file /etc/dbsurveyor/prod.pass",
+            ]
+        );
+
+        let postprocessor = prod.postprocessor_args();
+        assert_eq!(
+            postprocessor[0],
+            ProfileArg::value("report-title", "Acme Data Catalog")
+        );
+        assert_eq!(postprocessor[1], ProfileArg::value("redact-mode", "strict"));
+        assert!(
+            config
+                .profile("dev")
+                .expect("dev")
+                .collector_args()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_invalid_profiles_rejected() {
+        let config = ProfileConfig::from_toml(CONFIG).expect("valid config");
+        let err = config.profile("staging").expect_err("unknown profile");
+        assert!(err.to_string().contains("available profiles: dev, prod"));
+
+        // Misspelled settings are errors, not silently ignored
+        assert!(ProfileConfig::from_toml("[profiles.prod.collect]\nsamples = 20\n").is_err());
+        assert!(ProfileConfig::from_toml("").is_ok());
+    }
+
+    #[test]
+    fn test_merge_args_keeps_explicit_options() {
+        let args = ["dbsurveyor-collect", "--sample", "5", "postgres://db"]
+            .map(OsString::from)
+            .to_vec();
+        let profile_args = [
+            ProfileArg::value("sample", 20),
+            ProfileArg::value("throttle", 250),
+            ProfileArg {
+                long: "encrypt",
+                value: None,
+            },
+        ];
+        let merged = merge_args(args, &profile_args, |long| {
+            (long == "sample").then_some(Override::Explicit)
+        })
+        .expect("no protective option dropped");
+        assert_eq!(
+            merged,
+            [
+                "dbsurveyor-collect",
+                "--throttle",
+                "250",
+                "--encrypt",
+                "--sample",
+                "5",
+                "postgres://db"
+            ]
+            .map(OsString::from)
+        );
+    }
+
+    #[test]
+    fn test_conflicting_option_dropped_unless_protective() {
+        let args = ["dbsurveyor-collect", "--append-to", "survey.journal"].map(OsString::from);
+        let overridden = |_: &str| Some(Override::Conflict("append-to".to_string()));
+
+        let compress = [ProfileArg {
+            long: "compress",
+            value: None,
+        }];
+        let merged = merge_args(args.to_vec(), &compress, overridden).expect("compress dropped");
+        assert_eq!(merged, args);
+
+        let sign = [ProfileArg {
+            long: "sign",
+            value: None,
+        }];
+        let err = merge_args(args.to_vec(), &sign, overridden).expect_err("sign kept");
+        assert!(
+            err.to_string()
+                .contains("--sign, which conflicts with --append-to")
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_conflicting_protective_option_rejected() {
+        let command = clap::Command::new("dbsurveyor-collect")
+            .arg(
+                clap::Arg::new("append_to")
+                    .long("append-to")
+                    .conflicts_with_all(["compress", "encrypt"]),
+            )
+            .arg(
+                clap::Arg::new("compress")
+                    .long("compress")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                clap::Arg::new("encrypt")
+                    .long("encrypt")
+                    .action(clap::ArgAction::SetTrue),
+            );
+        let args = ["dbsurveyor-collect", "--append-to", "survey.journal"].map(OsString::from);
+        let matches = command.clone().get_matches_from(args.clone());
+        let overridden = |long: &str| overridden_by(&command, &matches, long);
+
+        assert_eq!(overridden("append-to"), Some(Override::Explicit));
+        assert_eq!(
+            overridden("compress"),
+            Some(Override::Conflict("append-to".to_string()))
+        );
+
+        // A dropped --compress only changes the file format
+        let compress = [ProfileArg {
+            long: "compress",
+            value: None,
+        }];
+        let merged = merge_args(args.to_vec(), &compress, overridden).expect("compress dropped");
+        assert_eq!(merged, args);
+
+        // A dropped --encrypt would write the survey in plaintext
+        let encrypt = [ProfileArg {
+            long: "encrypt",
+            value: None,
+        }];
+        let err = merge_args(args.to_vec(), &encrypt, overridden).expect_err("encrypt kept");
+        assert!(
+            err.to_string()
+                .contains("--encrypt, which conflicts with --append-to")
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! Detection of columns that store hashed or encrypted values.
+//!
+//! A column named `password` or `ssn` may hold plaintext or a protected
+//! value; reviewers need to tell them apart. [`detect_protected_columns`]
+//! flags columns whose sampled values look like password hashes (bcrypt,
+//! argon2, scrypt, crypt(3), PBKDF2), fixed-length digests in hex or base64,
+//! or ciphertext, and columns whose default or generation expression calls
+//! a hashing or encryption function (pgcrypto, MySQL `AES_ENCRYPT`, ...).
+//!
+//! Findings are heuristics: a 32-character hex column may be an MD5 digest
+//! or an identifier written without dashes.
+//!
+//! # Security Guarantees
+//! - Only match counts are reported, never the sampled values themselves
+
+use crate::models::{Column, DatabaseSchema, Table, TableSample};
+use serde::{Deserialize, Serialize};
+
+/// Fraction of sampled values that must share one protection
+const MIN_MATCH_RATIO: f64 = 0.9;
+/// Shortest base64 string treated as ciphertext
+const MIN_CIPHERTEXT_LENGTH: usize = 24;
+
+/// Functions in default or generation expressions, in match order.
+const PROTECTING_FUNCTIONS: &[(&str, ProtectionKind)] = &[
+    ("pgp_sym_encrypt", ProtectionKind::Encrypted),
+    ("pgp_pub_encrypt", ProtectionKind::Encrypted),
+    ("encrypt_iv", ProtectionKind::Encrypted),
+    ("aes_encrypt", ProtectionKind::Encrypted),
+    ("encrypt", ProtectionKind::Encrypted),
+    ("gen_salt", ProtectionKind::PasswordHash),
+    ("crypt", ProtectionKind::PasswordHash),
+    ("hmac", ProtectionKind::Digest),
+    ("digest", ProtectionKind::Digest),
+    ("sha2", ProtectionKind::Digest),
+    ("sha256", ProtectionKind::Digest),
+    ("sha512", ProtectionKind::Digest),
+    ("sha1", ProtectionKind::Digest),
+    ("md5", ProtectionKind::Digest),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProtectionKind {
+    PasswordHash,
+    Digest,
+    Encrypted,
+}
+
+/// How a column's values appear to be protected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Protection {
+    /// Salted password hash, e.g. `bcrypt` or `argon2id`
+    PasswordHash { algorithm: String },
+    /// Unsalted digest or HMAC, e.g. `SHA-256`
+    Digest { algorithm: String },
+    /// Reversible encryption
+    Encrypted { scheme: Option<String> },
+}
+
+impl Protection {
+    /// Short human-readable description.
+    pub fn label(&self) -> String {
+        match self {
+            Self::PasswordHash { algorithm } => format!("{} password hash", algorithm),
+            Self::Digest { algorithm } => format!("{} digest", algorithm),
+            Self::Encrypted {
+                scheme: Some(scheme),
+            } => format!("encrypted ({})", scheme),
+            Self::Encrypted { scheme: None } => "encrypted".to_string(),
+        }
+    }
+}
+
+/// Signal that a column holds protected values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProtectionEvidence {
+    /// Sampled values matching the protection's format
+    SampledValues { matched: usize, sampled: usize },
+    /// A protecting function in the column's default or generation expression
+    Expression { function: String },
+}
+
+impl ProtectionEvidence {
+    /// Short human-readable description.
+    pub fn label(&self) -> String {
+        match self {
+            Self::SampledValues { matched, sampled } => {
+                format!("{} of {} sampled values", matched, sampled)
+            }
+            Self::Expression { function } => format!("{}() in expression", function),
+        }
+    }
+}
+
+/// A column that appears to store hashed or encrypted values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtectedColumn {
+    pub schema: Option<String>,
+    pub table: String,
+    pub column: String,
+    pub protection: Protection,
+    pub evidence: Vec<ProtectionEvidence>,
+}
+
+/// Finds columns whose samples or expressions indicate hashed or encrypted
+/// values, in table and column order.
+pub fn detect_protected_columns(schema: &DatabaseSchema) -> Vec<ProtectedColumn> {
+    let samples = schema.samples.as_deref().unwrap_or_default();
+    let mut protected = Vec::new();
+    for table in &schema.tables {
+        let sample = samples
+            .iter()
+            .find(|s| s.table_name == table.name && s.schema_name == table.schema);
+        for column in &table.columns {
+            if let Some(found) = detect_column(table, column, sample) {
+                protected.push(found);
+            }
+        }
+    }
+    protected
+}
+
+fn detect_column(
+    table: &Table,
+    column: &Column,
+    sample: Option<&TableSample>,
+) -> Option<ProtectedColumn> {
+    let from_values = sample.and_then(|sample| sampled_protection(sample, &column.name));
+    let from_expression = column
+        .default_value
+        .as_deref()
+        .into_iter()
+        .chain(column.generated.as_ref().map(|g| g.expression.as_str()))
+        .find_map(expression_protection);
+
+    let mut evidence = Vec::new();
+    let protection = match (from_values, from_expression) {
+        (None, None) => return None,
+        (Some((protection, matched, sampled)), expression) => {
+            evidence.push(ProtectionEvidence::SampledValues { matched, sampled });
+            if let Some((_, function)) = expression {
+                evidence.push(ProtectionEvidence::Expression { function });
+            }
+            protection
+        }
+        (None, Some((protection, function))) => {
+            evidence.push(ProtectionEvidence::Expression { function });
+            protection
+        }
+    };
+    Some(ProtectedColumn {
+        schema: table.schema.clone(),
+        table: table.name.clone(),
+        column: column.name.clone(),
+        protection,
+        evidence,
+    })
+}
+
+/// The protection shared by most sampled string values of a column, with
+/// the number of matching and sampled values.
+fn sampled_protection(sample: &TableSample, column: &str) -> Option<(Protection, usize, usize)> {
+    let values: Vec<&str> = sample
+        .rows
+        .iter()
+        .filter_map(|row| row.get(column)?.as_str())
+        .filter(|value| !value.is_empty())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut counts: Vec<(Protection, usize)> = Vec::new();
+    for protection in values.iter().filter_map(|value| value_protection(value)) {
+        match counts.iter_mut().find(|(p, _)| *p == protection) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((protection, 1)),
+        }
+    }
+    let enough = |matched: usize| matched as f64 >= values.len() as f64 * MIN_MATCH_RATIO;
+    let (protection, matched) = counts.iter().max_by_key(|(_, count)| *count)?.clone();
+    if enough(matched) {
+        return Some((protection, matched, values.len()));
+    }
+
+    // Columns migrating between password hash algorithms mix formats
+    let mut algorithms = Vec::new();
+    let mut hashed = 0;
+    for (protection, count) in &counts {
+        if let Protection::PasswordHash { algorithm } = protection {
+            algorithms.push(algorithm.as_str());
+            hashed += count;
+        }
+    }
+    enough(hashed).then(|| {
+        (
+            Protection::PasswordHash {
+                algorithm: algorithms.join("/"),
+            },
+            hashed,
+            values.len(),
+        )
+    })
+}
+
+/// Recognizes one value's format.
+fn value_protection(value: &str) -> Option<Protection> {
+    let password_hash = |algorithm: &str| {
+        Some(Protection::PasswordHash {
+            algorithm: algorithm.to_string(),
+        })
+    };
+    if value.len() == 60
+        && ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| value.starts_with(prefix))
+    {
+        return password_hash("bcrypt");
+    }
+    for (prefix, algorithm) in [
+        ("$argon2id$", "argon2id"),
+        ("$argon2i$", "argon2i"),
+        ("$argon2d$", "argon2d"),
+        ("$scrypt$", "scrypt"),
+        ("$7$", "scrypt"),
+        ("$6$", "SHA-512 crypt"),
+        ("$5$", "SHA-256 crypt"),
+        ("$1$", "MD5 crypt"),
+        ("pbkdf2_sha256$", "PBKDF2"),
+        ("$pbkdf2-sha256$", "PBKDF2"),
+        ("$pbkdf2-sha512$", "PBKDF2"),
+    ] {
+        if value.starts_with(prefix) {
+            return password_hash(algorithm);
+        }
+    }
+    if value.starts_with("-----BEGIN PGP MESSAGE-----") {
+        return Some(Protection::Encrypted {
+            scheme: Some("PGP".to_string()),
+        });
+    }
+
+    let hex = value.strip_prefix("\\x").unwrap_or(value);
+    if hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return digest_algorithm(hex.len() / 2).map(|algorithm| Protection::Digest {
+            algorithm: algorithm.to_string(),
+        });
+    }
+
+    if is_base64(value) {
+        let padding = value.chars().rev().take_while(|&c| c == '=').count();
+        let bytes = value.len() / 4 * 3 - padding;
+        return Some(match digest_algorithm(bytes) {
+            Some(algorithm) => Protection::Digest {
+                algorithm: algorithm.to_string(),
+            },
+            None => Protection::Encrypted { scheme: None },
+        });
+    }
+    None
+}
+
+/// Digest algorithm producing `bytes` bytes, by the most common choice.
+fn digest_algorithm(bytes: usize) -> Option<&'static str> {
+    match bytes {
+        16 => Some("MD5"),
+        20 => Some("SHA-1"),
+        32 => Some("SHA-256"),
+        64 => Some("SHA-512"),
+        _ => None,
+    }
+}
+
+/// Padded base64 that mixes letter cases with digits or symbols, so plain
+/// words and identifiers are not mistaken for encoded bytes.
+fn is_base64(value: &str) -> bool {
+    let body = value.trim_end_matches('=');
+    value.len() >= MIN_CIPHERTEXT_LENGTH
+        && value.len().is_multiple_of(4)
+        && value.len() - body.len() <= 2
+        && body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        && body.chars().any(|c| c.is_ascii_uppercase())
+        && body.chars().any(|c| c.is_ascii_lowercase())
+        && body
+            .chars()
+            .any(|c| c.is_ascii_digit() || c == '+' || c == '/')
+}
+
+/// Finds a protecting function call in an expression.
+fn expression_protection(expression: &str) -> Option<(Protection, String)> {
+    let lower = expression.to_ascii_lowercase();
+    PROTECTING_FUNCTIONS.iter().find_map(|(function, kind)| {
+        calls(&lower, function).then(|| {
+            let protection = match kind {
+                ProtectionKind::PasswordHash => Protection::PasswordHash {
+                    algorithm: "crypt".to_string(),
+                },
+                ProtectionKind::Digest => Protection::Digest {
+                    algorithm: digest_argument(&lower, function)
+                        .unwrap_or(function)
+                        .to_ascii_uppercase(),
+                },
+                ProtectionKind::Encrypted => Protection::Encrypted {
+                    scheme: Some(function.to_string()),
+                },
+            };
+            (protection, function.to_string())
+        })
+    })
+}
+
+/// Algorithm named by pgcrypto's `digest(data, 'type')` or
+/// `hmac(data, key, 'type')`: the last string literal inside the call.
+fn digest_argument<'e>(expression: &'e str, function: &str) -> Option<&'e str> {
+    if !matches!(function, "digest" | "hmac") {
+        return None;
+    }
+    let start = expression.find(function)? + function.len();
+    let arguments = &expression[start..];
+    let mut depth = 0usize;
+    let mut end = arguments.len();
+    for (index, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    end = index;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let arguments = &arguments[..end];
+    let close = arguments.rfind('\'')?;
+    let open = arguments[..close].rfind('\'')?;
+    Some(&arguments[open + 1..close]).filter(|name| !name.is_empty())
+}
+
+/// Whether `expression` calls `function`, as a whole identifier.
+fn calls(expression: &str, function: &str) -> bool {
+    expression.match_indices(function).any(|(start, _)| {
+        let before = expression[..start].chars().next_back();
+        let after = expression[start + function.len()..].trim_start();
+        !before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') && after.starts_with('(')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DatabaseInfo, SamplingStrategy, UnifiedDataType};
+
+    fn column(name: &str, default_value: Option<&str>) -> Column {
+        Column {
+            default_value: default_value.map(str::to_string),
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        }
+    }
+
+    fn fixture(rows: Vec<serde_json::Value>) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables.push(Table::new(
+            "users".to_string(),
+            Some("public".to_string()),
+            vec![
+                column("password", None),
+                column("email", None),
+                column("api_key_sha", None),
+                column(
+                    "ssn",
+                    Some("pgp_sym_encrypt('', current_setting('app.key'))"),
+                ),
+                column("card", None),
+            ],
+        ));
+        schema.samples = Some(vec![TableSample {
+            table_name: "users".to_string(),
+            schema_name: Some("public".to_string()),
+            sample_size: u32::try_from(rows.len()).unwrap_or_default(),
+            rows,
+            total_rows: None,
+            sampling_strategy: SamplingStrategy::Random { limit: 10 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }]);
+        schema
+    }
+
+    fn row(password: &str, email: &str, api_key: &str, card: &str) -> serde_json::Value {
+        serde_json::json!({
+            "password": password,
+            "email": email,
+            "api_key_sha": api_key,
+            "card": card,
+        })
+    }
+
+    #[test]
+    fn test_detects_hashes_digests_and_encryption() {
+        let bcrypt = format!("$2b$12${}", "a".repeat(53));
+        let schema = fixture(vec![
+            row(
+                &bcrypt,
+                "ada@example.com",
+                &"ab12".repeat(16),
+                "q83vEjRWeJq8Ze7vEjRWeJq8Ze7vEjRW",
+            ),
+            row(
+                "$argon2id$v=19$m=65536,t=3,p=4$c2FsdA$aGFzaA",
+                "bob@example.com",
+                &"cd34".repeat(16),
+                "Zm9vYmFyQmF6UXV4MTIzNDU2Nzg5MEFCQ0RFRkdISUpLTA==",
+            ),
+        ]);
+        let protected = detect_protected_columns(&schema);
+        let found: Vec<(&str, String)> = protected
+            .iter()
+            .map(|p| (p.column.as_str(), p.protection.label()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("password", "bcrypt/argon2id password hash".to_string()),
+                ("api_key_sha", "SHA-256 digest".to_string()),
+                ("ssn", "encrypted (pgp_sym_encrypt)".to_string()),
+                ("card", "encrypted".to_string()),
+            ]
+        );
+        assert_eq!(
+            protected[2].evidence,
+            vec![ProtectionEvidence::Expression {
+                function: "pgp_sym_encrypt".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_password_hashes_require_consensus() {
+        let bcrypt = format!("$2y$10${}", "b".repeat(53));
+        let schema = fixture(vec![row(&bcrypt, "", "", ""), row(&bcrypt, "", "", "")]);
+        let protected = detect_protected_columns(&schema);
+        assert_eq!(protected.len(), 2);
+        assert_eq!(
+            protected[0].protection,
+            Protection::PasswordHash {
+                algorithm: "bcrypt".to_string()
+            }
+        );
+        assert_eq!(protected[0].evidence[0].label(), "2 of 2 sampled values");
+
+        // Half plaintext: not protected
+        let schema = fixture(vec![row(&bcrypt, "", "", ""), row("hunter2", "", "", "")]);
+        assert_eq!(detect_protected_columns(&schema).len(), 1);
+    }
+
+    #[test]
+    fn test_plain_values_are_not_flagged() {
+        for value in [
+            "hello world",
+            "ThisIsJustAPlainSentenceOk",
+            "12345678",
+            "customer_reference_value",
+        ] {
+            assert_eq!(value_protection(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_expression_calls() {
+        assert!(calls("crypt('x', gen_salt('bf'))", "crypt"));
+        assert!(!calls("decrypt(data)", "crypt"));
+        assert!(!calls("md5_total", "md5"));
+        assert_eq!(
+            expression_protection("SHA2(CONCAT(salt, pin), 256)").map(|(p, _)| p.label()),
+            Some("SHA2 digest".to_string())
+        );
+        assert_eq!(
+            expression_protection("encode(digest(gen_random_bytes(16), 'sha256'), 'hex')")
+                .map(|(p, _)| p.label()),
+            Some("SHA256 digest".to_string())
+        );
+    }
+}
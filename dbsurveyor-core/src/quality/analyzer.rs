@@ -216,6 +216,7 @@ fn create_sample(table_name: &str, rows: Vec<serde_json::Value>) -> TableSample
             collected_at: chrono::Utc::now(),
             warnings: vec![],
             sample_status: None,
+            column_types: Vec::new(),
         }
     }
 
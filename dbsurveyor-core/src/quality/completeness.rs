@@ -89,6 +89,7 @@ fn create_sample(rows: Vec<serde_json::Value>) -> TableSample {
             collected_at: chrono::Utc::now(),
             warnings: vec![],
             sample_status: None,
+            column_types: Vec::new(),
         }
     }
 
@@ -0,0 +1,434 @@
+//! Redaction of collected data in diagnostic output.
+//!
+//! Credentials are kept out of `Debug` and `Display` by hand-written impls
+//! that omit them (see `ConnectionConfig` and the adapters). Collected data
+//! needs the same treatment: sampled rows and the enum values inferred from
+//! them, column and parameter defaults, and view, routine, and trigger
+//! bodies can all hold personal data or secrets, and a `{:?}` in a log
+//! line, panic message, or failed assertion would print them.
+//!
+//! Models carrying such strings implement [`Redact`] and take their `Debug`
+//! impl from it with [`redacted_debug!`](crate::redacted_debug). The
+//! output keeps the shape of the data (field names, identifiers, row
+//! counts, and text lengths) and replaces the contents with [`REDACTED`].
+
+use crate::enums::InferredEnum;
+use crate::models::{
+    Column, DatabaseSchema, DatabaseServerSchema, DefaultValue, Parameter, Procedure, TableSample,
+    TenantDatabase, Trigger, View,
+};
+use std::fmt;
+
+/// Written in place of a redacted value.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Formats a value with its sensitive contents replaced.
+///
+/// Implementors must not write sampled values, defaults, or object bodies,
+/// including through the `Debug` impls of their fields.
+pub trait Redact {
+    /// Writes a `Debug`-style representation of `self` with sensitive
+    /// strings replaced by [`Placeholder`]s.
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Wraps `self` for use with `{}` in log and error messages.
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+/// Implements `Debug` for models through their [`Redact`] impl.
+///
+/// A model using this cannot also derive `Debug`: the two impls conflict
+/// at compile time.
+#[macro_export]
+macro_rules! redacted_debug {
+    ($($model:ty),+ $(,)?) => {
+        $(
+            impl ::std::fmt::Debug for $model {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    $crate::redact::Redact::fmt_redacted(self, f)
+                }
+            }
+        )+
+    };
+}
+
+/// `Display` adapter returned by [`Redact::redacted`].
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<T: Redact + ?Sized> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_redacted(f)
+    }
+}
+
+/// `Debug` stand-in for a redacted value, noting its size.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder {
+    size: usize,
+    unit: &'static str,
+}
+
+impl Placeholder {
+    /// Stand-in for a string, noting its length in bytes.
+    pub fn text(text: &str) -> Self {
+        Self {
+            size: text.len(),
+            unit: "bytes",
+        }
+    }
+
+    /// Stand-in for an optional string.
+    pub fn optional_text(text: Option<&str>) -> Option<Self> {
+        text.map(Self::text)
+    }
+
+    /// Stand-in for sampled rows, noting how many there are.
+    pub fn rows(count: usize) -> Self {
+        Self {
+            size: count,
+            unit: "rows",
+        }
+    }
+
+    /// Stand-in for sampled values, noting how many there are.
+    pub fn values(count: usize) -> Self {
+        Self {
+            size: count,
+            unit: "values",
+        }
+    }
+}
+
+impl fmt::Debug for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} {})", REDACTED, self.size, self.unit)
+    }
+}
+
+redacted_debug!(
+    Column,
+    DefaultValue,
+    Parameter,
+    View,
+    Procedure,
+    Trigger,
+    TableSample,
+    InferredEnum,
+    DatabaseSchema,
+    TenantDatabase,
+    DatabaseServerSchema,
+);
+
+impl Redact for Column {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Column")
+            .field("name", &self.name)
+            .field("data_type", &self.data_type)
+            .field("is_nullable", &self.is_nullable)
+            .field("is_primary_key", &self.is_primary_key)
+            .field("is_auto_increment", &self.is_auto_increment)
+            .field(
+                "default_value",
+                &Placeholder::optional_text(self.default_value.as_deref()),
+            )
+            .field("normalized_default", &self.normalized_default)
+            .field("comment", &self.comment)
+            .field("ordinal_position", &self.ordinal_position)
+            .field("generated", &self.generated)
+            .field("declared_type", &self.declared_type)
+            .field("collation", &self.collation)
+            .field("character_set", &self.character_set)
+            .field("storage", &self.storage)
+            .field("identity", &self.identity)
+            .finish()
+    }
+}
+
+impl Redact for DefaultValue {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal { value } => f
+                .debug_struct("Literal")
+                .field("value", &Placeholder::text(value))
+                .finish(),
+            Self::Expression { expression } => f
+                .debug_struct("Expression")
+                .field("expression", &Placeholder::text(expression))
+                .finish(),
+            Self::SequenceNextval { sequence } => f
+                .debug_struct("SequenceNextval")
+                .field("sequence", sequence)
+                .finish(),
+            Self::CurrentTimestamp => f.write_str("CurrentTimestamp"),
+            Self::UuidGenerate => f.write_str("UuidGenerate"),
+        }
+    }
+}
+
+impl Redact for Parameter {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parameter")
+            .field("name", &self.name)
+            .field("data_type", &self.data_type)
+            .field("direction", &self.direction)
+            .field(
+                "default_value",
+                &Placeholder::optional_text(self.default_value.as_deref()),
+            )
+            .finish()
+    }
+}
+
+impl Redact for View {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("View")
+            .field("name", &self.name)
+            .field("schema", &self.schema)
+            .field(
+                "definition",
+                &Placeholder::optional_text(self.definition.as_deref()),
+            )
+            .field("columns", &self.columns)
+            .field("comment", &self.comment)
+            .finish()
+    }
+}
+
+impl Redact for Procedure {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Procedure")
+            .field("name", &self.name)
+            .field("schema", &self.schema)
+            .field(
+                "definition",
+                &Placeholder::optional_text(self.definition.as_deref()),
+            )
+            .field("parameters", &self.parameters)
+            .field("return_type", &self.return_type)
+            .field("language", &self.language)
+            .field("comment", &self.comment)
+            .finish()
+    }
+}
+
+impl Redact for Trigger {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trigger")
+            .field("name", &self.name)
+            .field("table_name", &self.table_name)
+            .field("schema", &self.schema)
+            .field("event", &self.event)
+            .field("timing", &self.timing)
+            .field(
+                "definition",
+                &Placeholder::optional_text(self.definition.as_deref()),
+            )
+            .finish()
+    }
+}
+
+impl Redact for TableSample {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableSample")
+            .field("table_name", &self.table_name)
+            .field("schema_name", &self.schema_name)
+            .field("rows", &Placeholder::rows(self.rows.len()))
+            .field("sample_size", &self.sample_size)
+            .field("total_rows", &self.total_rows)
+            .field("sampling_strategy", &self.sampling_strategy)
+            .field("collected_at", &self.collected_at)
+            .field("warnings", &self.warnings)
+            .field("sample_status", &self.sample_status)
+            .field("column_types", &self.column_types)
+            .finish()
+    }
+}
+
+impl Redact for InferredEnum {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InferredEnum")
+            .field("schema", &self.schema)
+            .field("table", &self.table)
+            .field("column", &self.column)
+            .field("values", &Placeholder::values(self.values.len()))
+            .field("sampled", &self.sampled)
+            .field("complete", &self.complete)
+            .finish()
+    }
+}
+
+// The schema containers hold no sensitive strings of their own; their
+// fields' `Debug` impls do the redacting.
+
+impl Redact for DatabaseSchema {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseSchema")
+            .field("format_version", &self.format_version)
+            .field("database_info", &self.database_info)
+            .field("tables", &self.tables)
+            .field("views", &self.views)
+            .field("indexes", &self.indexes)
+            .field("constraints", &self.constraints)
+            .field("procedures", &self.procedures)
+            .field("functions", &self.functions)
+            .field("triggers", &self.triggers)
+            .field("custom_types", &self.custom_types)
+            .field("samples", &self.samples)
+            .field("quality_metrics", &self.quality_metrics)
+            .field("collection_metadata", &self.collection_metadata)
+            .finish()
+    }
+}
+
+impl Redact for TenantDatabase {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantDatabase")
+            .field("database_info", &self.database_info)
+            .field("deviations", &self.deviations)
+            .field("samples", &self.samples)
+            .field("quality_metrics", &self.quality_metrics)
+            .field("collection_metadata", &self.collection_metadata)
+            .finish()
+    }
+}
+
+impl Redact for DatabaseServerSchema {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseServerSchema")
+            .field("format_version", &self.format_version)
+            .field("server_info", &self.server_info)
+            .field("manifest", &self.manifest)
+            .field("databases", &self.databases)
+            .field("tenant_groups", &self.tenant_groups)
+            .field("collection_metadata", &self.collection_metadata)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        DatabaseInfo, ParameterDirection, SamplingStrategy, TriggerEvent, TriggerTiming,
+        UnifiedDataType,
+    };
+
+    /// Models that can hold sampled rows. Each must format through
+    /// [`Redact`]; combined with [`redacted_debug!`], which conflicts with a
+    /// derived `Debug`, this fails to compile if one of them goes back to
+    /// `#[derive(Debug)]` or drops its `Redact` impl.
+    const _: fn() = || {
+        fn formats_redacted<T: Redact + fmt::Debug>() {}
+        formats_redacted::<TableSample>();
+        formats_redacted::<InferredEnum>();
+        formats_redacted::<DatabaseSchema>();
+        formats_redacted::<TenantDatabase>();
+        formats_redacted::<DatabaseServerSchema>();
+    };
+
+    const SECRET: &str = "hunter2-4111111111111111";
+
+    fn sample() -> TableSample {
+        TableSample {
+            table_name: "customers".to_string(),
+            schema_name: Some("public".to_string()),
+            rows: vec![serde_json::json!({ "email": "a@example.com", "card": SECRET })],
+            sample_size: 1,
+            total_rows: Some(10),
+            sampling_strategy: SamplingStrategy::Random { limit: 1 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_debug_omits_sampled_values() {
+        let sample = sample();
+        let output = format!("{:?}", sample);
+        assert!(!output.contains(SECRET), "{output}");
+        assert!(!output.contains("a@example.com"), "{output}");
+        assert!(output.contains("customers"), "{output}");
+        assert!(output.contains("[REDACTED] (1 rows)"), "{output}");
+        assert!(!sample.redacted().to_string().contains(SECRET));
+
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.samples = Some(vec![sample]);
+        let output = format!("{:#?}", schema);
+        assert!(!output.contains(SECRET), "{output}");
+        assert!(output.contains("shop"), "{output}");
+
+        let inferred = InferredEnum {
+            schema: None,
+            table: "customers".to_string(),
+            column: "card".to_string(),
+            values: vec![serde_json::json!(SECRET)],
+            sampled: 20,
+            complete: false,
+        };
+        let output = format!("{:?}", inferred);
+        assert!(!output.contains(SECRET), "{output}");
+        assert!(output.contains("[REDACTED] (1 values)"), "{output}");
+    }
+
+    #[test]
+    fn test_debug_omits_defaults_and_bodies() {
+        let mut column = Column {
+            default_value: Some(format!("'{}'", SECRET)),
+            normalized_default: Some(DefaultValue::Literal {
+                value: format!("'{}'", SECRET),
+            }),
+            ..Column::new(
+                "token".to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        };
+        let output = format!("{:?}", column);
+        assert!(!output.contains(SECRET), "{output}");
+        assert!(output.contains("token"), "{output}");
+        column.default_value = None;
+        assert!(format!("{:?}", column).contains("default_value: None"));
+
+        let body = Some(format!("SELECT '{}'", SECRET));
+        let view = View {
+            name: "v".to_string(),
+            schema: None,
+            definition: body.clone(),
+            columns: Vec::new(),
+            comment: None,
+        };
+        let procedure = Procedure {
+            name: "p".to_string(),
+            schema: None,
+            definition: body.clone(),
+            parameters: vec![Parameter {
+                name: "key".to_string(),
+                data_type: UnifiedDataType::String { max_length: None },
+                direction: ParameterDirection::In,
+                default_value: body.clone(),
+            }],
+            return_type: None,
+            language: None,
+            comment: None,
+        };
+        let trigger = Trigger {
+            name: "t".to_string(),
+            table_name: "orders".to_string(),
+            schema: None,
+            event: TriggerEvent::Insert,
+            timing: TriggerTiming::After,
+            definition: body,
+        };
+        for output in [
+            format!("{:?}", view),
+            format!("{:?}", procedure),
+            format!("{:?}", trigger),
+        ] {
+            assert!(!output.contains(SECRET), "{output}");
+            assert!(output.contains("[REDACTED] ("), "{output}");
+        }
+    }
+}
@@ -0,0 +1,500 @@
+//! Inference of relationships that are not declared as foreign keys.
+//!
+//! Many databases rely on application-enforced references, so declared
+//! foreign keys understate how tables relate. [`infer_relationships`]
+//! proposes candidate references from column naming conventions
+//! (`customer_id` -> `customers`), compatible data types, and, when samples
+//! were collected, the overlap between sampled values.
+//!
+//! Inferred relationships are hints, not constraints: they carry a
+//! confidence and must be presented separately from declared foreign keys.
+//!
+//! [`cardinality`] classifies both declared and inferred references as
+//! one-to-one or many-to-one from the uniqueness of the referencing columns.
+//!
+//! # Security Guarantees
+//! - Only overlap ratios are reported, never the sampled values themselves
+
+use crate::models::{Column, ConstraintType, DatabaseSchema, Table, TableSample, UnifiedDataType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// Confidence from a naming match with compatible types, before samples
+const BASE_CONFIDENCE: f64 = 0.7;
+/// Confidence from naming and types when samples share no values
+const SAMPLED_BASE_CONFIDENCE: f64 = 0.6;
+
+/// A proposed reference from one column to another table's key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferredRelationship {
+    pub schema: Option<String>,
+    pub table: String,
+    pub column: String,
+    pub referenced_schema: Option<String>,
+    pub referenced_table: String,
+    pub referenced_column: String,
+    /// Likelihood that the relationship is real, from 0.0 to 1.0
+    pub confidence: f64,
+    pub evidence: Vec<RelationshipEvidence>,
+    #[serde(default)]
+    pub cardinality: Cardinality,
+}
+
+/// How many child rows may reference one parent row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cardinality {
+    #[default]
+    ManyToOne,
+    OneToOne,
+}
+
+impl Cardinality {
+    /// Short notation, e.g. `N:1`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ManyToOne => "N:1",
+            Self::OneToOne => "1:1",
+        }
+    }
+}
+
+/// Signal that contributed to an inferred relationship.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RelationshipEvidence {
+    /// Column name refers to the referenced table (e.g. `customer_id`)
+    NamingConvention,
+    /// Column and referenced key have compatible data types
+    TypeMatch,
+    /// Fraction of distinct sampled values found in the referenced key's samples
+    ValueOverlap { ratio: f64, sampled_values: usize },
+}
+
+impl RelationshipEvidence {
+    /// Short human-readable description.
+    pub fn label(&self) -> String {
+        match self {
+            Self::NamingConvention => "naming".to_string(),
+            Self::TypeMatch => "types".to_string(),
+            Self::ValueOverlap {
+                ratio,
+                sampled_values,
+            } => format!("{:.0}% of {} sampled values", ratio * 100.0, sampled_values),
+        }
+    }
+}
+
+/// Proposes relationships that are not covered by declared foreign keys.
+///
+/// Results are sorted by descending confidence.
+pub fn infer_relationships(schema: &DatabaseSchema) -> Vec<InferredRelationship> {
+    let samples = schema.samples.as_deref().unwrap_or_default();
+    let mut inferred = Vec::new();
+
+    for table in &schema.tables {
+        let declared: HashSet<&str> = table
+            .foreign_keys
+            .iter()
+            .flat_map(|fk| fk.columns.iter().map(String::as_str))
+            .collect();
+
+        for column in &table.columns {
+            if declared.contains(column.name.as_str()) {
+                continue;
+            }
+            let Some(stem) = reference_stem(&column.name) else {
+                continue;
+            };
+            let Some((target, key)) = find_target(schema, table, &stem) else {
+                continue;
+            };
+            if std::ptr::eq(target, table) && key.name == column.name {
+                continue;
+            }
+            if !types_compatible(&column.data_type, &key.data_type) {
+                continue;
+            }
+
+            let mut evidence = vec![
+                RelationshipEvidence::NamingConvention,
+                RelationshipEvidence::TypeMatch,
+            ];
+            let mut confidence = BASE_CONFIDENCE;
+            if let Some((ratio, sampled_values)) =
+                value_overlap(samples, table, &column.name, target, &key.name)
+            {
+                confidence = SAMPLED_BASE_CONFIDENCE + (1.0 - SAMPLED_BASE_CONFIDENCE) * ratio;
+                evidence.push(RelationshipEvidence::ValueOverlap {
+                    ratio,
+                    sampled_values,
+                });
+            }
+
+            inferred.push(InferredRelationship {
+                schema: table.schema.clone(),
+                table: table.name.clone(),
+                column: column.name.clone(),
+                referenced_schema: target.schema.clone(),
+                referenced_table: target.name.clone(),
+                referenced_column: key.name.clone(),
+                confidence,
+                evidence,
+                cardinality: cardinality(table, std::slice::from_ref(&column.name)),
+            });
+        }
+    }
+
+    inferred.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    inferred
+}
+
+/// Cardinality of a reference made by `columns` of `table`.
+///
+/// The reference is one-to-one when the columns include the primary key, a
+/// unique constraint, or a unique index: no two child rows can then hold the
+/// same key, so each parent row has at most one child.
+pub fn cardinality(table: &Table, columns: &[String]) -> Cardinality {
+    let key: BTreeSet<&str> = columns.iter().map(String::as_str).collect();
+    let covers = |unique: &mut dyn Iterator<Item = &str>| {
+        let mut unique = unique.peekable();
+        unique.peek().is_some() && unique.all(|column| key.contains(column))
+    };
+
+    let primary = table
+        .primary_key
+        .as_ref()
+        .is_some_and(|pk| covers(&mut pk.columns.iter().map(String::as_str)));
+    let unique_constraint = table.constraints.iter().any(|constraint| {
+        constraint.constraint_type == ConstraintType::Unique
+            && covers(&mut constraint.columns.iter().map(String::as_str))
+    });
+    let unique_index = table
+        .indexes
+        .iter()
+        .any(|index| index.is_unique && covers(&mut index.columns.iter().map(|c| c.name.as_str())));
+
+    if primary || unique_constraint || unique_index {
+        Cardinality::OneToOne
+    } else {
+        Cardinality::ManyToOne
+    }
+}
+
+/// Extracts the referenced entity name from `customer_id` or `customerId`.
+fn reference_stem(column: &str) -> Option<String> {
+    let stem = if let Some(stem) = column.strip_suffix("Id") {
+        stem.ends_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+            .then_some(stem)?
+    } else {
+        let lower = column.to_ascii_lowercase();
+        if !lower.ends_with("_id") {
+            return None;
+        }
+        &column[..column.len().saturating_sub(3)]
+    };
+    let stem = stem.trim_end_matches('_');
+    (!stem.is_empty()).then(|| stem.to_ascii_lowercase())
+}
+
+/// Finds the table named after `stem` and its single-column key, preferring
+/// tables in the same schema as `source`.
+fn find_target<'a>(
+    schema: &'a DatabaseSchema,
+    source: &Table,
+    stem: &str,
+) -> Option<(&'a Table, &'a Column)> {
+    let mut candidates: Vec<&Table> = schema
+        .tables
+        .iter()
+        .filter(|t| table_matches_stem(&t.name, stem))
+        .collect();
+    candidates.sort_by_key(|t| t.schema != source.schema);
+    candidates
+        .into_iter()
+        .find_map(|t| key_column(t).map(|key| (t, key)))
+}
+
+/// Accepts the stem itself and its common English plurals.
+fn table_matches_stem(table: &str, stem: &str) -> bool {
+    let table = table.to_ascii_lowercase();
+    if table == stem
+        || table.strip_suffix('s') == Some(stem)
+        || table.strip_suffix("es") == Some(stem)
+    {
+        return true;
+    }
+    match (table.strip_suffix("ies"), stem.strip_suffix('y')) {
+        (Some(table_base), Some(stem_base)) => table_base == stem_base,
+        _ => false,
+    }
+}
+
+/// The column a reference would point at: a single-column primary key, or
+/// a column named `id`.
+fn key_column(table: &Table) -> Option<&Column> {
+    let key_name = match &table.primary_key {
+        Some(pk) if pk.columns.len() == 1 => pk.columns.first().map(String::as_str),
+        Some(_) => None,
+        None => table
+            .columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case("id") || c.name == "_id")
+            .map(|c| c.name.as_str()),
+    }?;
+    table.columns.iter().find(|c| c.name == key_name)
+}
+
+fn types_compatible(a: &UnifiedDataType, b: &UnifiedDataType) -> bool {
+    match (a, b) {
+        (UnifiedDataType::Integer { .. }, UnifiedDataType::Integer { .. })
+        | (UnifiedDataType::String { .. }, UnifiedDataType::String { .. }) => true,
+        _ => a == b,
+    }
+}
+
+/// Fraction of the distinct non-null values sampled from the source column
+/// that also appear in the referenced column's sample.
+///
+/// Returns `None` when either table has no sampled values for the column.
+fn value_overlap(
+    samples: &[TableSample],
+    source: &Table,
+    column: &str,
+    target: &Table,
+    key: &str,
+) -> Option<(f64, usize)> {
+    let source_values = sampled_values(samples, source, column)?;
+    let target_values = sampled_values(samples, target, key)?;
+    let matched = source_values.intersection(&target_values).count();
+    let ratio = matched as f64 / source_values.len() as f64;
+    Some((ratio, source_values.len()))
+}
+
+fn sampled_values(samples: &[TableSample], table: &Table, column: &str) -> Option<HashSet<String>> {
+    let sample = samples
+        .iter()
+        .find(|s| s.table_name == table.name && s.schema_name == table.schema)?;
+    let values: HashSet<String> = sample
+        .rows
+        .iter()
+        .filter_map(|row| row.get(column))
+        .filter(|value| !value.is_null())
+        .map(|value| match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect();
+    (!values.is_empty()).then_some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Constraint, DatabaseInfo, ForeignKey, Index, IndexColumn, PrimaryKey, SamplingStrategy,
+        TableSample,
+    };
+    use serde_json::json;
+
+    fn column(name: &str, data_type: UnifiedDataType) -> Column {
+        Column::new(name.to_string(), data_type)
+    }
+
+    fn int() -> UnifiedDataType {
+        UnifiedDataType::Integer {
+            bits: 32,
+            signed: true,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<Column>, primary_key: Option<&str>) -> Table {
+        Table {
+            primary_key: primary_key.map(|pk| PrimaryKey {
+                name: None,
+                columns: vec![pk.to_string()],
+            }),
+            ..Table::new(name.to_string(), Some("public".to_string()), columns)
+        }
+    }
+
+    fn sample(table: &str, rows: Vec<serde_json::Value>) -> TableSample {
+        TableSample {
+            table_name: table.to_string(),
+            schema_name: Some("public".to_string()),
+            sample_size: rows.len() as u32,
+            rows,
+            total_rows: None,
+            sampling_strategy: SamplingStrategy::Random { limit: 10 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![
+            table("customers", vec![column("id", int())], Some("id")),
+            table(
+                "categories",
+                vec![column("category_id", int())],
+                Some("category_id"),
+            ),
+            table(
+                "orders",
+                vec![
+                    column("id", int()),
+                    column("customerId", int()),
+                    column("category_id", UnifiedDataType::Uuid),
+                    column("paid", UnifiedDataType::Boolean),
+                ],
+                Some("id"),
+            ),
+        ];
+        schema
+    }
+
+    #[test]
+    fn test_reference_stem() {
+        assert_eq!(reference_stem("customer_id").as_deref(), Some("customer"));
+        assert_eq!(reference_stem("CUSTOMER_ID").as_deref(), Some("customer"));
+        assert_eq!(reference_stem("customerId").as_deref(), Some("customer"));
+        assert_eq!(reference_stem("id"), None);
+        assert_eq!(reference_stem("paid"), None);
+        assert_eq!(reference_stem("ID"), None);
+    }
+
+    #[test]
+    fn test_table_matches_stem() {
+        assert!(table_matches_stem("Customers", "customer"));
+        assert!(table_matches_stem("addresses", "address"));
+        assert!(table_matches_stem("categories", "category"));
+        assert!(table_matches_stem("person", "person"));
+        assert!(!table_matches_stem("customer_notes", "customer"));
+    }
+
+    #[test]
+    fn test_infers_from_naming_and_types() {
+        let inferred = infer_relationships(&fixture());
+
+        // category_id is a UUID but categories.category_id is an integer
+        assert_eq!(inferred.len(), 1);
+        let relationship = &inferred[0];
+        assert_eq!(relationship.table, "orders");
+        assert_eq!(relationship.column, "customerId");
+        assert_eq!(relationship.referenced_table, "customers");
+        assert_eq!(relationship.referenced_column, "id");
+        assert!((relationship.confidence - BASE_CONFIDENCE).abs() < f64::EPSILON);
+        assert_eq!(relationship.cardinality, Cardinality::ManyToOne);
+    }
+
+    #[test]
+    fn test_cardinality_from_unique_keys() {
+        let names = |columns: &[&str]| columns.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let mut profiles = table(
+            "profiles",
+            vec![
+                column("id", int()),
+                column("user_id", int()),
+                column("tenant_id", int()),
+            ],
+            Some("id"),
+        );
+
+        assert_eq!(
+            cardinality(&profiles, &names(&["id"])),
+            Cardinality::OneToOne
+        );
+        assert_eq!(
+            cardinality(&profiles, &names(&["user_id"])),
+            Cardinality::ManyToOne
+        );
+
+        profiles.indexes.push(Index {
+            name: "profiles_user_id_key".to_string(),
+            table_name: "profiles".to_string(),
+            schema: None,
+            columns: vec![IndexColumn {
+                name: "user_id".to_string(),
+                sort_order: None,
+            }],
+            is_unique: true,
+            is_primary: false,
+            index_type: None,
+            options: None,
+        });
+        assert_eq!(
+            cardinality(&profiles, &names(&["user_id"])),
+            Cardinality::OneToOne
+        );
+        // A composite key containing a unique column is unique as well
+        assert_eq!(
+            cardinality(&profiles, &names(&["tenant_id", "user_id"])),
+            Cardinality::OneToOne
+        );
+
+        profiles.indexes.clear();
+        profiles.constraints.push(Constraint {
+            name: "profiles_tenant_user_key".to_string(),
+            table_name: "profiles".to_string(),
+            schema: None,
+            constraint_type: ConstraintType::Unique,
+            columns: names(&["tenant_id", "user_id"]),
+            check_clause: None,
+        });
+        assert_eq!(
+            cardinality(&profiles, &names(&["user_id"])),
+            Cardinality::ManyToOne
+        );
+        assert_eq!(
+            cardinality(&profiles, &names(&["user_id", "tenant_id"])),
+            Cardinality::OneToOne
+        );
+    }
+
+    #[test]
+    fn test_skips_declared_foreign_keys_and_own_key() {
+        let mut schema = fixture();
+        schema.tables[2].foreign_keys.push(ForeignKey {
+            name: None,
+            columns: vec!["customerId".to_string()],
+            referenced_table: "customers".to_string(),
+            referenced_schema: None,
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+
+        // categories.category_id is the table's own key, not a reference
+        assert!(infer_relationships(&schema).is_empty());
+    }
+
+    #[test]
+    fn test_value_overlap_adjusts_confidence() {
+        let mut schema = fixture();
+        schema.samples = Some(vec![
+            sample("customers", vec![json!({"id": 1}), json!({"id": 2})]),
+            sample(
+                "orders",
+                vec![
+                    json!({"customerId": 1}),
+                    json!({"customerId": 3}),
+                    json!({"customerId": null}),
+                ],
+            ),
+        ]);
+
+        let inferred = infer_relationships(&schema);
+        assert_eq!(inferred.len(), 1);
+        assert!((inferred[0].confidence - 0.8).abs() < 1e-9);
+        assert_eq!(
+            inferred[0].evidence.last(),
+            Some(&RelationshipEvidence::ValueOverlap {
+                ratio: 0.5,
+                sampled_values: 2
+            })
+        );
+    }
+}
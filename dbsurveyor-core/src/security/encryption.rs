@@ -1,11 +1,19 @@
-//! AES-GCM encryption with Argon2id key derivation.
+//! AES-GCM encryption with Argon2id or PBKDF2 key derivation.
 //!
 //! This module provides secure encryption and decryption using AES-GCM-256
-//! with random 96-bit nonces and Argon2id key derivation function.
+//! with random 96-bit nonces and a password-based key derivation function.
+//!
+//! # Crypto Providers
+//! Default builds use the pure-Rust `aes-gcm` and `argon2` crates. Building
+//! with the `fips` feature routes every primitive (AES-GCM, key derivation,
+//! random generation) through the AWS-LC FIPS 140-3 validated module and
+//! switches key derivation to PBKDF2-HMAC-SHA256, since Argon2id is not a
+//! FIPS-approved algorithm. The provider is recorded in every
+//! [`EncryptedData`] header.
 //!
 //! # Security Guarantees
 //! - AES-GCM-256 authenticated encryption with random nonces
-//! - Argon2id key derivation with secure parameters
+//! - Argon2id (or PBKDF2 under `fips`) key derivation with secure parameters
 //! - No nonce reuse (each encryption uses a fresh random nonce)
 //! - Authenticated headers prevent tampering
 //! - Memory-safe key handling with automatic zeroing
@@ -13,17 +21,21 @@
 //! # Cryptographic Standards
 //! - AES-GCM: NIST SP 800-38D
 //! - Argon2id: RFC 9106
+//! - PBKDF2: NIST SP 800-132
 //! - Key size: 256 bits (NSA CNSSP-15)
 
+#[cfg(not(feature = "fips"))]
 use aes_gcm::{
     Aes256Gcm, Key, Nonce,
-    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+    aead::{Aead, KeyInit, OsRng, rand_core::RngCore},
 };
+#[cfg(not(feature = "fips"))]
 use argon2::{
     Argon2, Params, Version,
     password_hash::{PasswordHasher, SaltString},
 };
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use zeroize::Zeroizing;
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -105,16 +117,104 @@
 /// **Standard:** RFC 9106 §4, recommended for multi-core systems
 const ARGON2_PARALLELISM: u32 = 4;
 
-/// Key derivation parameters for Argon2id.
+/// PBKDF2 salt size: 128 bits (16 bytes)
+///
+/// **Rationale:** SP 800-132 requires at least 128 bits of salt generated
+/// by an approved random bit generator, matching the Argon2id salt size.
+///
+/// **Standard:** NIST SP 800-132 §5.1
+const PBKDF2_SALT_SIZE: usize = 16;
+
+/// PBKDF2-HMAC-SHA256 iteration count: 600,000
+///
+/// **Rationale:** PBKDF2 has no memory hardness, so the iteration count is
+/// its only defense against GPU cracking. SP 800-132 sets a floor of 1,000
+/// but asks for "as large as feasible"; OWASP's 2023 guidance of 600,000
+/// for HMAC-SHA256 costs roughly the same wall time as the Argon2id
+/// parameters above.
 ///
-/// Uses secure defaults as specified in requirements:
+/// **Standard:** NIST SP 800-132 §5.2, OWASP Password Storage Cheat Sheet
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Cryptographic library that produced an encrypted file.
+///
+/// Recorded in the [`EncryptedData`] header so auditors can confirm which
+/// module handled the data. Files written before the provider was recorded
+/// deserialize as [`CryptoProvider::RustCrypto`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CryptoProvider {
+    /// Pure-Rust `aes-gcm` and `argon2` crates (default build)
+    #[default]
+    RustCrypto,
+    /// AWS-LC FIPS 140-3 validated module (`fips` feature)
+    AwsLcFips,
+}
+
+impl CryptoProvider {
+    /// Returns the provider compiled into this build.
+    pub const fn active() -> Self {
+        if cfg!(feature = "fips") {
+            Self::AwsLcFips
+        } else {
+            Self::RustCrypto
+        }
+    }
+
+    /// Returns true if the provider is a FIPS-validated module.
+    pub const fn is_fips_validated(self) -> bool {
+        matches!(self, Self::AwsLcFips)
+    }
+}
+
+impl fmt::Display for CryptoProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RustCrypto => write!(f, "rust-crypto"),
+            Self::AwsLcFips => write!(f, "aws-lc-fips"),
+        }
+    }
+}
+
+/// Password-based key derivation function used for an encrypted file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KdfAlgorithm {
+    /// Argon2id v1.3 (default build)
+    #[default]
+    Argon2id,
+    /// PBKDF2-HMAC-SHA256 (`fips` build)
+    Pbkdf2Sha256,
+}
+
+impl fmt::Display for KdfAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Argon2id => write!(f, "argon2id"),
+            Self::Pbkdf2Sha256 => write!(f, "pbkdf2-sha256"),
+        }
+    }
+}
+
+/// Key derivation parameters.
+///
+/// Default builds use Argon2id with the secure defaults specified in
+/// requirements:
 /// - 16-byte salt
 /// - Version 1.3 (Argon2id)
 /// - Time cost: 3 iterations
 /// - Memory: 64 MiB (65536 KiB)
 /// - Parallelism: 4 threads
+///
+/// `fips` builds use PBKDF2-HMAC-SHA256 with a 16-byte salt and 600,000
+/// iterations (stored in `time_cost`); the memory and parallelism fields
+/// are unused.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KdfParams {
+    /// Key derivation function (absent in files written before PBKDF2
+    /// support, which were always Argon2id)
+    #[serde(default)]
+    pub(crate) algorithm: KdfAlgorithm,
     /// Random salt (16 bytes as per requirements)
     pub(crate) salt: Vec<u8>,
     /// Memory cost in KiB (64 MiB = 65536 KiB)
@@ -130,6 +230,7 @@ pub struct KdfParams {
 impl Default for KdfParams {
     fn default() -> Self {
         Self {
+            algorithm: KdfAlgorithm::Argon2id,
             salt: Vec::new(), // Will be filled with random data
             memory_cost: ARGON2_MEMORY_COST,
             time_cost: ARGON2_TIME_COST,
@@ -140,17 +241,45 @@ fn default() -> Self {
 }
 
 impl KdfParams {
-    /// Creates new KDF parameters with a random 16-byte salt.
-    pub fn new() -> Self {
-        let mut salt = vec![0u8; ARGON2_SALT_SIZE];
-        OsRng.fill_bytes(&mut salt);
+    /// Creates KDF parameters with a random 16-byte salt for the key
+    /// derivation function of the active [`CryptoProvider`].
+    ///
+    /// # Errors
+    /// Returns error if the random number generator fails
+    pub fn new() -> crate::Result<Self> {
+        if CryptoProvider::active().is_fips_validated() {
+            Self::for_algorithm(KdfAlgorithm::Pbkdf2Sha256)
+        } else {
+            Self::for_algorithm(KdfAlgorithm::Argon2id)
+        }
+    }
 
-        Self {
-            salt,
-            memory_cost: ARGON2_MEMORY_COST,
-            time_cost: ARGON2_TIME_COST,
-            parallelism: ARGON2_PARALLELISM,
-            version: "1.3".to_string(),
+    /// Creates KDF parameters with a random 16-byte salt for `algorithm`.
+    ///
+    /// # Errors
+    /// Returns error if the random number generator fails
+    pub fn for_algorithm(algorithm: KdfAlgorithm) -> crate::Result<Self> {
+        match algorithm {
+            KdfAlgorithm::Argon2id => {
+                let mut salt = vec![0u8; ARGON2_SALT_SIZE];
+                fill_random(&mut salt)?;
+                Ok(Self {
+                    salt,
+                    ..Self::default()
+                })
+            }
+            KdfAlgorithm::Pbkdf2Sha256 => {
+                let mut salt = vec![0u8; PBKDF2_SALT_SIZE];
+                fill_random(&mut salt)?;
+                Ok(Self {
+                    algorithm,
+                    salt,
+                    memory_cost: 0,
+                    time_cost: PBKDF2_ITERATIONS,
+                    parallelism: 1,
+                    version: String::new(),
+                })
+            }
         }
     }
 
@@ -159,6 +288,13 @@ pub fn new() -> Self {
     /// # Errors
     /// Returns error if parameters don't meet minimum security thresholds
     pub fn validate(&self) -> crate::Result<()> {
+        match self.algorithm {
+            KdfAlgorithm::Argon2id => self.validate_argon2id(),
+            KdfAlgorithm::Pbkdf2Sha256 => self.validate_pbkdf2(),
+        }
+    }
+
+    fn validate_argon2id(&self) -> crate::Result<()> {
         if self.salt.len() < ARGON2_SALT_SIZE {
             return Err(crate::error::DbSurveyorError::encryption_error(format!(
                 "Salt must be at least {} bytes",
@@ -185,6 +321,27 @@ pub fn validate(&self) -> crate::Result<()> {
         Ok(())
     }
 
+    fn validate_pbkdf2(&self) -> crate::Result<()> {
+        if self.salt.len() < PBKDF2_SALT_SIZE {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "Salt must be at least {} bytes",
+                PBKDF2_SALT_SIZE
+            )));
+        }
+        if self.time_cost < PBKDF2_ITERATIONS {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "PBKDF2 iteration count must be at least {}",
+                PBKDF2_ITERATIONS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the key derivation function.
+    pub fn algorithm(&self) -> KdfAlgorithm {
+        self.algorithm
+    }
+
     /// Returns the salt bytes.
     pub fn salt(&self) -> &[u8] {
         &self.salt
@@ -195,7 +352,7 @@ pub fn memory_cost(&self) -> u32 {
         self.memory_cost
     }
 
-    /// Returns the time cost (number of iterations).
+    /// Returns the time cost (Argon2id passes or PBKDF2 iterations).
     pub fn time_cost(&self) -> u32 {
         self.time_cost
     }
@@ -211,26 +368,63 @@ pub fn version(&self) -> &str {
     }
 }
 
-/// Encrypted data container with AES-GCM and embedded KDF parameters.
+/// Label given to the recipient of a file encrypted with a single password.
+pub const DEFAULT_RECIPIENT: &str = "default";
+
+/// A password able to open an [`EncryptedData`] envelope.
+///
+/// Holds the random data key encrypted ("wrapped") with AES-GCM-256 under a
+/// key derived from the recipient's password. Labels are stored in clear
+/// text and only identify the recipient for `rekey`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recipient {
+    /// Name used to remove or rotate this recipient
+    pub label: String,
+    /// Random 96-bit (12-byte) nonce for the key wrap
+    pub nonce: Vec<u8>,
+    /// Encrypted 32-byte data key
+    pub wrapped_key: Vec<u8>,
+    /// Authentication tag of the key wrap (16 bytes)
+    pub auth_tag: Vec<u8>,
+    /// Key derivation parameters for this recipient's password
+    pub kdf_params: KdfParams,
+}
+
+/// Encrypted data container using envelope encryption.
+///
+/// The payload is encrypted once with a random 256-bit data key, and the
+/// data key is wrapped separately for each [`Recipient`], so passwords can be
+/// added, removed, or rotated without re-encrypting the payload.
 ///
 /// Contains all information needed for decryption including:
-/// - Algorithm identifier
+/// - Algorithm identifier and crypto provider
 /// - Random 96-bit nonce
 /// - Encrypted ciphertext
 /// - Authentication tag (separate from ciphertext)
-/// - KDF parameters and salt for key derivation
+/// - Wrapped data keys with their KDF parameters and salts
+///
+/// Files written before envelope encryption have no recipients; their
+/// payload key is derived directly from the password using `kdf_params`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedData {
     /// Encryption algorithm (always "AES-GCM-256")
     pub algorithm: String,
+    /// Library that encrypted the payload
+    #[serde(default)]
+    pub provider: CryptoProvider,
     /// Random 96-bit (12-byte) nonce
     pub nonce: Vec<u8>,
     /// Encrypted payload
     pub ciphertext: Vec<u8>,
     /// Authentication tag (16 bytes)
     pub auth_tag: Vec<u8>,
-    /// Key derivation parameters
-    pub kdf_params: KdfParams,
+    /// Recipients holding a wrapped copy of the data key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<Recipient>,
+    /// Key derivation parameters of a single-password file written before
+    /// envelope encryption
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf_params: Option<KdfParams>,
 }
 
 /// Validates encrypted data structure before decryption.
@@ -249,36 +443,63 @@ fn validate_encrypted_data(encrypted: &EncryptedData) -> crate::Result<()> {
         )));
     }
 
+    validate_aead_fields(&encrypted.nonce, &encrypted.auth_tag)?;
+
+    // Validate key sources
+    if encrypted.recipients.is_empty() && encrypted.kdf_params.is_none() {
+        return Err(crate::error::DbSurveyorError::encryption_error(
+            "Encrypted data has no recipients",
+        ));
+    }
+    for recipient in &encrypted.recipients {
+        validate_aead_fields(&recipient.nonce, &recipient.auth_tag)?;
+        if recipient.wrapped_key.len() != AES_KEY_SIZE {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "Invalid wrapped key length for recipient '{}': expected {}, got {}",
+                recipient.label,
+                AES_KEY_SIZE,
+                recipient.wrapped_key.len()
+            )));
+        }
+        recipient.kdf_params.validate()?;
+    }
+    if let Some(kdf_params) = &encrypted.kdf_params {
+        kdf_params.validate()?;
+    }
+
+    Ok(())
+}
+
+/// Checks the nonce and authentication tag lengths of one AES-GCM message.
+fn validate_aead_fields(nonce: &[u8], auth_tag: &[u8]) -> crate::Result<()> {
     // Validate nonce length
-    if encrypted.nonce.len() != AES_GCM_NONCE_SIZE {
+    if nonce.len() != AES_GCM_NONCE_SIZE {
         return Err(crate::error::DbSurveyorError::encryption_error(format!(
             "Invalid nonce length: expected {}, got {}",
             AES_GCM_NONCE_SIZE,
-            encrypted.nonce.len()
+            nonce.len()
         )));
     }
 
     // Validate auth tag length
-    if encrypted.auth_tag.len() != AES_GCM_TAG_SIZE {
+    if auth_tag.len() != AES_GCM_TAG_SIZE {
         return Err(crate::error::DbSurveyorError::encryption_error(format!(
             "Invalid authentication tag length: expected {}, got {}",
             AES_GCM_TAG_SIZE,
-            encrypted.auth_tag.len()
+            auth_tag.len()
         )));
     }
 
-    // Validate KDF parameters
-    encrypted.kdf_params.validate()?;
-
     Ok(())
 }
 
-/// Derives an AES-256 key from a password using Argon2id.
+/// Derives an AES-256 key from a password using the KDF named in the
+/// parameters.
 ///
 /// # Security
-/// - Uses Argon2id with secure parameters
 /// - Key material is automatically zeroed on drop
 /// - Salt and parameters are embedded for verification
+/// - `fips` builds refuse Argon2id, which is not FIPS-approved
 ///
 /// # Arguments
 /// * `password` - Password for key derivation
@@ -290,6 +511,41 @@ fn validate_encrypted_data(encrypted: &EncryptedData) -> crate::Result<()> {
     // Validate parameters first
     kdf_params.validate()?;
 
+    match kdf_params.algorithm {
+        KdfAlgorithm::Argon2id => derive_argon2id(password, kdf_params),
+        KdfAlgorithm::Pbkdf2Sha256 => derive_pbkdf2(password, kdf_params),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PROVIDER PRIMITIVES
+//
+// Each primitive has one implementation per provider. The `fips` variants
+// call only into the AWS-LC FIPS module so that no non-validated code
+// touches key material or plaintext.
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Fills `buf` with bytes from the operating system CSPRNG.
+#[cfg(not(feature = "fips"))]
+fn fill_random(buf: &mut [u8]) -> crate::Result<()> {
+    OsRng.try_fill_bytes(buf).map_err(|e| {
+        crate::error::DbSurveyorError::encryption_error(format!(
+            "Random number generation failed: {}",
+            e
+        ))
+    })
+}
+
+/// Fills `buf` with bytes from the AWS-LC approved DRBG.
+#[cfg(feature = "fips")]
+fn fill_random(buf: &mut [u8]) -> crate::Result<()> {
+    aws_lc_rs::rand::fill(buf).map_err(|_| {
+        crate::error::DbSurveyorError::encryption_error("Random number generation failed")
+    })
+}
+
+#[cfg(not(feature = "fips"))]
+fn derive_argon2id(password: &str, kdf_params: &KdfParams) -> crate::Result<Zeroizing<[u8; 32]>> {
     // Create Argon2 parameters
     let params = Params::new(
         kdf_params.memory_cost,
@@ -336,12 +592,268 @@ fn validate_encrypted_data(encrypted: &EncryptedData) -> crate::Result<()> {
     Ok(key)
 }
 
-/// Encrypts data using AES-GCM-256 with Argon2id key derivation.
+#[cfg(feature = "fips")]
+fn derive_argon2id(_password: &str, _kdf_params: &KdfParams) -> crate::Result<Zeroizing<[u8; 32]>> {
+    Err(crate::error::DbSurveyorError::encryption_error(
+        "Argon2id key derivation is not FIPS-approved; decrypt this file with a non-FIPS \
+         build and re-encrypt it",
+    ))
+}
+
+#[cfg(not(feature = "fips"))]
+fn derive_pbkdf2(password: &str, kdf_params: &KdfParams) -> crate::Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+        password.as_bytes(),
+        &kdf_params.salt,
+        kdf_params.time_cost,
+        &mut *key,
+    );
+    Ok(key)
+}
+
+#[cfg(feature = "fips")]
+fn derive_pbkdf2(password: &str, kdf_params: &KdfParams) -> crate::Result<Zeroizing<[u8; 32]>> {
+    let iterations = std::num::NonZeroU32::new(kdf_params.time_cost).ok_or_else(|| {
+        crate::error::DbSurveyorError::encryption_error("PBKDF2 iteration count must be non-zero")
+    })?;
+    let mut key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    aws_lc_rs::pbkdf2::derive(
+        aws_lc_rs::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &kdf_params.salt,
+        password.as_bytes(),
+        &mut *key,
+    );
+    Ok(key)
+}
+
+/// Encrypts `data`, returning the payload and the detached 16-byte tag.
+#[cfg(not(feature = "fips"))]
+fn seal(
+    key: &[u8; AES_KEY_SIZE],
+    nonce: &[u8; AES_GCM_NONCE_SIZE],
+    data: &[u8],
+) -> crate::Result<(Vec<u8>, Vec<u8>)> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut ciphertext = cipher
+        .encrypt(Nonce::from_slice(nonce), data)
+        .map_err(|e| {
+            crate::error::DbSurveyorError::encryption_error(format!("Encryption failed: {}", e))
+        })?;
+
+    // AES-GCM appends the 16-byte auth tag to the ciphertext
+    let payload_len = ciphertext
+        .len()
+        .checked_sub(AES_GCM_TAG_SIZE)
+        .ok_or_else(|| {
+            crate::error::DbSurveyorError::encryption_error(format!(
+                "Encrypted data too short (minimum {} bytes for auth tag)",
+                AES_GCM_TAG_SIZE
+            ))
+        })?;
+    let auth_tag = ciphertext.split_off(payload_len);
+    Ok((ciphertext, auth_tag))
+}
+
+/// Encrypts `data`, returning the payload and the detached 16-byte tag.
+#[cfg(feature = "fips")]
+fn seal(
+    key: &[u8; AES_KEY_SIZE],
+    nonce: &[u8; AES_GCM_NONCE_SIZE],
+    data: &[u8],
+) -> crate::Result<(Vec<u8>, Vec<u8>)> {
+    use aws_lc_rs::aead::{AES_256_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+
+    let cipher = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| crate::error::DbSurveyorError::encryption_error("Invalid AES-256 key"))?,
+    );
+    let mut payload = data.to_vec();
+    let tag = cipher
+        .seal_in_place_separate_tag(
+            Nonce::assume_unique_for_key(*nonce),
+            Aad::empty(),
+            &mut payload,
+        )
+        .map_err(|_| crate::error::DbSurveyorError::encryption_error("Encryption failed"))?;
+    Ok((payload, tag.as_ref().to_vec()))
+}
+
+/// Verifies the tag and decrypts `payload`.
+#[cfg(not(feature = "fips"))]
+fn open(
+    key: &[u8; AES_KEY_SIZE],
+    nonce: &[u8; AES_GCM_NONCE_SIZE],
+    payload: &[u8],
+    auth_tag: &[u8],
+) -> crate::Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    // Reconstruct full ciphertext (payload + auth tag)
+    let mut full_ciphertext = payload.to_vec();
+    full_ciphertext.extend_from_slice(auth_tag);
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), full_ciphertext.as_slice())
+        .map_err(|e| {
+            crate::error::DbSurveyorError::encryption_error(format!(
+                "Decryption failed (wrong password or corrupted data): {}",
+                e
+            ))
+        })
+}
+
+/// Verifies the tag and decrypts `payload`.
+#[cfg(feature = "fips")]
+fn open(
+    key: &[u8; AES_KEY_SIZE],
+    nonce: &[u8; AES_GCM_NONCE_SIZE],
+    payload: &[u8],
+    auth_tag: &[u8],
+) -> crate::Result<Vec<u8>> {
+    use aws_lc_rs::aead::{AES_256_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+
+    let cipher = LessSafeKey::new(
+        UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| crate::error::DbSurveyorError::encryption_error("Invalid AES-256 key"))?,
+    );
+    let mut in_out = payload.to_vec();
+    in_out.extend_from_slice(auth_tag);
+    let plaintext_len = cipher
+        .open_in_place(
+            Nonce::assume_unique_for_key(*nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| {
+            crate::error::DbSurveyorError::encryption_error(
+                "Decryption failed (wrong password or corrupted data)",
+            )
+        })?
+        .len();
+    in_out.truncate(plaintext_len);
+    Ok(in_out)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ENVELOPE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Copies a validated nonce into the fixed-size array the cipher expects.
+fn nonce_array(nonce: &[u8]) -> crate::Result<[u8; AES_GCM_NONCE_SIZE]> {
+    nonce
+        .try_into()
+        .map_err(|_| crate::error::DbSurveyorError::encryption_error("Invalid nonce length"))
+}
+
+/// Generates `N` random bytes in a zeroizing array.
+fn random_array<const N: usize>() -> crate::Result<Zeroizing<[u8; N]>> {
+    let mut bytes = Zeroizing::new([0u8; N]);
+    fill_random(&mut *bytes)?;
+    Ok(bytes)
+}
+
+/// Encrypts the payload under `data_key`, leaving recipients to the caller.
+fn seal_payload(data: &[u8], data_key: &[u8; AES_KEY_SIZE]) -> crate::Result<EncryptedData> {
+    let nonce = random_array::<AES_GCM_NONCE_SIZE>()?;
+    let (ciphertext, auth_tag) = seal(data_key, &nonce, data)?;
+
+    Ok(EncryptedData {
+        algorithm: "AES-GCM-256".to_string(),
+        provider: CryptoProvider::active(),
+        nonce: nonce.to_vec(),
+        ciphertext,
+        auth_tag,
+        recipients: Vec::new(),
+        kdf_params: None,
+    })
+}
+
+/// Wraps `data_key` for a recipient identified by `label`.
+fn wrap_key(
+    data_key: &[u8; AES_KEY_SIZE],
+    label: &str,
+    password: &str,
+    kdf_params: KdfParams,
+) -> crate::Result<Recipient> {
+    if label.trim().is_empty() {
+        return Err(crate::error::DbSurveyorError::encryption_error(
+            "Recipient label cannot be empty",
+        ));
+    }
+
+    let key = derive_key(password, &kdf_params)?;
+    let nonce = random_array::<AES_GCM_NONCE_SIZE>()?;
+    let (wrapped_key, auth_tag) = seal(&key, &nonce, data_key)?;
+
+    Ok(Recipient {
+        label: label.to_string(),
+        nonce: nonce.to_vec(),
+        wrapped_key,
+        auth_tag,
+        kdf_params,
+    })
+}
+
+/// Recovers the data key from `recipient` if `password` is theirs.
+fn unwrap_key(recipient: &Recipient, password: &str) -> crate::Result<Zeroizing<[u8; 32]>> {
+    let key = derive_key(password, &recipient.kdf_params)?;
+    let unwrapped = Zeroizing::new(open(
+        &key,
+        &nonce_array(&recipient.nonce)?,
+        &recipient.wrapped_key,
+        &recipient.auth_tag,
+    )?);
+
+    let mut data_key = Zeroizing::new([0u8; AES_KEY_SIZE]);
+    if unwrapped.len() != AES_KEY_SIZE {
+        return Err(crate::error::DbSurveyorError::encryption_error(
+            "Unwrapped data key has the wrong length",
+        ));
+    }
+    data_key.copy_from_slice(&unwrapped);
+    Ok(data_key)
+}
+
+/// Finds the payload key for `password`, returning the label of the
+/// recipient it matched (`None` for a pre-envelope file).
+///
+/// Each recipient costs one key derivation, so files with many recipients
+/// take proportionally longer to open with the last recipient's password.
+fn payload_key(
+    encrypted: &EncryptedData,
+    password: &str,
+) -> crate::Result<(Zeroizing<[u8; 32]>, Option<String>)> {
+    if encrypted.recipients.is_empty() {
+        let kdf_params = encrypted.kdf_params.as_ref().ok_or_else(|| {
+            crate::error::DbSurveyorError::encryption_error("Encrypted data has no recipients")
+        })?;
+        return Ok((derive_key(password, kdf_params)?, None));
+    }
+
+    let mut last_error = None;
+    for recipient in &encrypted.recipients {
+        match unwrap_key(recipient, password) {
+            Ok(data_key) => return Ok((data_key, Some(recipient.label.clone()))),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        crate::error::DbSurveyorError::encryption_error("Encrypted data has no recipients")
+    }))
+}
+
+/// Encrypts data using AES-GCM-256 with password-based key derivation.
+///
+/// The file has a single recipient labelled [`DEFAULT_RECIPIENT`]; use
+/// [`encrypt_for_recipients`] to encrypt for several passwords at once.
 ///
 /// # Security Guarantees
 /// - Uses AES-GCM-256 authenticated encryption
 /// - Random 96-bit nonce generated for each encryption
-/// - Argon2id key derivation with secure parameters
+/// - Random 256-bit data key wrapped under an Argon2id (PBKDF2-HMAC-SHA256
+///   under `fips`) password-derived key
 /// - Authentication tag prevents tampering
 /// - Key material is automatically zeroed
 ///
@@ -354,57 +866,82 @@ fn validate_encrypted_data(encrypted: &EncryptedData) -> crate::Result<()> {
 ///
 /// # Example
 /// ```rust
-/// use dbsurveyor_core::security::encryption::encrypt_data;
+/// use dbsurveyor_core::security::encryption::{CryptoProvider, encrypt_data};
 ///
 /// let data = b"sensitive database schema";
 /// let encrypted = encrypt_data(data, "strong_password")?;
 /// assert_eq!(encrypted.algorithm, "AES-GCM-256");
+/// assert_eq!(encrypted.provider, CryptoProvider::active());
 /// assert_eq!(encrypted.nonce.len(), 12); // 96 bits
+/// assert_eq!(encrypted.recipients.len(), 1);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn encrypt_data(data: &[u8], password: &str) -> crate::Result<EncryptedData> {
-    // Generate KDF parameters with random salt
-    let kdf_params = KdfParams::new();
-
-    // Derive encryption key
-    let key = derive_key(password, &kdf_params)?;
-
-    // Create AES-GCM cipher
-    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&*key));
-
-    // Generate random 96-bit nonce
-    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
-
-    // Encrypt data
-    let ciphertext = cipher.encrypt(&nonce, data).map_err(|e| {
-        crate::error::DbSurveyorError::encryption_error(format!("Encryption failed: {}", e))
-    })?;
+    encrypt_with_params(data, password, KdfParams::new()?)
+}
 
-    // Split ciphertext and auth tag
-    // AES-GCM appends the 16-byte auth tag to the ciphertext
-    if ciphertext.len() < AES_GCM_TAG_SIZE {
-        return Err(crate::error::DbSurveyorError::encryption_error(format!(
-            "Encrypted data too short (minimum {} bytes for auth tag)",
-            AES_GCM_TAG_SIZE
-        )));
+/// Encrypts data so that any of several passwords can decrypt it.
+///
+/// # Arguments
+/// * `data` - Data to encrypt
+/// * `recipients` - `(label, password)` pairs; labels must be unique
+///
+/// # Errors
+/// Returns error if no recipients are given, a label is empty or repeated,
+/// or encryption fails
+pub fn encrypt_for_recipients(
+    data: &[u8],
+    recipients: &[(&str, &str)],
+) -> crate::Result<EncryptedData> {
+    if recipients.is_empty() {
+        return Err(crate::error::DbSurveyorError::encryption_error(
+            "At least one recipient is required",
+        ));
     }
 
-    let (payload, auth_tag) = ciphertext.split_at(ciphertext.len() - AES_GCM_TAG_SIZE);
+    let data_key = random_array::<AES_KEY_SIZE>()?;
+    let mut encrypted = seal_payload(data, &data_key)?;
+    for (label, password) in recipients {
+        if encrypted.recipients.iter().any(|r| r.label == *label) {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "Duplicate recipient label: {}",
+                label
+            )));
+        }
+        encrypted
+            .recipients
+            .push(wrap_key(&data_key, label, password, KdfParams::new()?)?);
+    }
+    Ok(encrypted)
+}
 
-    Ok(EncryptedData {
-        algorithm: "AES-GCM-256".to_string(),
-        nonce: nonce.to_vec(),
-        ciphertext: payload.to_vec(),
-        auth_tag: auth_tag.to_vec(),
+/// Encrypts for the default recipient with caller-supplied KDF parameters.
+fn encrypt_with_params(
+    data: &[u8],
+    password: &str,
+    kdf_params: KdfParams,
+) -> crate::Result<EncryptedData> {
+    let data_key = random_array::<AES_KEY_SIZE>()?;
+    let mut encrypted = seal_payload(data, &data_key)?;
+    encrypted.recipients.push(wrap_key(
+        &data_key,
+        DEFAULT_RECIPIENT,
+        password,
         kdf_params,
-    })
+    )?);
+    Ok(encrypted)
 }
 
 /// Decrypts data using AES-GCM-256 with embedded parameters.
 ///
+/// The password is tried against each recipient in turn. Files from either
+/// provider can be decrypted as long as this build supports their key
+/// derivation function: default builds read both Argon2id and PBKDF2
+/// files, `fips` builds read only PBKDF2 files.
+///
 /// # Security Guarantees
 /// - Validates all parameters before decryption
-/// - Verifies authentication tag to prevent tampering
+/// - Verifies authentication tags to prevent tampering
 /// - Uses embedded KDF parameters for key derivation
 /// - Key material is automatically zeroed
 ///
@@ -418,8 +955,9 @@ pub fn encrypt_data(data: &[u8], password: &str) -> crate::Result<EncryptedData>
 /// # Errors
 /// Returns error if:
 /// - Authentication fails (data was tampered with)
-/// - Password is incorrect
+/// - Password matches no recipient
 /// - Parameters are invalid
+/// - The key derivation function is not available in this build
 ///
 /// # Example
 /// ```rust
@@ -435,34 +973,146 @@ pub fn decrypt_data(encrypted: &EncryptedData, password: &str) -> crate::Result<
     // Validate encrypted data structure
     validate_encrypted_data(encrypted)?;
 
-    // Derive decryption key using embedded parameters
-    let key = derive_key(password, &encrypted.kdf_params)?;
+    let (key, _) = payload_key(encrypted, password)?;
 
-    // Create AES-GCM cipher
-    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&*key));
+    open(
+        &key,
+        &nonce_array(&encrypted.nonce)?,
+        &encrypted.ciphertext,
+        &encrypted.auth_tag,
+    )
+}
 
-    // Reconstruct nonce
-    let nonce = Nonce::from_slice(&encrypted.nonce);
+/// An [`EncryptedData`] envelope opened with one recipient's password.
+///
+/// Recipients can be added, removed, or rotated without decrypting the
+/// payload. Removing a recipient stops their password from opening the
+/// rewritten file, but the data key itself is unchanged, so a removed
+/// recipient who kept an earlier copy of the file can still read it.
+pub struct UnlockedEnvelope {
+    encrypted: EncryptedData,
+    data_key: Zeroizing<[u8; AES_KEY_SIZE]>,
+    unlocked_by: String,
+}
 
-    // Reconstruct full ciphertext (payload + auth tag)
-    let mut full_ciphertext = encrypted.ciphertext.clone();
-    full_ciphertext.extend_from_slice(&encrypted.auth_tag);
+impl UnlockedEnvelope {
+    /// Opens `encrypted` with `password`.
+    ///
+    /// A file written before envelope encryption is upgraded: its payload is
+    /// re-encrypted under a fresh data key wrapped for [`DEFAULT_RECIPIENT`]
+    /// with the same password.
+    ///
+    /// # Errors
+    /// Returns error if the data is invalid or the password matches no
+    /// recipient
+    pub fn open(encrypted: EncryptedData, password: &str) -> crate::Result<Self> {
+        validate_encrypted_data(&encrypted)?;
+
+        let (key, label) = payload_key(&encrypted, password)?;
+        if let Some(unlocked_by) = label {
+            return Ok(Self {
+                encrypted,
+                data_key: key,
+                unlocked_by,
+            });
+        }
 
-    // Decrypt and verify
-    let plaintext = cipher
-        .decrypt(nonce, full_ciphertext.as_slice())
-        .map_err(|e| {
-            crate::error::DbSurveyorError::encryption_error(format!(
-                "Decryption failed (wrong password or corrupted data): {}",
-                e
-            ))
-        })?;
+        let plaintext = Zeroizing::new(open(
+            &key,
+            &nonce_array(&encrypted.nonce)?,
+            &encrypted.ciphertext,
+            &encrypted.auth_tag,
+        )?);
+        let data_key = random_array::<AES_KEY_SIZE>()?;
+        let mut upgraded = seal_payload(&plaintext, &data_key)?;
+        upgraded.recipients.push(wrap_key(
+            &data_key,
+            DEFAULT_RECIPIENT,
+            password,
+            KdfParams::new()?,
+        )?);
+        Ok(Self {
+            encrypted: upgraded,
+            data_key,
+            unlocked_by: DEFAULT_RECIPIENT.to_string(),
+        })
+    }
+
+    /// Returns the label of the recipient whose password opened the file.
+    pub fn unlocked_by(&self) -> &str {
+        &self.unlocked_by
+    }
 
-    Ok(plaintext)
+    /// Returns the recipient labels in file order.
+    pub fn recipients(&self) -> impl Iterator<Item = &str> {
+        self.encrypted.recipients.iter().map(|r| r.label.as_str())
+    }
+
+    /// Adds a recipient who can open the file with `password`.
+    ///
+    /// # Errors
+    /// Returns error if `label` is empty or already in use
+    pub fn add_recipient(&mut self, label: &str, password: &str) -> crate::Result<()> {
+        if self.recipients().any(|existing| existing == label) {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "Recipient '{}' already exists",
+                label
+            )));
+        }
+        let recipient = wrap_key(&self.data_key, label, password, KdfParams::new()?)?;
+        self.encrypted.recipients.push(recipient);
+        Ok(())
+    }
+
+    /// Removes the recipient labelled `label`.
+    ///
+    /// # Errors
+    /// Returns error if there is no such recipient or it is the last one
+    pub fn remove_recipient(&mut self, label: &str) -> crate::Result<()> {
+        let index = self.position(label)?;
+        if self.encrypted.recipients.len() == 1 {
+            return Err(crate::error::DbSurveyorError::encryption_error(format!(
+                "Cannot remove '{}', the only recipient",
+                label
+            )));
+        }
+        self.encrypted.recipients.remove(index);
+        Ok(())
+    }
+
+    /// Replaces the password of the recipient labelled `label`, with a
+    /// fresh salt and nonce.
+    ///
+    /// # Errors
+    /// Returns error if there is no such recipient
+    pub fn rotate_recipient(&mut self, label: &str, password: &str) -> crate::Result<()> {
+        let index = self.position(label)?;
+        let recipient = wrap_key(&self.data_key, label, password, KdfParams::new()?)?;
+        if let Some(slot) = self.encrypted.recipients.get_mut(index) {
+            *slot = recipient;
+        }
+        Ok(())
+    }
+
+    /// Returns the envelope with its current recipients.
+    pub fn into_encrypted(self) -> EncryptedData {
+        self.encrypted
+    }
+
+    fn position(&self, label: &str) -> crate::Result<usize> {
+        self.recipients()
+            .position(|existing| existing == label)
+            .ok_or_else(|| {
+                crate::error::DbSurveyorError::encryption_error(format!(
+                    "No recipient labelled '{}'",
+                    label
+                ))
+            })
+    }
 }
 
 /// Async wrapper for [`encrypt_data`] that offloads the CPU-intensive
-/// Argon2id key derivation to a blocking thread via [`tokio::task::spawn_blocking`].
+/// key derivation to a blocking thread via [`tokio::task::spawn_blocking`].
 ///
 /// Use this from async contexts to avoid blocking the Tokio executor.
 ///
@@ -486,7 +1136,7 @@ pub async fn encrypt_data_async(data: &[u8], password: &str) -> crate::Result<En
 }
 
 /// Async wrapper for [`decrypt_data`] that offloads the CPU-intensive
-/// Argon2id key derivation to a blocking thread via [`tokio::task::spawn_blocking`].
+/// key derivation to a blocking thread via [`tokio::task::spawn_blocking`].
 ///
 /// Use this from async contexts to avoid blocking the Tokio executor.
 ///
@@ -516,8 +1166,9 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(feature = "fips"))]
     fn test_kdf_params_new() {
-        let params = KdfParams::new();
+        let params = KdfParams::new().unwrap();
 
         // Verify salt is 16 bytes
         assert_eq!(params.salt.len(), 16);
@@ -534,7 +1185,7 @@ fn test_kdf_params_new() {
 
     #[test]
     fn test_kdf_params_validation() {
-        let mut params = KdfParams::new();
+        let mut params = KdfParams::for_algorithm(KdfAlgorithm::Argon2id).unwrap();
 
         // Test salt length validation
         params.salt = vec![0u8; 15]; // Too short
@@ -578,7 +1229,7 @@ fn test_encrypt_decrypt_roundtrip() {
         assert_eq!(encrypted.nonce.len(), 12); // 96 bits
         assert_eq!(encrypted.auth_tag.len(), 16); // 128 bits
         assert!(!encrypted.ciphertext.is_empty());
-        assert_eq!(encrypted.kdf_params.salt.len(), 16);
+        assert_eq!(encrypted.recipients[0].kdf_params.salt.len(), 16);
 
         // Decrypt the data
         let decrypted = decrypt_data(&encrypted, password).unwrap();
@@ -749,6 +1400,7 @@ fn test_large_data_encryption() {
     }
 
     #[test]
+    #[cfg(not(feature = "fips"))]
     fn test_kdf_parameters_embedded() {
         let data = b"test data";
         let password = "test_password";
@@ -756,11 +1408,11 @@ fn test_kdf_parameters_embedded() {
         let encrypted = encrypt_data(data, password).unwrap();
 
         // Verify KDF parameters are properly embedded
-        assert_eq!(encrypted.kdf_params.salt.len(), 16);
-        assert_eq!(encrypted.kdf_params.memory_cost, 65536);
-        assert_eq!(encrypted.kdf_params.time_cost, 3);
-        assert_eq!(encrypted.kdf_params.parallelism, 4);
-        assert_eq!(encrypted.kdf_params.version, "1.3");
+        assert_eq!(encrypted.recipients[0].kdf_params.salt.len(), 16);
+        assert_eq!(encrypted.recipients[0].kdf_params.memory_cost, 65536);
+        assert_eq!(encrypted.recipients[0].kdf_params.time_cost, 3);
+        assert_eq!(encrypted.recipients[0].kdf_params.parallelism, 4);
+        assert_eq!(encrypted.recipients[0].kdf_params.version, "1.3");
 
         // Decryption should work using embedded parameters
         let decrypted = decrypt_data(&encrypted, password).unwrap();
@@ -874,4 +1526,171 @@ fn test_short_unicode_password_wrong_password_fails() {
         let result = decrypt_data(&encrypted, wrong_password);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_pbkdf2_params_validation() {
+        let mut params = KdfParams::for_algorithm(KdfAlgorithm::Pbkdf2Sha256).unwrap();
+        assert_eq!(params.algorithm(), KdfAlgorithm::Pbkdf2Sha256);
+        assert_eq!(params.salt().len(), 16);
+        assert_eq!(params.time_cost(), 600_000);
+        assert!(params.validate().is_ok());
+
+        params.time_cost = 599_999;
+        assert!(params.validate().is_err());
+
+        params.time_cost = 600_000;
+        params.salt = vec![0u8; 15];
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_provider_recorded_in_header() {
+        let encrypted = encrypt_data(b"data", "password").unwrap();
+        assert_eq!(encrypted.provider, CryptoProvider::active());
+
+        let json = serde_json::to_value(&encrypted).unwrap();
+        assert_eq!(json["provider"], CryptoProvider::active().to_string());
+    }
+
+    #[test]
+    fn test_pbkdf2_roundtrip() {
+        let data = b"data derived with PBKDF2";
+        let params = KdfParams::for_algorithm(KdfAlgorithm::Pbkdf2Sha256).unwrap();
+        let encrypted = encrypt_with_params(data, "password", params).unwrap();
+
+        let json = serde_json::to_value(&encrypted).unwrap();
+        assert_eq!(
+            json["recipients"][0]["kdf_params"]["algorithm"],
+            "pbkdf2-sha256"
+        );
+
+        let decrypted = decrypt_data(&encrypted, "password").unwrap();
+        assert_eq!(data, &decrypted[..]);
+        assert!(decrypt_data(&encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_header_without_provider_reads_as_argon2id() {
+        let data = b"file written before providers were recorded";
+        let encrypted = encrypt_data(data, "password").unwrap();
+
+        let mut json = serde_json::to_value(&encrypted).unwrap();
+        let header = json.as_object_mut().unwrap();
+        header.remove("provider");
+        header["recipients"][0]["kdf_params"]
+            .as_object_mut()
+            .unwrap()
+            .remove("algorithm");
+
+        let legacy: EncryptedData = serde_json::from_value(json).unwrap();
+        assert_eq!(legacy.provider, CryptoProvider::RustCrypto);
+        assert_eq!(
+            legacy.recipients[0].kdf_params.algorithm(),
+            KdfAlgorithm::Argon2id
+        );
+        assert_eq!(decrypt_data(&legacy, "password").unwrap(), data);
+    }
+
+    #[test]
+    #[cfg(feature = "fips")]
+    fn test_fips_rejects_argon2id() {
+        let params = KdfParams::for_algorithm(KdfAlgorithm::Argon2id).unwrap();
+        let error = encrypt_with_params(b"data", "password", params)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("not FIPS-approved"));
+    }
+
+    /// Builds a file in the pre-envelope format, where the payload key is
+    /// derived directly from the password.
+    fn legacy_encrypt(data: &[u8], password: &str) -> EncryptedData {
+        let kdf_params = KdfParams::new().unwrap();
+        let key = derive_key(password, &kdf_params).unwrap();
+        let mut encrypted = seal_payload(data, &key).unwrap();
+        encrypted.kdf_params = Some(kdf_params);
+        encrypted
+    }
+
+    #[test]
+    fn test_each_recipient_can_decrypt() {
+        let data = b"shared schema";
+        let encrypted =
+            encrypt_for_recipients(data, &[("alice", "alice-pass"), ("bob", "bob-pass")]).unwrap();
+
+        assert_eq!(encrypted.recipients.len(), 2);
+        assert!(encrypted.kdf_params.is_none());
+        assert_eq!(decrypt_data(&encrypted, "alice-pass").unwrap(), data);
+        assert_eq!(decrypt_data(&encrypted, "bob-pass").unwrap(), data);
+
+        let error = decrypt_data(&encrypted, "mallory").unwrap_err().to_string();
+        assert!(error.contains("Decryption failed"));
+    }
+
+    #[test]
+    fn test_recipient_labels_validated() {
+        assert!(encrypt_for_recipients(b"data", &[]).is_err());
+        assert!(encrypt_for_recipients(b"data", &[("", "password")]).is_err());
+        let error = encrypt_for_recipients(b"data", &[("ops", "one"), ("ops", "two")])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Duplicate recipient label"));
+    }
+
+    #[test]
+    fn test_rekey_add_rotate_remove() {
+        let data = b"schema to rekey";
+        let encrypted = encrypt_data(data, "original").unwrap();
+        let payload = encrypted.ciphertext.clone();
+
+        let mut envelope = UnlockedEnvelope::open(encrypted, "original").unwrap();
+        assert_eq!(envelope.unlocked_by(), DEFAULT_RECIPIENT);
+        envelope.add_recipient("auditor", "auditor-pass").unwrap();
+        assert!(envelope.add_recipient("auditor", "other").is_err());
+        envelope
+            .rotate_recipient(DEFAULT_RECIPIENT, "rotated")
+            .unwrap();
+        let rekeyed = envelope.into_encrypted();
+
+        // The payload is untouched; only the key wraps change
+        assert_eq!(rekeyed.ciphertext, payload);
+        assert!(decrypt_data(&rekeyed, "original").is_err());
+        assert_eq!(decrypt_data(&rekeyed, "rotated").unwrap(), data);
+        assert_eq!(decrypt_data(&rekeyed, "auditor-pass").unwrap(), data);
+
+        let mut envelope = UnlockedEnvelope::open(rekeyed, "auditor-pass").unwrap();
+        assert_eq!(envelope.unlocked_by(), "auditor");
+        envelope.remove_recipient(DEFAULT_RECIPIENT).unwrap();
+        assert!(envelope.remove_recipient("auditor").is_err());
+        assert!(envelope.rotate_recipient("missing", "x").is_err());
+        assert_eq!(envelope.recipients().collect::<Vec<_>>(), vec!["auditor"]);
+    }
+
+    #[test]
+    fn test_legacy_file_decrypts_and_upgrades() {
+        let data = b"file from before envelope encryption";
+        let legacy = legacy_encrypt(data, "password");
+        assert!(legacy.recipients.is_empty());
+
+        let json = serde_json::to_string(&legacy).unwrap();
+        let legacy: EncryptedData = serde_json::from_str(&json).unwrap();
+        assert_eq!(decrypt_data(&legacy, "password").unwrap(), data);
+
+        let envelope = UnlockedEnvelope::open(legacy, "password").unwrap();
+        assert_eq!(envelope.unlocked_by(), DEFAULT_RECIPIENT);
+        let upgraded = envelope.into_encrypted();
+        assert!(upgraded.kdf_params.is_none());
+        assert_eq!(upgraded.recipients.len(), 1);
+        assert_eq!(decrypt_data(&upgraded, "password").unwrap(), data);
+    }
+
+    #[test]
+    fn test_missing_key_source_fails() {
+        let mut encrypted = encrypt_data(b"data", "password").unwrap();
+        encrypted.recipients.clear();
+        let error = decrypt_data(&encrypted, "password")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no recipients"));
+    }
 }
@@ -4,6 +4,8 @@
 //! - Credential sanitization and secure memory handling
 //! - Connection string parsing with automatic credential extraction
 //! - Optional AES-GCM encryption (feature-gated)
+//! - HMAC-SHA256 signatures over canonical survey JSON
+//! - Reading passwords and keys from files
 //!
 //! # Security Guarantees
 //! - Credentials are stored in `Zeroizing` containers for automatic memory clearing
@@ -13,10 +15,12 @@
 //! # Module Structure
 //! - `credentials`: Secure credential container with automatic memory zeroing
 //! - `connection`: Connection string parsing and info extraction
-//! - `encryption`: AES-GCM encryption with Argon2id key derivation (feature-gated)
+//! - `encryption`: AES-GCM encryption with Argon2id or PBKDF2 key derivation (feature-gated)
+//! - `signing`: HMAC-SHA256 signing and verification of canonical JSON
 
 mod connection;
 mod credentials;
+pub mod signing;
 
 #[cfg(feature = "encryption")]
 pub mod encryption;
@@ -25,6 +29,28 @@
 pub use connection::{ConnectionInfo, parse_connection_string};
 pub use credentials::Credentials;
 
+use zeroize::Zeroizing;
+
+/// Reads a password or key from a file, such as `--password-file`, without
+/// its trailing line ending. Both the file contents and the returned secret
+/// are zeroized on drop.
+pub fn read_secret_file(path: &std::path::Path) -> crate::Result<Zeroizing<String>> {
+    let content = Zeroizing::new(std::fs::read_to_string(path).map_err(|e| {
+        crate::error::DbSurveyorError::Io {
+            context: format!("Failed to read key file {}", path.display()),
+            source: e,
+        }
+    })?);
+    let secret = content.trim_end_matches(['\r', '\n']);
+    if secret.is_empty() {
+        return Err(crate::error::DbSurveyorError::configuration(format!(
+            "Key file {} is empty",
+            path.display()
+        )));
+    }
+    Ok(Zeroizing::new(secret.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
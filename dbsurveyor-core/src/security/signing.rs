@@ -0,0 +1,86 @@
+//! HMAC-SHA256 signatures over canonical survey JSON.
+//!
+//! Signatures are computed over the output of
+//! [`crate::canonical::to_canonical_json`], so they verify against any
+//! faithful copy of the survey regardless of how it was re-serialized,
+//! compressed, or encrypted in between.
+
+use crate::{DbSurveyorError, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signs canonical JSON, returning the lowercase hex HMAC-SHA256.
+///
+/// # Errors
+/// Returns a configuration error if the key is rejected.
+pub fn sign(canonical_json: &[u8], key: &[u8]) -> Result<String> {
+    let mut mac = new_mac(key)?;
+    mac.update(canonical_json);
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Checks a hex signature produced by [`sign`] in constant time.
+pub fn verify(canonical_json: &[u8], key: &[u8], signature_hex: &str) -> bool {
+    let Some(expected) = decode_hex(signature_hex.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = new_mac(key) else {
+        return false;
+    };
+    mac.update(canonical_json);
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn new_mac(key: &[u8]) -> Result<HmacSha256> {
+    // HMAC itself accepts any key length; an empty key almost certainly
+    // means the key was not configured
+    if key.is_empty() {
+        return Err(DbSurveyorError::configuration(
+            "Signing key must not be empty",
+        ));
+    }
+    HmacSha256::new_from_slice(key)
+        .map_err(|_| DbSurveyorError::configuration("Invalid signing key"))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i.checked_add(2)?)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_rfc4231_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign(b"what do ya want for nothing?", b"Jefe").expect("valid key"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert!(sign(b"{}", b"").is_err());
+    }
+
+    #[test]
+    fn test_verify_round_trip() {
+        let signature = sign(b"{\"a\":1}", b"secret").expect("valid key");
+        assert!(verify(b"{\"a\":1}", b"secret", &signature));
+        assert!(verify(b"{\"a\":1}", b"secret", &format!("{signature}\n")));
+        assert!(!verify(b"{\"a\":2}", b"secret", &signature));
+        assert!(!verify(b"{\"a\":1}", b"other", &signature));
+        assert!(!verify(b"{\"a\":1}", b"secret", "not hex"));
+    }
+}
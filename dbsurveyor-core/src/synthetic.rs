@@ -0,0 +1,427 @@
+//! Synthetic schema generation for benchmarks and demos.
+//!
+//! [`generate_synthetic_schema`] fabricates a realistic-looking
+//! [`DatabaseSchema`] of arbitrary size: tables spread over schemas, primary
+//! keys, foreign keys to earlier tables with supporting indexes, comments,
+//! and a few views. Output is deterministic for a given configuration, so
+//! generated surveys can be compared across runs and used as benchmark
+//! fixtures without access to a real database.
+
+use crate::models::{
+    Column, DatabaseInfo, DatabaseSchema, ForeignKey, Index, IndexColumn, PrimaryKey,
+    ReferentialAction, Table, UnifiedDataType, View,
+};
+
+/// Entity names used for generated tables; repeated with a numeric suffix.
+const TABLE_NOUNS: &[&str] = &[
+    "customers",
+    "orders",
+    "order_items",
+    "products",
+    "invoices",
+    "payments",
+    "shipments",
+    "suppliers",
+    "warehouses",
+    "employees",
+    "departments",
+    "accounts",
+    "subscriptions",
+    "events",
+    "audit_log",
+    "sessions",
+];
+
+/// Schemas used after `public` when more than one is requested.
+const SCHEMA_NAMES: &[&str] = &["sales", "billing", "inventory", "hr", "analytics"];
+
+/// A view is generated for every this many tables.
+const TABLES_PER_VIEW: usize = 25;
+
+/// Size and shape of a synthetic schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticSchemaConfig {
+    /// Number of tables to generate
+    pub tables: usize,
+    /// Average number of columns per table; individual tables vary by up to half of this
+    pub columns_avg: usize,
+    /// Number of schemas the tables are spread over
+    pub schemas: usize,
+    /// Seed for the pseudo-random generator; equal seeds give equal schemas
+    pub seed: u64,
+}
+
+impl Default for SyntheticSchemaConfig {
+    fn default() -> Self {
+        Self {
+            tables: 100,
+            columns_avg: 12,
+            schemas: 1,
+            seed: 0,
+        }
+    }
+}
+
+impl SyntheticSchemaConfig {
+    /// Builder method to set the number of tables.
+    #[must_use]
+    pub fn with_tables(mut self, tables: usize) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    /// Builder method to set the average number of columns per table.
+    #[must_use]
+    pub fn with_columns_avg(mut self, columns_avg: usize) -> Self {
+        self.columns_avg = columns_avg;
+        self
+    }
+
+    /// Builder method to set the number of schemas.
+    #[must_use]
+    pub fn with_schemas(mut self, schemas: usize) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Builder method to set the random seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// SplitMix64; small, fast, and good enough for fabricating fixtures.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound`; `bound` must be non-zero.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Returns true with probability `percent` / 100.
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Generates a synthetic schema of the configured size.
+///
+/// Every table has an `id` primary key; most tables after the first
+/// reference one or two earlier tables through indexed foreign key columns,
+/// and the remaining columns are drawn from common business column types.
+/// The collection timestamp is fixed so equal configurations serialize
+/// identically.
+pub fn generate_synthetic_schema(config: &SyntheticSchemaConfig) -> DatabaseSchema {
+    let mut rng = Rng(config.seed);
+    let mut schema = DatabaseSchema::new(DatabaseInfo::new("synthetic".to_string()));
+    schema.database_info.version = Some("synthetic".to_string());
+    schema.collection_metadata.collected_at =
+        chrono::DateTime::from_timestamp(1_704_067_200, 0).unwrap_or_default();
+
+    let schema_names: Vec<String> = (0..config.schemas.max(1))
+        .map(|i| match i {
+            0 => "public".to_string(),
+            i if i <= SCHEMA_NAMES.len() => SCHEMA_NAMES[i - 1].to_string(),
+            i => format!("schema_{i}"),
+        })
+        .collect();
+
+    let columns_avg = config.columns_avg.max(1);
+    let spread = columns_avg / 2;
+    for index in 0..config.tables {
+        let noun = TABLE_NOUNS[index % TABLE_NOUNS.len()];
+        let name = match index / TABLE_NOUNS.len() {
+            0 => noun.to_string(),
+            round => format!("{noun}_{round}"),
+        };
+        let table_schema = schema_names[index % schema_names.len()].clone();
+        let column_count = (columns_avg - spread + rng.below(2 * spread + 1)).max(1);
+        let table = generate_table(&mut rng, &name, table_schema, column_count, &schema.tables);
+        schema.tables.push(table);
+    }
+
+    for table in schema.tables.iter().step_by(TABLES_PER_VIEW) {
+        schema.views.push(summary_view(table));
+    }
+
+    schema
+}
+
+fn generate_table(
+    rng: &mut Rng,
+    name: &str,
+    schema: String,
+    column_count: usize,
+    earlier: &[Table],
+) -> Table {
+    let mut columns = vec![Column {
+        is_nullable: false,
+        is_primary_key: true,
+        is_auto_increment: true,
+        ..column(
+            "id",
+            UnifiedDataType::Integer {
+                bits: 64,
+                signed: true,
+            },
+            1,
+        )
+    }];
+    let mut foreign_keys = Vec::new();
+    let mut indexes = Vec::new();
+
+    if !earlier.is_empty() && rng.chance(70) {
+        let references = 1 + rng.below(2);
+        for _ in 0..references.min(column_count.saturating_sub(1)) {
+            let target = &earlier[rng.below(earlier.len())];
+            let column_name = format!("{}_id", target.name);
+            if columns.iter().any(|c| c.name == column_name) {
+                continue;
+            }
+            let mut fk_column = column(
+                &column_name,
+                UnifiedDataType::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+                next_position(&columns),
+            );
+            fk_column.is_nullable = rng.chance(30);
+            columns.push(fk_column);
+            foreign_keys.push(ForeignKey {
+                name: Some(format!("{name}_{column_name}_fkey")),
+                columns: vec![column_name.clone()],
+                referenced_table: target.name.clone(),
+                referenced_schema: target.schema.clone(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: Some(if rng.chance(50) {
+                    ReferentialAction::Cascade
+                } else {
+                    ReferentialAction::Restrict
+                }),
+                on_update: None,
+            });
+            // Leave some foreign keys unindexed, as real schemas do
+            if rng.chance(85) {
+                indexes.push(Index {
+                    name: format!("idx_{name}_{column_name}"),
+                    table_name: name.to_string(),
+                    schema: Some(schema.clone()),
+                    columns: vec![IndexColumn {
+                        name: column_name,
+                        sort_order: None,
+                    }],
+                    is_unique: false,
+                    is_primary: false,
+                    index_type: Some("btree".to_string()),
+                    options: None,
+                });
+            }
+        }
+    }
+
+    while columns.len() < column_count {
+        let (base, data_type) = random_column_type(rng);
+        let column_name = if columns.iter().any(|c| c.name == base) {
+            format!("{base}_{}", columns.len())
+        } else {
+            base.to_string()
+        };
+        let mut generated = column(&column_name, data_type, next_position(&columns));
+        if rng.chance(20) {
+            generated.comment = Some(format!("{} of the {}", column_name.replace('_', " "), name));
+        }
+        columns.push(generated);
+    }
+
+    indexes.push(Index {
+        name: format!("{name}_pkey"),
+        table_name: name.to_string(),
+        schema: Some(schema.clone()),
+        columns: vec![IndexColumn {
+            name: "id".to_string(),
+            sort_order: None,
+        }],
+        is_unique: true,
+        is_primary: true,
+        index_type: Some("btree".to_string()),
+        options: None,
+    });
+
+    Table {
+        comment: rng
+            .chance(50)
+            .then(|| format!("Synthetic {} table", name.replace('_', " "))),
+        primary_key: Some(PrimaryKey {
+            name: Some(format!("{name}_pkey")),
+            columns: vec!["id".to_string()],
+        }),
+        foreign_keys,
+        indexes,
+        row_count: Some(rng.next() % 10_000_000),
+        ..Table::new(name.to_string(), Some(schema), columns)
+    }
+}
+
+fn next_position(columns: &[Column]) -> u32 {
+    u32::try_from(columns.len() + 1).unwrap_or(u32::MAX)
+}
+
+fn column(name: &str, data_type: UnifiedDataType, ordinal_position: u32) -> Column {
+    Column {
+        ordinal_position,
+        ..Column::new(name.to_string(), data_type)
+    }
+}
+
+fn random_column_type(rng: &mut Rng) -> (&'static str, UnifiedDataType) {
+    let text = |max_length| UnifiedDataType::String {
+        max_length: Some(max_length),
+    };
+    match rng.below(14) {
+        0 => ("name", text(255)),
+        1 => ("email", text(320)),
+        2 => ("status", text(32)),
+        3 => ("description", UnifiedDataType::String { max_length: None }),
+        4 => ("phone_number", text(32)),
+        5 => (
+            "amount",
+            UnifiedDataType::Float {
+                precision: Some(53),
+            },
+        ),
+        6 => (
+            "quantity",
+            UnifiedDataType::Integer {
+                bits: 32,
+                signed: true,
+            },
+        ),
+        7 => ("is_active", UnifiedDataType::Boolean),
+        8 => (
+            "created_at",
+            UnifiedDataType::DateTime {
+                with_timezone: true,
+            },
+        ),
+        9 => (
+            "updated_at",
+            UnifiedDataType::DateTime {
+                with_timezone: true,
+            },
+        ),
+        10 => ("due_date", UnifiedDataType::Date),
+        11 => ("external_id", UnifiedDataType::Uuid),
+        12 => ("metadata", UnifiedDataType::Json),
+        _ => (
+            "tags",
+            UnifiedDataType::Array {
+                element_type: Box::new(text(64)),
+            },
+        ),
+    }
+}
+
+fn summary_view(table: &Table) -> View {
+    let schema = table.schema.as_deref().unwrap_or("public");
+    View {
+        name: format!("{}_summary", table.name),
+        schema: table.schema.clone(),
+        definition: Some(format!(
+            "SELECT id FROM {schema}.{} WHERE id IS NOT NULL",
+            table.name
+        )),
+        columns: vec![Column {
+            is_nullable: false,
+            ..column(
+                "id",
+                UnifiedDataType::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+                1,
+            )
+        }],
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generates_requested_size() {
+        let config = SyntheticSchemaConfig::default()
+            .with_tables(200)
+            .with_columns_avg(30)
+            .with_schemas(3);
+        let schema = generate_synthetic_schema(&config);
+
+        assert_eq!(schema.tables.len(), 200);
+        assert_eq!(schema.views.len(), 8);
+        let columns: usize = schema.tables.iter().map(|t| t.columns.len()).sum();
+        let average = columns / schema.tables.len();
+        assert!((25..=35).contains(&average), "average {average}");
+
+        let schemas: std::collections::HashSet<_> = schema
+            .tables
+            .iter()
+            .filter_map(|t| t.schema.as_deref())
+            .collect();
+        assert_eq!(schemas.len(), 3);
+    }
+
+    #[test]
+    fn test_schema_is_consistent() {
+        let schema = generate_synthetic_schema(&SyntheticSchemaConfig::default());
+
+        let mut names = std::collections::HashSet::new();
+        for table in &schema.tables {
+            assert!(names.insert((table.schema.clone(), table.name.clone())));
+            let mut column_names = std::collections::HashSet::new();
+            for (position, column) in table.columns.iter().enumerate() {
+                assert!(column_names.insert(&column.name));
+                assert_eq!(column.ordinal_position as usize, position + 1);
+            }
+            for fk in &table.foreign_keys {
+                assert!(column_names.contains(&fk.columns[0]));
+                assert!(
+                    schema
+                        .tables
+                        .iter()
+                        .any(|t| t.name == fk.referenced_table && t.schema == fk.referenced_schema)
+                );
+            }
+        }
+        assert!(schema.tables.iter().any(|t| !t.foreign_keys.is_empty()));
+
+        crate::initialize_schema_validator().expect("initialize validator");
+        let json = serde_json::to_value(&schema).expect("serialize schema");
+        crate::validate_schema_output(&json).expect("synthetic schema is valid");
+    }
+
+    #[test]
+    fn test_deterministic_per_seed() {
+        let config = SyntheticSchemaConfig::default().with_tables(50);
+        assert_eq!(
+            generate_synthetic_schema(&config),
+            generate_synthetic_schema(&config)
+        );
+        assert_ne!(
+            generate_synthetic_schema(&config),
+            generate_synthetic_schema(&config.with_seed(1))
+        );
+    }
+}
@@ -0,0 +1,575 @@
+//! Adapter conformance suite.
+//!
+//! [`run_conformance_suite`] runs a fixed set of checks against a connected
+//! adapter and a database seeded with a known table (see [`FIXTURE_SQL`]).
+//! Checks never panic; each records a failure message in the returned
+//! [`ConformanceReport`], so one run shows every contract violation.
+
+use crate::adapters::config::MASKED_VALUE;
+use crate::adapters::{AdapterFeature, DatabaseAdapter, TableRef};
+use crate::models::{DatabaseSchema, DatabaseType, Table, TableSample};
+use crate::validation::{initialize_schema_validator, validate_schema_output};
+use crate::{SamplingConfig, SensitiveAction, SensitivePattern};
+use std::fmt;
+
+/// Statements creating and filling the standard fixture table.
+///
+/// Portable across PostgreSQL, MySQL, SQLite, and SQL Server.
+pub const FIXTURE_SQL: &[&str] = &[
+    "CREATE TABLE conformance_users (
+        id INTEGER PRIMARY KEY,
+        email VARCHAR(255),
+        display_name VARCHAR(100) NOT NULL
+    )",
+    "CREATE INDEX idx_conformance_users_email ON conformance_users (email)",
+    "INSERT INTO conformance_users (id, email, display_name) VALUES
+        (1, 'ada@example.com', 'Ada'),
+        (2, 'grace@example.com', 'Grace'),
+        (3, NULL, 'Linus')",
+];
+
+/// What the suite expects to find in the target database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceFixture {
+    /// Table the suite inspects and samples
+    pub table: String,
+    /// Schema of the table; `None` accepts the table in any schema
+    pub schema: Option<String>,
+    /// Columns the table must have
+    pub columns: Vec<String>,
+    /// Exact number of rows in the table (at least 3)
+    pub row_count: u64,
+    /// Column whose name matches a sensitive pattern, used to check masking
+    pub sensitive_column: Option<String>,
+    /// Credential that must not appear in any adapter output, typically
+    /// the connection password
+    pub secret: Option<String>,
+}
+
+impl ConformanceFixture {
+    /// The fixture created by [`FIXTURE_SQL`].
+    pub fn standard() -> Self {
+        Self {
+            table: "conformance_users".to_string(),
+            schema: None,
+            columns: vec![
+                "id".to_string(),
+                "email".to_string(),
+                "display_name".to_string(),
+            ],
+            row_count: 3,
+            sensitive_column: Some("email".to_string()),
+            secret: None,
+        }
+    }
+
+    /// Builder method to set the credential that must stay redacted.
+    #[must_use]
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+}
+
+/// Outcome of one conformance check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceCheck {
+    /// Stable check name
+    pub name: &'static str,
+    /// Why the check failed; `None` when it passed
+    pub failure: Option<String>,
+}
+
+impl ConformanceCheck {
+    fn new(name: &'static str, outcome: Result<(), String>) -> Self {
+        Self {
+            name,
+            failure: outcome.err(),
+        }
+    }
+
+    /// Whether the check passed.
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Results of a conformance run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// Database type reported by the adapter
+    pub database_type: DatabaseType,
+    /// Checks in the order they ran
+    pub checks: Vec<ConformanceCheck>,
+}
+
+impl ConformanceReport {
+    /// Whether every check passed.
+    pub fn is_conformant(&self) -> bool {
+        self.checks.iter().all(ConformanceCheck::passed)
+    }
+
+    /// Checks that failed.
+    pub fn failures(&self) -> Vec<&ConformanceCheck> {
+        self.checks.iter().filter(|check| !check.passed()).collect()
+    }
+
+    /// Panics with every failure unless all checks passed.
+    ///
+    /// # Panics
+    /// Panics if any check failed.
+    #[track_caller]
+    pub fn assert_conformant(&self) {
+        assert!(self.is_conformant(), "{self}");
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failures = self.failures();
+        writeln!(
+            f,
+            "{} adapter conformance: {} of {} checks passed",
+            self.database_type,
+            self.checks.len().saturating_sub(failures.len()),
+            self.checks.len()
+        )?;
+        for check in failures {
+            writeln!(
+                f,
+                "  {}: {}",
+                check.name,
+                check.failure.as_deref().unwrap_or_default()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every conformance check against `adapter`.
+///
+/// The adapter must be connected to a database containing `fixture`.
+pub async fn run_conformance_suite(
+    adapter: &dyn DatabaseAdapter,
+    fixture: &ConformanceFixture,
+) -> ConformanceReport {
+    let mut checks = Vec::new();
+
+    checks.push(ConformanceCheck::new(
+        "connection",
+        adapter
+            .test_connection()
+            .await
+            .map_err(|e| format!("test_connection failed: {e}")),
+    ));
+
+    checks.push(ConformanceCheck::new(
+        "feature_flags",
+        if adapter.supports_feature(AdapterFeature::SchemaCollection) {
+            Ok(())
+        } else {
+            Err("SchemaCollection must be supported".to_string())
+        },
+    ));
+
+    let schema = adapter.collect_schema().await;
+    let table = schema
+        .as_ref()
+        .ok()
+        .and_then(|schema| find_table(schema, fixture));
+    checks.push(ConformanceCheck::new(
+        "schema_collection",
+        match &schema {
+            Ok(schema) => check_schema(schema, table, fixture),
+            Err(e) => Err(format!("collect_schema failed: {e}")),
+        },
+    ));
+    checks.push(ConformanceCheck::new(
+        "schema_contract",
+        match &schema {
+            Ok(schema) => check_schema_contract(schema),
+            Err(_) => Err("no schema collected".to_string()),
+        },
+    ));
+
+    let table_ref = TableRef {
+        schema_name: table
+            .and_then(|table| table.schema.as_deref())
+            .or(fixture.schema.as_deref()),
+        table_name: &fixture.table,
+    };
+    let mut samples = Vec::new();
+    if adapter.supports_feature(AdapterFeature::DataSampling) {
+        let limited = adapter
+            .sample_table(
+                table_ref.clone(),
+                &SamplingConfig::new().with_sample_size(2),
+            )
+            .await;
+        checks.push(ConformanceCheck::new(
+            "sampling",
+            check_sample(limited.as_ref(), fixture, 2),
+        ));
+
+        let all_rows = u32::try_from(fixture.row_count.saturating_add(10)).unwrap_or(u32::MAX);
+        let unlimited = adapter
+            .sample_table(
+                table_ref.clone(),
+                &SamplingConfig::new().with_sample_size(all_rows),
+            )
+            .await;
+        checks.push(ConformanceCheck::new(
+            "sampling_limit",
+            check_sample(unlimited.as_ref(), fixture, fixture.row_count),
+        ));
+
+        if let Some(column) = &fixture.sensitive_column {
+            checks.push(ConformanceCheck::new(
+                "sensitive_columns",
+                match &unlimited {
+                    Ok(sample) => check_masking(sample, column),
+                    Err(_) => Err("no sample collected".to_string()),
+                },
+            ));
+        }
+        samples.extend(limited.ok());
+        samples.extend(unlimited.ok());
+    } else {
+        let sampled = adapter
+            .sample_table(table_ref, &SamplingConfig::new())
+            .await;
+        checks.push(ConformanceCheck::new(
+            "sampling",
+            match sampled {
+                Ok(_) => {
+                    Err("sample_table succeeded although DataSampling is not supported".to_string())
+                }
+                Err(_) => Ok(()),
+            },
+        ));
+    }
+
+    if let Some(secret) = &fixture.secret {
+        checks.push(ConformanceCheck::new(
+            "credential_redaction",
+            check_redaction(adapter, schema.as_ref().ok(), &samples, secret),
+        ));
+    }
+
+    ConformanceReport {
+        database_type: adapter.database_type(),
+        checks,
+    }
+}
+
+fn find_table<'a>(schema: &'a DatabaseSchema, fixture: &ConformanceFixture) -> Option<&'a Table> {
+    schema.tables.iter().find(|table| {
+        table.name == fixture.table && (fixture.schema.is_none() || table.schema == fixture.schema)
+    })
+}
+
+/// Checks the fixture table and the internal consistency of its keys.
+fn check_schema(
+    schema: &DatabaseSchema,
+    table: Option<&Table>,
+    fixture: &ConformanceFixture,
+) -> Result<(), String> {
+    if schema.database_info.name.is_empty() {
+        return Err("database_info.name is empty".to_string());
+    }
+    let table = table.ok_or_else(|| format!("table '{}' not collected", fixture.table))?;
+
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    for (position, name) in names.iter().enumerate() {
+        if names[..position].contains(name) {
+            return Err(format!("column '{name}' reported twice"));
+        }
+    }
+    for column in &fixture.columns {
+        if !names.contains(&column.as_str()) {
+            return Err(format!("column '{column}' missing from '{}'", table.name));
+        }
+    }
+
+    let primary_key = table.primary_key.iter().flat_map(|key| &key.columns);
+    let index_columns = table
+        .indexes
+        .iter()
+        .chain(
+            schema
+                .indexes
+                .iter()
+                .filter(|index| index.table_name == table.name),
+        )
+        .flat_map(|index| index.columns.iter().map(|column| &column.name));
+    for column in primary_key.chain(index_columns) {
+        if !names.contains(&column.as_str()) {
+            return Err(format!(
+                "key or index column '{column}' is not a column of '{}'",
+                table.name
+            ));
+        }
+    }
+    for foreign_key in &table.foreign_keys {
+        if foreign_key.columns.len() != foreign_key.referenced_columns.len() {
+            return Err(format!(
+                "foreign key on ({}) references {} columns",
+                foreign_key.columns.join(", "),
+                foreign_key.referenced_columns.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the schema validates against the official JSON Schema.
+fn check_schema_contract(schema: &DatabaseSchema) -> Result<(), String> {
+    initialize_schema_validator().map_err(|e| e.to_string())?;
+    let json = serde_json::to_value(schema).map_err(|e| e.to_string())?;
+    validate_schema_output(&json).map_err(|e| e.to_string())
+}
+
+fn check_sample(
+    sample: Result<&TableSample, &crate::DbSurveyorError>,
+    fixture: &ConformanceFixture,
+    expected_rows: u64,
+) -> Result<(), String> {
+    let sample = sample.map_err(|e| format!("sample_table failed: {e}"))?;
+    if sample.table_name != fixture.table {
+        return Err(format!(
+            "sample reports table '{}', expected '{}'",
+            sample.table_name, fixture.table
+        ));
+    }
+    if sample.rows.len() as u64 != expected_rows {
+        return Err(format!(
+            "sampled {} rows, expected {expected_rows}",
+            sample.rows.len()
+        ));
+    }
+    if sample.sample_size as usize != sample.rows.len() {
+        return Err(format!(
+            "sample_size is {} but {} rows were returned",
+            sample.sample_size,
+            sample.rows.len()
+        ));
+    }
+    for row in &sample.rows {
+        let object = row
+            .as_object()
+            .ok_or_else(|| format!("sampled row is not a JSON object: {row}"))?;
+        if let Some(column) = fixture.columns.iter().find(|c| !object.contains_key(*c)) {
+            return Err(format!("sampled row has no '{column}' key"));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that sampled rows are keyed by column name, so sensitive
+/// pattern actions can mask them.
+fn check_masking(sample: &TableSample, column: &str) -> Result<(), String> {
+    let config = SamplingConfig::new().add_sensitive_pattern(
+        SensitivePattern::new(format!("^{}$", regex::escape(column)), "conformance")
+            .with_action(SensitiveAction::Mask),
+    );
+    let mut rows = sample.rows.clone();
+    config.apply_sensitive_actions(&mut rows);
+
+    let masked = rows
+        .iter()
+        .filter_map(|row| row.get(column))
+        .filter(|value| !value.is_null())
+        .all(|value| value == MASKED_VALUE);
+    if masked {
+        Ok(())
+    } else {
+        Err(format!("values of '{column}' were not masked"))
+    }
+}
+
+fn check_redaction(
+    adapter: &dyn DatabaseAdapter,
+    schema: Option<&DatabaseSchema>,
+    samples: &[TableSample],
+    secret: &str,
+) -> Result<(), String> {
+    if format!("{:?}", adapter.connection_config()).contains(secret) {
+        return Err("connection_config() exposes the credential".to_string());
+    }
+    if let Some(schema) = schema {
+        let json = serde_json::to_string(schema).map_err(|e| e.to_string())?;
+        if json.contains(secret) {
+            return Err("collected schema contains the credential".to_string());
+        }
+    }
+    let json = serde_json::to_string(samples).map_err(|e| e.to_string())?;
+    if json.contains(secret) {
+        return Err("samples contain the credential".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::ConnectionConfig;
+    use crate::models::{DatabaseInfo, SamplingStrategy, UnifiedDataType};
+    use crate::{Column, Result};
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    /// Adapter serving the standard fixture from memory.
+    struct FixtureAdapter {
+        sampling: bool,
+        username: String,
+    }
+
+    fn column(name: &str, ordinal_position: u32) -> Column {
+        Column {
+            is_primary_key: name == "id",
+            ordinal_position,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::String { max_length: None },
+            )
+        }
+    }
+
+    #[async_trait]
+    impl DatabaseAdapter for FixtureAdapter {
+        async fn test_connection(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn collect_schema(&self) -> Result<DatabaseSchema> {
+            let mut schema = DatabaseSchema::new(DatabaseInfo::new("fixture".to_string()));
+            schema.tables.push(Table {
+                row_count: Some(3),
+                ..Table::new(
+                    "conformance_users".to_string(),
+                    None,
+                    vec![
+                        column("id", 1),
+                        column("email", 2),
+                        column("display_name", 3),
+                    ],
+                )
+            });
+            Ok(schema)
+        }
+
+        async fn sample_table(
+            &self,
+            table_ref: TableRef<'_>,
+            config: &SamplingConfig,
+        ) -> Result<TableSample> {
+            if !self.sampling {
+                return Err(crate::DbSurveyorError::unsupported_feature(
+                    "sampling",
+                    "not supported",
+                ));
+            }
+            let rows: Vec<_> = [
+                json!({"id": 1, "email": "ada@example.com", "display_name": "Ada"}),
+                json!({"id": 2, "email": "grace@example.com", "display_name": "Grace"}),
+                json!({"id": 3, "email": null, "display_name": "Linus"}),
+            ]
+            .into_iter()
+            .take(config.sample_size as usize)
+            .collect();
+            Ok(TableSample {
+                table_name: table_ref.table_name.to_string(),
+                schema_name: None,
+                sample_size: rows.len() as u32,
+                rows,
+                total_rows: Some(3),
+                sampling_strategy: SamplingStrategy::MostRecent {
+                    limit: config.sample_size,
+                },
+                collected_at: chrono::Utc::now(),
+                warnings: Vec::new(),
+                sample_status: None,
+                column_types: Vec::new(),
+            })
+        }
+
+        fn database_type(&self) -> DatabaseType {
+            DatabaseType::SQLite
+        }
+
+        fn supports_feature(&self, feature: AdapterFeature) -> bool {
+            feature != AdapterFeature::DataSampling || self.sampling
+        }
+
+        fn connection_config(&self) -> ConnectionConfig {
+            ConnectionConfig::new("localhost".to_string()).with_username(self.username.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conformant_adapter_passes() {
+        let adapter = FixtureAdapter {
+            sampling: true,
+            username: "reader".to_string(),
+        };
+        let fixture = ConformanceFixture::standard().with_secret("hunter2");
+        let report = run_conformance_suite(&adapter, &fixture).await;
+
+        report.assert_conformant();
+        let names: Vec<&str> = report.checks.iter().map(|check| check.name).collect();
+        assert_eq!(
+            names,
+            [
+                "connection",
+                "feature_flags",
+                "schema_collection",
+                "schema_contract",
+                "sampling",
+                "sampling_limit",
+                "sensitive_columns",
+                "credential_redaction"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adapter_without_sampling_must_reject_sampling() {
+        let adapter = FixtureAdapter {
+            sampling: false,
+            username: "reader".to_string(),
+        };
+        let report = run_conformance_suite(&adapter, &ConformanceFixture::standard()).await;
+        assert!(report.is_conformant(), "{report}");
+    }
+
+    #[tokio::test]
+    async fn test_violations_are_reported() {
+        let adapter = FixtureAdapter {
+            sampling: true,
+            username: "hunter2".to_string(),
+        };
+        let fixture = ConformanceFixture {
+            row_count: 4,
+            columns: vec!["id".to_string(), "created_at".to_string()],
+            ..ConformanceFixture::standard().with_secret("hunter2")
+        };
+        let report = run_conformance_suite(&adapter, &fixture).await;
+
+        let failed: Vec<&str> = report.failures().iter().map(|check| check.name).collect();
+        assert_eq!(
+            failed,
+            [
+                "schema_collection",
+                "sampling",
+                "sampling_limit",
+                "credential_redaction"
+            ]
+        );
+        let rendered = report.to_string();
+        assert!(rendered.contains("4 of 8 checks passed"), "{rendered}");
+        assert!(
+            rendered.contains("column 'created_at' missing"),
+            "{rendered}"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! In-memory adapter serving a fixed schema and sample rows.
+//!
+//! [`MockAdapter`] needs no database or network, so whole collection and
+//! postprocessing pipelines can run deterministically in tests:
+//!
+//! ```rust,ignore
+//! use dbsurveyor_core::{DatabaseAdapter, SamplingConfig, testing::MockAdapter};
+//!
+//! let adapter = MockAdapter::demo();
+//! let schema = adapter.collect_schema().await?;
+//! let samples = adapter.sample_all(&SamplingConfig::default()).await?;
+//! let survey = schema.with_samples(samples);
+//! ```
+
+use crate::adapters::{AdapterFeature, ConnectionConfig, DatabaseAdapter, TableRef};
+use crate::error::DbSurveyorError;
+use crate::models::{
+    Column, DatabaseInfo, DatabaseSchema, DatabaseType, ForeignKey, Index, IndexColumn, PrimaryKey,
+    ReferentialAction, SamplingStrategy, Table, TableSample, UnifiedDataType,
+};
+use crate::{Result, SamplingConfig};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+
+/// Adapter serving a configured [`DatabaseSchema`] and per-table rows.
+///
+/// Samples return the first `sample_size` configured rows of a table, and
+/// every sample carries the schema's `collected_at` timestamp, so output is
+/// identical across runs.
+#[derive(Debug, Clone)]
+pub struct MockAdapter {
+    schema: DatabaseSchema,
+    database_type: DatabaseType,
+    rows: HashMap<String, Vec<Value>>,
+    unsupported: HashSet<AdapterFeature>,
+    failing_tables: HashSet<String>,
+    config: ConnectionConfig,
+}
+
+impl MockAdapter {
+    /// Creates an adapter serving `schema`, with no sample rows.
+    pub fn new(schema: DatabaseSchema) -> Self {
+        let config = ConnectionConfig::new("mock".to_string())
+            .with_database(schema.database_info.name.clone());
+        Self {
+            schema,
+            database_type: DatabaseType::PostgreSQL,
+            rows: HashMap::new(),
+            unsupported: HashSet::new(),
+            failing_tables: HashSet::new(),
+            config,
+        }
+    }
+
+    /// A small shop database: `customers` and `orders` (with a foreign key
+    /// and an index on it), three rows each, collected at
+    /// 2024-01-01T00:00:00Z.
+    pub fn demo() -> Self {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.collection_metadata.collected_at =
+            chrono::DateTime::from_timestamp(1_704_067_200, 0).unwrap_or_default();
+
+        let integer = UnifiedDataType::Integer {
+            bits: 32,
+            signed: true,
+        };
+        let text = UnifiedDataType::String {
+            max_length: Some(255),
+        };
+        let timestamp = UnifiedDataType::DateTime {
+            with_timezone: true,
+        };
+        schema.tables.push(table(
+            "customers",
+            vec![
+                column("id", integer.clone(), 1),
+                column("email", text.clone(), 2),
+                column("display_name", text, 3),
+                column("created_at", timestamp.clone(), 4),
+            ],
+            Vec::new(),
+        ));
+        schema.tables.push(table(
+            "orders",
+            vec![
+                column("id", integer.clone(), 1),
+                column("customer_id", integer, 2),
+                column("total", UnifiedDataType::Float { precision: None }, 3),
+                column("placed_at", timestamp, 4),
+            ],
+            vec![ForeignKey {
+                name: Some("orders_customer_id_fkey".to_string()),
+                columns: vec!["customer_id".to_string()],
+                referenced_table: "customers".to_string(),
+                referenced_schema: Some("public".to_string()),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: Some(ReferentialAction::Cascade),
+                on_update: None,
+            }],
+        ));
+        schema.indexes.push(Index {
+            name: "idx_orders_customer_id".to_string(),
+            table_name: "orders".to_string(),
+            schema: Some("public".to_string()),
+            columns: vec![IndexColumn {
+                name: "customer_id".to_string(),
+                sort_order: None,
+            }],
+            is_unique: false,
+            is_primary: false,
+            index_type: Some("btree".to_string()),
+            options: None,
+        });
+
+        Self::new(schema)
+            .with_rows(
+                "customers",
+                vec![
+                    json!({"id": 1, "email": "ada@example.com", "display_name": "Ada", "created_at": "2023-12-01T09:00:00Z"}),
+                    json!({"id": 2, "email": "grace@example.com", "display_name": "Grace", "created_at": "2023-12-02T09:00:00Z"}),
+                    json!({"id": 3, "email": null, "display_name": "Linus", "created_at": "2023-12-03T09:00:00Z"}),
+                ],
+            )
+            .with_rows(
+                "orders",
+                vec![
+                    json!({"id": 1, "customer_id": 1, "total": 19.99, "placed_at": "2023-12-10T12:00:00Z"}),
+                    json!({"id": 2, "customer_id": 1, "total": 5.0, "placed_at": "2023-12-11T12:00:00Z"}),
+                    json!({"id": 3, "customer_id": 2, "total": 42.5, "placed_at": "2023-12-12T12:00:00Z"}),
+                ],
+            )
+    }
+
+    /// Builder method to set the rows served for a table, in sample order.
+    #[must_use]
+    pub fn with_rows(mut self, table: impl Into<String>, rows: Vec<Value>) -> Self {
+        self.rows.insert(table.into(), rows);
+        self
+    }
+
+    /// Builder method to set the reported database type.
+    #[must_use]
+    pub fn with_database_type(mut self, database_type: DatabaseType) -> Self {
+        self.database_type = database_type;
+        self
+    }
+
+    /// Builder method to report a feature as unsupported.
+    ///
+    /// Without [`AdapterFeature::DataSampling`], `sample_table` fails.
+    #[must_use]
+    pub fn without_feature(mut self, feature: AdapterFeature) -> Self {
+        self.unsupported.insert(feature);
+        self
+    }
+
+    /// Builder method to make sampling a table fail.
+    #[must_use]
+    pub fn with_failing_table(mut self, table: impl Into<String>) -> Self {
+        self.failing_tables.insert(table.into());
+        self
+    }
+
+    /// Builder method to set the reported connection configuration.
+    #[must_use]
+    pub fn with_connection_config(mut self, config: ConnectionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Samples every table of the schema, skipping tables that fail.
+    ///
+    /// # Errors
+    /// Returns an error if sampling is not supported.
+    pub async fn sample_all(&self, config: &SamplingConfig) -> Result<Vec<TableSample>> {
+        if self.unsupported.contains(&AdapterFeature::DataSampling) {
+            return Err(sampling_unsupported());
+        }
+        let mut samples = Vec::new();
+        for table in &self.schema.tables {
+            let table_ref = TableRef {
+                schema_name: table.schema.as_deref(),
+                table_name: &table.name,
+            };
+            if let Ok(sample) = self.sample_table(table_ref, config).await {
+                samples.push(sample);
+            }
+        }
+        Ok(samples)
+    }
+}
+
+fn sampling_unsupported() -> DbSurveyorError {
+    DbSurveyorError::unsupported_feature("Data sampling", "Disabled on this mock adapter")
+}
+
+fn column(name: &str, data_type: UnifiedDataType, ordinal_position: u32) -> Column {
+    Column {
+        is_nullable: name != "id",
+        is_primary_key: name == "id",
+        is_auto_increment: name == "id",
+        ordinal_position,
+        ..Column::new(name.to_string(), data_type)
+    }
+}
+
+fn table(name: &str, columns: Vec<Column>, foreign_keys: Vec<ForeignKey>) -> Table {
+    Table {
+        primary_key: Some(PrimaryKey {
+            name: Some(format!("{name}_pkey")),
+            columns: vec!["id".to_string()],
+        }),
+        foreign_keys,
+        row_count: Some(3),
+        ..Table::new(name.to_string(), Some("public".to_string()), columns)
+    }
+}
+
+#[async_trait]
+impl DatabaseAdapter for MockAdapter {
+    async fn test_connection(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn collect_schema(&self) -> Result<DatabaseSchema> {
+        Ok(self.schema.clone())
+    }
+
+    async fn sample_table(
+        &self,
+        table_ref: TableRef<'_>,
+        config: &SamplingConfig,
+    ) -> Result<TableSample> {
+        if self.unsupported.contains(&AdapterFeature::DataSampling) {
+            return Err(sampling_unsupported());
+        }
+        let table = self
+            .schema
+            .tables
+            .iter()
+            .find(|table| {
+                table.name == table_ref.table_name
+                    && (table_ref.schema_name.is_none()
+                        || table.schema.as_deref() == table_ref.schema_name)
+            })
+            .ok_or_else(|| {
+                DbSurveyorError::query_failed(format!("Table '{table_ref}' does not exist"))
+            })?;
+        if self.failing_tables.contains(&table.name) {
+            return Err(DbSurveyorError::query_failed(format!(
+                "Sampling '{table_ref}' failed"
+            )));
+        }
+
+        let all_rows = self.rows.get(&table.name).map_or(&[][..], Vec::as_slice);
+        let rows: Vec<Value> = all_rows
+            .iter()
+            .take(config.sample_size as usize)
+            .cloned()
+            .collect();
+        let sampling_strategy = match &table.primary_key {
+            Some(_) => SamplingStrategy::MostRecent {
+                limit: config.sample_size,
+            },
+            None => SamplingStrategy::Random {
+                limit: config.sample_size,
+            },
+        };
+
+        Ok(TableSample {
+            table_name: table.name.clone(),
+            schema_name: table.schema.clone(),
+            sample_size: u32::try_from(rows.len()).unwrap_or(u32::MAX),
+            rows,
+            total_rows: Some(all_rows.len() as u64),
+            sampling_strategy,
+            collected_at: self.schema.collection_metadata.collected_at,
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        })
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        self.database_type
+    }
+
+    fn supports_feature(&self, feature: AdapterFeature) -> bool {
+        !self.unsupported.contains(&feature)
+    }
+
+    fn connection_config(&self) -> ConnectionConfig {
+        self.config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_demo_schema_and_samples() {
+        let adapter = MockAdapter::demo();
+        let schema = adapter.collect_schema().await.expect("schema");
+        assert_eq!(schema.database_info.name, "shop");
+        assert_eq!(schema.tables.len(), 2);
+
+        let config = SamplingConfig::new().with_sample_size(2);
+        let samples = adapter.sample_all(&config).await.expect("samples");
+        assert_eq!(samples.len(), 2);
+        assert!(samples.iter().all(|sample| sample.rows.len() == 2));
+        assert_eq!(samples[0].total_rows, Some(3));
+        assert_eq!(
+            samples[0].collected_at,
+            schema.collection_metadata.collected_at
+        );
+
+        // Identical across runs
+        let again = MockAdapter::demo()
+            .collect_schema()
+            .await
+            .expect("schema")
+            .with_samples(
+                MockAdapter::demo()
+                    .sample_all(&config)
+                    .await
+                    .expect("samples"),
+            );
+        assert_eq!(
+            serde_json::to_value(schema.with_samples(samples)).expect("json"),
+            serde_json::to_value(again).expect("json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_and_unsupported_sampling() {
+        let adapter = MockAdapter::demo().with_failing_table("orders");
+        let table_ref = TableRef {
+            schema_name: Some("public"),
+            table_name: "orders",
+        };
+        let config = SamplingConfig::new();
+        assert!(
+            adapter
+                .sample_table(table_ref.clone(), &config)
+                .await
+                .is_err()
+        );
+        assert_eq!(adapter.sample_all(&config).await.expect("samples").len(), 1);
+
+        let missing = TableRef {
+            schema_name: None,
+            table_name: "missing",
+        };
+        assert!(adapter.sample_table(missing, &config).await.is_err());
+
+        let adapter = MockAdapter::demo().without_feature(AdapterFeature::DataSampling);
+        assert!(!adapter.supports_feature(AdapterFeature::DataSampling));
+        assert!(adapter.supports_feature(AdapterFeature::SchemaCollection));
+        assert!(adapter.sample_table(table_ref, &config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_demo_is_conformant() {
+        use crate::testing::{ConformanceFixture, run_conformance_suite};
+
+        let fixture = ConformanceFixture {
+            table: "customers".to_string(),
+            schema: Some("public".to_string()),
+            ..ConformanceFixture::standard()
+        };
+        run_conformance_suite(&MockAdapter::demo(), &fixture)
+            .await
+            .assert_conformant();
+    }
+}
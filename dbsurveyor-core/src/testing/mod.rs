@@ -0,0 +1,28 @@
+//! Test support for database adapter implementations.
+//!
+//! Available with the `testing` feature. [`conformance`] checks any
+//! [`DatabaseAdapter`](crate::DatabaseAdapter) implementation, built-in or
+//! third-party, against the trait contract; [`containers`] starts and seeds
+//! disposable databases for it with testcontainers; [`mock`] serves a
+//! synthetic schema without any database.
+//!
+//! ```rust,ignore
+//! use dbsurveyor_core::testing::{ConformanceFixture, containers, run_conformance_suite};
+//!
+//! let database = containers::start_postgres().await?;
+//! containers::seed_postgres(&database.url).await?;
+//! let adapter = dbsurveyor_core::adapters::create_adapter(&database.url).await?;
+//! run_conformance_suite(&*adapter, &ConformanceFixture::standard())
+//!     .await
+//!     .assert_conformant();
+//! ```
+
+pub mod conformance;
+#[cfg(any(feature = "postgresql", feature = "mysql", feature = "mongodb"))]
+pub mod containers;
+pub mod mock;
+
+pub use conformance::{
+    ConformanceCheck, ConformanceFixture, ConformanceReport, FIXTURE_SQL, run_conformance_suite,
+};
+pub use mock::MockAdapter;
@@ -33,6 +33,10 @@
 use std::sync::OnceLock;
 use thiserror::Error;
 
+mod limits;
+
+pub use limits::{InputLimits, check_input_limits};
+
 /// JSON Schema validation errors with detailed field-level reporting
 #[derive(Debug, Error)]
 pub enum ValidationError {
@@ -58,6 +62,10 @@ pub enum ValidationError {
     #[error("Security validation failed: {reason}")]
     SecurityViolation { reason: String },
 
+    /// Input exceeds a configured parsing limit
+    #[error("Input exceeds the {limit} limit of {max}")]
+    LimitExceeded { limit: String, max: u64 },
+
     /// JSON parsing error
     #[error("JSON parsing failed: {source}")]
     JsonParsing {
@@ -70,257 +78,7 @@ pub enum ValidationError {
 const SUPPORTED_VERSIONS: &[&str] = &[crate::models::FORMAT_VERSION];
 
 /// Embedded JSON Schema for v1.0 format validation
-const SCHEMA_V1_0: &str = r##"{
-  "$schema": "https://json-schema.org/draft/2020-12/schema",
-  "title": "DBSurveyor Database Schema Collection Format v1.0",
-  "type": "object",
-  "required": ["format_version", "database_info", "collection_metadata"],
-  "properties": {
-    "format_version": {
-      "type": "string",
-      "pattern": "^1\\.0$"
-    },
-    "database_info": {
-      "type": "object",
-      "required": ["name", "access_level", "collection_status"],
-      "properties": {
-        "name": { "type": "string", "minLength": 1 },
-        "version": { "type": ["string", "null"] },
-        "size_bytes": { "type": ["integer", "null"], "minimum": 0 },
-        "encoding": { "type": ["string", "null"] },
-        "collation": { "type": ["string", "null"] },
-        "owner": { "type": ["string", "null"] },
-        "is_system_database": { "type": "boolean", "default": false },
-        "access_level": { "enum": ["Full", "Limited", "None"] },
-        "collection_status": {
-          "oneOf": [
-            { "const": "Success" },
-            {
-              "type": "object",
-              "required": ["Failed"],
-              "additionalProperties": false,
-              "properties": {
-                "Failed": {
-                  "type": "object",
-                  "required": ["error"],
-                  "properties": { "error": { "type": "string" } }
-                }
-              }
-            },
-            {
-              "type": "object",
-              "required": ["Skipped"],
-              "additionalProperties": false,
-              "properties": {
-                "Skipped": {
-                  "type": "object",
-                  "required": ["reason"],
-                  "properties": { "reason": { "type": "string" } }
-                }
-              }
-            }
-          ]
-        }
-      }
-    },
-    "tables": { "type": "array", "items": { "$ref": "#/$defs/Table" }, "default": [] },
-    "views": { "type": "array", "default": [] },
-    "indexes": { "type": "array", "default": [] },
-    "constraints": { "type": "array", "default": [] },
-    "procedures": { "type": "array", "default": [] },
-    "functions": { "type": "array", "default": [] },
-    "triggers": { "type": "array", "default": [] },
-    "custom_types": { "type": "array", "default": [] },
-    "samples": { "type": ["array", "null"] },
-    "collection_metadata": {
-      "type": "object",
-      "required": ["collected_at", "collection_duration_ms", "collector_version"],
-      "properties": {
-        "collected_at": { "type": "string", "format": "date-time" },
-        "collection_duration_ms": { "type": "integer", "minimum": 0 },
-        "collector_version": { "type": "string", "minLength": 1 },
-        "warnings": { "type": "array", "items": { "type": "string" }, "default": [] }
-      }
-    }
-  },
-  "$defs": {
-    "Table": {
-      "type": "object",
-      "required": ["name", "columns"],
-      "properties": {
-        "name": { "type": "string", "minLength": 1 },
-        "schema": { "type": ["string", "null"] },
-        "columns": { "type": "array", "items": { "$ref": "#/$defs/Column" }, "minItems": 1 },
-        "primary_key": { "$ref": "#/$defs/PrimaryKey" },
-        "foreign_keys": { "type": "array", "items": { "$ref": "#/$defs/ForeignKey" } },
-        "indexes": { "type": "array" },
-        "constraints": { "type": "array" },
-        "comment": { "type": ["string", "null"] },
-        "row_count": { "type": ["integer", "null"], "minimum": 0 }
-      }
-    },
-    "Column": {
-      "type": "object",
-      "required": ["name", "data_type", "is_nullable", "ordinal_position"],
-      "properties": {
-        "name": { "type": "string", "minLength": 1 },
-        "data_type": { "$ref": "#/$defs/UnifiedDataType" },
-        "is_nullable": { "type": "boolean" },
-        "is_primary_key": { "type": "boolean" },
-        "is_auto_increment": { "type": "boolean" },
-        "default_value": { "type": ["string", "null"] },
-        "comment": { "type": ["string", "null"] },
-        "ordinal_position": { "type": "integer", "minimum": 1 }
-      }
-    },
-    "UnifiedDataType": {
-      "oneOf": [
-        { "const": "Boolean" },
-        { "const": "Date" },
-        { "const": "Json" },
-        { "const": "Uuid" },
-        {
-          "type": "object",
-          "required": ["String"],
-          "additionalProperties": false,
-          "properties": {
-            "String": {
-              "type": "object",
-              "additionalProperties": false,
-              "properties": {
-                "max_length": { "type": ["integer", "null"], "minimum": 1 }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Integer"],
-          "additionalProperties": false,
-          "properties": {
-            "Integer": {
-              "type": "object",
-              "additionalProperties": false,
-              "required": ["bits", "signed"],
-              "properties": {
-                "bits": { "type": "integer", "enum": [8, 16, 32, 64, 128] },
-                "signed": { "type": "boolean" }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Float"],
-          "additionalProperties": false,
-          "properties": {
-            "Float": {
-              "type": "object",
-              "additionalProperties": false,
-              "properties": {
-                "precision": { "type": ["integer", "null"], "minimum": 1, "maximum": 255 }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["DateTime"],
-          "additionalProperties": false,
-          "properties": {
-            "DateTime": {
-              "type": "object",
-              "additionalProperties": false,
-              "required": ["with_timezone"],
-              "properties": {
-                "with_timezone": { "type": "boolean" }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Time"],
-          "additionalProperties": false,
-          "properties": {
-            "Time": {
-              "type": "object",
-              "additionalProperties": false,
-              "required": ["with_timezone"],
-              "properties": {
-                "with_timezone": { "type": "boolean" }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Binary"],
-          "additionalProperties": false,
-          "properties": {
-            "Binary": {
-              "type": "object",
-              "additionalProperties": false,
-              "properties": {
-                "max_length": { "type": ["integer", "null"], "minimum": 1 }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Array"],
-          "additionalProperties": false,
-          "properties": {
-            "Array": {
-              "type": "object",
-              "additionalProperties": false,
-              "required": ["element_type"],
-              "properties": {
-                "element_type": { "$ref": "#/$defs/UnifiedDataType" }
-              }
-            }
-          }
-        },
-        {
-          "type": "object",
-          "required": ["Custom"],
-          "additionalProperties": false,
-          "properties": {
-            "Custom": {
-              "type": "object",
-              "additionalProperties": false,
-              "required": ["type_name"],
-              "properties": {
-                "type_name": { "type": "string", "minLength": 1 }
-              }
-            }
-          }
-        }
-      ]
-    },
-    "PrimaryKey": {
-      "type": ["object", "null"],
-      "properties": {
-        "name": { "type": ["string", "null"] },
-        "columns": { "type": "array", "items": { "type": "string" }, "minItems": 1 }
-      }
-    },
-    "ForeignKey": {
-      "type": "object",
-      "required": ["referenced_table", "referenced_columns", "columns"],
-      "properties": {
-        "name": { "type": ["string", "null"] },
-        "columns": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
-        "referenced_table": { "type": "string", "minLength": 1 },
-        "referenced_schema": { "type": ["string", "null"] },
-        "referenced_columns": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
-        "on_delete": { "enum": ["Cascade", "SetNull", "SetDefault", "Restrict", "NoAction", null] },
-        "on_update": { "enum": ["Cascade", "SetNull", "SetDefault", "Restrict", "NoAction", null] }
-      }
-    }
-  }
-}"##;
+const SCHEMA_V1_0: &str = include_str!("../schemas/dbsurveyor-schema-v1.0.json");
 
 /// Compiled JSON Schema instance (initialized once)
 static COMPILED_SCHEMA: OnceLock<Validator> = OnceLock::new();
@@ -419,6 +177,73 @@ pub fn validate_schema_output(json_value: &Value) -> Result<(), ValidationError>
     Ok(())
 }
 
+/// Validate a DatabaseServerSchema JSON output
+///
+/// Each entry of `databases` is validated as a [`validate_schema_output`]
+/// document; the server-level fields get the same format version and
+/// security checks.
+///
+/// # Errors
+/// Returns validation errors for a malformed server document, or the first
+/// failing database, with its position prefixed to the error messages.
+pub fn validate_server_schema_output(json_value: &Value) -> Result<(), ValidationError> {
+    validate_format_version(json_value)?;
+
+    let missing = ["server_info", "databases", "collection_metadata"]
+        .iter()
+        .filter(|field| json_value.get(**field).is_none())
+        .map(|field| format!("Missing required field '{}'", field))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(ValidationError::ValidationFailed {
+            error_count: missing.len(),
+            errors: missing,
+        });
+    }
+
+    let databases = json_value
+        .get("databases")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ValidationError::ValidationFailed {
+            error_count: 1,
+            errors: vec!["Field 'databases' must be an array".to_string()],
+        })?;
+
+    for (index, database) in databases.iter().enumerate() {
+        validate_schema_output(database).map_err(|e| match e {
+            ValidationError::ValidationFailed {
+                error_count,
+                errors,
+            } => ValidationError::ValidationFailed {
+                error_count,
+                errors: errors
+                    .into_iter()
+                    .map(|error| format!("databases[{}]: {}", index, error))
+                    .collect(),
+            },
+            ValidationError::SecurityViolation { reason } => ValidationError::SecurityViolation {
+                reason: format!("databases[{}]: {}", index, reason),
+            },
+            other => other,
+        })?;
+    }
+
+    // Databases were checked above; cover the server-level fields
+    for field in [
+        "server_info",
+        "manifest",
+        "tenant_groups",
+        "collection_metadata",
+    ] {
+        if let Some(value) = json_value.get(field) {
+            validate_no_credentials_recursive(value, field)?;
+            validate_no_connection_strings_recursive(value, field)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate format version compatibility
 ///
 /// Ensures the format_version field is present and supported.
@@ -600,6 +425,23 @@ fn validate_no_connection_strings_recursive(
 pub fn validate_and_parse_schema(
     json_str: &str,
 ) -> Result<crate::models::DatabaseSchema, ValidationError> {
+    validate_and_parse_schema_with_limits(json_str, &InputLimits::default())
+}
+
+/// Validate and load a DatabaseSchema from JSON, rejecting input over `limits`
+///
+/// The limits are checked on the raw text before parsing, so oversized or
+/// deeply nested input is rejected without being materialized.
+///
+/// # Errors
+/// Returns `ValidationError::LimitExceeded` if the input exceeds `limits`,
+/// otherwise the same errors as [`validate_and_parse_schema`].
+pub fn validate_and_parse_schema_with_limits(
+    json_str: &str,
+    limits: &InputLimits,
+) -> Result<crate::models::DatabaseSchema, ValidationError> {
+    check_input_limits(json_str, limits)?;
+
     // Parse JSON
     let json_value: Value = serde_json::from_str(json_str)?;
 
@@ -613,6 +455,38 @@ pub fn validate_and_parse_schema(
     Ok(schema)
 }
 
+/// Validate and load a survey file of either shape, rejecting input over `limits`
+///
+/// Documents with a top-level `databases` array are parsed as a
+/// [`crate::models::DatabaseServerSchema`]; anything else as a single
+/// [`crate::models::DatabaseSchema`].
+///
+/// # Errors
+/// Returns the same errors as [`validate_and_parse_schema_with_limits`], or
+/// those of [`validate_server_schema_output`] for server documents.
+pub fn validate_and_parse_survey_with_limits(
+    json_str: &str,
+    limits: &InputLimits,
+) -> Result<crate::models::SurveyDocument, ValidationError> {
+    use crate::models::SurveyDocument;
+
+    check_input_limits(json_str, limits)?;
+
+    let json_value: Value = serde_json::from_str(json_str)?;
+
+    if json_value.get("databases").is_some_and(Value::is_array) {
+        validate_server_schema_output(&json_value)?;
+        let server = serde_json::from_value(json_value)
+            .map_err(|e| ValidationError::JsonParsing { source: e })?;
+        Ok(SurveyDocument::Server(Box::new(server)))
+    } else {
+        validate_schema_output(&json_value)?;
+        let schema = serde_json::from_value(json_value)
+            .map_err(|e| ValidationError::JsonParsing { source: e })?;
+        Ok(SurveyDocument::Database(Box::new(schema)))
+    }
+}
+
 /// Get the embedded JSON Schema as a parsed Value for external use
 ///
 /// This function provides access to the compiled JSON Schema for tools
@@ -623,5 +497,24 @@ pub fn get_schema_definition() -> Result<Value, ValidationError> {
     })
 }
 
+/// Get the JSON Schema for a specific survey format version
+///
+/// Used by tools that publish the schema for a version other than the one
+/// this build writes, such as `dbsurveyor schema --format-version`.
+///
+/// # Errors
+/// Returns `ValidationError::UnsupportedVersion` if no schema is embedded for
+/// `version`.
+pub fn get_schema_definition_for_version(version: &str) -> Result<Value, ValidationError> {
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        return Err(ValidationError::UnsupportedVersion {
+            version: version.to_string(),
+            supported: SUPPORTED_VERSIONS.iter().map(|s| s.to_string()).collect(),
+        });
+    }
+
+    get_schema_definition()
+}
+
 #[cfg(test)]
 mod tests;
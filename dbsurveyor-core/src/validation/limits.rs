@@ -0,0 +1,217 @@
+//! Resource limits for parsing untrusted survey files.
+//!
+//! Survey files may come from sources the postprocessor does not control.
+//! [`check_input_limits`] scans raw JSON text before it is parsed so that a
+//! crafted file cannot exhaust memory or stack with deep nesting, huge
+//! strings, or enormous arrays.
+
+use super::ValidationError;
+
+/// Limits enforced when loading a survey file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+    /// Maximum size in bytes of the file, and of its content after
+    /// decompression or decryption
+    pub max_file_size_bytes: u64,
+    /// Maximum nesting depth of JSON objects and arrays
+    pub max_json_depth: usize,
+    /// Maximum length in bytes of any JSON string, including object keys
+    pub max_string_length: usize,
+    /// Maximum number of elements in any single JSON array
+    pub max_array_items: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size_bytes: 512 * 1024 * 1024,
+            max_json_depth: 128,
+            max_string_length: 16 * 1024 * 1024,
+            max_array_items: 1_000_000,
+        }
+    }
+}
+
+impl InputLimits {
+    /// Builder method to set the maximum file size.
+    pub fn with_max_file_size_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_size_bytes = bytes;
+        self
+    }
+
+    /// Builder method to set the maximum JSON nesting depth.
+    pub fn with_max_json_depth(mut self, depth: usize) -> Self {
+        self.max_json_depth = depth;
+        self
+    }
+
+    /// Builder method to set the maximum string length.
+    pub fn with_max_string_length(mut self, length: usize) -> Self {
+        self.max_string_length = length;
+        self
+    }
+
+    /// Builder method to set the maximum number of items per array.
+    pub fn with_max_array_items(mut self, items: usize) -> Self {
+        self.max_array_items = items;
+        self
+    }
+
+    /// Checks a byte count against `max_file_size_bytes`.
+    ///
+    /// # Errors
+    /// Returns `ValidationError::LimitExceeded` if `size` is over the limit.
+    pub fn check_size(&self, size: u64) -> Result<(), ValidationError> {
+        if size > self.max_file_size_bytes {
+            return Err(limit_exceeded(
+                "file size (bytes)",
+                self.max_file_size_bytes,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Open container while scanning
+struct Frame {
+    is_array: bool,
+    items: usize,
+    /// Whether the next value starts a new array element
+    expecting_item: bool,
+}
+
+/// Scan raw JSON text and reject it if it exceeds any of `limits`.
+///
+/// This is a single pass over the bytes that does not allocate per value;
+/// it does not check syntax, which is left to the JSON parser.
+///
+/// # Errors
+/// Returns `ValidationError::LimitExceeded` for the first limit exceeded.
+pub fn check_input_limits(json_str: &str, limits: &InputLimits) -> Result<(), ValidationError> {
+    limits.check_size(json_str.len() as u64)?;
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut bytes = json_str.bytes();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b' ' | b'\t' | b'\n' | b'\r' | b':' => continue,
+            b',' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expecting_item = frame.is_array;
+                }
+                continue;
+            }
+            b']' | b'}' => {
+                stack.pop();
+                continue;
+            }
+            _ => {}
+        }
+
+        // Any other byte starts a value (or an object key)
+        if let Some(frame) = stack.last_mut()
+            && frame.expecting_item
+        {
+            frame.items = frame.items.saturating_add(1);
+            frame.expecting_item = false;
+            if frame.items > limits.max_array_items {
+                return Err(limit_exceeded("array items", limits.max_array_items));
+            }
+        }
+
+        match byte {
+            b'[' | b'{' => {
+                if stack.len() >= limits.max_json_depth {
+                    return Err(limit_exceeded("JSON depth", limits.max_json_depth));
+                }
+                stack.push(Frame {
+                    is_array: byte == b'[',
+                    items: 0,
+                    expecting_item: byte == b'[',
+                });
+            }
+            b'"' => {
+                let mut length: usize = 0;
+                while let Some(byte) = bytes.next() {
+                    match byte {
+                        b'"' => break,
+                        b'\\' => {
+                            bytes.next();
+                        }
+                        _ => {}
+                    }
+                    length = length.saturating_add(1);
+                    if length > limits.max_string_length {
+                        return Err(limit_exceeded("string length", limits.max_string_length));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn limit_exceeded(limit: &str, max: impl TryInto<u64>) -> ValidationError {
+    ValidationError::LimitExceeded {
+        limit: limit.to_string(),
+        max: max.try_into().unwrap_or(u64::MAX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_limits() -> InputLimits {
+        InputLimits::default()
+            .with_max_json_depth(3)
+            .with_max_string_length(8)
+            .with_max_array_items(3)
+    }
+
+    #[test]
+    fn test_within_limits() {
+        let json = r#"{"tables": [{"name": "users"}, [], "a\"b"]}"#;
+        assert!(check_input_limits(json, &small_limits()).is_ok());
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let result = check_input_limits("[[[[]]]]", &small_limits());
+        assert!(matches!(
+            result,
+            Err(ValidationError::LimitExceeded { ref limit, max: 3 }) if limit == "JSON depth"
+        ));
+    }
+
+    #[test]
+    fn test_string_length_limit() {
+        assert!(check_input_limits(r#"["12345678"]"#, &small_limits()).is_ok());
+        let result = check_input_limits(r#"{"123456789": 1}"#, &small_limits());
+        assert!(matches!(
+            result,
+            Err(ValidationError::LimitExceeded { ref limit, .. }) if limit == "string length"
+        ));
+    }
+
+    #[test]
+    fn test_array_items_limit() {
+        // Commas inside strings and nested containers do not count as items
+        assert!(check_input_limits(r#"[1, "a,b", [4, 5, 6]]"#, &small_limits()).is_ok());
+        let result = check_input_limits("[1, 2, 3, 4]", &small_limits());
+        assert!(matches!(
+            result,
+            Err(ValidationError::LimitExceeded { ref limit, .. }) if limit == "array items"
+        ));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let limits = InputLimits::default().with_max_file_size_bytes(4);
+        assert!(limits.check_size(4).is_ok());
+        assert!(check_input_limits("[1, 2]", &limits).is_err());
+    }
+}
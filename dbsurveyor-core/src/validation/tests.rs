@@ -613,6 +613,18 @@ fn test_get_schema_definition_success() {
     assert!(schema.get("properties").is_some());
 }
 
+#[test]
+fn test_get_schema_definition_for_version() {
+    let schema = get_schema_definition_for_version("1.0").expect("v1.0 schema");
+    assert_eq!(schema, get_schema_definition().expect("embedded schema"));
+
+    let result = get_schema_definition_for_version("2.0");
+    assert!(matches!(
+        result,
+        Err(ValidationError::UnsupportedVersion { ref version, .. }) if version == "2.0"
+    ));
+}
+
 #[test]
 fn test_real_database_schema_validation() {
     setup();
@@ -630,44 +642,39 @@ fn test_real_database_schema_validation() {
 
     // Add a sample table
     let table = Table {
-        name: "users".to_string(),
-        schema: Some("public".to_string()),
-        columns: vec![
-            Column {
-                name: "id".to_string(),
-                data_type: UnifiedDataType::Integer {
-                    bits: 32,
-                    signed: true,
-                },
-                is_nullable: false,
-                is_primary_key: true,
-                is_auto_increment: true,
-                default_value: None,
-                comment: None,
-                ordinal_position: 1,
-            },
-            Column {
-                name: "email".to_string(),
-                data_type: UnifiedDataType::String {
-                    max_length: Some(255),
-                },
-                is_nullable: false,
-                is_primary_key: false,
-                is_auto_increment: false,
-                default_value: None,
-                comment: None,
-                ordinal_position: 2,
-            },
-        ],
         primary_key: Some(PrimaryKey {
             name: Some("users_pkey".to_string()),
             columns: vec!["id".to_string()],
         }),
-        foreign_keys: vec![],
-        indexes: vec![],
-        constraints: vec![],
-        comment: None,
         row_count: Some(1000),
+        ..Table::new(
+            "users".to_string(),
+            Some("public".to_string()),
+            vec![
+                Column {
+                    is_nullable: false,
+                    is_primary_key: true,
+                    is_auto_increment: true,
+                    ..Column::new(
+                        "id".to_string(),
+                        UnifiedDataType::Integer {
+                            bits: 32,
+                            signed: true,
+                        },
+                    )
+                },
+                Column {
+                    is_nullable: false,
+                    ordinal_position: 2,
+                    ..Column::new(
+                        "email".to_string(),
+                        UnifiedDataType::String {
+                            max_length: Some(255),
+                        },
+                    )
+                },
+            ],
+        )
     };
 
     schema.tables.push(table);
@@ -760,6 +767,57 @@ fn test_array_data_type_validation() {
     assert!(validate_schema_output(&schema_with_array).is_ok());
 }
 
+#[test]
+fn test_decimal_interval_bit_and_network_types_validate() {
+    setup();
+
+    let data_types = [
+        UnifiedDataType::Decimal {
+            precision: Some(10),
+            scale: Some(2),
+        },
+        UnifiedDataType::Decimal {
+            precision: None,
+            scale: None,
+        },
+        UnifiedDataType::Interval,
+        UnifiedDataType::Bit { length: Some(8) },
+        UnifiedDataType::NetworkAddress,
+    ];
+    let columns: Vec<_> = data_types
+        .iter()
+        .enumerate()
+        .map(|(i, data_type)| {
+            json!({
+                "name": format!("col_{}", i),
+                "data_type": data_type,
+                "is_nullable": true,
+                "ordinal_position": i + 1
+            })
+        })
+        .collect();
+
+    let schema = json!({
+        "format_version": "1.0",
+        "database_info": {
+            "name": "test_db",
+            "access_level": "Full",
+            "collection_status": "Success"
+        },
+        "tables": [{
+            "name": "ledger",
+            "columns": columns
+        }],
+        "collection_metadata": {
+            "collected_at": "2024-01-15T10:30:00Z",
+            "collection_duration_ms": 1500,
+            "collector_version": "1.0.0"
+        }
+    });
+
+    assert!(validate_schema_output(&schema).is_ok());
+}
+
 #[test]
 fn test_custom_data_type_validation() {
     setup();
@@ -902,4 +960,304 @@ fn test_collection_status_variants() {
         }
     });
     assert!(validate_schema_output(&skipped_schema).is_ok());
+
+    // Test Partial status with recorded object failures
+    let partial_schema = json!({
+        "format_version": "1.0",
+        "database_info": {
+            "name": "app_db",
+            "access_level": "Full",
+            "collection_status": {
+                "Partial": {
+                    "failed_objects": 1
+                }
+            }
+        },
+        "collection_metadata": {
+            "collected_at": "2024-01-15T10:30:00Z",
+            "collection_duration_ms": 900,
+            "collector_version": "1.0.0",
+            "object_failures": [
+                {
+                    "schema": "public",
+                    "name": "orders",
+                    "stage": "indexes",
+                    "error": "permission denied"
+                }
+            ]
+        }
+    });
+    assert!(validate_schema_output(&partial_schema).is_ok());
+}
+
+#[test]
+fn test_structured_and_legacy_warnings_validation() {
+    setup();
+
+    let schema_with_warnings = |warnings: serde_json::Value| {
+        json!({
+            "format_version": "1.0",
+            "database_info": {
+                "name": "app_db",
+                "access_level": "Full",
+                "collection_status": "Success"
+            },
+            "collection_metadata": {
+                "collected_at": "2024-01-15T10:30:00Z",
+                "collection_duration_ms": 900,
+                "collector_version": "1.0.0",
+                "warnings": warnings
+            }
+        })
+    };
+
+    let mixed = schema_with_warnings(json!([
+        "Legacy warning",
+        {
+            "severity": "error",
+            "category": "object_failure",
+            "message": "Failed to collect indexes: permission denied",
+            "objects": ["public.orders", "public.items"],
+            "count": 2
+        }
+    ]));
+    assert!(validate_schema_output(&mixed).is_ok());
+
+    let unknown_severity = schema_with_warnings(json!([
+        { "severity": "fatal", "message": "boom" }
+    ]));
+    assert!(validate_schema_output(&unknown_severity).is_err());
+}
+
+fn server_schema(databases: Vec<DatabaseSchema>) -> DatabaseServerSchema {
+    DatabaseServerSchema::new(
+        ServerInfo {
+            server_type: DatabaseType::PostgreSQL,
+            version: "16.2".to_string(),
+            host: "localhost".to_string(),
+            port: Some(5432),
+            total_databases: databases.len(),
+            collected_databases: databases.len(),
+            system_databases_excluded: 0,
+            connection_user: "surveyor".to_string(),
+            has_superuser_privileges: false,
+            collection_mode: CollectionMode::MultiDatabase {
+                discovered: databases.len(),
+                collected: databases.len(),
+                failed: 0,
+            },
+            timezone: None,
+            role: None,
+        },
+        databases,
+        CollectionMetadata {
+            collected_at: chrono::Utc::now(),
+            collection_duration_ms: 0,
+            collector_version: "1.0.0".to_string(),
+            warnings: Vec::new(),
+            object_failures: Vec::new(),
+            degraded: Vec::new(),
+            read_only: None,
+            integrity: None,
+            auxiliary_files: Vec::new(),
+            settings: None,
+        },
+    )
+}
+
+#[test]
+fn test_server_schema_round_trips_with_manifest() {
+    setup();
+
+    let mut failed = DatabaseInfo::new("archive".to_string());
+    failed.collection_status = CollectionStatus::Failed {
+        error: "permission denied".to_string(),
+    };
+    let server = server_schema(vec![
+        DatabaseSchema::new(DatabaseInfo::new("sales".to_string())),
+        DatabaseSchema::new(failed),
+    ]);
+    assert_eq!(server.manifest.len(), 2);
+    assert_eq!(server.manifest[0].name, "sales");
+
+    let json_str = serde_json::to_string(&server).unwrap();
+    validate_server_schema_output(&serde_json::from_str(&json_str).unwrap()).unwrap();
+
+    match validate_and_parse_survey_with_limits(&json_str, &InputLimits::default()).unwrap() {
+        SurveyDocument::Server(parsed) => assert_eq!(*parsed, server),
+        SurveyDocument::Database(_) => panic!("expected a server survey"),
+    }
+}
+
+#[test]
+fn test_survey_parses_single_database_schema() {
+    setup();
+
+    let schema = DatabaseSchema::new(DatabaseInfo::new("sales".to_string()));
+    let json_str = serde_json::to_string(&schema).unwrap();
+
+    let survey = validate_and_parse_survey_with_limits(&json_str, &InputLimits::default()).unwrap();
+    assert!(matches!(survey, SurveyDocument::Database(_)));
+    assert_eq!(survey.databases().len(), 1);
+}
+
+#[test]
+fn test_server_schema_reports_failing_database() {
+    setup();
+
+    let mut leaky = DatabaseInfo::new("crm".to_string());
+    leaky.owner = Some("postgres://admin:secret@db/crm".to_string());
+    let server = server_schema(vec![
+        DatabaseSchema::new(DatabaseInfo::new("sales".to_string())),
+        DatabaseSchema::new(leaky),
+    ]);
+
+    let result = validate_server_schema_output(&serde_json::to_value(&server).unwrap());
+    match result {
+        Err(ValidationError::SecurityViolation { reason }) => {
+            assert!(reason.starts_with("databases[1]: "), "{}", reason);
+        }
+        other => panic!("expected a security violation, got {:?}", other),
+    }
+}
+
+/// Deserializer that records the field names a struct declares to serde
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> serde::Deserializer<'de> for FieldNames<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(serde::de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Serialized field names of `T`
+fn serde_fields<T: serde::de::DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    assert!(
+        !fields.is_empty(),
+        "{} is not a struct",
+        std::any::type_name::<T>()
+    );
+    fields
+}
+
+/// Fails when a model field is written to surveys but not described by the
+/// published schema
+fn assert_schema_describes<T: serde::de::DeserializeOwned>(schema: &Value, pointer: &str) {
+    let properties = schema
+        .pointer(&format!("{}/properties", pointer))
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| panic!("schema has no properties at {}", pointer));
+    let missing: Vec<_> = serde_fields::<T>()
+        .iter()
+        .filter(|field| !properties.contains_key(**field))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "{} fields missing from schema {}: {:?}",
+        std::any::type_name::<T>(),
+        pointer,
+        missing
+    );
+}
+
+#[test]
+fn test_schema_describes_every_model_field() {
+    let schema = get_schema_definition().unwrap();
+
+    assert_schema_describes::<DatabaseSchema>(&schema, "");
+    assert_schema_describes::<DatabaseInfo>(&schema, "/properties/database_info");
+    assert_schema_describes::<AccessProbe>(
+        &schema,
+        "/properties/database_info/properties/access_probe",
+    );
+    assert_schema_describes::<CollectionMetadata>(&schema, "/properties/collection_metadata");
+    assert_schema_describes::<CollectionSettings>(
+        &schema,
+        "/properties/collection_metadata/properties/settings",
+    );
+    assert_schema_describes::<SamplingSettings>(
+        &schema,
+        "/properties/collection_metadata/properties/settings/properties/sampling",
+    );
+    assert_schema_describes::<Table>(&schema, "/$defs/Table");
+    assert_schema_describes::<Column>(&schema, "/$defs/Column");
+    assert_schema_describes::<GeneratedColumn>(&schema, "/$defs/GeneratedColumn");
+    assert_schema_describes::<ColumnStorage>(&schema, "/$defs/ColumnStorage");
+    assert_schema_describes::<ColumnIdentity>(&schema, "/$defs/ColumnIdentity");
+    assert_schema_describes::<ColumnMasking>(&schema, "/$defs/ColumnMasking");
+    assert_schema_describes::<SchemaValidator>(&schema, "/$defs/SchemaValidator");
+    assert_schema_describes::<PartitionInfo>(&schema, "/$defs/PartitionInfo");
+    assert_schema_describes::<Partition>(
+        &schema,
+        "/$defs/PartitionInfo/properties/partitions/items",
+    );
+    assert_schema_describes::<VirtualTable>(&schema, "/$defs/VirtualTable");
+    assert_schema_describes::<TableOptions>(&schema, "/$defs/TableOptions");
+    assert_schema_describes::<TableInheritance>(&schema, "/$defs/TableInheritance");
+    assert_schema_describes::<RelatedTable>(&schema, "/$defs/RelatedTable");
+    assert_schema_describes::<PrimaryKey>(&schema, "/$defs/PrimaryKey");
+    assert_schema_describes::<ForeignKey>(&schema, "/$defs/ForeignKey");
+}
+
+#[test]
+fn test_schema_accepts_fully_populated_column() {
+    setup();
+
+    let mut column = Column::new(
+        "id".to_string(),
+        UnifiedDataType::Integer {
+            bits: 64,
+            signed: true,
+        },
+    );
+    column.normalized_default = Some(DefaultValue::SequenceNextval {
+        sequence: "public.orders_id_seq".to_string(),
+    });
+    column.declared_type = Some("bigint".to_string());
+    column.collation = Some("C".to_string());
+    column.character_set = Some("utf8mb4".to_string());
+    column.storage = Some(ColumnStorage {
+        strategy: StorageStrategy::Plain,
+        compression: None,
+    });
+    column.identity = Some(ColumnIdentity {
+        generation: IdentityGeneration::Serial,
+        sequence: Some("public.orders_id_seq".to_string()),
+    });
+    column.masking = Some(ColumnMasking {
+        provider: "ddm".to_string(),
+        rule: "MASKED WITH FUNCTION default()".to_string(),
+    });
+    let mut schema = DatabaseSchema::new(DatabaseInfo::new("sales".to_string()));
+    schema.tables.push(Table::new(
+        "orders".to_string(),
+        Some("public".to_string()),
+        vec![column],
+    ));
+
+    assert!(validate_schema_output(&serde_json::to_value(&schema).unwrap()).is_ok());
+
+    let mut value = serde_json::to_value(&schema).unwrap();
+    value["tables"][0]["columns"][0]["identity"]["generation"] = json!("Sometimes");
+    assert!(validate_schema_output(&value).is_err());
 }
@@ -0,0 +1,270 @@
+//! Server versions each adapter is tested against.
+//!
+//! Catalog layouts change between releases: PostgreSQL added
+//! `pg_proc.prokind` in 11, and MySQL and MariaDB diverged in what their
+//! `information_schema` reports. Each adapter is tested against a range of
+//! releases. Where it has version-specific query variants it switches to
+//! them for older servers; a server outside the tested range is recorded
+//! as a compatibility warning either way, so gaps in the survey can be told
+//! apart from missing privileges.
+
+use crate::models::{CollectionWarning, DatabaseInfo, DatabaseType, WarningCategory};
+use std::fmt;
+
+/// A server release line: major and minor version, or the major version
+/// alone for engines that number releases that way (PostgreSQL 10 and
+/// later).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ServerVersion {
+    pub major: u32,
+    pub minor: Option<u32>,
+}
+
+impl ServerVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor: Some(minor),
+        }
+    }
+
+    pub const fn major(major: u32) -> Self {
+        Self { major, minor: None }
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{}", self.major, minor),
+            None => write!(f, "{}", self.major),
+        }
+    }
+}
+
+/// A server product with its own release numbering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerProduct {
+    PostgreSQL,
+    MySQL,
+    MariaDB,
+    SQLite,
+    MongoDB,
+}
+
+impl ServerProduct {
+    /// The product behind `engine`, told apart from the version string
+    /// where one engine adapter serves several products. `None` for
+    /// engines without a released adapter.
+    pub fn detect(engine: &DatabaseType, version: &str) -> Option<Self> {
+        match engine {
+            DatabaseType::PostgreSQL => Some(Self::PostgreSQL),
+            DatabaseType::MySQL if version.contains("MariaDB") => Some(Self::MariaDB),
+            DatabaseType::MySQL => Some(Self::MySQL),
+            DatabaseType::SQLite => Some(Self::SQLite),
+            DatabaseType::MongoDB => Some(Self::MongoDB),
+            DatabaseType::SqlServer => None,
+        }
+    }
+
+    /// Oldest and newest release lines the adapter is tested against.
+    pub const fn tested(self) -> (ServerVersion, ServerVersion) {
+        match self {
+            Self::PostgreSQL => (ServerVersion::major(11), ServerVersion::major(17)),
+            Self::MySQL => (ServerVersion::new(8, 0), ServerVersion::new(8, 4)),
+            Self::MariaDB => (ServerVersion::new(10, 5), ServerVersion::new(11, 4)),
+            Self::SQLite => (ServerVersion::new(3, 31), ServerVersion::new(3, 50)),
+            Self::MongoDB => (ServerVersion::new(5, 0), ServerVersion::new(8, 0)),
+        }
+    }
+
+    /// Parses the release line from a version string such as
+    /// `PostgreSQL 9.6.24 on x86_64-pc-linux-gnu`, `10.11.6-MariaDB`, or
+    /// `SQLite 3.45.1`.
+    pub fn release(self, version: &str) -> Option<ServerVersion> {
+        let number = version
+            .split_whitespace()
+            .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))?;
+        // MariaDB may report a 5.5.5- compatibility prefix before its own version
+        let number = match self {
+            Self::MariaDB => number.strip_prefix("5.5.5-").unwrap_or(number),
+            _ => number,
+        };
+        let mut parts = number
+            .split(|c: char| !c.is_ascii_digit())
+            .map(str::parse::<u32>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().and_then(|minor| minor.ok()).unwrap_or(0);
+        Some(match self {
+            Self::PostgreSQL if major >= 10 => ServerVersion::major(major),
+            _ => ServerVersion::new(major, minor),
+        })
+    }
+
+    /// Object classes the adapter collects with version-specific queries
+    /// on `release`.
+    pub fn query_variants(self, release: ServerVersion) -> Vec<&'static str> {
+        QUERY_VARIANTS
+            .iter()
+            .filter(|variant| variant.product == self && release < variant.before)
+            .map(|variant| variant.covers)
+            .collect()
+    }
+}
+
+impl fmt::Display for ServerProduct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PostgreSQL => "PostgreSQL",
+            Self::MySQL => "MySQL",
+            Self::MariaDB => "MariaDB",
+            Self::SQLite => "SQLite",
+            Self::MongoDB => "MongoDB",
+        })
+    }
+}
+
+/// A query an adapter replaces on releases before `before`.
+struct QueryVariant {
+    product: ServerProduct,
+    before: ServerVersion,
+    covers: &'static str,
+}
+
+const QUERY_VARIANTS: &[QueryVariant] = &[
+    // pg_proc.prokind
+    QueryVariant {
+        product: ServerProduct::PostgreSQL,
+        before: ServerVersion::major(11),
+        covers: "functions",
+    },
+    QueryVariant {
+        product: ServerProduct::PostgreSQL,
+        before: ServerVersion::major(11),
+        covers: "procedures",
+    },
+    // pg_class.relispartition and pg_attribute.attidentity
+    QueryVariant {
+        product: ServerProduct::PostgreSQL,
+        before: ServerVersion::major(10),
+        covers: "partitions",
+    },
+    QueryVariant {
+        product: ServerProduct::PostgreSQL,
+        before: ServerVersion::major(10),
+        covers: "identity columns",
+    },
+];
+
+/// Returns a compatibility warning when the server that produced `info` is
+/// outside its adapter's tested range, naming the version-specific queries
+/// used for it.
+pub fn check_server_version(info: &DatabaseInfo) -> Option<CollectionWarning> {
+    let engine = info.engine.as_ref()?;
+    let version = info.version.as_deref()?;
+    let product = ServerProduct::detect(engine, version)?;
+    let release = product.release(version)?;
+    let (oldest, newest) = product.tested();
+
+    let mut message = if release < oldest {
+        format!(
+            "{} {} is older than the oldest tested release ({})",
+            product, release, oldest
+        )
+    } else if release > newest {
+        format!(
+            "{} {} is newer than the newest tested release ({})",
+            product, release, newest
+        )
+    } else {
+        return None;
+    };
+    let variants = product.query_variants(release);
+    if !variants.is_empty() {
+        message.push_str(&format!(
+            "; version-specific queries were used for {}",
+            variants.join(", ")
+        ));
+    }
+    message.push_str("; other metadata may be missing or incomplete");
+    Some(CollectionWarning::new(
+        WarningCategory::Compatibility,
+        message,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(engine: DatabaseType, version: &str) -> DatabaseInfo {
+        let mut info = DatabaseInfo::new("app".to_string());
+        info.engine = Some(engine);
+        info.version = Some(version.to_string());
+        info
+    }
+
+    #[test]
+    fn test_release_parsing() {
+        let pg = ServerProduct::PostgreSQL;
+        assert_eq!(
+            pg.release("PostgreSQL 15.18 (Debian 15.18-0+deb12u1) on x86_64-pc-linux-gnu"),
+            Some(ServerVersion::major(15))
+        );
+        assert_eq!(
+            pg.release("PostgreSQL 9.4.26 on x86_64"),
+            Some(ServerVersion::new(9, 4))
+        );
+        assert_eq!(
+            ServerProduct::MariaDB.release("5.5.5-10.3.39-MariaDB"),
+            Some(ServerVersion::new(10, 3))
+        );
+        assert_eq!(
+            ServerProduct::MySQL.release("8.0.36-0ubuntu0.22.04.1"),
+            Some(ServerVersion::new(8, 0))
+        );
+        assert_eq!(
+            ServerProduct::SQLite.release("SQLite 3.45.1"),
+            Some(ServerVersion::new(3, 45))
+        );
+        assert_eq!(pg.release("unknown"), None);
+        assert_eq!(
+            ServerProduct::detect(&DatabaseType::MySQL, "10.11.6-MariaDB"),
+            Some(ServerProduct::MariaDB)
+        );
+    }
+
+    #[test]
+    fn test_tested_range_produces_no_warning() {
+        assert!(check_server_version(&info(DatabaseType::PostgreSQL, "PostgreSQL 16.2")).is_none());
+        assert!(check_server_version(&info(DatabaseType::MySQL, "8.0.36")).is_none());
+        assert!(check_server_version(&info(DatabaseType::MongoDB, "7.0.5")).is_none());
+        // Unknown engine or version: nothing to compare
+        assert!(check_server_version(&DatabaseInfo::new("app".to_string())).is_none());
+    }
+
+    #[test]
+    fn test_untested_release_is_warned() {
+        let warning = check_server_version(&info(
+            DatabaseType::PostgreSQL,
+            "PostgreSQL 9.4.26 on x86_64-pc-linux-gnu",
+        ))
+        .expect("9.4 is untested");
+        assert_eq!(warning.category, WarningCategory::Compatibility);
+        assert_eq!(
+            warning.message,
+            "PostgreSQL 9.4 is older than the oldest tested release (11); \
+             version-specific queries were used for functions, procedures, partitions, \
+             identity columns; other metadata may be missing or incomplete"
+        );
+
+        let warning =
+            check_server_version(&info(DatabaseType::MySQL, "9.1.0")).expect("9.1 is untested");
+        assert_eq!(
+            warning.message,
+            "MySQL 9.1 is newer than the newest tested release (8.4); \
+             other metadata may be missing or incomplete"
+        );
+        assert!(check_server_version(&info(DatabaseType::MySQL, "10.3.39-MariaDB")).is_some());
+    }
+}
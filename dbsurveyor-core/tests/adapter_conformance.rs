@@ -0,0 +1,47 @@
+//! Adapter conformance suite run against the built-in adapters.
+//!
+//! SQLite runs against an in-memory database; the other adapters use
+//! testcontainers and need Docker.
+
+#![cfg(feature = "testing")]
+
+use dbsurveyor_core::testing::{ConformanceFixture, run_conformance_suite};
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn test_sqlite_adapter_conformance() {
+    use dbsurveyor_core::adapters::{ConnectionConfig, sqlite::SqliteAdapter};
+    use dbsurveyor_core::testing::FIXTURE_SQL;
+    use sqlx::SqlitePool;
+
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    for statement in FIXTURE_SQL {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    let adapter = SqliteAdapter::from_pool(
+        pool,
+        "sqlite::memory:",
+        ConnectionConfig::new("localhost".to_string()).with_database(":memory:".to_string()),
+    );
+
+    run_conformance_suite(&adapter, &ConformanceFixture::standard())
+        .await
+        .assert_conformant();
+}
+
+#[cfg(feature = "postgresql")]
+#[tokio::test]
+async fn test_postgres_adapter_conformance() {
+    use dbsurveyor_core::testing::containers;
+
+    let database = containers::start_postgres().await.unwrap();
+    containers::seed_postgres(&database.url).await.unwrap();
+    let adapter = dbsurveyor_core::adapters::create_adapter(&database.url)
+        .await
+        .unwrap();
+
+    let fixture = ConformanceFixture::standard().with_secret("postgres:postgres@");
+    run_conformance_suite(&*adapter, &fixture)
+        .await
+        .assert_conformant();
+}
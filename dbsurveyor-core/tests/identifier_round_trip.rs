@@ -0,0 +1,219 @@
+//! Property tests for identifier handling with adversarial names.
+//!
+//! This test suite covers:
+//! - Quoting of ORDER BY columns for every SQL dialect
+//! - SQLite round trips of mixed-case, dotted, comma-separated, quoted, and
+//!   reserved-word table, column, and index names through schema collection
+//!   and sampling
+//!
+//! Note: SQLite tests use in-memory databases, so no testcontainers needed.
+
+#![cfg(feature = "sqlite")]
+
+use dbsurveyor_core::{
+    adapters::{ConnectionConfig, DatabaseAdapter, SamplingConfig, sqlite::SqliteAdapter},
+    models::{OrderingStrategy, SortDirection},
+};
+use proptest::prelude::*;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// Identifiers that break naive quoting, splitting, or case folding.
+fn identifier() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(vec![
+            "select",
+            "ORDER",
+            "Group",
+            "table",
+            "from",
+            "MixedCase",
+            "a.b",
+            "a,b",
+            "a, b",
+            "x\"y",
+            "\"",
+            "back`tick",
+            "it's",
+            "semi;colon",
+            "-- comment",
+            "new\nline",
+            "with space",
+            " padded ",
+            "(paren)",
+            "ünïcödé",
+        ])
+        .prop_map(str::to_string),
+        r#"[A-Za-z0-9_ .,;'"`()é-]{1,16}"#,
+    ]
+}
+
+/// Table and index names; SQLite reserves names starting with `sqlite_`.
+fn object_name() -> impl Strategy<Value = String> {
+    identifier().prop_filter("reserved by SQLite", |name| {
+        !name.to_ascii_lowercase().starts_with("sqlite_")
+    })
+}
+
+/// One to four column names, distinct under SQLite's ASCII case folding.
+fn column_names() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec(identifier(), 1..=4).prop_filter("duplicate column", |names| {
+        let mut seen = HashSet::new();
+        names
+            .iter()
+            .all(|name| seen.insert(name.to_ascii_lowercase()))
+    })
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Parses `ORDER BY <quoted> <dir>, ...` back into (column, direction) pairs.
+fn parse_order_by(clause: &str, open: char, close: char) -> Vec<(String, String)> {
+    let mut chars = clause
+        .strip_prefix("ORDER BY ")
+        .expect("ORDER BY prefix")
+        .chars()
+        .peekable();
+    let mut terms = Vec::new();
+    loop {
+        assert_eq!(chars.next(), Some(open), "identifier must be quoted");
+        let mut name = String::new();
+        loop {
+            match chars.next().expect("unterminated identifier") {
+                c if c == close && chars.peek() == Some(&close) => {
+                    chars.next();
+                    name.push(close);
+                }
+                c if c == close => break,
+                c => name.push(c),
+            }
+        }
+        assert_eq!(chars.next(), Some(' '));
+        let direction: String = chars.by_ref().take_while(|c| *c != ',').collect();
+        terms.push((name, direction));
+        match chars.next() {
+            Some(' ') => continue,
+            None => break,
+            Some(other) => panic!("unexpected {other:?} after ORDER BY term"),
+        }
+    }
+    terms
+}
+
+fn expected_terms(columns: &[String], direction: &str) -> Vec<(String, String)> {
+    columns
+        .iter()
+        .map(|column| (column.clone(), direction.to_string()))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn test_sqlite_order_by_round_trips(columns in column_names(), descending in any::<bool>()) {
+        let strategy = OrderingStrategy::PrimaryKey { columns: columns.clone() };
+        let clause = dbsurveyor_core::adapters::sqlite::generate_order_by_clause(&strategy, descending);
+        let direction = if descending { "DESC" } else { "ASC" };
+        prop_assert_eq!(parse_order_by(&clause, '"', '"'), expected_terms(&columns, direction));
+    }
+
+    #[cfg(feature = "postgresql")]
+    #[test]
+    fn test_postgres_order_by_round_trips(columns in column_names(), descending in any::<bool>()) {
+        let strategy = OrderingStrategy::PrimaryKey { columns: columns.clone() };
+        let clause = dbsurveyor_core::adapters::postgres::generate_order_by_clause(&strategy, descending);
+        let direction = if descending { "DESC" } else { "ASC" };
+        prop_assert_eq!(parse_order_by(&clause, '"', '"'), expected_terms(&columns, direction));
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_mysql_order_by_round_trips(columns in column_names(), descending in any::<bool>()) {
+        let strategy = OrderingStrategy::PrimaryKey { columns: columns.clone() };
+        let clause = dbsurveyor_core::adapters::mysql::generate_order_by_clause(&strategy, descending);
+        let direction = if descending { "DESC" } else { "ASC" };
+        prop_assert_eq!(parse_order_by(&clause, '`', '`'), expected_terms(&columns, direction));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Adversarial names survive collection and sampling unchanged.
+    #[test]
+    fn test_sqlite_identifiers_round_trip(table in object_name(), columns in column_names()) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.expect("pool");
+            // Tables and indexes share a namespace
+            let index = format!("{table} idx");
+            let column_list = columns.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+            let last = columns.last().expect("at least one column");
+
+            sqlx::query(&format!(
+                "CREATE TABLE {} ({}, PRIMARY KEY ({}))",
+                quote(&table),
+                columns.iter().map(|c| format!("{} TEXT", quote(c))).collect::<Vec<_>>().join(", "),
+                quote(&columns[0]),
+            ))
+            .execute(&pool)
+            .await
+            .expect("create table");
+            sqlx::query(&format!(
+                "CREATE INDEX {} ON {} ({} DESC)",
+                quote(&index),
+                quote(&table),
+                quote(last)
+            ))
+            .execute(&pool)
+            .await
+            .expect("create index");
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let insert = format!("INSERT INTO {} ({column_list}) VALUES ({placeholders})", quote(&table));
+            let mut query = sqlx::query(&insert);
+            for column in &columns {
+                query = query.bind(column.clone());
+            }
+            query.execute(&pool).await.expect("insert row");
+
+            let adapter = SqliteAdapter::from_pool(
+                pool,
+                "sqlite::memory:",
+                ConnectionConfig::new("localhost".to_string()).with_database(":memory:".to_string()),
+            );
+            let schema = adapter.collect_schema().await.expect("collect schema");
+            let collected = schema
+                .tables
+                .iter()
+                .find(|t| t.name == table)
+                .unwrap_or_else(|| panic!("table {table:?} not collected"));
+
+            let names: Vec<&str> = collected.columns.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, columns.iter().map(String::as_str).collect::<Vec<_>>());
+            let pk = collected.primary_key.as_ref().expect("primary key");
+            assert_eq!(pk.columns, vec![columns[0].clone()]);
+            let collected_index = collected
+                .indexes
+                .iter()
+                .find(|i| i.name == index)
+                .unwrap_or_else(|| panic!("index {index:?} not collected"));
+            assert_eq!(collected_index.columns.len(), 1);
+            assert_eq!(&collected_index.columns[0].name, last);
+            assert_eq!(collected_index.columns[0].sort_order, Some(SortDirection::Descending));
+
+            let sample = adapter
+                .sample_table(&table, &SamplingConfig::new().with_sample_size(5))
+                .await
+                .expect("sample table");
+            assert_eq!(sample.rows.len(), 1);
+            let row = sample.rows[0].as_object().expect("row object");
+            for column in &columns {
+                assert_eq!(row.get(column).and_then(|v| v.as_str()), Some(column.as_str()));
+            }
+        });
+    }
+}
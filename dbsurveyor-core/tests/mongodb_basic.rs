@@ -31,9 +31,9 @@ async fn test_mongodb_adapter_features() {
         assert!(adapter.supports_feature(AdapterFeature::SchemaCollection));
         assert!(adapter.supports_feature(AdapterFeature::DataSampling));
         assert!(adapter.supports_feature(AdapterFeature::QueryTimeout));
+        assert!(adapter.supports_feature(AdapterFeature::MultiDatabase));
 
         // MongoDB doesn't support these features
-        assert!(!adapter.supports_feature(AdapterFeature::MultiDatabase));
         assert!(!adapter.supports_feature(AdapterFeature::ConnectionPooling));
         assert!(!adapter.supports_feature(AdapterFeature::ReadOnlyMode));
     }
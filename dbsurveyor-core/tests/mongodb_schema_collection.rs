@@ -388,6 +388,98 @@ async fn test_mongodb_empty_collection() {
     );
 }
 
+#[tokio::test]
+async fn test_mongodb_views_and_validators() {
+    let container = Mongo::default()
+        .start()
+        .await
+        .expect("Failed to start MongoDB container");
+
+    let port = container
+        .get_host_port_ipv4(27017)
+        .await
+        .expect("Failed to get MongoDB port");
+
+    let connection_string = format!("mongodb://localhost:{}/testdb", port);
+
+    let client = mongodb::Client::with_uri_str(&connection_string)
+        .await
+        .expect("Failed to connect");
+    let db = client.database("testdb");
+
+    // Validator declares `age` as a required int; one document stores it as a string
+    db.create_collection("accounts")
+        .validator(doc! {
+            "$jsonSchema": {
+                "bsonType": "object",
+                "required": ["name", "age"],
+                "properties": {
+                    "name": { "bsonType": "string" },
+                    "age": { "bsonType": "int" }
+                }
+            }
+        })
+        .validation_action(mongodb::options::ValidationAction::Warn)
+        .await
+        .expect("Failed to create validated collection");
+    db.collection::<mongodb::bson::Document>("accounts")
+        .insert_many(vec![
+            doc! { "name": "Alice", "age": 30 },
+            doc! { "name": "Bob", "age": "unknown" },
+        ])
+        .await
+        .expect("Failed to insert accounts");
+
+    db.create_collection("adult_accounts")
+        .view_on("accounts".to_string())
+        .pipeline(vec![doc! { "$match": { "age": { "$gte": 18 } } }])
+        .await
+        .expect("Failed to create view");
+
+    let adapter = MongoAdapter::new(&connection_string)
+        .await
+        .expect("Failed to create adapter");
+    let schema = adapter
+        .collect_schema()
+        .await
+        .expect("Failed to collect schema");
+
+    // The view is reported as a view with its pipeline, not as a table
+    assert!(!schema.tables.iter().any(|t| t.name == "adult_accounts"));
+    let view = schema
+        .views
+        .iter()
+        .find(|v| v.name == "adult_accounts")
+        .expect("view not collected");
+    let definition = view.definition.as_deref().expect("view definition missing");
+    assert!(definition.contains("\"viewOn\":\"accounts\""));
+    assert!(definition.contains("$match"));
+
+    let accounts = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "accounts")
+        .expect("accounts collection not found");
+    let validator = accounts.validator.as_ref().expect("validator missing");
+    assert!(validator.definition.get("$jsonSchema").is_some());
+    assert_eq!(validator.validation_action.as_deref(), Some("warn"));
+    assert!(
+        validator
+            .discrepancies
+            .iter()
+            .any(|d| d.contains("'age'") && d.contains("string")),
+        "Expected age type discrepancy, got {:?}",
+        validator.discrepancies
+    );
+    assert!(
+        schema
+            .collection_metadata
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("Validator discrepancy") && w.objects == ["accounts"])
+    );
+}
+
 #[tokio::test]
 async fn test_mongodb_list_databases() {
     let container = Mongo::default()
@@ -5,6 +5,7 @@
 //! - Primary key and foreign key detection
 //! - Index collection
 //! - View collection
+//! - Generated columns and partitioning metadata
 //! - Data type mapping
 
 #![cfg(feature = "mysql")]
@@ -12,7 +13,7 @@
 use dbsurveyor_core::{
     Result,
     adapters::{DatabaseAdapter, mysql::MySqlAdapter},
-    models::DatabaseType,
+    models::{DatabaseType, GenerationKind},
 };
 use sqlx::MySqlPool;
 use testcontainers_modules::{mysql::Mysql, testcontainers::runners::AsyncRunner};
@@ -328,6 +329,95 @@ async fn test_collect_views() -> Result<()> {
     Ok(())
 }
 
+/// Test generated column and partition collection
+#[tokio::test]
+async fn test_collect_generated_columns_and_partitions() -> Result<()> {
+    let mysql = Mysql::default().start().await.unwrap();
+    let port = mysql.get_host_port_ipv4(3306).await.unwrap();
+    let database_url = format!("mysql://root@localhost:{}/test", port);
+
+    common::wait_for_mysql_ready(&database_url, 30).await?;
+
+    let pool = MySqlPool::connect(&database_url).await.unwrap();
+    sqlx::query(
+        "CREATE TABLE line_items (
+            id INT PRIMARY KEY,
+            price DECIMAL(10,2),
+            qty INT,
+            total DECIMAL(12,2) AS (price * qty) VIRTUAL,
+            total_stored DECIMAL(12,2) AS (price * qty) STORED
+        )",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "CREATE TABLE events (
+            id INT NOT NULL,
+            created_year INT NOT NULL,
+            PRIMARY KEY (id, created_year)
+        )
+        PARTITION BY RANGE (created_year) (
+            PARTITION p2023 VALUES LESS THAN (2024),
+            PARTITION p2024 VALUES LESS THAN (2025),
+            PARTITION pmax VALUES LESS THAN MAXVALUE
+        )",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool.close().await;
+
+    let adapter = MySqlAdapter::new(&database_url).await?;
+    let schema = adapter.collect_schema().await?;
+
+    let line_items = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "line_items")
+        .expect("Should find line_items table");
+    assert!(line_items.partitioning.is_none());
+
+    let generated = |name: &str| {
+        line_items
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .and_then(|c| c.generated.clone())
+    };
+    assert!(generated("price").is_none());
+    let total = generated("total").expect("total should be generated");
+    assert_eq!(total.kind, GenerationKind::Virtual);
+    assert!(total.expression.contains("price"));
+    let total_stored = generated("total_stored").expect("total_stored should be generated");
+    assert_eq!(total_stored.kind, GenerationKind::Stored);
+
+    let events = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "events")
+        .expect("Should find events table");
+    let partitioning = events
+        .partitioning
+        .as_ref()
+        .expect("events should be partitioned");
+    assert_eq!(partitioning.method, "RANGE");
+    let names: Vec<&str> = partitioning
+        .partitions
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["p2023", "p2024", "pmax"]);
+    assert_eq!(partitioning.partitions[0].ordinal_position, 1);
+    assert_eq!(
+        partitioning.partitions[2].description.as_deref(),
+        Some("MAXVALUE")
+    );
+
+    Ok(())
+}
+
 /// Test data type mapping
 #[tokio::test]
 async fn test_data_type_mapping() -> Result<()> {
@@ -805,6 +805,33 @@ fn test_multi_database_metadata_serialization() {
         assert_eq!(deserialized.max_concurrency, metadata.max_concurrency);
     }
 
+    #[tokio::test]
+    async fn test_multi_host_url_falls_back_to_primary() {
+        if !has_database_connection() {
+            eprintln!(
+                "Skipping test_multi_host_url_falls_back_to_primary: no database URL configured"
+            );
+            return;
+        }
+
+        // An unreachable standby listed first, then the test server
+        let database_url = get_test_database_url().unwrap();
+        let url = url::Url::parse(&database_url).expect("valid test URL");
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or("localhost"), port),
+            None => url.host_str().unwrap_or("localhost").to_string(),
+        };
+        let multi_host = database_url.replacen(&host, &format!("127.0.0.1:1,{}", host), 1);
+
+        let adapter = PostgresAdapter::new(&multi_host)
+            .await
+            .expect("Failed to connect to any host");
+        assert_eq!(
+            adapter.server_role().await,
+            Some(dbsurveyor_core::ServerRole::Primary)
+        );
+    }
+
     #[tokio::test]
     async fn test_collect_all_databases_basic() {
         if !has_database_connection() {
@@ -832,6 +859,10 @@ async fn test_collect_all_databases_basic() {
         );
         assert!(!result.server_info.version.is_empty());
         assert!(!result.server_info.connection_user.is_empty());
+        assert_eq!(
+            result.server_info.role,
+            Some(dbsurveyor_core::ServerRole::Primary)
+        );
 
         // Verify we collected at least one database
         assert!(
@@ -14,7 +14,7 @@
 use dbsurveyor_core::{
     Result, SamplingConfig, SamplingStrategy,
     adapters::{ConnectionConfig, sqlite::SqliteAdapter},
-    models::{OrderingStrategy, SortDirection},
+    models::{OrderingStrategy, SampleValueEncoding, SortDirection},
 };
 use sqlx::SqlitePool;
 
@@ -502,7 +502,9 @@ async fn test_sample_table_data_types() -> Result<()> {
             int_val INTEGER,
             real_val REAL,
             text_val TEXT,
-            blob_val BLOB
+            blob_val BLOB,
+            price DECIMAL(10, 2),
+            ratio DOUBLE
         )",
     )
     .execute(&pool)
@@ -510,8 +512,8 @@ async fn test_sample_table_data_types() -> Result<()> {
     .unwrap();
 
     sqlx::query(
-        "INSERT INTO test_types (int_val, real_val, text_val, blob_val)
-         VALUES (42, 3.14, 'hello', X'DEADBEEF')",
+        "INSERT INTO test_types (int_val, real_val, text_val, blob_val, price, ratio)
+         VALUES (42, 3.14, 'hello', X'DEADBEEF', 12.5, 1e999)",
     )
     .execute(&pool)
     .await
@@ -532,9 +534,24 @@ async fn test_sample_table_data_types() -> Result<()> {
     let expected = 3.14;
     assert!((real_val - expected).abs() < 0.001);
     assert_eq!(row["text_val"].as_str().unwrap(), "hello");
-    // BLOB should be base64 encoded
-    let blob_val = row["blob_val"].as_str().unwrap();
-    assert!(blob_val.starts_with("base64:"));
+    // BLOB should be base64 encoded with its length
+    assert_eq!(
+        row["blob_val"],
+        serde_json::json!({"base64": "3q2+7w==", "length": 4})
+    );
+    // Decimals keep their declared scale; infinities survive as strings
+    assert_eq!(row["price"], "12.50");
+    assert_eq!(row["ratio"], "Infinity");
+
+    let price = sample
+        .column_types
+        .iter()
+        .find(|c| c.name == "price")
+        .expect("price in legend");
+    assert_eq!(price.data_type, "DECIMAL(10, 2)");
+    assert_eq!(price.encoding, SampleValueEncoding::DecimalString);
+    assert_eq!(price.scale, Some(2));
+    assert_eq!(sample.column_types.len(), 7);
 
     Ok(())
 }
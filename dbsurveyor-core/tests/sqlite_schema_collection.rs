@@ -7,15 +7,29 @@
 //! - View collection
 //! - Trigger collection
 //! - Data type mapping
+//! - Virtual table detection
+//! - STRICT / WITHOUT ROWID options and declared column types
+//! - Column collations
+//! - Attached database collection
+//! - File integrity checks
+//! - Immutable (forensic) opens
 //!
-//! Note: SQLite tests use in-memory databases, so no testcontainers needed.
+//! Note: SQLite tests use in-memory databases (and temporary files for
+//! attached databases), so no testcontainers needed.
 
 #![cfg(feature = "sqlite")]
 
 use dbsurveyor_core::{
     Result,
-    adapters::{ConnectionConfig, DatabaseAdapter, sqlite::SqliteAdapter},
-    models::DatabaseType,
+    adapters::{
+        ConnectionConfig, DatabaseAdapter, SamplingConfig, TableRef,
+        sqlite::{AttachedDatabase, SqliteAdapter},
+    },
+    lint::{LintRule, lint_schema},
+    models::{
+        CollectionMode, CollectionStatus, DatabaseType, IntegrityCheckMode, IntegrityStatus,
+        WarningCategory,
+    },
 };
 use sqlx::SqlitePool;
 
@@ -517,3 +531,389 @@ async fn test_row_count() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that table options and the declared column type text are preserved
+#[tokio::test]
+async fn test_collect_table_options_and_declared_types() -> Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+    for statement in [
+        "CREATE TABLE plain (id INTEGER PRIMARY KEY, price DECIMAL(10, 2), raw)",
+        "CREATE TABLE kv (key INTEGER PRIMARY KEY, value ANY) STRICT, WITHOUT ROWID",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let adapter = create_adapter_with_pool(pool).await;
+    let schema = adapter.collect_schema().await?;
+
+    let plain = schema.tables.iter().find(|t| t.name == "plain").unwrap();
+    assert!(plain.options.is_none());
+    let declared: Vec<_> = plain
+        .columns
+        .iter()
+        .map(|c| c.declared_type.as_deref())
+        .collect();
+    assert_eq!(
+        declared,
+        vec![Some("INTEGER"), Some("DECIMAL(10, 2)"), Some("")]
+    );
+    assert!(plain.columns[0].is_auto_increment, "rowid alias");
+
+    let kv = schema.tables.iter().find(|t| t.name == "kv").unwrap();
+    let options = kv.options.as_ref().expect("kv declares options");
+    assert!(options.strict);
+    assert!(options.without_rowid);
+    assert_eq!(kv.columns[1].declared_type.as_deref(), Some("ANY"));
+    assert!(
+        !kv.columns[0].is_auto_increment,
+        "INTEGER PRIMARY KEY is not a rowid alias in a WITHOUT ROWID table"
+    );
+
+    Ok(())
+}
+
+/// Test that column COLLATE clauses are collected and flagged by lint
+#[tokio::test]
+async fn test_collect_column_collations() -> Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    sqlx::query(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT COLLATE NOCASE, name TEXT)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let adapter = create_adapter_with_pool(pool).await;
+    let schema = adapter.collect_schema().await?;
+
+    let users = schema.tables.iter().find(|t| t.name == "users").unwrap();
+    let collations: Vec<_> = users
+        .columns
+        .iter()
+        .map(|c| c.collation.as_deref())
+        .collect();
+    assert_eq!(collations, vec![None, Some("NOCASE"), None]);
+
+    let warnings = lint_schema(&schema, &[LintRule::MixedCollation]);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].objects, vec!["users.email"]);
+
+    Ok(())
+}
+
+// =============================================================================
+// Virtual Table Tests
+// =============================================================================
+
+/// Test that FTS5 and R*Tree virtual tables are detected with their modules
+#[tokio::test]
+async fn test_collect_virtual_tables() -> Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+    for statement in [
+        "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)",
+        "CREATE VIRTUAL TABLE notes_fts USING fts5(body, tokenize = 'porter')",
+        "CREATE VIRTUAL TABLE regions USING rtree(id, min_x, max_x)",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let adapter = create_adapter_with_pool(pool).await;
+    let schema = adapter.collect_schema().await?;
+
+    let notes = schema.tables.iter().find(|t| t.name == "notes").unwrap();
+    assert!(notes.virtual_table.is_none());
+
+    let fts = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "notes_fts")
+        .unwrap();
+    let fts_vt = fts.virtual_table.as_ref().expect("FTS5 table is virtual");
+    assert_eq!(fts_vt.module, "fts5");
+    assert_eq!(fts_vt.arguments, vec!["body", "tokenize = 'porter'"]);
+    assert!(fts_vt.shadow_tables.contains(&"notes_fts_data".to_string()));
+    assert!(fts.columns.iter().any(|c| c.name == "body"));
+
+    let rtree = schema.tables.iter().find(|t| t.name == "regions").unwrap();
+    let rtree_vt = rtree
+        .virtual_table
+        .as_ref()
+        .expect("R*Tree table is virtual");
+    assert_eq!(rtree_vt.module, "rtree");
+    assert_eq!(rtree_vt.arguments, vec!["id", "min_x", "max_x"]);
+    assert!(rtree_vt.shadow_tables.contains(&"regions_node".to_string()));
+
+    Ok(())
+}
+
+/// Test that a virtual table whose module is not compiled in is kept
+#[tokio::test]
+async fn test_collect_virtual_table_with_unavailable_module() -> Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    // SQLite refuses to create a table for an unknown module, so the entry
+    // is written into the schema directly, as another build would have
+    for statement in [
+        "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)",
+        "PRAGMA writable_schema = ON",
+        "INSERT INTO sqlite_master (type, name, tbl_name, rootpage, sql) \
+         VALUES ('table', 'embeddings', 'embeddings', 0, \
+         'CREATE VIRTUAL TABLE embeddings USING vec0(embedding float[4])')",
+        "PRAGMA writable_schema = OFF",
+        "PRAGMA schema_version = 100",
+    ] {
+        sqlx::query(statement).execute(&mut *conn).await.unwrap();
+    }
+
+    drop(conn);
+
+    let adapter = create_adapter_with_pool(pool).await;
+    let schema = adapter.collect_schema().await?;
+
+    let table = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "embeddings")
+        .expect("virtual table is kept");
+    let vt = table.virtual_table.as_ref().expect("table is virtual");
+    assert_eq!(vt.module, "vec0");
+    assert_eq!(vt.arguments, vec!["embedding float[4]"]);
+    assert!(table.columns.is_empty());
+    assert!(
+        schema
+            .collection_metadata
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("'vec0' is not available"))
+    );
+
+    // A virtual table without columns still passes output validation
+    dbsurveyor_core::initialize_schema_validator().expect("validator initializes");
+    let json = serde_json::to_value(&schema).expect("schema serializes");
+    dbsurveyor_core::validate_schema_output(&json).expect("valid output");
+
+    Ok(())
+}
+
+// =============================================================================
+// Attached Database Tests
+// =============================================================================
+
+/// Creates a SQLite database file and runs the given statements against it
+async fn create_database_file(path: &std::path::Path, statements: &[&str]) {
+    let pool = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", path.display()))
+        .await
+        .expect("Failed to create SQLite database file");
+    for statement in statements {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    pool.close().await;
+}
+
+/// Test that attached databases are collected as separate logical databases
+#[tokio::test]
+async fn test_collect_attached_databases() -> Result<()> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("dbsurveyor_attach_test_{}", std::process::id()));
+    std::fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let main_path = temp_dir.join("main.db");
+    let archive_path = temp_dir.join("archive.db");
+
+    create_database_file(
+        &main_path,
+        &["CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)"],
+    )
+    .await;
+    create_database_file(
+        &archive_path,
+        &[
+            "CREATE TABLE events (id INTEGER PRIMARY KEY, user_id INTEGER, kind TEXT)",
+            "CREATE INDEX idx_events_user ON events(user_id)",
+            "CREATE VIEW signups AS SELECT * FROM events WHERE kind = 'signup'",
+            "INSERT INTO events (user_id, kind) VALUES (1, 'signup'), (2, 'login')",
+        ],
+    )
+    .await;
+
+    let adapter = SqliteAdapter::with_attachments(
+        &format!("sqlite://{}", main_path.display()),
+        vec![AttachedDatabase::new("archive", &archive_path)?],
+    )
+    .await?;
+
+    let server = adapter.collect_server_schema().await?;
+    assert_eq!(server.server_info.server_type, DatabaseType::SQLite);
+    assert_eq!(
+        server.server_info.collection_mode,
+        CollectionMode::MultiDatabase {
+            discovered: 2,
+            collected: 2,
+            failed: 0
+        }
+    );
+
+    let names: Vec<&str> = server
+        .databases
+        .iter()
+        .map(|db| db.database_info.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["main.db", "archive"]);
+
+    // The main database keeps unqualified objects and does not see attached tables
+    let main = &server.databases[0];
+    assert_eq!(main.tables.len(), 1);
+    assert_eq!(main.tables[0].name, "users");
+    assert_eq!(main.tables[0].schema, None);
+
+    let archive = &server.databases[1];
+    assert_eq!(
+        archive.database_info.collection_status,
+        CollectionStatus::Success
+    );
+    assert!(archive.database_info.size_bytes.is_some());
+    let events = archive
+        .tables
+        .iter()
+        .find(|t| t.name == "events")
+        .expect("Should find events table in attached database");
+    assert_eq!(events.schema.as_deref(), Some("archive"));
+    assert_eq!(events.row_count, Some(2));
+    assert!(events.indexes.iter().any(|i| i.name == "idx_events_user"));
+    assert!(archive.views.iter().any(|v| v.name == "signups"));
+
+    // Sampling resolves the table within the attached schema
+    let sample = DatabaseAdapter::sample_table(
+        &adapter,
+        TableRef {
+            schema_name: Some("archive"),
+            table_name: "events",
+        },
+        &SamplingConfig::default(),
+    )
+    .await?;
+    assert_eq!(sample.rows.len(), 2);
+    assert_eq!(sample.schema_name.as_deref(), Some("archive"));
+
+    // Attached databases are read-only
+    let write = sqlx::query("INSERT INTO archive.events (user_id, kind) VALUES (3, 'x')")
+        .execute(&adapter.pool)
+        .await;
+    assert!(write.is_err(), "Writes to attached databases should fail");
+
+    drop(adapter);
+    let _ = std::fs::remove_dir_all(&temp_dir);
+
+    Ok(())
+}
+
+/// Test that a missing attached file is rejected before connecting
+#[tokio::test]
+async fn test_attach_missing_file_rejected() {
+    let attachment = AttachedDatabase::new("archive", "/nonexistent/archive.db").unwrap();
+    let result = SqliteAdapter::with_attachments("sqlite::memory:", vec![attachment]).await;
+    assert!(result.is_err());
+}
+
+/// Test that a full integrity check finds index damage that a quick check skips
+#[tokio::test]
+async fn test_integrity_check_detects_mismatched_index() -> Result<()> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("dbsurveyor_integrity_test_{}", std::process::id()));
+    std::fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let path = temp_dir.join("damaged.db");
+
+    // Swapping the root pages of the two indexes (pages 3 and 4 of a fresh
+    // file) leaves each index holding the other's entries: the pages are
+    // well formed but no longer match their table.
+    create_database_file(
+        &path,
+        &[
+            "CREATE TABLE events (id INTEGER PRIMARY KEY, a INTEGER, b TEXT)",
+            "CREATE INDEX idx_events_a ON events(a)",
+            "CREATE INDEX idx_events_b ON events(b)",
+            "INSERT INTO events (a, b) VALUES (1, 'x'), (2, 'y'), (3, 'z')",
+            "PRAGMA writable_schema = ON;
+             UPDATE sqlite_master SET rootpage = 7 - rootpage
+             WHERE name IN ('idx_events_a', 'idx_events_b');
+             PRAGMA writable_schema = OFF;",
+        ],
+    )
+    .await;
+
+    let adapter = SqliteAdapter::new(&format!("sqlite://{}", path.display())).await?;
+    let timeout = Some(std::time::Duration::from_secs(30));
+
+    let quick = adapter
+        .check_integrity(IntegrityCheckMode::Quick, timeout)
+        .await
+        .expect("SQLite supports integrity checks");
+    assert_eq!(quick.status, IntegrityStatus::Intact);
+
+    let full = adapter
+        .check_integrity(IntegrityCheckMode::Full, timeout)
+        .await
+        .expect("SQLite supports integrity checks");
+    assert_eq!(full.status, IntegrityStatus::Corrupt);
+    assert!(
+        full.problems.iter().any(|p| p.contains("idx_events_")),
+        "problems should name the damaged index: {:?}",
+        full.problems
+    );
+    let warning = full.to_warning().expect("corruption is a warning");
+    assert_eq!(warning.category, WarningCategory::Integrity);
+
+    drop(adapter);
+    let _ = std::fs::remove_dir_all(&temp_dir);
+
+    Ok(())
+}
+
+/// Test that an immutable open neither reads nor checkpoints the write-ahead log
+#[tokio::test]
+async fn test_immutable_open_ignores_write_ahead_log() -> Result<()> {
+    let temp_dir =
+        std::env::temp_dir().join(format!("dbsurveyor_immutable_test_{}", std::process::id()));
+    std::fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let path = temp_dir.join("evidence.db");
+    create_database_file(
+        &path,
+        &[
+            "PRAGMA journal_mode = WAL",
+            "CREATE TABLE checkpointed (id INTEGER PRIMARY KEY)",
+            "PRAGMA wal_checkpoint(TRUNCATE)",
+        ],
+    )
+    .await;
+
+    // Keep the writer open so the new table stays in the WAL only
+    let writer = SqlitePool::connect(&format!("sqlite://{}", path.display()))
+        .await
+        .expect("Failed to open writer");
+    sqlx::query("CREATE TABLE uncheckpointed (id INTEGER PRIMARY KEY)")
+        .execute(&writer)
+        .await
+        .unwrap();
+    let wal = temp_dir.join("evidence.db-wal");
+    let wal_size = std::fs::metadata(&wal).expect("WAL exists").len();
+    let database_bytes = std::fs::read(&path).unwrap();
+
+    let adapter =
+        SqliteAdapter::immutable(&format!("sqlite://{}", path.display()), Vec::new()).await?;
+    let schema = adapter.collect_schema().await?;
+    let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["checkpointed"]);
+    let read_only = adapter.verify_read_only().await?;
+    assert!(read_only.evidence.iter().any(|e| e.contains("immutable")));
+    drop(adapter);
+
+    assert_eq!(std::fs::read(&path).unwrap(), database_bytes);
+    assert_eq!(std::fs::metadata(&wal).unwrap().len(), wal_size);
+
+    writer.close().await;
+    let _ = std::fs::remove_dir_all(&temp_dir);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Criterion benchmarks for report generation.
+//!
+//! Runs the compiled `dbsurveyor` binary on synthetic survey files, so each
+//! iteration covers loading, validation, redaction, and rendering as a user
+//! sees them, including process startup.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use dbsurveyor_core::{SyntheticSchemaConfig, generate_synthetic_schema};
+
+/// Fixture sizes as (tables, average columns per table).
+const SIZES: [(usize, usize); 3] = [(50, 12), (500, 20), (2000, 30)];
+
+/// Writes a synthetic survey file and returns its path.
+fn write_fixture(dir: &Path, tables: usize, columns_avg: usize) -> std::path::PathBuf {
+    let schema = generate_synthetic_schema(
+        &SyntheticSchemaConfig::default()
+            .with_tables(tables)
+            .with_columns_avg(columns_avg)
+            .with_schemas(3),
+    );
+    let path = dir.join(format!("synthetic_{tables}t_{columns_avg}c.json"));
+    let json = serde_json::to_string(&schema).expect("serialize schema");
+    std::fs::write(&path, json).expect("write fixture");
+    path
+}
+
+fn generate(input: &Path, output: &Path) {
+    let status = Command::new(env!("CARGO_BIN_EXE_dbsurveyor"))
+        .args(["--quiet", "generate", "--format", "markdown", "--output"])
+        .arg(output)
+        .arg(input)
+        .stdout(Stdio::null())
+        .status()
+        .expect("run dbsurveyor");
+    assert!(status.success(), "dbsurveyor generate failed");
+}
+
+/// Markdown generation from survey files of increasing size.
+fn bench_report_generation(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("create temp dir");
+    let mut group = c.benchmark_group("report_generation");
+    group.sample_size(10);
+
+    for (tables, columns_avg) in SIZES {
+        let input = write_fixture(dir.path(), tables, columns_avg);
+        let id = format!("{tables}t_{columns_avg}c");
+        group.throughput(Throughput::Elements(tables as u64));
+
+        let output = dir.path().join("report.md");
+        group.bench_function(BenchmarkId::new("markdown", &id), |b| {
+            b.iter(|| generate(&input, &output));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_report_generation);
+criterion_main!(benches);
@@ -0,0 +1,180 @@
+//! Allow-lists of expected changes for `baseline check`.
+//!
+//! An allow-list file has one `<change> <object> <pattern>` rule per line;
+//! blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # Audit columns may be added anywhere
+//! added column *.*.created_at
+//! # Staging tables churn freely
+//! * * staging.*
+//! ```
+//!
+//! `<change>` is `added`, `removed`, `modified`, or `*`; `<object>` is an
+//! object kind such as `table`, `column`, or `foreign-key`, or `*`.
+//! `<pattern>` is a glob over the qualified object name (`schema.table` or
+//! `schema.table.column`), matched case-insensitively. A change is approved
+//! when any rule matches it.
+
+use dbsurveyor_core::drift::{ChangeKind, DriftObject, SchemaChange};
+use dbsurveyor_core::{DbSurveyorError, Result};
+
+/// Default baseline location, relative to the working directory.
+pub(crate) const DEFAULT_BASELINE_PATH: &str = "dbsurveyor-baseline.json";
+
+/// Process exit code when a survey has unapproved drift.
+pub(crate) const DRIFT_EXIT_CODE: i32 = 2;
+
+/// One allow-list rule; `None` matches anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowRule {
+    kind: Option<ChangeKind>,
+    object_kind: Option<DriftObject>,
+    /// Lowercase glob pattern
+    pattern: String,
+}
+
+/// Expected changes that do not count as drift.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AllowList {
+    rules: Vec<AllowRule>,
+}
+
+impl AllowList {
+    /// Parses allow-list file content, appending its rules.
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the first malformed line.
+    pub(crate) fn extend_from_str(&mut self, content: &str) -> Result<()> {
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let rule = match fields.as_slice() {
+                [kind, object_kind, pattern] => parse_kind(kind).and_then(|kind| {
+                    parse_object_kind(object_kind).map(|object_kind| AllowRule {
+                        kind,
+                        object_kind,
+                        pattern: pattern.to_lowercase(),
+                    })
+                }),
+                _ => None,
+            };
+            let Some(rule) = rule else {
+                return Err(DbSurveyorError::configuration(format!(
+                    "Invalid allow-list rule on line {}: expected `<added|removed|modified|*> <object|*> <pattern>`",
+                    index.saturating_add(1)
+                )));
+            };
+            self.rules.push(rule);
+        }
+        Ok(())
+    }
+
+    /// Whether a rule approves `change`.
+    pub(crate) fn allows(&self, change: &SchemaChange) -> bool {
+        let object = change.object.to_lowercase();
+        self.rules.iter().any(|rule| {
+            rule.kind.is_none_or(|kind| kind == change.kind)
+                && rule
+                    .object_kind
+                    .is_none_or(|object_kind| object_kind == change.object_kind)
+                && glob_match::glob_match(&rule.pattern, &object)
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.rules.len()
+    }
+}
+
+/// Parses a change kind; `Some(None)` is the `*` wildcard.
+fn parse_kind(text: &str) -> Option<Option<ChangeKind>> {
+    match text.to_ascii_lowercase().as_str() {
+        "*" => Some(None),
+        "added" => Some(Some(ChangeKind::Added)),
+        "removed" => Some(Some(ChangeKind::Removed)),
+        "modified" => Some(Some(ChangeKind::Modified)),
+        _ => None,
+    }
+}
+
+/// Parses an object kind; `Some(None)` is the `*` wildcard.
+fn parse_object_kind(text: &str) -> Option<Option<DriftObject>> {
+    if text == "*" {
+        return Some(None);
+    }
+    DriftObject::ALL
+        .into_iter()
+        .find(|kind| kind.as_str().eq_ignore_ascii_case(text))
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(kind: ChangeKind, object_kind: DriftObject, object: &str) -> SchemaChange {
+        SchemaChange {
+            database: "shop".to_string(),
+            kind,
+            object_kind,
+            object: object.to_string(),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_rules_match_kind_object_and_pattern() {
+        let mut allow = AllowList::default();
+        allow
+            .extend_from_str(
+                "# expected\n\nadded column *.*.created_at\n* * Staging.*\nremoved foreign-key public.orders.*\n",
+            )
+            .expect("valid allow-list");
+        assert_eq!(allow.len(), 3);
+
+        assert!(allow.allows(&change(
+            ChangeKind::Added,
+            DriftObject::Column,
+            "public.orders.created_at"
+        )));
+        assert!(!allow.allows(&change(
+            ChangeKind::Removed,
+            DriftObject::Column,
+            "public.orders.created_at"
+        )));
+        assert!(allow.allows(&change(
+            ChangeKind::Modified,
+            DriftObject::Table,
+            "staging.import"
+        )));
+        assert!(allow.allows(&change(
+            ChangeKind::Removed,
+            DriftObject::ForeignKey,
+            "public.orders.orders_customer_fk"
+        )));
+        assert!(!allow.allows(&change(
+            ChangeKind::Added,
+            DriftObject::Table,
+            "public.payments"
+        )));
+    }
+
+    #[test]
+    fn test_malformed_rule_is_rejected() {
+        let mut allow = AllowList::default();
+        let error = allow
+            .extend_from_str("added column a.b\nrenamed table *\n")
+            .expect_err("unknown change kind");
+        assert!(error.to_string().contains("line 2"));
+
+        assert!(
+            AllowList::default()
+                .extend_from_str("added widget *")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Documenting a directory of survey files.
+//!
+//! `generate <dir>` walks the directory for survey files matching a glob,
+//! documents each one on its own, and writes an `index.md` linking every
+//! generated output. A file that cannot be read or validated is listed in
+//! the index with its error instead of stopping the batch.
+
+use crate::markdown::escape_cell;
+use crate::search::INDEX_SUFFIX;
+use dbsurveyor_core::{DbSurveyorError, Result};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Survey files the collector writes: plain, compressed, encrypted, and
+/// JSON Lines bundles.
+pub(crate) const DEFAULT_PATTERN: &str = "**/*.{json,json.zst,enc,jsonl,jsonl.zst}";
+
+/// File name of the consolidated index page.
+pub(crate) const INDEX_FILE: &str = "index.md";
+
+/// Outcome of documenting one survey file.
+pub(crate) struct BatchEntry {
+    /// Survey file, relative to the input directory
+    pub(crate) survey: PathBuf,
+    /// Generated outputs, or the error that stopped this file
+    pub(crate) outcome: std::result::Result<Vec<PathBuf>, String>,
+}
+
+/// Finds survey files under `dir` whose path relative to `dir` matches
+/// `pattern`, in sorted order.
+///
+/// Search indexes and anything under `exclude` (the output directory, when
+/// it lives inside the input directory) are skipped.
+///
+/// # Errors
+/// Returns an error if a directory cannot be read.
+pub(crate) fn find_surveys(dir: &Path, pattern: &str, exclude: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current).map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to read directory {}", current.display()),
+            source: e,
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| DbSurveyorError::Io {
+                    context: format!("Failed to read directory {}", current.display()),
+                    source: e,
+                })?
+                .path();
+            if path.starts_with(exclude) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !relative.ends_with(INDEX_SUFFIX) && glob_match::glob_match(pattern, &relative) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Renders the index page for `output_dir`, linking outputs relative to it.
+pub(crate) fn render_index(input_dir: &Path, output_dir: &Path, entries: &[BatchEntry]) -> String {
+    let failed = entries
+        .iter()
+        .filter(|entry| entry.outcome.is_err())
+        .count();
+    let mut content = String::from("# Survey Index\n\n");
+    let _ = writeln!(
+        content,
+        "Documented {} of {} survey files from `{}`.\n",
+        entries.len().saturating_sub(failed),
+        entries.len(),
+        input_dir.display()
+    );
+    content.push_str("| Survey | Status | Documentation |\n");
+    content.push_str("| ------ | ------ | ------------- |\n");
+    for entry in entries {
+        let survey = escape_cell(&entry.survey.display().to_string());
+        match &entry.outcome {
+            Ok(outputs) => {
+                let links: Vec<String> = outputs
+                    .iter()
+                    .map(|output| {
+                        let target = output
+                            .strip_prefix(output_dir)
+                            .unwrap_or(output)
+                            .to_string_lossy()
+                            .replace('\\', "/");
+                        format!("[{}]({})", escape_cell(&target), target.replace(' ', "%20"))
+                    })
+                    .collect();
+                let _ = writeln!(content, "| `{}` | OK | {} |", survey, links.join("<br>"));
+            }
+            Err(error) => {
+                let _ = writeln!(
+                    content,
+                    "| `{}` | Failed: {} | |",
+                    survey,
+                    escape_cell(error)
+                );
+            }
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_surveys_matches_pattern_recursively() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("prod/eu")).expect("failed to create dirs");
+        std::fs::create_dir_all(root.join("docs")).expect("failed to create dirs");
+        for file in [
+            "a.dbsurveyor.json",
+            "a.dbsurveyor.json.index.json",
+            "notes.txt",
+            "prod/b.json.zst",
+            "prod/eu/c.enc",
+            "docs/report_graph.json",
+        ] {
+            std::fs::write(root.join(file), "").expect("failed to write file");
+        }
+
+        let found =
+            find_surveys(root, DEFAULT_PATTERN, &root.join("docs")).expect("directory is readable");
+        let found: Vec<&Path> = found
+            .iter()
+            .map(|path| path.strip_prefix(root).expect("path under root"))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                Path::new("a.dbsurveyor.json"),
+                Path::new("prod/b.json.zst"),
+                Path::new("prod/eu/c.enc"),
+            ]
+        );
+
+        let found =
+            find_surveys(root, "prod/**/*.enc", &root.join("docs")).expect("directory is readable");
+        assert_eq!(found, [root.join("prod/eu/c.enc")]);
+    }
+
+    #[test]
+    fn test_render_index_lists_outputs_and_failures() {
+        let entries = [
+            BatchEntry {
+                survey: PathBuf::from("prod/sales.json"),
+                outcome: Ok(vec![PathBuf::from("out/prod/sales.md")]),
+            },
+            BatchEntry {
+                survey: PathBuf::from("broken.json"),
+                outcome: Err("Schema validation failed: a | b".to_string()),
+            },
+        ];
+
+        let index = render_index(Path::new("surveys"), Path::new("out"), &entries);
+        assert!(index.contains("Documented 1 of 2 survey files from `surveys`."));
+        assert!(index.contains("| `prod/sales.json` | OK | [prod/sales.md](prod/sales.md) |"));
+        assert!(index.contains("| `broken.json` | Failed: Schema validation failed: a \\| b | |"));
+    }
+}
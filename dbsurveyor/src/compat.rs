@@ -0,0 +1,218 @@
+//! Type compatibility report for migration planning.
+//!
+//! `compat --dialect <target>` maps every column of a survey to the target
+//! dialect with the same type translation the `sql` command uses, and sorts
+//! the columns by how well the target type holds their values:
+//!
+//! - **exact**: same range and precision
+//! - **widened**: a larger target type holds every source value
+//! - **narrowed**: the target approximates the source and loses values or
+//!   attributes such as time zone offsets or decimal scale
+//! - **no equivalent**: the target has no such type; values fall back to
+//!   text or binary and lose their type
+//!
+//! The report opens with the counts per fit, followed by a matrix of each
+//! distinct source type and its target, and the columns that need review.
+
+use crate::SqlDialect;
+use crate::markdown::{column_type_label, escape_cell, qualified_table_name};
+use crate::sql::{Fit, map_type};
+use dbsurveyor_core::models::DatabaseSchema;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How one column's type maps to the target dialect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ColumnFit {
+    /// Qualified `table.column` name
+    pub(crate) column: String,
+    /// Source type as declared, or its unified label
+    pub(crate) source_type: String,
+    pub(crate) target_type: String,
+    pub(crate) fit: Fit,
+    /// Why the column is not an exact fit
+    pub(crate) reason: Option<String>,
+}
+
+/// Maps every column of `schema` to `dialect`.
+pub(crate) fn assess(schema: &DatabaseSchema, dialect: SqlDialect) -> Vec<ColumnFit> {
+    schema
+        .tables
+        .iter()
+        .flat_map(|table| {
+            table.columns.iter().map(move |column| {
+                let mapped = map_type(&column.data_type, dialect);
+                ColumnFit {
+                    column: format!("{}.{}", qualified_table_name(table), column.name),
+                    source_type: column_type_label(column),
+                    target_type: mapped.sql,
+                    reason: match mapped.fit {
+                        Fit::Widened => Some("target type holds a wider range".to_string()),
+                        _ => mapped.lossy,
+                    },
+                    fit: mapped.fit,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Renders the compatibility report for `schema` migrating to `dialect`.
+pub(crate) fn render_report(schema: &DatabaseSchema, dialect: SqlDialect) -> String {
+    let fits = assess(schema, dialect);
+    let mut content = format!(
+        "# Type Compatibility: {} to {}\n\n",
+        schema.database_info.name,
+        dialect.label()
+    );
+    if let Some(engine) = &schema.database_info.engine {
+        let _ = writeln!(content, "Source engine: {}\n", engine);
+    }
+
+    content.push_str("## Summary\n\n| Fit | Columns |\n| --- | ------- |\n");
+    for fit in Fit::ALL {
+        let count = fits.iter().filter(|f| f.fit == fit).count();
+        let _ = writeln!(content, "| {} | {} |", fit, count);
+    }
+    content.push('\n');
+    if fits.is_empty() {
+        content.push_str("No columns collected.\n");
+        return content;
+    }
+
+    // Distinct source types, with the target each maps to
+    let mut matrix: BTreeMap<(&str, &str, Fit), usize> = BTreeMap::new();
+    for fit in &fits {
+        *matrix
+            .entry((&fit.source_type, &fit.target_type, fit.fit))
+            .or_default() += 1;
+    }
+    content.push_str(
+        "## Type Matrix\n\n\
+        | Source Type | Target Type | Fit | Columns |\n\
+        | ----------- | ----------- | --- | ------- |\n",
+    );
+    for ((source, target, fit), count) in &matrix {
+        let _ = writeln!(
+            content,
+            "| {} | {} | {} | {} |",
+            escape_cell(source),
+            escape_cell(target),
+            fit,
+            count
+        );
+    }
+    content.push('\n');
+
+    let review: Vec<&ColumnFit> = fits.iter().filter(|f| f.fit != Fit::Exact).collect();
+    if !review.is_empty() {
+        content.push_str(
+            "## Columns to Review\n\n\
+            | Column | Source Type | Target Type | Fit | Reason |\n\
+            | ------ | ----------- | ----------- | --- | ------ |\n",
+        );
+        for fit in review {
+            let _ = writeln!(
+                content,
+                "| {} | {} | {} | {} | {} |",
+                escape_cell(&fit.column),
+                escape_cell(&fit.source_type),
+                escape_cell(&fit.target_type),
+                fit.fit,
+                escape_cell(fit.reason.as_deref().unwrap_or_default())
+            );
+        }
+        content.push('\n');
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, DatabaseInfo, Table, UnifiedDataType};
+
+    fn column(name: &str, declared: &str, data_type: UnifiedDataType) -> Column {
+        Column {
+            declared_type: Some(declared.to_string()),
+            ..Column::new(name.to_string(), data_type)
+        }
+    }
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables.push(Table::new(
+            "orders".to_string(),
+            Some("public".to_string()),
+            vec![
+                column(
+                    "id",
+                    "int unsigned",
+                    UnifiedDataType::Integer {
+                        bits: 32,
+                        signed: false,
+                    },
+                ),
+                column(
+                    "placed_at",
+                    "timestamptz",
+                    UnifiedDataType::DateTime {
+                        with_timezone: true,
+                    },
+                ),
+                column("note", "text", UnifiedDataType::String { max_length: None }),
+                column(
+                    "status",
+                    "order_status",
+                    UnifiedDataType::Custom {
+                        type_name: "order_status".to_string(),
+                    },
+                ),
+            ],
+        ));
+        schema
+    }
+
+    #[test]
+    fn test_assess_classifies_each_column() {
+        let fits = assess(&schema(), SqlDialect::PostgreSQL);
+        let by_column: Vec<(&str, &str, Fit)> = fits
+            .iter()
+            .map(|f| (f.column.as_str(), f.target_type.as_str(), f.fit))
+            .collect();
+        assert_eq!(
+            by_column,
+            [
+                ("public.orders.id", "BIGINT", Fit::Widened),
+                (
+                    "public.orders.placed_at",
+                    "TIMESTAMP WITH TIME ZONE",
+                    Fit::Exact
+                ),
+                ("public.orders.note", "TEXT", Fit::Exact),
+                ("public.orders.status", "TEXT", Fit::NoEquivalent),
+            ]
+        );
+
+        let mysql = assess(&schema(), SqlDialect::MySQL);
+        assert_eq!(mysql[0].fit, Fit::Exact);
+        assert_eq!(mysql[1].fit, Fit::Narrowed);
+        assert_eq!(
+            mysql[1].reason.as_deref(),
+            Some("time zone offset is not stored")
+        );
+    }
+
+    #[test]
+    fn test_report_lists_counts_matrix_and_review() {
+        let report = render_report(&schema(), SqlDialect::MySQL);
+        assert!(report.starts_with("# Type Compatibility: shop to MySQL\n"));
+        assert!(report.contains("| exact | 2 |\n| widened | 0 |\n| narrowed | 1 |\n"));
+        assert!(report.contains("| no equivalent | 1 |\n"));
+        assert!(report.contains("| timestamptz | DATETIME | narrowed | 1 |"));
+        assert!(report.contains(
+            "| public.orders.status | order_status | LONGTEXT | no equivalent | type order_status has no MySQL equivalent |"
+        ));
+        assert!(!report.contains("| public.orders.note |"));
+    }
+}
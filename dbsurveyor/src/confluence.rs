@@ -0,0 +1,755 @@
+//! Confluence storage-format export.
+//!
+//! Produces one XHTML storage-format page per database, schema, and table,
+//! plus a `pages.json` manifest describing the page hierarchy so pages can
+//! be imported in order (parents before children) through the Confluence
+//! REST API or pasted into the storage-format editor.
+
+use crate::growth::format_bytes;
+use crate::markdown::site::{SchemaPages, SiteFile, group_tables};
+use crate::markdown::{
+    ReportOptions, ReportScope, ReportSection, column_type_label, enum_values_label,
+    index_options_label, qualified_name, qualified_table_name, referential_action_label,
+    report_title, sample_headers, sample_preview, sample_value_label, sensitive_columns,
+    table_sample, unmatched_names, volume_label, yes_no,
+};
+use crate::naming::NamingDictionary;
+use dbsurveyor_core::models::{DatabaseSchema, Table};
+use dbsurveyor_core::relationships::{InferredRelationship, cardinality};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Entry in `pages.json`.
+#[derive(Debug, Serialize)]
+struct PageEntry {
+    title: String,
+    /// Title of the parent page; `None` for the root page
+    parent: Option<String>,
+    /// Storage-format file, relative to the export directory
+    file: String,
+}
+
+/// Renders the Confluence export for a database.
+pub(crate) fn render_export(schema: &DatabaseSchema, options: &ReportOptions<'_>) -> Vec<SiteFile> {
+    let groups = group_tables(schema);
+    let root_title = report_title(schema, options);
+    let mut files = Vec::new();
+    let mut pages = Vec::new();
+
+    files.push(SiteFile {
+        path: PathBuf::from("index.xhtml"),
+        content: root_page(schema, &groups, options),
+    });
+    pages.push(PageEntry {
+        title: root_title.clone(),
+        parent: None,
+        file: "index.xhtml".to_string(),
+    });
+
+    let database = ReportScope::database(schema, options);
+    for group in &groups {
+        let schema_title = schema_page_title(schema, group.name);
+        let file = format!("{}/index.xhtml", group.slug);
+        files.push(SiteFile {
+            path: PathBuf::from(&file),
+            content: schema_page(schema, group),
+        });
+        pages.push(PageEntry {
+            title: schema_title.clone(),
+            parent: Some(root_title.clone()),
+            file,
+        });
+
+        for (table, slug) in &group.tables {
+            let file = format!("{}/{}.xhtml", group.slug, slug);
+            files.push(SiteFile {
+                path: PathBuf::from(&file),
+                content: table_page(schema, table, &database.table(schema, table), options),
+            });
+            pages.push(PageEntry {
+                title: table_page_title(schema, table),
+                parent: Some(schema_title.clone()),
+                file,
+            });
+        }
+    }
+
+    let mut manifest = serde_json::to_string_pretty(&pages).unwrap_or_default();
+    manifest.push('\n');
+    files.push(SiteFile {
+        path: PathBuf::from("pages.json"),
+        content: manifest,
+    });
+    files
+}
+
+/// Page titles are unique per Confluence space, so they carry the database name.
+fn schema_page_title(schema: &DatabaseSchema, schema_name: &str) -> String {
+    format!("{}: {}", schema.database_info.name, schema_name)
+}
+
+fn table_page_title(schema: &DatabaseSchema, table: &Table) -> String {
+    format!(
+        "{}: {}",
+        schema.database_info.name,
+        qualified_table_name(table)
+    )
+}
+
+fn root_page(
+    schema: &DatabaseSchema,
+    groups: &[SchemaPages<'_>],
+    options: &ReportOptions<'_>,
+) -> String {
+    let mut page = format!(
+        "<p>Generated by DBSurveyor v{} on {}.</p>\n",
+        escape_xml(&schema.collection_metadata.collector_version),
+        schema
+            .collection_metadata
+            .collected_at
+            .format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    page.push_str("<h2>Summary</h2>\n");
+    page.push_str(&xhtml_table(
+        &["Object", "Count"],
+        vec![
+            vec!["Tables".to_string(), schema.tables.len().to_string()],
+            vec!["Views".to_string(), schema.views.len().to_string()],
+            vec!["Indexes".to_string(), schema.indexes.len().to_string()],
+        ],
+    ));
+    page.push_str("<h2>Schemas</h2>\n");
+    page.push_str(&xhtml_table(
+        &["Schema", "Tables"],
+        groups
+            .iter()
+            .map(|group| {
+                vec![
+                    page_link(&schema_page_title(schema, group.name), group.name),
+                    group.tables.len().to_string(),
+                ]
+            })
+            .collect(),
+    ));
+    if let Some(footer) = options.footer {
+        let _ = writeln!(page, "<p>{}</p>", escape_xml(footer));
+    }
+    page
+}
+
+fn schema_page(schema: &DatabaseSchema, group: &SchemaPages<'_>) -> String {
+    let mut page = String::from("<h2>Tables</h2>\n");
+    page.push_str(&xhtml_table(
+        &["Table", "Columns", "Rows"],
+        group
+            .tables
+            .iter()
+            .map(|(table, _)| {
+                vec![
+                    page_link(&table_page_title(schema, table), &table.name),
+                    table.columns.len().to_string(),
+                    table.row_count.map(|c| c.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    ));
+    page
+}
+
+fn table_page(
+    schema: &DatabaseSchema,
+    table: &Table,
+    scope: &ReportScope<'_>,
+    options: &ReportOptions<'_>,
+) -> String {
+    let mut page = String::new();
+    if let Some(comment) = &table.comment {
+        let _ = writeln!(page, "<p>{}</p>", escape_xml(comment));
+    }
+
+    for section in ReportSection::ALL {
+        if !options.sections.contains(&section) {
+            continue;
+        }
+        page.push_str(&match section {
+            ReportSection::Tables => {
+                let mut columns = columns_section(table, scope.names);
+                columns.push_str(&preview_section(table, scope));
+                columns
+            }
+            ReportSection::Indexes => indexes_section(scope),
+            ReportSection::Relationships => relationships_section(schema, table, scope),
+            ReportSection::Samples => samples_section(scope),
+            ReportSection::Quality => quality_section(scope),
+            ReportSection::Classification => classification_section(scope),
+            ReportSection::LargeObjects => large_objects_section(scope),
+            ReportSection::Enums => enums_section(scope),
+            ReportSection::Collation => collation_section(scope),
+            ReportSection::Naming => naming_section(table, scope.names),
+            ReportSection::Documentation => documentation_section(scope),
+        });
+    }
+    page
+}
+
+fn columns_section(table: &Table, names: Option<&NamingDictionary>) -> String {
+    let mut section = String::from("<h2>Columns</h2>\n");
+    if let Some(row_count) = table.row_count {
+        let _ = writeln!(section, "<p>Rows: {}</p>", row_count);
+    }
+    let mut headers = vec!["Column", "Type", "Nullable", "Primary Key", "Default"];
+    if names.is_some() {
+        headers.insert(1, "Display Name");
+    }
+    section.push_str(&xhtml_table(
+        &headers,
+        table
+            .columns
+            .iter()
+            .map(|column| {
+                let mut row = vec![
+                    escape_xml(&column.name),
+                    escape_xml(&column_type_label(column)),
+                    yes_no(column.is_nullable).to_string(),
+                    yes_no(column.is_primary_key).to_string(),
+                    escape_xml(column.default_value.as_deref().unwrap_or("")),
+                ];
+                if let Some(names) = names {
+                    let display = names.display_name(&column.name).unwrap_or_default();
+                    row.insert(1, escape_xml(&display));
+                }
+                row
+            })
+            .collect(),
+    ));
+    section
+}
+
+fn preview_section(table: &Table, scope: &ReportScope<'_>) -> String {
+    let Some(preview) = scope
+        .sample_preview
+        .and_then(|rows| sample_preview(table_sample(&scope.samples, table)?, table, rows))
+    else {
+        return String::new();
+    };
+    let headers: Vec<&str> = preview.headers.iter().map(String::as_str).collect();
+    format!(
+        "<p>Sample preview (first {} rows, masked):</p>\n{}",
+        preview.rows.len(),
+        xhtml_table(
+            &headers,
+            preview
+                .rows
+                .iter()
+                .map(|row| row.iter().map(|cell| escape_xml(cell)).collect())
+                .collect(),
+        )
+    )
+}
+
+fn indexes_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Indexes</h2>\n");
+    if scope.indexes.is_empty() {
+        section.push_str(&info_panel("No indexes collected."));
+        return section;
+    }
+    section.push_str(&xhtml_table(
+        &["Index", "Columns", "Unique", "Type", "Options"],
+        scope
+            .indexes
+            .iter()
+            .map(|index| {
+                let columns: Vec<&str> = index.columns.iter().map(|c| c.name.as_str()).collect();
+                vec![
+                    escape_xml(&index.name),
+                    escape_xml(&columns.join(", ")),
+                    yes_no(index.is_unique).to_string(),
+                    escape_xml(index.index_type.as_deref().unwrap_or("")),
+                    escape_xml(&index_options_label(index.options.as_ref())),
+                ]
+            })
+            .collect(),
+    ));
+    section
+}
+
+/// Foreign keys, linking to the referenced table's page when it was surveyed,
+/// followed by inferred relationships.
+fn relationships_section(
+    schema: &DatabaseSchema,
+    table: &Table,
+    scope: &ReportScope<'_>,
+) -> String {
+    let mut section = String::from("<h2>Relationships</h2>\n");
+    if table.foreign_keys.is_empty() {
+        section.push_str(&info_panel("No foreign keys collected."));
+    } else {
+        section.push_str(&declared_relationships(schema, table));
+    }
+    section.push_str(&inferred_relationships(schema, &scope.inferred));
+    section
+}
+
+fn declared_relationships(schema: &DatabaseSchema, table: &Table) -> String {
+    xhtml_table(
+        &[
+            "Columns",
+            "References",
+            "Cardinality",
+            "On Delete",
+            "On Update",
+        ],
+        table
+            .foreign_keys
+            .iter()
+            .map(|fk| {
+                let target = schema.tables.iter().find(|t| {
+                    t.name == fk.referenced_table
+                        && (fk.referenced_schema.is_none() || t.schema == fk.referenced_schema)
+                });
+                let referenced_name = match &fk.referenced_schema {
+                    Some(schema) => format!("{}.{}", schema, fk.referenced_table),
+                    None => fk.referenced_table.clone(),
+                };
+                let referenced = match target {
+                    Some(target) => page_link(&table_page_title(schema, target), &referenced_name),
+                    None => escape_xml(&referenced_name),
+                };
+                vec![
+                    escape_xml(&fk.columns.join(", ")),
+                    format!(
+                        "{} ({})",
+                        referenced,
+                        escape_xml(&fk.referenced_columns.join(", "))
+                    ),
+                    cardinality(table, &fk.columns).label().to_string(),
+                    referential_action_label(fk.on_delete.as_ref()).to_string(),
+                    referential_action_label(fk.on_update.as_ref()).to_string(),
+                ]
+            })
+            .collect(),
+    )
+}
+
+/// Inferred relationships, in a note panel so they are not mistaken for
+/// declared constraints.
+fn inferred_relationships(schema: &DatabaseSchema, inferred: &[InferredRelationship]) -> String {
+    if inferred.is_empty() {
+        return String::new();
+    }
+    let rows = inferred
+        .iter()
+        .map(|relationship| {
+            let referenced_name = qualified_name(
+                relationship.referenced_schema.as_deref(),
+                &relationship.referenced_table,
+            );
+            let target_title = format!("{}: {}", schema.database_info.name, referenced_name);
+            let evidence: Vec<String> = relationship.evidence.iter().map(|e| e.label()).collect();
+            vec![
+                escape_xml(&relationship.column),
+                format!(
+                    "{} ({})",
+                    page_link(&target_title, &referenced_name),
+                    escape_xml(&relationship.referenced_column)
+                ),
+                format!("{:.0}%", relationship.confidence * 100.0),
+                escape_xml(&evidence.join(", ")),
+            ]
+        })
+        .collect();
+    format!(
+        "<h3>Inferred Relationships</h3>\n\
+        <ac:structured-macro ac:name=\"note\">\n\
+        <ac:rich-text-body>\n\
+        <p>Not enforced by the database. Proposed from column names, data types, and \
+        sampled values; verify before relying on them.</p>\n{}</ac:rich-text-body>\n\
+        </ac:structured-macro>\n",
+        xhtml_table(&["Column", "References", "Confidence", "Evidence"], rows)
+    )
+}
+
+/// Sample rows, collapsed in an expand macro to keep pages short.
+fn samples_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Samples</h2>\n");
+    let Some(sample) = scope.samples.first() else {
+        section.push_str(&info_panel("No samples collected."));
+        return section;
+    };
+    if sample.rows.is_empty() {
+        section.push_str(&info_panel("No rows sampled."));
+        return section;
+    }
+
+    let headers = sample_headers(sample);
+    let body = xhtml_table(
+        &headers,
+        sample
+            .rows
+            .iter()
+            .map(|row| {
+                headers
+                    .iter()
+                    .map(|header| {
+                        escape_xml(&row.get(header).map(sample_value_label).unwrap_or_default())
+                    })
+                    .collect()
+            })
+            .collect(),
+    );
+    let _ = write!(
+        section,
+        "<ac:structured-macro ac:name=\"expand\">\n\
+        <ac:parameter ac:name=\"title\">{} sample rows</ac:parameter>\n\
+        <ac:rich-text-body>\n{}</ac:rich-text-body>\n\
+        </ac:structured-macro>\n",
+        sample.rows.len(),
+        body
+    );
+    section
+}
+
+fn quality_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Data Quality</h2>\n");
+    let Some(metric) = scope.quality.first() else {
+        section.push_str(&info_panel("No quality metrics collected."));
+        return section;
+    };
+    section.push_str(&xhtml_table(
+        &["Metric", "Value"],
+        vec![
+            vec![
+                "Rows Analyzed".to_string(),
+                metric.analyzed_rows.to_string(),
+            ],
+            vec![
+                "Completeness".to_string(),
+                format!("{:.1}%", metric.completeness.score * 100.0),
+            ],
+            vec![
+                "Consistency".to_string(),
+                format!("{:.1}%", metric.consistency.score * 100.0),
+            ],
+            vec![
+                "Uniqueness".to_string(),
+                format!("{:.1}%", metric.uniqueness.score * 100.0),
+            ],
+            vec![
+                "Score".to_string(),
+                format!("{:.1}%", metric.quality_score * 100.0),
+            ],
+            vec![
+                "Threshold Violations".to_string(),
+                metric.threshold_violations.len().to_string(),
+            ],
+        ],
+    ));
+    section
+}
+
+/// Sensitive columns, shown in a warning panel so they stand out.
+fn classification_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Data Classification</h2>\n");
+    let rows: Vec<Vec<String>> = sensitive_columns(scope)
+        .into_iter()
+        .map(|column| {
+            vec![
+                escape_xml(column.column),
+                column.class.unwrap_or("-").to_string(),
+                escape_xml(&column.protection_label()),
+            ]
+        })
+        .collect();
+    if rows.is_empty() {
+        section.push_str(&info_panel("No sensitive columns detected."));
+        return section;
+    }
+    let _ = write!(
+        section,
+        "<ac:structured-macro ac:name=\"warning\">\n\
+        <ac:rich-text-body>\n{}</ac:rich-text-body>\n\
+        </ac:structured-macro>\n",
+        xhtml_table(&["Column", "Classification", "Protection"], rows)
+    );
+    section
+}
+
+fn large_objects_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Large Objects</h2>\n");
+    let Some(table) = scope.large_objects.first() else {
+        section.push_str(&info_panel("No columns appear to store files."));
+        return section;
+    };
+    if let Some(volume) = &table.volume {
+        let _ = writeln!(
+            section,
+            "<p>Estimated volume: {}</p>",
+            escape_xml(&volume_label(volume))
+        );
+    }
+    section.push_str(&xhtml_table(
+        &["Column", "Signals", "Average Size"],
+        table
+            .columns
+            .iter()
+            .map(|column| {
+                let signals: Vec<String> = column.signals.iter().map(|s| s.label()).collect();
+                vec![
+                    escape_xml(&column.column),
+                    escape_xml(&signals.join(", ")),
+                    column
+                        .average_bytes
+                        .map(|bytes| format_bytes(bytes as f64))
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    ));
+    if !table.content_type_columns.is_empty() {
+        let _ = writeln!(
+            section,
+            "<p>Content type: {}</p>",
+            escape_xml(&table.content_type_columns.join(", "))
+        );
+    }
+    section
+}
+
+fn enums_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Inferred Enums</h2>\n");
+    if scope.enums.is_empty() {
+        section.push_str(&info_panel(
+            "No sampled column has a small closed set of values.",
+        ));
+        return section;
+    }
+    section.push_str(&xhtml_table(
+        &["Column", "Values", "Sampled", "Complete"],
+        scope
+            .enums
+            .iter()
+            .map(|inferred| {
+                vec![
+                    escape_xml(&inferred.column),
+                    escape_xml(&enum_values_label(scope, inferred)),
+                    inferred.sampled.to_string(),
+                    yes_no(inferred.complete).to_string(),
+                ]
+            })
+            .collect(),
+    ));
+    section
+}
+
+fn collation_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Collations</h2>\n");
+    if scope.collations.is_empty() {
+        section.push_str(&info_panel(
+            "All columns use the database collation and character set.",
+        ));
+        return section;
+    }
+    section.push_str(&xhtml_table(
+        &["Column", "Collation", "Character Set"],
+        scope
+            .collations
+            .iter()
+            .map(|mismatch| {
+                vec![
+                    escape_xml(&mismatch.column),
+                    escape_xml(mismatch.collation.as_deref().unwrap_or_default()),
+                    escape_xml(mismatch.character_set.as_deref().unwrap_or_default()),
+                ]
+            })
+            .collect(),
+    ));
+    section
+}
+
+fn documentation_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("<h2>Documentation</h2>\n");
+    let Some(undocumented) = scope.undocumented.first() else {
+        section.push_str(&info_panel("The table and all columns have comments."));
+        return section;
+    };
+    if undocumented.missing_table_comment {
+        section.push_str("<p>The table has no comment.</p>\n");
+    }
+    if !undocumented.undocumented_columns.is_empty() {
+        section.push_str(&xhtml_table(
+            &["Undocumented Column"],
+            undocumented
+                .undocumented_columns
+                .iter()
+                .map(|column| vec![escape_xml(column)])
+                .collect(),
+        ));
+    }
+    section
+}
+
+fn naming_section(table: &Table, names: Option<&NamingDictionary>) -> String {
+    let mut section = String::from("<h2>Naming</h2>\n");
+    let empty = NamingDictionary::default();
+    let unmatched = unmatched_names(&[table], names.unwrap_or(&empty));
+    if unmatched.is_empty() {
+        section.push_str(&info_panel("No unexpanded abbreviations found."));
+        return section;
+    }
+    section.push_str(&xhtml_table(
+        &["Name", "Abbreviations"],
+        unmatched
+            .into_iter()
+            .map(|(name, words)| vec![escape_xml(&name), escape_xml(&words.join(", "))])
+            .collect(),
+    ));
+    section
+}
+
+/// Renders a table; cells must already be escaped storage-format fragments.
+fn xhtml_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut table = String::from("<table>\n<tbody>\n<tr>");
+    for header in headers {
+        let _ = write!(table, "<th>{}</th>", escape_xml(header));
+    }
+    table.push_str("</tr>\n");
+    for row in rows {
+        table.push_str("<tr>");
+        for cell in row {
+            let _ = write!(table, "<td>{}</td>", cell);
+        }
+        table.push_str("</tr>\n");
+    }
+    table.push_str("</tbody>\n</table>\n");
+    table
+}
+
+fn info_panel(message: &str) -> String {
+    format!(
+        "<ac:structured-macro ac:name=\"info\">\n\
+        <ac:rich-text-body><p>{}</p></ac:rich-text-body>\n\
+        </ac:structured-macro>\n",
+        escape_xml(message)
+    )
+}
+
+/// Link to another page in the same space by title.
+fn page_link(title: &str, text: &str) -> String {
+    format!(
+        "<ac:link><ri:page ri:content-title=\"{}\" />\
+        <ac:plain-text-link-body><![CDATA[{}]]></ac:plain-text-link-body></ac:link>",
+        escape_xml(title),
+        text.replace("]]>", "]]]]><![CDATA[>")
+    )
+}
+
+/// Escapes text for XML content and attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{
+        Column, DatabaseInfo, ForeignKey, ReferentialAction, UnifiedDataType,
+    };
+
+    fn table(name: &str, columns: &[&str], foreign_keys: Vec<ForeignKey>) -> Table {
+        Table {
+            foreign_keys,
+            comment: Some("Orders & <returns>".to_string()),
+            ..Table::new(
+                name.to_string(),
+                Some("public".to_string()),
+                columns
+                    .iter()
+                    .map(|name| Column {
+                        is_nullable: false,
+                        ..Column::new(
+                            (*name).to_string(),
+                            UnifiedDataType::Integer {
+                                bits: 32,
+                                signed: true,
+                            },
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![
+            table("customers", &["id", "email"], Vec::new()),
+            table(
+                "orders",
+                &["id", "customer_id"],
+                vec![ForeignKey {
+                    name: None,
+                    columns: vec!["customer_id".to_string()],
+                    referenced_table: "customers".to_string(),
+                    referenced_schema: Some("public".to_string()),
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: Some(ReferentialAction::Cascade),
+                    on_update: None,
+                }],
+            ),
+        ];
+        schema
+    }
+
+    #[test]
+    fn test_page_hierarchy_manifest() {
+        let files = render_export(&fixture(), &ReportOptions::default());
+        let manifest = files
+            .iter()
+            .find(|f| f.path.to_str() == Some("pages.json"))
+            .expect("manifest rendered");
+        let pages: serde_json::Value =
+            serde_json::from_str(&manifest.content).expect("valid manifest");
+
+        assert_eq!(pages[0]["title"], "Database Schema: shop");
+        assert!(pages[0]["parent"].is_null());
+        assert_eq!(pages[1]["title"], "shop: public");
+        assert_eq!(pages[1]["parent"], "Database Schema: shop");
+        assert_eq!(pages[3]["title"], "shop: public.orders");
+        assert_eq!(pages[3]["parent"], "shop: public");
+        assert_eq!(pages[3]["file"], "public/orders.xhtml");
+    }
+
+    #[test]
+    fn test_table_page_storage_format() {
+        let schema = fixture();
+        let options = ReportOptions::default();
+        let database = ReportScope::database(&schema, &options);
+        let orders = &schema.tables[1];
+        let page = table_page(&schema, orders, &database.table(&schema, orders), &options);
+
+        assert!(page.contains("<p>Orders &amp; &lt;returns&gt;</p>"));
+        assert!(page.contains("<th>Column</th><th>Type</th>"));
+        assert!(page.contains("<ri:page ri:content-title=\"shop: public.customers\" />"));
+        assert!(page.contains("<ac:structured-macro ac:name=\"info\">"));
+
+        let options = ReportOptions {
+            sections: &[ReportSection::Classification],
+            ..ReportOptions::default()
+        };
+        let database = ReportScope::database(&schema, &options);
+        let customers = &schema.tables[0];
+        let customers = table_page(
+            &schema,
+            customers,
+            &database.table(&schema, customers),
+            &options,
+        );
+        assert!(customers.contains("ac:name=\"warning\""));
+        assert!(customers.contains("<td>email</td><td>PII</td>"));
+        assert!(!customers.contains("<h2>Columns</h2>"));
+    }
+}
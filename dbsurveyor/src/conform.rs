@@ -0,0 +1,347 @@
+//! Conformance of a survey to an expected-schema specification
+//! (`conform`).
+//!
+//! The specification is a JSON subset of the survey model naming the tables
+//! and columns a database must have, optionally with their types and
+//! nullability, plus the columns allowed to hold personal data:
+//!
+//! ```json
+//! {
+//!   "tables": [
+//!     {
+//!       "schema": "public",
+//!       "name": "orders",
+//!       "columns": [
+//!         { "name": "id", "data_type": "int*", "is_nullable": false },
+//!         { "name": "customer_id" }
+//!       ]
+//!     }
+//!   ],
+//!   "pii": { "allowed": ["public.customers.email"] }
+//! }
+//! ```
+//!
+//! Names match case-insensitively; a table without `schema` matches in any
+//! schema. `data_type` is a glob matched against both the declared type and
+//! the unified type (`int32`, `string(255)`, `timestamptz`). When `pii` is
+//! present, every column classified as PII or a credential must match one
+//! of the `allowed` globs over `schema.table.column`. Tables and columns not
+//! named in the specification are allowed.
+
+use crate::markdown::{data_type_label, qualified_table_name};
+use crate::redaction::classify_field;
+use dbsurveyor_core::models::{Column, DatabaseSchema, Table};
+use dbsurveyor_core::{DbSurveyorError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Process exit code when a survey does not conform.
+pub(crate) const NONCONFORMING_EXIT_CODE: i32 = 2;
+
+/// An expected-schema specification.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ExpectedSchema {
+    #[serde(default)]
+    tables: Vec<ExpectedTable>,
+    pii: Option<PiiRules>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedTable {
+    schema: Option<String>,
+    name: String,
+    #[serde(default)]
+    columns: Vec<ExpectedColumn>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedColumn {
+    name: String,
+    /// Glob over the declared or unified type
+    data_type: Option<String>,
+    is_nullable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PiiRules {
+    /// Globs over `schema.table.column` allowed to hold personal data
+    #[serde(default)]
+    allowed: Vec<String>,
+}
+
+impl ExpectedSchema {
+    /// Parses a specification file.
+    ///
+    /// # Errors
+    /// Returns a configuration error if the content is not a valid
+    /// specification.
+    pub(crate) fn parse(content: &str) -> Result<Self> {
+        serde_json::from_str(content).map_err(|e| {
+            DbSurveyorError::configuration(format!("Invalid expected-schema file: {}", e))
+        })
+    }
+}
+
+/// Result of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Finding {
+    pub(crate) database: String,
+    pub(crate) passed: bool,
+    /// `table`, `column`, `type`, `nullability`, or `pii`
+    pub(crate) check: &'static str,
+    pub(crate) object: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) detail: Option<String>,
+}
+
+/// Checks one database against `spec`, returning a finding per check in
+/// specification order, followed by the personal-data check.
+pub(crate) fn check_conformance(spec: &ExpectedSchema, schema: &DatabaseSchema) -> Vec<Finding> {
+    let database = &schema.database_info.name;
+    let finding =
+        |passed: bool, check: &'static str, object: String, detail: Option<String>| Finding {
+            database: database.clone(),
+            passed,
+            check,
+            object,
+            detail,
+        };
+    let mut findings = Vec::new();
+
+    for expected in &spec.tables {
+        let object = match &expected.schema {
+            Some(schema) => format!("{}.{}", schema, expected.name),
+            None => expected.name.clone(),
+        };
+        let Some(table) = schema
+            .tables
+            .iter()
+            .find(|table| table_matches(expected, table))
+        else {
+            findings.push(finding(false, "table", object, Some("missing".to_string())));
+            continue;
+        };
+        findings.push(finding(true, "table", object, None));
+
+        for expected_column in &expected.columns {
+            let object = format!("{}.{}", qualified_table_name(table), expected_column.name);
+            let Some(column) = table
+                .columns
+                .iter()
+                .find(|column| column.name.eq_ignore_ascii_case(&expected_column.name))
+            else {
+                findings.push(finding(
+                    false,
+                    "column",
+                    object,
+                    Some("missing".to_string()),
+                ));
+                continue;
+            };
+            findings.push(finding(true, "column", object.clone(), None));
+
+            if let Some(pattern) = &expected_column.data_type {
+                let actual = column_types(column);
+                let passed = actual
+                    .iter()
+                    .any(|actual| glob_match::glob_match(&pattern.to_lowercase(), actual));
+                findings.push(finding(
+                    passed,
+                    "type",
+                    object.clone(),
+                    Some(format!(
+                        "expected {}, found {}",
+                        pattern,
+                        actual.join(" / ")
+                    )),
+                ));
+            }
+            if let Some(nullable) = expected_column.is_nullable {
+                findings.push(finding(
+                    column.is_nullable == nullable,
+                    "nullability",
+                    object,
+                    Some(format!(
+                        "expected {}, found {}",
+                        nullability(nullable),
+                        nullability(column.is_nullable)
+                    )),
+                ));
+            }
+        }
+    }
+
+    if let Some(pii) = &spec.pii {
+        let allowed: Vec<String> = pii.allowed.iter().map(|p| p.to_lowercase()).collect();
+        let before = findings.len();
+        for table in &schema.tables {
+            for column in &table.columns {
+                let Some(classification) = classify_field(&column.name) else {
+                    continue;
+                };
+                let object = format!("{}.{}", qualified_table_name(table), column.name);
+                let lower = object.to_lowercase();
+                if !allowed
+                    .iter()
+                    .any(|pattern| glob_match::glob_match(pattern, &lower))
+                {
+                    findings.push(finding(
+                        false,
+                        "pii",
+                        object,
+                        Some(format!("unexpected {} column", classification)),
+                    ));
+                }
+            }
+        }
+        if findings.len() == before {
+            findings.push(finding(
+                true,
+                "pii",
+                "*".to_string(),
+                Some("no unexpected personal data columns".to_string()),
+            ));
+        }
+    }
+
+    findings
+}
+
+fn table_matches(expected: &ExpectedTable, table: &Table) -> bool {
+    table.name.eq_ignore_ascii_case(&expected.name)
+        && expected.schema.as_deref().is_none_or(|schema| {
+            table
+                .schema
+                .as_deref()
+                .is_some_and(|actual| actual.eq_ignore_ascii_case(schema))
+        })
+}
+
+/// Lowercase declared and unified type labels of a column.
+fn column_types(column: &Column) -> Vec<String> {
+    let unified = data_type_label(&column.data_type).to_lowercase();
+    match &column.declared_type {
+        Some(declared) if !declared.eq_ignore_ascii_case(&unified) => {
+            vec![declared.to_lowercase(), unified]
+        }
+        _ => vec![unified],
+    }
+}
+
+fn nullability(nullable: bool) -> &'static str {
+    if nullable { "NULL" } else { "NOT NULL" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{DatabaseInfo, UnifiedDataType};
+
+    fn column(name: &str, data_type: UnifiedDataType, nullable: bool) -> Column {
+        Column {
+            is_nullable: nullable,
+            ..Column::new(name.to_string(), data_type)
+        }
+    }
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        let columns = vec![
+            column(
+                "id",
+                UnifiedDataType::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+                false,
+            ),
+            column(
+                "email",
+                UnifiedDataType::String {
+                    max_length: Some(255),
+                },
+                true,
+            ),
+        ];
+        schema.tables.push(Table::new(
+            "orders".to_string(),
+            Some("public".to_string()),
+            columns,
+        ));
+        schema
+    }
+
+    fn failures(findings: &[Finding]) -> Vec<(&'static str, &str)> {
+        findings
+            .iter()
+            .filter(|finding| !finding.passed)
+            .map(|finding| (finding.check, finding.object.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_conforming_schema_passes() {
+        let spec = ExpectedSchema::parse(
+            r#"{
+                "tables": [{
+                    "schema": "PUBLIC",
+                    "name": "orders",
+                    "columns": [
+                        {"name": "id", "data_type": "int*", "is_nullable": false},
+                        {"name": "EMAIL", "data_type": "string(255)"}
+                    ]
+                }],
+                "pii": {"allowed": ["public.orders.email"]}
+            }"#,
+        )
+        .expect("valid spec");
+        let findings = check_conformance(&spec, &schema());
+        assert!(failures(&findings).is_empty(), "{findings:?}");
+        assert_eq!(findings.len(), 7);
+    }
+
+    #[test]
+    fn test_reports_missing_objects_mismatches_and_pii() {
+        let spec = ExpectedSchema::parse(
+            r#"{
+                "tables": [
+                    {"name": "orders", "columns": [
+                        {"name": "id", "data_type": "uuid", "is_nullable": true},
+                        {"name": "total"}
+                    ]},
+                    {"schema": "sales", "name": "orders"}
+                ],
+                "pii": {}
+            }"#,
+        )
+        .expect("valid spec");
+        let findings = check_conformance(&spec, &schema());
+        assert_eq!(
+            failures(&findings),
+            [
+                ("type", "public.orders.id"),
+                ("nullability", "public.orders.id"),
+                ("column", "public.orders.total"),
+                ("table", "sales.orders"),
+                ("pii", "public.orders.email"),
+            ]
+        );
+        let type_finding = findings
+            .iter()
+            .find(|finding| finding.check == "type")
+            .expect("type finding");
+        assert_eq!(
+            type_finding.detail.as_deref(),
+            Some("expected uuid, found int64")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        assert!(ExpectedSchema::parse(r#"{"tabels": []}"#).is_err());
+        assert!(ExpectedSchema::parse(r#"{"tables": [{"name": "t", "colums": []}]}"#).is_err());
+    }
+}
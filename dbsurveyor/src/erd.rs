@@ -0,0 +1,541 @@
+//! Built-in ER diagram layout and SVG rendering.
+//!
+//! Diagrams are laid out without external tools such as Graphviz so the
+//! HTML report can embed them on air-gapped machines. Tables are ordered so
+//! that related tables are adjacent (breadth-first over relationships, most
+//! connected first) and placed on a grid. Declared foreign keys are drawn as
+//! solid lines; inferred relationships are dashed. Each line's tooltip gives
+//! its cardinality (`1:1` or `N:1`).
+
+use crate::confluence::escape_xml;
+use crate::markdown::{column_type_label, qualified_table_name};
+use dbsurveyor_core::models::Table;
+use dbsurveyor_core::relationships::{Cardinality, InferredRelationship, cardinality};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+const CHAR_WIDTH: u32 = 7;
+const MIN_BOX_WIDTH: u32 = 160;
+const MAX_LABEL_CHARS: usize = 40;
+const HEADER_HEIGHT: u32 = 26;
+const ROW_HEIGHT: u32 = 18;
+const BOX_PADDING: u32 = 10;
+/// Columns shown per table before collapsing the rest into "N more"
+const MAX_ROWS: usize = 12;
+const GAP_X: u32 = 80;
+const GAP_Y: u32 = 60;
+const MARGIN: u32 = 20;
+const LEGEND_HEIGHT: u32 = 30;
+
+const DECLARED_COLOR: &str = "#334155";
+const INFERRED_COLOR: &str = "#b45309";
+
+/// A relationship between two diagram tables, by index.
+struct Edge {
+    child: usize,
+    parent: usize,
+    label: String,
+    cardinality: Cardinality,
+    inferred: bool,
+}
+
+/// A positioned table box.
+struct Node<'a> {
+    table: &'a Table,
+    rows: Vec<String>,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Node<'_> {
+    fn center(&self) -> (f64, f64) {
+        (
+            f64::from(self.x) + f64::from(self.width) / 2.0,
+            f64::from(self.y) + f64::from(self.height) / 2.0,
+        )
+    }
+
+    /// Point where the line from the center towards `(dx, dy)` leaves the box.
+    fn border_point(&self, dx: f64, dy: f64) -> (f64, f64) {
+        let (cx, cy) = self.center();
+        let half_width = f64::from(self.width) / 2.0;
+        let half_height = f64::from(self.height) / 2.0;
+        let scale_x = if dx == 0.0 {
+            f64::INFINITY
+        } else {
+            half_width / dx.abs()
+        };
+        let scale_y = if dy == 0.0 {
+            f64::INFINITY
+        } else {
+            half_height / dy.abs()
+        };
+        let scale = scale_x.min(scale_y);
+        (cx + dx * scale, cy + dy * scale)
+    }
+}
+
+/// Renders an SVG diagram of `tables` with the relationships among them.
+///
+/// `id` must be unique within the containing document; it prefixes the
+/// marker definitions.
+pub(crate) fn render_svg(id: &str, tables: &[&Table], inferred: &[InferredRelationship]) -> String {
+    let edges = collect_edges(tables, inferred);
+    let order = layout_order(tables.len(), &edges);
+    let nodes = place_nodes(tables, &order);
+
+    let width = nodes
+        .iter()
+        .map(|n| n.x.saturating_add(n.width))
+        .max()
+        .unwrap_or(0)
+        .saturating_add(MARGIN)
+        .max(2 * MIN_BOX_WIDTH);
+    let diagram_height = nodes
+        .iter()
+        .map(|n| n.y.saturating_add(n.height))
+        .max()
+        .unwrap_or(MARGIN);
+    let height = diagram_height.saturating_add(MARGIN + LEGEND_HEIGHT);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"erd\" viewBox=\"0 0 {width} {height}\" \
+        width=\"{width}\" height=\"{height}\" role=\"img\" aria-label=\"Entity relationship diagram\" \
+        font-family=\"ui-monospace, SFMono-Regular, Menlo, monospace\" font-size=\"12\">\n"
+    );
+    svg.push_str("<defs>");
+    for (kind, color) in [("declared", DECLARED_COLOR), ("inferred", INFERRED_COLOR)] {
+        let _ = write!(
+            svg,
+            "<marker id=\"{id}-{kind}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+            markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\
+            <path d=\"M0,0 L10,5 L0,10 z\" fill=\"{color}\"/></marker>"
+        );
+    }
+    svg.push_str("</defs>\n");
+
+    // Edges first so boxes are drawn over line ends
+    for edge in &edges {
+        svg.push_str(&edge_path(id, &nodes, &order, edge));
+    }
+    for node in &nodes {
+        svg.push_str(&table_box(node));
+    }
+    svg.push_str(&legend(id, diagram_height.saturating_add(MARGIN)));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Declared foreign keys and inferred relationships whose ends are both in
+/// `tables`.
+fn collect_edges(tables: &[&Table], inferred: &[InferredRelationship]) -> Vec<Edge> {
+    let position = |schema: Option<&str>, name: &str| {
+        tables
+            .iter()
+            .position(|t| t.name == name && (schema.is_none() || t.schema.as_deref() == schema))
+    };
+
+    let mut edges = Vec::new();
+    for (child, table) in tables.iter().enumerate() {
+        for fk in &table.foreign_keys {
+            let schema = fk.referenced_schema.as_deref().or(table.schema.as_deref());
+            if let Some(parent) = position(schema, &fk.referenced_table) {
+                edges.push(Edge {
+                    child,
+                    parent,
+                    label: fk.columns.join(", "),
+                    cardinality: cardinality(table, &fk.columns),
+                    inferred: false,
+                });
+            }
+        }
+    }
+    for relationship in inferred {
+        let child = position(relationship.schema.as_deref(), &relationship.table);
+        let parent = position(
+            relationship.referenced_schema.as_deref(),
+            &relationship.referenced_table,
+        );
+        if let (Some(child), Some(parent)) = (child, parent) {
+            edges.push(Edge {
+                child,
+                parent,
+                label: format!(
+                    "{} (inferred {:.0}%)",
+                    relationship.column,
+                    relationship.confidence * 100.0
+                ),
+                cardinality: relationship.cardinality,
+                inferred: true,
+            });
+        }
+    }
+    edges
+}
+
+/// Orders tables breadth-first over relationships, starting each connected
+/// group from its most connected table.
+fn layout_order(count: usize, edges: &[Edge]) -> Vec<usize> {
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for edge in edges {
+        if edge.child != edge.parent {
+            neighbours[edge.child].push(edge.parent);
+            neighbours[edge.parent].push(edge.child);
+        }
+    }
+    let mut by_degree: Vec<usize> = (0..count).collect();
+    by_degree.sort_by_key(|&i| std::cmp::Reverse(neighbours[i].len()));
+
+    let mut order = Vec::with_capacity(count);
+    let mut visited = HashSet::new();
+    for start in by_degree {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut queue = VecDeque::from([start]);
+        while let Some(index) = queue.pop_front() {
+            order.push(index);
+            for &next in &neighbours[index] {
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Places tables on a near-square grid in `order`.
+///
+/// Returns nodes indexed like `order` (i.e. `nodes[k]` is `tables[order[k]]`).
+fn place_nodes<'a>(tables: &[&'a Table], order: &[usize]) -> Vec<Node<'a>> {
+    let mut nodes: Vec<Node<'a>> = order.iter().map(|&i| sized_node(tables[i])).collect();
+
+    let mut grid_columns: usize = 1;
+    while grid_columns.saturating_mul(grid_columns) < nodes.len() {
+        grid_columns = grid_columns.saturating_add(1);
+    }
+
+    let mut column_widths = vec![0u32; grid_columns];
+    for (k, node) in nodes.iter().enumerate() {
+        let column = &mut column_widths[k % grid_columns];
+        *column = (*column).max(node.width);
+    }
+
+    let mut y = MARGIN;
+    for row in nodes.chunks_mut(grid_columns) {
+        let mut x = MARGIN;
+        let mut row_height = 0;
+        for (column, node) in row.iter_mut().enumerate() {
+            node.x = x;
+            node.y = y;
+            row_height = row_height.max(node.height);
+            x = x
+                .saturating_add(column_widths[column])
+                .saturating_add(GAP_X);
+        }
+        y = y.saturating_add(row_height).saturating_add(GAP_Y);
+    }
+    nodes
+}
+
+fn sized_node(table: &Table) -> Node<'_> {
+    let key_columns: HashSet<&str> = table
+        .primary_key
+        .iter()
+        .flat_map(|pk| pk.columns.iter().map(String::as_str))
+        .collect();
+    let foreign_key_columns: HashSet<&str> = table
+        .foreign_keys
+        .iter()
+        .flat_map(|fk| fk.columns.iter().map(String::as_str))
+        .collect();
+
+    let mut rows: Vec<String> = table
+        .columns
+        .iter()
+        .take(MAX_ROWS)
+        .map(|column| {
+            let marker = if column.is_primary_key || key_columns.contains(column.name.as_str()) {
+                "PK "
+            } else if foreign_key_columns.contains(column.name.as_str()) {
+                "FK "
+            } else {
+                "   "
+            };
+            truncate(&format!(
+                "{}{} {}",
+                marker,
+                column.name,
+                column_type_label(column)
+            ))
+        })
+        .collect();
+    if table.columns.len() > MAX_ROWS {
+        rows.push(format!(
+            "   ... {} more",
+            table.columns.len().saturating_sub(MAX_ROWS)
+        ));
+    }
+
+    let title = truncate(&qualified_table_name(table));
+    let widest = rows
+        .iter()
+        .map(|r| r.chars().count())
+        .chain(std::iter::once(title.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let width = u32::try_from(widest)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(CHAR_WIDTH)
+        .saturating_add(2 * BOX_PADDING)
+        .max(MIN_BOX_WIDTH);
+    let height = u32::try_from(rows.len())
+        .unwrap_or(u32::MAX)
+        .saturating_mul(ROW_HEIGHT)
+        .saturating_add(HEADER_HEIGHT + BOX_PADDING);
+
+    Node {
+        table,
+        rows,
+        x: 0,
+        y: 0,
+        width,
+        height,
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_LABEL_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text
+        .chars()
+        .take(MAX_LABEL_CHARS.saturating_sub(1))
+        .collect();
+    truncated.push('\u{2026}');
+    truncated
+}
+
+fn table_box(node: &Node<'_>) -> String {
+    let mut group = format!(
+        "<g class=\"erd-table\"><title>{}</title>\n\
+        <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"#ffffff\" stroke=\"{}\"/>\n\
+        <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"{}\"/>\n\
+        <text x=\"{}\" y=\"{}\" fill=\"#ffffff\" font-weight=\"bold\">{}</text>\n",
+        escape_xml(&qualified_table_name(node.table)),
+        node.x,
+        node.y,
+        node.width,
+        node.height,
+        DECLARED_COLOR,
+        node.x,
+        node.y,
+        node.width,
+        HEADER_HEIGHT,
+        DECLARED_COLOR,
+        node.x.saturating_add(BOX_PADDING),
+        node.y.saturating_add(HEADER_HEIGHT - 8),
+        escape_xml(&truncate(&qualified_table_name(node.table)))
+    );
+    let mut baseline = node.y.saturating_add(HEADER_HEIGHT + ROW_HEIGHT);
+    for row in &node.rows {
+        let _ = writeln!(
+            group,
+            "<text x=\"{}\" y=\"{}\" fill=\"#0f172a\" xml:space=\"preserve\">{}</text>",
+            node.x.saturating_add(BOX_PADDING),
+            baseline.saturating_sub(4),
+            escape_xml(row)
+        );
+        baseline = baseline.saturating_add(ROW_HEIGHT);
+    }
+    group.push_str("</g>\n");
+    group
+}
+
+/// Line from the child box to the parent box, with an arrowhead at the parent.
+fn edge_path(id: &str, nodes: &[Node<'_>], order: &[usize], edge: &Edge) -> String {
+    let node_of = |table: usize| order.iter().position(|&i| i == table).map(|k| &nodes[k]);
+    let (Some(child), Some(parent)) = (node_of(edge.child), node_of(edge.parent)) else {
+        return String::new();
+    };
+    let (kind, color, dash) = if edge.inferred {
+        ("inferred", INFERRED_COLOR, " stroke-dasharray=\"6 4\"")
+    } else {
+        ("declared", DECLARED_COLOR, "")
+    };
+
+    let path = if edge.child == edge.parent {
+        // Self-reference: loop out of the right edge of the box
+        let right = f64::from(child.x) + f64::from(child.width);
+        let top = f64::from(child.y) + f64::from(HEADER_HEIGHT) / 2.0;
+        format!(
+            "M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+            right,
+            top + 12.0,
+            right + 40.0,
+            top + 24.0,
+            right + 40.0,
+            top - 12.0,
+            right,
+            top
+        )
+    } else {
+        let (child_x, child_y) = child.center();
+        let (parent_x, parent_y) = parent.center();
+        let (start_x, start_y) = child.border_point(parent_x - child_x, parent_y - child_y);
+        let (end_x, end_y) = parent.border_point(child_x - parent_x, child_y - parent_y);
+        format!("M{start_x:.1},{start_y:.1} L{end_x:.1},{end_y:.1}")
+    };
+
+    format!(
+        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"{} marker-end=\"url(#{}-{})\">\
+        <title>{}</title></path>\n",
+        path,
+        color,
+        dash,
+        id,
+        kind,
+        escape_xml(&format!(
+            "{} \u{2192} {} ({}): {}",
+            qualified_table_name(child.table),
+            qualified_table_name(parent.table),
+            edge.cardinality.label(),
+            edge.label
+        ))
+    )
+}
+
+fn legend(id: &str, y: u32) -> String {
+    let line_y = y.saturating_add(10);
+    format!(
+        "<g class=\"erd-legend\">\
+        <path d=\"M{m},{line_y} L{m2},{line_y}\" stroke=\"{DECLARED_COLOR}\" stroke-width=\"1.5\" marker-end=\"url(#{id}-declared)\"/>\
+        <text x=\"{t}\" y=\"{text_y}\" fill=\"#0f172a\">Foreign key</text>\
+        <path d=\"M{m3},{line_y} L{m4},{line_y}\" stroke=\"{INFERRED_COLOR}\" stroke-width=\"1.5\" stroke-dasharray=\"6 4\" marker-end=\"url(#{id}-inferred)\"/>\
+        <text x=\"{t2}\" y=\"{text_y}\" fill=\"#0f172a\">Inferred (not enforced)</text>\
+        </g>\n",
+        m = MARGIN,
+        m2 = MARGIN + 40,
+        t = MARGIN + 48,
+        m3 = MARGIN + 150,
+        m4 = MARGIN + 190,
+        t2 = MARGIN + 198,
+        text_y = line_y.saturating_add(4),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, ForeignKey, UnifiedDataType};
+    use dbsurveyor_core::relationships::RelationshipEvidence;
+
+    fn table(name: &str, columns: usize, references: &[&str]) -> Table {
+        Table {
+            foreign_keys: references
+                .iter()
+                .map(|referenced| ForeignKey {
+                    name: None,
+                    columns: vec![format!("{referenced}_id")],
+                    referenced_table: (*referenced).to_string(),
+                    referenced_schema: None,
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                })
+                .collect(),
+            ..Table::new(
+                name.to_string(),
+                Some("public".to_string()),
+                (0..columns)
+                    .map(|i| Column {
+                        is_primary_key: i == 0,
+                        ..Column::new(format!("column_{i}"), UnifiedDataType::Boolean)
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    fn inferred(table: &str, referenced: &str) -> InferredRelationship {
+        InferredRelationship {
+            schema: Some("public".to_string()),
+            table: table.to_string(),
+            column: format!("{referenced}_id"),
+            referenced_schema: Some("public".to_string()),
+            referenced_table: referenced.to_string(),
+            referenced_column: "id".to_string(),
+            confidence: 0.7,
+            evidence: vec![RelationshipEvidence::NamingConvention],
+            cardinality: Cardinality::ManyToOne,
+        }
+    }
+
+    #[test]
+    fn test_related_tables_are_adjacent() {
+        let tables = [
+            table("audit", 1, &[]),
+            table("orders", 1, &["customers"]),
+            table("notes", 1, &[]),
+            table("customers", 1, &[]),
+        ];
+        let refs: Vec<&Table> = tables.iter().collect();
+        let edges = collect_edges(&refs, &[]);
+        assert_eq!(edges.len(), 1);
+
+        let order = layout_order(refs.len(), &edges);
+        assert_eq!(order, vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_boxes_do_not_overlap() {
+        let tables = [
+            table("a", 3, &[]),
+            table("b", 20, &[]),
+            table("c", 1, &[]),
+            table("d", 5, &[]),
+            table("e", 2, &[]),
+        ];
+        let refs: Vec<&Table> = tables.iter().collect();
+        let nodes = place_nodes(&refs, &[0, 1, 2, 3, 4]);
+        for (i, a) in nodes.iter().enumerate() {
+            for b in nodes.iter().skip(i + 1) {
+                let separate_x = a.x + a.width <= b.x || b.x + b.width <= a.x;
+                let separate_y = a.y + a.height <= b.y || b.y + b.height <= a.y;
+                assert!(
+                    separate_x || separate_y,
+                    "{} overlaps {}",
+                    a.table.name,
+                    b.table.name
+                );
+            }
+        }
+        // Long tables are collapsed
+        assert_eq!(nodes[1].rows.len(), MAX_ROWS + 1);
+    }
+
+    #[test]
+    fn test_svg_distinguishes_inferred_edges() {
+        let tables = [
+            table("customers", 2, &[]),
+            table("orders", 3, &["customers"]),
+            table("stores", 1, &[]),
+        ];
+        let refs: Vec<&Table> = tables.iter().collect();
+        let svg = render_svg("erd-0", &refs, &[inferred("orders", "stores")]);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<marker id=\"erd-0-inferred\""));
+        assert!(svg.contains(
+            "<title>public.orders \u{2192} public.customers (N:1): customers_id</title>"
+        ));
+        assert!(svg.contains(&format!(
+            "stroke=\"{INFERRED_COLOR}\" stroke-width=\"1.5\" stroke-dasharray=\"6 4\" marker-end=\"url(#erd-0-inferred)\"><title>public.orders \u{2192} public.stores (N:1): stores_id (inferred 70%)"
+        )));
+        assert!(svg.contains(">PK column_0 boolean</text>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Exporter interface for documentation formats.
+//!
+//! An [`Exporter`] turns one loaded database schema into output files. The
+//! built-in [`OutputFormat`](crate::OutputFormat) values are exporters, and
+//! formats specific to an organization (a CMDB feed, a ticketing import)
+//! are compiled in by implementing the trait and listing the implementation
+//! in [`CUSTOM_EXPORTERS`]. Custom exporters are selected with
+//! `--exporter <name>` and go through the same redaction, section selection,
+//! output paths, and result summary as the built-in formats.
+
+use crate::markdown::ReportOptions;
+use crate::markdown::site::SiteFile;
+use dbsurveyor_core::Result;
+use dbsurveyor_core::error::DbSurveyorError;
+use dbsurveyor_core::models::DatabaseSchema;
+
+/// Custom exporters compiled into this build, selectable by name with
+/// `--exporter`.
+static CUSTOM_EXPORTERS: &[&dyn Exporter] = &[];
+
+/// Documentation rendered for one database, ready to be written.
+pub(crate) enum Rendered {
+    /// Content of a single output file
+    File(String),
+    /// Files relative to an output directory (Markdown site, Confluence export)
+    Site(Vec<SiteFile>),
+}
+
+/// A documentation format.
+///
+/// Exporters run on report worker threads, one database at a time, and
+/// perform no I/O: the caller writes what [`Exporter::export`] returns.
+pub(crate) trait Exporter: Sync {
+    /// Name used in progress messages and the result summary; custom
+    /// exporters are also selected by it.
+    fn name(&self) -> &'static str;
+
+    /// Output file name for a survey named `base_name` when no output path
+    /// is given (`schema.md` for `schema`).
+    fn output_file_name(&self, base_name: &str) -> String;
+
+    /// Whether the output is a directory of files ([`Rendered::Site`])
+    /// rather than a single file. Server surveys then get a subdirectory
+    /// per database instead of a file name suffix.
+    fn writes_directory(&self) -> bool {
+        false
+    }
+
+    /// Renders one database. Samples in `options` are already redacted.
+    fn export(&self, schema: &DatabaseSchema, options: &ReportOptions<'_>) -> Result<Rendered>;
+}
+
+/// Finds the custom exporter compiled in under `name`.
+pub(crate) fn find_exporter(name: &str) -> Result<&'static dyn Exporter> {
+    find_in(CUSTOM_EXPORTERS, name)
+}
+
+fn find_in(exporters: &[&'static dyn Exporter], name: &str) -> Result<&'static dyn Exporter> {
+    if let Some(exporter) = exporters.iter().find(|exporter| exporter.name() == name) {
+        return Ok(*exporter);
+    }
+    let available: Vec<&str> = exporters.iter().map(|exporter| exporter.name()).collect();
+    Err(DbSurveyorError::configuration(if available.is_empty() {
+        format!(
+            "Unknown exporter '{}': no custom exporters are compiled into this build",
+            name
+        )
+    } else {
+        format!(
+            "Unknown exporter '{}'; available exporters: {}",
+            name,
+            available.join(", ")
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::DatabaseInfo;
+
+    struct TableCount;
+
+    impl Exporter for TableCount {
+        fn name(&self) -> &'static str {
+            "table-count"
+        }
+
+        fn output_file_name(&self, base_name: &str) -> String {
+            format!("{}_table_count.txt", base_name)
+        }
+
+        fn export(&self, schema: &DatabaseSchema, _: &ReportOptions<'_>) -> Result<Rendered> {
+            Ok(Rendered::File(format!("{} tables", schema.tables.len())))
+        }
+    }
+
+    static TABLE_COUNT: TableCount = TableCount;
+
+    #[test]
+    fn test_find_exporter_by_name() {
+        let exporters: &[&'static dyn Exporter] = &[&TABLE_COUNT];
+        let exporter = find_in(exporters, "table-count").expect("exporter registered");
+        assert_eq!(exporter.output_file_name("prod"), "prod_table_count.txt");
+        assert!(!exporter.writes_directory());
+
+        let schema = DatabaseSchema::new(DatabaseInfo::new("prod".to_string()));
+        match exporter.export(&schema, &ReportOptions::default()) {
+            Ok(Rendered::File(content)) => assert_eq!(content, "0 tables"),
+            _ => panic!("expected a single file"),
+        }
+
+        let err = find_in(exporters, "cmdb").err().expect("unknown exporter");
+        assert!(err.to_string().contains("available exporters: table-count"));
+        let err = find_in(&[], "cmdb").err().expect("no exporters");
+        assert!(err.to_string().contains("no custom exporters"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Consistent terminology across database engines.
+//!
+//! Reports always speak of schemas, tables, columns, and rows so that a
+//! reader comparing surveys from different engines sees one vocabulary.
+//! Where an engine uses its own word for a concept (a MongoDB collection
+//! is reported as a table), the native term is shown alongside the
+//! normalized one and listed in the report's terminology section.
+
+use dbsurveyor_core::models::DatabaseType;
+use std::fmt::Write as _;
+
+/// A concept reports name with a normalized term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Term {
+    Schema,
+    Table,
+    Column,
+    Row,
+    View,
+    Index,
+}
+
+impl Term {
+    /// Every term, in the order the terminology section lists them.
+    pub(crate) const ALL: [Self; 6] = [
+        Self::Schema,
+        Self::Table,
+        Self::Column,
+        Self::Row,
+        Self::View,
+        Self::Index,
+    ];
+
+    /// The normalized term used throughout reports.
+    pub(crate) const fn normalized(self) -> &'static str {
+        match self {
+            Self::Schema => "schema",
+            Self::Table => "table",
+            Self::Column => "column",
+            Self::Row => "row",
+            Self::View => "view",
+            Self::Index => "index",
+        }
+    }
+
+    /// The term `engine` uses for this concept.
+    pub(crate) const fn native(self, engine: &DatabaseType) -> &'static str {
+        match (engine, self) {
+            (DatabaseType::MySQL | DatabaseType::MongoDB, Self::Schema) => "database",
+            (DatabaseType::SQLite, Self::Schema) => "attached database",
+            (DatabaseType::MongoDB, Self::Table) => "collection",
+            (DatabaseType::MongoDB, Self::Column) => "field",
+            (DatabaseType::MongoDB, Self::Row) => "document",
+            _ => self.normalized(),
+        }
+    }
+
+    /// The native term for `engine` when it differs from the normalized one.
+    pub(crate) fn native_if_different(self, engine: Option<&DatabaseType>) -> Option<&'static str> {
+        let native = self.native(engine?);
+        (native != self.normalized()).then_some(native)
+    }
+}
+
+/// Appends the engine's native term to a heading or label, e.g.
+/// `Tables (collections)`, when it differs from the normalized term.
+pub(crate) fn annotate(label: &str, term: Term, engine: Option<&DatabaseType>) -> String {
+    match term.native_if_different(engine) {
+        Some(native) => format!("{} ({}s)", label, native),
+        None => label.to_string(),
+    }
+}
+
+/// Renders the terminology section mapping report terms to the engine's
+/// own, or an empty string when the engine is unknown or uses the same
+/// words.
+pub(crate) fn terminology_section(engine: Option<&DatabaseType>) -> String {
+    let Some(engine) = engine else {
+        return String::new();
+    };
+    let differing: Vec<Term> = Term::ALL
+        .into_iter()
+        .filter(|term| term.native_if_different(Some(engine)).is_some())
+        .collect();
+    if differing.is_empty() {
+        return String::new();
+    }
+
+    let mut section = format!(
+        "## Terminology\n\n\
+        This report uses the same terms for every engine. {} calls these objects:\n\n\
+        | Report Term | {} Term |\n\
+        | ----------- | ----------- |\n",
+        engine, engine
+    );
+    for term in differing {
+        let _ = writeln!(
+            section,
+            "| {} | {} |",
+            term.normalized(),
+            term.native(engine)
+        );
+    }
+    section.push('\n');
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_terms_only_where_they_differ() {
+        let mongo = Some(&DatabaseType::MongoDB);
+        assert_eq!(Term::Table.native_if_different(mongo), Some("collection"));
+        assert_eq!(Term::Column.native_if_different(mongo), Some("field"));
+        assert_eq!(Term::Index.native_if_different(mongo), None);
+        assert_eq!(
+            Term::Schema.native_if_different(Some(&DatabaseType::MySQL)),
+            Some("database")
+        );
+        assert_eq!(
+            Term::Table.native_if_different(Some(&DatabaseType::PostgreSQL)),
+            None
+        );
+        assert_eq!(Term::Table.native_if_different(None), None);
+    }
+
+    #[test]
+    fn test_annotate_adds_plural_native_term() {
+        let mongo = Some(&DatabaseType::MongoDB);
+        assert_eq!(
+            annotate("Tables", Term::Table, mongo),
+            "Tables (collections)"
+        );
+        assert_eq!(annotate("Columns", Term::Column, mongo), "Columns (fields)");
+        assert_eq!(
+            annotate("Tables", Term::Table, Some(&DatabaseType::SQLite)),
+            "Tables"
+        );
+    }
+
+    #[test]
+    fn test_terminology_section() {
+        let section = terminology_section(Some(&DatabaseType::MongoDB));
+        assert!(section.starts_with("## Terminology\n\n"));
+        assert!(section.contains("| Report Term | MongoDB Term |"));
+        assert!(section.contains("| table | collection |"));
+        assert!(section.contains("| row | document |"));
+        assert!(!section.contains("| index |"));
+
+        assert!(terminology_section(Some(&DatabaseType::PostgreSQL)).is_empty());
+        assert!(terminology_section(None).is_empty());
+    }
+}
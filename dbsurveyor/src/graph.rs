@@ -0,0 +1,316 @@
+//! Relationship graph export for external graph tools.
+//!
+//! `--format graph-json` writes tables as nodes and relationships as edges,
+//! shaped so D3 (`nodes`/`edges` with `source`/`target` ids), Cytoscape.js
+//! (wrap each element in `{"data": ...}`), and Gephi's JSON importer can load
+//! it without knowing the survey format. Edges point from the referencing
+//! (child) table to the referenced (parent) table.
+//!
+//! Tables referenced by a foreign key but missing from the survey (e.g. in
+//! a schema that was not collected) become `external` nodes so every edge
+//! resolves.
+
+use crate::markdown::{qualified_name, qualified_table_name};
+use dbsurveyor_core::models::{DatabaseSchema, Table};
+use dbsurveyor_core::relationships::{Cardinality, cardinality, infer_relationships};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+const GRAPH_FORMAT: &str = "dbsurveyor-graph";
+const GRAPH_VERSION: u32 = 1;
+
+/// The relationship graph of one database.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Graph {
+    pub(crate) format: &'static str,
+    pub(crate) version: u32,
+    pub(crate) database: String,
+    /// Always true: edges run from child to parent
+    pub(crate) directed: bool,
+    pub(crate) nodes: Vec<GraphNode>,
+    pub(crate) edges: Vec<GraphEdge>,
+}
+
+/// A table.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct GraphNode {
+    /// Qualified table name
+    pub(crate) id: String,
+    /// Unqualified table name
+    pub(crate) label: String,
+    pub(crate) schema: Option<String>,
+    pub(crate) row_count: Option<u64>,
+    pub(crate) column_count: usize,
+    pub(crate) primary_key: Vec<String>,
+    pub(crate) comment: Option<String>,
+    /// True for referenced tables that are not part of the survey
+    pub(crate) external: bool,
+}
+
+/// A declared foreign key or inferred relationship.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct GraphEdge {
+    pub(crate) id: String,
+    /// Referencing (child) table id
+    pub(crate) source: String,
+    /// Referenced (parent) table id
+    pub(crate) target: String,
+    /// Constraint name, for declared foreign keys that have one
+    pub(crate) name: Option<String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) referenced_columns: Vec<String>,
+    pub(crate) cardinality: Cardinality,
+    /// True when a child row may have no parent (a key column is nullable)
+    pub(crate) optional: bool,
+    pub(crate) inferred: bool,
+    /// Likelihood from 0.0 to 1.0, for inferred relationships
+    pub(crate) confidence: Option<f64>,
+}
+
+/// Builds the graph of declared foreign keys and inferred relationships.
+pub(crate) fn build_graph(schema: &DatabaseSchema) -> Graph {
+    let mut nodes: Vec<GraphNode> = schema
+        .tables
+        .iter()
+        .map(|table| GraphNode {
+            id: qualified_table_name(table),
+            label: table.name.clone(),
+            schema: table.schema.clone(),
+            row_count: table.row_count,
+            column_count: table.columns.len(),
+            primary_key: table
+                .primary_key
+                .as_ref()
+                .map(|pk| pk.columns.clone())
+                .unwrap_or_default(),
+            comment: table.comment.clone(),
+            external: false,
+        })
+        .collect();
+
+    let mut edges = Vec::new();
+    // Schema and table of every edge target, kept separately because a
+    // quoted identifier may itself contain a dot
+    let mut targets: BTreeMap<String, (Option<String>, String)> = BTreeMap::new();
+    for table in &schema.tables {
+        let source = qualified_table_name(table);
+        for fk in &table.foreign_keys {
+            let parent_schema = fk.referenced_schema.as_deref().or(table.schema.as_deref());
+            let target = qualified_name(parent_schema, &fk.referenced_table);
+            targets.entry(target.clone()).or_insert_with(|| {
+                (
+                    parent_schema.map(str::to_string),
+                    fk.referenced_table.clone(),
+                )
+            });
+            edges.push(GraphEdge {
+                id: format!("e{}", edges.len() + 1),
+                source: source.clone(),
+                target,
+                name: fk.name.clone(),
+                columns: fk.columns.clone(),
+                referenced_columns: fk.referenced_columns.clone(),
+                cardinality: cardinality(table, &fk.columns),
+                optional: is_optional(table, &fk.columns),
+                inferred: false,
+                confidence: None,
+            });
+        }
+    }
+
+    for relationship in infer_relationships(schema) {
+        let Some(table) = schema
+            .tables
+            .iter()
+            .find(|t| t.name == relationship.table && t.schema == relationship.schema)
+        else {
+            continue;
+        };
+        let columns = vec![relationship.column.clone()];
+        let target = qualified_name(
+            relationship.referenced_schema.as_deref(),
+            &relationship.referenced_table,
+        );
+        targets.entry(target.clone()).or_insert_with(|| {
+            (
+                relationship.referenced_schema.clone(),
+                relationship.referenced_table.clone(),
+            )
+        });
+        edges.push(GraphEdge {
+            id: format!("e{}", edges.len() + 1),
+            source: qualified_table_name(table),
+            target,
+            name: None,
+            cardinality: relationship.cardinality,
+            optional: is_optional(table, &columns),
+            columns,
+            referenced_columns: vec![relationship.referenced_column],
+            inferred: true,
+            confidence: Some(relationship.confidence),
+        });
+    }
+
+    let known: HashSet<String> = nodes.iter().map(|node| node.id.clone()).collect();
+    for (id, (schema, label)) in targets {
+        if known.contains(&id) {
+            continue;
+        }
+        nodes.push(GraphNode {
+            id,
+            label,
+            schema,
+            row_count: None,
+            column_count: 0,
+            primary_key: Vec::new(),
+            comment: None,
+            external: true,
+        });
+    }
+
+    Graph {
+        format: GRAPH_FORMAT,
+        version: GRAPH_VERSION,
+        database: schema.database_info.name.clone(),
+        directed: true,
+        nodes,
+        edges,
+    }
+}
+
+fn is_optional(table: &Table, columns: &[String]) -> bool {
+    table
+        .columns
+        .iter()
+        .any(|column| column.is_nullable && columns.contains(&column.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, DatabaseInfo, ForeignKey, PrimaryKey, UnifiedDataType};
+
+    fn column(name: &str, nullable: bool) -> Column {
+        Column {
+            is_nullable: nullable,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+            )
+        }
+    }
+
+    fn table(name: &str, columns: Vec<Column>, primary_key: &[&str]) -> Table {
+        Table {
+            primary_key: Some(PrimaryKey {
+                name: None,
+                columns: primary_key.iter().map(|c| c.to_string()).collect(),
+            }),
+            row_count: Some(10),
+            ..Table::new(name.to_string(), Some("public".to_string()), columns)
+        }
+    }
+
+    fn foreign_key(column: &str, schema: Option<&str>, referenced_table: &str) -> ForeignKey {
+        ForeignKey {
+            name: Some(format!("{}_fk", column)),
+            columns: vec![column.to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_schema: schema.map(str::to_string),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let customers = table("customers", vec![column("id", false)], &["id"]);
+        let mut orders = table(
+            "orders",
+            vec![
+                column("id", false),
+                column("customer_id", false),
+                column("coupon_id", true),
+            ],
+            &["id"],
+        );
+        orders
+            .foreign_keys
+            .push(foreign_key("customer_id", None, "customers"));
+        orders
+            .foreign_keys
+            .push(foreign_key("coupon_id", Some("billing"), "coupons"));
+        let mut profiles = table("customer_profiles", vec![column("id", false)], &["id"]);
+        profiles
+            .foreign_keys
+            .push(foreign_key("id", None, "customers"));
+
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![customers, orders, profiles];
+        schema
+    }
+
+    #[test]
+    fn test_declared_edges_and_cardinality() {
+        let graph = build_graph(&fixture());
+        let declared: Vec<&GraphEdge> = graph.edges.iter().filter(|e| !e.inferred).collect();
+        assert_eq!(declared.len(), 3);
+
+        let customer = declared[0];
+        assert_eq!(customer.source, "public.orders");
+        assert_eq!(customer.target, "public.customers");
+        assert_eq!(customer.cardinality, Cardinality::ManyToOne);
+        assert!(!customer.optional);
+
+        let coupon = declared[1];
+        assert_eq!(coupon.target, "billing.coupons");
+        assert!(coupon.optional);
+
+        let profile = declared[2];
+        assert_eq!(profile.cardinality, Cardinality::OneToOne);
+    }
+
+    #[test]
+    fn test_missing_parent_becomes_external_node() {
+        let graph = build_graph(&fixture());
+        let external: Vec<&GraphNode> = graph.nodes.iter().filter(|n| n.external).collect();
+        assert_eq!(external.len(), 1);
+        assert_eq!(external[0].id, "billing.coupons");
+        assert_eq!(external[0].label, "coupons");
+
+        let ids: HashSet<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert!(graph.edges.iter().all(|e| ids.contains(e.target.as_str())));
+    }
+
+    #[test]
+    fn test_external_node_keeps_dotted_table_name() {
+        let mut orders = table("orders", vec![column("rate_id", false)], &[]);
+        orders
+            .foreign_keys
+            .push(foreign_key("rate_id", Some("fx"), "rates.2024"));
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![orders];
+
+        let graph = build_graph(&schema);
+        let external = graph
+            .nodes
+            .iter()
+            .find(|n| n.external)
+            .expect("external node");
+        assert_eq!(external.id, "fx.rates.2024");
+        assert_eq!(external.schema.as_deref(), Some("fx"));
+        assert_eq!(external.label, "rates.2024");
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let json = serde_json::to_value(build_graph(&fixture())).expect("graph serializes");
+        assert_eq!(json["format"], "dbsurveyor-graph");
+        assert_eq!(json["directed"], true);
+        assert_eq!(json["nodes"][0]["id"], "public.customers");
+        assert_eq!(json["edges"][0]["cardinality"], "many-to-one");
+    }
+}
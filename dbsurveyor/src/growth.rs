@@ -0,0 +1,398 @@
+//! Storage growth across periodic surveys.
+//!
+//! When a documented directory holds several surveys of the same database
+//! taken at different times, the row counts and sizes of each table form a
+//! time series. Growth per day is the least-squares slope of that series,
+//! and capacity is projected by extending it over the horizon set with
+//! `--growth-horizon-days`.
+//!
+//! Surveys belong to the same series when they sit in the same directory
+//! and name the same database, so periodic surveys of one server are
+//! expected to be collected into a directory of their own.
+//!
+//! The series and projections are written as `growth.csv` next to the
+//! directory index, one row per table and date, and summarized in the
+//! index's Storage Growth section.
+
+use crate::markdown::{escape_cell, qualified_table_name};
+use chrono::{DateTime, Duration, Utc};
+use dbsurveyor_core::models::DatabaseSchema;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// File name of the growth series written next to the directory index.
+pub(crate) const GROWTH_FILE: &str = "growth.csv";
+
+/// Tables listed in the index summary, fastest-growing first.
+const SUMMARY_TABLES: usize = 10;
+
+/// Size and row count of one table in one survey.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Observation {
+    /// Directory of the survey file, relative to the documented directory
+    pub(crate) series: String,
+    pub(crate) database: String,
+    /// Schema-qualified table name
+    pub(crate) table: String,
+    pub(crate) collected_at: DateTime<Utc>,
+    pub(crate) row_count: Option<u64>,
+    pub(crate) size_bytes: Option<u64>,
+}
+
+/// Records the size and row count of every table in `schema`.
+pub(crate) fn observe(series: &str, schema: &DatabaseSchema) -> Vec<Observation> {
+    schema
+        .tables
+        .iter()
+        .map(|table| Observation {
+            series: series.to_string(),
+            database: schema.database_info.name.clone(),
+            table: qualified_table_name(table),
+            collected_at: schema.collection_metadata.collected_at,
+            row_count: table.row_count,
+            size_bytes: table.size_bytes,
+        })
+        .collect()
+}
+
+/// Growth of one table over its series of surveys.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TableGrowth {
+    pub(crate) series: String,
+    pub(crate) database: String,
+    pub(crate) table: String,
+    /// Observations in collection order, one per survey date
+    pub(crate) observations: Vec<Observation>,
+    pub(crate) rows_per_day: Option<f64>,
+    pub(crate) bytes_per_day: Option<f64>,
+}
+
+impl TableGrowth {
+    fn latest(&self) -> &Observation {
+        // Series are only built from at least two observations
+        &self.observations[self.observations.len() - 1]
+    }
+
+    /// Row count projected `horizon_days` after the latest survey.
+    pub(crate) fn projected_rows(&self, horizon_days: u32) -> Option<u64> {
+        project(self.latest().row_count, self.rows_per_day, horizon_days)
+    }
+
+    /// Size projected `horizon_days` after the latest survey.
+    pub(crate) fn projected_bytes(&self, horizon_days: u32) -> Option<u64> {
+        project(self.latest().size_bytes, self.bytes_per_day, horizon_days)
+    }
+}
+
+/// Groups observations into per-table series and computes their growth.
+///
+/// Tables seen on a single date have no growth and are left out; when two
+/// surveys share a timestamp, the later one in `observations` is kept.
+pub(crate) fn analyze(observations: &[Observation]) -> Vec<TableGrowth> {
+    type SeriesKey<'a> = (&'a str, &'a str, &'a str);
+    let mut series: BTreeMap<SeriesKey<'_>, BTreeMap<DateTime<Utc>, &Observation>> =
+        BTreeMap::new();
+    for observation in observations {
+        series
+            .entry((
+                &observation.series,
+                &observation.database,
+                &observation.table,
+            ))
+            .or_default()
+            .insert(observation.collected_at, observation);
+    }
+
+    series
+        .into_iter()
+        .filter(|(_, points)| points.len() >= 2)
+        .map(|((series, database, table), points)| {
+            let observations: Vec<Observation> = points.into_values().cloned().collect();
+            let first = observations[0].collected_at;
+            let slope = |value: fn(&Observation) -> Option<u64>| {
+                let points: Vec<(f64, f64)> = observations
+                    .iter()
+                    .filter_map(|o| Some((days_between(first, o.collected_at), value(o)? as f64)))
+                    .collect();
+                least_squares_slope(&points)
+            };
+            TableGrowth {
+                series: series.to_string(),
+                database: database.to_string(),
+                table: table.to_string(),
+                rows_per_day: slope(|o| o.row_count),
+                bytes_per_day: slope(|o| o.size_bytes),
+                observations,
+            }
+        })
+        .collect()
+}
+
+/// Renders the series as CSV for charting: one `observed` row per table
+/// and survey date, followed by a `projected` row at the horizon.
+pub(crate) fn render_csv(growth: &[TableGrowth], horizon_days: u32) -> String {
+    let mut csv = String::from("series,database,table,date,kind,row_count,size_bytes\n");
+    let optional = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+    for table in growth {
+        let prefix = format!(
+            "{},{},{}",
+            csv_field(&table.series),
+            csv_field(&table.database),
+            csv_field(&table.table)
+        );
+        for observation in &table.observations {
+            let _ = writeln!(
+                csv,
+                "{},{},observed,{},{}",
+                prefix,
+                observation.collected_at.format("%Y-%m-%dT%H:%M:%SZ"),
+                optional(observation.row_count),
+                optional(observation.size_bytes)
+            );
+        }
+        let projected_at = table.latest().collected_at + Duration::days(i64::from(horizon_days));
+        let _ = writeln!(
+            csv,
+            "{},{},projected,{},{}",
+            prefix,
+            projected_at.format("%Y-%m-%dT%H:%M:%SZ"),
+            optional(table.projected_rows(horizon_days)),
+            optional(table.projected_bytes(horizon_days))
+        );
+    }
+    csv
+}
+
+/// Renders the Storage Growth section of the directory index, or an empty
+/// string when no table was surveyed on more than one date.
+pub(crate) fn summary_section(growth: &[TableGrowth], horizon_days: u32) -> String {
+    if growth.is_empty() {
+        return String::new();
+    }
+
+    let mut section = format!(
+        "## Storage Growth\n\n\
+        {} tables were surveyed on more than one date. Projections extend the \
+        growth trend {} days past each table's latest survey; the full series \
+        is in [{}]({}).\n\n",
+        growth.len(),
+        horizon_days,
+        GROWTH_FILE,
+        GROWTH_FILE
+    );
+
+    // Totals per database, over the tables whose size is known
+    let mut databases: BTreeMap<(&str, &str), (u64, f64, u64)> = BTreeMap::new();
+    for table in growth {
+        let (Some(size), Some(rate), Some(projected)) = (
+            table.latest().size_bytes,
+            table.bytes_per_day,
+            table.projected_bytes(horizon_days),
+        ) else {
+            continue;
+        };
+        let totals = databases
+            .entry((&table.series, &table.database))
+            .or_default();
+        totals.0 += size;
+        totals.1 += rate;
+        totals.2 += projected;
+    }
+    if !databases.is_empty() {
+        section.push_str(
+            "| Database | Current Size | Growth per Day | Projected Size |\n\
+            | -------- | ------------ | -------------- | -------------- |\n",
+        );
+        for ((series, database), (size, rate, projected)) in &databases {
+            let _ = writeln!(
+                section,
+                "| {} | {} | {} | {} |",
+                escape_cell(&series_label(series, database)),
+                format_bytes(*size as f64),
+                format_bytes(*rate),
+                format_bytes(*projected as f64)
+            );
+        }
+        section.push('\n');
+    }
+
+    let mut fastest: Vec<&TableGrowth> = growth.iter().collect();
+    fastest.sort_by(|a, b| {
+        let rate = |t: &TableGrowth| t.bytes_per_day.unwrap_or_default();
+        rate(b).total_cmp(&rate(a))
+    });
+    let _ = writeln!(section, "### Fastest-Growing Tables\n");
+    section.push_str(
+        "| Table | Surveys | Rows per Day | Growth per Day | Projected Rows | Projected Size |\n\
+        | ----- | ------- | ------------ | -------------- | -------------- | -------------- |\n",
+    );
+    for table in fastest.into_iter().take(SUMMARY_TABLES) {
+        let rows = |value: Option<f64>| value.map(|v| format!("{:.0}", v)).unwrap_or_default();
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} | {} |",
+            escape_cell(&format!(
+                "{}: {}",
+                series_label(&table.series, &table.database),
+                table.table
+            )),
+            table.observations.len(),
+            rows(table.rows_per_day),
+            table.bytes_per_day.map(format_bytes).unwrap_or_default(),
+            rows(table.projected_rows(horizon_days).map(|v| v as f64)),
+            table
+                .projected_bytes(horizon_days)
+                .map(|v| format_bytes(v as f64))
+                .unwrap_or_default()
+        );
+    }
+    section.push('\n');
+    section
+}
+
+fn series_label(series: &str, database: &str) -> String {
+    if series.is_empty() {
+        database.to_string()
+    } else {
+        format!("{}/{}", series, database)
+    }
+}
+
+/// Extends `latest` by `per_day` over the horizon, never below zero.
+fn project(latest: Option<u64>, per_day: Option<f64>, horizon_days: u32) -> Option<u64> {
+    let projected = latest? as f64 + per_day? * f64::from(horizon_days);
+    // Saturating float-to-int conversion; shrinking tables stop at zero
+    Some(projected.max(0.0).round() as u64)
+}
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds() as f64 / 86_400.0
+}
+
+/// Slope of the least-squares line through `points`, or `None` with fewer
+/// than two distinct x values.
+fn least_squares_slope(points: &[(f64, f64)]) -> Option<f64> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(cov, var), (x, y)| {
+        (
+            cov + (x - mean_x) * (y - mean_y),
+            var + (x - mean_x) * (x - mean_x),
+        )
+    });
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Human-readable byte count with binary units, signed for shrinking tables.
+pub(crate) fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes.abs();
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let sign = if bytes < 0.0 { "-" } else { "" };
+    if unit == 0 {
+        format!("{}{:.0} {}", sign, value, UNITS[unit])
+    } else {
+        format!("{}{:.1} {}", sign, value, UNITS[unit])
+    }
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn observation(day: u32, rows: u64, bytes: Option<u64>) -> Observation {
+        Observation {
+            series: "prod".to_string(),
+            database: "sales".to_string(),
+            table: "public.orders".to_string(),
+            collected_at: Utc
+                .with_ymd_and_hms(2026, 1, day, 0, 0, 0)
+                .single()
+                .expect("valid date"),
+            row_count: Some(rows),
+            size_bytes: bytes,
+        }
+    }
+
+    #[test]
+    fn test_analyze_fits_growth_per_day() {
+        let observations = [
+            observation(1, 1_000, Some(1_048_576)),
+            observation(11, 2_000, Some(2_097_152)),
+            observation(21, 3_000, Some(3_145_728)),
+            Observation {
+                table: "public.customers".to_string(),
+                ..observation(1, 10, Some(8_192))
+            },
+        ];
+
+        let growth = analyze(&observations);
+        // customers was surveyed once and has no series
+        assert_eq!(growth.len(), 1);
+        let orders = &growth[0];
+        assert_eq!(orders.table, "public.orders");
+        assert_eq!(orders.observations.len(), 3);
+        assert_eq!(orders.rows_per_day, Some(100.0));
+        assert_eq!(orders.bytes_per_day, Some(104_857.6));
+        assert_eq!(orders.projected_rows(30), Some(6_000));
+        assert_eq!(orders.projected_bytes(30), Some(6_291_456));
+    }
+
+    #[test]
+    fn test_missing_sizes_and_shrinking_tables() {
+        let growth = analyze(&[observation(1, 500, None), observation(3, 100, None)]);
+        assert_eq!(growth[0].bytes_per_day, None);
+        assert_eq!(growth[0].projected_bytes(90), None);
+        assert_eq!(growth[0].rows_per_day, Some(-200.0));
+        assert_eq!(growth[0].projected_rows(90), Some(0));
+    }
+
+    #[test]
+    fn test_render_csv_lists_observed_and_projected_rows() {
+        let growth = analyze(&[
+            observation(1, 1_000, Some(4_096)),
+            observation(2, 1_100, None),
+        ]);
+        let csv = render_csv(&growth, 10);
+        assert_eq!(
+            csv,
+            "series,database,table,date,kind,row_count,size_bytes\n\
+            prod,sales,public.orders,2026-01-01T00:00:00Z,observed,1000,4096\n\
+            prod,sales,public.orders,2026-01-02T00:00:00Z,observed,1100,\n\
+            prod,sales,public.orders,2026-01-12T00:00:00Z,projected,2100,\n"
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_summary_section() {
+        assert!(summary_section(&[], 90).is_empty());
+
+        let growth = analyze(&[
+            observation(1, 1_000, Some(1_048_576)),
+            observation(11, 2_000, Some(2_097_152)),
+        ]);
+        let section = summary_section(&growth, 90);
+        assert!(section.starts_with("## Storage Growth\n\n1 tables were surveyed"));
+        assert!(section.contains("| prod/sales | 2.0 MiB | 102.4 KiB | 11.0 MiB |"));
+        assert!(
+            section
+                .contains("| prod/sales: public.orders | 2 | 100 | 102.4 KiB | 11000 | 11.0 MiB |")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Self-contained HTML report.
+//!
+//! The report has no external stylesheets, scripts, or fonts so it can be
+//! opened offline. Each schema gets an inline SVG ER diagram followed by its
+//! table listings.
+
+use crate::confluence::escape_xml;
+use crate::erd;
+use crate::markdown::site::group_tables;
+use crate::markdown::{
+    ReportOptions, column_type_label, qualified_table_name, report_title, sample_preview,
+    table_sample, yes_no,
+};
+use crate::redaction::RedactedTableSample;
+use dbsurveyor_core::models::{DatabaseSchema, Table};
+use dbsurveyor_core::relationships::infer_relationships;
+use std::fmt::Write;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#0f172a}\
+table{border-collapse:collapse;margin-bottom:1.5rem}\
+th,td{border:1px solid #cbd5e1;padding:4px 8px;text-align:left}\
+th{background:#f1f5f9}\
+figure{margin:0 0 2rem;overflow-x:auto;border:1px solid #e2e8f0;padding:0.5rem}\
+figcaption{font-size:0.9rem;color:#475569}";
+
+/// Renders the HTML report for a database.
+///
+/// A masked sample preview follows each table listing when
+/// `options.sample_preview` is set.
+pub(crate) fn render_html(schema: &DatabaseSchema, options: &ReportOptions<'_>) -> String {
+    let samples: Vec<&RedactedTableSample> = options.samples.unwrap_or_default().iter().collect();
+    let title = report_title(schema, options);
+    let inferred = infer_relationships(schema);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n\
+        <p>Generated by DBSurveyor v{} on {}</p>\n",
+        escape_xml(&title),
+        STYLE,
+        escape_xml(&title),
+        escape_xml(&schema.collection_metadata.collector_version),
+        schema
+            .collection_metadata
+            .collected_at
+            .format("%Y-%m-%d %H:%M:%S UTC"),
+    );
+    let _ = writeln!(
+        html,
+        "<h2>Summary</h2>\n<ul><li>Tables: {}</li><li>Views: {}</li><li>Indexes: {}</li></ul>",
+        schema.tables.len(),
+        schema.views.len(),
+        schema.indexes.len()
+    );
+
+    let groups = group_tables(schema);
+    if groups.is_empty() {
+        html.push_str("<p>No tables collected.</p>\n");
+    }
+    for (index, group) in groups.iter().enumerate() {
+        let tables: Vec<&Table> = group.tables.iter().map(|(table, _)| *table).collect();
+        let _ = writeln!(
+            html,
+            "<section id=\"schema-{}\">\n<h2>Schema: {}</h2>\n<figure>\n{}\
+            <figcaption>Solid lines are foreign keys; dashed lines are inferred relationships \
+            that the database does not enforce.</figcaption>\n</figure>",
+            escape_xml(&group.slug),
+            escape_xml(group.name),
+            erd::render_svg(&format!("erd-{index}"), &tables, &inferred)
+        );
+        for table in tables {
+            html.push_str(&table_listing(table));
+            if let Some(rows) = options.sample_preview {
+                html.push_str(&preview_listing(table, &samples, rows));
+            }
+        }
+        html.push_str("</section>\n");
+    }
+
+    if let Some(footer) = options.footer {
+        let _ = writeln!(html, "<footer><p>{}</p></footer>", escape_xml(footer));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn table_listing(table: &Table) -> String {
+    let mut listing = format!("<h3>{}</h3>\n", escape_xml(&qualified_table_name(table)));
+    if let Some(comment) = &table.comment {
+        let _ = writeln!(listing, "<p>{}</p>", escape_xml(comment));
+    }
+    listing.push_str(
+        "<table>\n<thead><tr><th>Column</th><th>Type</th><th>Nullable</th>\
+        <th>Primary Key</th><th>Default</th></tr></thead>\n<tbody>\n",
+    );
+    for column in &table.columns {
+        let _ = writeln!(
+            listing,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_xml(&column.name),
+            escape_xml(&column_type_label(column)),
+            yes_no(column.is_nullable),
+            yes_no(column.is_primary_key),
+            escape_xml(column.default_value.as_deref().unwrap_or(""))
+        );
+    }
+    listing.push_str("</tbody>\n</table>\n");
+    listing
+}
+
+fn preview_listing(table: &Table, samples: &[&RedactedTableSample], rows: usize) -> String {
+    let Some(preview) = table_sample(samples, table).and_then(|s| sample_preview(s, table, rows))
+    else {
+        return String::new();
+    };
+    let mut listing = format!(
+        "<p>Sample preview (first {} rows, masked):</p>\n<table>\n<thead><tr>",
+        preview.rows.len()
+    );
+    for header in &preview.headers {
+        let _ = write!(listing, "<th>{}</th>", escape_xml(header));
+    }
+    listing.push_str("</tr></thead>\n<tbody>\n");
+    for row in &preview.rows {
+        listing.push_str("<tr>");
+        for cell in row {
+            let _ = write!(listing, "<td>{}</td>", escape_xml(cell));
+        }
+        listing.push_str("</tr>\n");
+    }
+    listing.push_str("</tbody>\n</table>\n");
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::DatabaseInfo;
+
+    #[test]
+    fn test_report_embeds_one_diagram_per_schema() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop <prod>".to_string()));
+        for (schema_name, name) in [("public", "orders"), ("audit", "events")] {
+            schema.tables.push(Table::new(
+                name.to_string(),
+                Some(schema_name.to_string()),
+                Vec::new(),
+            ));
+        }
+
+        let html = render_html(&schema, &ReportOptions::default());
+        assert!(html.contains("<title>Database Schema: shop &lt;prod&gt;</title>"));
+        assert_eq!(html.matches("<svg ").count(), 2);
+        assert!(html.contains("<marker id=\"erd-0-declared\""));
+        assert!(html.contains("<marker id=\"erd-1-declared\""));
+        // Nothing is loaded from outside the file
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains(" src="));
+    }
+}
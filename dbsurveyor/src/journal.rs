@@ -0,0 +1,201 @@
+//! Survey journals (`dbsurveyor journal`).
+//!
+//! Lists the records of a journal written with
+//! `dbsurveyor-collect --append-to`, or reconstructs the survey as of one
+//! record into a standalone survey file that every other command accepts.
+//!
+//! A journal collected with `--sign` is keyed; reading it takes the same
+//! key from `DBSURVEYOR_SIGNING_KEY`.
+
+use crate::output;
+use crate::sandbox::PathPolicy;
+use chrono::{DateTime, NaiveDate, Utc};
+use dbsurveyor_core::journal::{Journal, JournalHeader, RecordKind};
+use dbsurveyor_core::{DbSurveyorError, InputLimits, Result};
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the key of a keyed journal, as for
+/// `dbsurveyor-collect --sign`.
+const SIGNING_KEY_ENV_VAR: &str = "DBSURVEYOR_SIGNING_KEY";
+
+/// Which record `--at` selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JournalPoint {
+    /// Record number
+    Seq(u64),
+    /// Latest record collected at or before this time
+    Time(DateTime<Utc>),
+}
+
+impl JournalPoint {
+    /// Parses a record number, an RFC 3339 timestamp, or a `YYYY-MM-DD`
+    /// date, which selects the end of that day (UTC).
+    ///
+    /// # Errors
+    /// Returns a configuration error if `text` is none of these.
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        if let Ok(seq) = text.parse::<u64>() {
+            return Ok(Self::Seq(seq));
+        }
+        if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+            return Ok(Self::Time(time.with_timezone(&Utc)));
+        }
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_milli_opt(23, 59, 59, 999))
+            .map(|end_of_day| Self::Time(end_of_day.and_utc()))
+            .ok_or_else(|| {
+                DbSurveyorError::configuration(format!(
+                    "Invalid --at value '{}': expected a record number, an RFC 3339 timestamp, or YYYY-MM-DD",
+                    text
+                ))
+            })
+    }
+}
+
+/// Prints one line per journal record and the head hash.
+pub(crate) async fn list_journal(input: &Path, limits: &InputLimits) -> Result<()> {
+    let journal = read_journal(input, limits).await?;
+    println!(
+        "{:>5}  {:<4}  {:<20}  {:<20}  HASH",
+        "SEQ", "KIND", "COLLECTED", "RECORDED"
+    );
+    for header in journal.index() {
+        println!(
+            "{:>5}  {:<4}  {:<20}  {:<20}  {}",
+            header.seq,
+            kind_label(header),
+            header
+                .collected_at
+                .map_or_else(|| "-".to_string(), format_time),
+            format_time(header.recorded_at),
+            &header.hash[..12]
+        );
+    }
+    let head = journal
+        .index()
+        .last()
+        .map_or("-", |header| header.hash.as_str());
+    println!("Verified {} records, head {}", journal.len(), head);
+    Ok(())
+}
+
+/// Writes the survey as of `at` to `output` (default:
+/// `<journal stem>-<seq>.dbsurveyor.json` beside the journal).
+pub(crate) async fn reconstruct_survey(
+    input: &Path,
+    at: JournalPoint,
+    output: Option<&PathBuf>,
+    limits: &InputLimits,
+    policy: &PathPolicy,
+) -> Result<()> {
+    let journal = read_journal(input, limits).await?;
+    let seq = match at {
+        JournalPoint::Seq(seq) => seq,
+        JournalPoint::Time(time) => journal.seq_at(time).ok_or_else(|| {
+            DbSurveyorError::configuration(format!(
+                "Journal {} has no record collected before {}",
+                input.display(),
+                format_time(time)
+            ))
+        })?,
+    };
+    let survey = journal.reconstruct(seq)?;
+
+    let content =
+        serde_json::to_string_pretty(&survey).map_err(|e| DbSurveyorError::Serialization {
+            context: "Failed to serialize reconstructed survey".to_string(),
+            source: e,
+        })?;
+    // A survey that no longer validates means the journal was written by an
+    // incompatible collector; refuse it rather than hand it downstream
+    dbsurveyor_core::validate_and_parse_survey_with_limits(&content, limits).map_err(|e| {
+        DbSurveyorError::configuration(format!(
+            "Reconstructed survey for record {} failed validation: {}",
+            seq, e
+        ))
+    })?;
+
+    let output_file = output
+        .cloned()
+        .unwrap_or_else(|| default_reconstructed_path(input, seq));
+    policy.check(&output_file)?;
+    output::write_atomic(&output_file, content.into_bytes()).await?;
+    println!("Reconstructed record {}: {}", seq, output_file.display());
+    Ok(())
+}
+
+/// Reads and verifies a journal, applying the same input limits as survey
+/// files to each record.
+async fn read_journal(input: &Path, limits: &InputLimits) -> Result<Journal> {
+    // Journal::read treats a missing file as empty, which suits appending
+    // but would hide a mistyped path here
+    tokio::fs::metadata(input)
+        .await
+        .map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to read journal {}", input.display()),
+            source: e,
+        })?;
+    let key = std::env::var(SIGNING_KEY_ENV_VAR)
+        .ok()
+        .filter(|key| !key.is_empty());
+    let (path, limits) = (input.to_path_buf(), *limits);
+    tokio::task::spawn_blocking(move || {
+        Journal::read(&path, key.as_deref().map(str::as_bytes), &limits)
+    })
+    .await
+    .map_err(|e| DbSurveyorError::collection_failed("Journal read task failed", e))?
+}
+
+fn kind_label(header: &JournalHeader) -> &'static str {
+    match header.kind {
+        RecordKind::Full => "full",
+        RecordKind::Diff => "diff",
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// `<stem>-<seq>.dbsurveyor.json` beside the journal; `prod.journal.jsonl`
+/// record 3 becomes `prod-3.dbsurveyor.json`.
+fn default_reconstructed_path(input: &Path, seq: u64) -> PathBuf {
+    let file_name = input
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("journal");
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    input.with_file_name(format!("{}-{}.dbsurveyor.json", stem, seq))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_journal_point() {
+        assert_eq!(JournalPoint::parse("3").unwrap(), JournalPoint::Seq(3));
+
+        let JournalPoint::Time(time) = JournalPoint::parse("2026-03-01T12:00:00+02:00").unwrap()
+        else {
+            panic!("expected a time");
+        };
+        assert_eq!(format_time(time), "2026-03-01T10:00:00Z");
+
+        let JournalPoint::Time(end_of_day) = JournalPoint::parse("2026-03-01").unwrap() else {
+            panic!("expected a time");
+        };
+        assert_eq!(format_time(end_of_day), "2026-03-01T23:59:59Z");
+
+        assert!(JournalPoint::parse("last week").is_err());
+    }
+
+    #[test]
+    fn test_default_reconstructed_path() {
+        assert_eq!(
+            default_reconstructed_path(Path::new("out/prod.journal.jsonl"), 4),
+            PathBuf::from("out/prod-4.dbsurveyor.json")
+        );
+    }
+}
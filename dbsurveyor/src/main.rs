@@ -10,12 +10,41 @@
 //! - Optional data redaction for privacy compliance
 //! - No telemetry or external reporting
 
+mod baseline;
+mod batch;
+#[cfg(feature = "experimental")]
+mod compat;
+mod confluence;
+mod conform;
+#[cfg(feature = "experimental")]
+mod erd;
+mod exporter;
+mod glossary;
+mod graph;
+mod growth;
+#[cfg(feature = "experimental")]
+mod html;
+mod journal;
+mod markdown;
+#[cfg(feature = "experimental")]
+mod mermaid;
+mod naming;
 mod output;
+mod output_template;
 mod redaction;
+#[cfg(feature = "encryption")]
+mod rekey;
+mod sandbox;
 mod schema;
+mod search;
+#[cfg(feature = "experimental")]
+mod sql;
+mod summary;
+mod where_used;
 
-use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
-use dbsurveyor_core::{Result, init_logging};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use dbsurveyor_core::{InputLimits, Result, init_logging, profile};
+pub use markdown::ReportSection;
 pub use redaction::RedactionMode;
 use std::path::PathBuf;
 
@@ -43,23 +72,43 @@
 - .dbsurveyor.json (standard JSON)
 - .dbsurveyor.json.zst (compressed)
 - .dbsurveyor.enc (encrypted)
+- .jsonl, .jsonl.zst (survey bundle, one survey per line, streamed)
+- directories (every survey file matching --glob, plus an index.md)
+- - (stdin; format detected from content)
 
 OUTPUT FORMATS:
 - Markdown documentation
+- Multi-file Markdown site (MkDocs/Docusaurus)
+- Confluence storage format (XHTML)
 
 EXAMPLES:
   dbsurveyor generate schema.dbsurveyor.json
   dbsurveyor generate --format markdown schema.json
+  dbsurveyor schema --format-version 1.0
+  dbsurveyor index schema.dbsurveyor.json
+  dbsurveyor search customer schema.dbsurveyor.json
+  dbsurveyor where-used --column customer_id sales.json billing.json
+  dbsurveyor anonymize prod.dbsurveyor.json
+  dbsurveyor conform prod.dbsurveyor.json --spec expected-schema.json
+  dbsurveyor rekey --add auditor prod.dbsurveyor.enc
 ")]
 pub struct Cli {
     #[command(flatten)]
     pub global: GlobalArgs,
 
+    #[command(flatten)]
+    pub limits: InputLimitArgs,
+
+    #[command(flatten)]
+    pub paths: sandbox::PathPolicyArgs,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 
     /// Input schema file
-    #[arg(help = "Path to schema file (.json, .json.zst, or .enc)")]
+    #[arg(
+        help = "Path to schema file (.json, .json.zst, or .enc), a directory of them, or - for stdin"
+    )]
     pub input: Option<PathBuf>,
 
     /// Output format
@@ -72,6 +121,15 @@ pub struct Cli {
     )]
     pub format: OutputFormat,
 
+    /// Custom exporter compiled into this build
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "format",
+        help = "Render with a custom exporter compiled into this build instead of --format"
+    )]
+    pub exporter: Option<String>,
+
     /// Output file path
     #[arg(
         short,
@@ -80,6 +138,23 @@ pub struct Cli {
     )]
     pub output: Option<PathBuf>,
 
+    /// Output path template
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        conflicts_with = "output",
+        help = "Output path per database, e.g. 'docs/{server}/{database}.md'"
+    )]
+    pub output_template: Option<String>,
+
+    /// Survey file pattern for directory input
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Glob selecting survey files when the input is a directory"
+    )]
+    pub glob: Option<String>,
+
     /// Data redaction mode
     #[arg(
         long,
@@ -96,6 +171,106 @@ pub struct Cli {
         help = "Disable all data redaction (show original sample data)"
     )]
     pub no_redact: bool,
+
+    /// Report sections to include
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_delimiter = ',',
+        help = "Comma-separated Markdown report sections to include (default: all)"
+    )]
+    pub sections: Vec<ReportSection>,
+
+    /// Abbreviation dictionary for display names
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Abbreviation dictionary (`abbr = expansion` per line) for friendly display names"
+    )]
+    pub naming_dictionary: Option<PathBuf>,
+
+    /// Embed a masked sample preview under each table
+    #[arg(
+        long,
+        global = true,
+        help = "Embed a masked preview of sampled rows under each table in generated documentation"
+    )]
+    pub include_samples: bool,
+
+    /// Rows per table in the sample preview
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = 3,
+        help = "Number of sampled rows to show per table with --include-samples"
+    )]
+    pub sample_preview_rows: usize,
+
+    /// Days ahead to project storage growth
+    #[arg(
+        long,
+        global = true,
+        value_name = "DAYS",
+        default_value_t = 90,
+        help = "Days past the latest survey to project table growth in a directory index"
+    )]
+    pub growth_horizon_days: u32,
+
+    /// Report worker threads
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Threads rendering databases of a server survey or bundle in parallel (default: available CPUs)"
+    )]
+    pub jobs: Option<std::num::NonZeroUsize>,
+
+    /// Print a JSON result summary to stderr
+    #[arg(
+        long,
+        global = true,
+        help = "Print a JSON summary of generated outputs, skipped inputs, and warnings to stderr"
+    )]
+    pub result_summary: bool,
+
+    /// Write the JSON result summary to a file
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Write a JSON summary of generated outputs (with SHA-256 checksums) to a file"
+    )]
+    pub result_file: Option<PathBuf>,
+
+    /// Report title
+    #[arg(
+        long,
+        global = true,
+        value_name = "TEXT",
+        help = "Heading used in place of 'Database Schema' in reports, e.g. an organization name"
+    )]
+    pub report_title: Option<String>,
+
+    /// Report footer
+    #[arg(
+        long,
+        global = true,
+        value_name = "TEXT",
+        help = "Text closing each report's overview page, e.g. a classification notice"
+    )]
+    pub report_footer: Option<String>,
+
+    /// Decryption password file
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Read the password for encrypted surveys from this file instead of DBSURVEYOR_ENCRYPTION_PASSWORD or a prompt"
+    )]
+    pub password_file: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -108,8 +283,32 @@ pub enum Command {
     #[cfg(feature = "experimental")]
     /// Reconstruct SQL DDL from schema
     Sql(SqlArgs),
+    #[cfg(feature = "experimental")]
+    /// Report how column types map to a target dialect for migration planning
+    Compat(CompatArgs),
     /// Validate schema file format
     Validate(ValidateArgs),
+    /// Print the JSON Schema for the survey file format
+    Schema(SchemaArgs),
+    /// Build a search index for a schema file
+    Index(IndexArgs),
+    /// Search object names, comments, and classifications
+    Search(SearchArgs),
+    /// Find tables containing a column across survey files
+    WhereUsed(WhereUsedArgs),
+    /// Report objects with the same name but different definitions across databases
+    Collisions(CollisionsArgs),
+    /// Approve a survey as the drift baseline or check a survey against it
+    Baseline(BaselineArgs),
+    /// Replace server, database, and user names with keyed pseudonyms
+    Anonymize(AnonymizeArgs),
+    /// Check a survey against an expected-schema specification
+    Conform(ConformArgs),
+    /// List or reconstruct surveys from a collection journal
+    Journal(JournalArgs),
+    #[cfg(feature = "encryption")]
+    /// Add, remove, or rotate the passwords of an encrypted survey
+    Rekey(RekeyArgs),
     /// Generate shell completions
     #[command(hide = true)]
     Completions {
@@ -121,17 +320,29 @@ pub enum Command {
 
 #[derive(Args)]
 pub struct GenerateArgs {
-    /// Input schema file
-    #[arg(help = "Path to schema file")]
+    /// Input schema file or directory
+    #[arg(help = "Path to schema file, directory of survey files, or - for stdin")]
     pub input: PathBuf,
 
     /// Output format
     #[arg(short, long, value_enum, default_value = "markdown")]
     pub format: OutputFormat,
 
+    /// Custom exporter compiled into this build, used instead of --format
+    #[arg(long, value_name = "NAME", conflicts_with = "format")]
+    pub exporter: Option<String>,
+
     /// Output file path
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// Output path per database, with {server} and {database} placeholders
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output")]
+    pub output_template: Option<String>,
+
+    /// Glob selecting survey files when the input is a directory
+    #[arg(long, value_name = "PATTERN")]
+    pub glob: Option<String>,
 }
 
 #[cfg(feature = "experimental")]
@@ -144,6 +355,13 @@ pub struct AnalyzeArgs {
     /// Show detailed statistics
     #[arg(long, help = "Show detailed analysis statistics")]
     pub detailed: bool,
+
+    /// List lint findings, including dangling and orphaned objects
+    #[arg(
+        long,
+        help = "List schema lint findings, including dangling and orphaned objects to clean up"
+    )]
+    pub lint: bool,
 }
 
 #[cfg(feature = "experimental")]
@@ -167,6 +385,27 @@ pub struct SqlArgs {
     pub output: Option<PathBuf>,
 }
 
+#[cfg(feature = "experimental")]
+#[derive(Args)]
+pub struct CompatArgs {
+    /// Input schema file
+    #[arg(help = "Path to schema file")]
+    pub input: PathBuf,
+
+    /// Target SQL dialect
+    #[arg(
+        long,
+        value_enum,
+        default_value = "postgresql",
+        help = "Dialect the database would be migrated to"
+    )]
+    pub dialect: SqlDialect,
+
+    /// Output file path
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
 #[derive(Args)]
 pub struct ValidateArgs {
     /// Input schema file
@@ -174,6 +413,245 @@ pub struct ValidateArgs {
     pub input: PathBuf,
 }
 
+#[derive(Args)]
+pub struct SchemaArgs {
+    /// Survey format version
+    #[arg(
+        long,
+        default_value = dbsurveyor_core::FORMAT_VERSION,
+        help = "Survey format version to print the JSON Schema for"
+    )]
+    pub format_version: String,
+
+    /// Output file path
+    #[arg(short, long, help = "Write the schema to a file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct IndexArgs {
+    /// Input schema file
+    #[arg(help = "Path to schema file (.json, .json.zst, or .enc)")]
+    pub input: PathBuf,
+
+    /// Output file path
+    #[arg(short, long, help = "Index file path (default: <input>.index.json)")]
+    pub output: Option<PathBuf>,
+
+    /// Index an encrypted survey
+    #[arg(
+        long,
+        help = "Index an encrypted survey, writing its object names and comments in plain text"
+    )]
+    pub allow_plaintext: bool,
+}
+
+#[derive(Args)]
+pub struct AnonymizeArgs {
+    /// Input schema file
+    #[arg(help = "Path to schema file (.json, .json.zst, or .enc)")]
+    pub input: PathBuf,
+
+    /// Output file path
+    #[arg(
+        short,
+        long,
+        help = "Anonymized survey path (default: <input stem>.anonymized.json)"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct JournalArgs {
+    /// Journal file
+    #[arg(help = "Journal written by dbsurveyor-collect --append-to")]
+    pub input: PathBuf,
+
+    /// Record to reconstruct
+    #[arg(
+        long,
+        value_name = "WHEN",
+        help = "Reconstruct the survey as of a record number, RFC 3339 timestamp, or YYYY-MM-DD (end of day); without it, list the records"
+    )]
+    pub at: Option<String>,
+
+    /// Output file path
+    #[arg(
+        short,
+        long,
+        requires = "at",
+        help = "Reconstructed survey path (default: <journal stem>-<seq>.dbsurveyor.json)"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ConformArgs {
+    /// Survey file to check
+    #[arg(help = "Survey file to check (.json, .json.zst, or .enc)")]
+    pub survey: PathBuf,
+
+    /// Expected-schema specification
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Expected-schema specification (JSON)"
+    )]
+    pub spec: PathBuf,
+
+    /// Print findings as JSON
+    #[arg(long, help = "Print findings as a JSON array instead of text")]
+    pub json: bool,
+}
+
+#[derive(Args)]
+#[command(group(
+    clap::ArgGroup::new("change")
+        .required(true)
+        .multiple(true)
+        .args(["add", "remove", "rotate", "list"])
+))]
+pub struct RekeyArgs {
+    /// Encrypted survey file
+    #[arg(help = "Path to an encrypted survey (.enc)")]
+    pub input: PathBuf,
+
+    /// Recipient to add
+    #[arg(
+        long,
+        value_name = "LABEL",
+        conflicts_with = "rotate",
+        help = "Add a recipient whose password is read from DBSURVEYOR_NEW_ENCRYPTION_PASSWORD or prompted"
+    )]
+    pub add: Option<String>,
+
+    /// Recipients to remove
+    #[arg(long, value_name = "LABEL", help = "Remove a recipient (repeatable)")]
+    pub remove: Vec<String>,
+
+    /// Replace the password that opened the file
+    #[arg(
+        long,
+        help = "Give the recipient whose password opened the file a new password"
+    )]
+    pub rotate: bool,
+
+    /// List recipients
+    #[arg(
+        long,
+        conflicts_with_all = ["add", "remove", "rotate", "output"],
+        help = "List recipient labels without decrypting"
+    )]
+    pub list: bool,
+
+    /// Output file path
+    #[arg(
+        short,
+        long,
+        help = "Write the rekeyed survey here instead of replacing the input"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Search query
+    #[arg(help = "Words to search for; each word may be a prefix")]
+    pub query: String,
+
+    /// Schema or index file
+    #[arg(help = "Path to an index file, or a schema file (uses <input>.index.json if present)")]
+    pub input: PathBuf,
+
+    /// Maximum number of results
+    #[arg(long, default_value_t = 20, help = "Maximum number of results")]
+    pub limit: usize,
+}
+
+#[derive(Args)]
+#[command(group(
+    clap::ArgGroup::new("criteria")
+        .required(true)
+        .multiple(true)
+        .args(["column", "classification"])
+))]
+pub struct WhereUsedArgs {
+    /// Column name or glob pattern
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Column name or glob pattern (`*`, `?`), matched case-insensitively"
+    )]
+    pub column: Option<String>,
+
+    /// Column classification
+    #[arg(
+        long,
+        value_parser = ["credential", "pii"],
+        help = "Only columns whose names suggest this classification"
+    )]
+    pub classification: Option<String>,
+
+    /// Survey files to search
+    #[arg(
+        required = true,
+        help = "Survey files to search (.json, .json.zst, or .enc)"
+    )]
+    pub surveys: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct CollisionsArgs {
+    /// Surveys and bundles to compare
+    #[arg(
+        required = true,
+        help = "Surveys and bundles to compare (.json, .json.zst, .enc, or .jsonl)"
+    )]
+    pub surveys: Vec<PathBuf>,
+
+    /// Print the report as JSON
+    #[arg(long, help = "Print the report as JSON instead of text")]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct BaselineArgs {
+    #[command(subcommand)]
+    pub command: BaselineCommand,
+}
+
+#[derive(Subcommand)]
+pub enum BaselineCommand {
+    /// Approve a survey's structure as the baseline
+    Set {
+        /// Survey file to approve
+        #[arg(help = "Survey file to approve (.json, .json.zst, or .enc)")]
+        survey: PathBuf,
+
+        /// Baseline file to write
+        #[arg(long, default_value = baseline::DEFAULT_BASELINE_PATH)]
+        baseline: PathBuf,
+    },
+    /// Check a newer survey against the approved baseline
+    Check {
+        /// Survey file to check
+        #[arg(help = "Survey file to check (.json, .json.zst, or .enc)")]
+        survey: PathBuf,
+
+        /// Approved baseline file
+        #[arg(long, default_value = baseline::DEFAULT_BASELINE_PATH)]
+        baseline: PathBuf,
+
+        /// Allow-list files of expected changes
+        #[arg(
+            long = "allow",
+            value_name = "FILE",
+            help = "Allow-list of expected changes (repeatable)"
+        )]
+        allow: Vec<PathBuf>,
+    },
+}
+
 #[derive(Args)]
 pub struct GlobalArgs {
     /// Increase verbosity
@@ -188,32 +666,103 @@ pub struct GlobalArgs {
     /// Suppress output
     #[arg(short, long, help = "Suppress all output except errors")]
     pub quiet: bool,
+
+    /// Configuration profile
+    #[arg(
+        long,
+        global = true,
+        env = "DBSURVEYOR_PROFILE",
+        value_name = "NAME",
+        help = "Profile from ~/.config/dbsurveyor/config.toml supplying defaults for options not given"
+    )]
+    pub profile: Option<String>,
+}
+
+/// Parsing limits for untrusted schema files
+#[derive(Args)]
+pub struct InputLimitArgs {
+    /// Maximum schema file size (MiB)
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 512,
+        help = "Maximum schema file size in MiB, also applied after decompression"
+    )]
+    pub max_file_size_mb: u64,
+
+    /// Maximum JSON nesting depth
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 128,
+        help = "Maximum JSON nesting depth in schema files"
+    )]
+    pub max_json_depth: usize,
+
+    /// Maximum JSON string length (bytes)
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 16 * 1024 * 1024,
+        help = "Maximum length in bytes of any string in schema files"
+    )]
+    pub max_string_length: usize,
+
+    /// Maximum items in a single JSON array
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 1_000_000,
+        help = "Maximum number of items in any array (tables, columns, sample rows, ...)"
+    )]
+    pub max_array_items: usize,
+}
+
+impl InputLimitArgs {
+    /// Builds the loader limits from the command-line values.
+    pub fn to_input_limits(&self) -> InputLimits {
+        InputLimits::default()
+            .with_max_file_size_bytes(self.max_file_size_mb.saturating_mul(1024 * 1024))
+            .with_max_json_depth(self.max_json_depth)
+            .with_max_string_length(self.max_string_length)
+            .with_max_array_items(self.max_array_items)
+    }
 }
 
 #[derive(Clone, ValueEnum)]
 pub enum OutputFormat {
     /// Markdown documentation
     Markdown,
+    /// Multi-file Markdown site with MkDocs and Docusaurus navigation
+    MarkdownSite,
+    /// Confluence storage-format pages with a page hierarchy manifest
+    Confluence,
+    /// Relationship graph as JSON nodes and edges for external graph tools
+    GraphJson,
     #[cfg(feature = "experimental")]
-    /// HTML report with search (not yet implemented)
+    /// Self-contained HTML report with ER diagrams
     Html,
     #[cfg(feature = "experimental")]
     /// JSON analysis report
     Json,
     #[cfg(feature = "experimental")]
-    /// Mermaid ERD diagram (not yet implemented)
+    /// Mermaid ERD diagram
     Mermaid,
 }
 
-#[derive(Clone, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum SqlDialect {
     /// PostgreSQL dialect
+    #[value(name = "postgresql")]
     PostgreSQL,
     /// MySQL dialect
+    #[value(name = "mysql")]
     MySQL,
     /// SQLite dialect
+    #[value(name = "sqlite")]
     SQLite,
     /// SQL Server dialect
+    #[value(name = "sqlserver")]
     SqlServer,
     /// Generic SQL (ANSI standard)
     Generic,
@@ -221,7 +770,10 @@ pub enum SqlDialect {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = parse_cli();
+    if let Some(path) = &cli.password_file {
+        schema::use_password_file(path.clone());
+    }
 
     // Initialize logging
     init_logging(cli.global.verbose, cli.global.quiet)?;
@@ -234,30 +786,146 @@ async fn main() -> Result<()> {
         ))
     })?;
 
+    let limits = cli.limits.to_input_limits();
+    sandbox::validate_cli_paths(&cli, &cli.paths.to_path_policy()?)?;
+
     // Handle commands
     match &cli.command {
         Some(Command::Generate(args)) => {
             output::generate_documentation(
                 &args.input,
-                args.format.clone(),
+                exporter_for(&args.format, args.exporter.as_deref())?,
                 args.output.as_ref(),
+                args.output_template.as_deref(),
+                args.glob.as_deref(),
+                &limits,
                 &cli,
             )
             .await
         }
         #[cfg(feature = "experimental")]
-        Some(Command::Analyze(args)) => output::analyze_schema(&args.input, args.detailed).await,
+        Some(Command::Analyze(args)) => {
+            output::analyze_schema(&args.input, args.detailed, args.lint, &limits).await
+        }
         #[cfg(feature = "experimental")]
         Some(Command::Sql(args)) => {
-            output::generate_sql(&args.input, args.dialect.clone(), args.output.as_ref()).await
+            output::generate_sql(
+                &args.input,
+                args.dialect,
+                args.output.as_ref(),
+                &limits,
+                &cli.paths.to_path_policy()?,
+            )
+            .await
+        }
+        #[cfg(feature = "experimental")]
+        Some(Command::Compat(args)) => {
+            output::generate_compat(
+                &args.input,
+                args.dialect,
+                args.output.as_ref(),
+                &limits,
+                &cli.paths.to_path_policy()?,
+            )
+            .await
+        }
+        Some(Command::Validate(args)) => output::validate_schema(&args.input, &limits).await,
+        Some(Command::Schema(args)) => {
+            output::print_json_schema(&args.format_version, args.output.as_ref()).await
+        }
+        Some(Command::Index(args)) => {
+            output::build_search_index(
+                &args.input,
+                args.output.as_ref(),
+                args.allow_plaintext,
+                &limits,
+                &cli.paths.to_path_policy()?,
+            )
+            .await
+        }
+        Some(Command::Search(args)) => {
+            output::search_schema(&args.query, &args.input, args.limit, &limits).await
+        }
+        Some(Command::WhereUsed(args)) => {
+            let query = where_used::ColumnQuery {
+                pattern: args.column.clone(),
+                classification: args.classification.clone(),
+            };
+            output::where_used(&query, &args.surveys, &limits).await
+        }
+        Some(Command::Collisions(args)) => {
+            output::find_collisions(&args.surveys, args.json, &limits).await
+        }
+        Some(Command::Baseline(args)) => match &args.command {
+            BaselineCommand::Set { survey, baseline } => {
+                output::set_baseline(survey, baseline, &limits).await
+            }
+            BaselineCommand::Check {
+                survey,
+                baseline,
+                allow,
+            } => {
+                if !output::check_baseline(survey, baseline, allow, &limits).await? {
+                    std::process::exit(baseline::DRIFT_EXIT_CODE);
+                }
+                Ok(())
+            }
+        },
+        Some(Command::Anonymize(args)) => {
+            output::anonymize_survey(
+                &args.input,
+                args.output.as_ref(),
+                &limits,
+                &cli.paths.to_path_policy()?,
+            )
+            .await
+        }
+        Some(Command::Journal(args)) => match &args.at {
+            Some(at) => {
+                let at = journal::JournalPoint::parse(at)?;
+                journal::reconstruct_survey(
+                    &args.input,
+                    at,
+                    args.output.as_ref(),
+                    &limits,
+                    &cli.paths.to_path_policy()?,
+                )
+                .await
+            }
+            None => journal::list_journal(&args.input, &limits).await,
+        },
+        Some(Command::Conform(args)) => {
+            if !output::conform_survey(&args.survey, &args.spec, args.json, &limits).await? {
+                std::process::exit(conform::NONCONFORMING_EXIT_CODE);
+            }
+            Ok(())
+        }
+        #[cfg(feature = "encryption")]
+        Some(Command::Rekey(args)) => {
+            if args.list {
+                return rekey::list_recipients(&args.input).await;
+            }
+            let request = rekey::RekeyRequest {
+                add: args.add.as_deref(),
+                remove: &args.remove,
+                rotate: args.rotate,
+            };
+            rekey::rekey(&args.input, args.output.as_ref(), &request).await
         }
-        Some(Command::Validate(args)) => output::validate_schema(&args.input).await,
         Some(Command::Completions { shell }) => print_completions(*shell),
         None => {
             // Default behavior: generate documentation if input is provided
             if let Some(ref input) = cli.input {
-                output::generate_documentation(input, cli.format.clone(), cli.output.as_ref(), &cli)
-                    .await
+                output::generate_documentation(
+                    input,
+                    exporter_for(&cli.format, cli.exporter.as_deref())?,
+                    cli.output.as_ref(),
+                    cli.output_template.as_deref(),
+                    cli.glob.as_deref(),
+                    &limits,
+                    &cli,
+                )
+                .await
             } else {
                 eprintln!("Error: Input file is required");
                 eprintln!("Use --help for usage information");
@@ -267,6 +935,45 @@ async fn main() -> Result<()> {
     }
 }
 
+/// Parses the command line, taking options not given from the `--profile`
+/// selected, if any.
+fn parse_cli() -> Cli {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    let command = Cli::command();
+    let matches = command.clone().get_matches_from(args.clone());
+    let Some(name) = matches.get_one::<String>("profile") else {
+        return Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    };
+    let profile = profile::load_profile(name).unwrap_or_else(|e| {
+        command
+            .clone()
+            .error(clap::error::ErrorKind::InvalidValue, e)
+            .exit()
+    });
+    let args = profile::merge_args(args, &profile.postprocessor_args(), |long| {
+        profile::overridden_by(&command, &matches, long)
+    })
+    .unwrap_or_else(|e| {
+        command
+            .clone()
+            .error(clap::error::ErrorKind::ArgumentConflict, e)
+            .exit()
+    });
+    Cli::parse_from(args)
+}
+
+/// The exporter to render with: the custom exporter named by `--exporter`,
+/// or the built-in `--format`.
+fn exporter_for<'a>(
+    format: &'a OutputFormat,
+    custom: Option<&str>,
+) -> Result<&'a dyn exporter::Exporter> {
+    match custom {
+        Some(name) => exporter::find_exporter(name),
+        None => Ok(format),
+    }
+}
+
 /// Creates a progress spinner with the given message and a 120ms tick interval.
 ///
 /// The spinner draw target is hidden when `NO_COLOR` is set, `TERM=dumb`,
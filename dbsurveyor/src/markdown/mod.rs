@@ -0,0 +1,1828 @@
+//! Markdown report rendering with selectable sections.
+
+pub(crate) mod site;
+
+use crate::glossary::{Term, annotate, terminology_section};
+use crate::growth::format_bytes;
+use crate::naming::NamingDictionary;
+use crate::redaction::{REDACTED_VALUE, RedactedTableSample, classify_field};
+use clap::ValueEnum;
+use dbsurveyor_core::TableQualityMetrics;
+use dbsurveyor_core::collation::{CollationMismatch, summarize_collations};
+use dbsurveyor_core::documentation::{UndocumentedTable, comment_coverage, undocumented_tables};
+use dbsurveyor_core::enums::{InferredEnum, infer_enums};
+use dbsurveyor_core::lob::{LobTable, VolumeEstimate, large_object_inventory};
+use dbsurveyor_core::models::{
+    Column, DatabaseSchema, DatabaseType, Index, IndexOptions, ReferentialAction, RelatedTable,
+    Table, TableInheritance, UnifiedDataType,
+};
+use dbsurveyor_core::protection::{ProtectedColumn, Protection, detect_protected_columns};
+use dbsurveyor_core::relationships::{InferredRelationship, cardinality, infer_relationships};
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Section of the Markdown report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportSection {
+    /// Tables and their columns
+    Tables,
+    /// Indexes
+    Indexes,
+    /// Foreign key relationships
+    Relationships,
+    /// Redacted sample data
+    Samples,
+    /// Data quality metrics
+    Quality,
+    /// Columns whose names suggest credentials or personal data
+    Classification,
+    /// Columns that appear to store files, with estimated volume
+    LargeObjects,
+    /// Text and integer columns whose sampled values form a small closed set
+    Enums,
+    /// Columns whose collation or character set differs from the database default
+    Collation,
+    /// Names containing abbreviations with no dictionary expansion
+    Naming,
+    /// Comment coverage and undocumented tables and columns
+    Documentation,
+}
+
+impl ReportSection {
+    /// Every section, in report order.
+    pub const ALL: [Self; 11] = [
+        Self::Tables,
+        Self::Indexes,
+        Self::Relationships,
+        Self::Samples,
+        Self::Quality,
+        Self::Classification,
+        Self::LargeObjects,
+        Self::Enums,
+        Self::Collation,
+        Self::Naming,
+        Self::Documentation,
+    ];
+}
+
+/// Settings shared by the Markdown, Markdown site, and Confluence renderers.
+#[derive(Clone, Copy)]
+pub(crate) struct ReportOptions<'a> {
+    /// Redacted samples, if the survey has any
+    pub(crate) samples: Option<&'a [RedactedTableSample]>,
+    /// Sections to render, in any order
+    pub(crate) sections: &'a [ReportSection],
+    /// Abbreviation expansions for display names
+    pub(crate) names: Option<&'a NamingDictionary>,
+    /// Rows of masked sample preview to show under each table, if enabled
+    pub(crate) sample_preview: Option<usize>,
+    /// Heading in place of "Database Schema" (`--report-title`)
+    pub(crate) title: Option<&'a str>,
+    /// Text closing the report's overview page (`--report-footer`)
+    pub(crate) footer: Option<&'a str>,
+}
+
+impl Default for ReportOptions<'_> {
+    fn default() -> Self {
+        Self {
+            samples: None,
+            sections: &ReportSection::ALL,
+            names: None,
+            sample_preview: None,
+            title: None,
+            footer: None,
+        }
+    }
+}
+
+/// Title of a database's report: `--report-title`, or "Database Schema",
+/// and the database name.
+pub(crate) fn report_title(schema: &DatabaseSchema, options: &ReportOptions<'_>) -> String {
+    format!(
+        "{}: {}",
+        options.title.unwrap_or("Database Schema"),
+        schema.database_info.name
+    )
+}
+
+/// Markdown footer for `--report-footer`, after a rule.
+pub(crate) fn report_footer(options: &ReportOptions<'_>) -> String {
+    options
+        .footer
+        .map(|footer| format!("\n---\n\n{}\n", footer))
+        .unwrap_or_default()
+}
+
+/// Objects covered by a rendered document: a whole database or one table.
+pub(crate) struct ReportScope<'a> {
+    pub(crate) tables: Vec<&'a Table>,
+    /// Set when rendering the page of a single table
+    pub(crate) page_table: Option<&'a Table>,
+    pub(crate) indexes: Vec<&'a Index>,
+    pub(crate) samples: Vec<&'a RedactedTableSample>,
+    pub(crate) quality: Vec<&'a TableQualityMetrics>,
+    /// Relationships proposed by inference, not declared in the database
+    pub(crate) inferred: Vec<InferredRelationship>,
+    /// Columns overriding the database collation or character set
+    pub(crate) collations: Vec<CollationMismatch>,
+    /// Tables missing comments, most important first
+    pub(crate) undocumented: Vec<UndocumentedTable>,
+    /// Columns that appear to hold hashed or encrypted values
+    pub(crate) protected: Vec<ProtectedColumn>,
+    /// Tables storing file content
+    pub(crate) large_objects: Vec<LobTable>,
+    /// Columns whose sampled values form a small closed set
+    pub(crate) enums: Vec<InferredEnum>,
+    pub(crate) names: Option<&'a NamingDictionary>,
+    /// Rows of masked sample preview to show under each table, if enabled
+    pub(crate) sample_preview: Option<usize>,
+    /// Engine whose native terms are shown beside the normalized ones
+    pub(crate) engine: Option<&'a DatabaseType>,
+}
+
+impl<'a> ReportScope<'a> {
+    /// Scope covering every object in the database.
+    pub(crate) fn database(schema: &'a DatabaseSchema, options: &ReportOptions<'a>) -> Self {
+        Self {
+            tables: schema.tables.iter().collect(),
+            page_table: None,
+            indexes: schema
+                .indexes
+                .iter()
+                .chain(schema.tables.iter().flat_map(|t| t.indexes.iter()))
+                .collect(),
+            samples: options.samples.unwrap_or_default().iter().collect(),
+            quality: schema
+                .quality_metrics
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .collect(),
+            inferred: infer_relationships(schema),
+            collations: summarize_collations(schema).mismatches,
+            undocumented: undocumented_tables(schema),
+            protected: detect_protected_columns(schema),
+            large_objects: large_object_inventory(schema),
+            enums: infer_enums(schema),
+            names: options.names,
+            sample_preview: options.sample_preview,
+            engine: schema.database_info.engine.as_ref(),
+        }
+    }
+
+    /// Scope covering one table of this database scope and the objects
+    /// attached to it. Whole-schema analyses are taken from this scope
+    /// rather than rerun for every table.
+    pub(crate) fn table(&self, schema: &'a DatabaseSchema, table: &'a Table) -> Self {
+        let in_table =
+            |name: &str, schema: &Option<String>| name == table.name && *schema == table.schema;
+        Self {
+            tables: vec![table],
+            page_table: Some(table),
+            indexes: schema
+                .indexes
+                .iter()
+                .filter(|i| in_table(&i.table_name, &i.schema))
+                .chain(table.indexes.iter())
+                .collect(),
+            samples: self
+                .samples
+                .iter()
+                .copied()
+                .filter(|s| in_table(&s.table_name, &s.schema_name))
+                .collect(),
+            quality: self
+                .quality
+                .iter()
+                .copied()
+                .filter(|m| in_table(&m.table_name, &m.schema_name))
+                .collect(),
+            inferred: self
+                .inferred
+                .iter()
+                .filter(|r| in_table(&r.table, &r.schema))
+                .cloned()
+                .collect(),
+            collations: self
+                .collations
+                .iter()
+                .filter(|m| m.table == qualified_table_name(table))
+                .cloned()
+                .collect(),
+            undocumented: self
+                .undocumented
+                .iter()
+                .filter(|u| in_table(&u.table, &u.schema))
+                .cloned()
+                .collect(),
+            protected: self
+                .protected
+                .iter()
+                .filter(|p| in_table(&p.table, &p.schema))
+                .cloned()
+                .collect(),
+            large_objects: self
+                .large_objects
+                .iter()
+                .filter(|l| in_table(&l.table, &l.schema))
+                .cloned()
+                .collect(),
+            enums: self
+                .enums
+                .iter()
+                .filter(|e| in_table(&e.table, &e.schema))
+                .cloned()
+                .collect(),
+            names: self.names,
+            sample_preview: self.sample_preview,
+            engine: self.engine,
+        }
+    }
+}
+
+/// Renders the Markdown report containing only the selected sections.
+///
+/// The title and summary are always included. Sections are emitted in the
+/// fixed order of [`ReportSection::ALL`] regardless of the order requested.
+pub(crate) fn render_report(schema: &DatabaseSchema, options: &ReportOptions<'_>) -> String {
+    let mut content = format!(
+        "# {}\n\n\
+        Generated by DBSurveyor v{}\n\
+        Collection Date: {}\n\n",
+        report_title(schema, options),
+        schema.collection_metadata.collector_version,
+        schema
+            .collection_metadata
+            .collected_at
+            .format("%Y-%m-%d %H:%M:%S UTC"),
+    );
+    content.push_str(&summary_section(schema));
+    content.push_str(&render_sections(
+        &ReportScope::database(schema, options),
+        options.sections,
+    ));
+    content.push_str(&terminology_section(schema.database_info.engine.as_ref()));
+    content.push_str(&report_footer(options));
+    content
+}
+
+/// Renders the engine, summary counts, access, encoding, and collation for
+/// a database.
+pub(crate) fn summary_section(schema: &DatabaseSchema) -> String {
+    let engine = schema.database_info.engine.as_ref();
+    let mut section = String::from("## Summary\n\n");
+    if let Some(engine) = engine {
+        let _ = writeln!(section, "- **Engine**: {}", engine);
+    }
+    let _ = write!(
+        section,
+        "- **{}**: {}\n\
+        - **Views**: {}\n\
+        - **Indexes**: {}\n",
+        annotate("Tables", Term::Table, engine),
+        schema.tables.len(),
+        schema.views.len(),
+        schema.indexes.len()
+    );
+    if let Some(probe) = &schema.database_info.access_probe {
+        let _ = writeln!(section, "- **Access**: {}", probe);
+    }
+    let collations = summarize_collations(schema);
+    if let Some(encoding) = &collations.database_encoding {
+        let _ = writeln!(section, "- **Encoding**: {}", encoding);
+    }
+    if let Some(collation) = &collations.database_collation {
+        let _ = writeln!(section, "- **Collation**: {}", collation);
+    }
+    if collations.is_mixed() {
+        let _ = writeln!(
+            section,
+            "- **Columns with non-default collation**: {}",
+            collations.mismatches.len()
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Renders the selected sections for the objects in `scope`.
+pub(crate) fn render_sections(scope: &ReportScope<'_>, sections: &[ReportSection]) -> String {
+    let mut content = String::new();
+    for section in ReportSection::ALL {
+        if !sections.contains(&section) {
+            continue;
+        }
+        content.push_str(&match section {
+            ReportSection::Tables => match scope.page_table {
+                Some(table) => format!(
+                    "## {}\n\n{}{}",
+                    annotate("Columns", Term::Column, scope.engine),
+                    columns_table(table, scope.names),
+                    preview_table(table, scope)
+                ),
+                None => {
+                    let mut tables = tables_section(scope);
+                    tables.push_str(&virtual_tables_section(&scope.tables));
+                    tables.push_str(&special_collections_section(&scope.tables));
+                    tables.push_str(&system_versioning_section(&scope.tables));
+                    tables.push_str(&masked_columns_section(&scope.tables));
+                    tables
+                }
+            },
+            ReportSection::Indexes => indexes_section(&scope.indexes),
+            ReportSection::Relationships => relationships_section(&scope.tables, &scope.inferred),
+            ReportSection::Samples => samples_section(&scope.samples),
+            ReportSection::Quality => quality_section(&scope.quality),
+            ReportSection::Classification => classification_section(scope),
+            ReportSection::LargeObjects => large_objects_section(&scope.large_objects),
+            ReportSection::Enums => enums_section(scope),
+            ReportSection::Collation => collation_section(&scope.collations),
+            ReportSection::Naming => naming_section(&scope.tables, scope.names),
+            ReportSection::Documentation => documentation_section(scope),
+        });
+    }
+    content
+}
+
+fn tables_section(scope: &ReportScope<'_>) -> String {
+    let mut section = format!("## {}\n\n", annotate("Tables", Term::Table, scope.engine));
+    let (tables, names) = (&scope.tables, scope.names);
+    if tables.is_empty() {
+        section.push_str("No tables collected.\n\n");
+        return section;
+    }
+
+    for table in tables {
+        match names.and_then(|n| n.display_name(&table.name)) {
+            Some(display) => {
+                let _ = writeln!(
+                    section,
+                    "### {} ({})\n",
+                    qualified_table_name(table),
+                    display
+                );
+            }
+            None => {
+                let _ = writeln!(section, "### {}\n", qualified_table_name(table));
+            }
+        }
+        section.push_str(&columns_table(table, names));
+        section.push_str(&preview_table(table, scope));
+    }
+    section
+}
+
+/// Renders the masked sample preview of a table, when enabled and sampled.
+fn preview_table(table: &Table, scope: &ReportScope<'_>) -> String {
+    let Some(preview) = scope
+        .sample_preview
+        .and_then(|rows| sample_preview(table_sample(&scope.samples, table)?, table, rows))
+    else {
+        return String::new();
+    };
+
+    let mut section = format!(
+        "Sample preview (first {} rows, masked):\n\n",
+        preview.rows.len()
+    );
+    let headers: Vec<String> = preview.headers.iter().map(|h| escape_cell(h)).collect();
+    let _ = writeln!(section, "| {} |", headers.join(" | "));
+    let _ = writeln!(section, "|{}", "---|".repeat(headers.len()));
+    for row in &preview.rows {
+        let cells: Vec<String> = row.iter().map(|cell| escape_cell(cell)).collect();
+        let _ = writeln!(section, "| {} |", cells.join(" | "));
+    }
+    section.push('\n');
+    section
+}
+
+/// Renders a table's comment, row count, and column listing.
+///
+/// A Display Name column is added when a naming dictionary is in use.
+fn columns_table(table: &Table, names: Option<&NamingDictionary>) -> String {
+    let mut section = String::new();
+    if let Some(comment) = &table.comment {
+        let _ = writeln!(section, "{}\n", comment);
+    }
+    if let Some(row_count) = table.row_count {
+        let _ = writeln!(section, "Rows: {}\n", row_count);
+    }
+    if let Some(inheritance) = &table.inheritance {
+        section.push_str(&inheritance_lines(inheritance));
+    }
+    if names.is_some() {
+        section.push_str(
+            "| Column | Display Name | Type | Nullable | Primary Key | Default |\n\
+            |--------|--------------|------|----------|-------------|---------|\n",
+        );
+    } else {
+        section.push_str(
+            "| Column | Type | Nullable | Primary Key | Default |\n\
+            |--------|------|----------|-------------|---------|\n",
+        );
+    }
+    for column in &table.columns {
+        let mut name = escape_cell(&column.name);
+        if let Some(names) = names {
+            let display = names.display_name(&column.name).unwrap_or_default();
+            let _ = write!(name, " | {}", escape_cell(&display));
+        }
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} |",
+            name,
+            escape_cell(&column_type_label(column)),
+            yes_no(column.is_nullable),
+            yes_no(column.is_primary_key),
+            escape_cell(column.default_value.as_deref().unwrap_or(""))
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Renders a table's parents, children, and inherited columns.
+fn inheritance_lines(inheritance: &TableInheritance) -> String {
+    let list = |tables: &[RelatedTable]| {
+        tables
+            .iter()
+            .map(|t| qualified_name(t.schema.as_deref(), &t.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut lines = String::new();
+    if !inheritance.parents.is_empty() {
+        let label = if inheritance.is_partition {
+            "Partition of"
+        } else {
+            "Inherits from"
+        };
+        let _ = writeln!(lines, "{}: {}\n", label, list(&inheritance.parents));
+    }
+    if !inheritance.children.is_empty() {
+        let _ = writeln!(lines, "Children: {}\n", list(&inheritance.children));
+    }
+    if !inheritance.inherited_columns.is_empty() {
+        let _ = writeln!(
+            lines,
+            "Inherited columns: {}\n",
+            inheritance.inherited_columns.join(", ")
+        );
+    }
+    lines
+}
+
+/// Renders the virtual tables (e.g. SQLite FTS or R*Tree) as a Markdown section.
+///
+/// Returns an empty string when there are no virtual tables.
+fn virtual_tables_section(tables: &[&Table]) -> String {
+    let virtual_tables: Vec<_> = tables
+        .iter()
+        .filter_map(|t| t.virtual_table.as_ref().map(|vt| (t, vt)))
+        .collect();
+    if virtual_tables.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from(
+        "## Virtual Tables\n\n\
+        | Table | Module | Arguments | Shadow Tables |\n\
+        |-------|--------|-----------|---------------|\n",
+    );
+    for (table, vt) in virtual_tables {
+        section.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            table.name,
+            vt.module,
+            vt.arguments.join(", ").replace('|', "\\|"),
+            vt.shadow_tables.join(", ")
+        ));
+    }
+    section.push('\n');
+    section
+}
+
+/// Renders capped and time-series collections (MongoDB) as a Markdown
+/// section. Their documents are evicted by size or age, so samples and row
+/// counts describe a moving window rather than the full history.
+///
+/// Returns an empty string when there are none.
+fn special_collections_section(tables: &[&Table]) -> String {
+    let mut rows = String::new();
+    for table in tables {
+        let Some(options) = &table.options else {
+            continue;
+        };
+        if let Some(capped) = &options.capped {
+            let mut limits = Vec::new();
+            if let Some(bytes) = capped.max_bytes {
+                limits.push(format!("max {} bytes", bytes));
+            }
+            if let Some(documents) = capped.max_documents {
+                limits.push(format!("max {} documents", documents));
+            }
+            let _ = writeln!(
+                rows,
+                "| {} | capped | {} |",
+                escape_cell(&qualified_table_name(table)),
+                limits.join(", ")
+            );
+        }
+        if let Some(time_series) = &options.time_series {
+            let mut details = vec![format!("time field: {}", time_series.time_field)];
+            if let Some(meta) = &time_series.meta_field {
+                details.push(format!("meta field: {}", meta));
+            }
+            if let Some(granularity) = &time_series.granularity {
+                details.push(format!("granularity: {}", granularity));
+            }
+            if let Some(seconds) = time_series.expire_after_seconds {
+                details.push(format!("expires after {}s", seconds));
+            }
+            let _ = writeln!(
+                rows,
+                "| {} | time-series | {} |",
+                escape_cell(&qualified_table_name(table)),
+                escape_cell(&details.join(", "))
+            );
+        }
+    }
+    if rows.is_empty() {
+        return rows;
+    }
+    format!(
+        "## Special Collections\n\n\
+        | Collection | Kind | Details |\n\
+        |------------|------|---------|\n\
+        {}\n",
+        rows
+    )
+}
+
+/// Renders system-versioned tables as a Markdown section. Their history is
+/// not part of row counts or samples but can be queried as of a past time
+/// until the retention period removes it.
+///
+/// Returns an empty string when there are none.
+fn system_versioning_section(tables: &[&Table]) -> String {
+    let mut rows = String::new();
+    for table in tables {
+        let Some(versioning) = table
+            .options
+            .as_ref()
+            .and_then(|options| options.system_versioning.as_ref())
+        else {
+            continue;
+        };
+        let period = match (&versioning.row_start, &versioning.row_end) {
+            (Some(start), Some(end)) => format!("{} to {}", start, end),
+            _ => "hidden".to_string(),
+        };
+        let _ = writeln!(
+            rows,
+            "| {} | {} | {} | {} |",
+            escape_cell(&qualified_table_name(table)),
+            escape_cell(&period),
+            escape_cell(versioning.history_table.as_deref().unwrap_or("in table")),
+            escape_cell(
+                versioning
+                    .retention
+                    .as_deref()
+                    .unwrap_or("kept until deleted")
+            )
+        );
+    }
+    if rows.is_empty() {
+        return rows;
+    }
+    format!(
+        "## System-Versioned Tables\n\n\
+        | Table | Period | History | Retention |\n\
+        |-------|--------|---------|-----------|\n\
+        {}\n",
+        rows
+    )
+}
+
+/// Renders columns with server-side masking rules as a Markdown section.
+/// Roles subject to masking read the rule's output, so sampled values of
+/// these columns may not be the stored data.
+///
+/// Returns an empty string when there are none.
+fn masked_columns_section(tables: &[&Table]) -> String {
+    let mut rows = String::new();
+    for table in tables {
+        for column in &table.columns {
+            if let Some(masking) = &column.masking {
+                let _ = writeln!(
+                    rows,
+                    "| {}.{} | {} | {} |",
+                    escape_cell(&qualified_table_name(table)),
+                    escape_cell(&column.name),
+                    escape_cell(&masking.provider),
+                    escape_cell(&masking.rule)
+                );
+            }
+        }
+    }
+    if rows.is_empty() {
+        return rows;
+    }
+    format!(
+        "## Masked Columns\n\n\
+        Sampled values of these columns are masked when the collecting role is subject to masking.\n\n\
+        | Column | Provider | Rule |\n\
+        |--------|----------|------|\n\
+        {}\n",
+        rows
+    )
+}
+
+fn indexes_section(indexes: &[&Index]) -> String {
+    let mut section = String::from("## Indexes\n\n");
+    if indexes.is_empty() {
+        section.push_str("No indexes collected.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "| Index | Table | Columns | Unique | Type | Options |\n\
+        |-------|-------|---------|--------|------|---------|\n",
+    );
+    for index in indexes {
+        let table = match &index.schema {
+            Some(schema) => format!("{}.{}", schema, index.table_name),
+            None => index.table_name.clone(),
+        };
+        let columns: Vec<&str> = index.columns.iter().map(|c| c.name.as_str()).collect();
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} | {} |",
+            escape_cell(&index.name),
+            escape_cell(&table),
+            escape_cell(&columns.join(", ")),
+            yes_no(index.is_unique),
+            escape_cell(index.index_type.as_deref().unwrap_or("")),
+            escape_cell(&index_options_label(index.options.as_ref()))
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Declared index options, e.g. `TTL 3600s, sparse, partial: {"active":true}`.
+pub(crate) fn index_options_label(options: Option<&IndexOptions>) -> String {
+    let Some(options) = options else {
+        return String::new();
+    };
+    let mut parts = Vec::new();
+    if let Some(seconds) = options.expire_after_seconds {
+        parts.push(format!("TTL {}s", seconds));
+    }
+    if options.sparse {
+        parts.push("sparse".to_string());
+    }
+    if options.hidden {
+        parts.push("hidden".to_string());
+    }
+    if let Some(filter) = &options.partial_filter {
+        parts.push(format!("partial: {}", filter));
+    }
+    if let Some(collation) = &options.collation {
+        parts.push(format!("collation: {}", collation));
+    }
+    parts.join(", ")
+}
+
+fn relationships_section(tables: &[&Table], inferred: &[InferredRelationship]) -> String {
+    let mut section = String::from("## Relationships\n\n");
+    let relationships: Vec<_> = tables
+        .iter()
+        .flat_map(|t| t.foreign_keys.iter().map(move |fk| (t, fk)))
+        .collect();
+    if relationships.is_empty() {
+        section.push_str("No foreign keys collected.\n\n");
+        section.push_str(&inferred_relationships_section(inferred));
+        return section;
+    }
+
+    section.push_str(
+        "| Table | Columns | References | Cardinality | On Delete | On Update |\n\
+        |-------|---------|------------|-------------|-----------|-----------|\n",
+    );
+    for (table, fk) in relationships {
+        let referenced = match &fk.referenced_schema {
+            Some(schema) => format!("{}.{}", schema, fk.referenced_table),
+            None => fk.referenced_table.clone(),
+        };
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} ({}) | {} | {} | {} |",
+            escape_cell(&qualified_table_name(table)),
+            escape_cell(&fk.columns.join(", ")),
+            escape_cell(&referenced),
+            escape_cell(&fk.referenced_columns.join(", ")),
+            cardinality(table, &fk.columns).label(),
+            referential_action_label(fk.on_delete.as_ref()),
+            referential_action_label(fk.on_update.as_ref())
+        );
+    }
+    section.push('\n');
+    section.push_str(&inferred_relationships_section(inferred));
+    section
+}
+
+/// Renders relationships proposed by inference, kept apart from declared
+/// foreign keys. Returns an empty string when nothing was inferred.
+fn inferred_relationships_section(inferred: &[InferredRelationship]) -> String {
+    if inferred.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from(
+        "### Inferred Relationships\n\n\
+        > Not enforced by the database. Proposed from column names, data types, and\n\
+        > sampled values; verify before relying on them.\n\n\
+        | Table | Column | References | Confidence | Evidence |\n\
+        |-------|--------|------------|------------|----------|\n",
+    );
+    for relationship in inferred {
+        let evidence: Vec<String> = relationship.evidence.iter().map(|e| e.label()).collect();
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} ({}) | {:.0}% | {} |",
+            escape_cell(&qualified_name(
+                relationship.schema.as_deref(),
+                &relationship.table
+            )),
+            escape_cell(&relationship.column),
+            escape_cell(&qualified_name(
+                relationship.referenced_schema.as_deref(),
+                &relationship.referenced_table
+            )),
+            escape_cell(&relationship.referenced_column),
+            relationship.confidence * 100.0,
+            escape_cell(&evidence.join(", "))
+        );
+    }
+    section.push('\n');
+    section
+}
+
+fn samples_section(samples: &[&RedactedTableSample]) -> String {
+    let mut section = String::from("## Samples\n\n");
+    if samples.is_empty() {
+        section.push_str("No samples collected.\n\n");
+        return section;
+    }
+
+    for sample in samples {
+        let name = match &sample.schema_name {
+            Some(schema) => format!("{}.{}", schema, sample.table_name),
+            None => sample.table_name.clone(),
+        };
+        let _ = writeln!(section, "### {}\n", name);
+        if sample.rows.is_empty() {
+            section.push_str("No rows sampled.\n\n");
+            continue;
+        }
+
+        let headers = sample_headers(sample);
+        let escaped: Vec<String> = headers.iter().map(|h| escape_cell(h)).collect();
+        let _ = writeln!(section, "| {} |", escaped.join(" | "));
+        let _ = writeln!(section, "|{}", "---|".repeat(headers.len()));
+        for row in &sample.rows {
+            let cells: Vec<String> = headers
+                .iter()
+                .map(|header| row.get(header).map(sample_value_label).unwrap_or_default())
+                .map(|cell| escape_cell(&cell))
+                .collect();
+            let _ = writeln!(section, "| {} |", cells.join(" | "));
+        }
+        section.push('\n');
+    }
+    section
+}
+
+fn quality_section(metrics: &[&TableQualityMetrics]) -> String {
+    let mut section = String::from("## Data Quality\n\n");
+    if metrics.is_empty() {
+        section.push_str("No quality metrics collected.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "| Table | Rows Analyzed | Completeness | Consistency | Uniqueness | Score | Violations |\n\
+        |-------|---------------|--------------|-------------|------------|-------|------------|\n",
+    );
+    for metric in metrics {
+        let name = match &metric.schema_name {
+            Some(schema) => format!("{}.{}", schema, metric.table_name),
+            None => metric.table_name.clone(),
+        };
+        let _ = writeln!(
+            section,
+            "| {} | {} | {:.1}% | {:.1}% | {:.1}% | {:.1}% | {} |",
+            escape_cell(&name),
+            metric.analyzed_rows,
+            metric.completeness.score * 100.0,
+            metric.consistency.score * 100.0,
+            metric.uniqueness.score * 100.0,
+            metric.quality_score * 100.0,
+            metric.threshold_violations.len()
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// A column that is sensitive by name, protected by its values, or both.
+pub(crate) struct SensitiveColumn<'a> {
+    pub(crate) table: &'a Table,
+    pub(crate) column: &'a str,
+    /// Classification suggested by the column name
+    pub(crate) class: Option<&'static str>,
+    pub(crate) protection: Option<&'a Protection>,
+}
+
+impl SensitiveColumn<'_> {
+    /// Protection label, or a note that the values look like plaintext.
+    pub(crate) fn protection_label(&self) -> String {
+        self.protection
+            .map_or_else(|| "None detected".to_string(), Protection::label)
+    }
+}
+
+/// Columns in scope that are classified or protected, in table order.
+pub(crate) fn sensitive_columns<'a>(scope: &'a ReportScope<'_>) -> Vec<SensitiveColumn<'a>> {
+    scope
+        .tables
+        .iter()
+        .flat_map(|table| table.columns.iter().map(move |column| (*table, column)))
+        .filter_map(|(table, column)| {
+            let class = classify_field(&column.name);
+            let protection = scope
+                .protected
+                .iter()
+                .find(|p| {
+                    p.column == column.name && p.table == table.name && p.schema == table.schema
+                })
+                .map(|p| &p.protection);
+            (class.is_some() || protection.is_some()).then_some(SensitiveColumn {
+                table,
+                column: &column.name,
+                class,
+                protection,
+            })
+        })
+        .collect()
+}
+
+/// Sensitive columns, with whether their values appear hashed or encrypted
+/// so reviewers can tell protected secrets from plaintext.
+fn classification_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("## Data Classification\n\n");
+    let columns = sensitive_columns(scope);
+    if columns.is_empty() {
+        section.push_str("No sensitive columns detected.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "| Table | Column | Classification | Protection |\n\
+        |-------|--------|----------------|------------|\n",
+    );
+    for column in columns {
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} |",
+            escape_cell(&qualified_table_name(column.table)),
+            escape_cell(column.column),
+            column.class.unwrap_or("-"),
+            escape_cell(&column.protection_label())
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Lists tables storing file content with the estimated volume of each.
+fn large_objects_section(inventory: &[LobTable]) -> String {
+    let mut section = String::from("## Large Objects\n\n");
+    if inventory.is_empty() {
+        section.push_str("No columns appear to store files.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "Columns that appear to store files or other large objects. Volume is \
+        estimated from sampled value sizes; volumes marked \"at most\" are the \
+        total table size because no values were sampled.\n\n\
+        | Table | Columns | Content Type | Rows | Estimated Volume |\n\
+        |-------|---------|--------------|------|------------------|\n",
+    );
+    for table in inventory {
+        let columns: Vec<String> = table
+            .columns
+            .iter()
+            .map(|column| {
+                let signals: Vec<String> = column.signals.iter().map(|s| s.label()).collect();
+                format!("{} ({})", column.column, signals.join(", "))
+            })
+            .collect();
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} |",
+            escape_cell(&qualified_name(table.schema.as_deref(), &table.table)),
+            escape_cell(&columns.join("; ")),
+            escape_cell(&table.content_type_columns.join(", ")),
+            table
+                .row_count
+                .map_or_else(|| "-".to_string(), |rows| rows.to_string()),
+            table
+                .volume
+                .as_ref()
+                .map_or_else(|| "-".to_string(), volume_label)
+        );
+    }
+    let total: u64 = inventory
+        .iter()
+        .filter_map(|table| table.volume.as_ref())
+        .map(VolumeEstimate::bytes)
+        .sum();
+    let _ = writeln!(
+        section,
+        "\n**Estimated total**: {} in {} tables\n",
+        format_bytes(total as f64),
+        inventory.len()
+    );
+    section
+}
+
+/// Estimated volume, marking table sizes as an upper bound.
+pub(crate) fn volume_label(volume: &VolumeEstimate) -> String {
+    match volume {
+        VolumeEstimate::Sampled { bytes } => format_bytes(*bytes as f64),
+        VolumeEstimate::TableSize { bytes } => format!("at most {}", format_bytes(*bytes as f64)),
+    }
+}
+
+/// Lists de-facto enums, with values masked wherever the samples are.
+fn enums_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from(
+        "## Inferred Enums
+
+",
+    );
+    if scope.enums.is_empty() {
+        section.push_str(
+            "No sampled column has a small closed set of values.
+
+",
+        );
+        return section;
+    }
+
+    section.push_str(
+        "Text and integer columns whose sampled values form a small closed set \
+        although no enum type is declared. Unless the set is complete, the \
+        sample did not cover every row and rare values may be missing.\n\n\
+        | Table | Column | Values | Sampled | Complete |\n\
+        |-------|--------|--------|---------|----------|\n",
+    );
+    for inferred in &scope.enums {
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} |",
+            escape_cell(&qualified_name(inferred.schema.as_deref(), &inferred.table)),
+            escape_cell(&inferred.column),
+            escape_cell(&enum_values_label(scope, inferred)),
+            inferred.sampled,
+            yes_no(inferred.complete)
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Whether the values of an inferred enum may be shown: only when the
+/// samples in scope show the column unredacted.
+pub(crate) fn enum_values_shown(scope: &ReportScope<'_>, inferred: &InferredEnum) -> bool {
+    scope
+        .samples
+        .iter()
+        .filter(|s| s.table_name == inferred.table && s.schema_name == inferred.schema)
+        .any(|sample| {
+            !sample.rows.is_empty()
+                && sample
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(&inferred.column))
+                    .all(|value| value.as_str() != Some(REDACTED_VALUE))
+        })
+}
+
+/// Values of an inferred enum, or their count when they are masked.
+pub(crate) fn enum_values_label(scope: &ReportScope<'_>, inferred: &InferredEnum) -> String {
+    if !enum_values_shown(scope, inferred) {
+        return format!("{} ({} values)", REDACTED_VALUE, inferred.values.len());
+    }
+    inferred
+        .values
+        .iter()
+        .map(sample_value_label)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Lists columns whose collation or character set differs from the database default.
+fn collation_section(mismatches: &[CollationMismatch]) -> String {
+    let mut section = String::from("## Collations\n\n");
+    if mismatches.is_empty() {
+        section.push_str("All columns use the database collation and character set.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "Comparing or joining these columns with others can fail or match unexpectedly.\n\n\
+        | Table | Column | Collation | Character Set |\n\
+        |-------|--------|-----------|---------------|\n",
+    );
+    for mismatch in mismatches {
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} |",
+            escape_cell(&mismatch.table),
+            escape_cell(&mismatch.column),
+            escape_cell(mismatch.collation.as_deref().unwrap_or_default()),
+            escape_cell(mismatch.character_set.as_deref().unwrap_or_default())
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Lists table and column names with abbreviations that have no expansion.
+fn naming_section(tables: &[&Table], names: Option<&NamingDictionary>) -> String {
+    let mut section = String::from("## Naming\n\n");
+    let empty = NamingDictionary::default();
+    let unmatched = unmatched_names(tables, names.unwrap_or(&empty));
+    if unmatched.is_empty() {
+        section.push_str("No unexpanded abbreviations found.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "Add expansions for these abbreviations with `--naming-dictionary`.\n\n\
+        | Name | Abbreviations |\n\
+        |------|---------------|\n",
+    );
+    for (name, words) in unmatched {
+        let _ = writeln!(
+            section,
+            "| {} | {} |",
+            escape_cell(&name),
+            escape_cell(&words.join(", "))
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Renders comment coverage per schema and the tables missing comments.
+fn documentation_section(scope: &ReportScope<'_>) -> String {
+    let mut section = String::from("## Documentation\n\n");
+    let coverage = comment_coverage(scope.tables.iter().copied());
+    if coverage.is_empty() {
+        section.push_str("No tables collected.\n\n");
+        return section;
+    }
+
+    section.push_str(
+        "| Schema | Tables Documented | Columns Documented |\n\
+        |--------|-------------------|--------------------|\n",
+    );
+    for schema in &coverage {
+        let _ = writeln!(
+            section,
+            "| {} | {}/{} ({:.0}%) | {}/{} ({:.0}%) |",
+            escape_cell(schema.schema.as_deref().unwrap_or("-")),
+            schema.documented_tables,
+            schema.tables,
+            schema.table_percent(),
+            schema.documented_columns,
+            schema.columns,
+            schema.column_percent()
+        );
+    }
+    section.push('\n');
+
+    if scope.undocumented.is_empty() {
+        section.push_str("All tables and columns have comments.\n\n");
+        return section;
+    }
+    section.push_str(
+        "Undocumented objects, largest and most referenced tables first:\n\n\
+        | Table | Rows | References | Table Comment | Undocumented Columns |\n\
+        |-------|------|------------|---------------|----------------------|\n",
+    );
+    for table in &scope.undocumented {
+        let _ = writeln!(
+            section,
+            "| {} | {} | {} | {} | {} |",
+            escape_cell(&qualified_name(table.schema.as_deref(), &table.table)),
+            table.row_count.map(|c| c.to_string()).unwrap_or_default(),
+            table.references,
+            if table.missing_table_comment {
+                "missing"
+            } else {
+                "present"
+            },
+            escape_cell(&table.undocumented_columns.join(", "))
+        );
+    }
+    section.push('\n');
+    section
+}
+
+/// Qualified table and column names paired with their unexpanded abbreviations.
+pub(crate) fn unmatched_names(
+    tables: &[&Table],
+    names: &NamingDictionary,
+) -> Vec<(String, Vec<String>)> {
+    let mut unmatched = Vec::new();
+    for table in tables {
+        let words = names.unmatched(&table.name);
+        if !words.is_empty() {
+            unmatched.push((qualified_table_name(table), words));
+        }
+        for column in &table.columns {
+            let words = names.unmatched(&column.name);
+            if !words.is_empty() {
+                unmatched.push((
+                    format!("{}.{}", qualified_table_name(table), column.name),
+                    words,
+                ));
+            }
+        }
+    }
+    unmatched
+}
+
+/// Longest value shown in a sample preview cell, in characters.
+const PREVIEW_MAX_CHARS: usize = 40;
+
+/// The first rows of a redacted sample, ready to embed next to its table.
+pub(crate) struct SamplePreview {
+    pub(crate) headers: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+}
+
+/// Finds the sample collected for `table`.
+pub(crate) fn table_sample<'a>(
+    samples: &[&'a RedactedTableSample],
+    table: &Table,
+) -> Option<&'a RedactedTableSample> {
+    samples
+        .iter()
+        .find(|s| s.table_name == table.name && s.schema_name == table.schema)
+        .copied()
+}
+
+/// Builds a preview of the first `rows` rows of a sample, or `None` if no
+/// rows were sampled.
+///
+/// Columns follow the table's column order, with sampled fields the table
+/// does not declare last, by name. Columns classified as credentials or
+/// personal data are masked whatever the redaction mode and value type, and
+/// long values are truncated.
+pub(crate) fn sample_preview(
+    sample: &RedactedTableSample,
+    table: &Table,
+    rows: usize,
+) -> Option<SamplePreview> {
+    if sample.rows.is_empty() || rows == 0 {
+        return None;
+    }
+    let mut headers = sample_headers(sample);
+    headers.sort_by_key(|header| {
+        (
+            table
+                .columns
+                .iter()
+                .position(|column| column.name == *header)
+                .unwrap_or(usize::MAX),
+            *header,
+        )
+    });
+    let rows = sample
+        .rows
+        .iter()
+        .take(rows)
+        .map(|row| {
+            headers
+                .iter()
+                .map(|header| {
+                    if classify_field(header).is_some() {
+                        return REDACTED_VALUE.to_string();
+                    }
+                    let value = row.get(header).map(sample_value_label).unwrap_or_default();
+                    truncate_preview(value)
+                })
+                .collect()
+        })
+        .collect();
+    Some(SamplePreview {
+        headers: headers.into_iter().map(str::to_string).collect(),
+        rows,
+    })
+}
+
+fn truncate_preview(value: String) -> String {
+    match value.char_indices().nth(PREVIEW_MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value,
+    }
+}
+
+/// Field names across all rows of a sample, in order of first appearance.
+pub(crate) fn sample_headers(sample: &RedactedTableSample) -> Vec<&str> {
+    let mut headers: Vec<&str> = Vec::new();
+    for row in &sample.rows {
+        if let Value::Object(map) = row {
+            for key in map.keys() {
+                if !headers.contains(&key.as_str()) {
+                    headers.push(key);
+                }
+            }
+        }
+    }
+    headers
+}
+
+pub(crate) fn qualified_table_name(table: &Table) -> String {
+    qualified_name(table.schema.as_deref(), &table.name)
+}
+
+pub(crate) fn qualified_name(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+/// Prefers the type as declared in the database over the unified mapping.
+pub(crate) fn column_type_label(column: &Column) -> String {
+    column
+        .declared_type
+        .clone()
+        .unwrap_or_else(|| data_type_label(&column.data_type))
+}
+
+pub(crate) fn data_type_label(data_type: &UnifiedDataType) -> String {
+    match data_type {
+        UnifiedDataType::String {
+            max_length: Some(length),
+        } => format!("string({})", length),
+        UnifiedDataType::String { max_length: None } => "string".to_string(),
+        UnifiedDataType::Integer { bits, signed } => {
+            format!("{}int{}", if *signed { "" } else { "u" }, bits)
+        }
+        UnifiedDataType::Float { .. } => "float".to_string(),
+        UnifiedDataType::Decimal { precision, scale } => match (precision, scale) {
+            (Some(precision), Some(scale)) => format!("decimal({},{})", precision, scale),
+            (Some(precision), None) => format!("decimal({})", precision),
+            _ => "decimal".to_string(),
+        },
+        UnifiedDataType::Boolean => "boolean".to_string(),
+        UnifiedDataType::DateTime { with_timezone } => if *with_timezone {
+            "timestamptz"
+        } else {
+            "timestamp"
+        }
+        .to_string(),
+        UnifiedDataType::Date => "date".to_string(),
+        UnifiedDataType::Time { .. } => "time".to_string(),
+        UnifiedDataType::Interval => "interval".to_string(),
+        UnifiedDataType::Binary { .. } => "binary".to_string(),
+        UnifiedDataType::Bit {
+            length: Some(length),
+        } => format!("bit({})", length),
+        UnifiedDataType::Bit { length: None } => "bit".to_string(),
+        UnifiedDataType::Json => "json".to_string(),
+        UnifiedDataType::Uuid => "uuid".to_string(),
+        UnifiedDataType::NetworkAddress => "network_address".to_string(),
+        UnifiedDataType::Array { element_type } => {
+            format!("{}[]", data_type_label(element_type))
+        }
+        UnifiedDataType::Custom { type_name } => type_name.clone(),
+    }
+}
+
+pub(crate) fn referential_action_label(action: Option<&ReferentialAction>) -> &'static str {
+    match action {
+        Some(ReferentialAction::Cascade) => "CASCADE",
+        Some(ReferentialAction::SetNull) => "SET NULL",
+        Some(ReferentialAction::SetDefault) => "SET DEFAULT",
+        Some(ReferentialAction::Restrict) => "RESTRICT",
+        Some(ReferentialAction::NoAction) | None => "NO ACTION",
+    }
+}
+
+pub(crate) fn sample_value_label(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "NULL".to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub(crate) const fn yes_no(value: bool) -> &'static str {
+    if value { "Yes" } else { "No" }
+}
+
+/// Escapes characters that would break a Markdown table cell.
+pub(crate) fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RedactionMode;
+    use dbsurveyor_core::models::{
+        ColumnMasking, DatabaseInfo, ForeignKey, SystemVersioning, TableOptions,
+        TimeSeriesCollection,
+    };
+
+    fn column(name: &str) -> Column {
+        Column::new(
+            name.to_string(),
+            UnifiedDataType::String { max_length: None },
+        )
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("app".to_string()));
+        schema.tables.push(Table {
+            foreign_keys: vec![ForeignKey {
+                name: None,
+                columns: vec!["customer_id".to_string()],
+                referenced_table: "customers".to_string(),
+                referenced_schema: Some("public".to_string()),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: Some(ReferentialAction::Cascade),
+                on_update: None,
+            }],
+            ..Table::new(
+                "orders".to_string(),
+                Some("public".to_string()),
+                vec![column("id"), column("customer_email")],
+            )
+        });
+        schema
+    }
+
+    #[test]
+    fn test_all_sections_rendered() {
+        let report = render_report(&fixture(), &ReportOptions::default());
+        for heading in [
+            "## Tables",
+            "## Indexes",
+            "## Relationships",
+            "## Samples",
+            "## Data Quality",
+            "## Data Classification",
+        ] {
+            assert!(report.contains(heading), "missing {}", heading);
+        }
+        assert!(
+            report
+                .contains("| public.orders | customer_id | public.customers (id) | N:1 | CASCADE")
+        );
+        assert!(report.contains("| public.orders | customer_email | PII |"));
+    }
+
+    #[test]
+    fn test_classification_shows_protection() {
+        let mut schema = fixture();
+        let mut token = column("lookup_digest");
+        token.default_value =
+            Some("encode(digest(gen_random_bytes(16), 'sha256'), 'hex')".to_string());
+        schema.tables[0].columns.push(token);
+
+        let report = render_report(
+            &schema,
+            &ReportOptions {
+                sections: &[ReportSection::Classification],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("| Table | Column | Classification | Protection |"));
+        assert!(report.contains("| public.orders | customer_email | PII | None detected |"));
+        assert!(report.contains("| public.orders | lookup_digest | - | SHA256 digest |"));
+    }
+
+    #[test]
+    fn test_report_branding() {
+        let report = render_report(
+            &fixture(),
+            &ReportOptions {
+                title: Some("Acme Data Catalog"),
+                footer: Some("Internal use only"),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.starts_with("# Acme Data Catalog: "));
+        assert!(report.ends_with("\n---\n\nInternal use only\n"));
+
+        let report = render_report(&fixture(), &ReportOptions::default());
+        assert!(report.starts_with("# Database Schema: "));
+        assert!(!report.contains("\n---\n"));
+    }
+
+    #[test]
+    fn test_only_selected_sections_rendered() {
+        let report = render_report(
+            &fixture(),
+            &ReportOptions {
+                sections: &[ReportSection::Relationships],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("## Summary"));
+        assert!(report.contains("## Relationships"));
+        assert!(!report.contains("## Tables"));
+        assert!(!report.contains("## Data Classification"));
+    }
+
+    #[test]
+    fn test_inferred_relationships_listed_separately() {
+        let mut schema = fixture();
+        let mut customers = schema.tables[0].clone();
+        customers.name = "customers".to_string();
+        customers.foreign_keys.clear();
+        schema.tables[0].columns.push(column("store_id"));
+        customers.columns = vec![column("id")];
+        let mut stores = customers.clone();
+        stores.name = "stores".to_string();
+        schema.tables.extend([customers, stores]);
+
+        let report = render_report(
+            &schema,
+            &ReportOptions {
+                sections: &[ReportSection::Relationships],
+                ..ReportOptions::default()
+            },
+        );
+        let (declared, inferred) = report
+            .split_once("### Inferred Relationships")
+            .expect("inferred subsection rendered");
+        assert!(declared.contains("| public.orders | customer_id | public.customers (id)"));
+        assert!(inferred.contains("| public.orders | store_id | public.stores (id) | 70% |"));
+        assert!(!inferred.contains("customer_id"));
+
+        // Table pages take their share of the database scope's inference
+        let options = ReportOptions::default();
+        let database = ReportScope::database(&schema, &options);
+        assert_eq!(database.table(&schema, &schema.tables[0]).inferred.len(), 1);
+        assert!(
+            database
+                .table(&schema, &schema.tables[2])
+                .inferred
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_access_probe_rendered_in_summary() {
+        let mut schema = fixture();
+        assert!(!render_report(&schema, &ReportOptions::default()).contains("**Access**"));
+
+        schema.database_info =
+            schema
+                .database_info
+                .with_access_probe(Ok(dbsurveyor_core::AccessProbe {
+                    superuser: false,
+                    catalog_access: false,
+                    readable_tables: 1,
+                    total_tables: 3,
+                    identity: None,
+                    evidence: Vec::new(),
+                }));
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains(
+            "- **Access**: Limited (rows readable in 1 of 3 tables and views; catalogs restricted)\n"
+        ));
+    }
+
+    #[test]
+    fn test_native_terms_shown_alongside() {
+        let mut schema = fixture();
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("## Tables\n"));
+        assert!(!report.contains("## Terminology"));
+
+        schema.database_info.engine = Some(DatabaseType::MongoDB);
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("- **Engine**: MongoDB\n- **Tables (collections)**: 1\n"));
+        assert!(report.contains("## Tables (collections)\n"));
+        assert!(report.contains("## Terminology\n"));
+        assert!(report.contains("| column | field |"));
+    }
+
+    #[test]
+    fn test_collation_mismatches_rendered() {
+        let mut schema = fixture();
+        schema.database_info.encoding = Some("utf8mb4".to_string());
+        schema.database_info.collation = Some("utf8mb4_0900_ai_ci".to_string());
+        let column = &mut schema.tables[0].columns[1];
+        column.collation = Some("latin1_swedish_ci".to_string());
+        column.character_set = Some("latin1".to_string());
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("- **Encoding**: utf8mb4\n"));
+        assert!(report.contains("- **Collation**: utf8mb4_0900_ai_ci\n"));
+        assert!(report.contains("- **Columns with non-default collation**: 1\n"));
+        assert!(report.contains("## Collations"));
+        assert!(report.contains("| public.orders | customer_email | latin1_swedish_ci | latin1 |"));
+    }
+
+    fn orders_sample() -> RedactedTableSample {
+        RedactedTableSample {
+            table_name: "orders".to_string(),
+            schema_name: Some("public".to_string()),
+            rows: (1..=4)
+                .map(|id| {
+                    serde_json::json!({
+                        "id": id,
+                        "customer_email": "ada@example.com",
+                        "note": "x".repeat(60),
+                    })
+                })
+                .collect(),
+            mode_applied: RedactionMode::None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sample_preview_masks_and_truncates() {
+        let sample = orders_sample();
+        let schema = fixture();
+        let orders = &schema.tables[0];
+        let preview = sample_preview(&sample, orders, 2).expect("sampled rows");
+        assert_eq!(preview.headers, vec!["id", "customer_email", "note"]);
+        assert_eq!(preview.rows.len(), 2);
+        assert_eq!(preview.rows[0][0], "1");
+        assert_eq!(preview.rows[0][1], REDACTED_VALUE);
+        assert_eq!(preview.rows[0][2], format!("{}...", "x".repeat(40)));
+        assert!(sample_preview(&sample, orders, 0).is_none());
+    }
+
+    #[test]
+    fn test_sample_preview_only_when_enabled() {
+        let samples = [orders_sample()];
+        let options = ReportOptions {
+            samples: Some(&samples),
+            sections: &[ReportSection::Tables],
+            ..ReportOptions::default()
+        };
+        let report = render_report(&fixture(), &options);
+        assert!(!report.contains("Sample preview"));
+
+        let report = render_report(
+            &fixture(),
+            &ReportOptions {
+                sample_preview: Some(3),
+                ..options
+            },
+        );
+        assert!(report.contains("Sample preview (first 3 rows, masked):"));
+        assert!(report.contains("| 3 | [REDACTED] |"));
+        assert!(!report.contains("| 4 | [REDACTED] |"));
+        assert!(!report.contains("ada@example.com"));
+    }
+
+    #[test]
+    fn test_documentation_coverage_rendered() {
+        let mut schema = fixture();
+        schema.tables[0].columns[0].comment = Some("Order id".to_string());
+        schema.tables[0].row_count = Some(42);
+
+        let report = render_report(
+            &schema,
+            &ReportOptions {
+                sections: &[ReportSection::Documentation],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("## Documentation"));
+        assert!(report.contains("| public | 0/1 (0%) | 1/2 (50%) |"));
+        assert!(report.contains("| public.orders | 42 | 1 | missing | customer_email |"));
+    }
+
+    #[test]
+    fn test_large_objects_rendered() {
+        let options = ReportOptions {
+            sections: &[ReportSection::LargeObjects],
+            ..ReportOptions::default()
+        };
+        let report = render_report(&fixture(), &options);
+        assert!(report.contains("No columns appear to store files."));
+
+        let mut schema = fixture();
+        let mut scan = column("scan");
+        scan.data_type = UnifiedDataType::Binary { max_length: None };
+        schema.tables[0]
+            .columns
+            .extend([scan, column("content_type")]);
+        schema.tables[0].size_bytes = Some(3 * 1024 * 1024);
+
+        let report = render_report(&schema, &options);
+        assert!(report.contains("## Large Objects"));
+        assert!(report.contains(
+            "| public.orders | scan (large binary type, file-like name) | content_type | - | at most 3.0 MiB |"
+        ));
+        assert!(report.contains("**Estimated total**: 3.0 MiB in 1 tables"));
+    }
+
+    #[test]
+    fn test_inferred_enums_rendered() {
+        let mut schema = fixture();
+        schema.tables[0].columns.push(column("status"));
+        let rows = (0..30)
+            .map(|i| {
+                serde_json::json!({
+                    "status": (["open", "paid", "void"][i % 3]),
+                    "customer_email": (["a@example.com", "b@example.com"][i % 2]),
+                })
+            })
+            .collect();
+        schema.samples = Some(vec![dbsurveyor_core::models::TableSample {
+            table_name: "orders".to_string(),
+            schema_name: Some("public".to_string()),
+            rows,
+            sample_size: 30,
+            total_rows: Some(30),
+            sampling_strategy: dbsurveyor_core::models::SamplingStrategy::Random { limit: 30 },
+            collected_at: chrono::Utc::now(),
+            warnings: Vec::new(),
+            sample_status: None,
+            column_types: Vec::new(),
+        }]);
+        let samples = crate::redaction::Redactor::new(RedactionMode::Balanced)
+            .redact(schema.samples.as_deref().unwrap_or_default());
+
+        let report = render_report(
+            &schema,
+            &ReportOptions {
+                samples: Some(&samples),
+                sections: &[ReportSection::Enums],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("## Inferred Enums"));
+        assert!(report.contains("| public.orders | status | open, paid, void | 30 | Yes |"));
+        // Values stay masked where the samples are
+        assert!(
+            report
+                .contains("| public.orders | customer_email | [REDACTED] (2 values) | 30 | Yes |")
+        );
+        assert!(!report.contains("a@example.com"));
+
+        let report = render_report(&fixture(), &ReportOptions::default());
+        assert!(report.contains("No sampled column has a small closed set of values."));
+    }
+
+    #[test]
+    fn test_inheritance_hierarchy_rendered() {
+        let mut schema = fixture();
+        let related = |name: &str| RelatedTable {
+            schema: Some("public".to_string()),
+            name: name.to_string(),
+        };
+        schema.tables[0].inheritance = Some(TableInheritance {
+            parents: vec![related("orders_all")],
+            children: vec![related("orders_2024_q1"), related("orders_2024_q2")],
+            is_partition: true,
+            inherited_columns: vec!["id".to_string(), "customer_email".to_string()],
+        });
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("Partition of: public.orders_all\n"));
+        assert!(report.contains("Children: public.orders_2024_q1, public.orders_2024_q2\n"));
+        assert!(report.contains("Inherited columns: id, customer_email\n"));
+    }
+
+    #[test]
+    fn test_naming_dictionary_display_names() {
+        let mut schema = fixture();
+        schema.tables[0].columns.push(column("cust_nbr"));
+        let names = NamingDictionary::parse("cust = customer").expect("valid dictionary");
+
+        let report = render_report(
+            &schema,
+            &ReportOptions {
+                sections: &[ReportSection::Tables, ReportSection::Naming],
+                names: Some(&names),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("| Column | Display Name | Type |"));
+        assert!(report.contains("| cust_nbr | Customer Nbr | string |"));
+        assert!(report.contains("| id |  | string |"));
+        assert!(report.contains("| public.orders.cust_nbr | nbr |"));
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("| Column | Type |"));
+    }
+
+    #[test]
+    fn test_index_options_rendered() {
+        let mut schema = fixture();
+        schema.indexes.push(Index {
+            name: "session_expiry".to_string(),
+            table_name: "orders".to_string(),
+            schema: Some("public".to_string()),
+            columns: Vec::new(),
+            is_unique: false,
+            is_primary: false,
+            index_type: Some("btree".to_string()),
+            options: Some(IndexOptions {
+                expire_after_seconds: Some(3600),
+                partial_filter: Some(r#"{"active":true}"#.to_string()),
+                sparse: true,
+                ..IndexOptions::default()
+            }),
+        });
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains(
+            r#"| session_expiry | public.orders |  | No | btree | TTL 3600s, sparse, partial: {"active":true} |"#
+        ));
+    }
+
+    #[test]
+    fn test_special_collections_rendered() {
+        let mut schema = fixture();
+        schema.tables[0].options = Some(TableOptions {
+            time_series: Some(TimeSeriesCollection {
+                time_field: "ts".to_string(),
+                meta_field: Some("sensor".to_string()),
+                granularity: Some("minutes".to_string()),
+                expire_after_seconds: None,
+            }),
+            ..TableOptions::default()
+        });
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("## Special Collections"));
+        assert!(report.contains(
+            "| public.orders | time-series | time field: ts, meta field: sensor, granularity: minutes |"
+        ));
+
+        let report = render_report(&fixture(), &ReportOptions::default());
+        assert!(!report.contains("## Special Collections"));
+    }
+
+    #[test]
+    fn test_system_versioned_tables_rendered() {
+        let mut schema = fixture();
+        schema.tables[0].options = Some(TableOptions {
+            system_versioning: Some(SystemVersioning {
+                row_start: Some("valid_from".to_string()),
+                row_end: Some("valid_to".to_string()),
+                ..SystemVersioning::default()
+            }),
+            ..TableOptions::default()
+        });
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("## System-Versioned Tables"));
+        assert!(report.contains(
+            "| public.orders | valid_from to valid_to | in table | kept until deleted |"
+        ));
+
+        let report = render_report(&fixture(), &ReportOptions::default());
+        assert!(!report.contains("## System-Versioned Tables"));
+    }
+
+    #[test]
+    fn test_masked_columns_rendered() {
+        let mut schema = fixture();
+        schema.tables[0].columns[1].masking = Some(ColumnMasking {
+            provider: "anon".to_string(),
+            rule: "MASKED WITH FUNCTION anon.fake_email()".to_string(),
+        });
+
+        let report = render_report(&schema, &ReportOptions::default());
+        assert!(report.contains("## Masked Columns"));
+        assert!(report.contains(
+            "| public.orders.customer_email | anon | MASKED WITH FUNCTION anon.fake_email() |"
+        ));
+
+        let report = render_report(&fixture(), &ReportOptions::default());
+        assert!(!report.contains("## Masked Columns"));
+    }
+}
@@ -0,0 +1,335 @@
+//! Multi-file Markdown output for static documentation sites.
+//!
+//! Writes one page per schema and per table under `docs/`, plus navigation
+//! for MkDocs (`mkdocs.yml`) and Docusaurus (`sidebars.json`):
+//!
+//! ```text
+//! <output>/
+//!   mkdocs.yml
+//!   sidebars.json
+//!   docs/
+//!     index.md
+//!     <schema>/index.md
+//!     <schema>/<table>.md
+//! ```
+
+use super::{
+    ReportOptions, ReportScope, escape_cell, qualified_table_name, render_sections, report_footer,
+    report_title, summary_section,
+};
+use crate::glossary::terminology_section;
+use dbsurveyor_core::models::{DatabaseSchema, Table};
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Directory name used for tables that have no schema
+const DEFAULT_SCHEMA: &str = "default";
+
+/// A rendered file, relative to the site root.
+pub(crate) struct SiteFile {
+    pub(crate) path: PathBuf,
+    pub(crate) content: String,
+}
+
+/// Tables of one schema with the page slugs assigned to them.
+pub(crate) struct SchemaPages<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) slug: String,
+    pub(crate) tables: Vec<(&'a Table, String)>,
+}
+
+/// Renders the documentation site for a database.
+pub(crate) fn render_site(schema: &DatabaseSchema, options: &ReportOptions<'_>) -> Vec<SiteFile> {
+    let groups = group_tables(schema);
+    let mut files = vec![SiteFile {
+        path: PathBuf::from("docs/index.md"),
+        content: index_page(schema, &groups, options),
+    }];
+
+    let database = ReportScope::database(schema, options);
+    for group in &groups {
+        files.push(SiteFile {
+            path: PathBuf::from(format!("docs/{}/index.md", group.slug)),
+            content: schema_page(group),
+        });
+        for (table, slug) in &group.tables {
+            let mut content = front_matter(&qualified_table_name(table), &table.name);
+            let _ = writeln!(content, "# {}\n", qualified_table_name(table));
+            content.push_str(&render_sections(
+                &database.table(schema, table),
+                options.sections,
+            ));
+            files.push(SiteFile {
+                path: PathBuf::from(format!("docs/{}/{}.md", group.slug, slug)),
+                content,
+            });
+        }
+    }
+
+    files.push(SiteFile {
+        path: PathBuf::from("mkdocs.yml"),
+        content: mkdocs_config(schema, &groups, options),
+    });
+    files.push(SiteFile {
+        path: PathBuf::from("sidebars.json"),
+        content: docusaurus_sidebar(&groups),
+    });
+    files
+}
+
+/// Groups tables by schema in order of first appearance and assigns each
+/// a unique file name.
+pub(crate) fn group_tables(schema: &DatabaseSchema) -> Vec<SchemaPages<'_>> {
+    let mut groups: Vec<SchemaPages<'_>> = Vec::new();
+    let mut schema_slugs = HashSet::new();
+    let mut table_slugs: Vec<HashSet<String>> = Vec::new();
+
+    for table in &schema.tables {
+        let name = table.schema.as_deref().unwrap_or(DEFAULT_SCHEMA);
+        let position = if let Some(position) = groups.iter().position(|g| g.name == name) {
+            position
+        } else {
+            groups.push(SchemaPages {
+                name,
+                slug: unique_slug(name, &mut schema_slugs),
+                tables: Vec::new(),
+            });
+            // `index` is the schema overview page
+            table_slugs.push(HashSet::from(["index".to_string()]));
+            groups.len().saturating_sub(1)
+        };
+        let slug = unique_slug(&table.name, &mut table_slugs[position]);
+        groups[position].tables.push((table, slug));
+    }
+
+    groups
+}
+
+/// Makes a file-name-safe slug, suffixed with a counter if already taken.
+///
+/// Uniqueness is checked case-insensitively so pages do not collide on
+/// case-insensitive filesystems.
+fn unique_slug(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut base: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if base.is_empty() {
+        base.push('_');
+    }
+
+    let mut slug = base.clone();
+    let mut counter: u32 = 1;
+    while !taken.insert(slug.to_ascii_lowercase()) {
+        counter = counter.saturating_add(1);
+        slug = format!("{}-{}", base, counter);
+    }
+    slug
+}
+
+fn index_page(
+    schema: &DatabaseSchema,
+    groups: &[SchemaPages<'_>],
+    options: &ReportOptions<'_>,
+) -> String {
+    let title = report_title(schema, options);
+    let mut content = front_matter(&title, "Overview");
+    let _ = writeln!(
+        content,
+        "# {}\n\n\
+        Generated by DBSurveyor v{}\n\
+        Collection Date: {}\n",
+        title,
+        schema.collection_metadata.collector_version,
+        schema
+            .collection_metadata
+            .collected_at
+            .format("%Y-%m-%d %H:%M:%S UTC"),
+    );
+    content.push_str(&summary_section(schema));
+
+    content.push_str("## Schemas\n\n");
+    if groups.is_empty() {
+        content.push_str("No tables collected.\n");
+    }
+    for group in groups {
+        let _ = writeln!(
+            content,
+            "- [{}]({}/index.md) ({} tables)",
+            group.name,
+            group.slug,
+            group.tables.len()
+        );
+    }
+    content.push('\n');
+    content.push_str(&terminology_section(schema.database_info.engine.as_ref()));
+    content.push_str(&report_footer(options));
+    content
+}
+
+fn schema_page(group: &SchemaPages<'_>) -> String {
+    let mut content = front_matter(&format!("Schema: {}", group.name), group.name);
+    let _ = writeln!(content, "# Schema: {}\n", group.name);
+    content.push_str(
+        "| Table | Columns | Rows |\n\
+        |-------|---------|------|\n",
+    );
+    for (table, slug) in &group.tables {
+        let _ = writeln!(
+            content,
+            "| [{}]({}.md) | {} | {} |",
+            escape_cell(&table.name),
+            slug,
+            table.columns.len(),
+            table.row_count.map(|c| c.to_string()).unwrap_or_default()
+        );
+    }
+    content
+}
+
+/// YAML front matter read by Docusaurus (title, sidebar label) and MkDocs
+/// (page meta-data).
+fn front_matter(title: &str, sidebar_label: &str) -> String {
+    format!(
+        "---\ntitle: {}\nsidebar_label: {}\n---\n\n",
+        yaml_string(title),
+        yaml_string(sidebar_label)
+    )
+}
+
+fn mkdocs_config(
+    schema: &DatabaseSchema,
+    groups: &[SchemaPages<'_>],
+    options: &ReportOptions<'_>,
+) -> String {
+    let mut config = format!(
+        "site_name: {}\ndocs_dir: docs\nnav:\n  - Overview: index.md\n",
+        yaml_string(&report_title(schema, options))
+    );
+    for group in groups {
+        let _ = writeln!(config, "  - {}:", yaml_string(group.name));
+        let _ = writeln!(config, "      - Overview: {}/index.md", group.slug);
+        for (table, slug) in &group.tables {
+            let _ = writeln!(
+                config,
+                "      - {}: {}/{}.md",
+                yaml_string(&table.name),
+                group.slug,
+                slug
+            );
+        }
+    }
+    config
+}
+
+fn docusaurus_sidebar(groups: &[SchemaPages<'_>]) -> String {
+    let mut items = vec![json!("index")];
+    for group in groups {
+        let pages: Vec<String> = group
+            .tables
+            .iter()
+            .map(|(_, slug)| format!("{}/{}", group.slug, slug))
+            .collect();
+        items.push(json!({
+            "type": "category",
+            "label": group.name,
+            "link": { "type": "doc", "id": format!("{}/index", group.slug) },
+            "items": pages,
+        }));
+    }
+
+    let sidebar = json!({ "dbsurveyor": items });
+    let mut content = serde_json::to_string_pretty(&sidebar).unwrap_or_default();
+    content.push('\n');
+    content
+}
+
+/// Quotes a string as a YAML scalar.
+///
+/// JSON string syntax is a subset of YAML double-quoted scalars.
+fn yaml_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::DatabaseInfo;
+
+    fn table(schema: Option<&str>, name: &str) -> Table {
+        Table {
+            row_count: Some(3),
+            ..Table::new(name.to_string(), schema.map(str::to_string), Vec::new())
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("app".to_string()));
+        schema.tables = vec![
+            table(Some("public"), "orders"),
+            table(Some("audit"), "events"),
+            table(Some("public"), "Orders"),
+            table(Some("public"), "index"),
+            table(None, "order items"),
+        ];
+        schema
+    }
+
+    #[test]
+    fn test_site_layout() {
+        let files = render_site(&fixture(), &ReportOptions::default());
+        let paths: Vec<String> = files.iter().map(|f| f.path.display().to_string()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "docs/index.md",
+                "docs/public/index.md",
+                "docs/public/orders.md",
+                "docs/public/Orders-2.md",
+                "docs/public/index-2.md",
+                "docs/audit/index.md",
+                "docs/audit/events.md",
+                "docs/default/index.md",
+                "docs/default/order-items.md",
+                "mkdocs.yml",
+                "sidebars.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_navigation_metadata() {
+        let files = render_site(&fixture(), &ReportOptions::default());
+        let file = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.path.to_str() == Some(path))
+                .map(|f| f.content.as_str())
+                .expect("file rendered")
+        };
+
+        let mkdocs = file("mkdocs.yml");
+        assert!(mkdocs.contains("docs_dir: docs"));
+        assert!(mkdocs.contains("  - \"public\":\n      - Overview: public/index.md\n"));
+        assert!(mkdocs.contains("      - \"Orders\": public/Orders-2.md\n"));
+
+        let sidebar: serde_json::Value =
+            serde_json::from_str(file("sidebars.json")).expect("valid sidebar JSON");
+        assert_eq!(sidebar["dbsurveyor"][0], "index");
+        assert_eq!(sidebar["dbsurveyor"][1]["link"]["id"], "public/index");
+        assert_eq!(sidebar["dbsurveyor"][2]["items"][0], "audit/events");
+
+        let page = file("docs/public/orders.md");
+        assert!(page.starts_with("---\ntitle: \"public.orders\"\nsidebar_label: \"orders\"\n---"));
+        assert!(page.contains("## Columns"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Mermaid entity-relationship diagram generation.
+//!
+//! Declared foreign keys are drawn as solid (identifying) lines. Inferred
+//! relationships are drawn as dashed lines labelled with their confidence so
+//! they cannot be mistaken for constraints enforced by the database. The
+//! child end is `o|` (zero or one) for one-to-one references and `o{` (zero
+//! or more) otherwise.
+
+use crate::markdown::{column_type_label, qualified_name, qualified_table_name};
+use dbsurveyor_core::models::{DatabaseSchema, Table};
+use dbsurveyor_core::relationships::{Cardinality, cardinality, infer_relationships};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Renders an `erDiagram` for every table in the schema.
+pub(crate) fn render_erd(schema: &DatabaseSchema) -> String {
+    let mut diagram = String::from("erDiagram\n");
+
+    for table in &schema.tables {
+        let _ = writeln!(
+            diagram,
+            "    {} {{",
+            identifier(&qualified_table_name(table))
+        );
+        let foreign_key_columns: HashSet<&str> = table
+            .foreign_keys
+            .iter()
+            .flat_map(|fk| fk.columns.iter().map(String::as_str))
+            .collect();
+        for column in &table.columns {
+            let mut keys = Vec::new();
+            if column.is_primary_key || is_primary_key_column(table, &column.name) {
+                keys.push("PK");
+            }
+            if foreign_key_columns.contains(column.name.as_str()) {
+                keys.push("FK");
+            }
+            let _ = writeln!(
+                diagram,
+                "        {} {}{}",
+                identifier(&column_type_label(column)),
+                identifier(&column.name),
+                if keys.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {}", keys.join(", "))
+                }
+            );
+        }
+        diagram.push_str("    }\n");
+    }
+
+    for table in &schema.tables {
+        let child = identifier(&qualified_table_name(table));
+        for fk in &table.foreign_keys {
+            let parent_schema = fk.referenced_schema.as_deref().or(table.schema.as_deref());
+            let _ = writeln!(
+                diagram,
+                "    {} ||--{} {} : \"{}\"",
+                identifier(&qualified_name(parent_schema, &fk.referenced_table)),
+                child_end(cardinality(table, &fk.columns)),
+                child,
+                label(&fk.columns.join(", "))
+            );
+        }
+    }
+
+    for relationship in infer_relationships(schema) {
+        let _ = writeln!(
+            diagram,
+            "    {} ||..{} {} : \"{} (inferred {:.0}%)\"",
+            identifier(&qualified_name(
+                relationship.referenced_schema.as_deref(),
+                &relationship.referenced_table
+            )),
+            child_end(relationship.cardinality),
+            identifier(&qualified_name(
+                relationship.schema.as_deref(),
+                &relationship.table
+            )),
+            label(&relationship.column),
+            relationship.confidence * 100.0
+        );
+    }
+
+    diagram
+}
+
+fn child_end(cardinality: Cardinality) -> &'static str {
+    match cardinality {
+        Cardinality::ManyToOne => "o{",
+        Cardinality::OneToOne => "o|",
+    }
+}
+
+fn is_primary_key_column(table: &Table, column: &str) -> bool {
+    table
+        .primary_key
+        .as_ref()
+        .is_some_and(|pk| pk.columns.iter().any(|c| c == column))
+}
+
+/// Mermaid entity, type, and attribute names cannot contain dots or spaces.
+fn identifier(name: &str) -> String {
+    let identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier.is_empty() {
+        "_".to_string()
+    } else {
+        identifier
+    }
+}
+
+/// Relationship labels are quoted; strip characters that would end the quote.
+fn label(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, DatabaseInfo, ForeignKey, PrimaryKey, UnifiedDataType};
+
+    fn column(name: &str) -> Column {
+        Column {
+            is_nullable: false,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::Integer {
+                    bits: 32,
+                    signed: true,
+                },
+            )
+        }
+    }
+
+    fn table(name: &str, columns: &[&str], foreign_keys: Vec<ForeignKey>) -> Table {
+        Table {
+            primary_key: Some(PrimaryKey {
+                name: None,
+                columns: vec!["id".to_string()],
+            }),
+            foreign_keys,
+            ..Table::new(
+                name.to_string(),
+                Some("public".to_string()),
+                columns.iter().map(|c| column(c)).collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_declared_and_inferred_relationships_are_distinct() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![
+            table("customers", &["id"], Vec::new()),
+            table("stores", &["id"], Vec::new()),
+            table(
+                "orders",
+                &["id", "customer_id", "store_id"],
+                vec![ForeignKey {
+                    name: None,
+                    columns: vec!["customer_id".to_string()],
+                    referenced_table: "customers".to_string(),
+                    referenced_schema: None,
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                }],
+            ),
+        ];
+
+        let diagram = render_erd(&schema);
+        assert!(diagram.starts_with("erDiagram\n    public_customers {\n        int32 id PK\n"));
+        assert!(diagram.contains("        int32 customer_id FK\n"));
+        assert!(diagram.contains("    public_customers ||--o{ public_orders : \"customer_id\"\n"));
+        assert!(
+            diagram
+                .contains("    public_stores ||..o{ public_orders : \"store_id (inferred 70%)\"\n")
+        );
+    }
+
+    #[test]
+    fn test_unique_foreign_key_is_one_to_one() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        // The foreign key column is the table's primary key
+        schema.tables = vec![
+            table("customers", &["id"], Vec::new()),
+            table(
+                "loyalty_accounts",
+                &["id"],
+                vec![ForeignKey {
+                    name: None,
+                    columns: vec!["id".to_string()],
+                    referenced_table: "customers".to_string(),
+                    referenced_schema: None,
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                }],
+            ),
+        ];
+
+        let diagram = render_erd(&schema);
+        assert!(diagram.contains("    public_customers ||--o| public_loyalty_accounts : \"id\"\n"));
+    }
+}
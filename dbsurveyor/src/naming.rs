@@ -0,0 +1,195 @@
+//! Abbreviation dictionary for friendly display names.
+//!
+//! A dictionary file has one `abbreviation = expansion` entry per line;
+//! blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! cust = customer
+//! amt  = amount
+//! ```
+//!
+//! Identifiers are split into words on `_`, `-`, spaces, camelCase
+//! boundaries, and digits, so `cust_ord_amt` and `custOrdAmt` both render as
+//! "Customer Order Amount".
+
+use dbsurveyor_core::{DbSurveyorError, Result};
+use std::collections::HashMap;
+
+/// Words that are short or vowel-less but well understood.
+const COMMON_WORDS: &[&str] = &[
+    "at", "by", "db", "id", "in", "ip", "is", "no", "of", "on", "or", "to", "api", "css", "csv",
+    "dns", "html", "http", "https", "jwt", "pdf", "sku", "sms", "sql", "ssn", "url", "uri", "utc",
+    "xml",
+];
+
+/// Abbreviation expansions, keyed by lowercase abbreviation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NamingDictionary {
+    expansions: HashMap<String, String>,
+}
+
+impl NamingDictionary {
+    /// Parses dictionary file content.
+    ///
+    /// # Errors
+    /// Returns a configuration error naming the first malformed line.
+    pub(crate) fn parse(content: &str) -> Result<Self> {
+        let mut expansions = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line.split_once('=').and_then(|(abbreviation, expansion)| {
+                let abbreviation = abbreviation.trim();
+                let expansion = expansion.trim();
+                (!abbreviation.is_empty()
+                    && !expansion.is_empty()
+                    && abbreviation.chars().all(|c| c.is_ascii_alphanumeric()))
+                .then_some((abbreviation, expansion))
+            });
+            let Some((abbreviation, expansion)) = entry else {
+                return Err(DbSurveyorError::configuration(format!(
+                    "Invalid naming dictionary entry on line {}: expected `abbreviation = expansion`",
+                    index.saturating_add(1)
+                )));
+            };
+            expansions.insert(abbreviation.to_ascii_lowercase(), expansion.to_string());
+        }
+        Ok(Self { expansions })
+    }
+
+    /// Number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.expansions.len()
+    }
+
+    /// Title-cased name with abbreviations expanded.
+    ///
+    /// Returns `None` when no word of `name` is in the dictionary.
+    pub(crate) fn display_name(&self, name: &str) -> Option<String> {
+        let mut expanded_any = false;
+        let words: Vec<String> = split_words(name)
+            .into_iter()
+            .map(|word| match self.expansions.get(&word) {
+                Some(expansion) => {
+                    expanded_any = true;
+                    title_case(expansion)
+                }
+                None => title_case(&word),
+            })
+            .collect();
+        expanded_any.then(|| words.join(" "))
+    }
+
+    /// Words of `name` that look like abbreviations but have no expansion.
+    pub(crate) fn unmatched(&self, name: &str) -> Vec<String> {
+        let mut unmatched: Vec<String> = split_words(name)
+            .into_iter()
+            .filter(|word| !self.expansions.contains_key(word) && is_cryptic(word))
+            .collect();
+        unmatched.dedup();
+        unmatched
+    }
+}
+
+/// Splits an identifier into lowercase words.
+pub(crate) fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous = None;
+            continue;
+        }
+        let boundary = match previous {
+            Some(p) => {
+                (p.is_lowercase() && c.is_uppercase()) || (p.is_ascii_digit() != c.is_ascii_digit())
+            }
+            None => false,
+        };
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Heuristic for abbreviations: alphabetic words with no vowels (`qty`,
+/// `nbr`) or of one or two letters, excluding well-known short words.
+fn is_cryptic(word: &str) -> bool {
+    if !word.chars().all(|c| c.is_ascii_alphabetic()) || COMMON_WORDS.contains(&word) {
+        return false;
+    }
+    word.len() <= 2
+        || !word
+            .chars()
+            .any(|c| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u'))
+}
+
+fn title_case(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> NamingDictionary {
+        NamingDictionary::parse("# expansions\ncust = customer\nAMT=amount\n\nqty = quantity\n")
+            .expect("valid dictionary")
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        assert_eq!(dictionary().len(), 3);
+        let error = NamingDictionary::parse("cust = customer\ncust\n").expect_err("malformed");
+        assert!(error.to_string().contains("line 2"));
+        assert!(NamingDictionary::parse("cust id = customer id").is_err());
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("cust_ordAmt2"), ["cust", "ord", "amt", "2"]);
+        assert_eq!(split_words("CUST-ID"), ["cust", "id"]);
+        assert_eq!(split_words("HTMLBody"), ["htmlbody"]);
+    }
+
+    #[test]
+    fn test_display_name() {
+        let names = dictionary();
+        assert_eq!(
+            names.display_name("cust_ord_amt").as_deref(),
+            Some("Customer Ord Amount")
+        );
+        assert_eq!(names.display_name("custId").as_deref(), Some("Customer Id"));
+        assert_eq!(names.display_name("order_total"), None);
+    }
+
+    #[test]
+    fn test_unmatched_abbreviations() {
+        let names = dictionary();
+        assert_eq!(names.unmatched("cust_nbr_qty_id"), ["nbr"]);
+        assert_eq!(names.unmatched("created_at"), Vec::<String>::new());
+        assert_eq!(names.unmatched("x_pos"), ["x"]);
+    }
+}
@@ -1,26 +1,522 @@
-//! Documentation output generation (Markdown, HTML, JSON, Mermaid, SQL).
+//! Documentation output generation (Markdown, Confluence, HTML, JSON, Mermaid, SQL).
 
 #[cfg(feature = "experimental")]
 use crate::SqlDialect;
+use crate::baseline::AllowList;
+use crate::batch;
+#[cfg(feature = "experimental")]
+use crate::compat;
+use crate::confluence;
+use crate::conform::{ExpectedSchema, check_conformance};
+use crate::exporter::{Exporter, Rendered};
+use crate::graph;
+use crate::growth;
+#[cfg(feature = "experimental")]
+use crate::html;
+use crate::markdown::site::SiteFile;
+use crate::markdown::{self, ReportOptions, ReportSection};
+#[cfg(feature = "experimental")]
+use crate::mermaid;
+use crate::naming::NamingDictionary;
+use crate::output_template::{OutputTemplate, file_safe};
 use crate::redaction::Redactor;
+use crate::sandbox::PathPolicy;
+use crate::schema::BundleDatabase;
+use crate::search::{self, SearchIndex};
+#[cfg(feature = "experimental")]
+use crate::sql;
+use crate::summary::ResultRecorder;
+use crate::where_used::{ColumnQuery, find_column_usage};
 use crate::{Cli, OutputFormat, RedactionMode, create_spinner, schema};
-use dbsurveyor_core::{Result, models::DatabaseSchema};
-use std::path::PathBuf;
+use dbsurveyor_core::anonymize::Pseudonymizer;
+use dbsurveyor_core::collisions::CollisionFinder;
+use dbsurveyor_core::{
+    Baseline, InputLimits, Result, diff_surveys,
+    models::{CollectionStatus, DatabaseSchema, SurveyDocument},
+};
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
 /// Generates documentation from schema.
+///
+/// Server surveys and survey bundles produce one output per collected
+/// database, named after the database (see [`database_output_path`]) or
+/// placed by `output_template`. Their databases are rendered in parallel on
+/// `--jobs` threads and written in survey order. A directory input documents
+/// every survey file matching `pattern` (see [`generate_directory_documentation`]).
+///
+/// With `--result-summary` or `--result-file`, a JSON summary of the run is
+/// emitted afterwards, including when generation fails.
 pub(crate) async fn generate_documentation(
     input_path: &PathBuf,
-    format: OutputFormat,
+    format: &dyn Exporter,
+    output_path: Option<&PathBuf>,
+    output_template: Option<&str>,
+    pattern: Option<&str>,
+    limits: &InputLimits,
+    cli: &Cli,
+) -> Result<()> {
+    let recorder = ResultRecorder::new(input_path, format.name(), &cli.sections);
+    let outcome = generate_documentation_inner(
+        input_path,
+        format,
+        output_path,
+        output_template,
+        pattern,
+        limits,
+        cli,
+        &recorder,
+    )
+    .await;
+
+    if cli.result_summary || cli.result_file.is_some() {
+        let emitted = recorder
+            .finish(&outcome)
+            .emit(cli.result_summary, cli.result_file.as_deref());
+        // A generation error is more useful than a failure to report it
+        outcome.and(emitted)
+    } else {
+        outcome
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn generate_documentation_inner(
+    input_path: &PathBuf,
+    format: &dyn Exporter,
     output_path: Option<&PathBuf>,
+    output_template: Option<&str>,
+    pattern: Option<&str>,
+    limits: &InputLimits,
+    cli: &Cli,
+    recorder: &ResultRecorder,
+) -> Result<()> {
+    let template = output_template.map(OutputTemplate::parse).transpose()?;
+    let names = match &cli.naming_dictionary {
+        Some(path) => Some(load_naming_dictionary(path).await?),
+        None => None,
+    };
+
+    if input_path.is_dir() {
+        return generate_directory_documentation(
+            input_path,
+            format,
+            output_path,
+            template.as_ref(),
+            pattern.unwrap_or(batch::DEFAULT_PATTERN),
+            names.as_ref(),
+            limits,
+            cli,
+            recorder,
+        )
+        .await;
+    }
+
+    let paths = match template {
+        Some(template) => OutputPaths::Template(template),
+        None => OutputPaths::Derived(
+            output_path
+                .cloned()
+                .unwrap_or_else(|| default_output_path(input_path, format)),
+        ),
+    };
+    document_survey(
+        input_path,
+        format,
+        &paths,
+        names.as_ref(),
+        limits,
+        cli,
+        recorder,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Documents every survey file under `input_dir` matching `pattern`.
+///
+/// Outputs mirror the directory layout under `output_dir` (by default
+/// `<input_dir>_docs`), which also receives an `index.md` linking them. A
+/// survey that fails is listed in the index with its error and the batch
+/// moves on; the command fails at the end if any survey did.
+#[allow(clippy::too_many_arguments)]
+async fn generate_directory_documentation(
+    input_dir: &Path,
+    format: &dyn Exporter,
+    output_dir: Option<&PathBuf>,
+    template: Option<&OutputTemplate>,
+    pattern: &str,
+    names: Option<&NamingDictionary>,
+    limits: &InputLimits,
+    cli: &Cli,
+    recorder: &ResultRecorder,
+) -> Result<()> {
+    let output_dir = output_dir.cloned().unwrap_or_else(|| {
+        let name = input_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("surveys");
+        PathBuf::from(format!("{}_docs", name))
+    });
+    let surveys = batch::find_surveys(input_dir, pattern, &output_dir)?;
+    info!(
+        "Found {} survey files matching {} in {}",
+        surveys.len(),
+        pattern,
+        input_dir.display()
+    );
+    if surveys.is_empty() {
+        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+            format!(
+                "No survey files matching '{}' in {}",
+                pattern,
+                input_dir.display()
+            ),
+        ));
+    }
+
+    let mut entries = Vec::with_capacity(surveys.len());
+    let mut observations = Vec::new();
+    for survey in surveys {
+        let relative = survey
+            .strip_prefix(input_dir)
+            .unwrap_or(&survey)
+            .to_path_buf();
+        let paths = match template {
+            Some(template) => OutputPaths::Template(template.clone()),
+            None => OutputPaths::Derived(output_dir.join(default_output_path(&relative, format))),
+        };
+        let outcome = document_survey(&survey, format, &paths, names, limits, cli, recorder)
+            .await
+            .map_err(|e| {
+                warn!("Failed to document {}: {}", survey.display(), e);
+                recorder.record_skipped(&relative.display().to_string(), e.to_string());
+                e.to_string()
+            });
+        let series = relative
+            .parent()
+            .map(|parent| parent.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        observations.extend(recorder.take_observations().into_iter().map(|observation| {
+            growth::Observation {
+                series: series.clone(),
+                ..observation
+            }
+        }));
+        entries.push(batch::BatchEntry {
+            survey: relative,
+            outcome,
+        });
+    }
+    let growth = growth::analyze(&observations);
+
+    let index_path = output_dir.join(batch::INDEX_FILE);
+    cli.paths.to_path_policy()?.check(&index_path)?;
+    tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to create directory {}", output_dir.display()),
+            source: e,
+        }
+    })?;
+    let mut index = batch::render_index(input_dir, &output_dir, &entries);
+    if !growth.is_empty() {
+        index.push('\n');
+        index.push_str(&growth::summary_section(&growth, cli.growth_horizon_days));
+    }
+    tokio::fs::write(&index_path, index).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to write index to {}", index_path.display()),
+            source: e,
+        }
+    })?;
+    println!("Index generated: {}", index_path.display());
+    recorder.record_output(&index_path);
+
+    if !growth.is_empty() {
+        let growth_path = output_dir.join(growth::GROWTH_FILE);
+        cli.paths.to_path_policy()?.check(&growth_path)?;
+        tokio::fs::write(
+            &growth_path,
+            growth::render_csv(&growth, cli.growth_horizon_days),
+        )
+        .await
+        .map_err(|e| dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to write growth series to {}", growth_path.display()),
+            source: e,
+        })?;
+        println!("Growth series generated: {}", growth_path.display());
+        recorder.record_output(&growth_path);
+    }
+
+    let failed = entries
+        .iter()
+        .filter(|entry| entry.outcome.is_err())
+        .count();
+    if failed > 0 {
+        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+            format!(
+                "{} of {} survey files could not be documented; see {}",
+                failed,
+                entries.len(),
+                index_path.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Default output path for a survey file: its file stem with the format's
+/// extension, next to the input's relative location. Surveys read from
+/// stdin are named `schema`.
+fn default_output_path(input_path: &Path, format: &dyn Exporter) -> PathBuf {
+    let base_name = input_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|_| !schema::is_stdin(input_path))
+        .unwrap_or("schema");
+
+    let file_name = format.output_file_name(base_name);
+    input_path.with_file_name(file_name)
+}
+
+/// Documents one survey file, returning the outputs written.
+async fn document_survey(
+    input_path: &PathBuf,
+    format: &dyn Exporter,
+    paths: &OutputPaths,
+    names: Option<&NamingDictionary>,
+    limits: &InputLimits,
+    cli: &Cli,
+    recorder: &ResultRecorder,
+) -> Result<Vec<PathBuf>> {
+    if schema::is_survey_bundle(input_path) {
+        return generate_bundle_documentation(
+            input_path, format, paths, names, limits, cli, recorder,
+        )
+        .await;
+    }
+
+    let survey = schema::load_survey(input_path, limits).await?;
+    info!("Format version: {}", survey.format_version());
+
+    match &survey {
+        SurveyDocument::Database(schema) => {
+            let output_file = match paths {
+                OutputPaths::Derived(output_file) => output_file.clone(),
+                OutputPaths::Template(template) => {
+                    template.render(None, &schema.database_info.name)
+                }
+            };
+            generate_database_documentation(schema, format, &output_file, names, cli, recorder)
+                .await?;
+            Ok(vec![output_file])
+        }
+        SurveyDocument::Server(server) => {
+            info!(
+                "Loaded {} server survey for {} with {} databases",
+                server.server_info.server_type,
+                server.server_info.host,
+                server.databases.len()
+            );
+            let databases: Vec<(&DatabaseSchema, PathBuf)> = server
+                .databases
+                .iter()
+                .filter(|schema| {
+                    let documentable = is_documentable(schema);
+                    if !documentable {
+                        recorder.record_skipped_database(schema);
+                    }
+                    documentable
+                })
+                .map(|schema| {
+                    let path = paths.database(
+                        Some(&server.server_info.host),
+                        &schema.database_info.name,
+                        format,
+                    );
+                    (schema, path)
+                })
+                .collect();
+            let pool = worker_pool(cli)?;
+            document_databases(&databases, format, names, cli, &pool, recorder).await?;
+            Ok(databases.into_iter().map(|(_, path)| path).collect())
+        }
+    }
+}
+
+/// Generates documentation for each database of a survey bundle as it is
+/// read, so memory use does not grow with the size of the bundle.
+///
+/// Databases are taken from the bundle in batches of `--jobs`, so at most
+/// that many are held in memory while rendering. Bundles may hold databases
+/// of the same name from different servers; names that would share an
+/// output path get a numeric suffix (`sales`, `sales_2`).
+async fn generate_bundle_documentation(
+    input_path: &Path,
+    format: &dyn Exporter,
+    paths: &OutputPaths,
+    names: Option<&NamingDictionary>,
+    limits: &InputLimits,
     cli: &Cli,
+    recorder: &ResultRecorder,
+) -> Result<Vec<PathBuf>> {
+    let mut databases = schema::stream_survey_bundle(input_path, limits)?;
+    let pool = worker_pool(cli)?;
+    let mut seen: HashMap<PathBuf, usize> = HashMap::new();
+    let mut documented = Vec::new();
+    let mut finished = false;
+
+    while !finished {
+        let mut batch = Vec::new();
+        let mut failure = None;
+        while batch.len() < pool.current_num_threads() {
+            let BundleDatabase { server, schema } = match databases.recv().await {
+                Some(Ok(database)) => database,
+                Some(Err(e)) => {
+                    failure = Some(e);
+                    break;
+                }
+                None => {
+                    finished = true;
+                    break;
+                }
+            };
+            if !is_documentable(&schema) {
+                warn!(
+                    "Skipping database {}: {:?}",
+                    schema.database_info.name, schema.database_info.collection_status
+                );
+                recorder.record_skipped_database(&schema);
+                continue;
+            }
+            let server = server.as_deref();
+            let path = paths.database(server, &schema.database_info.name, format);
+            let occurrences = seen.entry(path.clone()).or_default();
+            *occurrences += 1;
+            let path = match *occurrences {
+                1 => path,
+                n => {
+                    let name = format!("{}_{}", schema.database_info.name, n);
+                    paths.database(server, &name, format)
+                }
+            };
+            batch.push((schema, path));
+        }
+
+        // Databases read before a failing line are still documented
+        let batch: Vec<(&DatabaseSchema, PathBuf)> = batch
+            .iter()
+            .map(|(schema, path)| (schema, path.clone()))
+            .collect();
+        document_databases(&batch, format, names, cli, &pool, recorder).await?;
+        documented.extend(batch.into_iter().map(|(_, path)| path));
+        if let Some(e) = failure {
+            return Err(e);
+        }
+    }
+
+    info!("Documented {} databases from bundle", documented.len());
+    Ok(documented)
+}
+
+/// Thread pool for rendering several databases at once, sized by `--jobs`
+/// or the available parallelism.
+fn worker_pool(cli: &Cli) -> Result<rayon::ThreadPool> {
+    let jobs = cli
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+                "Failed to start {} report workers: {}",
+                jobs, e
+            ))
+        })
+}
+
+/// Renders `databases` in parallel on `pool`, then writes each output in
+/// the order given so results do not depend on thread scheduling.
+async fn document_databases(
+    databases: &[(&DatabaseSchema, PathBuf)],
+    format: &dyn Exporter,
+    names: Option<&NamingDictionary>,
+    cli: &Cli,
+    pool: &rayon::ThreadPool,
+    recorder: &ResultRecorder,
 ) -> Result<()> {
-    let schema = schema::load_schema(input_path).await?;
+    if databases.is_empty() {
+        return Ok(());
+    }
 
+    let spinner = create_spinner(&format!(
+        "Generating {} documentation for {} databases...",
+        format.name(),
+        databases.len()
+    ));
+    let rendered: Vec<Result<Rendered>> = tokio::task::block_in_place(|| {
+        pool.install(|| {
+            databases
+                .par_iter()
+                .map(|(schema, _)| render_documentation(schema, format, names, cli))
+                .collect()
+        })
+    });
+    spinner.finish_and_clear();
+
+    let policy = cli.paths.to_path_policy()?;
+    for ((schema, path), rendered) in databases.iter().zip(rendered) {
+        policy.check(path)?;
+        info!(
+            "Rendered documentation for database: {}",
+            schema.database_info.name
+        );
+        write_documentation(rendered?, format, path).await?;
+        recorder.record_database(schema, path);
+        info!("[OK]Documentation generated: {}", path.display());
+        println!("Documentation generated: {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Generates documentation for one database schema at `output_file`.
+async fn generate_database_documentation(
+    schema: &DatabaseSchema,
+    format: &dyn Exporter,
+    output_file: &Path,
+    names: Option<&NamingDictionary>,
+    cli: &Cli,
+    recorder: &ResultRecorder,
+) -> Result<()> {
     info!("Loaded schema for database: {}", schema.database_info.name);
-    info!("Format version: {}", schema.format_version);
     info!("Tables: {}", schema.tables.len());
 
+    let spinner = create_spinner(&format!("Generating {} documentation...", format.name()));
+    let gen_result = render_documentation(schema, format, names, cli);
+    spinner.finish_and_clear();
+    cli.paths.to_path_policy()?.check(output_file)?;
+    write_documentation(gen_result?, format, output_file).await?;
+    recorder.record_database(schema, output_file);
+    info!("[OK]Documentation generated: {}", output_file.display());
+    println!("Documentation generated: {}", output_file.display());
+
+    Ok(())
+}
+
+/// Renders one database with `format`, redacting samples first. Runs on
+/// report worker threads, so it performs no I/O.
+fn render_documentation(
+    schema: &DatabaseSchema,
+    format: &dyn Exporter,
+    names: Option<&NamingDictionary>,
+    cli: &Cli,
+) -> Result<Rendered> {
     // Apply redaction to samples if present
     let redact_mode = if cli.no_redact {
         RedactionMode::None
@@ -28,112 +524,203 @@ pub(crate) async fn generate_documentation(
         cli.redact_mode.clone()
     };
 
-    let _redacted_samples = if let Some(ref samples) = schema.samples {
+    let redacted_samples = if let Some(ref samples) = schema.samples {
         let redactor = Redactor::new(redact_mode);
         Some(redactor.redact(samples))
     } else {
         None
     };
 
-    let output_file = match output_path {
-        Some(path) => path.clone(),
-        None => {
-            let base_name = input_path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("schema");
+    let sections = if cli.sections.is_empty() {
+        &ReportSection::ALL[..]
+    } else {
+        &cli.sections[..]
+    };
+    let options = ReportOptions {
+        samples: redacted_samples.as_deref(),
+        sections,
+        names,
+        sample_preview: cli.include_samples.then_some(cli.sample_preview_rows),
+        title: cli.report_title.as_deref(),
+        footer: cli.report_footer.as_deref(),
+    };
+    format.export(schema, &options)
+}
 
-            match format {
-                OutputFormat::Markdown => format!("{}.md", base_name).into(),
-                #[cfg(feature = "experimental")]
-                OutputFormat::Html => format!("{}.html", base_name).into(),
-                #[cfg(feature = "experimental")]
-                OutputFormat::Json => format!("{}_analysis.json", base_name).into(),
-                #[cfg(feature = "experimental")]
-                OutputFormat::Mermaid => format!("{}.mmd", base_name).into(),
-            }
+/// The built-in formats.
+impl Exporter for OutputFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::MarkdownSite => "Markdown site",
+            OutputFormat::Confluence => "Confluence",
+            OutputFormat::GraphJson => "graph JSON",
+            #[cfg(feature = "experimental")]
+            OutputFormat::Html => "HTML",
+            #[cfg(feature = "experimental")]
+            OutputFormat::Json => "JSON",
+            #[cfg(feature = "experimental")]
+            OutputFormat::Mermaid => "Mermaid",
         }
-    };
+    }
 
-    let format_name = match format {
-        OutputFormat::Markdown => "markdown",
-        #[cfg(feature = "experimental")]
-        OutputFormat::Html => "HTML",
-        #[cfg(feature = "experimental")]
-        OutputFormat::Json => "JSON",
-        #[cfg(feature = "experimental")]
-        OutputFormat::Mermaid => "Mermaid",
-    };
-    let spinner = create_spinner(&format!("Generating {} documentation...", format_name));
-
-    let gen_result = match format {
-        OutputFormat::Markdown => generate_markdown(&schema, &output_file).await,
-        #[cfg(feature = "experimental")]
-        OutputFormat::Html => generate_html(&schema, &output_file).await,
-        #[cfg(feature = "experimental")]
-        OutputFormat::Json => generate_json_analysis(&schema, &output_file).await,
-        #[cfg(feature = "experimental")]
-        OutputFormat::Mermaid => generate_mermaid(&schema, &output_file).await,
-    };
+    fn output_file_name(&self, base_name: &str) -> String {
+        match self {
+            OutputFormat::Markdown => format!("{}.md", base_name),
+            OutputFormat::MarkdownSite => format!("{}_site", base_name),
+            OutputFormat::Confluence => format!("{}_confluence", base_name),
+            OutputFormat::GraphJson => format!("{}_graph.json", base_name),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Html => format!("{}.html", base_name),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Json => format!("{}_analysis.json", base_name),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Mermaid => format!("{}.mmd", base_name),
+        }
+    }
 
-    spinner.finish_and_clear();
-    gen_result?;
-    info!("[OK]Documentation generated: {}", output_file.display());
-    println!("Documentation generated: {}", output_file.display());
+    fn writes_directory(&self) -> bool {
+        matches!(self, OutputFormat::MarkdownSite | OutputFormat::Confluence)
+    }
 
-    Ok(())
+    fn export(&self, schema: &DatabaseSchema, options: &ReportOptions<'_>) -> Result<Rendered> {
+        Ok(match self {
+            OutputFormat::Markdown => Rendered::File(markdown::render_report(schema, options)),
+            OutputFormat::MarkdownSite => {
+                Rendered::Site(markdown::site::render_site(schema, options))
+            }
+            OutputFormat::Confluence => Rendered::Site(confluence::render_export(schema, options)),
+            OutputFormat::GraphJson => Rendered::File(render_graph_json(schema)?),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Html => Rendered::File(html::render_html(schema, options)),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Json => Rendered::File(render_json_analysis(schema)?),
+            #[cfg(feature = "experimental")]
+            OutputFormat::Mermaid => Rendered::File(mermaid::render_erd(schema)),
+        })
+    }
 }
 
-/// Generates Markdown documentation (placeholder).
-async fn generate_markdown(schema: &DatabaseSchema, output_path: &PathBuf) -> Result<()> {
-    let content = format!(
-        "# Database Schema: {}\n\n\
-        Generated by DBSurveyor v{}\n\
-        Collection Date: {}\n\n\
-        ## Summary\n\n\
-        - **Tables**: {}\n\
-        - **Views**: {}\n\
-        - **Indexes**: {}\n\n\
-        ## Tables\n\n",
-        schema.database_info.name,
-        schema.collection_metadata.collector_version,
-        schema
-            .collection_metadata
-            .collected_at
-            .format("%Y-%m-%d %H:%M:%S UTC"),
-        schema.tables.len(),
-        schema.views.len(),
-        schema.indexes.len()
-    );
-
-    tokio::fs::write(output_path, content).await.map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::Io {
-            context: format!("Failed to write Markdown to {}", output_path.display()),
-            source: e,
+/// Writes rendered documentation to `output_file`, a directory for site formats.
+async fn write_documentation(
+    rendered: Rendered,
+    format: &dyn Exporter,
+    output_file: &Path,
+) -> Result<()> {
+    match rendered {
+        Rendered::File(content) => {
+            // Templated paths may name directories that do not exist yet
+            if let Some(parent) = output_file.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                    dbsurveyor_core::error::DbSurveyorError::Io {
+                        context: format!("Failed to create directory {}", parent.display()),
+                        source: e,
+                    }
+                })?;
+            }
+            tokio::fs::write(output_file, content).await.map_err(|e| {
+                dbsurveyor_core::error::DbSurveyorError::Io {
+                    context: format!(
+                        "Failed to write {} to {}",
+                        format.name(),
+                        output_file.display()
+                    ),
+                    source: e,
+                }
+            })
         }
-    })?;
+        Rendered::Site(files) => write_site_files(files, output_file).await,
+    }
+}
 
-    Ok(())
+/// Whether a database in a server survey has anything to document.
+fn is_documentable(schema: &DatabaseSchema) -> bool {
+    !matches!(
+        schema.database_info.collection_status,
+        CollectionStatus::Failed { .. } | CollectionStatus::Skipped { .. }
+    )
 }
 
-#[cfg(feature = "experimental")]
-/// Generates HTML documentation (placeholder).
-async fn generate_html(_schema: &DatabaseSchema, output_path: &PathBuf) -> Result<()> {
-    let content = "<!DOCTYPE html><html><head><title>Database Schema</title></head><body><h1>Schema Documentation</h1><p>HTML generation not yet implemented</p></body></html>";
+/// Where the documentation of each database in a survey is written.
+enum OutputPaths {
+    /// Derived from the output file (see [`database_output_path`])
+    Derived(PathBuf),
+    /// Placed by `--output-template`
+    Template(OutputTemplate),
+}
+
+impl OutputPaths {
+    fn database(&self, server: Option<&str>, database: &str, format: &dyn Exporter) -> PathBuf {
+        match self {
+            Self::Derived(output_file) => database_output_path(output_file, database, format),
+            Self::Template(template) => template.render(server, database),
+        }
+    }
+}
 
-    tokio::fs::write(output_path, content).await.map_err(|e| {
+/// Output location for one database of a server survey.
+///
+/// Directory formats get a subdirectory per database; single-file formats
+/// get the database name appended to the file stem (`survey_sales.md`).
+fn database_output_path(output_file: &Path, database: &str, format: &dyn Exporter) -> PathBuf {
+    let database = file_safe(database);
+
+    if format.writes_directory() {
+        return output_file.join(database);
+    }
+
+    let stem = output_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("schema");
+    let file_name = match output_file.extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!("{}_{}.{}", stem, database, extension),
+        None => format!("{}_{}", stem, database),
+    };
+    output_file.with_file_name(file_name)
+}
+
+/// Reads an abbreviation dictionary file.
+async fn load_naming_dictionary(path: &Path) -> Result<NamingDictionary> {
+    let content = tokio::fs::read_to_string(path).await.map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::Io {
-            context: format!("Failed to write HTML to {}", output_path.display()),
+            context: format!("Failed to read naming dictionary {}", path.display()),
             source: e,
         }
     })?;
+    let names = NamingDictionary::parse(&content)?;
+    info!("Loaded {} naming dictionary entries", names.len());
+    Ok(names)
+}
+
+/// Writes multi-file output (Markdown site, Confluence export) under `output_dir`.
+async fn write_site_files(files: Vec<SiteFile>, output_dir: &Path) -> Result<()> {
+    for file in files {
+        let path = output_dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                dbsurveyor_core::error::DbSurveyorError::Io {
+                    context: format!("Failed to create directory {}", parent.display()),
+                    source: e,
+                }
+            })?;
+        }
+        tokio::fs::write(&path, file.content).await.map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to write {}", path.display()),
+                source: e,
+            }
+        })?;
+    }
 
     Ok(())
 }
 
 #[cfg(feature = "experimental")]
-/// Generates JSON analysis (placeholder).
-async fn generate_json_analysis(schema: &DatabaseSchema, output_path: &PathBuf) -> Result<()> {
+/// Renders the JSON analysis (placeholder).
+fn render_json_analysis(schema: &DatabaseSchema) -> Result<String> {
     let analysis = serde_json::json!({
         "database_name": schema.database_info.name,
         "table_count": schema.tables.len(),
@@ -142,80 +729,110 @@ async fn generate_json_analysis(schema: &DatabaseSchema, output_path: &PathBuf)
         "collection_date": schema.collection_metadata.collected_at
     });
 
-    let file = std::fs::File::create(output_path).map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::Io {
-            context: format!("Failed to create {}", output_path.display()),
-            source: e,
-        }
-    })?;
-    let writer = std::io::BufWriter::new(file);
-    serde_json::to_writer_pretty(writer, &analysis).map_err(|e| {
+    serde_json::to_string_pretty(&analysis).map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::Serialization {
             context: "Failed to serialize analysis".to_string(),
             source: e,
         }
-    })?;
-
-    Ok(())
+    })
 }
 
-#[cfg(feature = "experimental")]
-/// Generates Mermaid ERD (placeholder).
-async fn generate_mermaid(_schema: &DatabaseSchema, output_path: &PathBuf) -> Result<()> {
-    let content = "erDiagram\n    %% Mermaid ERD generation not yet implemented\n    PLACEHOLDER {\n        string note\n    }";
-
-    tokio::fs::write(output_path, content).await.map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::Io {
-            context: format!("Failed to write Mermaid to {}", output_path.display()),
+/// Renders the relationship graph as JSON nodes and edges.
+fn render_graph_json(schema: &DatabaseSchema) -> Result<String> {
+    let graph = graph::build_graph(schema);
+    serde_json::to_string_pretty(&graph).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: "Failed to serialize relationship graph".to_string(),
             source: e,
         }
-    })?;
-
-    Ok(())
+    })
 }
 
 #[cfg(feature = "experimental")]
 /// Analyzes schema for insights (placeholder).
-pub(crate) async fn analyze_schema(input_path: &PathBuf, detailed: bool) -> Result<()> {
-    let schema = schema::load_schema(input_path).await?;
+pub(crate) async fn analyze_schema(
+    input_path: &PathBuf,
+    detailed: bool,
+    lint: bool,
+    limits: &InputLimits,
+) -> Result<()> {
+    let survey = schema::load_survey(input_path, limits).await?;
 
-    println!("Schema Analysis: {}", schema.database_info.name);
-    println!("================");
-    println!("Tables: {}", schema.tables.len());
-    println!("Views: {}", schema.views.len());
-    println!("Indexes: {}", schema.indexes.len());
-    println!("Constraints: {}", schema.constraints.len());
+    for (position, schema) in survey.databases().iter().enumerate() {
+        if position > 0 {
+            println!();
+        }
+        println!("Schema Analysis: {}", schema.database_info.name);
+        println!("================");
+        println!("Tables: {}", schema.tables.len());
+        println!("Views: {}", schema.views.len());
+        println!("Indexes: {}", schema.indexes.len());
+        println!("Constraints: {}", schema.constraints.len());
+
+        if detailed {
+            println!("\nDetailed Analysis:");
+            println!("- Procedures: {}", schema.procedures.len());
+            println!("- Functions: {}", schema.functions.len());
+            println!("- Triggers: {}", schema.triggers.len());
+            println!("- Custom Types: {}", schema.custom_types.len());
+        }
 
-    if detailed {
-        println!("\nDetailed Analysis:");
-        println!("- Procedures: {}", schema.procedures.len());
-        println!("- Functions: {}", schema.functions.len());
-        println!("- Triggers: {}", schema.triggers.len());
-        println!("- Custom Types: {}", schema.custom_types.len());
+        if lint {
+            print!("{}", lint_report(schema));
+        }
     }
 
     Ok(())
 }
 
+#[cfg(feature = "experimental")]
+/// Lists lint findings grouped by rule, with every affected object, so
+/// dangling references and orphaned objects can be worked through.
+fn lint_report(schema: &DatabaseSchema) -> String {
+    use dbsurveyor_core::lint::{LintRule, lint_schema};
+    use std::fmt::Write as _;
+
+    let findings = lint_schema(schema, &LintRule::ALL);
+    let mut report = format!("\nLint Findings: {}\n", findings.len());
+    for rule in LintRule::ALL {
+        let prefix = format!("{}: ", rule.id());
+        let matching: Vec<_> = findings
+            .iter()
+            .filter(|finding| finding.message.starts_with(&prefix))
+            .collect();
+        let Some(first) = matching.first() else {
+            continue;
+        };
+        let _ = writeln!(report, "- {} ({})", first.message, matching.len());
+        for object in matching.iter().flat_map(|finding| &finding.objects) {
+            let _ = writeln!(report, "    {}", object);
+        }
+    }
+    report
+}
+
 #[cfg(feature = "experimental")]
 /// Generates SQL DDL (placeholder).
 pub(crate) async fn generate_sql(
     input_path: &PathBuf,
-    _dialect: SqlDialect,
+    dialect: SqlDialect,
     output_path: Option<&PathBuf>,
+    limits: &InputLimits,
+    policy: &PathPolicy,
 ) -> Result<()> {
-    warn!("SQL DDL generation is not yet fully implemented. Output will be minimal.");
-    warn!("--dialect is not yet implemented and will be ignored");
-    let schema = schema::load_schema(input_path).await?;
-
-    let sql_content = format!(
-        "-- Database Schema: {}\n\
-        -- Generated by DBSurveyor\n\n\
-        -- SQL DDL generation not yet implemented\n\
-        -- Tables: {}\n",
-        schema.database_info.name,
-        schema.tables.len()
-    );
+    let survey = schema::load_survey(input_path, limits).await?;
+
+    let scripts: Vec<sql::SqlScript> = survey
+        .databases()
+        .iter()
+        .map(|schema| sql::render_ddl(schema, dialect))
+        .collect();
+    let untranslated: usize = scripts.iter().map(|s| s.untranslated.len()).sum();
+    let sql_content = scripts
+        .into_iter()
+        .map(|script| script.ddl)
+        .collect::<Vec<_>>()
+        .join("\n");
 
     let output_file = match output_path {
         Some(path) => path.clone(),
@@ -227,6 +844,7 @@ pub(crate) async fn generate_sql(
             format!("{}.sql", base_name).into()
         }
     };
+    policy.check(&output_file)?;
 
     tokio::fs::write(&output_file, sql_content)
         .await
@@ -236,24 +854,700 @@ pub(crate) async fn generate_sql(
         })?;
 
     println!("SQL DDL generated: {}", output_file.display());
+    if untranslated > 0 {
+        warn!(
+            "{} constructs could not be translated; they are listed at the end of {}",
+            untranslated,
+            output_file.display()
+        );
+    }
+    Ok(())
+}
+
+/// Writes a type compatibility report for migrating the survey to `dialect`.
+#[cfg(feature = "experimental")]
+pub(crate) async fn generate_compat(
+    input_path: &PathBuf,
+    dialect: SqlDialect,
+    output_path: Option<&PathBuf>,
+    limits: &InputLimits,
+    policy: &PathPolicy,
+) -> Result<()> {
+    let survey = schema::load_survey(input_path, limits).await?;
+
+    let report = survey
+        .databases()
+        .iter()
+        .map(|schema| compat::render_report(schema, dialect))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let output_file = match output_path {
+        Some(path) => path.clone(),
+        None => {
+            let base_name = input_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("schema");
+            format!("{}_compat.md", base_name).into()
+        }
+    };
+    policy.check(&output_file)?;
+
+    tokio::fs::write(&output_file, report).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!(
+                "Failed to write compatibility report to {}",
+                output_file.display()
+            ),
+            source: e,
+        }
+    })?;
+
+    println!("Compatibility report generated: {}", output_file.display());
     Ok(())
 }
 
 /// Validates schema file format.
-pub(crate) async fn validate_schema(input_path: &PathBuf) -> Result<()> {
-    let schema = schema::load_schema(input_path).await?;
+pub(crate) async fn validate_schema(input_path: &PathBuf, limits: &InputLimits) -> Result<()> {
+    let survey = schema::load_survey(input_path, limits).await?;
 
     println!("[OK]Schema file is valid");
-    println!("Format version: {}", schema.format_version);
-    println!("Database: {}", schema.database_info.name);
-    println!("Objects: {}", schema.object_count());
+    println!("Format version: {}", survey.format_version());
+    match &survey {
+        SurveyDocument::Database(schema) => {
+            println!("Database: {}", schema.database_info.name);
+            println!("Objects: {}", schema.object_count());
+        }
+        SurveyDocument::Server(server) => {
+            println!(
+                "Server: {} {} at {}",
+                server.server_info.server_type, server.server_info.version, server.server_info.host
+            );
+            println!("Databases: {}", server.databases.len());
+            for entry in &server.manifest {
+                println!(
+                    "  - {}: {} objects ({:?})",
+                    entry.name, entry.object_count, entry.collection_status
+                );
+            }
+        }
+    }
 
-    if !schema.collection_metadata.warnings.is_empty() {
-        println!("\nWarnings from collection:");
+    for schema in survey.databases() {
+        if schema.collection_metadata.warnings.is_empty() {
+            continue;
+        }
+        if survey.databases().len() > 1 {
+            println!(
+                "\nWarnings from collection of {}:",
+                schema.database_info.name
+            );
+        } else {
+            println!("\nWarnings from collection:");
+        }
         for warning in &schema.collection_metadata.warnings {
-            println!("  - {}", warning);
+            println!("  - {:?}: {}", warning.severity, warning);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the JSON Schema for a survey format version to stdout or a file.
+pub(crate) async fn print_json_schema(
+    format_version: &str,
+    output_path: Option<&PathBuf>,
+) -> Result<()> {
+    let definition = dbsurveyor_core::validation::get_schema_definition_for_version(format_version)
+        .map_err(|e| dbsurveyor_core::error::DbSurveyorError::configuration(e.to_string()))?;
+
+    let mut content = serde_json::to_string_pretty(&definition).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: "Failed to serialize JSON Schema".to_string(),
+            source: e,
+        }
+    })?;
+    content.push('\n');
+
+    match output_path {
+        Some(path) => {
+            tokio::fs::write(path, content).await.map_err(|e| {
+                dbsurveyor_core::error::DbSurveyorError::Io {
+                    context: format!("Failed to write JSON Schema to {}", path.display()),
+                    source: e,
+                }
+            })?;
+            info!("JSON Schema written to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Builds a search index for a schema file and writes it as compact JSON.
+///
+/// The index stores object names and comments in plain text, so an
+/// encrypted survey is only indexed when `allow_plaintext` is set.
+///
+/// # Errors
+/// Returns an error if `input_path` is encrypted and `allow_plaintext` is
+/// not set, or if the index path is rejected by `policy`.
+pub(crate) async fn build_search_index(
+    input_path: &PathBuf,
+    output_path: Option<&PathBuf>,
+    allow_plaintext: bool,
+    limits: &InputLimits,
+    policy: &PathPolicy,
+) -> Result<()> {
+    let output_file = output_path
+        .cloned()
+        .unwrap_or_else(|| default_index_path(input_path));
+    policy.check(&output_file)?;
+
+    if schema::is_encrypted(input_path) {
+        if !allow_plaintext {
+            return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+                format!(
+                    "{} is encrypted; its search index would store table, column, and comment \
+                     names in plain text. Pass --allow-plaintext to build it anyway",
+                    input_path.display()
+                ),
+            ));
+        }
+        warn!(
+            "Writing object names from encrypted survey {} to plain-text index {}",
+            input_path.display(),
+            output_file.display()
+        );
+    }
+
+    let survey = schema::load_survey(input_path, limits).await?;
+    let index = SearchIndex::build_survey(&survey, file_size(input_path).await?);
+    let content = serde_json::to_vec(&index).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: "Failed to serialize search index".to_string(),
+            source: e,
+        }
+    })?;
+    write_atomic(&output_file, content).await?;
+
+    println!(
+        "Indexed {} objects ({} words): {}",
+        index.objects.len(),
+        index.term_count(),
+        output_file.display()
+    );
+    Ok(())
+}
+
+/// Checks every database in a survey against an expected-schema
+/// specification and prints each finding.
+///
+/// Returns `false` if any check failed.
+pub(crate) async fn conform_survey(
+    survey_path: &PathBuf,
+    spec_path: &Path,
+    json: bool,
+    limits: &InputLimits,
+) -> Result<bool> {
+    let content = tokio::fs::read_to_string(spec_path).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!(
+                "Failed to read expected-schema file {}",
+                spec_path.display()
+            ),
+            source: e,
+        }
+    })?;
+    let spec = ExpectedSchema::parse(&content)?;
+    let survey = schema::load_survey(survey_path, limits).await?;
+    let findings: Vec<_> = survey
+        .databases()
+        .iter()
+        .flat_map(|schema| check_conformance(&spec, schema))
+        .collect();
+    let failed = findings.iter().filter(|finding| !finding.passed).count();
+
+    if json {
+        let content = serde_json::to_string_pretty(&findings).map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Serialization {
+                context: "Failed to serialize conformance findings".to_string(),
+                source: e,
+            }
+        })?;
+        println!("{}", content);
+    } else {
+        for finding in &findings {
+            let mut line = format!(
+                "[{}] {}: {} {}",
+                if finding.passed { "PASS" } else { "FAIL" },
+                finding.database,
+                finding.check,
+                finding.object
+            );
+            if let Some(detail) = &finding.detail {
+                line.push_str(&format!(" ({})", detail));
+            }
+            println!("{}", line);
+        }
+        println!(
+            "{} checks against {} ({} passed, {} failed)",
+            findings.len(),
+            spec_path.display(),
+            findings.len() - failed,
+            failed
+        );
+    }
+    Ok(failed == 0)
+}
+
+/// Environment variable holding the anonymization key.
+pub(crate) const ANONYMIZE_KEY_ENV_VAR: &str = "DBSURVEYOR_ANONYMIZE_KEY";
+
+/// Writes a copy of a survey with server, database, and user names replaced
+/// by keyed pseudonyms (see [`dbsurveyor_core::anonymize`]).
+///
+/// The key is read from `DBSURVEYOR_ANONYMIZE_KEY`; reusing it across an
+/// engagement keeps pseudonyms consistent between surveys. The copy is
+/// written atomically and never replaces the input survey.
+pub(crate) async fn anonymize_survey(
+    input_path: &PathBuf,
+    output_path: Option<&PathBuf>,
+    limits: &InputLimits,
+    policy: &PathPolicy,
+) -> Result<()> {
+    let output_file = output_path
+        .cloned()
+        .unwrap_or_else(|| default_anonymized_path(input_path));
+    policy.check(&output_file)?;
+    if crate::sandbox::resolve(&output_file)? == crate::sandbox::resolve(input_path)? {
+        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+            format!(
+                "Refusing to overwrite {} with its anonymized copy; choose another --output",
+                input_path.display()
+            ),
+        ));
+    }
+
+    let key = std::env::var(ANONYMIZE_KEY_ENV_VAR).map_err(|_| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+            "anonymize requires a key in {ANONYMIZE_KEY_ENV_VAR}"
+        ))
+    })?;
+    let pseudonyms = Pseudonymizer::new(key.as_bytes())?;
+
+    let mut survey = schema::load_survey(input_path, limits).await?;
+    let replacements = dbsurveyor_core::anonymize::anonymize_survey(&mut survey, &pseudonyms);
+    if survey
+        .databases()
+        .iter()
+        .any(|schema| schema.samples.is_some())
+    {
+        warn!("Sampled rows are not anonymized; review them before sharing the survey");
+    }
+
+    let content = match &survey {
+        SurveyDocument::Database(schema) => serde_json::to_vec_pretty(schema),
+        SurveyDocument::Server(server) => serde_json::to_vec_pretty(server),
+    }
+    .map_err(|e| dbsurveyor_core::error::DbSurveyorError::Serialization {
+        context: "Failed to serialize anonymized survey".to_string(),
+        source: e,
+    })?;
+    write_atomic(&output_file, content).await?;
+
+    println!(
+        "Anonymized {} identifiers: {}",
+        replacements.len(),
+        output_file.display()
+    );
+    Ok(())
+}
+
+/// `<stem>.anonymized.json` beside the input; the stem drops the survey
+/// extensions, so `prod.dbsurveyor.json.zst` becomes
+/// `prod.anonymized.json`.
+fn default_anonymized_path(input_path: &Path) -> PathBuf {
+    let file_name = input_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|_| !schema::is_stdin(input_path))
+        .unwrap_or("schema");
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    input_path.with_file_name(format!("{}.anonymized.json", stem))
+}
+
+/// Searches a schema file, using its search index when one is available.
+///
+/// `input_path` may be an index file or a schema file. For a schema file the
+/// sibling `<input>.index.json` is used if it was built from a file of the
+/// same size; otherwise the schema is loaded and indexed in memory.
+pub(crate) async fn search_schema(
+    query: &str,
+    input_path: &PathBuf,
+    limit: usize,
+    limits: &InputLimits,
+) -> Result<()> {
+    let is_index = input_path.to_string_lossy().ends_with(search::INDEX_SUFFIX);
+    let index = if is_index {
+        load_search_index(input_path, limits).await?
+    } else {
+        let index_path = default_index_path(input_path);
+        let source_size = file_size(input_path).await?;
+        match load_search_index(&index_path, limits).await {
+            Ok(index) if index.source_size == source_size => index,
+            Ok(_) => {
+                warn!(
+                    "Search index {} is out of date; searching {} directly",
+                    index_path.display(),
+                    input_path.display()
+                );
+                SearchIndex::build_survey(
+                    &schema::load_survey(input_path, limits).await?,
+                    source_size,
+                )
+            }
+            Err(_) => SearchIndex::build_survey(
+                &schema::load_survey(input_path, limits).await?,
+                source_size,
+            ),
+        }
+    };
+
+    let hits = index.search(query, limit);
+    if hits.is_empty() {
+        println!("No matches for \"{}\" in {}", query, index.database);
+        return Ok(());
+    }
+    for object in hits {
+        let mut line = format!("{:<10} {}", object.kind.to_string(), object.name);
+        if let Some(classification) = &object.classification {
+            line.push_str(&format!(" [{}]", classification));
+        }
+        if let Some(comment) = &object.comment {
+            line.push_str(&format!(" - {}", comment));
+        }
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Lists the tables in each survey that contain a column matching `query`.
+pub(crate) async fn where_used(
+    query: &ColumnQuery,
+    survey_paths: &[PathBuf],
+    limits: &InputLimits,
+) -> Result<()> {
+    let mut total = 0;
+    let mut tables = BTreeSet::new();
+    for path in survey_paths {
+        let survey = schema::load_survey(path, limits).await?;
+        for usage in find_column_usage(&survey, query) {
+            let mut line = format!(
+                "{}: {}.{}.{} ({})",
+                path.display(),
+                usage.database,
+                usage.table,
+                usage.column,
+                usage.data_type
+            );
+            if let Some(classification) = usage.classification {
+                line.push_str(&format!(" [{}]", classification));
+            }
+            println!("{}", line);
+            tables.insert((path, usage.database, usage.table));
+            total += 1;
         }
     }
 
+    println!(
+        "{} matching columns in {} tables across {} surveys",
+        total,
+        tables.len(),
+        survey_paths.len()
+    );
     Ok(())
 }
+
+/// Reports names defined differently across the databases of the given
+/// surveys and bundles, and names shadowed across schemas within one.
+pub(crate) async fn find_collisions(
+    survey_paths: &[PathBuf],
+    json: bool,
+    limits: &InputLimits,
+) -> Result<()> {
+    let mut finder = CollisionFinder::new();
+    for path in survey_paths {
+        let file_name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let label = |server: Option<&str>, schema: &DatabaseSchema| match server {
+            Some(server) => format!("{}:{}/{}", file_name, server, schema.database_info.name),
+            None => format!("{}:{}", file_name, schema.database_info.name),
+        };
+        if schema::is_survey_bundle(path) {
+            let mut databases = schema::stream_survey_bundle(path, limits)?;
+            while let Some(database) = databases.recv().await {
+                let BundleDatabase { server, schema } = database?;
+                finder.add(&label(server.as_deref(), &schema), &schema);
+            }
+            continue;
+        }
+        match schema::load_survey(path, limits).await? {
+            SurveyDocument::Database(schema) => finder.add(&label(None, &schema), &schema),
+            SurveyDocument::Server(server) => {
+                for schema in &server.databases {
+                    finder.add(&label(Some(&server.server_info.host), schema), schema);
+                }
+            }
+        }
+    }
+    let report = finder.finish();
+
+    if json {
+        let content = serde_json::to_string_pretty(&report).map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Serialization {
+                context: "Failed to serialize collision report".to_string(),
+                source: e,
+            }
+        })?;
+        println!("{}", content);
+        return Ok(());
+    }
+    for collision in &report.collisions {
+        println!(
+            "{} {}: {} definitions",
+            collision.object_kind,
+            collision.name,
+            collision.definitions.len()
+        );
+        for (position, definition) in collision.definitions.iter().enumerate() {
+            println!("  [{}] {}", position + 1, definition.databases.join(", "));
+            for change in &definition.differences {
+                let mut line = format!(
+                    "      {} {} {}",
+                    change.kind, change.object_kind, change.object
+                );
+                if let Some(detail) = &change.detail {
+                    line.push_str(&format!(" ({})", detail));
+                }
+                println!("{}", line);
+            }
+        }
+    }
+    for shadowed in &report.shadowed {
+        println!(
+            "shadowed {} in {}: {}",
+            shadowed.name,
+            shadowed.database,
+            shadowed.objects.join(", ")
+        );
+    }
+    println!(
+        "{} colliding names and {} shadowed names across {} databases",
+        report.collisions.len(),
+        report.shadowed.len(),
+        report.databases
+    );
+    Ok(())
+}
+
+/// Default index location: the schema path with `.index.json` appended.
+fn default_index_path(input_path: &Path) -> PathBuf {
+    let mut path = input_path.as_os_str().to_owned();
+    path.push(search::INDEX_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Writes `content` to a temporary file beside `path` and renames it into
+/// place, so readers never see a partially written file.
+pub(crate) async fn write_atomic(path: &Path, content: Vec<u8>) -> Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        use std::io::Write;
+
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to create temporary file for {}", path.display()),
+                source: e,
+            }
+        })?;
+        tmp.write_all(&content)
+            .and_then(|()| tmp.as_file().sync_all())
+            .map_err(|e| dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to write {}", path.display()),
+                source: e,
+            })?;
+        tmp.persist(&path)
+            .map_err(|e| dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to persist {}", path.display()),
+                source: e.error,
+            })?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!("Write task failed: {}", e))
+    })?
+}
+
+async fn file_size(path: &Path) -> Result<u64> {
+    let metadata = tokio::fs::metadata(path).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to read {}", path.display()),
+            source: e,
+        }
+    })?;
+    Ok(metadata.len())
+}
+
+/// Approves the structure of a survey as the drift baseline.
+pub(crate) async fn set_baseline(
+    survey_path: &PathBuf,
+    baseline_path: &Path,
+    limits: &InputLimits,
+) -> Result<()> {
+    let survey = schema::load_survey(survey_path, limits).await?;
+    let baseline = Baseline::approve(survey.databases(), survey_path.display().to_string())?;
+
+    let content = serde_json::to_vec_pretty(&baseline).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: "Failed to serialize baseline".to_string(),
+            source: e,
+        }
+    })?;
+    tokio::fs::write(baseline_path, content)
+        .await
+        .map_err(|e| dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to write baseline to {}", baseline_path.display()),
+            source: e,
+        })?;
+
+    println!(
+        "Approved {} as baseline (sha256 {}): {}",
+        survey_path.display(),
+        baseline.checksum,
+        baseline_path.display()
+    );
+    Ok(())
+}
+
+/// Checks a survey against the approved baseline and prints every change.
+///
+/// Returns false when a change is not covered by the allow-lists.
+pub(crate) async fn check_baseline(
+    survey_path: &PathBuf,
+    baseline_path: &Path,
+    allow_paths: &[PathBuf],
+    limits: &InputLimits,
+) -> Result<bool> {
+    let baseline = load_baseline(baseline_path, limits).await?;
+    let mut allow = AllowList::default();
+    for path in allow_paths {
+        let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to read allow-list {}", path.display()),
+                source: e,
+            }
+        })?;
+        allow.extend_from_str(&content)?;
+    }
+    info!("Loaded {} allow-list rules", allow.len());
+
+    let survey = schema::load_survey(survey_path, limits).await?;
+    let changes = diff_surveys(&baseline.databases, survey.databases());
+    let mut unapproved = 0;
+    for change in &changes {
+        let allowed = allow.allows(change);
+        unapproved += usize::from(!allowed);
+        let mut line = format!(
+            "[{}] {}: {} {} {}",
+            if allowed { "allowed" } else { "DRIFT" },
+            change.database,
+            change.kind,
+            change.object_kind,
+            change.object
+        );
+        if let Some(detail) = &change.detail {
+            line.push_str(&format!(" ({})", detail));
+        }
+        println!("{}", line);
+    }
+
+    println!(
+        "{} changes since baseline approved {} ({} allowed, {} unapproved)",
+        changes.len(),
+        baseline.approved_at.format("%Y-%m-%d %H:%M UTC"),
+        changes.len() - unapproved,
+        unapproved
+    );
+    Ok(unapproved == 0)
+}
+
+async fn load_baseline(path: &Path, limits: &InputLimits) -> Result<Baseline> {
+    let rejected = |e: dbsurveyor_core::ValidationError| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!("Baseline rejected: {}", e))
+    };
+    limits
+        .check_size(file_size(path).await?)
+        .map_err(rejected)?;
+    let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to read baseline {}", path.display()),
+            source: e,
+        }
+    })?;
+    dbsurveyor_core::validation::check_input_limits(&content, limits).map_err(rejected)?;
+
+    let baseline: Baseline = serde_json::from_str(&content).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: format!("Failed to parse baseline {}", path.display()),
+            source: e,
+        }
+    })?;
+    baseline.verify()?;
+    Ok(baseline)
+}
+
+/// Reads an index file, applying the same input limits as schema files.
+async fn load_search_index(path: &Path, limits: &InputLimits) -> Result<SearchIndex> {
+    let rejected = |e: dbsurveyor_core::ValidationError| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+            "Search index rejected: {}",
+            e
+        ))
+    };
+    limits
+        .check_size(file_size(path).await?)
+        .map_err(rejected)?;
+    let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Io {
+            context: format!("Failed to read search index {}", path.display()),
+            source: e,
+        }
+    })?;
+    dbsurveyor_core::validation::check_input_limits(&content, limits).map_err(rejected)?;
+
+    let index: SearchIndex = serde_json::from_str(&content).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: format!("Failed to parse search index {}", path.display()),
+            source: e,
+        }
+    })?;
+    if !index.is_supported() {
+        return Err(dbsurveyor_core::error::DbSurveyorError::configuration(
+            format!(
+                "{} is not a supported search index; rebuild it with `dbsurveyor index`",
+                path.display()
+            ),
+        ));
+    }
+    Ok(index)
+}
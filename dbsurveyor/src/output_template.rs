@@ -0,0 +1,166 @@
+//! Output path templates for surveys with many databases.
+//!
+//! A template such as `docs/{server}/{database}.md` places each database's
+//! documentation in its own file, so a server survey or bundle produces a
+//! directory tree. Placeholders:
+//!
+//! - `{server}`: host of the server survey the database came from, or
+//!   `standalone` for single-database surveys
+//! - `{database}`: database name
+//!
+//! Substituted values come from the survey file and are not trusted: every
+//! character other than ASCII letters, digits, `-`, and `_` is replaced
+//! with `_`, so a name such as `../../etc` cannot leave the template's
+//! directory.
+
+use dbsurveyor_core::{DbSurveyorError, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Value of `{server}` for databases not collected from a server survey.
+const STANDALONE_SERVER: &str = "standalone";
+
+/// A validated output path template.
+#[derive(Debug, Clone)]
+pub(crate) struct OutputTemplate {
+    template: String,
+}
+
+impl OutputTemplate {
+    /// Parses and validates a template.
+    ///
+    /// # Errors
+    /// Returns an error if the template contains an unknown or unterminated
+    /// placeholder, does not contain `{database}` (every database would
+    /// overwrite the same file), or contains a `..` component.
+    pub(crate) fn parse(template: &str) -> Result<Self> {
+        let mut has_database = false;
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if rest[..open].contains('}') {
+                return Err(invalid(template, "unbalanced braces"));
+            }
+            let close = rest[open..].find('}').ok_or_else(|| {
+                invalid(
+                    template,
+                    "unterminated placeholder; expected {server} or {database}",
+                )
+            })?;
+            match &rest[open + 1..open + close] {
+                "database" => has_database = true,
+                "server" => {}
+                other => {
+                    return Err(invalid(
+                        template,
+                        &format!(
+                            "unknown placeholder {{{}}}; expected {{server}} or {{database}}",
+                            other
+                        ),
+                    ));
+                }
+            }
+            rest = &rest[open + close + 1..];
+        }
+        if rest.contains('}') {
+            return Err(invalid(template, "unbalanced braces"));
+        }
+        if !has_database {
+            return Err(invalid(
+                template,
+                "must contain {database} so each database gets its own output",
+            ));
+        }
+        // CWE-22: templates may only descend from their base directory
+        if Path::new(template)
+            .components()
+            .any(|c| c == Component::ParentDir)
+        {
+            return Err(invalid(template, "must not contain '..' components"));
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+        })
+    }
+
+    /// Output path for `database`, collected from `server` if it came from
+    /// a server survey.
+    pub(crate) fn render(&self, server: Option<&str>, database: &str) -> PathBuf {
+        let server = file_safe(server.unwrap_or(STANDALONE_SERVER));
+        let database = file_safe(database);
+        PathBuf::from(
+            self.template
+                .replace("{server}", &server)
+                .replace("{database}", &database),
+        )
+    }
+}
+
+/// Replaces characters that are unsafe in a file name with `_`.
+pub(crate) fn file_safe(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if safe.is_empty() {
+        "_".to_string()
+    } else {
+        safe
+    }
+}
+
+fn invalid(template: &str, reason: &str) -> DbSurveyorError {
+    DbSurveyorError::configuration(format!(
+        "Invalid output template '{}': {}",
+        template, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let template =
+            OutputTemplate::parse("docs/{server}/{database}.md").expect("valid template");
+        assert_eq!(
+            template.render(Some("db.internal"), "sales"),
+            PathBuf::from("docs/db_internal/sales.md")
+        );
+        assert_eq!(
+            template.render(None, "hr"),
+            PathBuf::from("docs/standalone/hr.md")
+        );
+    }
+
+    #[test]
+    fn test_render_cannot_escape_template_directory() {
+        let template =
+            OutputTemplate::parse("docs/{server}/{database}.md").expect("valid template");
+        let path = template.render(Some(".."), "../../etc/passwd");
+        assert!(!path.components().any(|c| c == Component::ParentDir));
+        assert_eq!(path, PathBuf::from("docs/__/______etc_passwd.md"));
+        assert_eq!(template.render(Some(""), ""), PathBuf::from("docs/_/_.md"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        for template in [
+            "docs/{server}.md",
+            "docs/{db}.md",
+            "docs/{database.md",
+            "docs/database}.md",
+            "docs/}{database}.md",
+            "../docs/{database}.md",
+            "docs/{server}/../{database}.md",
+        ] {
+            assert!(OutputTemplate::parse(template).is_err(), "{template}");
+        }
+    }
+}
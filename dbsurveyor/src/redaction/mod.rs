@@ -2,9 +2,11 @@
 
 use clap::ValueEnum;
 use dbsurveyor_core::models::TableSample;
+use dbsurveyor_core::redact::{Placeholder, Redact};
 use serde_json::Value;
+use std::fmt;
 
-const REDACTED_VALUE: &str = "[REDACTED]";
+pub(crate) const REDACTED_VALUE: &str = "[REDACTED]";
 
 /// Data redaction mode for postprocessor sample rendering.
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
@@ -20,7 +22,7 @@ pub enum RedactionMode {
 }
 
 /// A table sample with row values redacted for output rendering.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub(crate) struct RedactedTableSample {
     pub(crate) table_name: String,
     pub(crate) schema_name: Option<String>,
@@ -29,6 +31,22 @@ pub(crate) struct RedactedTableSample {
     pub(crate) warnings: Vec<String>,
 }
 
+dbsurveyor_core::redacted_debug!(RedactedTableSample);
+
+// Rows are left out even though they went through the redactor: with
+// `RedactionMode::None` they are the sampled values unchanged.
+impl Redact for RedactedTableSample {
+    fn fmt_redacted(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedactedTableSample")
+            .field("table_name", &self.table_name)
+            .field("schema_name", &self.schema_name)
+            .field("rows", &Placeholder::rows(self.rows.len()))
+            .field("mode_applied", &self.mode_applied)
+            .field("warnings", &self.warnings)
+            .finish()
+    }
+}
+
 /// Redacts sample rows without mutating source samples.
 pub(crate) struct Redactor {
     mode: RedactionMode,
@@ -130,6 +148,20 @@ fn should_redact_string(key: Option<&str>, value: &str, mode: &RedactionMode) ->
     "time",
 ];
 
+/// Classifies a field by name using the same patterns as redaction.
+///
+/// Returns `"Credential"` for fields masked even in minimal mode and `"PII"`
+/// for the personal-data fields masked in balanced mode.
+pub(crate) fn classify_field(name: &str) -> Option<&'static str> {
+    if matches_key(Some(name), MINIMAL_PATTERNS) {
+        Some("Credential")
+    } else if matches_key(Some(name), BALANCED_PATTERNS) {
+        Some("PII")
+    } else {
+        None
+    }
+}
+
 fn matches_key(key: Option<&str>, patterns: &[&str]) -> bool {
     let Some(key) = key else {
         return false;
@@ -177,6 +209,7 @@ fn sample_fixture() -> TableSample {
             collected_at: chrono::Utc::now(),
             warnings: Vec::new(),
             sample_status: None,
+            column_types: Vec::new(),
         }
     }
 
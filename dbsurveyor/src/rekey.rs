@@ -0,0 +1,172 @@
+//! Recipient management for encrypted surveys (`dbsurveyor rekey`).
+//!
+//! Encrypted surveys use envelope encryption: the payload is encrypted once
+//! with a random data key, and the data key is wrapped for each recipient
+//! password. Rekeying unlocks the envelope with one existing password and
+//! rewrites only the wrapped keys, so recipients can be added, removed, or
+//! given a new password without re-collecting the survey.
+
+use crate::schema;
+use dbsurveyor_core::security::encryption::{DEFAULT_RECIPIENT, EncryptedData, UnlockedEnvelope};
+use dbsurveyor_core::{DbSurveyorError, Result};
+use std::path::{Path, PathBuf};
+
+/// Environment variable consulted for a non-interactive new password
+/// (`--add` and `--rotate`).
+const NEW_PASSWORD_ENV_VAR: &str = "DBSURVEYOR_NEW_ENCRYPTION_PASSWORD";
+
+/// Minimum length for new passwords, matching the collector.
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Changes requested by `dbsurveyor rekey`.
+pub(crate) struct RekeyRequest<'a> {
+    /// Label of a recipient to add
+    pub(crate) add: Option<&'a str>,
+    /// Labels of recipients to remove
+    pub(crate) remove: &'a [String],
+    /// Replace the password of the recipient that opened the file
+    pub(crate) rotate: bool,
+}
+
+/// Prints the recipient labels of an encrypted survey.
+///
+/// Labels are stored in clear text, so no password is needed.
+pub(crate) async fn list_recipients(input: &Path) -> Result<()> {
+    let encrypted = read_encrypted(input).await?;
+    if encrypted.recipients.is_empty() {
+        println!("{DEFAULT_RECIPIENT} (single-password file; rekey to upgrade)");
+    }
+    for recipient in &encrypted.recipients {
+        println!("{}", recipient.label);
+    }
+    Ok(())
+}
+
+/// Applies `request` to the encrypted survey at `input`, writing the result
+/// to `output` (default: replace `input`).
+pub(crate) async fn rekey(
+    input: &Path,
+    output: Option<&PathBuf>,
+    request: &RekeyRequest<'_>,
+) -> Result<()> {
+    let encrypted = read_encrypted(input).await?;
+
+    let password = schema::decryption_password()?;
+    let new_password = if request.add.is_some() || request.rotate {
+        Some(new_password()?)
+    } else {
+        None
+    };
+    let add = request.add.map(str::to_string);
+    let remove = request.remove.to_vec();
+    let rotate = request.rotate;
+
+    let envelope = tokio::task::spawn_blocking(move || -> Result<UnlockedEnvelope> {
+        let mut envelope = UnlockedEnvelope::open(encrypted, &password)?;
+        if let Some(new_password) = &new_password {
+            if let Some(label) = &add {
+                envelope.add_recipient(label, new_password)?;
+            }
+            if rotate {
+                let label = envelope.unlocked_by().to_string();
+                envelope.rotate_recipient(&label, new_password)?;
+            }
+        }
+        for label in &remove {
+            envelope.remove_recipient(label)?;
+        }
+        Ok(envelope)
+    })
+    .await
+    .map_err(|e| DbSurveyorError::configuration(format!("Rekey task failed: {}", e)))??;
+
+    let recipients = envelope
+        .recipients()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let content = serde_json::to_vec_pretty(&envelope.into_encrypted()).map_err(|e| {
+        DbSurveyorError::Serialization {
+            context: "Failed to serialize rekeyed survey".to_string(),
+            source: e,
+        }
+    })?;
+    let output_file = output.map_or(input, PathBuf::as_path);
+    write_replacing(output_file, input, content).await?;
+
+    println!(
+        "Rekeyed {} (recipients: {})",
+        output_file.display(),
+        recipients.join(", ")
+    );
+    Ok(())
+}
+
+async fn read_encrypted(input: &Path) -> Result<EncryptedData> {
+    let data = tokio::fs::read(input)
+        .await
+        .map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to read {}", input.display()),
+            source: e,
+        })?;
+    schema::parse_encrypted(&data)
+}
+
+/// Gets the new password from `DBSURVEYOR_NEW_ENCRYPTION_PASSWORD` or
+/// prompts for it twice.
+fn new_password() -> Result<String> {
+    let password = if let Ok(password) = std::env::var(NEW_PASSWORD_ENV_VAR) {
+        password
+    } else {
+        let password = schema::prompt_password("Enter new password: ")?;
+        if schema::prompt_password("Confirm new password: ")? != password {
+            return Err(DbSurveyorError::configuration("Passwords do not match"));
+        }
+        password
+    };
+
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(DbSurveyorError::configuration(format!(
+            "Encryption password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+    Ok(password)
+}
+
+/// Writes `content` to a temporary file beside `path` and renames it into
+/// place, so an interrupted rekey never leaves a survey that no password
+/// can open. The result keeps the permissions of the `source` survey.
+async fn write_replacing(path: &Path, source: &Path, content: Vec<u8>) -> Result<()> {
+    let path = path.to_path_buf();
+    let source = source.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        use std::io::Write;
+
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to create temporary file for {}", path.display()),
+            source: e,
+        })?;
+        std::fs::metadata(&source)
+            .and_then(|metadata| tmp.as_file().set_permissions(metadata.permissions()))
+            .map_err(|e| DbSurveyorError::Io {
+                context: format!("Failed to copy permissions of {}", source.display()),
+                source: e,
+            })?;
+        tmp.write_all(&content)
+            .and_then(|()| tmp.as_file().sync_all())
+            .map_err(|e| DbSurveyorError::Io {
+                context: format!("Failed to write {}", path.display()),
+                source: e,
+            })?;
+        tmp.persist(&path).map_err(|e| DbSurveyorError::Io {
+            context: format!("Failed to replace {}", path.display()),
+            source: e.error,
+        })?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| DbSurveyorError::configuration(format!("Rekey write task failed: {}", e)))?
+}
@@ -0,0 +1,311 @@
+//! Output path validation.
+//!
+//! Every file the postprocessor writes is checked against a [`PathPolicy`]:
+//!
+//! - By default any absolute or relative path is accepted as long as it has
+//!   no `..` components (CWE-22).
+//! - With `--allow-path <dir>` (repeatable) the path must also resolve
+//!   inside one of the given directories. Symlinks in the existing part of
+//!   the path are resolved first, so a link cannot point the write elsewhere.
+//! - `--unrestricted-paths` turns all checks off.
+
+use crate::{Cli, Command};
+use clap::Args;
+use dbsurveyor_core::{DbSurveyorError, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Output path restrictions
+#[derive(Args)]
+pub struct PathPolicyArgs {
+    /// Directories outputs may be written to
+    #[arg(
+        long = "allow-path",
+        value_name = "DIR",
+        global = true,
+        help = "Only write outputs inside this directory (repeatable)"
+    )]
+    pub allow_path: Vec<PathBuf>,
+
+    /// Disable output path checks
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "allow_path",
+        help = "Disable all output path checks, including '..' traversal"
+    )]
+    pub unrestricted_paths: bool,
+}
+
+impl PathPolicyArgs {
+    /// Builds the policy, resolving the allowed directories.
+    ///
+    /// # Errors
+    /// Returns an error if an allowed directory does not exist.
+    pub fn to_path_policy(&self) -> Result<PathPolicy> {
+        let allowed = self
+            .allow_path
+            .iter()
+            .map(|dir| {
+                dir.canonicalize().map_err(|e| DbSurveyorError::Io {
+                    context: format!("Failed to resolve --allow-path {}", dir.display()),
+                    source: e,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(PathPolicy {
+            allowed,
+            unrestricted: self.unrestricted_paths,
+        })
+    }
+}
+
+/// Where outputs may be written.
+#[derive(Debug, Default)]
+pub struct PathPolicy {
+    /// Canonical allowed directories; empty means anywhere
+    allowed: Vec<PathBuf>,
+    unrestricted: bool,
+}
+
+impl PathPolicy {
+    /// Checks that `path` may be written.
+    ///
+    /// # Errors
+    /// Returns an error if `path` contains a `..` component or lies outside
+    /// every allowed directory.
+    pub(crate) fn check(&self, path: &Path) -> Result<()> {
+        if self.unrestricted {
+            return Ok(());
+        }
+        if path.components().any(|c| c == Component::ParentDir) {
+            return Err(DbSurveyorError::configuration(format!(
+                "Output path {} contains '..' traversal; use --unrestricted-paths to allow it",
+                path.display()
+            )));
+        }
+        if self.allowed.is_empty() {
+            return Ok(());
+        }
+
+        let resolved = resolve(path)?;
+        if self.allowed.iter().any(|dir| resolved.starts_with(dir)) {
+            return Ok(());
+        }
+        Err(DbSurveyorError::configuration(format!(
+            "Output path {} is outside the allowed directories ({}); add it with --allow-path",
+            path.display(),
+            self.allowed
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )))
+    }
+}
+
+/// Checks the output paths given on the command line before any work is
+/// done. Paths derived from them (per-database outputs, templates) and the
+/// default paths a command falls back to without `--output` are checked
+/// when written. The match is exhaustive so each subcommand must
+/// declare the path it writes.
+///
+/// # Errors
+/// Returns an error for the first output path the policy rejects.
+pub(crate) fn validate_cli_paths(cli: &Cli, policy: &PathPolicy) -> Result<()> {
+    let output = match &cli.command {
+        Some(Command::Generate(args)) => args.output.as_ref(),
+        #[cfg(feature = "experimental")]
+        Some(Command::Sql(args)) => args.output.as_ref(),
+        #[cfg(feature = "experimental")]
+        Some(Command::Compat(args)) => args.output.as_ref(),
+        Some(Command::Schema(args)) => args.output.as_ref(),
+        Some(Command::Index(args)) => args.output.as_ref(),
+        Some(Command::Anonymize(args)) => args.output.as_ref(),
+        Some(Command::Journal(args)) => args.output.as_ref(),
+        #[cfg(feature = "encryption")]
+        Some(Command::Rekey(args)) => {
+            if args.list {
+                None
+            } else {
+                args.output.as_ref().or(Some(&args.input))
+            }
+        }
+        Some(Command::Baseline(args)) => match &args.command {
+            crate::BaselineCommand::Set { baseline, .. } => Some(baseline),
+            crate::BaselineCommand::Check { .. } => None,
+        },
+        // Commands that only read surveys or print to stdout
+        #[cfg(feature = "experimental")]
+        Some(Command::Analyze(_)) => None,
+        Some(
+            Command::Validate(_)
+            | Command::Search(_)
+            | Command::WhereUsed(_)
+            | Command::Collisions(_)
+            | Command::Conform(_)
+            | Command::Completions { .. },
+        ) => None,
+        None => cli.output.as_ref(),
+    };
+    output
+        .into_iter()
+        .chain(cli.result_file.as_ref())
+        .try_for_each(|path| policy.check(path))
+}
+
+/// Absolute form of `path` with symlinks in its existing ancestors resolved.
+pub(crate) fn resolve(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map_err(|e| DbSurveyorError::Io {
+                context: "Failed to read the current directory".to_string(),
+                source: e,
+            })?
+            .join(path)
+    };
+
+    let mut missing = Vec::new();
+    let mut existing = absolute.as_path();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return Ok(missing
+                .iter()
+                .rev()
+                .fold(canonical, |resolved, part| resolved.join(part)));
+        }
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return Ok(absolute),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&Path]) -> PathPolicy {
+        PathPolicyArgs {
+            allow_path: allowed.iter().map(|dir| dir.to_path_buf()).collect(),
+            unrestricted_paths: false,
+        }
+        .to_path_policy()
+        .expect("allowed directories exist")
+    }
+
+    #[test]
+    fn test_default_policy_rejects_traversal_only() {
+        let policy = PathPolicy::default();
+        assert!(policy.check(Path::new("/tmp/docs/report.md")).is_ok());
+        assert!(policy.check(Path::new("docs/report.md")).is_ok());
+        assert!(policy.check(Path::new("docs/../../report.md")).is_err());
+
+        let unrestricted = PathPolicy {
+            unrestricted: true,
+            ..PathPolicy::default()
+        };
+        assert!(unrestricted.check(Path::new("../report.md")).is_ok());
+    }
+
+    #[test]
+    fn test_allowed_directories() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let docs = dir.path().join("docs");
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(&docs).expect("failed to create dir");
+        std::fs::create_dir_all(&other).expect("failed to create dir");
+
+        let policy = policy(&[&docs]);
+        assert!(policy.check(&docs.join("report.md")).is_ok());
+        assert!(policy.check(&docs.join("new/nested/report.md")).is_ok());
+        assert!(policy.check(&other.join("report.md")).is_err());
+        assert!(
+            policy
+                .check(&dir.path().join("docs_backup/report.md"))
+                .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cannot_escape_allowed_directory() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let docs = dir.path().join("docs");
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(&docs).expect("failed to create dir");
+        std::fs::create_dir_all(&other).expect("failed to create dir");
+        std::os::unix::fs::symlink(&other, docs.join("link")).expect("failed to create symlink");
+
+        let policy = policy(&[&docs]);
+        assert!(policy.check(&docs.join("link/report.md")).is_err());
+    }
+
+    #[test]
+    fn test_subcommand_outputs_are_checked() {
+        use clap::Parser;
+
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let policy = policy(&[dir.path()]);
+
+        for args in [
+            &[
+                "index",
+                "schema.json",
+                "--output",
+                "/outside/schema.index.json",
+            ][..],
+            &["anonymize", "schema.json", "--output", "/outside/anon.json"],
+            &[
+                "journal",
+                "survey.journal",
+                "--at",
+                "1",
+                "--output",
+                "/outside/s.json",
+            ],
+            &["schema", "--output", "/outside/schema.json"],
+        ] {
+            let cli = Cli::parse_from(std::iter::once("dbsurveyor").chain(args.iter().copied()));
+            assert!(validate_cli_paths(&cli, &policy).is_err(), "{:?}", args);
+        }
+
+        let read_only = Cli::parse_from(["dbsurveyor", "validate", "schema.json"]);
+        assert!(validate_cli_paths(&read_only, &policy).is_ok());
+    }
+
+    #[cfg(feature = "experimental")]
+    #[test]
+    fn test_compat_output_is_checked() {
+        use clap::Parser;
+        use std::ffi::OsStr;
+
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let docs = dir.path().join("docs");
+        std::fs::create_dir_all(&docs).expect("failed to create dir");
+        let policy = policy(&[&docs]);
+
+        let outside = Cli::parse_from([
+            "dbsurveyor",
+            "compat",
+            "schema.json",
+            "--output",
+            "/outside/compat.md",
+        ]);
+        assert!(validate_cli_paths(&outside, &policy).is_err());
+
+        let inside_path = docs.join("compat.md");
+        let inside = Cli::parse_from([
+            OsStr::new("dbsurveyor"),
+            OsStr::new("compat"),
+            OsStr::new("schema.json"),
+            OsStr::new("--output"),
+            inside_path.as_os_str(),
+        ]);
+        assert!(validate_cli_paths(&inside, &policy).is_ok());
+    }
+}
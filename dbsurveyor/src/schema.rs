@@ -1,53 +1,108 @@
 //! Schema file loading with support for JSON, compressed, and encrypted formats.
+//!
+//! Both single-database surveys and multi-database server surveys are
+//! accepted; see [`SurveyDocument`]. Tenants folded by the collector's
+//! `--dedup-tenants` are expanded on load.
+//!
+//! An input path of `-` reads the survey from stdin; its format is detected
+//! from the content instead of the extension.
+//!
+//! Survey bundles in JSON Lines form (`.jsonl`, optionally `.jsonl.zst`)
+//! hold one complete survey per line and are streamed with
+//! [`stream_survey_bundle`] instead of being loaded whole.
 
 use crate::create_spinner;
-use dbsurveyor_core::{Result, models::DatabaseSchema};
-use std::path::PathBuf;
+use dbsurveyor_core::error::DbSurveyorError;
+use dbsurveyor_core::{
+    InputLimits, Result,
+    models::{DatabaseSchema, SurveyDocument},
+};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::sync::mpsc;
 use tracing::info;
+#[cfg(feature = "encryption")]
+use zeroize::Zeroizing;
 
 /// Environment variable consulted for a non-interactive decryption password.
 #[cfg(feature = "encryption")]
 const PASSWORD_ENV_VAR: &str = "DBSURVEYOR_ENCRYPTION_PASSWORD";
 
+/// Input path that reads the survey from stdin.
+pub(crate) const STDIN_PATH: &str = "-";
+
 /// Zstandard frame magic number, used to detect compressed payloads inside
-/// encrypted files (combined `--compress --encrypt` collector output).
-#[cfg(feature = "encryption")]
+/// encrypted files (combined `--compress --encrypt` collector output) and
+/// compressed surveys read from stdin.
+#[cfg(any(feature = "encryption", feature = "compression"))]
 const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
 
-/// Loads schema from file with support for different formats.
-pub(crate) async fn load_schema(input_path: &PathBuf) -> Result<DatabaseSchema> {
+/// Loads a survey from file with support for different formats.
+///
+/// The file is treated as untrusted: `limits` are enforced on the file
+/// size, on decompressed or decrypted content, and on the JSON itself.
+pub(crate) async fn load_survey(
+    input_path: &PathBuf,
+    limits: &InputLimits,
+) -> Result<SurveyDocument> {
     let spinner = create_spinner("Loading schema...");
-    let result = load_schema_inner(input_path, &spinner).await;
+    let result = load_survey_inner(input_path, limits, &spinner).await;
     spinner.finish_and_clear();
-    result
+
+    // Deduplicated tenants are documented like any other database
+    match result? {
+        SurveyDocument::Server(mut server) => {
+            dbsurveyor_core::dedup::expand_tenants(&mut server)?;
+            Ok(SurveyDocument::Server(server))
+        }
+        survey => Ok(survey),
+    }
 }
 
-/// Inner implementation for schema loading, separated to guarantee spinner cleanup.
-async fn load_schema_inner(
+/// Inner implementation for survey loading, separated to guarantee spinner cleanup.
+async fn load_survey_inner(
     input_path: &PathBuf,
+    limits: &InputLimits,
     spinner: &indicatif::ProgressBar,
-) -> Result<DatabaseSchema> {
+) -> Result<SurveyDocument> {
     info!("Loading schema from {}", input_path.display());
 
-    let file_content = tokio::fs::read(input_path).await.map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::Io {
-            context: format!("Failed to read {}", input_path.display()),
-            source: e,
-        }
-    })?;
+    let (file_content, extension) = if is_stdin(input_path) {
+        spinner.set_message("Reading stdin...");
+        let data = read_stdin(limits).await?;
+        let format = sniff_format(&data);
+        (data, format)
+    } else {
+        let metadata = tokio::fs::metadata(input_path).await.map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to read {}", input_path.display()),
+                source: e,
+            }
+        })?;
+        limits.check_size(metadata.len()).map_err(limit_error)?;
 
-    // Detect file format based on extension and content
-    let extension = input_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("");
+        let data = tokio::fs::read(input_path).await.map_err(|e| {
+            dbsurveyor_core::error::DbSurveyorError::Io {
+                context: format!("Failed to read {}", input_path.display()),
+                source: e,
+            }
+        })?;
+
+        // Detect file format based on extension
+        let extension = input_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("");
+        (data, extension)
+    };
 
     match extension {
         "enc" => {
             spinner.set_message("Decrypting...");
             #[cfg(feature = "encryption")]
             {
-                load_encrypted_schema(&file_content).await
+                load_encrypted_schema(&file_content, limits).await
             }
             #[cfg(not(feature = "encryption"))]
             {
@@ -60,7 +115,7 @@ async fn load_schema_inner(
             spinner.set_message("Decompressing...");
             #[cfg(feature = "compression")]
             {
-                load_compressed_schema(&file_content).await
+                load_compressed_schema(&file_content, limits).await
             }
             #[cfg(not(feature = "compression"))]
             {
@@ -71,13 +126,202 @@ async fn load_schema_inner(
         }
         _ => {
             spinner.set_message("Parsing JSON...");
-            load_json_schema(&file_content).await
+            load_json_schema(&file_content, limits).await
         }
     }
 }
 
+/// Whether `path` is an encrypted survey (`.enc`).
+pub(crate) fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "enc")
+}
+
+/// Whether `path` means stdin.
+pub(crate) fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN_PATH)
+}
+
+/// Reads all of stdin on the blocking thread pool, stopping one byte past
+/// the size limit so oversized input is rejected without being buffered.
+async fn read_stdin(limits: &InputLimits) -> Result<Vec<u8>> {
+    let max_size = limits.max_file_size_bytes;
+    let data = tokio::task::spawn_blocking(move || -> std::io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        std::io::stdin()
+            .lock()
+            .take(max_size.saturating_add(1))
+            .read_to_end(&mut data)?;
+        Ok(data)
+    })
+    .await
+    .map_err(|e| DbSurveyorError::configuration(format!("Stdin reader task failed: {}", e)))?
+    .map_err(|e| DbSurveyorError::Io {
+        context: "Failed to read survey from stdin".to_string(),
+        source: e,
+    })?;
+    limits.check_size(data.len() as u64).map_err(limit_error)?;
+    Ok(data)
+}
+
+/// Detects the format of a survey read from stdin, returning the file
+/// extension the collector would have given it.
+fn sniff_format(data: &[u8]) -> &'static str {
+    #[cfg(any(feature = "encryption", feature = "compression"))]
+    if data.starts_with(&ZSTD_MAGIC) {
+        return "zst";
+    }
+    #[cfg(feature = "encryption")]
+    if serde_json::from_slice::<dbsurveyor_core::security::encryption::EncryptedData>(data).is_ok()
+    {
+        return "enc";
+    }
+    #[cfg(not(any(feature = "encryption", feature = "compression")))]
+    let _ = data;
+    "json"
+}
+
+/// Whether `path` is a JSON Lines survey bundle (`.jsonl` or `.jsonl.zst`).
+pub(crate) fn is_survey_bundle(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    name.ends_with(".jsonl") || name.ends_with(".jsonl.zst")
+}
+
+/// A database read from a survey bundle.
+pub(crate) struct BundleDatabase {
+    /// Host of the server survey the database came from
+    pub(crate) server: Option<String>,
+    pub(crate) schema: DatabaseSchema,
+}
+
+/// Streams the databases of a JSON Lines survey bundle.
+///
+/// Each non-empty line is a complete survey, either a database or a server.
+/// Lines are read and validated on the blocking thread pool one at a time
+/// and each database is handed over before the next line is read, so only
+/// a couple of databases are in memory at once. `limits` apply to each line
+/// rather than to the bundle as a whole.
+///
+/// # Errors
+/// Returns an error if the bundle cannot be opened. Errors reading or
+/// validating a line are sent through the channel, which then closes.
+pub(crate) fn stream_survey_bundle(
+    input_path: &Path,
+    limits: &InputLimits,
+) -> Result<mpsc::Receiver<Result<BundleDatabase>>> {
+    info!("Streaming survey bundle {}", input_path.display());
+    let mut reader = open_bundle(input_path)?;
+    let limits = *limits;
+    let (sender, receiver) = mpsc::channel(1);
+
+    tokio::task::spawn_blocking(move || {
+        let mut line_number = 0usize;
+        loop {
+            line_number += 1;
+            let databases = match read_bundle_line(reader.as_mut(), &limits) {
+                Ok(None) => return,
+                Ok(Some(line)) if line.trim().is_empty() => continue,
+                Ok(Some(line)) => parse_bundle_line(&line, &limits),
+                Err(e) => Err(e),
+            }
+            .map_err(|e| {
+                DbSurveyorError::configuration(format!("Bundle line {}: {}", line_number, e))
+            });
+
+            let databases = match databases {
+                Ok(databases) => databases,
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(e));
+                    return;
+                }
+            };
+            for database in databases {
+                // The receiver is gone when generation failed
+                if sender.blocking_send(Ok(database)).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+fn open_bundle(input_path: &Path) -> Result<Box<dyn BufRead + Send>> {
+    let file = std::fs::File::open(input_path).map_err(|e| DbSurveyorError::Io {
+        context: format!("Failed to read {}", input_path.display()),
+        source: e,
+    })?;
+    if input_path.extension().and_then(|ext| ext.to_str()) != Some("zst") {
+        return Ok(Box::new(BufReader::new(file)));
+    }
+
+    #[cfg(feature = "compression")]
+    {
+        let decoder = zstd::Decoder::new(file)
+            .map_err(|e| DbSurveyorError::configuration(format!("Decompression failed: {}", e)))?;
+        Ok(Box::new(BufReader::new(decoder)))
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        Err(DbSurveyorError::configuration(
+            "Compression support not available. Compile with --features compression",
+        ))
+    }
+}
+
+/// Reads one line, stopping one byte past the size limit so an oversized
+/// line is rejected without being buffered whole.
+fn read_bundle_line(reader: &mut dyn BufRead, limits: &InputLimits) -> Result<Option<String>> {
+    let mut line = Vec::new();
+    let read = reader
+        .take(limits.max_file_size_bytes.saturating_add(1))
+        .read_until(b'\n', &mut line)
+        .map_err(|e| DbSurveyorError::Io {
+            context: "Failed to read survey bundle".to_string(),
+            source: e,
+        })?;
+    if read == 0 {
+        return Ok(None);
+    }
+    limits.check_size(line.len() as u64).map_err(limit_error)?;
+    String::from_utf8(line).map(Some).map_err(|e| {
+        DbSurveyorError::configuration(format!("Invalid UTF-8 in survey bundle: {}", e))
+    })
+}
+
+fn parse_bundle_line(line: &str, limits: &InputLimits) -> Result<Vec<BundleDatabase>> {
+    let survey = dbsurveyor_core::validate_and_parse_survey_with_limits(line, limits)
+        .map_err(|e| DbSurveyorError::configuration(format!("Schema validation failed: {}", e)))?;
+    match survey {
+        SurveyDocument::Database(schema) => Ok(vec![BundleDatabase {
+            server: None,
+            schema: *schema,
+        }]),
+        SurveyDocument::Server(mut server) => {
+            dbsurveyor_core::dedup::expand_tenants(&mut server)?;
+            let host = server.server_info.host;
+            Ok(server
+                .databases
+                .into_iter()
+                .map(|schema| BundleDatabase {
+                    server: Some(host.clone()),
+                    schema,
+                })
+                .collect())
+        }
+    }
+}
+
+/// Converts an input limit violation into a loader error.
+fn limit_error(e: dbsurveyor_core::ValidationError) -> dbsurveyor_core::error::DbSurveyorError {
+    dbsurveyor_core::error::DbSurveyorError::configuration(format!("Schema file rejected: {}", e))
+}
+
 /// Loads JSON schema from bytes.
-async fn load_json_schema(data: &[u8]) -> Result<DatabaseSchema> {
+async fn load_json_schema(data: &[u8], limits: &InputLimits) -> Result<SurveyDocument> {
     let json_str = std::str::from_utf8(data).map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::configuration(format!(
             "Invalid UTF-8 in schema file: {}",
@@ -86,7 +330,7 @@ async fn load_json_schema(data: &[u8]) -> Result<DatabaseSchema> {
     })?;
 
     // Use the validation function that combines parsing, validation, and deserialization
-    dbsurveyor_core::validate_and_parse_schema(json_str).map_err(|e| {
+    dbsurveyor_core::validate_and_parse_survey_with_limits(json_str, limits).map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::configuration(format!(
             "Schema validation failed: {}",
             e
@@ -96,14 +340,20 @@ async fn load_json_schema(data: &[u8]) -> Result<DatabaseSchema> {
 
 /// Decompresses a Zstandard payload to a UTF-8 string on the blocking
 /// thread pool.
+///
+/// Decompression stops one byte past the size limit, so a compression bomb
+/// is rejected without being fully expanded.
 #[cfg(feature = "compression")]
-async fn decompress_zstd(data: &[u8]) -> Result<String> {
+async fn decompress_zstd(data: &[u8], limits: &InputLimits) -> Result<String> {
     let owned_data = data.to_vec();
-    tokio::task::spawn_blocking(move || -> std::io::Result<String> {
+    let max_size = limits.max_file_size_bytes;
+    let buf = tokio::task::spawn_blocking(move || -> std::io::Result<Vec<u8>> {
         use std::io::Read;
-        let mut decoder = zstd::Decoder::new(owned_data.as_slice())?;
-        let mut buf = String::new();
-        decoder.read_to_string(&mut buf)?;
+        let decoder = zstd::Decoder::new(owned_data.as_slice())?;
+        let mut buf = Vec::new();
+        decoder
+            .take(max_size.saturating_add(1))
+            .read_to_end(&mut buf)?;
         Ok(buf)
     })
     .await
@@ -118,15 +368,23 @@ async fn decompress_zstd(data: &[u8]) -> Result<String> {
             "Decompression failed: {}",
             e
         ))
+    })?;
+
+    limits.check_size(buf.len() as u64).map_err(limit_error)?;
+    String::from_utf8(buf).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+            "Invalid UTF-8 in decompressed schema: {}",
+            e
+        ))
     })
 }
 
 /// Loads compressed schema.
 #[cfg(feature = "compression")]
-async fn load_compressed_schema(data: &[u8]) -> Result<DatabaseSchema> {
-    let decompressed = decompress_zstd(data).await?;
+async fn load_compressed_schema(data: &[u8], limits: &InputLimits) -> Result<SurveyDocument> {
+    let decompressed = decompress_zstd(data, limits).await?;
 
-    dbsurveyor_core::validate_and_parse_schema(&decompressed).map_err(|e| {
+    dbsurveyor_core::validate_and_parse_survey_with_limits(&decompressed, limits).map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::configuration(format!(
             "Decompressed schema validation failed: {}",
             e
@@ -136,43 +394,11 @@ async fn load_compressed_schema(data: &[u8]) -> Result<DatabaseSchema> {
 
 /// Loads encrypted schema.
 #[cfg(feature = "encryption")]
-async fn load_encrypted_schema(data: &[u8]) -> Result<DatabaseSchema> {
-    use dbsurveyor_core::security::encryption::{EncryptedData, decrypt_data_async};
-    use std::io::{self, Write};
-
-    let json_str = std::str::from_utf8(data).map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::configuration(format!(
-            "Invalid UTF-8 in encrypted file: {}",
-            e
-        ))
-    })?;
-
-    let encrypted: EncryptedData = serde_json::from_str(json_str).map_err(|e| {
-        dbsurveyor_core::error::DbSurveyorError::Serialization {
-            context: "Failed to parse encrypted data structure".to_string(),
-            source: e,
-        }
-    })?;
-
-    // Get password from the environment or prompt the user
-    let password = if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
-        password
-    } else {
-        print!("Enter decryption password: ");
-        io::stdout().flush().map_err(|e| {
-            dbsurveyor_core::error::DbSurveyorError::configuration(format!(
-                "Failed to flush stdout before reading password: {}",
-                e
-            ))
-        })?;
-        rpassword::read_password().map_err(|e| {
-            dbsurveyor_core::error::DbSurveyorError::configuration(format!(
-                "Failed to read password: {}",
-                e
-            ))
-        })?
-    };
+async fn load_encrypted_schema(data: &[u8], limits: &InputLimits) -> Result<SurveyDocument> {
+    use dbsurveyor_core::security::encryption::decrypt_data_async;
 
+    let encrypted = parse_encrypted(data)?;
+    let password = decryption_password()?;
     let decrypted_data = decrypt_data_async(encrypted, &password).await?;
 
     // Combined collector output (--compress --encrypt) compresses the JSON
@@ -180,13 +406,14 @@ async fn load_encrypted_schema(data: &[u8]) -> Result<DatabaseSchema> {
     if decrypted_data.starts_with(&ZSTD_MAGIC) {
         #[cfg(feature = "compression")]
         {
-            let decompressed = decompress_zstd(&decrypted_data).await?;
-            return dbsurveyor_core::validate_and_parse_schema(&decompressed).map_err(|e| {
-                dbsurveyor_core::error::DbSurveyorError::configuration(format!(
-                    "Decrypted schema validation failed: {}",
-                    e
-                ))
-            });
+            let decompressed = decompress_zstd(&decrypted_data, limits).await?;
+            return dbsurveyor_core::validate_and_parse_survey_with_limits(&decompressed, limits)
+                .map_err(|e| {
+                    dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+                        "Decrypted schema validation failed: {}",
+                        e
+                    ))
+                });
         }
         #[cfg(not(feature = "compression"))]
         {
@@ -203,10 +430,68 @@ async fn load_encrypted_schema(data: &[u8]) -> Result<DatabaseSchema> {
         ))
     })?;
 
-    dbsurveyor_core::validate_and_parse_schema(decrypted_str).map_err(|e| {
+    dbsurveyor_core::validate_and_parse_survey_with_limits(decrypted_str, limits).map_err(|e| {
         dbsurveyor_core::error::DbSurveyorError::configuration(format!(
             "Decrypted schema validation failed: {}",
             e
         ))
     })
 }
+
+/// Parses the JSON envelope of an encrypted survey.
+#[cfg(feature = "encryption")]
+pub(crate) fn parse_encrypted(
+    data: &[u8],
+) -> Result<dbsurveyor_core::security::encryption::EncryptedData> {
+    let json_str = std::str::from_utf8(data).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::configuration(format!(
+            "Invalid UTF-8 in encrypted file: {}",
+            e
+        ))
+    })?;
+
+    serde_json::from_str(json_str).map_err(|e| {
+        dbsurveyor_core::error::DbSurveyorError::Serialization {
+            context: "Failed to parse encrypted data structure".to_string(),
+            source: e,
+        }
+    })
+}
+
+/// Password file set by `--password-file`.
+static PASSWORD_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Reads the password for encrypted surveys from `path` instead of the
+/// environment or a prompt.
+pub(crate) fn use_password_file(path: PathBuf) {
+    let _ = PASSWORD_FILE.set(path);
+}
+
+/// Gets the decryption password from `--password-file`,
+/// `DBSURVEYOR_ENCRYPTION_PASSWORD`, or a prompt.
+#[cfg(feature = "encryption")]
+pub(crate) fn decryption_password() -> Result<Zeroizing<String>> {
+    if let Some(path) = PASSWORD_FILE.get() {
+        return dbsurveyor_core::security::read_secret_file(path);
+    }
+    if let Ok(password) = std::env::var(PASSWORD_ENV_VAR) {
+        return Ok(Zeroizing::new(password));
+    }
+    prompt_password("Enter decryption password: ").map(Zeroizing::new)
+}
+
+/// Reads a password from the terminal without echoing it.
+#[cfg(feature = "encryption")]
+pub(crate) fn prompt_password(prompt: &str) -> Result<String> {
+    use std::io::{self, Write};
+
+    print!("{prompt}");
+    io::stdout().flush().map_err(|e| {
+        DbSurveyorError::configuration(format!(
+            "Failed to flush stdout before reading password: {}",
+            e
+        ))
+    })?;
+    rpassword::read_password()
+        .map_err(|e| DbSurveyorError::configuration(format!("Failed to read password: {}", e)))
+}
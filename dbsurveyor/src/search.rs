@@ -0,0 +1,395 @@
+//! Search index over survey object names, comments, and classifications.
+//!
+//! `dbsurveyor index` writes the index next to the survey so `search` can
+//! answer queries without loading (or decrypting) a large survey file. The
+//! index is an inverted index from lowercase words to object ids, stored as
+//! compact JSON.
+
+use crate::markdown::qualified_name;
+use crate::naming::split_words;
+use crate::redaction::classify_field;
+use dbsurveyor_core::models::{DatabaseSchema, SurveyDocument};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+/// Suffix appended to the survey path for the default index location.
+pub(crate) const INDEX_SUFFIX: &str = ".index.json";
+
+const INDEX_FORMAT: &str = "dbsurveyor-search-index";
+const INDEX_VERSION: u32 = 2;
+
+/// Score for a query word equal to an indexed word (prefix matches score 1)
+const EXACT_WORD_SCORE: u32 = 2;
+/// Bonus when the whole query equals the object's unqualified name
+const EXACT_NAME_SCORE: u32 = 5;
+
+/// Kind of surveyed object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ObjectKind {
+    Table,
+    Column,
+    View,
+    Index,
+    Procedure,
+    Function,
+    Trigger,
+    CustomType,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Table => "table",
+            Self::Column => "column",
+            Self::View => "view",
+            Self::Index => "index",
+            Self::Procedure => "procedure",
+            Self::Function => "function",
+            Self::Trigger => "trigger",
+            Self::CustomType => "type",
+        })
+    }
+}
+
+/// An object that can be found by search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct IndexedObject {
+    pub(crate) kind: ObjectKind,
+    /// Qualified name, e.g. `public.orders.customer_id`
+    pub(crate) name: String,
+    /// Name without schema or table qualifiers, e.g. `customer_id`; stored
+    /// because a quoted identifier may itself contain a dot
+    pub(crate) short_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) classification: Option<String>,
+}
+
+/// Inverted index over the objects of one survey.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SearchIndex {
+    format: String,
+    version: u32,
+    pub(crate) database: String,
+    /// Size in bytes of the survey file the index was built from
+    pub(crate) source_size: u64,
+    pub(crate) objects: Vec<IndexedObject>,
+    /// Word -> ascending ids (positions in `objects`) of objects containing it
+    terms: BTreeMap<String, Vec<u32>>,
+}
+
+impl SearchIndex {
+    /// Indexes every table, column, view, index, routine, trigger, and custom type.
+    pub(crate) fn build(schema: &DatabaseSchema, source_size: u64) -> Self {
+        Self::from_objects(
+            schema.database_info.name.clone(),
+            schema_objects(schema),
+            source_size,
+        )
+    }
+
+    /// Indexes a survey of either shape.
+    ///
+    /// Objects of a server survey are qualified with their database name,
+    /// e.g. `sales.public.orders`.
+    pub(crate) fn build_survey(survey: &SurveyDocument, source_size: u64) -> Self {
+        match survey {
+            SurveyDocument::Database(schema) => Self::build(schema, source_size),
+            SurveyDocument::Server(server) => {
+                let objects = server
+                    .databases
+                    .iter()
+                    .flat_map(|schema| {
+                        let database = &schema.database_info.name;
+                        schema_objects(schema).into_iter().map(move |mut object| {
+                            object.name = format!("{}.{}", database, object.name);
+                            object
+                        })
+                    })
+                    .collect();
+                let label = format!(
+                    "{} ({} databases)",
+                    server.server_info.host,
+                    server.databases.len()
+                );
+                Self::from_objects(label, objects, source_size)
+            }
+        }
+    }
+
+    fn from_objects(database: String, objects: Vec<IndexedObject>, source_size: u64) -> Self {
+        let mut terms: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (id, object) in objects.iter().enumerate() {
+            let id = u32::try_from(id).unwrap_or(u32::MAX);
+            // Qualifiers are not indexed so that a table's columns do not all
+            // match a search for the table
+            let mut words: BTreeSet<String> = split_words(&object.short_name).into_iter().collect();
+            words.extend(object.comment.iter().flat_map(|c| split_words(c)));
+            words.extend(object.classification.iter().flat_map(|c| split_words(c)));
+            for word in words {
+                terms.entry(word).or_default().push(id);
+            }
+        }
+
+        Self {
+            format: INDEX_FORMAT.to_string(),
+            version: INDEX_VERSION,
+            database,
+            source_size,
+            objects,
+            terms,
+        }
+    }
+
+    /// Checks that deserialized JSON is an index this version can read.
+    pub(crate) fn is_supported(&self) -> bool {
+        self.format == INDEX_FORMAT && self.version == INDEX_VERSION
+    }
+
+    /// Number of distinct indexed words.
+    pub(crate) fn term_count(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Finds objects containing every word of `query`, best matches first.
+    ///
+    /// Query words match indexed words they are a prefix of, so `cust`
+    /// finds `customer_id`.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<&IndexedObject> {
+        let words = split_words(query);
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<u32, u32> = HashMap::new();
+        for (position, word) in words.iter().enumerate() {
+            let mut word_scores: HashMap<u32, u32> = HashMap::new();
+            for (term, ids) in self
+                .terms
+                .range(word.clone()..)
+                .take_while(|(term, _)| term.starts_with(word.as_str()))
+            {
+                let score = if term == word { EXACT_WORD_SCORE } else { 1 };
+                for &id in ids {
+                    let best = word_scores.entry(id).or_default();
+                    *best = (*best).max(score);
+                }
+            }
+            if position == 0 {
+                scores = word_scores;
+            } else {
+                scores.retain(|id, score| match word_scores.get(id) {
+                    Some(word_score) => {
+                        *score = score.saturating_add(*word_score);
+                        true
+                    }
+                    None => false,
+                });
+            }
+        }
+
+        let query = query.trim().to_lowercase();
+        let mut hits: Vec<(u32, &IndexedObject)> = scores
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let object = self.objects.get(usize::try_from(id).ok()?)?;
+                let score = if object.short_name.to_lowercase() == query {
+                    score.saturating_add(EXACT_NAME_SCORE)
+                } else {
+                    score
+                };
+                Some((score, object))
+            })
+            .collect();
+        hits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        hits.into_iter()
+            .take(limit)
+            .map(|(_, object)| object)
+            .collect()
+    }
+}
+
+/// Every searchable object of one database schema.
+fn schema_objects(schema: &DatabaseSchema) -> Vec<IndexedObject> {
+    let mut objects = Vec::new();
+    for table in &schema.tables {
+        let table_name = qualified_name(table.schema.as_deref(), &table.name);
+        for column in &table.columns {
+            objects.push(IndexedObject {
+                kind: ObjectKind::Column,
+                name: format!("{}.{}", table_name, column.name),
+                short_name: column.name.clone(),
+                comment: column.comment.clone(),
+                classification: classify_field(&column.name).map(str::to_string),
+            });
+        }
+        objects.push(IndexedObject {
+            kind: ObjectKind::Table,
+            name: table_name,
+            short_name: table.name.clone(),
+            comment: table.comment.clone(),
+            classification: None,
+        });
+    }
+    for view in &schema.views {
+        objects.push(object(
+            ObjectKind::View,
+            view.schema.as_deref(),
+            &view.name,
+            view.comment.clone(),
+        ));
+    }
+    let indexes = schema
+        .indexes
+        .iter()
+        .chain(schema.tables.iter().flat_map(|t| t.indexes.iter()));
+    for index in indexes {
+        objects.push(object(
+            ObjectKind::Index,
+            index.schema.as_deref(),
+            &index.name,
+            None,
+        ));
+    }
+    for (kind, routines) in [
+        (ObjectKind::Procedure, &schema.procedures),
+        (ObjectKind::Function, &schema.functions),
+    ] {
+        for routine in routines {
+            objects.push(object(
+                kind,
+                routine.schema.as_deref(),
+                &routine.name,
+                routine.comment.clone(),
+            ));
+        }
+    }
+    for trigger in &schema.triggers {
+        objects.push(object(
+            ObjectKind::Trigger,
+            trigger.schema.as_deref(),
+            &trigger.name,
+            None,
+        ));
+    }
+    for custom_type in &schema.custom_types {
+        objects.push(object(
+            ObjectKind::CustomType,
+            custom_type.schema.as_deref(),
+            &custom_type.name,
+            None,
+        ));
+    }
+    objects
+}
+
+fn object(
+    kind: ObjectKind,
+    schema: Option<&str>,
+    name: &str,
+    comment: Option<String>,
+) -> IndexedObject {
+    IndexedObject {
+        kind,
+        name: qualified_name(schema, name),
+        short_name: name.to_string(),
+        comment,
+        classification: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, DatabaseInfo, Table, UnifiedDataType, View};
+
+    fn column(name: &str, comment: Option<&str>) -> Column {
+        Column {
+            comment: comment.map(str::to_string),
+            ..Column::new(name.to_string(), UnifiedDataType::Boolean)
+        }
+    }
+
+    fn fixture() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables.push(Table {
+            comment: Some("People who placed orders".to_string()),
+            ..Table::new(
+                "customers".to_string(),
+                Some("public".to_string()),
+                vec![
+                    column("customer_id", None),
+                    column("email", None),
+                    column("loyalty_tier", Some("Gold, silver, or bronze")),
+                ],
+            )
+        });
+        schema.views.push(View {
+            name: "customerOrders".to_string(),
+            schema: Some("public".to_string()),
+            definition: None,
+            columns: Vec::new(),
+            comment: None,
+        });
+        schema
+    }
+
+    fn names(hits: &[&IndexedObject]) -> Vec<String> {
+        hits.iter().map(|o| o.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_search_matches_names_comments_and_classifications() {
+        let index = SearchIndex::build(&fixture(), 0);
+
+        assert_eq!(names(&index.search("customers", 10)), ["public.customers"]);
+        assert_eq!(
+            names(&index.search("customer", 10)),
+            [
+                "public.customerOrders",
+                "public.customers.customer_id",
+                "public.customers"
+            ]
+        );
+        assert_eq!(
+            names(&index.search("gold", 10)),
+            ["public.customers.loyalty_tier"]
+        );
+        assert_eq!(names(&index.search("pii", 10)), ["public.customers.email"]);
+        assert_eq!(
+            names(&index.search("placed order", 10)),
+            ["public.customers"]
+        );
+        assert!(index.search("invoice", 10).is_empty());
+        assert_eq!(index.search("cust", 1).len(), 1);
+    }
+
+    #[test]
+    fn test_dotted_names_are_not_split() {
+        let mut schema = fixture();
+        schema.tables[0].columns.push(column("geo.lat", None));
+
+        let index = SearchIndex::build(&schema, 0);
+        let hits = index.search("geo.lat", 10);
+        assert_eq!(names(&hits), ["public.customers.geo.lat"]);
+        assert_eq!(hits[0].short_name, "geo.lat");
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        let index = SearchIndex::build(&fixture(), 42);
+        let json = serde_json::to_string(&index).expect("serializes");
+        let loaded: SearchIndex = serde_json::from_str(&json).expect("deserializes");
+
+        assert!(loaded.is_supported());
+        assert_eq!(loaded.source_size, 42);
+        assert_eq!(loaded.objects, index.objects);
+        assert_eq!(
+            names(&loaded.search("tier", 10)),
+            ["public.customers.loyalty_tier"]
+        );
+    }
+}
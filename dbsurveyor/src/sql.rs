@@ -0,0 +1,1175 @@
+//! SQL DDL reconstruction with cross-dialect translation.
+//!
+//! Tables are rebuilt from the unified model rather than from the source
+//! engine's DDL, so a survey of one engine can be emitted for another:
+//! column types are mapped from [`UnifiedDataType`], identifiers are quoted
+//! with the target's rules, and keys and indexes are recreated.
+//!
+//! Anything that cannot be expressed faithfully in the target dialect is
+//! either approximated or left out, and recorded as [`Untranslated`] so the
+//! script never silently differs from the surveyed database. Expressions
+//! (non-literal defaults, generated columns, check constraints) and code
+//! objects (views, routines, triggers) are written in the source engine's
+//! SQL and are always reported rather than copied.
+
+use crate::SqlDialect;
+use crate::markdown::qualified_name;
+use dbsurveyor_core::classify_default;
+use dbsurveyor_core::models::{
+    Column, ConstraintType, DatabaseSchema, DefaultValue, ForeignKey, IdentityGeneration, Index,
+    ReferentialAction, SortDirection, Table, UnifiedDataType,
+};
+use std::fmt::Write;
+
+/// Largest `VARCHAR` length before falling back to an unbounded text type
+const MYSQL_MAX_VARCHAR: u32 = 16_383;
+const SQLSERVER_MAX_NVARCHAR: u32 = 4_000;
+const SQLSERVER_MAX_VARBINARY: u32 = 8_000;
+const MYSQL_MAX_BIT: u32 = 64;
+
+impl SqlDialect {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SqlDialect::PostgreSQL => "PostgreSQL",
+            SqlDialect::MySQL => "MySQL",
+            SqlDialect::SQLite => "SQLite",
+            SqlDialect::SqlServer => "SQL Server",
+            SqlDialect::Generic => "ANSI SQL",
+        }
+    }
+
+    /// Quotes an identifier, escaping embedded quote characters.
+    fn quote(self, identifier: &str) -> String {
+        match self {
+            SqlDialect::MySQL => format!("`{}`", identifier.replace('`', "``")),
+            SqlDialect::SqlServer => format!("[{}]", identifier.replace(']', "]]")),
+            SqlDialect::PostgreSQL | SqlDialect::SQLite | SqlDialect::Generic => {
+                format!("\"{}\"", identifier.replace('"', "\"\""))
+            }
+        }
+    }
+
+    /// Quotes a possibly schema-qualified name. SQLite has no schemas.
+    fn quote_qualified(self, schema: Option<&str>, name: &str) -> String {
+        match schema {
+            Some(schema) if self != SqlDialect::SQLite => {
+                format!("{}.{}", self.quote(schema), self.quote(name))
+            }
+            _ => self.quote(name),
+        }
+    }
+
+    /// Type used for values the target has no native type for.
+    fn text_type(self) -> &'static str {
+        match self {
+            SqlDialect::PostgreSQL | SqlDialect::SQLite => "TEXT",
+            SqlDialect::MySQL => "LONGTEXT",
+            SqlDialect::SqlServer => "NVARCHAR(MAX)",
+            SqlDialect::Generic => "CLOB",
+        }
+    }
+}
+
+/// A construct left out of, or approximated in, the generated DDL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Untranslated {
+    /// Qualified name of the affected object
+    pub(crate) object: String,
+    pub(crate) reason: String,
+}
+
+/// Generated DDL and the constructs it could not reproduce.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SqlScript {
+    pub(crate) ddl: String,
+    pub(crate) untranslated: Vec<Untranslated>,
+}
+
+/// How faithfully a target column type holds the source type's values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Fit {
+    /// Same range and precision
+    Exact,
+    /// A larger type that holds every source value
+    Widened,
+    /// An approximation that loses values or attributes
+    Narrowed,
+    /// No such type in the target; values fall back to text or binary
+    NoEquivalent,
+}
+
+impl Fit {
+    /// Every fit, from best to worst.
+    pub(crate) const ALL: [Self; 4] = [
+        Self::Exact,
+        Self::Widened,
+        Self::Narrowed,
+        Self::NoEquivalent,
+    ];
+}
+
+impl std::fmt::Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Exact => "exact",
+            Self::Widened => "widened",
+            Self::Narrowed => "narrowed",
+            Self::NoEquivalent => "no equivalent",
+        })
+    }
+}
+
+/// A target column type, with the reason when it only approximates the source.
+pub(crate) struct MappedType {
+    pub(crate) sql: String,
+    pub(crate) lossy: Option<String>,
+    pub(crate) fit: Fit,
+}
+
+impl MappedType {
+    fn exact(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            lossy: None,
+            fit: Fit::Exact,
+        }
+    }
+
+    fn widened(sql: impl Into<String>) -> Self {
+        Self {
+            fit: Fit::Widened,
+            ..Self::exact(sql)
+        }
+    }
+
+    fn lossy(sql: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            lossy: Some(reason.into()),
+            fit: Fit::Narrowed,
+        }
+    }
+
+    /// A fallback type for values the target has no type for.
+    fn fallback(sql: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            fit: Fit::NoEquivalent,
+            ..Self::lossy(sql, reason)
+        }
+    }
+}
+
+/// Renders `CREATE TABLE`, `CREATE INDEX`, and foreign key statements for
+/// `schema` in `dialect`, followed by a comment block listing everything
+/// that could not be translated.
+pub(crate) fn render_ddl(schema: &DatabaseSchema, dialect: SqlDialect) -> SqlScript {
+    let mut script = SqlScript::default();
+    let mut ddl = String::new();
+    let _ = writeln!(
+        ddl,
+        "-- Database: {}",
+        single_line(&schema.database_info.name)
+    );
+    let _ = writeln!(ddl, "-- Generated by DBSurveyor for {}", dialect.label());
+
+    for table in &schema.tables {
+        ddl.push('\n');
+        ddl.push_str(&create_table(table, dialect, &mut script.untranslated));
+        for index in &table.indexes {
+            if let Some(statement) = create_index(table, index, dialect, &mut script.untranslated) {
+                ddl.push_str(&statement);
+            }
+        }
+    }
+
+    // SQLite cannot add constraints to an existing table, so its foreign
+    // keys are declared inline; everyone else gets them once all tables exist.
+    if dialect != SqlDialect::SQLite {
+        let mut foreign_keys = String::new();
+        for table in &schema.tables {
+            for (position, fk) in table.foreign_keys.iter().enumerate() {
+                let Some(clause) = foreign_key_clause(table, fk, dialect, &mut script.untranslated)
+                else {
+                    continue;
+                };
+                let name = fk
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("fk_{}_{}", table.name, position + 1));
+                let _ = writeln!(
+                    foreign_keys,
+                    "ALTER TABLE {} ADD CONSTRAINT {} {};",
+                    dialect.quote_qualified(table.schema.as_deref(), &table.name),
+                    dialect.quote(&name),
+                    clause
+                );
+            }
+        }
+        if !foreign_keys.is_empty() {
+            ddl.push('\n');
+            ddl.push_str(&foreign_keys);
+        }
+    }
+
+    report_code_objects(schema, &mut script.untranslated);
+
+    if !script.untranslated.is_empty() {
+        let _ = writeln!(
+            ddl,
+            "\n-- Untranslated constructs ({}):",
+            script.untranslated.len()
+        );
+        for item in &script.untranslated {
+            let _ = writeln!(
+                ddl,
+                "-- {}: {}",
+                single_line(&item.object),
+                single_line(&item.reason)
+            );
+        }
+    }
+
+    script.ddl = ddl;
+    script
+}
+
+fn create_table(
+    table: &Table,
+    dialect: SqlDialect,
+    untranslated: &mut Vec<Untranslated>,
+) -> String {
+    let table_name = qualified_name(table.schema.as_deref(), &table.name);
+    let primary_key = table
+        .primary_key
+        .as_ref()
+        .filter(|pk| !pk.columns.is_empty());
+    // SQLite only auto-increments a column declared `INTEGER PRIMARY KEY`
+    let inline_primary_key = dialect == SqlDialect::SQLite
+        && primary_key.is_some_and(|pk| {
+            pk.columns.len() == 1
+                && table
+                    .columns
+                    .iter()
+                    .any(|c| c.name == pk.columns[0] && c.is_auto_increment)
+        });
+
+    let mut lines: Vec<String> = table
+        .columns
+        .iter()
+        .map(|column| {
+            column_definition(
+                &table_name,
+                column,
+                inline_primary_key,
+                dialect,
+                untranslated,
+            )
+        })
+        .collect();
+
+    if let Some(pk) = primary_key.filter(|_| !inline_primary_key) {
+        lines.push(format!(
+            "{}PRIMARY KEY ({})",
+            constraint_name(pk.name.as_deref(), dialect),
+            quote_list(&pk.columns, dialect)
+        ));
+    }
+
+    for constraint in &table.constraints {
+        let object = format!("{}.{}", table_name, constraint.name);
+        match constraint.constraint_type {
+            ConstraintType::Unique if !constraint.columns.is_empty() => lines.push(format!(
+                "{}UNIQUE ({})",
+                constraint_name(Some(&constraint.name), dialect),
+                quote_list(&constraint.columns, dialect)
+            )),
+            ConstraintType::Check => untranslated.push(Untranslated {
+                object,
+                reason: format!(
+                    "check constraint not translated: {}",
+                    constraint
+                        .check_clause
+                        .as_deref()
+                        .unwrap_or("(no expression)")
+                ),
+            }),
+            _ => {}
+        }
+    }
+
+    if dialect == SqlDialect::SQLite {
+        for fk in &table.foreign_keys {
+            if let Some(clause) = foreign_key_clause(table, fk, dialect, untranslated) {
+                lines.push(format!(
+                    "{}{}",
+                    constraint_name(fk.name.as_deref(), dialect),
+                    clause
+                ));
+            }
+        }
+    }
+
+    let mut statement = String::new();
+    if let Some(comment) = table.comment.as_deref().filter(|c| !c.trim().is_empty()) {
+        let _ = writeln!(statement, "-- {}", single_line(comment));
+    }
+    let _ = writeln!(
+        statement,
+        "CREATE TABLE {} (\n    {}\n);",
+        dialect.quote_qualified(table.schema.as_deref(), &table.name),
+        lines.join(",\n    ")
+    );
+    statement
+}
+
+fn column_definition(
+    table_name: &str,
+    column: &Column,
+    inline_primary_key: bool,
+    dialect: SqlDialect,
+    untranslated: &mut Vec<Untranslated>,
+) -> String {
+    let object = format!("{}.{}", table_name, column.name);
+    let mapped = map_type(&column.data_type, dialect);
+    if let Some(reason) = mapped.lossy {
+        untranslated.push(Untranslated {
+            object: object.clone(),
+            reason,
+        });
+    }
+
+    let mut definition = format!("{} {}", dialect.quote(&column.name), mapped.sql);
+
+    if column.is_auto_increment {
+        let generation = column.identity.as_ref().map(|identity| identity.generation);
+        // Legacy serial columns stay serial when the target is PostgreSQL
+        let serial = match (dialect, generation) {
+            (SqlDialect::PostgreSQL, Some(IdentityGeneration::Serial)) => serial_type(&mapped.sql),
+            _ => None,
+        };
+        match dialect {
+            SqlDialect::PostgreSQL | SqlDialect::Generic => match (serial, generation) {
+                (Some(serial), _) => {
+                    definition = format!("{} {}", dialect.quote(&column.name), serial);
+                }
+                (None, Some(IdentityGeneration::Always)) => {
+                    definition.push_str(" GENERATED ALWAYS AS IDENTITY");
+                }
+                _ => definition.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+            },
+            SqlDialect::MySQL => definition.push_str(" AUTO_INCREMENT"),
+            SqlDialect::SqlServer => definition.push_str(" IDENTITY(1,1)"),
+            SqlDialect::SQLite if inline_primary_key => {
+                return format!(
+                    "{} INTEGER PRIMARY KEY AUTOINCREMENT",
+                    dialect.quote(&column.name)
+                );
+            }
+            SqlDialect::SQLite => untranslated.push(Untranslated {
+                object: object.clone(),
+                reason: "auto-increment requires a single-column INTEGER PRIMARY KEY in SQLite"
+                    .to_string(),
+            }),
+        }
+    }
+
+    if !column.is_nullable {
+        definition.push_str(" NOT NULL");
+    }
+
+    if let Some(generated) = &column.generated {
+        untranslated.push(Untranslated {
+            object: object.clone(),
+            reason: format!(
+                "generated column emitted as a plain column: {}",
+                generated.expression
+            ),
+        });
+    } else if let Some(default) = column.default_value.as_deref()
+        && !column.is_auto_increment
+    {
+        let normalized = column
+            .normalized_default
+            .clone()
+            .unwrap_or_else(|| classify_default(default));
+        match translate_default(&normalized, dialect) {
+            Some(value) => {
+                let _ = write!(definition, " DEFAULT {}", value);
+            }
+            None => untranslated.push(Untranslated {
+                object,
+                reason: format!("default not translated: {}", default),
+            }),
+        }
+    }
+
+    definition
+}
+
+fn create_index(
+    table: &Table,
+    index: &Index,
+    dialect: SqlDialect,
+    untranslated: &mut Vec<Untranslated>,
+) -> Option<String> {
+    // Primary keys and unique constraints are declared on the table
+    let backs_constraint = table.constraints.iter().any(|constraint| {
+        constraint.constraint_type == ConstraintType::Unique && constraint.name == index.name
+    });
+    if index.is_primary || backs_constraint || index.columns.is_empty() {
+        return None;
+    }
+    if let Some(index_type) = index
+        .index_type
+        .as_deref()
+        .filter(|t| !t.eq_ignore_ascii_case("btree"))
+    {
+        untranslated.push(Untranslated {
+            object: qualified_name(index.schema.as_deref(), &index.name),
+            reason: format!("{} index emitted as a default index", index_type),
+        });
+    }
+
+    let columns: Vec<String> = index
+        .columns
+        .iter()
+        .map(|column| match column.sort_order {
+            Some(SortDirection::Descending) => format!("{} DESC", dialect.quote(&column.name)),
+            _ => dialect.quote(&column.name),
+        })
+        .collect();
+    Some(format!(
+        "CREATE {}INDEX {} ON {} ({});\n",
+        if index.is_unique { "UNIQUE " } else { "" },
+        // The index always lives with its table, so its name is unqualified
+        dialect.quote(&index.name),
+        dialect.quote_qualified(table.schema.as_deref(), &table.name),
+        columns.join(", ")
+    ))
+}
+
+/// Builds `FOREIGN KEY (...) REFERENCES ...`, or `None` when the key is
+/// malformed.
+fn foreign_key_clause(
+    table: &Table,
+    fk: &ForeignKey,
+    dialect: SqlDialect,
+    untranslated: &mut Vec<Untranslated>,
+) -> Option<String> {
+    if fk.columns.is_empty() || fk.columns.len() != fk.referenced_columns.len() {
+        untranslated.push(Untranslated {
+            object: qualified_name(table.schema.as_deref(), &table.name),
+            reason: format!(
+                "foreign key to {} skipped: column lists do not match",
+                fk.referenced_table
+            ),
+        });
+        return None;
+    }
+    let referenced_schema = fk.referenced_schema.as_deref().or(table.schema.as_deref());
+    let mut clause = format!(
+        "FOREIGN KEY ({}) REFERENCES {} ({})",
+        quote_list(&fk.columns, dialect),
+        dialect.quote_qualified(referenced_schema, &fk.referenced_table),
+        quote_list(&fk.referenced_columns, dialect)
+    );
+    for (event, action) in [("DELETE", &fk.on_delete), ("UPDATE", &fk.on_update)] {
+        let Some(action) = action else { continue };
+        match referential_action(action, dialect) {
+            Some(sql) => {
+                let _ = write!(clause, " ON {} {}", event, sql);
+            }
+            None => untranslated.push(Untranslated {
+                object: qualified_name(table.schema.as_deref(), &table.name),
+                reason: format!(
+                    "ON {} SET DEFAULT on foreign key to {} is not supported",
+                    event, fk.referenced_table
+                ),
+            }),
+        }
+    }
+    Some(clause)
+}
+
+fn referential_action(action: &ReferentialAction, dialect: SqlDialect) -> Option<&'static str> {
+    match action {
+        ReferentialAction::Cascade => Some("CASCADE"),
+        ReferentialAction::SetNull => Some("SET NULL"),
+        ReferentialAction::SetDefault if dialect == SqlDialect::MySQL => None,
+        ReferentialAction::SetDefault => Some("SET DEFAULT"),
+        // SQL Server has no RESTRICT; NO ACTION also rejects the change
+        ReferentialAction::Restrict if dialect == SqlDialect::SqlServer => Some("NO ACTION"),
+        ReferentialAction::Restrict => Some("RESTRICT"),
+        ReferentialAction::NoAction => Some("NO ACTION"),
+    }
+}
+
+/// Views, routines, and triggers are written in the source engine's SQL.
+fn report_code_objects(schema: &DatabaseSchema, untranslated: &mut Vec<Untranslated>) {
+    let objects = schema
+        .views
+        .iter()
+        .map(|v| ("view", v.schema.as_deref(), v.name.as_str()))
+        .chain(
+            schema
+                .procedures
+                .iter()
+                .map(|p| ("procedure", p.schema.as_deref(), p.name.as_str())),
+        )
+        .chain(
+            schema
+                .functions
+                .iter()
+                .map(|f| ("function", f.schema.as_deref(), f.name.as_str())),
+        )
+        .chain(
+            schema
+                .triggers
+                .iter()
+                .map(|t| ("trigger", t.schema.as_deref(), t.name.as_str())),
+        );
+    for (kind, object_schema, name) in objects {
+        untranslated.push(Untranslated {
+            object: qualified_name(object_schema, name),
+            reason: format!("{} definitions are not translated", kind),
+        });
+    }
+}
+
+/// Maps a unified type to `dialect`, the reverse of the collectors'
+/// per-engine type mappers.
+pub(crate) fn map_type(data_type: &UnifiedDataType, dialect: SqlDialect) -> MappedType {
+    use SqlDialect::{Generic, MySQL, PostgreSQL, SQLite, SqlServer};
+
+    match data_type {
+        UnifiedDataType::String {
+            max_length: Some(length),
+        } => match dialect {
+            MySQL if *length > MYSQL_MAX_VARCHAR => MappedType::widened("LONGTEXT"),
+            SqlServer if *length > SQLSERVER_MAX_NVARCHAR => MappedType::widened("NVARCHAR(MAX)"),
+            SqlServer => MappedType::exact(format!("NVARCHAR({})", length)),
+            _ => MappedType::exact(format!("VARCHAR({})", length)),
+        },
+        UnifiedDataType::String { max_length: None } => MappedType::exact(dialect.text_type()),
+        UnifiedDataType::Integer { bits, signed } => map_integer(*bits, *signed, dialect),
+        UnifiedDataType::Float { precision } => {
+            let single = precision.is_some_and(|p| p <= 24);
+            // SQLite's REAL is always double precision
+            if dialect == SQLite && single {
+                return MappedType::widened("REAL");
+            }
+            MappedType::exact(match (dialect, single) {
+                (SQLite, _) | (PostgreSQL | Generic | SqlServer, true) => "REAL",
+                (MySQL, true) => "FLOAT",
+                (MySQL, false) => "DOUBLE",
+                (SqlServer, false) => "FLOAT",
+                (PostgreSQL | Generic, false) => "DOUBLE PRECISION",
+            })
+        }
+        UnifiedDataType::Decimal { precision, scale } => map_decimal(*precision, *scale, dialect),
+        UnifiedDataType::Boolean => match dialect {
+            SqlServer => MappedType::exact("BIT"),
+            _ => MappedType::exact("BOOLEAN"),
+        },
+        UnifiedDataType::DateTime { with_timezone } => match (dialect, with_timezone) {
+            (PostgreSQL | Generic, false) => MappedType::exact("TIMESTAMP"),
+            (PostgreSQL | Generic, true) => MappedType::exact("TIMESTAMP WITH TIME ZONE"),
+            (SqlServer, false) => MappedType::exact("DATETIME2"),
+            (SqlServer, true) => MappedType::exact("DATETIMEOFFSET"),
+            (MySQL | SQLite, false) => MappedType::exact("DATETIME"),
+            (MySQL | SQLite, true) => {
+                MappedType::lossy("DATETIME", "time zone offset is not stored")
+            }
+        },
+        UnifiedDataType::Date => MappedType::exact("DATE"),
+        UnifiedDataType::Time { with_timezone } => match (dialect, with_timezone) {
+            (PostgreSQL | Generic, true) => MappedType::exact("TIME WITH TIME ZONE"),
+            (_, true) => MappedType::lossy("TIME", "time zone offset is not stored"),
+            (_, false) => MappedType::exact("TIME"),
+        },
+        UnifiedDataType::Interval => match dialect {
+            PostgreSQL | Generic => MappedType::exact("INTERVAL"),
+            _ => MappedType::fallback(dialect.text_type(), "interval stored as text"),
+        },
+        UnifiedDataType::Binary { max_length } => match (dialect, max_length) {
+            (PostgreSQL, _) => MappedType::exact("BYTEA"),
+            (SQLite, _) | (Generic, None) => MappedType::exact("BLOB"),
+            (MySQL, Some(length)) if *length <= u32::from(u16::MAX) => {
+                MappedType::exact(format!("VARBINARY({})", length))
+            }
+            (MySQL, Some(_)) => MappedType::widened("LONGBLOB"),
+            (MySQL, None) => MappedType::exact("LONGBLOB"),
+            (SqlServer, Some(length)) if *length <= SQLSERVER_MAX_VARBINARY => {
+                MappedType::exact(format!("VARBINARY({})", length))
+            }
+            (SqlServer, Some(_)) => MappedType::widened("VARBINARY(MAX)"),
+            (SqlServer, None) => MappedType::exact("VARBINARY(MAX)"),
+            (Generic, Some(length)) => MappedType::exact(format!("VARBINARY({})", length)),
+        },
+        UnifiedDataType::Bit { length } => match (dialect, length) {
+            (PostgreSQL | Generic, Some(length)) => MappedType::exact(format!("BIT({})", length)),
+            (PostgreSQL | Generic, None) => MappedType::exact("BIT VARYING"),
+            (MySQL, Some(length)) if *length <= MYSQL_MAX_BIT => {
+                MappedType::exact(format!("BIT({})", length))
+            }
+            (SqlServer, Some(1)) => MappedType::exact("BIT"),
+            _ => {
+                let bytes = UnifiedDataType::Binary {
+                    max_length: length.map(|bits| bits.div_ceil(8)),
+                };
+                MappedType::fallback(map_type(&bytes, dialect).sql, "bit string stored as binary")
+            }
+        },
+        UnifiedDataType::Json => match dialect {
+            PostgreSQL => MappedType::exact("JSONB"),
+            MySQL | Generic => MappedType::exact("JSON"),
+            SQLite => MappedType::exact("TEXT"),
+            SqlServer => MappedType::fallback("NVARCHAR(MAX)", "JSON stored as text"),
+        },
+        UnifiedDataType::Uuid => match dialect {
+            PostgreSQL => MappedType::exact("UUID"),
+            SqlServer => MappedType::exact("UNIQUEIDENTIFIER"),
+            SQLite => MappedType::exact("TEXT"),
+            MySQL | Generic => MappedType::exact("CHAR(36)"),
+        },
+        UnifiedDataType::NetworkAddress => match dialect {
+            PostgreSQL => MappedType::exact("INET"),
+            SqlServer => MappedType::fallback("NVARCHAR(45)", "network address stored as text"),
+            _ => MappedType::fallback("VARCHAR(45)", "network address stored as text"),
+        },
+        UnifiedDataType::Array { element_type } => match dialect {
+            PostgreSQL => {
+                let element = map_type(element_type, dialect);
+                MappedType {
+                    sql: format!("{}[]", element.sql),
+                    ..element
+                }
+            }
+            MySQL => MappedType::fallback("JSON", "array stored as a JSON document"),
+            _ => MappedType::fallback(dialect.text_type(), "array stored as JSON text"),
+        },
+        UnifiedDataType::Custom { type_name } => MappedType::fallback(
+            dialect.text_type(),
+            format!("type {} has no {} equivalent", type_name, dialect.label()),
+        ),
+    }
+}
+
+fn map_decimal(precision: Option<u32>, scale: Option<u32>, dialect: SqlDialect) -> MappedType {
+    // (maximum precision, maximum scale, scale used for unconstrained decimals)
+    let limits = match dialect {
+        SqlDialect::MySQL => Some((65, 30, 30)),
+        SqlDialect::SqlServer => Some((38, 38, 18)),
+        SqlDialect::SQLite => {
+            return MappedType::lossy("NUMERIC", "fractional values are stored as REAL");
+        }
+        SqlDialect::PostgreSQL | SqlDialect::Generic => None,
+    };
+    let render = |precision: u32, scale: Option<u32>| match scale {
+        Some(scale) => format!("DECIMAL({},{})", precision, scale),
+        None => format!("DECIMAL({})", precision),
+    };
+
+    match (precision, limits) {
+        (None, None) => MappedType::exact("DECIMAL"),
+        (Some(precision), None) => MappedType::exact(render(precision, scale)),
+        (None, Some((max_precision, _, default_scale))) => MappedType::lossy(
+            render(max_precision, Some(default_scale)),
+            "unconstrained decimal given a fixed precision and scale",
+        ),
+        (Some(precision), Some((max_precision, max_scale, _))) => {
+            if precision <= max_precision && scale.is_none_or(|s| s <= max_scale) {
+                MappedType::exact(render(precision, scale))
+            } else {
+                let clamped = precision.min(max_precision);
+                MappedType::lossy(
+                    render(clamped, scale.map(|s| s.min(max_scale).min(clamped))),
+                    format!(
+                        "decimal exceeds the {} precision or scale limit",
+                        dialect.label()
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// PostgreSQL `serial` pseudo-type for an integer column type.
+fn serial_type(sql: &str) -> Option<&'static str> {
+    match sql {
+        "SMALLINT" => Some("SMALLSERIAL"),
+        "INTEGER" => Some("SERIAL"),
+        "BIGINT" => Some("BIGSERIAL"),
+        _ => None,
+    }
+}
+
+fn map_integer(bits: u8, signed: bool, dialect: SqlDialect) -> MappedType {
+    match dialect {
+        // SQLite stores every integer as a signed 64-bit value
+        SqlDialect::SQLite => match (bits, signed) {
+            (0..=63, _) => MappedType::widened("INTEGER"),
+            (64, true) => MappedType::exact("INTEGER"),
+            _ => MappedType::lossy(
+                "INTEGER",
+                "values beyond the signed 64-bit range do not fit",
+            ),
+        },
+        SqlDialect::MySQL => {
+            let name = match bits {
+                0..=8 => "TINYINT",
+                9..=16 => "SMALLINT",
+                17..=24 => "MEDIUMINT",
+                25..=32 => "INT",
+                _ => "BIGINT",
+            };
+            MappedType::exact(if signed {
+                name.to_string()
+            } else {
+                format!("{} UNSIGNED", name)
+            })
+        }
+        SqlDialect::PostgreSQL | SqlDialect::SqlServer | SqlDialect::Generic => {
+            // SQL Server's TINYINT is the only unsigned type; otherwise
+            // widen so the full unsigned range still fits
+            if dialect == SqlDialect::SqlServer && !signed && bits <= 8 {
+                return MappedType::exact("TINYINT");
+            }
+            let needed = if signed { bits } else { bits.saturating_add(1) };
+            let (target_bits, sql) = match needed {
+                0..=16 => (16, "SMALLINT"),
+                17..=32 => (
+                    32,
+                    if dialect == SqlDialect::SqlServer {
+                        "INT"
+                    } else {
+                        "INTEGER"
+                    },
+                ),
+                33..=64 => (64, "BIGINT"),
+                _ => return MappedType::widened("NUMERIC(20)"),
+            };
+            if target_bits > bits {
+                MappedType::widened(sql)
+            } else {
+                MappedType::exact(sql)
+            }
+        }
+    }
+}
+
+/// Translates portable defaults; other expressions are engine-specific and yield `None`.
+fn translate_default(default: &DefaultValue, dialect: SqlDialect) -> Option<String> {
+    match default {
+        DefaultValue::Literal { value } => Some(match (dialect, value.as_str()) {
+            (SqlDialect::SqlServer, "TRUE") => "1".to_string(),
+            (SqlDialect::SqlServer, "FALSE") => "0".to_string(),
+            _ => value.clone(),
+        }),
+        DefaultValue::CurrentTimestamp => Some("CURRENT_TIMESTAMP".to_string()),
+        DefaultValue::UuidGenerate => match dialect {
+            SqlDialect::PostgreSQL => Some("gen_random_uuid()".to_string()),
+            SqlDialect::MySQL => Some("(UUID())".to_string()),
+            SqlDialect::SqlServer => Some("NEWID()".to_string()),
+            _ => None,
+        },
+        DefaultValue::Expression { expression }
+            if expression.trim().eq_ignore_ascii_case("CURRENT_DATE") =>
+        {
+            Some("CURRENT_DATE".to_string())
+        }
+        DefaultValue::SequenceNextval { .. } | DefaultValue::Expression { .. } => None,
+    }
+}
+
+fn constraint_name(name: Option<&str>, dialect: SqlDialect) -> String {
+    name.map(|name| format!("CONSTRAINT {} ", dialect.quote(name)))
+        .unwrap_or_default()
+}
+
+fn quote_list(names: &[String], dialect: SqlDialect) -> String {
+    names
+        .iter()
+        .map(|name| dialect.quote(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{
+        ColumnIdentity, DatabaseInfo, GeneratedColumn, GenerationKind, IndexColumn, PrimaryKey,
+        View,
+    };
+
+    fn column(name: &str, data_type: UnifiedDataType) -> Column {
+        Column::new(name.to_string(), data_type)
+    }
+
+    fn table(name: &str, columns: Vec<Column>) -> Table {
+        Table::new(name.to_string(), Some("shop".to_string()), columns)
+    }
+
+    /// A MySQL-flavoured survey: unsigned keys, a set-typed column, and a view.
+    fn mysql_survey() -> DatabaseSchema {
+        let mut id = column(
+            "id",
+            UnifiedDataType::Integer {
+                bits: 32,
+                signed: false,
+            },
+        );
+        id.is_nullable = false;
+        id.is_auto_increment = true;
+
+        let mut status = column(
+            "status",
+            UnifiedDataType::String {
+                max_length: Some(20),
+            },
+        );
+        status.default_value = Some("'new'".to_string());
+
+        let mut total = column("total", UnifiedDataType::Float { precision: None });
+        total.generated = Some(GeneratedColumn {
+            expression: "`price` * `qty`".to_string(),
+            kind: GenerationKind::Stored,
+        });
+
+        let mut orders = table(
+            "orders",
+            vec![
+                id,
+                status,
+                total,
+                column(
+                    "flags",
+                    UnifiedDataType::Custom {
+                        type_name: "set('a','b')".to_string(),
+                    },
+                ),
+                column(
+                    "customer_id",
+                    UnifiedDataType::Integer {
+                        bits: 64,
+                        signed: true,
+                    },
+                ),
+            ],
+        );
+        orders.primary_key = Some(PrimaryKey {
+            name: Some("PRIMARY".to_string()),
+            columns: vec!["id".to_string()],
+        });
+        orders.foreign_keys.push(ForeignKey {
+            name: Some("orders_customer".to_string()),
+            columns: vec!["customer_id".to_string()],
+            referenced_table: "customers".to_string(),
+            referenced_schema: None,
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::Restrict),
+            on_update: None,
+        });
+        orders.indexes.push(Index {
+            name: "orders_status".to_string(),
+            table_name: "orders".to_string(),
+            schema: Some("shop".to_string()),
+            columns: vec![IndexColumn {
+                name: "status".to_string(),
+                sort_order: Some(SortDirection::Descending),
+            }],
+            is_unique: false,
+            is_primary: false,
+            index_type: Some("BTREE".to_string()),
+            options: None,
+        });
+
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables.push(orders);
+        schema.views.push(View {
+            name: "open_orders".to_string(),
+            schema: Some("shop".to_string()),
+            definition: Some("select * from orders".to_string()),
+            columns: Vec::new(),
+            comment: None,
+        });
+        schema
+    }
+
+    fn reasons(script: &SqlScript) -> Vec<String> {
+        script
+            .untranslated
+            .iter()
+            .map(|u| format!("{}: {}", u.object, u.reason))
+            .collect()
+    }
+
+    #[test]
+    fn test_mysql_survey_to_postgresql() {
+        let script = render_ddl(&mysql_survey(), SqlDialect::PostgreSQL);
+        assert!(script.ddl.contains("CREATE TABLE \"shop\".\"orders\" ("));
+        assert!(
+            script
+                .ddl
+                .contains("\"id\" BIGINT GENERATED BY DEFAULT AS IDENTITY NOT NULL")
+        );
+        assert!(script.ddl.contains("\"status\" VARCHAR(20) DEFAULT 'new'"));
+        assert!(
+            script
+                .ddl
+                .contains("CONSTRAINT \"PRIMARY\" PRIMARY KEY (\"id\")")
+        );
+        assert!(
+            script.ddl.contains(
+                "CREATE INDEX \"orders_status\" ON \"shop\".\"orders\" (\"status\" DESC);"
+            )
+        );
+        assert!(script.ddl.contains(
+            "ALTER TABLE \"shop\".\"orders\" ADD CONSTRAINT \"orders_customer\" FOREIGN KEY \
+             (\"customer_id\") REFERENCES \"shop\".\"customers\" (\"id\") ON DELETE RESTRICT;"
+        ));
+
+        assert_eq!(
+            reasons(&script),
+            vec![
+                "shop.orders.total: generated column emitted as a plain column: `price` * `qty`",
+                "shop.orders.flags: type set('a','b') has no PostgreSQL equivalent",
+                "shop.open_orders: view definitions are not translated",
+            ]
+        );
+        assert!(script.ddl.contains("-- Untranslated constructs (3):"));
+    }
+
+    #[test]
+    fn test_identifier_quoting_per_dialect() {
+        assert_eq!(SqlDialect::PostgreSQL.quote("a\"b"), "\"a\"\"b\"");
+        assert_eq!(SqlDialect::MySQL.quote("a`b"), "`a``b`");
+        assert_eq!(SqlDialect::SqlServer.quote("a]b"), "[a]]b]");
+        assert_eq!(
+            SqlDialect::SQLite.quote_qualified(Some("main"), "users"),
+            "\"users\""
+        );
+    }
+
+    #[test]
+    fn test_identifiers_cannot_escape_comments() {
+        let mut schema =
+            DatabaseSchema::new(DatabaseInfo::new("shop\nDROP TABLE users;".to_string()));
+        schema.tables.push(table(
+            "Order.Items",
+            vec![column("select", UnifiedDataType::Boolean)],
+        ));
+        schema.views.push(View {
+            name: "v\nDROP TABLE users;".to_string(),
+            schema: Some("shop".to_string()),
+            definition: None,
+            columns: Vec::new(),
+            comment: None,
+        });
+
+        let script = render_ddl(&schema, SqlDialect::PostgreSQL);
+        assert!(script.ddl.contains("-- Database: shop DROP TABLE users;"));
+        assert!(script.ddl.contains("CREATE TABLE \"shop\".\"Order.Items\""));
+        assert!(script.ddl.contains("\"select\" BOOLEAN"));
+        assert!(
+            script
+                .ddl
+                .lines()
+                .all(|line| !line.starts_with("DROP TABLE")),
+            "{}",
+            script.ddl
+        );
+    }
+
+    #[test]
+    fn test_sqlite_inlines_keys() {
+        let script = render_ddl(&mysql_survey(), SqlDialect::SQLite);
+        assert!(
+            script
+                .ddl
+                .contains("\"id\" INTEGER PRIMARY KEY AUTOINCREMENT,")
+        );
+        assert!(!script.ddl.contains("ALTER TABLE"));
+        assert!(script.ddl.contains(
+            "CONSTRAINT \"orders_customer\" FOREIGN KEY (\"customer_id\") REFERENCES \
+             \"customers\" (\"id\") ON DELETE RESTRICT"
+        ));
+    }
+
+    #[test]
+    fn test_identity_and_serial_columns() {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        let mut columns = Vec::new();
+        for (name, bits, generation) in [
+            ("legacy_id", 32, IdentityGeneration::Serial),
+            ("big_id", 64, IdentityGeneration::Serial),
+            ("always_id", 64, IdentityGeneration::Always),
+            ("default_id", 64, IdentityGeneration::ByDefault),
+        ] {
+            let mut id = column(name, UnifiedDataType::Integer { bits, signed: true });
+            id.is_nullable = false;
+            id.is_auto_increment = true;
+            id.default_value = Some(format!("nextval('{}_seq'::regclass)", name));
+            id.identity = Some(ColumnIdentity {
+                generation,
+                sequence: Some(format!("shop.{}_seq", name)),
+            });
+            columns.push(id);
+        }
+        schema.tables.push(table("ids", columns));
+
+        let script = render_ddl(&schema, SqlDialect::PostgreSQL);
+        assert!(script.ddl.contains("\"legacy_id\" SERIAL NOT NULL,"));
+        assert!(script.ddl.contains("\"big_id\" BIGSERIAL NOT NULL,"));
+        assert!(
+            script
+                .ddl
+                .contains("\"always_id\" BIGINT GENERATED ALWAYS AS IDENTITY NOT NULL,")
+        );
+        assert!(
+            script
+                .ddl
+                .contains("\"default_id\" BIGINT GENERATED BY DEFAULT AS IDENTITY NOT NULL")
+        );
+        assert!(!script.ddl.contains("nextval"));
+
+        let generic = render_ddl(&schema, SqlDialect::Generic);
+        assert!(!generic.ddl.contains("SERIAL"));
+    }
+
+    #[test]
+    fn test_sql_server_type_and_action_mapping() {
+        let script = render_ddl(&mysql_survey(), SqlDialect::SqlServer);
+        assert!(script.ddl.contains("[id] BIGINT IDENTITY(1,1) NOT NULL"));
+        assert!(script.ddl.contains("[status] NVARCHAR(20) DEFAULT 'new'"));
+        assert!(script.ddl.contains("ON DELETE NO ACTION"));
+        assert!(
+            script
+                .ddl
+                .contains("CREATE INDEX [orders_status] ON [shop].[orders]")
+        );
+    }
+
+    #[test]
+    fn test_type_mapping() {
+        let array = UnifiedDataType::Array {
+            element_type: Box::new(UnifiedDataType::Integer {
+                bits: 32,
+                signed: true,
+            }),
+        };
+        assert_eq!(map_type(&array, SqlDialect::PostgreSQL).sql, "INTEGER[]");
+        let mysql = map_type(&array, SqlDialect::MySQL);
+        assert_eq!(mysql.sql, "JSON");
+        assert!(mysql.lossy.is_some());
+
+        let unsigned_tiny = UnifiedDataType::Integer {
+            bits: 8,
+            signed: false,
+        };
+        assert_eq!(
+            map_type(&unsigned_tiny, SqlDialect::MySQL).sql,
+            "TINYINT UNSIGNED"
+        );
+        assert_eq!(
+            map_type(&unsigned_tiny, SqlDialect::SqlServer).sql,
+            "TINYINT"
+        );
+        assert_eq!(
+            map_type(&unsigned_tiny, SqlDialect::PostgreSQL).sql,
+            "SMALLINT"
+        );
+
+        let timestamptz = UnifiedDataType::DateTime {
+            with_timezone: true,
+        };
+        assert!(map_type(&timestamptz, SqlDialect::MySQL).lossy.is_some());
+        assert_eq!(
+            map_type(&timestamptz, SqlDialect::SqlServer).sql,
+            "DATETIMEOFFSET"
+        );
+
+        let money = UnifiedDataType::Decimal {
+            precision: Some(19),
+            scale: Some(4),
+        };
+        assert_eq!(map_type(&money, SqlDialect::MySQL).sql, "DECIMAL(19,4)");
+        let wide = UnifiedDataType::Decimal {
+            precision: Some(80),
+            scale: Some(40),
+        };
+        let mysql = map_type(&wide, SqlDialect::MySQL);
+        assert_eq!(mysql.sql, "DECIMAL(65,30)");
+        assert!(mysql.lossy.is_some());
+        let unconstrained = UnifiedDataType::Decimal {
+            precision: None,
+            scale: None,
+        };
+        assert_eq!(
+            map_type(&unconstrained, SqlDialect::PostgreSQL).sql,
+            "DECIMAL"
+        );
+        assert_eq!(
+            map_type(&unconstrained, SqlDialect::SqlServer).sql,
+            "DECIMAL(38,18)"
+        );
+
+        let bits = UnifiedDataType::Bit { length: Some(12) };
+        assert_eq!(map_type(&bits, SqlDialect::PostgreSQL).sql, "BIT(12)");
+        assert_eq!(map_type(&bits, SqlDialect::MySQL).sql, "BIT(12)");
+        let sqlserver = map_type(&bits, SqlDialect::SqlServer);
+        assert_eq!(sqlserver.sql, "VARBINARY(2)");
+        assert!(sqlserver.lossy.is_some());
+
+        assert_eq!(
+            map_type(&UnifiedDataType::Interval, SqlDialect::PostgreSQL).sql,
+            "INTERVAL"
+        );
+        assert!(
+            map_type(&UnifiedDataType::Interval, SqlDialect::MySQL)
+                .lossy
+                .is_some()
+        );
+        assert_eq!(
+            map_type(&UnifiedDataType::NetworkAddress, SqlDialect::PostgreSQL).sql,
+            "INET"
+        );
+    }
+
+    #[test]
+    fn test_translate_default() {
+        let translate =
+            |default: &str, dialect| translate_default(&classify_default(default), dialect);
+        let pg = SqlDialect::PostgreSQL;
+        assert_eq!(
+            translate("'new'::character varying", pg).as_deref(),
+            Some("'new'")
+        );
+        assert_eq!(translate("42", pg).as_deref(), Some("42"));
+        assert_eq!(
+            translate("now()", SqlDialect::MySQL).as_deref(),
+            Some("CURRENT_TIMESTAMP")
+        );
+        assert_eq!(
+            translate("true", SqlDialect::SqlServer).as_deref(),
+            Some("1")
+        );
+        assert_eq!(translate("nextval('seq'::regclass)", pg), None);
+        assert_eq!(translate("'a' || 'b'", pg), None);
+        assert_eq!(translate("'it''s'", pg).as_deref(), Some("'it''s'"));
+        assert_eq!(
+            translate("gen_random_uuid()", SqlDialect::SqlServer).as_deref(),
+            Some("NEWID()")
+        );
+        assert_eq!(translate("uuid()", SqlDialect::SQLite), None);
+        assert_eq!(
+            translate("CURRENT_DATE", pg).as_deref(),
+            Some("CURRENT_DATE")
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Machine-readable result of a documentation run.
+//!
+//! `--result-summary` prints a JSON summary of what `generate` did to
+//! stderr and `--result-file <PATH>` writes it to a file, so pipelines can
+//! check that documentation is complete without parsing log output. The
+//! summary is written whether or not generation succeeded and lists:
+//!
+//! - the objects rendered per kind and the report sections left out
+//! - databases and survey files that were skipped, with the reason
+//! - collection warnings carried in the surveys
+//! - every file written, with its size and SHA-256 checksum
+//!
+//! `status` is `complete` when everything was documented, `partial` when
+//! some outputs were written but something was skipped or failed, and
+//! `failed` when nothing was written.
+
+use crate::ReportSection;
+use crate::growth::{self, Observation};
+use clap::ValueEnum;
+use dbsurveyor_core::{
+    DbSurveyorError, Result,
+    models::{CollectionStatus, DatabaseSchema},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Overall outcome of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ResultStatus {
+    Complete,
+    Partial,
+    Failed,
+}
+
+/// Objects rendered, summed over every documented database.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ObjectCounts {
+    pub(crate) tables: usize,
+    pub(crate) views: usize,
+    pub(crate) indexes: usize,
+    pub(crate) constraints: usize,
+    pub(crate) procedures: usize,
+    pub(crate) functions: usize,
+    pub(crate) triggers: usize,
+    pub(crate) custom_types: usize,
+}
+
+/// A database or survey file that was not documented.
+#[derive(Debug, Serialize)]
+pub(crate) struct SkippedInput {
+    pub(crate) name: String,
+    pub(crate) reason: String,
+}
+
+/// A file written by the run.
+#[derive(Debug, Serialize)]
+pub(crate) struct OutputFile {
+    pub(crate) path: PathBuf,
+    pub(crate) bytes: u64,
+    pub(crate) sha256: String,
+}
+
+/// Summary of one `generate` run.
+#[derive(Debug, Serialize)]
+pub(crate) struct GenerationResult {
+    pub(crate) status: ResultStatus,
+    pub(crate) input: PathBuf,
+    pub(crate) format: &'static str,
+    pub(crate) databases_documented: usize,
+    pub(crate) objects_rendered: ObjectCounts,
+    /// Report sections left out with `--sections`
+    pub(crate) skipped_sections: Vec<String>,
+    pub(crate) skipped: Vec<SkippedInput>,
+    pub(crate) warnings: Vec<String>,
+    pub(crate) outputs: Vec<OutputFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+impl GenerationResult {
+    /// Prints the summary to stderr and/or writes it to `path`.
+    ///
+    /// # Errors
+    /// Returns an error if the summary cannot be serialized or written.
+    pub(crate) fn emit(&self, to_stderr: bool, path: Option<&Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            DbSurveyorError::configuration(format!("Failed to serialize result summary: {}", e))
+        })?;
+        if to_stderr {
+            eprintln!("{}", json);
+        }
+        if let Some(path) = path {
+            std::fs::write(path, json).map_err(|e| DbSurveyorError::Io {
+                context: format!("Failed to write result file {}", path.display()),
+                source: e,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the summary while documentation is generated.
+///
+/// Outputs are recorded by path only; their checksums are computed in
+/// [`ResultRecorder::finish`] once everything has been written.
+pub(crate) struct ResultRecorder {
+    result: Mutex<GenerationResult>,
+    outputs: Mutex<Vec<PathBuf>>,
+    /// Table sizes of documented databases, for storage growth
+    observations: Mutex<Vec<Observation>>,
+}
+
+impl ResultRecorder {
+    /// Starts a summary for documenting `input` as `format` with the
+    /// report sections selected by `--sections` (empty for all).
+    pub(crate) fn new(input: &Path, format: &'static str, sections: &[ReportSection]) -> Self {
+        let skipped_sections = ReportSection::ALL
+            .iter()
+            .filter(|section| !sections.is_empty() && !sections.contains(section))
+            .filter_map(|section| section.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        Self {
+            result: Mutex::new(GenerationResult {
+                status: ResultStatus::Complete,
+                input: input.to_path_buf(),
+                format,
+                databases_documented: 0,
+                objects_rendered: ObjectCounts::default(),
+                skipped_sections,
+                skipped: Vec::new(),
+                warnings: Vec::new(),
+                outputs: Vec::new(),
+                error: None,
+            }),
+            outputs: Mutex::new(Vec::new()),
+            observations: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records a database documented at `output`.
+    pub(crate) fn record_database(&self, schema: &DatabaseSchema, output: &Path) {
+        {
+            let mut result = self.lock_result();
+            result.databases_documented += 1;
+            let counts = &mut result.objects_rendered;
+            counts.tables += schema.tables.len();
+            counts.views += schema.views.len();
+            counts.indexes += schema.indexes.len();
+            counts.constraints += schema.constraints.len();
+            counts.procedures += schema.procedures.len();
+            counts.functions += schema.functions.len();
+            counts.triggers += schema.triggers.len();
+            counts.custom_types += schema.custom_types.len();
+            let database = &schema.database_info.name;
+            result.warnings.extend(
+                schema
+                    .collection_metadata
+                    .warnings
+                    .iter()
+                    .map(|warning| format!("{}: {}", database, warning)),
+            );
+        }
+        self.observations
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .extend(growth::observe("", schema));
+        self.record_output(output);
+    }
+
+    /// Takes the table sizes recorded since the last call.
+    pub(crate) fn take_observations(&self) -> Vec<Observation> {
+        std::mem::take(
+            &mut *self
+                .observations
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+    }
+
+    /// Records a database that was not documented because of its
+    /// collection status.
+    pub(crate) fn record_skipped_database(&self, schema: &DatabaseSchema) {
+        let reason = match &schema.database_info.collection_status {
+            CollectionStatus::Failed { error } => format!("collection failed: {}", error),
+            CollectionStatus::Skipped { reason } => format!("collection skipped: {}", reason),
+            status => format!("{:?}", status),
+        };
+        self.record_skipped(&schema.database_info.name, reason);
+    }
+
+    /// Records an input that was not documented.
+    pub(crate) fn record_skipped(&self, name: &str, reason: String) {
+        self.lock_result().skipped.push(SkippedInput {
+            name: name.to_string(),
+            reason,
+        });
+    }
+
+    /// Records a file or directory written outside of a database's
+    /// documentation, such as a directory index.
+    pub(crate) fn record_output(&self, path: &Path) {
+        self.outputs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(path.to_path_buf());
+    }
+
+    /// Completes the summary with the run's outcome and checksums of every
+    /// recorded output. Directory outputs (Markdown sites, Confluence
+    /// exports) list each file inside them.
+    pub(crate) fn finish(self, outcome: &Result<()>) -> GenerationResult {
+        let outputs = self
+            .outputs
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut result = self
+            .result
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        for path in outputs {
+            if let Err(e) = checksum_outputs(&path, &mut result.outputs) {
+                result
+                    .warnings
+                    .push(format!("Failed to checksum {}: {}", path.display(), e));
+            }
+        }
+        result.error = outcome.as_ref().err().map(ToString::to_string);
+        result.status = if result.outputs.is_empty() && outcome.is_err() {
+            ResultStatus::Failed
+        } else if outcome.is_err() || !result.skipped.is_empty() {
+            ResultStatus::Partial
+        } else {
+            ResultStatus::Complete
+        };
+        result
+    }
+
+    fn lock_result(&self) -> std::sync::MutexGuard<'_, GenerationResult> {
+        self.result
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Adds `path`, or every file under it if it is a directory, to `outputs`.
+fn checksum_outputs(path: &Path, outputs: &mut Vec<OutputFile>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let bytes = std::io::copy(&mut file, &mut hasher)?;
+        outputs.push(OutputFile {
+            path: path.to_path_buf(),
+            bytes,
+            sha256: hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        });
+        return Ok(());
+    }
+
+    let mut entries = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        checksum_outputs(&entry, outputs)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::DatabaseInfo;
+
+    #[test]
+    fn test_summary_counts_objects_and_checksums_outputs() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let report = dir.path().join("sales.md");
+        std::fs::write(&report, "abc").expect("failed to write report");
+        let site = dir.path().join("sales_site");
+        std::fs::create_dir_all(site.join("tables")).expect("failed to create site");
+        std::fs::write(site.join("index.md"), "").expect("failed to write site");
+        std::fs::write(site.join("tables/orders.md"), "").expect("failed to write site");
+
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("sales".to_string()));
+        schema
+            .collection_metadata
+            .warnings
+            .push("slow query".into());
+        let recorder = ResultRecorder::new(
+            Path::new("sales.json"),
+            "markdown",
+            &[ReportSection::Tables, ReportSection::Indexes],
+        );
+        recorder.record_database(&schema, &report);
+        recorder.record_output(&site);
+        let result = recorder.finish(&Ok(()));
+
+        assert_eq!(result.status, ResultStatus::Complete);
+        assert_eq!(result.databases_documented, 1);
+        assert_eq!(result.warnings, ["sales: slow query"]);
+        assert!(!result.skipped_sections.contains(&"tables".to_string()));
+        assert!(result.skipped_sections.contains(&"samples".to_string()));
+        assert_eq!(result.outputs.len(), 3);
+        assert_eq!(result.outputs[0].bytes, 3);
+        assert_eq!(
+            result.outputs[0].sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(result.outputs[1].path, site.join("index.md"));
+        assert_eq!(result.outputs[2].path, site.join("tables/orders.md"));
+    }
+
+    #[test]
+    fn test_status_reflects_skips_and_errors() {
+        let skipped = ResultRecorder::new(Path::new("server.json"), "markdown", &[]);
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("hr".to_string()));
+        schema.database_info.collection_status = CollectionStatus::Failed {
+            error: "permission denied".to_string(),
+        };
+        skipped.record_skipped_database(&schema);
+        let result = skipped.finish(&Ok(()));
+        assert_eq!(result.status, ResultStatus::Partial);
+        assert_eq!(
+            result.skipped[0].reason,
+            "collection failed: permission denied"
+        );
+
+        let failed = ResultRecorder::new(Path::new("broken.json"), "markdown", &[]);
+        let result = failed.finish(&Err(DbSurveyorError::configuration("bad survey")));
+        assert_eq!(result.status, ResultStatus::Failed);
+        assert!(result.error.is_some());
+    }
+}
@@ -0,0 +1,144 @@
+//! Cross-survey column usage lookup (`where-used`).
+//!
+//! Finds every table column, across any number of surveys, whose name
+//! matches a pattern or whose name-based classification matches, so the
+//! impact of changing a data element can be assessed before making it.
+
+use crate::markdown::{column_type_label, qualified_table_name};
+use crate::redaction::classify_field;
+use dbsurveyor_core::models::SurveyDocument;
+
+/// Column criteria; a column must satisfy every criterion that is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ColumnQuery {
+    /// Column name, or a glob pattern (`*`, `?`, `[...]`), matched case-insensitively
+    pub(crate) pattern: Option<String>,
+    /// Classification from [`classify_field`], matched case-insensitively
+    pub(crate) classification: Option<String>,
+}
+
+impl ColumnQuery {
+    fn matches(&self, column: &str) -> bool {
+        let name_matches = self.pattern.as_deref().is_none_or(|pattern| {
+            glob_match::glob_match(&pattern.to_lowercase(), &column.to_lowercase())
+        });
+        let class_matches = self.classification.as_deref().is_none_or(|wanted| {
+            classify_field(column).is_some_and(|class| class.eq_ignore_ascii_case(wanted))
+        });
+        name_matches && class_matches
+    }
+}
+
+/// A column found by [`find_column_usage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ColumnUsage {
+    pub(crate) database: String,
+    /// Qualified table name
+    pub(crate) table: String,
+    pub(crate) column: String,
+    pub(crate) data_type: String,
+    pub(crate) classification: Option<&'static str>,
+}
+
+/// Lists the table columns in a survey that match `query`, in table order.
+pub(crate) fn find_column_usage(survey: &SurveyDocument, query: &ColumnQuery) -> Vec<ColumnUsage> {
+    let mut usages = Vec::new();
+    for schema in survey.databases() {
+        for table in &schema.tables {
+            for column in &table.columns {
+                if query.matches(&column.name) {
+                    usages.push(ColumnUsage {
+                        database: schema.database_info.name.clone(),
+                        table: qualified_table_name(table),
+                        column: column.name.clone(),
+                        data_type: column_type_label(column),
+                        classification: classify_field(&column.name),
+                    });
+                }
+            }
+        }
+    }
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsurveyor_core::models::{Column, DatabaseInfo, DatabaseSchema, Table, UnifiedDataType};
+
+    fn column(name: &str) -> Column {
+        Column {
+            is_nullable: false,
+            ..Column::new(
+                name.to_string(),
+                UnifiedDataType::Integer {
+                    bits: 64,
+                    signed: true,
+                },
+            )
+        }
+    }
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        Table::new(
+            name.to_string(),
+            Some("public".to_string()),
+            columns.iter().map(|c| column(c)).collect(),
+        )
+    }
+
+    fn survey() -> SurveyDocument {
+        let mut schema = DatabaseSchema::new(DatabaseInfo::new("shop".to_string()));
+        schema.tables = vec![
+            table("orders", &["id", "customer_id", "Customer_Email"]),
+            table("customers", &["id", "email", "password_hash"]),
+        ];
+        SurveyDocument::Database(Box::new(schema))
+    }
+
+    fn found(query: &ColumnQuery) -> Vec<String> {
+        find_column_usage(&survey(), query)
+            .into_iter()
+            .map(|u| format!("{}.{}", u.table, u.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_name_is_case_insensitive() {
+        let query = ColumnQuery {
+            pattern: Some("CUSTOMER_ID".to_string()),
+            ..ColumnQuery::default()
+        };
+        assert_eq!(found(&query), vec!["public.orders.customer_id"]);
+    }
+
+    #[test]
+    fn test_glob_pattern() {
+        let query = ColumnQuery {
+            pattern: Some("customer_*".to_string()),
+            ..ColumnQuery::default()
+        };
+        assert_eq!(
+            found(&query),
+            vec!["public.orders.customer_id", "public.orders.Customer_Email"]
+        );
+    }
+
+    #[test]
+    fn test_classification_combined_with_pattern() {
+        let pii = ColumnQuery {
+            classification: Some("pii".to_string()),
+            ..ColumnQuery::default()
+        };
+        assert_eq!(
+            found(&pii),
+            vec!["public.orders.Customer_Email", "public.customers.email"]
+        );
+
+        let customers_pii = ColumnQuery {
+            pattern: Some("customer*".to_string()),
+            classification: Some("PII".to_string()),
+        };
+        assert_eq!(found(&customers_pii), vec!["public.orders.Customer_Email"]);
+    }
+}
@@ -84,3 +84,75 @@ fn test_postprocessor_invalid_args() {
         "expected non-zero exit for invalid args"
     );
 }
+
+#[test]
+fn test_postprocessor_unknown_exporter() {
+    let output = Command::new(bin_path())
+        .args(["generate", "schema.json", "--exporter", "cmdb"])
+        .output()
+        .expect("failed to execute dbsurveyor generate --exporter");
+
+    assert!(
+        !output.status.success(),
+        "expected non-zero exit for an exporter not compiled in"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown exporter 'cmdb'"),
+        "error should name the exporter: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_postprocessor_schema_prints_json_schema() {
+    let output = Command::new(bin_path())
+        .args(["schema", "--format-version", "1.0"])
+        .output()
+        .expect("failed to execute dbsurveyor schema");
+
+    assert!(output.status.success(), "expected exit 0 for schema");
+
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema output should be valid JSON");
+    assert!(schema.get("$schema").is_some());
+    assert!(
+        schema["properties"].get("collection_metadata").is_some(),
+        "schema should describe the survey format"
+    );
+}
+
+#[test]
+fn test_postprocessor_schema_unsupported_version() {
+    let output = Command::new(bin_path())
+        .args(["schema", "--format-version", "9.9"])
+        .output()
+        .expect("failed to execute dbsurveyor schema");
+
+    assert!(
+        !output.status.success(),
+        "expected non-zero exit for unsupported format version"
+    );
+}
+
+#[test]
+fn test_postprocessor_index_refuses_encrypted_survey() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey = dir.path().join("survey.enc");
+    std::fs::write(&survey, "{}").expect("failed to write survey");
+
+    let output = Command::new(bin_path())
+        .arg("index")
+        .arg(&survey)
+        .output()
+        .expect("failed to execute dbsurveyor index");
+
+    assert!(!output.status.success(), "expected non-zero exit");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--allow-plaintext"),
+        "error should name the opt-in flag: {}",
+        stderr
+    );
+    assert!(!dir.path().join("survey.enc.index.json").exists());
+}
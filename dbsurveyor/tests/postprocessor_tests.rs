@@ -105,6 +105,501 @@ fn test_validate_incomplete_schema() {
     );
 }
 
+#[test]
+fn test_validate_rejects_schema_over_input_limits() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let path = tmp.path().to_str().expect("non-UTF8 path");
+
+    for limit_args in [
+        ["--max-json-depth", "2"],
+        ["--max-string-length", "4"],
+        ["--max-file-size-mb", "0"],
+    ] {
+        let output = Command::new(bin_path())
+            .arg("validate")
+            .args(limit_args)
+            .arg(path)
+            .output()
+            .expect("failed to execute dbsurveyor validate");
+
+        assert!(
+            !output.status.success(),
+            "validate should fail with {:?}",
+            limit_args
+        );
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("limit"),
+            "error should name the exceeded limit for {:?}",
+            limit_args
+        );
+    }
+}
+
+#[test]
+fn test_generate_markdown_selected_sections() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            tmp.path().to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+            "--sections",
+            "relationships,classification",
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = std::fs::read_to_string(&report_path).expect("failed to read report");
+    assert!(report.contains("## Relationships"));
+    assert!(report.contains("## Data Classification"));
+    assert!(!report.contains("## Tables"));
+    assert!(!report.contains("## Samples"));
+}
+
+#[test]
+fn test_generate_rejects_malformed_naming_dictionary() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let dictionary_path = out_dir.path().join("naming.txt");
+    std::fs::write(&dictionary_path, "cust = customer\namt amount\n")
+        .expect("failed to write dictionary");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            tmp.path().to_str().expect("non-UTF8 path"),
+            "--output",
+            out_dir
+                .path()
+                .join("report.md")
+                .to_str()
+                .expect("non-UTF8 path"),
+            "--naming-dictionary",
+            dictionary_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(!output.status.success(), "malformed dictionary should fail");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("line 2"),
+        "error should name the malformed line: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_index_and_search() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let schema_path = out_dir.path().join("survey.json");
+    let schema = minimal_valid_schema().replace(
+        r#""tables": []"#,
+        r#""tables": [{
+            "name": "customers",
+            "schema": "public",
+            "columns": [{
+                "name": "shop_credit",
+                "data_type": "Boolean",
+                "is_nullable": false,
+                "is_primary_key": false,
+                "is_auto_increment": false,
+                "default_value": null,
+                "comment": null,
+                "ordinal_position": 1
+            }],
+            "primary_key": null,
+            "foreign_keys": [],
+            "indexes": [],
+            "constraints": [],
+            "comment": "Registered shoppers",
+            "row_count": null
+        }]"#,
+    );
+    std::fs::write(&schema_path, schema).expect("failed to write schema");
+
+    let output = Command::new(bin_path())
+        .args(["index", schema_path.to_str().expect("non-UTF8 path")])
+        .output()
+        .expect("failed to execute dbsurveyor index");
+    assert!(
+        output.status.success(),
+        "index should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let index_path = out_dir.path().join("survey.json.index.json");
+    assert!(index_path.is_file());
+
+    let output = Command::new(bin_path())
+        .args([
+            "search",
+            "shop",
+            index_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor search");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("public.customers - Registered shoppers"),
+        "unexpected search output: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_where_used_across_surveys() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let table = |table: &str, database: &str| {
+        minimal_valid_schema()
+            .replace(
+                r#""name": "test_db""#,
+                &format!(r#""name": "{}""#, database),
+            )
+            .replace(
+                r#""tables": []"#,
+                &format!(
+                    r#""tables": [{{
+                        "name": "{}",
+                        "schema": "public",
+                        "columns": [{{
+                            "name": "customer_id",
+                            "data_type": "Boolean",
+                            "is_nullable": false,
+                            "is_primary_key": false,
+                            "is_auto_increment": false,
+                            "ordinal_position": 1
+                        }}],
+                        "primary_key": null,
+                        "foreign_keys": [],
+                        "indexes": [],
+                        "constraints": []
+                    }}]"#,
+                    table
+                ),
+            )
+    };
+    let sales = out_dir.path().join("sales.json");
+    let billing = out_dir.path().join("billing.json");
+    std::fs::write(&sales, table("orders", "sales")).expect("failed to write schema");
+    std::fs::write(&billing, table("invoices", "billing")).expect("failed to write schema");
+
+    let output = Command::new(bin_path())
+        .args(["where-used", "--column", "CUSTOMER_*"])
+        .args([&sales, &billing])
+        .output()
+        .expect("failed to execute dbsurveyor where-used");
+    assert!(
+        output.status.success(),
+        "where-used should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("sales.public.orders.customer_id"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("billing.public.invoices.customer_id"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("2 matching columns in 2 tables across 2 surveys"));
+
+    let output = Command::new(bin_path())
+        .arg("where-used")
+        .arg(&sales)
+        .output()
+        .expect("failed to execute dbsurveyor where-used");
+    assert!(
+        !output.status.success(),
+        "a column or classification is required"
+    );
+}
+
+#[test]
+fn test_baseline_set_and_check() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey = |tables: &[&str]| {
+        let tables: Vec<String> = tables
+            .iter()
+            .map(|name| {
+                format!(
+                    r#"{{
+                        "name": "{}",
+                        "schema": "public",
+                        "columns": [{{
+                            "name": "id",
+                            "data_type": "Boolean",
+                            "is_nullable": false,
+                            "is_primary_key": false,
+                            "is_auto_increment": false,
+                            "ordinal_position": 1
+                        }}],
+                        "primary_key": null,
+                        "foreign_keys": [],
+                        "indexes": [],
+                        "constraints": []
+                    }}"#,
+                    name
+                )
+            })
+            .collect();
+        minimal_valid_schema().replace(
+            r#""tables": []"#,
+            &format!(r#""tables": [{}]"#, tables.join(",")),
+        )
+    };
+    let approved = out_dir.path().join("approved.json");
+    let drifted = out_dir.path().join("drifted.json");
+    let baseline = out_dir.path().join("baseline.json");
+    let allow = out_dir.path().join("allow.txt");
+    std::fs::write(&approved, survey(&["orders"])).expect("failed to write schema");
+    std::fs::write(&drifted, survey(&["orders", "staging_import"]))
+        .expect("failed to write schema");
+    std::fs::write(&allow, "added table public.staging_*\n").expect("failed to write allow-list");
+
+    let output = Command::new(bin_path())
+        .args(["baseline", "set"])
+        .arg(&approved)
+        .arg("--baseline")
+        .arg(&baseline)
+        .output()
+        .expect("failed to execute dbsurveyor baseline set");
+    assert!(
+        output.status.success(),
+        "baseline set should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let check = |survey: &std::path::Path, allow_lists: &[&std::path::Path]| {
+        let mut command = Command::new(bin_path());
+        command
+            .args(["baseline", "check"])
+            .arg(survey)
+            .arg("--baseline")
+            .arg(&baseline);
+        for path in allow_lists {
+            command.arg("--allow").arg(path);
+        }
+        command
+            .output()
+            .expect("failed to execute dbsurveyor baseline check")
+    };
+
+    assert!(check(&approved, &[]).status.success());
+
+    let output = check(&drifted, &[]);
+    assert_eq!(output.status.code(), Some(2), "unapproved drift exits 2");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[DRIFT] test_db: added table public.staging_import"),
+        "unexpected check output: {}",
+        stdout
+    );
+
+    let output = check(&drifted, &[&allow]);
+    assert!(
+        output.status.success(),
+        "allow-listed drift should pass: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let tampered = std::fs::read_to_string(&baseline)
+        .expect("failed to read baseline")
+        .replace("\"orders\"", "\"invoices\"");
+    std::fs::write(&baseline, tampered).expect("failed to write baseline");
+    let output = check(&approved, &[]);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "tampered baseline is an error"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("checksum mismatch"));
+}
+
+#[test]
+fn test_conform_reports_findings_and_exit_code() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("survey.json");
+    let table = r#"{
+        "name": "customers",
+        "schema": "public",
+        "columns": [
+            {"name": "id", "data_type": "Boolean", "is_nullable": false,
+             "is_primary_key": true, "is_auto_increment": false, "ordinal_position": 1},
+            {"name": "email", "data_type": {"String": {"max_length": 255}}, "is_nullable": true,
+             "is_primary_key": false, "is_auto_increment": false, "ordinal_position": 2}
+        ],
+        "primary_key": null,
+        "foreign_keys": [],
+        "indexes": [],
+        "constraints": []
+    }"#;
+    std::fs::write(
+        &survey_path,
+        minimal_valid_schema().replace(r#""tables": []"#, &format!(r#""tables": [{}]"#, table)),
+    )
+    .expect("failed to write schema");
+
+    let conform = |spec: &str| {
+        let spec_path = out_dir.path().join("spec.json");
+        std::fs::write(&spec_path, spec).expect("failed to write spec");
+        Command::new(bin_path())
+            .arg("conform")
+            .arg(&survey_path)
+            .arg("--spec")
+            .arg(&spec_path)
+            .output()
+            .expect("failed to execute dbsurveyor conform")
+    };
+
+    let output = conform(
+        r#"{"tables": [{"schema": "public", "name": "customers",
+            "columns": [{"name": "email", "data_type": "string*"}]}],
+            "pii": {"allowed": ["public.customers.email"]}}"#,
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "conforming survey: {}", stdout);
+    assert!(stdout.contains("[PASS] test_db: column public.customers.email"));
+    assert!(stdout.contains("4 checks against"));
+
+    let output = conform(r#"{"tables": [{"name": "orders"}], "pii": {}}"#);
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "nonconforming survey exits 2"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[FAIL] test_db: table orders (missing)"));
+    assert!(stdout.contains("[FAIL] test_db: pii public.customers.email (unexpected PII column)"));
+
+    let output = conform(r#"{"tabels": []}"#);
+    assert_eq!(output.status.code(), Some(1), "invalid spec is an error");
+}
+
+#[test]
+fn test_generate_markdown_site() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let site_dir = out_dir.path().join("site");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            tmp.path().to_str().expect("non-UTF8 path"),
+            "--format",
+            "markdown-site",
+            "--output",
+            site_dir.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for file in ["mkdocs.yml", "sidebars.json", "docs/index.md"] {
+        assert!(site_dir.join(file).is_file(), "missing {}", file);
+    }
+}
+
+#[test]
+fn test_generate_confluence_export() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let export_dir = out_dir.path().join("confluence");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            tmp.path().to_str().expect("non-UTF8 path"),
+            "--format",
+            "confluence",
+            "--output",
+            export_dir.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let manifest =
+        std::fs::read_to_string(export_dir.join("pages.json")).expect("failed to read manifest");
+    let pages: serde_json::Value = serde_json::from_str(&manifest).expect("invalid manifest");
+    assert_eq!(pages[0]["file"], "index.xhtml");
+    assert!(export_dir.join("index.xhtml").is_file());
+}
+
+#[test]
+fn test_generate_graph_json() {
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let graph_path = out_dir.path().join("graph.json");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            tmp.path().to_str().expect("non-UTF8 path"),
+            "--format",
+            "graph-json",
+            "--output",
+            graph_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let content = std::fs::read_to_string(&graph_path).expect("failed to read graph");
+    let graph: serde_json::Value = serde_json::from_str(&content).expect("invalid graph JSON");
+    assert_eq!(graph["format"], "dbsurveyor-graph");
+    assert_eq!(graph["database"], "test_db");
+    assert!(graph["nodes"].as_array().is_some_and(Vec::is_empty));
+    assert!(graph["edges"].as_array().is_some_and(Vec::is_empty));
+}
+
 #[cfg(feature = "experimental")]
 #[test]
 fn test_analyze_valid_schema() {
@@ -167,6 +662,45 @@ fn test_analyze_detailed_flag() {
     );
 }
 
+#[cfg(feature = "experimental")]
+#[test]
+fn test_analyze_lint_lists_cleanup_findings() {
+    let schema = minimal_valid_schema().replace(
+        r#""access_level": "Full","#,
+        r#""access_level": "Full", "schemas": ["public", "staging"],"#,
+    );
+    let mut tmp =
+        tempfile::NamedTempFile::with_suffix(".json").expect("failed to create temp file");
+    tmp.write_all(schema.as_bytes())
+        .expect("failed to write schema");
+    tmp.flush().expect("failed to flush");
+
+    let output = Command::new(bin_path())
+        .args([
+            "analyze",
+            "--lint",
+            tmp.path().to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor analyze --lint");
+
+    assert!(
+        output.status.success(),
+        "analyze --lint should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Lint Findings: 2"), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "- empty-schema: schema contains no collected objects (2)\n    public\n    staging\n"
+        ),
+        "{}",
+        stdout
+    );
+}
+
 /// Password used for encrypted schema fixtures, provided to the binary via
 /// the `DBSURVEYOR_ENCRYPTION_PASSWORD` environment variable.
 #[cfg(feature = "encryption")]
@@ -258,6 +792,84 @@ fn test_validate_encrypted_schema_wrong_password_fails() {
     );
 }
 
+#[cfg(feature = "encryption")]
+#[test]
+fn test_rekey_adds_recipient_and_rotates_password() {
+    use dbsurveyor_core::security::encryption::encrypt_data;
+
+    let encrypted = encrypt_data(minimal_valid_schema().as_bytes(), TEST_PASSWORD)
+        .expect("failed to encrypt schema");
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let path = dir.path().join("schema.enc");
+    std::fs::write(
+        &path,
+        serde_json::to_vec(&encrypted).expect("failed to serialize encrypted data"),
+    )
+    .expect("failed to write");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640))
+            .expect("failed to set permissions");
+    }
+    let path_arg = path.to_str().expect("non-UTF8 path");
+
+    let rekey = |args: &[&str], password: &str, new_password: &str| {
+        Command::new(bin_path())
+            .arg("rekey")
+            .args(args)
+            .arg(path_arg)
+            .env("DBSURVEYOR_ENCRYPTION_PASSWORD", password)
+            .env("DBSURVEYOR_NEW_ENCRYPTION_PASSWORD", new_password)
+            .output()
+            .expect("failed to execute dbsurveyor rekey")
+    };
+
+    let output = rekey(&["--add", "auditor"], TEST_PASSWORD, "auditor-password");
+    assert!(
+        output.status.success(),
+        "rekey --add should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = rekey(&["--rotate"], TEST_PASSWORD, "rotated-password");
+    assert!(
+        output.status.success(),
+        "rekey --rotate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let listed = rekey(&["--list"], "", "");
+    assert_eq!(
+        String::from_utf8_lossy(&listed.stdout),
+        "default\nauditor\n"
+    );
+
+    for password in ["auditor-password", "rotated-password"] {
+        let output = run_validate(&path, &[("DBSURVEYOR_ENCRYPTION_PASSWORD", password)]);
+        assert!(
+            output.status.success(),
+            "validate should succeed with {password}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let output = run_validate(&path, &[("DBSURVEYOR_ENCRYPTION_PASSWORD", TEST_PASSWORD)]);
+    assert!(
+        !output.status.success(),
+        "the rotated-out password should no longer open the survey"
+    );
+
+    // The replaced survey keeps the permissions of the original
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path)
+            .expect("failed to stat")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}
+
 #[cfg(feature = "encryption")]
 #[test]
 fn test_validate_tampered_encrypted_schema_fails() {
@@ -315,3 +927,788 @@ fn test_validate_combined_compressed_encrypted_schema_file() {
         "validate output should show database name"
     );
 }
+
+/// Returns a server survey wrapping two copies of the minimal schema.
+fn server_survey() -> String {
+    let database = |name: &str| minimal_valid_schema().replace("test_db", name);
+    format!(
+        r#"{{
+            "format_version": "1.0",
+            "server_info": {{
+                "server_type": "PostgreSQL",
+                "version": "16.2",
+                "host": "db.internal",
+                "port": 5432,
+                "total_databases": 2,
+                "collected_databases": 2,
+                "system_databases_excluded": 1,
+                "connection_user": "surveyor",
+                "has_superuser_privileges": false,
+                "collection_mode": {{"MultiDatabase": {{"discovered": 2, "collected": 2, "failed": 0}}}}
+            }},
+            "manifest": [
+                {{"name": "sales", "collection_status": "Success", "table_count": 0, "view_count": 0, "object_count": 0, "sampled_tables": 0}},
+                {{"name": "hr", "collection_status": "Success", "table_count": 0, "view_count": 0, "object_count": 0, "sampled_tables": 0}}
+            ],
+            "databases": [{}, {}],
+            "collection_metadata": {{
+                "collected_at": "2024-01-15T10:30:00Z",
+                "collection_duration_ms": 3000,
+                "collector_version": "1.0.0",
+                "warnings": []
+            }}
+        }}"#,
+        database("sales"),
+        database("hr")
+    )
+}
+
+#[test]
+fn test_anonymize_server_survey() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("server.dbsurveyor.json");
+    std::fs::write(&survey_path, server_survey()).expect("failed to write survey");
+    let anonymize = |output: &std::path::Path, key: Option<&str>| {
+        let mut command = Command::new(bin_path());
+        command
+            .args([
+                "anonymize",
+                survey_path.to_str().expect("non-UTF8 path"),
+                "--output",
+                output.to_str().expect("non-UTF8 path"),
+            ])
+            .env_remove("DBSURVEYOR_ANONYMIZE_KEY");
+        if let Some(key) = key {
+            command.env("DBSURVEYOR_ANONYMIZE_KEY", key);
+        }
+        command
+            .output()
+            .expect("failed to execute dbsurveyor anonymize")
+    };
+
+    let missing_key = anonymize(&out_dir.path().join("none.json"), None);
+    assert!(!missing_key.status.success());
+    assert!(String::from_utf8_lossy(&missing_key.stderr).contains("DBSURVEYOR_ANONYMIZE_KEY"));
+
+    let first = out_dir.path().join("first.json");
+    let second = out_dir.path().join("second.json");
+    assert!(anonymize(&first, Some("engagement-key")).status.success());
+    assert!(anonymize(&second, Some("engagement-key")).status.success());
+    let anonymized = std::fs::read_to_string(&first).expect("failed to read anonymized survey");
+    assert_eq!(
+        anonymized,
+        std::fs::read_to_string(&second).expect("failed to read anonymized survey"),
+        "pseudonyms are consistent for the same key"
+    );
+    for original in ["db.internal", "\"surveyor\"", "\"sales\"", "\"hr\""] {
+        assert!(!anonymized.contains(original), "{original} leaked");
+    }
+    assert!(anonymized.contains("\"host\": \"server_"));
+
+    // The anonymized copy never replaces the survey it was made from
+    let original = std::fs::read_to_string(&survey_path).expect("failed to read survey");
+    let output = anonymize(&survey_path, Some("engagement-key"));
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Refusing to overwrite"));
+    assert_eq!(
+        std::fs::read_to_string(&survey_path).expect("failed to read survey"),
+        original
+    );
+
+    // The anonymized survey is still a valid survey
+    let output = Command::new(bin_path())
+        .args(["validate", first.to_str().expect("non-UTF8 path")])
+        .output()
+        .expect("failed to execute dbsurveyor validate");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_validate_server_survey() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("server.json");
+    std::fs::write(&survey_path, server_survey()).expect("failed to write survey");
+
+    let output = Command::new(bin_path())
+        .args(["validate", survey_path.to_str().expect("non-UTF8 path")])
+        .output()
+        .expect("failed to execute dbsurveyor validate");
+
+    assert!(
+        output.status.success(),
+        "validate should accept a server survey: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Server: PostgreSQL 16.2 at db.internal"));
+    assert!(stdout.contains("Databases: 2"));
+    assert!(stdout.contains("  - sales:"));
+    assert!(stdout.contains("  - hr:"));
+}
+
+#[test]
+fn test_generate_markdown_per_database_for_server_survey() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("server.json");
+    std::fs::write(&survey_path, server_survey()).expect("failed to write survey");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            survey_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for database in ["sales", "hr"] {
+        let path = out_dir.path().join(format!("report_{}.md", database));
+        let report = std::fs::read_to_string(&path).expect("failed to read report");
+        assert!(report.contains(database));
+    }
+    assert!(!report_path.exists());
+}
+
+#[test]
+fn test_generate_expands_deduplicated_tenants() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("server.json");
+
+    // Fold "hr" into a tenant group with "sales" as its canonical schema
+    let mut survey: serde_json::Value =
+        serde_json::from_str(&server_survey()).expect("server survey parses");
+    let hr = survey["databases"]
+        .as_array_mut()
+        .and_then(|databases| databases.pop())
+        .expect("hr database present");
+    survey["tenant_groups"] = serde_json::json!([{
+        "canonical": "sales",
+        "fingerprint": "00",
+        "tenants": [{
+            "database_info": hr["database_info"],
+            "collection_metadata": hr["collection_metadata"]
+        }]
+    }]);
+    let survey = survey.to_string();
+    std::fs::write(&survey_path, survey).expect("failed to write survey");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            survey_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(out_dir.path().join("report_sales.md").is_file());
+    let report = std::fs::read_to_string(out_dir.path().join("report_hr.md"))
+        .expect("failed to read report");
+    assert!(report.contains("hr"));
+}
+
+/// Compacts a survey document onto a single JSON Lines line.
+fn bundle_line(survey: &str) -> String {
+    let value: serde_json::Value = serde_json::from_str(survey).expect("survey parses");
+    value.to_string()
+}
+
+#[test]
+fn test_generate_markdown_from_survey_bundle() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let bundle_path = out_dir.path().join("fleet.jsonl");
+    let database = minimal_valid_schema().replace("test_db", "sales");
+    let bundle = format!(
+        "{}\n\n{}\n",
+        bundle_line(&database),
+        bundle_line(&server_survey())
+    );
+    std::fs::write(&bundle_path, bundle).expect("failed to write bundle");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            bundle_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // The second "sales" comes from the server survey on the next line
+    for (file, database) in [
+        ("report_sales.md", "sales"),
+        ("report_sales_2.md", "sales"),
+        ("report_hr.md", "hr"),
+    ] {
+        let report =
+            std::fs::read_to_string(out_dir.path().join(file)).expect("failed to read report");
+        assert!(report.contains(database));
+    }
+    assert!(!report_path.exists());
+}
+
+#[test]
+fn test_generate_survey_bundle_in_parallel_keeps_bundle_order() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let bundle_path = out_dir.path().join("fleet.jsonl");
+    let bundle = ["alpha", "bravo", "charlie", "delta", "echo"]
+        .iter()
+        .map(|name| bundle_line(&minimal_valid_schema().replace("test_db", name)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&bundle_path, bundle).expect("failed to write bundle");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            bundle_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+            "--jobs",
+            "3",
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let generated: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("Documentation generated: "))
+        .map(String::from)
+        .collect();
+    let expected: Vec<String> = ["alpha", "bravo", "charlie", "delta", "echo"]
+        .iter()
+        .map(|name| {
+            out_dir
+                .path()
+                .join(format!("report_{}.md", name))
+                .display()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(generated, expected);
+}
+
+#[test]
+fn test_generate_survey_bundle_with_output_template() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let bundle_path = out_dir.path().join("fleet.jsonl");
+    let bundle = format!(
+        "{}\n{}\n",
+        bundle_line(minimal_valid_schema()),
+        bundle_line(&server_survey())
+    );
+    std::fs::write(&bundle_path, bundle).expect("failed to write bundle");
+    let template = out_dir.path().join("docs/{server}/{database}.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            bundle_path.to_str().expect("non-UTF8 path"),
+            "--output-template",
+            template.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for (file, database) in [
+        ("docs/standalone/test_db.md", "test_db"),
+        ("docs/db_internal/sales.md", "sales"),
+        ("docs/db_internal/hr.md", "hr"),
+    ] {
+        let report =
+            std::fs::read_to_string(out_dir.path().join(file)).expect("failed to read report");
+        assert!(report.contains(database));
+    }
+}
+
+#[test]
+fn test_generate_rejects_output_template_with_traversal() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = out_dir.path().join("server.json");
+    std::fs::write(&survey_path, server_survey()).expect("failed to write survey");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            survey_path.to_str().expect("non-UTF8 path"),
+            "--output-template",
+            "docs/../../{database}.md",
+        ])
+        .current_dir(out_dir.path())
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid output template"));
+}
+
+#[test]
+fn test_generate_directory_isolates_corrupt_surveys() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let surveys = dir.path().join("surveys");
+    std::fs::create_dir_all(surveys.join("prod")).expect("failed to create dirs");
+    std::fs::write(surveys.join("a.dbsurveyor.json"), minimal_valid_schema())
+        .expect("failed to write survey");
+    std::fs::write(surveys.join("prod/b.dbsurveyor.json"), server_survey())
+        .expect("failed to write survey");
+    std::fs::write(surveys.join("corrupt.dbsurveyor.json"), "{ not json")
+        .expect("failed to write survey");
+    std::fs::write(surveys.join("notes.txt"), "not a survey").expect("failed to write notes");
+    let docs = dir.path().join("docs");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            surveys.to_str().expect("non-UTF8 path"),
+            "--output",
+            docs.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    // The corrupt survey fails the command but not the rest of the batch
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 3 survey files"));
+    assert!(docs.join("a.dbsurveyor.md").is_file());
+    assert!(docs.join("prod/b.dbsurveyor_sales.md").is_file());
+    assert!(docs.join("prod/b.dbsurveyor_hr.md").is_file());
+
+    let index = std::fs::read_to_string(docs.join("index.md")).expect("failed to read index");
+    assert!(index.contains("Documented 2 of 3 survey files"));
+    assert!(index.contains("| `a.dbsurveyor.json` | OK | [a.dbsurveyor.md](a.dbsurveyor.md) |"));
+    assert!(index.contains("[prod/b.dbsurveyor_hr.md](prod/b.dbsurveyor_hr.md)"));
+    assert!(index.contains("| `corrupt.dbsurveyor.json` | Failed:"));
+    assert!(!index.contains("notes.txt"));
+}
+
+#[test]
+fn test_generate_directory_reports_storage_growth() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let surveys = dir.path().join("surveys");
+    std::fs::create_dir_all(surveys.join("prod")).expect("failed to create dirs");
+    for (day, rows, bytes) in [("01", 1000, 1_048_576), ("11", 2000, 2_097_152)] {
+        let survey = minimal_valid_schema()
+            .replace(
+                "2024-01-15T10:30:00Z",
+                &format!("2024-01-{}T00:00:00Z", day),
+            )
+            .replace(
+                r#""tables": []"#,
+                &format!(
+                    r#""tables": [{{
+                        "name": "orders",
+                        "schema": "public",
+                        "columns": [{{
+                            "name": "id",
+                            "data_type": {{ "Integer": {{ "bits": 32, "signed": true }} }},
+                            "is_nullable": false,
+                            "is_primary_key": true,
+                            "is_auto_increment": false,
+                            "default_value": null,
+                            "comment": null,
+                            "ordinal_position": 1
+                        }}],
+                        "primary_key": null,
+                        "foreign_keys": [],
+                        "indexes": [],
+                        "constraints": [],
+                        "comment": null,
+                        "row_count": {},
+                        "size_bytes": {}
+                    }}]"#,
+                    rows, bytes
+                ),
+            );
+        std::fs::write(
+            surveys.join(format!("prod/test_db_{}.dbsurveyor.json", day)),
+            survey,
+        )
+        .expect("failed to write survey");
+    }
+    let docs = dir.path().join("docs");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            surveys.to_str().expect("non-UTF8 path"),
+            "--output",
+            docs.to_str().expect("non-UTF8 path"),
+            "--growth-horizon-days",
+            "10",
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let index = std::fs::read_to_string(docs.join("index.md")).expect("failed to read index");
+    assert!(index.contains("## Storage Growth"), "{}", index);
+    assert!(index.contains("| prod/test_db | 2.0 MiB | 102.4 KiB | 3.0 MiB |"));
+
+    let csv = std::fs::read_to_string(docs.join("growth.csv")).expect("failed to read growth");
+    assert_eq!(
+        csv,
+        "series,database,table,date,kind,row_count,size_bytes\n\
+        prod,test_db,public.orders,2024-01-01T00:00:00Z,observed,1000,1048576\n\
+        prod,test_db,public.orders,2024-01-11T00:00:00Z,observed,2000,2097152\n\
+        prod,test_db,public.orders,2024-01-21T00:00:00Z,projected,3000,3145728\n"
+    );
+}
+
+#[test]
+fn test_generate_writes_result_file() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let surveys = dir.path().join("surveys");
+    std::fs::create_dir_all(&surveys).expect("failed to create dirs");
+    std::fs::write(surveys.join("a.dbsurveyor.json"), minimal_valid_schema())
+        .expect("failed to write survey");
+    std::fs::write(surveys.join("corrupt.dbsurveyor.json"), "{ not json")
+        .expect("failed to write survey");
+    let docs = dir.path().join("docs");
+    let result_file = dir.path().join("result.json");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            surveys.to_str().expect("non-UTF8 path"),
+            "--output",
+            docs.to_str().expect("non-UTF8 path"),
+            "--sections",
+            "tables,indexes",
+            "--result-file",
+            result_file.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    // The summary is written even though the command fails
+    assert!(!output.status.success());
+    let result: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&result_file).expect("failed to read result file"),
+    )
+    .expect("result file is JSON");
+    assert_eq!(result["status"], "partial");
+    assert_eq!(result["databases_documented"], 1);
+    assert_eq!(result["skipped"][0]["name"], "corrupt.dbsurveyor.json");
+    assert!(
+        result["skipped_sections"]
+            .as_array()
+            .expect("skipped sections")
+            .contains(&serde_json::json!("samples"))
+    );
+
+    let outputs = result["outputs"].as_array().expect("outputs");
+    assert_eq!(outputs.len(), 2);
+    let report = &outputs[0];
+    assert!(
+        report["path"]
+            .as_str()
+            .expect("output path")
+            .ends_with("a.dbsurveyor.md")
+    );
+    let content = std::fs::read(docs.join("a.dbsurveyor.md")).expect("failed to read report");
+    assert_eq!(report["bytes"], content.len());
+    assert_eq!(report["sha256"].as_str().expect("checksum").len(), 64);
+    assert!(
+        outputs[1]["path"]
+            .as_str()
+            .expect("output path")
+            .ends_with("index.md")
+    );
+}
+
+#[test]
+fn test_generate_from_stdin() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let report_path = out_dir.path().join("report.md");
+
+    let mut child = Command::new(bin_path())
+        .args([
+            "generate",
+            "-",
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to spawn dbsurveyor generate");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(minimal_valid_schema().as_bytes())
+        .expect("failed to write survey to stdin");
+    let output = child
+        .wait_with_output()
+        .expect("failed to wait for dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = std::fs::read_to_string(&report_path).expect("failed to read report");
+    assert!(report.contains("test_db"));
+}
+
+#[test]
+fn test_generate_enforces_allowed_output_paths() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let survey_path = dir.path().join("schema.json");
+    std::fs::write(&survey_path, minimal_valid_schema()).expect("failed to write survey");
+    let docs = dir.path().join("docs");
+    std::fs::create_dir_all(&docs).expect("failed to create docs dir");
+    let run = |output: &std::path::Path, extra: &[&str]| {
+        Command::new(bin_path())
+            .args([
+                "generate",
+                survey_path.to_str().expect("non-UTF8 path"),
+                "--output",
+                output.to_str().expect("non-UTF8 path"),
+            ])
+            .args(extra)
+            .output()
+            .expect("failed to execute dbsurveyor generate")
+    };
+    let allow = ["--allow-path", docs.to_str().expect("non-UTF8 path")];
+
+    let output = run(&docs.join("report.md"), &allow);
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let outside = dir.path().join("report.md");
+    let output = run(&outside, &allow);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("outside the allowed directories"));
+    assert!(!outside.exists());
+
+    // Traversal is rejected by default and allowed only when unrestricted
+    let traversal = docs.join("../report.md");
+    assert!(!run(&traversal, &[]).status.success());
+    let output = run(&traversal, &["--unrestricted-paths"]);
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(outside.is_file());
+}
+
+#[test]
+fn test_default_output_paths_enforce_allowed_paths() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let data = dir.path().join("data");
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(&data).expect("failed to create data dir");
+    std::fs::create_dir_all(&out).expect("failed to create out dir");
+    let survey_path = data.join("schema.json");
+    std::fs::write(&survey_path, minimal_valid_schema()).expect("failed to write survey");
+    let run = |args: &[&str]| {
+        Command::new(bin_path())
+            .args(["--allow-path", out.to_str().expect("non-UTF8 path")])
+            .args(args)
+            .arg(survey_path.to_str().expect("non-UTF8 path"))
+            .env("DBSURVEYOR_ANONYMIZE_KEY", "engagement-key")
+            .current_dir(&data)
+            .output()
+            .expect("failed to execute dbsurveyor")
+    };
+
+    // Without --output, sql writes to the working directory, outside /out
+    #[cfg(feature = "experimental")]
+    {
+        let output = run(&["sql"]);
+        assert!(!output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("outside the allowed directories")
+        );
+        assert!(!data.join("schema.sql").exists());
+    }
+
+    // Without --output, anonymize writes beside the input, outside /out
+    let output = run(&["anonymize"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("outside the allowed directories"));
+    assert!(!data.join("schema.anonymized.json").exists());
+
+    let anonymized = out.join("schema.anonymized.json");
+    let output = run(&[
+        "anonymize",
+        "--output",
+        anonymized.to_str().expect("non-UTF8 path"),
+    ]);
+    assert!(
+        output.status.success(),
+        "anonymize should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(anonymized.is_file());
+}
+
+#[test]
+fn test_journal_reconstructs_survey() {
+    let dir = tempfile::tempdir().expect("failed to create temp dir");
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(&out).expect("failed to create out dir");
+    let journal_path = dir.path().join("prod.journal.jsonl");
+    let survey: serde_json::Value =
+        serde_json::from_str(minimal_valid_schema()).expect("valid survey JSON");
+    dbsurveyor_core::journal::append(
+        &journal_path,
+        &survey,
+        None,
+        &dbsurveyor_core::InputLimits::default(),
+    )
+    .expect("failed to append to journal");
+    let reconstruct = |extra: &[&str]| {
+        Command::new(bin_path())
+            .arg("journal")
+            .arg(&journal_path)
+            .args(["--at", "1"])
+            .args(extra)
+            .env_remove("DBSURVEYOR_SIGNING_KEY")
+            .output()
+            .expect("failed to execute dbsurveyor journal")
+    };
+
+    // The default path beside the journal is outside --allow-path
+    let output = reconstruct(&["--allow-path", out.to_str().expect("non-UTF8 path")]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("outside the allowed directories"));
+    let default_path = dir.path().join("prod-1.dbsurveyor.json");
+    assert!(!default_path.exists());
+
+    let output = reconstruct(&[]);
+    assert!(
+        output.status.success(),
+        "journal --at should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let reconstructed: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(&default_path).expect("failed to read reconstructed survey"),
+    )
+    .expect("reconstructed survey is JSON");
+    assert_eq!(reconstructed, survey);
+}
+
+#[test]
+fn test_generate_survey_bundle_reports_invalid_line() {
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let bundle_path = out_dir.path().join("fleet.jsonl");
+    let bundle = format!(
+        "{}\n{{\"format_version\": \"1.0\"}}\n",
+        bundle_line(minimal_valid_schema())
+    );
+    std::fs::write(&bundle_path, bundle).expect("failed to write bundle");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            bundle_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Bundle line 2"));
+    // Databases before the invalid line are still documented
+    assert!(out_dir.path().join("report_test_db.md").is_file());
+}
+
+#[tokio::test]
+async fn test_generate_from_mock_adapter_survey() {
+    use dbsurveyor_core::{DatabaseAdapter, SamplingConfig, testing::MockAdapter};
+
+    let adapter = MockAdapter::demo();
+    let schema = adapter
+        .collect_schema()
+        .await
+        .expect("mock schema collection");
+    let samples = adapter
+        .sample_all(&SamplingConfig::default())
+        .await
+        .expect("mock sampling");
+    let survey = schema.with_samples(samples);
+
+    let out_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let schema_path = out_dir.path().join("shop.dbsurveyor.json");
+    std::fs::write(
+        &schema_path,
+        serde_json::to_vec(&survey).expect("failed to serialize survey"),
+    )
+    .expect("failed to write survey");
+    let report_path = out_dir.path().join("report.md");
+
+    let output = Command::new(bin_path())
+        .args([
+            "generate",
+            schema_path.to_str().expect("non-UTF8 path"),
+            "--output",
+            report_path.to_str().expect("non-UTF8 path"),
+        ])
+        .output()
+        .expect("failed to execute dbsurveyor generate");
+
+    assert!(
+        output.status.success(),
+        "generate should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = std::fs::read_to_string(&report_path).expect("failed to read report");
+    assert!(report.contains("### public.customers"));
+    assert!(report.contains("| public.orders | customer_id | public.customers (id) |"));
+    // Sampled email addresses are redacted by default
+    assert!(
+        report
+            .lines()
+            .any(|line| line.contains("| Ada |") && line.contains("| [REDACTED] |"))
+    );
+    assert!(!report.contains("ada@example.com"));
+}